@@ -6,12 +6,13 @@
 //! Widget data types
 
 #[allow(unused)]
-use super::Layout;
-use super::{Widget, WidgetId};
+use super::{Layout, SaveState};
+use super::{Widget, WidgetId, WidgetState};
 use crate::event::{self, EventMgr};
 use crate::geom::Rect;
 use crate::layout::{SetRectMgr, StorageChain};
-use crate::{dir::Direction, WindowId};
+use crate::theme::DrawMgr;
+use crate::{dir::Direction, TkAction, WindowId};
 
 #[cfg(feature = "winit")]
 pub use winit::window::Icon;
@@ -45,6 +46,7 @@ pub struct CoreData {
     pub rect: Rect,
     pub id: WidgetId,
     pub disabled: bool,
+    pub hit_transparent: bool,
 }
 
 /// Note: the clone has default-initialised layout storage and identifier.
@@ -56,6 +58,7 @@ impl Clone for CoreData {
             rect: self.rect,
             id: WidgetId::default(),
             disabled: self.disabled,
+            hit_transparent: self.hit_transparent,
         }
     }
 }
@@ -87,6 +90,53 @@ pub struct Popup {
     pub direction: Direction,
 }
 
+/// Window decoration style
+///
+/// See [`Window::decorations`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Decorations {
+    /// No decorations
+    None,
+    /// Decorations provided by the window manager or OS
+    Server,
+    /// Decorations drawn by KAS
+    ///
+    /// Server-side decorations are disabled; the window is expected to draw
+    /// its own title bar and handle moving and resizing via [`EventMgr`]
+    /// methods such as [`EventMgr::drag_window`].
+    Kas,
+}
+
+impl Default for Decorations {
+    fn default() -> Self {
+        Decorations::Server
+    }
+}
+
+bitflags! {
+    /// Window level and behaviour flags
+    ///
+    /// See [`Window::flags`] and [`EventMgr::set_window_flags`]. Support is
+    /// platform-dependent; unsupported flags are ignored.
+    #[derive(Default)]
+    pub struct WindowFlags: u32 {
+        /// Keep the window above other (normal) windows
+        ///
+        /// This is suitable for tool palettes and notification pop-ups.
+        const ALWAYS_ON_TOP = 1 << 0;
+        /// Do not show the window in the taskbar
+        ///
+        /// This can only be set when the window is created (currently only
+        /// supported on X11).
+        const SKIP_TASKBAR = 1 << 1;
+        /// Do not give the window input focus when shown
+        ///
+        /// This can only be set when the window is created (currently only
+        /// supported on X11, by hinting that the window is a notification).
+        const NO_FOCUS = 1 << 2;
+    }
+}
+
 /// Functionality required by a window
 pub trait Window: Widget<Msg = event::VoidMsg> {
     /// Get the window title
@@ -107,6 +157,59 @@ pub trait Window: Widget<Msg = event::VoidMsg> {
     /// windows.
     fn restrict_dimensions(&self) -> (bool, bool);
 
+    /// Window decorations
+    ///
+    /// This is queried once when the window is created.
+    /// By default, [`Decorations::Server`] is used.
+    fn decorations(&self) -> Decorations {
+        Decorations::Server
+    }
+
+    /// Window level and behaviour flags
+    ///
+    /// This is queried once when the window is created. Some flags may be
+    /// adjusted later via [`EventMgr::set_window_flags`].
+    /// By default, no flags are set.
+    fn flags(&self) -> WindowFlags {
+        WindowFlags::empty()
+    }
+
+    /// Monitor on which to centre the window
+    ///
+    /// This is queried once when the window is created. If `Some(index)`, the
+    /// window is centred on monitor `index` (see
+    /// [`crate::shell::Displays`]) where supported; otherwise placement is
+    /// left to the platform. By default, `None` is returned.
+    fn monitor(&self) -> Option<usize> {
+        None
+    }
+
+    /// Whether the window is modal
+    ///
+    /// This is queried once when the window is created. A modal window blocks
+    /// input to the window from which it was opened (its parent) until closed.
+    /// By default, `false` is returned.
+    fn modal(&self) -> bool {
+        false
+    }
+
+    /// Capture the interactive state of all widgets in the window
+    ///
+    /// See [`SaveState`]. The result may be stored (e.g. on exit) and later
+    /// passed to [`Window::restore_widget_state`] to restore a session.
+    fn capture_widget_state(&self) -> WidgetState {
+        WidgetState::capture(self.as_widget())
+    }
+
+    /// Restore state previously captured by [`Window::capture_widget_state`]
+    ///
+    /// The window must be configured and should be sized (since e.g. scroll
+    /// offsets are clamped to the current content size). Returns any action
+    /// required.
+    fn restore_widget_state(&mut self, state: &WidgetState) -> TkAction {
+        state.restore(self.as_widget_mut())
+    }
+
     /// Add a pop-up as a layer in the current window
     ///
     /// Each [`Popup`] is assigned a [`WindowId`]; both are passed.
@@ -118,6 +221,34 @@ pub trait Window: Widget<Msg = event::VoidMsg> {
     /// existing pop-ups.
     fn resize_popups(&mut self, mgr: &mut SetRectMgr);
 
+    /// Place a pop-up in a native window
+    ///
+    /// Shells supporting pop-ups as native (borderless) windows call this
+    /// after [`Self::add_popup`], passing as `bounds` the area available to
+    /// the pop-up (usually the current monitor) relative to this window.
+    /// If the pop-up does not fit within this window it may be placed within
+    /// `bounds` instead; in this case its new [`Rect`] (relative to this
+    /// window) is returned and the pop-up must not be drawn as an overlay
+    /// (the shell draws it using [`Self::draw_popup`]).
+    ///
+    /// If `bounds` is `None`, the pop-up reverts to an overlay.
+    ///
+    /// The default implementation always uses an overlay.
+    fn set_popup_bounds(
+        &mut self,
+        _mgr: &mut SetRectMgr,
+        _id: WindowId,
+        _bounds: Option<Rect>,
+    ) -> Option<Rect> {
+        None
+    }
+
+    /// Draw a pop-up placed in a native window
+    ///
+    /// The pop-up is drawn translated such that the top-left corner of the
+    /// [`Rect`] returned by [`Self::set_popup_bounds`] is at the origin.
+    fn draw_popup(&mut self, _draw: DrawMgr, _id: WindowId) {}
+
     /// Trigger closure of a pop-up
     ///
     /// If the given `id` refers to a pop-up, it should be closed.
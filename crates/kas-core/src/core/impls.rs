@@ -10,7 +10,7 @@ use crate::event::{self, Event, EventMgr, Response};
 use crate::geom::{Coord, Rect};
 use crate::layout::{AlignHints, AxisInfo, SetRectMgr, SizeRules};
 use crate::theme::{DrawMgr, SizeMgr};
-use crate::{CoreData, WidgetId};
+use crate::{CoreData, StateValue, TkAction, WidgetId};
 use std::any::Any;
 
 impl<M: 'static> WidgetCore for Box<dyn Widget<Msg = M>> {
@@ -73,9 +73,30 @@ impl<M: 'static> WidgetConfig for Box<dyn Widget<Msg = M>> {
     fn hover_highlight(&self) -> bool {
         self.as_ref().hover_highlight()
     }
+    fn hover_events(&self) -> bool {
+        self.as_ref().hover_events()
+    }
     fn cursor_icon(&self) -> event::CursorIcon {
         self.as_ref().cursor_icon()
     }
+    fn window_region(&self) -> event::WindowRegion {
+        self.as_ref().window_region()
+    }
+    fn value_description(&self) -> Option<String> {
+        self.as_ref().value_description()
+    }
+}
+
+impl<M: 'static> SaveState for Box<dyn Widget<Msg = M>> {
+    fn save_state(&self) -> Option<StateValue> {
+        self.as_ref().save_state()
+    }
+    fn restore_state(&mut self, state: &StateValue) -> TkAction {
+        self.as_mut().restore_state(state)
+    }
+    fn saves_children(&self) -> bool {
+        self.as_ref().saves_children()
+    }
 }
 
 impl<M: 'static> Layout for Box<dyn Widget<Msg = M>> {
@@ -7,10 +7,12 @@
 
 mod data;
 mod impls;
+mod state;
 mod widget;
 mod widget_id;
 
 pub use data::*;
+pub use state::{StateValue, WidgetState};
 pub use widget::*;
 pub use widget_id::WidgetId;
 
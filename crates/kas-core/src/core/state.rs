@@ -0,0 +1,184 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Widget state save and restore
+
+use super::WidgetConfig;
+use crate::geom::Offset;
+use crate::TkAction;
+use std::collections::BTreeMap;
+
+/// A saved value of interactive widget state
+///
+/// See [`super::SaveState`].
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "config", derive(serde::Serialize, serde::Deserialize))]
+pub enum StateValue {
+    /// A boolean (e.g. check state)
+    Bool(bool),
+    /// An integer (e.g. selected index)
+    Int(i64),
+    /// A floating-point value (e.g. slider value)
+    Float(f64),
+    /// Text (e.g. edit field contents)
+    Text(String),
+    /// A list of values
+    List(Vec<StateValue>),
+}
+
+impl StateValue {
+    /// Get as `bool`, if this is a [`StateValue::Bool`]
+    pub fn as_bool(&self) -> Option<bool> {
+        match self {
+            StateValue::Bool(b) => Some(*b),
+            _ => None,
+        }
+    }
+
+    /// Get as `i64`, if this is a [`StateValue::Int`]
+    pub fn as_int(&self) -> Option<i64> {
+        match self {
+            StateValue::Int(x) => Some(*x),
+            _ => None,
+        }
+    }
+
+    /// Get as `f64`, if this is a [`StateValue::Float`] or [`StateValue::Int`]
+    pub fn as_float(&self) -> Option<f64> {
+        match self {
+            StateValue::Float(x) => Some(*x),
+            StateValue::Int(x) => Some(*x as f64),
+            _ => None,
+        }
+    }
+
+    /// Get as `&str`, if this is a [`StateValue::Text`]
+    pub fn as_text(&self) -> Option<&str> {
+        match self {
+            StateValue::Text(s) => Some(s),
+            _ => None,
+        }
+    }
+
+    /// Get as a slice, if this is a [`StateValue::List`]
+    pub fn as_list(&self) -> Option<&[StateValue]> {
+        match self {
+            StateValue::List(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Get as an [`Offset`], if this is a list of two integers
+    pub fn as_offset(&self) -> Option<Offset> {
+        match self.as_list()? {
+            [x, y] => {
+                let x = i32::try_from(x.as_int()?).ok()?;
+                let y = i32::try_from(y.as_int()?).ok()?;
+                Some(Offset(x, y))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl From<Offset> for StateValue {
+    fn from(offset: Offset) -> Self {
+        let v = vec![
+            StateValue::Int(offset.0.into()),
+            StateValue::Int(offset.1.into()),
+        ];
+        StateValue::List(v)
+    }
+}
+
+/// Saved state of a widget tree
+///
+/// Values are keyed by widget path (the [`Display`](std::fmt::Display)
+/// representation of the widget's [`super::WidgetId`]). Since identifiers are
+/// assigned from widget position during configuration, state may be restored
+/// to a tree of the same structure, e.g. after restarting an application.
+/// Entries for widgets which no longer exist are ignored.
+///
+/// With feature `config`, this type may be (de)serialised (e.g. via
+/// [`crate::config::Format`]).
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "config", derive(serde::Serialize, serde::Deserialize))]
+pub struct WidgetState(BTreeMap<String, StateValue>);
+
+impl WidgetState {
+    /// Construct an empty state
+    #[inline]
+    pub fn new() -> Self {
+        WidgetState::default()
+    }
+
+    /// Capture state from `widget` and all its descendants
+    pub fn capture(widget: &dyn WidgetConfig) -> Self {
+        let mut state = WidgetState::new();
+        state.capture_from(widget);
+        state
+    }
+
+    fn capture_from(&mut self, widget: &dyn WidgetConfig) {
+        if let Some(value) = widget.save_state() {
+            self.0.insert(widget.id_ref().to_string(), value);
+        }
+        if !widget.saves_children() {
+            return;
+        }
+        for index in 0..widget.num_children() {
+            if let Some(child) = widget.get_child(index) {
+                self.capture_from(child);
+            }
+        }
+    }
+
+    /// Restore state to `widget` and all its descendants
+    ///
+    /// The widget tree should be configured (so that identifiers are
+    /// assigned). Returns the union of actions required by widgets.
+    pub fn restore(&self, widget: &mut dyn WidgetConfig) -> TkAction {
+        let mut action = TkAction::empty();
+        if let Some(value) = self.0.get(&widget.id_ref().to_string()) {
+            action |= widget.restore_state(value);
+        }
+        if !widget.saves_children() {
+            return action;
+        }
+        for index in 0..widget.num_children() {
+            if let Some(child) = widget.get_child_mut(index) {
+                action |= self.restore(child);
+            }
+        }
+        action
+    }
+
+    /// True if no state is saved
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Number of saved entries
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Get the state saved for a widget path
+    pub fn get(&self, path: &str) -> Option<&StateValue> {
+        self.0.get(path)
+    }
+
+    /// Set the state for a widget path
+    pub fn insert(&mut self, path: String, value: StateValue) -> Option<StateValue> {
+        self.0.insert(path, value)
+    }
+
+    /// Iterate over `(path, value)` entries
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &StateValue)> {
+        self.0.iter().map(|(k, v)| (k.as_str(), v))
+    }
+}
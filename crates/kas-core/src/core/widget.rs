@@ -17,7 +17,9 @@ use crate::layout::{self, AlignHints, AxisInfo, SetRectMgr, SizeRules};
 use crate::theme::DrawCtx;
 use crate::theme::{DrawMgr, SizeMgr};
 use crate::util::IdentifyWidget;
-use crate::{CoreData, TkAction, WidgetId};
+#[allow(unused)]
+use crate::WidgetState;
+use crate::{CoreData, StateValue, TkAction, WidgetId};
 
 impl dyn WidgetCore {
     /// Forwards to the method defined on the type `Any`.
@@ -123,6 +125,40 @@ pub trait WidgetCore: Any + fmt::Debug {
         self
     }
 
+    /// Get whether the widget is transparent to hit-testing
+    #[inline]
+    fn is_hit_transparent(&self) -> bool {
+        self.core_data().hit_transparent
+    }
+
+    /// Set whether the widget is transparent to hit-testing
+    ///
+    /// A transparent widget is never itself the target of mouse/touch input:
+    /// the default implementation of [`Layout::find_id`] returns the id of a
+    /// child under the coordinate, if any, and otherwise `None`, thus input
+    /// passes through to the widgets beneath (e.g. siblings in an overlay, or
+    /// the parent). Custom implementations of `find_id` should respect this.
+    ///
+    /// This does not affect children of this widget.
+    #[inline]
+    fn set_hit_transparent(&mut self, transparent: bool) {
+        self.core_data_mut().hit_transparent = transparent;
+    }
+
+    /// Set hit-test transparency (chaining)
+    ///
+    /// This is identical to [`WidgetCore::set_hit_transparent`], but can be
+    /// called in chaining fashion.
+    #[inline]
+    #[must_use]
+    fn with_hit_transparent(mut self, transparent: bool) -> Self
+    where
+        Self: Sized,
+    {
+        self.core_data_mut().hit_transparent = transparent;
+        self
+    }
+
     /// Get the widget's region, relative to its parent.
     #[inline]
     fn rect(&self) -> Rect {
@@ -234,6 +270,45 @@ pub trait WidgetChildren: WidgetCore {
     }
 }
 
+/// Widget state save and restore
+///
+/// This trait is part of the [`Widget`] family and is derived by
+/// [`derive(Widget)`] (as a no-op, or forwarding to the inner widget with
+/// `#[widget{ derive = self.inner; }]`) unless implemented manually.
+///
+/// Widgets with interactive state which the user may expect to persist
+/// (e.g. scroll offsets, selections, edited text) should implement this.
+/// State is captured and restored over a whole widget tree using
+/// [`WidgetState`]; child widgets need not be considered here.
+///
+/// [`derive(Widget)`]: https://docs.rs/kas/latest/kas/macros/index.html#the-derivewidget-macro
+pub trait SaveState: WidgetChildren {
+    /// Save interactive state
+    ///
+    /// Returns `None` (the default) if there is no state to save.
+    fn save_state(&self) -> Option<StateValue> {
+        None
+    }
+
+    /// Restore interactive state
+    ///
+    /// The `state` is a value previously returned by [`SaveState::save_state`],
+    /// though possibly from a widget of a different version; invalid values
+    /// should be ignored. Returns any action required.
+    fn restore_state(&mut self, state: &StateValue) -> TkAction {
+        let _ = state;
+        TkAction::empty()
+    }
+
+    /// Whether the state of child widgets should be saved
+    ///
+    /// Returns `true` by default. Widgets re-using children for different
+    /// data (e.g. view widgets) should return `false`.
+    fn saves_children(&self) -> bool {
+        true
+    }
+}
+
 /// Widget configuration
 ///
 /// This trait is part of the [`Widget`] family and is derived by
@@ -249,7 +324,7 @@ pub trait WidgetChildren: WidgetCore {
 //
 // TODO(specialization): provide a blanket implementation, so that users only
 // need implement manually when they have something to configure.
-pub trait WidgetConfig: Layout {
+pub trait WidgetConfig: Layout + SaveState {
     /// Pre-configure widget
     ///
     /// Widgets are *configured* on window creation (before sizing) and when
@@ -308,6 +383,17 @@ pub trait WidgetConfig: Layout {
         false
     }
 
+    /// Does this widget receive mouse enter/leave events?
+    ///
+    /// If true, [`event::Event::MouseEnter`] and [`event::Event::MouseLeave`]
+    /// will be sent whenever this widget gains or loses mouse-hover status.
+    ///
+    /// Defaults to `false`.
+    #[inline]
+    fn hover_events(&self) -> bool {
+        false
+    }
+
     /// Which cursor icon should be used on hover?
     ///
     /// The "hovered" widget is determined by [`Layout::find_id`], thus is the
@@ -319,6 +405,32 @@ pub trait WidgetConfig: Layout {
     fn cursor_icon(&self) -> event::CursorIcon {
         event::CursorIcon::Default
     }
+
+    /// Which window region does this widget represent?
+    ///
+    /// This allows a widget to act as a window-drag region (e.g. a custom
+    /// title bar) or a resize border. Before a primary mouse button press is
+    /// dispatched, the shell tests the hovered widget (see
+    /// [`WidgetConfig::cursor_icon`]); presses on drag and resize regions are
+    /// consumed by the shell and not sent to the widget.
+    ///
+    /// Defaults to [`event::WindowRegion::Client`].
+    #[inline]
+    fn window_region(&self) -> event::WindowRegion {
+        event::WindowRegion::Client
+    }
+
+    /// Describe the widget's value
+    ///
+    /// Widgets representing a value (e.g. a slider) may return a short,
+    /// human-readable description such as "Volume 75%". This is intended for
+    /// use by accessibility tools; see also [`EventState::announce_value`].
+    ///
+    /// Defaults to `None`.
+    #[inline]
+    fn value_description(&self) -> Option<String> {
+        None
+    }
 }
 
 /// Positioning and drawing routines for widgets
@@ -469,13 +581,17 @@ pub trait Layout: WidgetChildren {
     /// -   Return `None` if `coord` is not within `self.rect()`
     /// -   Find the child which should respond to input at `coord`, if any, and
     ///     call `find_id` recursively on this child
-    /// -   Otherwise return `self.id()`
+    /// -   Otherwise return `self.id()`, unless the widget is
+    ///     [transparent to hit-testing](WidgetCore::is_hit_transparent), in
+    ///     which case return `None`
     fn find_id(&mut self, coord: Coord) -> Option<WidgetId> {
         if !self.rect().contains(coord) {
             return None;
         }
         let coord = coord + self.translation();
-        self.layout().find_id(coord).or_else(|| Some(self.id()))
+        self.layout()
+            .find_id(coord)
+            .or_else(|| (!self.is_hit_transparent()).then(|| self.id()))
     }
 
     /// Draw a widget and its children
@@ -502,6 +618,7 @@ pub trait Layout: WidgetChildren {
 /// -   [`WidgetChildren`] — enumerates children and provides methods derived
 ///     from this
 /// -   [`Layout`] — handles sizing and positioning of self and children
+/// -   [`SaveState`] — saves and restores interactive state
 /// -   [`WidgetConfig`] — the last unparametrised trait allows customisation of
 ///     some aspects of widget behaviour
 /// -   [`event::Handler`] — parametrised widgets over a `Msg` type and handles
@@ -268,7 +268,7 @@ impl Rgba8Srgb {
 
 impl From<Rgba8Srgb> for [u8; 4] {
     fn from(c: Rgba8Srgb) -> Self {
-        [c.0[0], c.0[1], c.0[2], c.0[2]]
+        [c.0[0], c.0[1], c.0[2], c.0[3]]
     }
 }
 
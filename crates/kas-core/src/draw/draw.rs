@@ -6,9 +6,11 @@
 //! Drawing APIs — draw interface
 
 use super::{color::Rgba, AnimationState};
+use super::{
+    Affine, DrawSharedImpl, Gradient, ImageId, PassId, PassType, SharedState, StrokeStyle,
+};
 #[allow(unused)]
 use super::{DrawRounded, DrawRoundedImpl};
-use super::{DrawSharedImpl, ImageId, PassId, PassType, SharedState};
 use crate::geom::{Offset, Quad, Rect, Vec2};
 #[allow(unused)]
 use crate::text::TextApi;
@@ -103,8 +105,27 @@ impl<'a, DS: DrawSharedImpl> DrawIface<'a, DS> {
     /// Case `class == PassType::Overlay`: the new pass is derived from the
     /// base pass (i.e. the window). Draw operations still happen after those in
     /// `parent_pass`.
-    pub fn new_pass(&mut self, rect: Rect, offset: Offset, class: PassType) -> DrawIface<DS> {
-        let pass = self.draw.new_pass(self.pass, rect, offset, class);
+    ///
+    /// Case `class == PassType::Elevated`: as for `Clip`, except that `rect` is
+    /// intersected with the window's rect and the pass is drawn after all
+    /// passes of lower elevation.
+    ///
+    /// Draw operations at position `p` within the new pass are drawn at
+    /// `transform.transform_point(p - offset)` in the parent's coordinate
+    /// system (for overlays, the window's). Clipping to `rect` happens before
+    /// this transform, thus a rotated pass is clipped to the (axis-aligned)
+    /// bounding box of `rect` mapped through any parent transforms.
+    /// Use [`Affine::IDENTITY`] for a pass which is only offset.
+    pub fn new_pass(
+        &mut self,
+        rect: Rect,
+        offset: Offset,
+        transform: Affine,
+        class: PassType,
+    ) -> DrawIface<DS> {
+        let pass = self
+            .draw
+            .new_pass(self.pass, rect, offset, transform, class);
         DrawIface {
             draw: &mut *self.draw,
             shared: &mut *self.shared,
@@ -158,6 +179,10 @@ pub trait Draw {
     /// Case `class == PassType::Overlay`: the new pass is derived from the
     /// base pass (i.e. the window). Draw operations still happen after those in
     /// `parent_pass`.
+    ///
+    /// Case `class == PassType::Elevated`: as for `Clip`, except that `rect` is
+    /// intersected with the window's rect and the pass is drawn after all
+    /// passes of lower elevation.
     #[cfg(feature = "stack_dst")]
     #[cfg_attr(doc_cfg, doc(cfg(feature = "stack_dst")))]
     fn new_dyn_pass<'b>(
@@ -183,14 +208,46 @@ pub trait Draw {
     /// else will draw "in front of" a rect.
     fn rect(&mut self, rect: Quad, col: Rgba);
 
+    /// Draw many rectangles of the same size and uniform colour
+    ///
+    /// Draws a rect `pos..(pos + size)` for each `pos` in `positions`. This is
+    /// similar to calling [`Self::rect`] for each, but may be more efficient
+    /// (e.g. for the rows of a large list). Instanced rects may be drawn
+    /// beneath other square-edged primitives (see [draw order](super#draw-order)).
+    fn rect_instances(&mut self, size: Vec2, positions: &[Vec2], col: Rgba);
+
     /// Draw a frame of uniform colour
     ///
     /// The frame is defined by the area inside `outer` and not inside `inner`.
     fn frame(&mut self, outer: Quad, inner: Quad, col: Rgba);
 
+    /// Draw a frame of uniform colour with the given stroke `style`
+    ///
+    /// The frame is defined by the area inside `outer` and not inside `inner`.
+    /// The stroke pattern runs clockwise from the top-left corner.
+    fn frame_styled(&mut self, outer: Quad, inner: Quad, style: StrokeStyle, col: Rgba);
+
+    /// Draw a rectangle with a gradient fill
+    ///
+    /// Gradient fills are drawn beneath [`Self::rect`] and other square-edged
+    /// primitives (see [draw order](super#draw-order)).
+    fn rect_grad(&mut self, rect: Quad, grad: Gradient);
+
+    /// Draw a frame with a gradient fill
+    ///
+    /// The frame is defined by the area inside `outer` and not inside `inner`.
+    fn frame_grad(&mut self, outer: Quad, inner: Quad, grad: Gradient);
+
     /// Draw the image in the given `rect`
     fn image(&mut self, id: ImageId, rect: Quad);
 
+    /// Draw the image in the given `rect` as a background
+    ///
+    /// Unlike [`Self::image`], this is drawn along with gradient fills,
+    /// beneath [`Self::rect`] and other square-edged primitives (see
+    /// [draw order](super#draw-order)).
+    fn image_background(&mut self, id: ImageId, rect: Quad);
+
     /// Draw text with a colour
     ///
     /// It is required to call [`TextDisplay::prepare`] or [`TextApi::prepare`]
@@ -247,7 +304,7 @@ impl<'a, DS: DrawSharedImpl> Draw for DrawIface<'a, DS> {
         offset: Offset,
         class: PassType,
     ) -> stack_dst::ValueA<dyn Draw + 'b, [usize; 4]> {
-        let draw = self.new_pass(rect, offset, class);
+        let draw = self.new_pass(rect, offset, Affine::IDENTITY, class);
         stack_dst::ValueA::new_stable(draw, |d| d as &dyn Draw)
             .unwrap_or_else(|_| panic!("boxed window too big for StackDst!"))
     }
@@ -259,13 +316,30 @@ impl<'a, DS: DrawSharedImpl> Draw for DrawIface<'a, DS> {
     fn rect(&mut self, rect: Quad, col: Rgba) {
         self.draw.rect(self.pass, rect, col);
     }
+    fn rect_instances(&mut self, size: Vec2, positions: &[Vec2], col: Rgba) {
+        self.draw.rect_instances(self.pass, size, positions, col);
+    }
     fn frame(&mut self, outer: Quad, inner: Quad, col: Rgba) {
         self.draw.frame(self.pass, outer, inner, col);
     }
+    fn frame_styled(&mut self, outer: Quad, inner: Quad, style: StrokeStyle, col: Rgba) {
+        self.draw.frame_styled(self.pass, outer, inner, style, col);
+    }
+    fn rect_grad(&mut self, rect: Quad, grad: Gradient) {
+        self.draw.rect_grad(self.pass, rect, grad);
+    }
+    fn frame_grad(&mut self, outer: Quad, inner: Quad, grad: Gradient) {
+        self.draw.frame_grad(self.pass, outer, inner, grad);
+    }
 
     fn image(&mut self, id: ImageId, rect: Quad) {
         self.shared.draw.draw_image(self.draw, self.pass, id, rect);
     }
+    fn image_background(&mut self, id: ImageId, rect: Quad) {
+        self.shared
+            .draw
+            .draw_image_background(self.draw, self.pass, id, rect);
+    }
 
     fn text(&mut self, pos: Vec2, text: &TextDisplay, col: Rgba) {
         self.shared
@@ -345,11 +419,19 @@ pub trait DrawImpl: Any {
     /// Case `class == PassType::Overlay`: the new pass is derived from the
     /// base pass (i.e. the window). Draw operations still happen after those in
     /// `parent_pass`.
+    ///
+    /// Case `class == PassType::Elevated`: as for `Clip`, except that `rect` is
+    /// intersected with the window's rect and the pass is drawn after all
+    /// passes of lower elevation.
+    ///
+    /// Draw operations in the new pass are offset, then transformed by
+    /// `transform` (see [`DrawIface::new_pass`]).
     fn new_pass(
         &mut self,
         parent_pass: PassId,
         rect: Rect,
         offset: Offset,
+        transform: Affine,
         class: PassType,
     ) -> PassId;
 
@@ -365,6 +447,32 @@ pub trait DrawImpl: Any {
     /// Draw a rectangle of uniform colour
     fn rect(&mut self, pass: PassId, rect: Quad, col: Rgba);
 
+    /// Draw many rectangles of the same size and uniform colour
+    ///
+    /// The default implementation calls [`DrawImpl::rect`] for each rect;
+    /// shells should override this where instanced drawing is supported.
+    fn rect_instances(&mut self, pass: PassId, size: Vec2, positions: &[Vec2], col: Rgba) {
+        for pos in positions {
+            self.rect(pass, Quad::from_coords(*pos, *pos + size), col);
+        }
+    }
+
     /// Draw a frame of uniform colour
     fn frame(&mut self, pass: PassId, outer: Quad, inner: Quad, col: Rgba);
+
+    /// Draw a frame of uniform colour with the given stroke `style`
+    fn frame_styled(
+        &mut self,
+        pass: PassId,
+        outer: Quad,
+        inner: Quad,
+        style: StrokeStyle,
+        col: Rgba,
+    );
+
+    /// Draw a rectangle with a gradient fill
+    fn rect_grad(&mut self, pass: PassId, rect: Quad, grad: Gradient);
+
+    /// Draw a frame with a gradient fill
+    fn frame_grad(&mut self, pass: PassId, outer: Quad, inner: Quad, grad: Gradient);
 }
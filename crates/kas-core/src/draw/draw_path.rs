@@ -0,0 +1,261 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Drawing APIs — draw paths
+
+use super::color::Rgba;
+use super::{Draw, DrawIface, DrawImpl, DrawSharedImpl, PassId};
+use crate::cast::{Conv, ConvFloat};
+use crate::geom::Vec2;
+
+/// Style of joins between line segments
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum LineJoin {
+    /// Segments are extended to meet at a point
+    ///
+    /// Where this point would be far from the vertex (at very sharp angles),
+    /// a bevel join is used instead.
+    Miter,
+    /// The gap between segments is filled with a triangle
+    Bevel,
+    /// The gap between segments is filled with a circular arc
+    Round,
+}
+
+impl Default for LineJoin {
+    fn default() -> Self {
+        LineJoin::Miter
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+enum PathSeg {
+    MoveTo(Vec2),
+    LineTo(Vec2),
+    QuadTo(Vec2, Vec2),
+    CubicTo(Vec2, Vec2, Vec2),
+    Close,
+}
+
+/// A path composed of lines and Bézier curves
+///
+/// A path consists of one or more sub-paths, each started by
+/// [`Path::new`] or [`Path::move_to`] and optionally closed by
+/// [`Path::close`].
+///
+/// Curves are flattened to line segments before drawing (see
+/// [`Path::flatten`]).
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Path {
+    segments: Vec<PathSeg>,
+}
+
+impl Path {
+    /// Construct, starting at point `p`
+    #[inline]
+    pub fn new(p: Vec2) -> Self {
+        Path {
+            segments: vec![PathSeg::MoveTo(p)],
+        }
+    }
+
+    /// Start a new sub-path at point `p` (chain style)
+    #[inline]
+    pub fn move_to(mut self, p: Vec2) -> Self {
+        self.segments.push(PathSeg::MoveTo(p));
+        self
+    }
+
+    /// Add a line to point `p` (chain style)
+    #[inline]
+    pub fn line_to(mut self, p: Vec2) -> Self {
+        self.segments.push(PathSeg::LineTo(p));
+        self
+    }
+
+    /// Add a quadratic Bézier curve with control point `c` to point `p`
+    /// (chain style)
+    #[inline]
+    pub fn quad_to(mut self, c: Vec2, p: Vec2) -> Self {
+        self.segments.push(PathSeg::QuadTo(c, p));
+        self
+    }
+
+    /// Add a cubic Bézier curve with control points `c1`, `c2` to point `p`
+    /// (chain style)
+    #[inline]
+    pub fn cubic_to(mut self, c1: Vec2, c2: Vec2, p: Vec2) -> Self {
+        self.segments.push(PathSeg::CubicTo(c1, c2, p));
+        self
+    }
+
+    /// Close the current sub-path (chain style)
+    ///
+    /// This adds a line back to the start of the sub-path.
+    #[inline]
+    pub fn close(mut self) -> Self {
+        self.segments.push(PathSeg::Close);
+        self
+    }
+
+    /// Flatten to polylines
+    ///
+    /// Curves are approximated by line segments such that the error does not
+    /// exceed `tolerance` (approximately). The closure `f` is called once for
+    /// each sub-path with its points and whether it is closed.
+    pub fn flatten<F: FnMut(&[Vec2], bool)>(&self, tolerance: f32, mut f: F) {
+        let tolerance = tolerance.max(0.01);
+        let mut points: Vec<Vec2> = vec![];
+        let mut last = Vec2::ZERO;
+        for seg in &self.segments {
+            match *seg {
+                PathSeg::MoveTo(p) => {
+                    if points.len() > 1 {
+                        f(&points, false);
+                    }
+                    points.clear();
+                    points.push(p);
+                    last = p;
+                }
+                PathSeg::LineTo(p) => {
+                    start_if_empty(&mut points, last);
+                    points.push(p);
+                    last = p;
+                }
+                PathSeg::QuadTo(c, p) => {
+                    start_if_empty(&mut points, last);
+                    // Max deviation of the control polygon from the curve
+                    let dd = (last - c * 2.0 + p).max_abs_comp();
+                    let n = segments_for(dd * 0.25, tolerance);
+                    for i in 1..=n {
+                        let t = f32::conv(i) / f32::conv(n);
+                        let u = 1.0 - t;
+                        points.push(last * (u * u) + c * (2.0 * u * t) + p * (t * t));
+                    }
+                    last = p;
+                }
+                PathSeg::CubicTo(c1, c2, p) => {
+                    start_if_empty(&mut points, last);
+                    let dd1 = (last - c1 * 2.0 + c2).max_abs_comp();
+                    let dd2 = (c1 - c2 * 2.0 + p).max_abs_comp();
+                    let n = segments_for(dd1.max(dd2) * 0.75, tolerance);
+                    for i in 1..=n {
+                        let t = f32::conv(i) / f32::conv(n);
+                        let u = 1.0 - t;
+                        points.push(
+                            last * (u * u * u)
+                                + c1 * (3.0 * u * u * t)
+                                + c2 * (3.0 * u * t * t)
+                                + p * (t * t * t),
+                        );
+                    }
+                    last = p;
+                }
+                PathSeg::Close => {
+                    if points.len() > 2 {
+                        f(&points, true);
+                    }
+                    if let Some(first) = points.first() {
+                        last = *first;
+                    }
+                    points.clear();
+                }
+            }
+        }
+        if points.len() > 1 {
+            f(&points, false);
+        }
+
+        fn start_if_empty(points: &mut Vec<Vec2>, last: Vec2) {
+            if points.is_empty() {
+                points.push(last);
+            }
+        }
+
+        // Number of line segments required given second-difference magnitude
+        fn segments_for(dev: f32, tolerance: f32) -> u32 {
+            let n = (dev / tolerance).sqrt().ceil();
+            u32::conv_nearest(n.min(256.0)).max(1)
+        }
+    }
+}
+
+/// Extension over [`Draw`] for paths and polygons
+///
+/// All methods draw some feature. Paths are triangulated by the shell and
+/// drawn without anti-aliasing along with square-edged primitives (see
+/// [draw order](super#draw-order)).
+pub trait DrawPath: Draw {
+    /// Draw a filled polygon of uniform colour
+    ///
+    /// The polygon is implicitly closed. It may be concave but should not
+    /// self-intersect.
+    fn polygon(&mut self, points: &[Vec2], col: Rgba);
+
+    /// Draw a line through `points` of uniform colour
+    ///
+    /// Lines have the given `width` (centered on `points`), flat ends, and
+    /// segments are joined in the given style.
+    fn polyline(&mut self, points: &[Vec2], width: f32, join: LineJoin, col: Rgba);
+
+    /// Fill a [`Path`]
+    ///
+    /// Each sub-path is implicitly closed and filled as by [`Self::polygon`]
+    /// (holes are not supported). Curves are flattened with a tolerance of
+    /// a quarter pixel.
+    fn fill_path(&mut self, path: &Path, col: Rgba) {
+        path.flatten(0.25, |points, _| self.polygon(points, col));
+    }
+
+    /// Stroke a [`Path`]
+    ///
+    /// Each sub-path is drawn as by [`Self::polyline`]; closed sub-paths
+    /// include a final segment back to the start. Curves are flattened with a
+    /// tolerance of a quarter pixel.
+    fn stroke_path(&mut self, path: &Path, width: f32, join: LineJoin, col: Rgba) {
+        path.flatten(0.25, |points, closed| {
+            if closed {
+                let mut points = points.to_vec();
+                points.push(points[0]);
+                if points.len() > 2 {
+                    // Start mid-segment such that the first vertex is joined
+                    let mid = (points[0] + points[1]) * 0.5;
+                    points[0] = mid;
+                    points.push(mid);
+                }
+                self.polyline(&points, width, join, col);
+            } else {
+                self.polyline(points, width, join, col);
+            }
+        });
+    }
+}
+
+impl<'a, DS: DrawSharedImpl> DrawPath for DrawIface<'a, DS>
+where
+    DS::Draw: DrawPathImpl,
+{
+    #[inline]
+    fn polygon(&mut self, points: &[Vec2], col: Rgba) {
+        self.draw.polygon(self.pass, points, col);
+    }
+    #[inline]
+    fn polyline(&mut self, points: &[Vec2], width: f32, join: LineJoin, col: Rgba) {
+        self.draw.polyline(self.pass, points, width, join, col);
+    }
+}
+
+/// Drawing commands for paths and polygons
+///
+/// This trait is an extension over [`DrawImpl`] providing paths.
+#[cfg_attr(not(feature = "internal_doc"), doc(hidden))]
+#[cfg_attr(doc_cfg, doc(cfg(internal_doc)))]
+pub trait DrawPathImpl: DrawImpl {
+    /// Draw a filled polygon of uniform colour
+    fn polygon(&mut self, pass: PassId, points: &[Vec2], col: Rgba);
+
+    /// Draw a line through `points` of uniform colour
+    fn polyline(&mut self, pass: PassId, points: &[Vec2], width: f32, join: LineJoin, col: Rgba);
+}
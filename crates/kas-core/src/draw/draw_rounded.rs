@@ -6,7 +6,7 @@
 //! Drawing APIs — draw rounded
 
 use super::color::Rgba;
-use super::{Draw, DrawIface, DrawImpl, DrawSharedImpl, PassId};
+use super::{Draw, DrawIface, DrawImpl, DrawSharedImpl, PassId, StrokeStyle};
 use crate::geom::{Quad, Vec2};
 
 /// Extension over [`Draw`] for rounded shapes
@@ -23,6 +23,19 @@ pub trait DrawRounded: Draw {
     /// preferred.
     fn rounded_line(&mut self, p1: Vec2, p2: Vec2, radius: f32, col: Rgba);
 
+    /// Draw a line with rounded ends, uniform colour and the given `style`
+    ///
+    /// This is a variant of [`Self::rounded_line`]. Each dash (or dot) has
+    /// rounded ends; pattern lengths include these ends.
+    fn rounded_line_styled(
+        &mut self,
+        p1: Vec2,
+        p2: Vec2,
+        radius: f32,
+        style: StrokeStyle,
+        col: Rgba,
+    );
+
     /// Draw a circle or oval of uniform colour
     ///
     /// More generally, this shape is an axis-aligned oval which may be hollow.
@@ -75,6 +88,18 @@ where
         self.draw.rounded_line(self.pass, p1, p2, radius, col);
     }
     #[inline]
+    fn rounded_line_styled(
+        &mut self,
+        p1: Vec2,
+        p2: Vec2,
+        radius: f32,
+        style: StrokeStyle,
+        col: Rgba,
+    ) {
+        self.draw
+            .rounded_line_styled(self.pass, p1, p2, radius, style, col);
+    }
+    #[inline]
     fn circle(&mut self, rect: Quad, inner_radius: f32, col: Rgba) {
         self.draw.circle(self.pass, rect, inner_radius, col);
     }
@@ -107,6 +132,17 @@ pub trait DrawRoundedImpl: DrawImpl {
     /// Draw a line with rounded ends and uniform colour
     fn rounded_line(&mut self, pass: PassId, p1: Vec2, p2: Vec2, radius: f32, col: Rgba);
 
+    /// Draw a line with rounded ends, uniform colour and the given `style`
+    fn rounded_line_styled(
+        &mut self,
+        pass: PassId,
+        p1: Vec2,
+        p2: Vec2,
+        radius: f32,
+        style: StrokeStyle,
+        col: Rgba,
+    );
+
     /// Draw a circle or oval of uniform colour
     fn circle(&mut self, pass: PassId, rect: Quad, inner_radius: f32, col: Rgba);
 
@@ -57,6 +57,15 @@ pub trait DrawShared {
     /// according to `format`. Data must be in row-major order.
     fn image_upload(&mut self, id: ImageId, data: &[u8], format: ImageFormat);
 
+    /// Import an existing GPU texture as an image
+    ///
+    /// See [`DrawSharedImpl::import_texture`].
+    fn import_texture(
+        &mut self,
+        texture: Box<dyn Any>,
+        size: (u32, u32),
+    ) -> Result<ImageId, ImageError>;
+
     /// Load an image from a path, autodetecting file type
     ///
     /// This deduplicates multiple loads of the same path, instead incrementing
@@ -86,6 +95,15 @@ impl<DS: DrawSharedImpl> DrawShared for SharedState<DS> {
         self.draw.image_upload(id, data, format);
     }
 
+    #[inline]
+    fn import_texture(
+        &mut self,
+        texture: Box<dyn Any>,
+        size: (u32, u32),
+    ) -> Result<ImageId, ImageError> {
+        self.draw.import_texture(texture, size)
+    }
+
     #[inline]
     fn image_from_path(&mut self, path: &Path) -> Result<ImageId, ImageError> {
         self.images.load_path(&mut self.draw, path)
@@ -126,6 +144,28 @@ pub trait DrawSharedImpl: Any {
     /// called again to update the image contents.
     fn image_upload(&mut self, id: ImageId, data: &[u8], format: ImageFormat);
 
+    /// Import an existing GPU texture as an image
+    ///
+    /// This allows content rendered or decoded directly on the GPU (e.g. video
+    /// or a 3D view) to be drawn via the usual image API without a round-trip
+    /// through [`DrawSharedImpl::image_upload`]. The type of `texture` depends
+    /// on the shell: for `kas-wgpu` this is a `wgpu::Texture` created on the
+    /// shell's device with usage `TEXTURE_BINDING` and dimensions `size`.
+    ///
+    /// The texture is drawn directly, thus later writes to it are visible on
+    /// the next frame. It is dropped by [`DrawSharedImpl::image_free`].
+    /// Imported images may not be written via `image_upload`.
+    ///
+    /// The default implementation returns [`ImageError::Unsupported`].
+    fn import_texture(
+        &mut self,
+        texture: Box<dyn Any>,
+        size: (u32, u32),
+    ) -> Result<ImageId, ImageError> {
+        let _ = (texture, size);
+        Err(ImageError::Unsupported)
+    }
+
     /// Free an image allocation
     fn image_free(&mut self, id: ImageId);
 
@@ -135,6 +175,11 @@ pub trait DrawSharedImpl: Any {
     /// Draw the image in the given `rect`
     fn draw_image(&self, draw: &mut Self::Draw, pass: PassId, id: ImageId, rect: Quad);
 
+    /// Draw the image in the given `rect` as a background
+    ///
+    /// See [`super::Draw::image_background`].
+    fn draw_image_background(&self, draw: &mut Self::Draw, pass: PassId, id: ImageId, rect: Quad);
+
     /// Draw text with a colour
     fn draw_text(
         &mut self,
@@ -0,0 +1,74 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Gradient fills
+
+use super::color::Rgba;
+use crate::geom::{Quad, Vec2};
+
+/// A two-colour gradient
+///
+/// Positions are in the same coordinate space as the shape being filled.
+/// Beyond the ends of the gradient, the nearest end colour is used.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum Gradient {
+    /// Linear gradient from colour `col1` at `start` to `col2` at `end`
+    Linear {
+        start: Vec2,
+        end: Vec2,
+        col1: Rgba,
+        col2: Rgba,
+    },
+    /// Radial (elliptic) gradient from colour `col1` at `center` to `col2`
+    /// at distance `radius` (per axis) from the center
+    Radial {
+        center: Vec2,
+        radius: Vec2,
+        col1: Rgba,
+        col2: Rgba,
+    },
+}
+
+impl Gradient {
+    /// Vertical gradient over `rect` from `top` to `bottom` colour
+    pub fn vertical(rect: Quad, top: Rgba, bottom: Rgba) -> Self {
+        let x = 0.5 * (rect.a.0 + rect.b.0);
+        Gradient::Linear {
+            start: Vec2(x, rect.a.1),
+            end: Vec2(x, rect.b.1),
+            col1: top,
+            col2: bottom,
+        }
+    }
+
+    /// Horizontal gradient over `rect` from `left` to `right` colour
+    pub fn horizontal(rect: Quad, left: Rgba, right: Rgba) -> Self {
+        let y = 0.5 * (rect.a.1 + rect.b.1);
+        Gradient::Linear {
+            start: Vec2(rect.a.0, y),
+            end: Vec2(rect.b.0, y),
+            col1: left,
+            col2: right,
+        }
+    }
+
+    /// Radial gradient filling `rect`, from `center` to `edge` colour
+    pub fn radial(rect: Quad, center: Rgba, edge: Rgba) -> Self {
+        Gradient::Radial {
+            center: (rect.a + rect.b) * 0.5,
+            radius: rect.size() * 0.5,
+            col1: center,
+            col2: edge,
+        }
+    }
+
+    /// Get the colours: `(col1, col2)`
+    pub fn colors(&self) -> (Rgba, Rgba) {
+        match *self {
+            Gradient::Linear { col1, col2, .. } => (col1, col2),
+            Gradient::Radial { col1, col2, .. } => (col1, col2),
+        }
+    }
+}
@@ -47,6 +47,8 @@ pub enum ImageError {
     Image(#[from] image::ImageError),
     #[error("failed to allocate texture space for image")]
     Allocation,
+    #[error("operation not supported by the graphics backend")]
+    Unsupported,
 }
 
 pub struct Images {
@@ -120,8 +122,9 @@ impl Images {
     /// (It also removes images not created through [`Images::load_path`].)
     pub fn remove_id<DS: DrawSharedImpl>(&mut self, draw: &mut DS, id: ImageId) {
         // We don't have a map from id to path, hence have to iterate. We can
-        // however do a fast check that id is used.
+        // however do a fast check whether id was loaded from a path.
         if !self.images.contains_key(&id) {
+            draw.image_free(id);
             return;
         }
 
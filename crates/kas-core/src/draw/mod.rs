@@ -21,37 +21,49 @@
 //! ## Draw order
 //!
 //! All draw operations happen within a "draw pass". The first pass corresponds
-//! to the window, while additional passes may be clipped and offset (see
-//! [`DrawIface::new_pass`]). Draw passes are executed sequentially in the order
+//! to the window, while additional passes may be clipped, offset and
+//! transformed (see [`DrawIface::new_pass`]). Draw passes are executed sequentially in the order
 //! defined.
 //!
 //! Within each pass, draw operations may be batched by the shell, thus draw
 //! operations may not happen in the order queued. In general, it may be
 //! expected that batches are executed in the following order:
 //!
-//! 1.  Square-edged primitives (e.g. [`Draw::rect`])
-//! 2.  Images
-//! 3.  Rounded or other partially-transparent primitives (e.g. [`DrawRounded::circle`])
-//! 4.  Custom draw routines (`CustomPipe`)
-//! 5.  Text
+//! 1.  Backgrounds: gradient fills (e.g. [`Draw::rect_grad`]), then shadows
+//!     (e.g. [`DrawRounded::circle_2col`]), then [`Draw::image_background`]
+//! 2.  Instanced rectangles ([`Draw::rect_instances`]), then other
+//!     square-edged primitives (e.g. [`Draw::rect`], [`Draw::frame`]) and
+//!     paths (e.g. [`DrawPath::polygon`])
+//! 3.  Images
+//! 4.  Rounded or other partially-transparent primitives (e.g. [`DrawRounded::circle`])
+//! 5.  Custom draw routines (`CustomPipe`)
+//! 6.  Text
 
 pub mod color;
 
 #[allow(clippy::module_inception)]
 mod draw;
+mod draw_path;
 mod draw_rounded;
 mod draw_shared;
+mod gradient;
 mod images;
+mod stroke;
+mod transform;
 
 use crate::cast::Cast;
 #[allow(unused)]
 use crate::theme::DrawMgr;
 
 pub use draw::{Draw, DrawIface, DrawImpl};
+pub use draw_path::{DrawPath, DrawPathImpl, LineJoin, Path};
 pub use draw_rounded::{DrawRounded, DrawRoundedImpl};
 pub use draw_shared::{DrawShared, DrawSharedImpl, SharedState};
+pub use gradient::Gradient;
 pub use images::{ImageError, ImageFormat, ImageId};
 use std::time::Instant;
+pub use stroke::StrokeStyle;
+pub use transform::Affine;
 
 /// Animation status
 #[cfg_attr(not(feature = "internal_doc"), doc(hidden))]
@@ -122,4 +134,11 @@ pub enum PassType {
     /// or combobox menu. The rect and offset are relative to the base window.
     /// The theme may draw a shadow or border around this rect.
     Overlay,
+    /// New pass is elevated above its siblings
+    ///
+    /// As with [`PassType::Clip`], the rect and offset are relative to the
+    /// parent, but drawing is clipped only by the window. The pass (and its
+    /// descendants) is drawn after all passes of lower elevation, including
+    /// passes created later, for example to draw a dragged item over siblings.
+    Elevated,
 }
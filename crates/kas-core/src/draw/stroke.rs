@@ -0,0 +1,90 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Stroke styles
+
+/// Style of lines and frames
+///
+/// Lengths are measured along the stroke and include any rounded ends.
+/// The `offset` shifts the pattern forwards along the stroke; animating this
+/// value gives a "marching ants" effect.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum StrokeStyle {
+    /// A continuous stroke
+    Solid,
+    /// Dashes of length `dash` separated by gaps of length `gap`
+    Dashed { dash: f32, gap: f32, offset: f32 },
+    /// Dots (of the same size as the stroke width) separated by `gap`
+    Dotted { gap: f32, offset: f32 },
+}
+
+impl Default for StrokeStyle {
+    fn default() -> Self {
+        StrokeStyle::Solid
+    }
+}
+
+impl StrokeStyle {
+    /// Construct a dashed style
+    pub fn dashed(dash: f32, gap: f32) -> Self {
+        StrokeStyle::Dashed {
+            dash,
+            gap,
+            offset: 0.0,
+        }
+    }
+
+    /// Construct a dotted style
+    pub fn dotted(gap: f32) -> Self {
+        StrokeStyle::Dotted { gap, offset: 0.0 }
+    }
+
+    /// Set the pattern offset (chain style)
+    ///
+    /// This has no effect on [`StrokeStyle::Solid`].
+    #[must_use]
+    pub fn with_offset(mut self, value: f32) -> Self {
+        match self {
+            StrokeStyle::Solid => (),
+            StrokeStyle::Dashed { ref mut offset, .. } => *offset = value,
+            StrokeStyle::Dotted { ref mut offset, .. } => *offset = value,
+        }
+        self
+    }
+
+    /// Find the visible parts of a stroke
+    ///
+    /// Calls `f(a, b)` for each visible interval `a..b` of a stroke section
+    /// of length `len` and `width`. The section starts at distance `start`
+    /// along the whole stroke; this allows continuing the pattern over
+    /// multiple sections (e.g. the sides of a frame). Intervals are relative
+    /// to the start of the section.
+    pub fn for_each_dash<F: FnMut(f32, f32)>(&self, width: f32, start: f32, len: f32, mut f: F) {
+        let (on, off, offset) = match *self {
+            StrokeStyle::Solid => (len, 0.0, 0.0),
+            StrokeStyle::Dashed { dash, gap, offset } => (dash, gap, offset),
+            StrokeStyle::Dotted { gap, offset } => (width, gap, offset),
+        };
+        if len <= 0.0 || on <= 0.0 {
+            return;
+        }
+        let period = on + off.max(0.0);
+        // Very short periods would be expensive to draw and are not visible
+        if off <= 0.0 || period < 0.5 {
+            f(0.0, len);
+            return;
+        }
+
+        let phase = (start - offset).rem_euclid(period);
+        let mut x = -phase;
+        while x < len {
+            let (a, b) = (x.max(0.0), (x + on).min(len));
+            if a < b {
+                f(a, b);
+            }
+            x += period;
+        }
+    }
+}
@@ -0,0 +1,132 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Affine transforms
+
+use crate::geom::{Quad, Vec2};
+
+/// A 2D affine transformation
+///
+/// Maps point `p` to `a * p.0 + b * p.1 + t`; that is, `a` and `b` are the
+/// columns of the linear part and `t` is the translation.
+///
+/// Coordinates follow the usual screen convention: `x` increases to the
+/// right and `y` increases downwards, thus a positive rotation angle is
+/// clockwise on screen.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Affine {
+    pub a: Vec2,
+    pub b: Vec2,
+    pub t: Vec2,
+}
+
+impl Default for Affine {
+    fn default() -> Self {
+        Affine::IDENTITY
+    }
+}
+
+impl Affine {
+    /// The identity transform
+    pub const IDENTITY: Affine = Affine {
+        a: Vec2(1.0, 0.0),
+        b: Vec2(0.0, 1.0),
+        t: Vec2::ZERO,
+    };
+
+    /// Translation by `t`
+    #[inline]
+    pub fn translate(t: Vec2) -> Self {
+        Affine {
+            t,
+            ..Affine::IDENTITY
+        }
+    }
+
+    /// Scaling (per axis) about the origin
+    #[inline]
+    pub fn scale(s: Vec2) -> Self {
+        Affine {
+            a: Vec2(s.0, 0.0),
+            b: Vec2(0.0, s.1),
+            t: Vec2::ZERO,
+        }
+    }
+
+    /// Rotation about the origin by `angle` (radians, clockwise on screen)
+    #[inline]
+    pub fn rotate(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Affine {
+            a: Vec2(cos, sin),
+            b: Vec2(-sin, cos),
+            t: Vec2::ZERO,
+        }
+    }
+
+    /// Rotation about `center` by `angle` (radians, clockwise on screen)
+    pub fn rotate_about(angle: f32, center: Vec2) -> Self {
+        Affine::translate(-center)
+            .then(Affine::rotate(angle))
+            .then(Affine::translate(center))
+    }
+
+    /// Apply `self`, then `next`
+    #[must_use]
+    pub fn then(self, next: Affine) -> Self {
+        Affine {
+            a: next.transform_vector(self.a),
+            b: next.transform_vector(self.b),
+            t: next.transform_point(self.t),
+        }
+    }
+
+    /// True if this transform is a pure translation
+    #[inline]
+    pub fn is_translation(&self) -> bool {
+        self.a == Vec2(1.0, 0.0) && self.b == Vec2(0.0, 1.0)
+    }
+
+    /// Determinant of the linear part
+    #[inline]
+    pub fn determinant(&self) -> f32 {
+        self.a.0 * self.b.1 - self.a.1 * self.b.0
+    }
+
+    /// The inverse transform, if any
+    pub fn inverse(&self) -> Option<Affine> {
+        let det = self.determinant();
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let a = Vec2(self.b.1, -self.a.1) / det;
+        let b = Vec2(-self.b.0, self.a.0) / det;
+        let t = -(a * self.t.0 + b * self.t.1);
+        Some(Affine { a, b, t })
+    }
+
+    /// Transform a point
+    #[inline]
+    pub fn transform_point(&self, p: Vec2) -> Vec2 {
+        self.a * p.0 + self.b * p.1 + self.t
+    }
+
+    /// Transform a vector (ignoring translation)
+    #[inline]
+    pub fn transform_vector(&self, v: Vec2) -> Vec2 {
+        self.a * v.0 + self.b * v.1
+    }
+
+    /// Get the bounding box of a transformed `quad`
+    pub fn transform_bounds(&self, quad: Quad) -> Quad {
+        let corners = [quad.a, quad.ab(), quad.ba(), quad.b].map(|p| self.transform_point(p));
+        let mut bounds = Quad::from_coords(corners[0], corners[0]);
+        for p in &corners[1..] {
+            bounds.a = bounds.a.min(*p);
+            bounds.b = bounds.b.max(*p);
+        }
+        bounds
+    }
+}
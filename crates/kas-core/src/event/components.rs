@@ -4,11 +4,22 @@
 //     https://www.apache.org/licenses/LICENSE-2.0
 
 //! Event handling components
+//!
+//! Components implement re-usable event-handling logic for custom widgets:
+//!
+//! -   [`ScrollComponent`] implements scrolling of a view by keyboard, mouse
+//!     wheel and press-drag (with momentum)
+//! -   [`TextInput`] implements press handling for text-like widgets: cursor
+//!     placement, drag selection (with word/line selection on double/triple
+//!     click or double-tap), touch panning and drag-scrolling at view edges
+//!
+//! A component is stored as a field of the widget. The widget passes it events
+//! from [`crate::event::Handler::handle`] and acts on the result.
 
 use super::ScrollDelta::{LineDelta, PixelDelta};
 use super::{Command, Event, EventMgr, PressSource, Response, VoidMsg};
-use crate::cast::CastFloat;
 use crate::geom::{Coord, Offset, Rect, Size, Vec2};
+use crate::layout::Align;
 #[allow(unused)]
 use crate::text::SelectionHelper;
 use crate::{TkAction, WidgetId};
@@ -16,7 +27,9 @@ use std::time::{Duration, Instant};
 
 const PAYLOAD_SELECT: u64 = 1 << 60;
 const PAYLOAD_GLIDE: u64 = (1 << 60) + 1;
+const PAYLOAD_EDGE: u64 = (1 << 60) + 2;
 const GLIDE_POLL_MS: u64 = 3;
+const EDGE_POLL_MS: u64 = 50;
 
 #[derive(Clone, Debug, PartialEq)]
 enum Glide {
@@ -97,10 +110,16 @@ impl Glide {
 /// Logic for a scroll region
 ///
 /// This struct handles some scroll logic. It does not provide scrollbars.
+///
+/// Where content is smaller than the scroll window, it is aligned according
+/// to [`ScrollComponent::set_align`] (by default, top-left). In this case the
+/// [`ScrollComponent::translation`] differs from the scroll offset.
 #[derive(Clone, Debug, PartialEq)]
 pub struct ScrollComponent {
     max_offset: Offset,
     offset: Offset,
+    align: (Align, Align),
+    align_offset: Offset,
     scroll_rate: f32,
     glide: Glide,
 }
@@ -111,6 +130,8 @@ impl Default for ScrollComponent {
         ScrollComponent {
             max_offset: Offset::ZERO,
             offset: Offset::ZERO,
+            align: (Align::Default, Align::Default),
+            align_offset: Offset::ZERO,
             scroll_rate: 30.0,
             glide: Glide::None,
         }
@@ -145,7 +166,40 @@ impl ScrollComponent {
     /// required updates regardless and the return value can be safely ignored.
     pub fn set_sizes(&mut self, window_size: Size, content_size: Size) -> TkAction {
         self.max_offset = Offset::from(content_size) - Offset::from(window_size);
-        self.set_offset(self.offset)
+        // Where content is smaller, space is negative
+        let space = self.max_offset.min(Offset::ZERO);
+        let align = |align, space| match align {
+            Align::Center => space / 2,
+            Align::BR => space,
+            Align::Default | Align::TL | Align::Stretch => 0,
+        };
+        let align_offset = Offset(align(self.align.0, space.0), align(self.align.1, space.1));
+        let mut action = TkAction::empty();
+        if align_offset != self.align_offset {
+            self.align_offset = align_offset;
+            action = TkAction::REGION_MOVED;
+        }
+        action | self.set_offset(self.offset)
+    }
+
+    /// Set alignment of content smaller than the scroll window
+    ///
+    /// The horizontal and vertical alignments are applied on the next call to
+    /// [`Self::set_sizes`]. [`Align::Center`] and [`Align::BR`] are
+    /// supported; other values align content to the top-left.
+    #[inline]
+    pub fn set_align(&mut self, align: (Align, Align)) {
+        self.align = align;
+    }
+
+    /// Get the translation from outer coordinates to content coordinates
+    ///
+    /// This is the scroll offset, adjusted for alignment where content is
+    /// smaller than the scroll window. Use this (instead of
+    /// [`Self::offset`]) to translate coordinates and draw content.
+    #[inline]
+    pub fn translation(&self) -> Offset {
+        self.offset + self.align_offset
     }
 
     /// Set the scroll offset
@@ -174,17 +228,20 @@ impl ScrollComponent {
     }
 
     /// Apply offset to an event being sent to the scrolled child
+    ///
+    /// This uses [`Self::translation`].
     #[inline]
     pub fn offset_event(&self, mut event: Event) -> Event {
+        let translation = self.translation();
         match &mut event {
             Event::PressStart { coord, .. } => {
-                *coord += self.offset;
+                *coord += translation;
             }
             Event::PressMove { coord, .. } => {
-                *coord += self.offset;
+                *coord += translation;
             }
             Event::PressEnd { coord, .. } => {
-                *coord += self.offset;
+                *coord += translation;
             }
             _ => {}
         };
@@ -206,7 +263,7 @@ impl ScrollComponent {
         let off = Offset::from(rect.size) - Offset::from(window_rect.size);
         let offset = self.offset.max(v + off).min(v);
         let action = self.set_offset(offset);
-        (rect - self.offset, action)
+        (rect - self.translation(), action)
     }
 
     /// Use an event to scroll, if possible
@@ -276,8 +333,8 @@ impl ScrollComponent {
             }
             Event::Command(cmd, _) => {
                 let delta = match cmd {
-                    Command::Left => LineDelta(-1.0, 0.0),
-                    Command::Right => LineDelta(1.0, 0.0),
+                    Command::Left => LineDelta(1.0, 0.0),
+                    Command::Right => LineDelta(-1.0, 0.0),
                     Command::Up => LineDelta(0.0, 1.0),
                     Command::Down => LineDelta(0.0, -1.0),
                     Command::PageUp => PixelDelta(Offset(0, window_size.1 / 2)),
@@ -285,23 +342,11 @@ impl ScrollComponent {
                     _ => return (action, Response::Unused),
                 };
 
-                let d = match delta {
-                    LineDelta(x, y) => Offset(
-                        (-self.scroll_rate * x).cast_nearest(),
-                        (self.scroll_rate * y).cast_nearest(),
-                    ),
-                    PixelDelta(d) => d,
-                };
+                let d = delta.as_offset(self.scroll_rate);
                 action = self.set_offset(self.offset - d);
             }
             Event::Scroll(delta) => {
-                let d = match delta {
-                    LineDelta(x, y) => Offset(
-                        (-self.scroll_rate * x).cast_nearest(),
-                        (self.scroll_rate * y).cast_nearest(),
-                    ),
-                    PixelDelta(d) => d,
-                };
+                let d = delta.as_offset(self.scroll_rate);
                 let old_offset = self.offset;
                 action = self.set_offset(old_offset - d);
                 let delta = d - (old_offset - self.offset);
@@ -363,10 +408,115 @@ impl Default for TouchPhase {
 }
 
 /// Handles text selection and panning from mouse and touch events
-#[derive(Clone, Debug, Default)]
+///
+/// This component is intended for any text-like widget (see for example
+/// `ScrollLabel` and `EditField`). Usage:
+///
+/// -   construct via [`TextInput::new`] or `Default`, optionally configured via
+///     [`TextInput::with_touch_select`] and [`TextInput::with_edge_scroll`]
+/// -   call [`TextInput::set_rect`] from [`crate::Layout::set_rect`]
+/// -   pass events to [`TextInput::handle`] and act on the resulting
+///     [`TextInputAction`]; for [`TextInputAction::Cursor`] this usually means
+///     setting the edit position of a [`SelectionHelper`] to the text index
+///     nearest `coord` then applying [`SelectionHelper::set_anchor`],
+///     [`SelectionHelper::set_empty`] and [`SelectionHelper::expand`] as
+///     directed
+///
+/// While drag-selecting beyond the edge of the widget's rect, `Cursor` actions
+/// are repeated (every 50ms) so that a widget keeping the cursor in view
+/// scrolls its content.
+#[derive(Clone, Debug)]
 pub struct TextInput {
     touch_phase: TouchPhase,
     glide: Glide,
+    touch_select: bool,
+    edge_scroll: bool,
+    rect: Option<Rect>,
+    /// Coordinate and repeat count of an active selection drag
+    drag: Option<(Coord, u32)>,
+    /// Time and coordinate of the last touch tap
+    last_tap: Option<(Instant, Coord)>,
+}
+
+impl Default for TextInput {
+    fn default() -> Self {
+        TextInput::new()
+    }
+}
+
+impl TextInput {
+    /// Construct
+    ///
+    /// By default, touch selection and edge scrolling are enabled.
+    pub fn new() -> Self {
+        TextInput {
+            touch_phase: Default::default(),
+            glide: Default::default(),
+            touch_select: true,
+            edge_scroll: true,
+            rect: None,
+            drag: None,
+            last_tap: None,
+        }
+    }
+
+    /// Enable or disable touch selection (inline)
+    ///
+    /// If enabled (default), a touch held without motion (see
+    /// [`crate::event::config::WindowConfig::touch_select_delay`]) starts a
+    /// selection and a double-tap selects a word. If disabled, touch input
+    /// only pans.
+    #[inline]
+    #[must_use]
+    pub fn with_touch_select(mut self, enable: bool) -> Self {
+        self.touch_select = enable;
+        self
+    }
+
+    /// Enable or disable drag-scrolling at edges (inline)
+    ///
+    /// If enabled (default) and [`TextInput::set_rect`] has been called,
+    /// [`TextInputAction::Cursor`] actions are repeated while drag-selecting
+    /// outside of this rect.
+    #[inline]
+    #[must_use]
+    pub fn with_edge_scroll(mut self, enable: bool) -> Self {
+        self.edge_scroll = enable;
+        self
+    }
+
+    /// Set the widget's rect
+    ///
+    /// This is the view region, in the same coordinate space as event
+    /// coordinates (usually `self.core.rect`). It is used for edge scrolling.
+    #[inline]
+    pub fn set_rect(&mut self, rect: Rect) {
+        self.rect = Some(rect);
+    }
+
+    /// True if `coord` is outside the view rect (requires edge scrolling)
+    fn outside(&self, coord: Coord) -> bool {
+        match self.rect {
+            Some(rect) if self.edge_scroll => !rect.contains(coord),
+            _ => false,
+        }
+    }
+
+    /// Record a drag-selection position, returning a `Cursor` action
+    fn drag_to(
+        &mut self,
+        mgr: &mut EventMgr,
+        w_id: WidgetId,
+        coord: Coord,
+        repeats: u32,
+    ) -> TextInputAction {
+        self.drag = Some((coord, repeats));
+        if self.outside(coord) {
+            let delay = Duration::from_millis(EDGE_POLL_MS);
+            mgr.update_on_timer(delay, w_id, PAYLOAD_EDGE);
+        }
+        TextInputAction::Cursor(coord, false, false, repeats)
+    }
 }
 
 /// Result of [`TextInput::handle`]
@@ -394,6 +544,9 @@ pub enum TextInputAction {
     ///
     /// If `repeats > 1`, [`SelectionHelper::expand`] should be called with
     /// this parameter to enable word/line selection mode.
+    ///
+    /// Note: `coord` may lie outside the widget's rect (see
+    /// [`TextInput::with_edge_scroll`]).
     Cursor(Coord, bool, bool, u32),
 }
 
@@ -401,7 +554,7 @@ impl TextInput {
     /// Handle input events
     ///
     /// Consumes the following events: `PressStart`, `PressMove`, `PressEnd`,
-    /// `TimerUpdate(pl)` where `pl == 1<<60 || pl == (1<<60)+1`.
+    /// `TimerUpdate(pl)` where `(1<<60) <= pl <= (1<<60)+2`.
     /// May request press grabs and timer updates.
     ///
     /// Implements scrolling and text selection behaviour, excluding handling of
@@ -413,13 +566,23 @@ impl TextInput {
                 mgr.grab_press_unique(w_id.clone(), source, coord, None);
                 match source {
                     PressSource::Touch(touch_id) => {
+                        if self.touch_select && self.is_double_tap(mgr, coord) {
+                            // Double-tap: select a word
+                            self.touch_phase = TouchPhase::Cursor(touch_id);
+                            self.last_tap = None;
+                            self.drag = Some((coord, 2));
+                            return Action::Cursor(coord, true, true, 2);
+                        }
                         self.touch_phase = TouchPhase::Start(touch_id, coord);
-                        let delay = mgr.config().touch_select_delay();
-                        mgr.update_on_timer(delay, w_id, PAYLOAD_SELECT);
+                        if self.touch_select {
+                            let delay = mgr.config().touch_select_delay();
+                            mgr.update_on_timer(delay, w_id, PAYLOAD_SELECT);
+                        }
                         Action::Focus
                     }
                     PressSource::Mouse(..) if mgr.config_enable_mouse_text_pan() => Action::Focus,
                     PressSource::Mouse(_, repeats) => {
+                        self.drag = Some((coord, repeats));
                         Action::Cursor(coord, true, !mgr.modifiers().shift(), repeats)
                     }
                 }
@@ -443,15 +606,25 @@ impl TextInput {
                             }
                         }
                         TouchPhase::Pan(id) if id == touch_id => Action::Pan(delta),
-                        _ => Action::Cursor(coord, false, false, 1),
+                        _ => {
+                            let repeats = self.drag.map(|d| d.1).unwrap_or(1);
+                            self.drag_to(mgr, w_id, coord, repeats)
+                        }
                     },
                     PressSource::Mouse(..) if mgr.config_enable_mouse_text_pan() => {
                         Action::Pan(delta)
                     }
-                    PressSource::Mouse(_, repeats) => Action::Cursor(coord, false, false, repeats),
+                    PressSource::Mouse(_, repeats) => self.drag_to(mgr, w_id, coord, repeats),
                 }
             }
-            Event::PressEnd { source, .. } => {
+            Event::PressEnd { source, coord, .. } => {
+                self.drag = None;
+                self.last_tap = match (source, &self.touch_phase) {
+                    (PressSource::Touch(id), TouchPhase::Start(start_id, _)) if id == *start_id => {
+                        Some((Instant::now(), coord))
+                    }
+                    _ => None,
+                };
                 if self.glide.opt_start(mgr.config().scroll_flick_timeout())
                     && (matches!(source, PressSource::Touch(id) if self.touch_phase == TouchPhase::Pan(id))
                         || matches!(source, PressSource::Mouse(..) if mgr.config_enable_mouse_text_pan()))
@@ -464,6 +637,7 @@ impl TextInput {
                 match self.touch_phase {
                     TouchPhase::Start(touch_id, coord) => {
                         self.touch_phase = TouchPhase::Cursor(touch_id);
+                        self.drag = Some((coord, 1));
                         Action::Cursor(coord, true, !mgr.modifiers().shift(), 1)
                     }
                     // Note: if the TimerUpdate were from another requester it
@@ -476,13 +650,31 @@ impl TextInput {
                 // Momentum/glide scrolling: update per arbitrary step time until movment stops.
                 let decay = mgr.config().scroll_flick_decay();
                 if let Some(delta) = self.glide.step(decay) {
-                    mgr.update_on_timer(Duration::from_millis(GLIDE_POLL_MS), w_id, 0);
+                    mgr.update_on_timer(Duration::from_millis(GLIDE_POLL_MS), w_id, PAYLOAD_GLIDE);
                     Action::Pan(delta)
                 } else {
                     Action::None
                 }
             }
+            Event::TimerUpdate(pl) if pl == PAYLOAD_EDGE => match self.drag {
+                Some((coord, repeats)) if self.outside(coord) => {
+                    self.drag_to(mgr, w_id, coord, repeats)
+                }
+                _ => Action::None,
+            },
             _ => Action::Unused,
         }
     }
+
+    /// True if a touch at `coord` is the second tap of a double-tap
+    fn is_double_tap(&self, mgr: &EventMgr, coord: Coord) -> bool {
+        match self.last_tap {
+            Some((time, last)) => {
+                let thresh = mgr.config().double_click_dist_thresh();
+                time.elapsed() <= mgr.config().double_click_timeout()
+                    && Vec2::from(coord - last).sum_square() <= thresh * thresh
+            }
+            None => false,
+        }
+    }
 }
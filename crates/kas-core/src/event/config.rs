@@ -9,6 +9,8 @@ mod shortcuts;
 pub use shortcuts::Shortcuts;
 
 use super::ModifiersState;
+#[allow(unused)]
+use super::{Command, EventState}; // for doc-links
 use crate::cast::Cast;
 #[cfg(feature = "config")]
 use serde::{Deserialize, Serialize};
@@ -22,14 +24,26 @@ use std::time::Duration;
 ///
 /// > `menu_delay_ms`: `u32` (milliseconds) \
 /// > `touch_select_delay_ms`: `u32` (milliseconds) \
+/// > `long_press_ms`: `u32` (milliseconds, zero to disable) \
 /// > `scroll_flick_timeout_ms`: `u32` (milliseconds) \
 /// > `scroll_flick_mul`: `f32` (unitless, applied each second) \
 /// > `scroll_flick_sub`: `f32` (pixels per second) \
 /// > `pan_dist_thresh`: `f32` (pixels) \
+/// > `scroll_natural`: `bool` \
+/// > `scroll_x_mul`: `f32` (unitless) \
+/// > `scroll_y_mul`: `f32` (unitless) \
+/// > `scroll_shift_horizontal`: `bool` \
+/// > `double_click_ms`: `u32` (milliseconds) \
+/// > `double_click_dist_thresh`: `f32` (pixels) \
+/// > `key_repeat`: [`KeyRepeat`] \
+/// > `compose_input`: `bool` \
+/// > `unicode_hex_input`: `bool` \
 /// > `mouse_pan`: [`MousePan`] \
 /// > `mouse_text_pan`: [`MousePan`] \
 /// > `mouse_nav_focus`: `bool` \
 /// > `touch_nav_focus`: `bool` \
+/// > `accel_labels`: [`AccelLabels`] \
+/// > `zoom_step`: `f32` (unitless, font size multiplier) \
 /// > `shortcuts`: [`Shortcuts`]
 ///
 /// For descriptions of configuration effects, see [`WindowConfig`] methods.
@@ -42,6 +56,9 @@ pub struct Config {
     #[cfg_attr(feature = "config", serde(default = "defaults::touch_select_delay_ms"))]
     pub touch_select_delay_ms: u32,
 
+    #[cfg_attr(feature = "config", serde(default = "defaults::long_press_ms"))]
+    pub long_press_ms: u32,
+
     #[cfg_attr(
         feature = "config",
         serde(default = "defaults::scroll_flick_timeout_ms")
@@ -57,6 +74,34 @@ pub struct Config {
     #[cfg_attr(feature = "config", serde(default = "defaults::pan_dist_thresh"))]
     pub pan_dist_thresh: f32,
 
+    #[cfg_attr(feature = "config", serde(default = "defaults::scroll_natural"))]
+    pub scroll_natural: bool,
+    #[cfg_attr(feature = "config", serde(default = "defaults::scroll_mul"))]
+    pub scroll_x_mul: f32,
+    #[cfg_attr(feature = "config", serde(default = "defaults::scroll_mul"))]
+    pub scroll_y_mul: f32,
+    #[cfg_attr(
+        feature = "config",
+        serde(default = "defaults::scroll_shift_horizontal")
+    )]
+    pub scroll_shift_horizontal: bool,
+
+    #[cfg_attr(feature = "config", serde(default = "defaults::double_click_ms"))]
+    pub double_click_ms: u32,
+    #[cfg_attr(
+        feature = "config",
+        serde(default = "defaults::double_click_dist_thresh")
+    )]
+    pub double_click_dist_thresh: f32,
+
+    #[cfg_attr(feature = "config", serde(default = "defaults::key_repeat"))]
+    pub key_repeat: KeyRepeat,
+
+    #[cfg_attr(feature = "config", serde(default = "defaults::compose_input"))]
+    pub compose_input: bool,
+    #[cfg_attr(feature = "config", serde(default = "defaults::unicode_hex_input"))]
+    pub unicode_hex_input: bool,
+
     #[cfg_attr(feature = "config", serde(default = "defaults::mouse_pan"))]
     pub mouse_pan: MousePan,
     #[cfg_attr(feature = "config", serde(default = "defaults::mouse_text_pan"))]
@@ -67,8 +112,17 @@ pub struct Config {
     #[cfg_attr(feature = "config", serde(default = "defaults::touch_nav_focus"))]
     pub touch_nav_focus: bool,
 
+    #[cfg_attr(feature = "config", serde(default = "defaults::accel_labels"))]
+    pub accel_labels: AccelLabels,
+
+    #[cfg_attr(feature = "config", serde(default = "defaults::zoom_step"))]
+    pub zoom_step: f32,
+
     #[cfg_attr(feature = "config", serde(default = "Shortcuts::platform_defaults"))]
     pub shortcuts: Shortcuts,
+
+    #[cfg_attr(feature = "config", serde(skip))]
+    dirty: bool,
 }
 
 impl Default for Config {
@@ -76,15 +130,28 @@ impl Default for Config {
         Config {
             menu_delay_ms: defaults::menu_delay_ms(),
             touch_select_delay_ms: defaults::touch_select_delay_ms(),
+            long_press_ms: defaults::long_press_ms(),
             scroll_flick_timeout_ms: defaults::scroll_flick_timeout_ms(),
             scroll_flick_mul: defaults::scroll_flick_mul(),
             scroll_flick_sub: defaults::scroll_flick_sub(),
             pan_dist_thresh: defaults::pan_dist_thresh(),
+            scroll_natural: defaults::scroll_natural(),
+            scroll_x_mul: defaults::scroll_mul(),
+            scroll_y_mul: defaults::scroll_mul(),
+            scroll_shift_horizontal: defaults::scroll_shift_horizontal(),
+            double_click_ms: defaults::double_click_ms(),
+            double_click_dist_thresh: defaults::double_click_dist_thresh(),
+            key_repeat: defaults::key_repeat(),
+            compose_input: defaults::compose_input(),
+            unicode_hex_input: defaults::unicode_hex_input(),
             mouse_pan: defaults::mouse_pan(),
             mouse_text_pan: defaults::mouse_text_pan(),
             mouse_nav_focus: defaults::mouse_nav_focus(),
             touch_nav_focus: defaults::touch_nav_focus(),
+            accel_labels: defaults::accel_labels(),
+            zoom_step: defaults::zoom_step(),
             shortcuts: Shortcuts::platform_defaults(),
+            dirty: false,
         }
     }
 }
@@ -93,8 +160,7 @@ impl Default for Config {
 #[derive(Clone, Debug)]
 pub struct WindowConfig {
     config: Rc<RefCell<Config>>,
-    scroll_flick_sub: f32,
-    pan_dist_thresh: f32,
+    scale_factor: f32,
 }
 
 impl WindowConfig {
@@ -102,22 +168,28 @@ impl WindowConfig {
     #[cfg_attr(not(feature = "internal_doc"), doc(hidden))]
     #[cfg_attr(doc_cfg, doc(cfg(internal_doc)))]
     pub fn new(config: Rc<RefCell<Config>>, scale_factor: f32) -> Self {
-        let mut w = WindowConfig {
+        WindowConfig {
             config,
-            scroll_flick_sub: f32::NAN,
-            pan_dist_thresh: f32::NAN,
-        };
-        w.set_scale_factor(scale_factor);
-        w
+            scale_factor,
+        }
     }
 
     /// Set scale factor
     #[cfg_attr(not(feature = "internal_doc"), doc(hidden))]
     #[cfg_attr(doc_cfg, doc(cfg(internal_doc)))]
     pub fn set_scale_factor(&mut self, scale_factor: f32) {
-        let base = self.config.borrow();
-        self.scroll_flick_sub = base.scroll_flick_sub * scale_factor;
-        self.pan_dist_thresh = base.pan_dist_thresh * scale_factor;
+        self.scale_factor = scale_factor;
+    }
+
+    /// Update the base configuration
+    ///
+    /// Changes take effect immediately in all windows and mark the
+    /// configuration as dirty (see [`Config::is_dirty`]), thus the shell may
+    /// save it to disk.
+    pub fn update_base<F: FnOnce(&mut Config)>(&self, f: F) {
+        let mut base = self.config.borrow_mut();
+        f(&mut base);
+        base.dirty = true;
     }
 
     /// Delay before opening/closing menus on mouse hover
@@ -132,6 +204,18 @@ impl WindowConfig {
         Duration::from_millis(self.config.borrow().touch_select_delay_ms.cast())
     }
 
+    /// Delay before a held touch press is recognised as a long press
+    ///
+    /// See [`Event::LongPress`](super::Event::LongPress). Returns `None` if
+    /// long-press recognition is disabled.
+    #[inline]
+    pub fn long_press_delay(&self) -> Option<Duration> {
+        match self.config.borrow().long_press_ms {
+            0 => None,
+            ms => Some(Duration::from_millis(ms.cast())),
+        }
+    }
+
     /// Controls activation of glide/momentum scrolling
     ///
     /// This is the maximum time between the last press-movement and final
@@ -154,7 +238,11 @@ impl WindowConfig {
     /// Units are pixels/second (output is adjusted for the window's scale factor).
     #[inline]
     pub fn scroll_flick_decay(&self) -> (f32, f32) {
-        (self.config.borrow().scroll_flick_mul, self.scroll_flick_sub)
+        let base = self.config.borrow();
+        (
+            base.scroll_flick_mul,
+            base.scroll_flick_sub * self.scale_factor,
+        )
     }
 
     /// Drag distance threshold before panning (scrolling) starts
@@ -166,7 +254,76 @@ impl WindowConfig {
     /// Units are pixels (output is adjusted for the window's scale factor).
     #[inline]
     pub fn pan_dist_thresh(&self) -> f32 {
-        self.pan_dist_thresh
+        self.config.borrow().pan_dist_thresh * self.scale_factor
+    }
+
+    /// Whether to use "natural" scrolling
+    ///
+    /// If true, the direction of mouse-wheel and touchpad scrolling is
+    /// reversed, thus content follows the motion of the fingers.
+    #[inline]
+    pub fn scroll_natural(&self) -> bool {
+        self.config.borrow().scroll_natural
+    }
+
+    /// Mouse-wheel and touchpad scroll speed multipliers: `(x, y)`
+    #[inline]
+    pub fn scroll_multiplier(&self) -> (f32, f32) {
+        let base = self.config.borrow();
+        (base.scroll_x_mul, base.scroll_y_mul)
+    }
+
+    /// Whether Shift+wheel scrolls horizontally
+    ///
+    /// If true, vertical scroll deltas are converted to horizontal deltas while
+    /// Shift is held. This does not affect deltas with a horizontal component
+    /// (some platforms already make this conversion).
+    #[inline]
+    pub fn scroll_shift_horizontal(&self) -> bool {
+        self.config.borrow().scroll_shift_horizontal
+    }
+
+    /// Maximum interval between clicks counted as a repeat (double-click)
+    ///
+    /// Repeated clicks are reported via [`super::PressSource::repetitions`].
+    #[inline]
+    pub fn double_click_timeout(&self) -> Duration {
+        Duration::from_millis(self.config.borrow().double_click_ms.cast())
+    }
+
+    /// Maximum mouse movement between clicks counted as a repeat
+    ///
+    /// We use the L-inf distance metric (max of abs of values).
+    ///
+    /// Units are pixels (output is adjusted for the window's scale factor).
+    #[inline]
+    pub fn double_click_dist_thresh(&self) -> f32 {
+        self.config.borrow().double_click_dist_thresh * self.scale_factor
+    }
+
+    /// Handling of key-repeat (keys held down)
+    #[inline]
+    pub fn key_repeat(&self) -> KeyRepeat {
+        self.config.borrow().key_repeat
+    }
+
+    /// Whether the Compose key starts a compose sequence
+    ///
+    /// When enabled, a widget with character focus receives the composed
+    /// character (e.g. Compose, `'`, `e` inputs `é`) instead of the sequence.
+    #[inline]
+    pub fn compose_input(&self) -> bool {
+        self.config.borrow().compose_input
+    }
+
+    /// Whether Ctrl+Shift+U starts Unicode hex input
+    ///
+    /// When enabled, a widget with character focus receives the character
+    /// with the code point typed in hexadecimal, ended by Space or Enter
+    /// (e.g. Ctrl+Shift+U, `e`, `9`, Space inputs `é`).
+    #[inline]
+    pub fn unicode_hex_input(&self) -> bool {
+        self.config.borrow().unicode_hex_input
     }
 
     /// When to pan general widgets (unhandled events) with the mouse
@@ -193,6 +350,21 @@ impl WindowConfig {
         self.config.borrow().touch_nav_focus
     }
 
+    /// When accelerator key labels (underlines) are shown
+    #[inline]
+    pub fn accel_labels(&self) -> AccelLabels {
+        self.config.borrow().accel_labels
+    }
+
+    /// Font size multiplier applied by [`Command::ZoomIn`] and [`Command::ZoomOut`]
+    ///
+    /// Zoom commands are only handled by windows which opt in; see
+    /// [`EventState::enable_zoom`].
+    #[inline]
+    pub fn zoom_step(&self) -> f32 {
+        self.config.borrow().zoom_step
+    }
+
     /// Access shortcut config
     pub fn shortcuts<F: FnOnce(&Shortcuts) -> T, T>(&self, f: F) -> T {
         let base = self.config.borrow();
@@ -203,9 +375,11 @@ impl WindowConfig {
 /// Other functions
 impl Config {
     /// Has the config ever been updated?
+    ///
+    /// This is set by [`WindowConfig::update_base`].
     #[inline]
     pub fn is_dirty(&self) -> bool {
-        false // current code never updates config
+        self.dirty
     }
 }
 
@@ -244,8 +418,58 @@ impl MousePan {
     }
 }
 
+/// Handling of key-repeat
+///
+/// Holding a key down causes the system to repeat key presses. This controls
+/// which repeated presses are passed to widgets.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "config", derive(Serialize, Deserialize))]
+#[derive(num_enum::IntoPrimitive, num_enum::TryFromPrimitive)]
+#[repr(u8)]
+pub enum KeyRepeat {
+    /// Ignore repeated key presses
+    Never,
+    /// Only repeat keys bound to a [`Command`] (e.g. arrow keys and
+    /// Backspace)
+    ///
+    /// Other repeated keys do not input characters or activate accelerator keys.
+    Commands,
+    /// Repeat all keys
+    Always,
+}
+
+impl KeyRepeat {
+    /// Is a repeated press allowed, given whether the key is bound to a command?
+    pub fn allows(self, is_command: bool) -> bool {
+        match self {
+            KeyRepeat::Never => false,
+            KeyRepeat::Commands => is_command,
+            KeyRepeat::Always => true,
+        }
+    }
+}
+
+/// When to show accelerator key labels
+///
+/// Accelerator keys are indicated by underlining a character of a label (e.g.
+/// "&File" is drawn as "File" with "F" underlined). See
+/// [`EventState::show_accel_labels`].
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq)]
+#[cfg_attr(feature = "config", derive(Serialize, Deserialize))]
+#[derive(num_enum::IntoPrimitive, num_enum::TryFromPrimitive)]
+#[repr(u8)]
+pub enum AccelLabels {
+    /// Never underline
+    Never,
+    /// Underline while the Alt key is held (unless a widget has character
+    /// focus)
+    WithAlt,
+    /// Always underline
+    Always,
+}
+
 mod defaults {
-    use super::MousePan;
+    use super::{AccelLabels, KeyRepeat, MousePan};
 
     pub fn menu_delay_ms() -> u32 {
         250
@@ -253,6 +477,9 @@ mod defaults {
     pub fn touch_select_delay_ms() -> u32 {
         1000
     }
+    pub fn long_press_ms() -> u32 {
+        500
+    }
     pub fn scroll_flick_timeout_ms() -> u32 {
         25
     }
@@ -265,6 +492,30 @@ mod defaults {
     pub fn pan_dist_thresh() -> f32 {
         2.1
     }
+    pub fn scroll_natural() -> bool {
+        false
+    }
+    pub fn scroll_mul() -> f32 {
+        1.0
+    }
+    pub fn scroll_shift_horizontal() -> bool {
+        true
+    }
+    pub fn double_click_ms() -> u32 {
+        1000
+    }
+    pub fn double_click_dist_thresh() -> f32 {
+        4.0
+    }
+    pub fn key_repeat() -> KeyRepeat {
+        KeyRepeat::Always
+    }
+    pub fn compose_input() -> bool {
+        true
+    }
+    pub fn unicode_hex_input() -> bool {
+        true
+    }
     pub fn mouse_pan() -> MousePan {
         MousePan::Always
     }
@@ -284,4 +535,10 @@ mod defaults {
     pub fn touch_nav_focus() -> bool {
         true
     }
+    pub fn accel_labels() -> AccelLabels {
+        AccelLabels::WithAlt
+    }
+    pub fn zoom_step() -> f32 {
+        1.1
+    }
 }
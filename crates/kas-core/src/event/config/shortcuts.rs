@@ -78,6 +78,8 @@ impl Shortcuts {
         {
             let shortcuts = [
                 (VK::F4, Command::Close),
+                (VK::F7, Command::WindowMove),
+                (VK::F8, Command::WindowResize),
                 (VK::Left, Command::NavPrev),
                 (VK::Right, Command::NavNext),
                 (VK::Up, Command::NavParent),
@@ -108,6 +110,7 @@ impl Shortcuts {
             (VK::A, Command::SelectAll),
             (VK::B, Command::Bold),
             (VK::C, Command::Copy),
+            (VK::D, Command::SelectNext),
             (VK::F, Command::Find),
             (VK::I, Command::Italic),
             (VK::K, Command::Link),
@@ -122,6 +125,13 @@ impl Shortcuts {
             (VK::X, Command::Cut),
             (VK::Z, Command::Undo),
             (VK::Tab, Command::TabNext),
+            (VK::Equals, Command::ZoomIn),
+            (VK::Plus, Command::ZoomIn),
+            (VK::NumpadAdd, Command::ZoomIn),
+            (VK::Minus, Command::ZoomOut),
+            (VK::NumpadSubtract, Command::ZoomOut),
+            (VK::Key0, Command::ZoomReset),
+            (VK::Numpad0, Command::ZoomReset),
         ];
         map.extend(shortcuts.iter().cloned());
         #[cfg(target_os = "macos")]
@@ -175,6 +185,10 @@ impl Shortcuts {
             (VK::A, Command::Deselect),
             (VK::Z, Command::Redo),
             (VK::Tab, Command::TabPrev),
+            // Plus is usually typed as Shift+Equals
+            (VK::Equals, Command::ZoomIn),
+            // Question mark is usually typed as Shift+Slash
+            (VK::Slash, Command::Help),
         ];
         map.extend(shortcuts.iter().cloned());
         #[cfg(target_os = "macos")]
@@ -215,6 +229,66 @@ impl Shortcuts {
         }
         None
     }
+
+    /// Describe a key combination, e.g. `Ctrl+Shift+Z`
+    pub fn key_name(modifiers: ModifiersState, vkey: VirtualKeyCode) -> String {
+        let mut s = Self::modifiers_name(modifiers);
+        s.push_str(&format!("{:?}", vkey));
+        s
+    }
+
+    /// Describe a modifier state, e.g. `Ctrl+Shift+`
+    ///
+    /// This is the prefix used by [`Shortcuts::key_name`]. The result is
+    /// empty when no modifiers are held.
+    pub fn modifiers_name(modifiers: ModifiersState) -> String {
+        let mut s = String::new();
+        if modifiers.ctrl() {
+            s.push_str("Ctrl+");
+        }
+        if modifiers.logo() {
+            s.push_str("Super+");
+        }
+        if modifiers.alt() {
+            s.push_str("Alt+");
+        }
+        if modifiers.shift() {
+            s.push_str("Shift+");
+        }
+        s
+    }
+
+    /// Bind `modifiers` + `vkey` to `command`
+    ///
+    /// Returns the command previously bound to this key combination, if any.
+    pub fn insert(
+        &mut self,
+        modifiers: ModifiersState,
+        vkey: VirtualKeyCode,
+        command: Command,
+    ) -> Option<Command> {
+        let map = self.map.entry(modifiers).or_insert_with(Default::default);
+        map.insert(vkey, command)
+    }
+
+    /// Remove the binding for `modifiers` + `vkey`
+    ///
+    /// Returns the command previously bound to this key combination, if any.
+    pub fn remove(&mut self, modifiers: ModifiersState, vkey: VirtualKeyCode) -> Option<Command> {
+        self.map
+            .get_mut(&modifiers)
+            .and_then(|map| map.remove(&vkey))
+    }
+
+    /// Iterate over all bindings
+    ///
+    /// Yields `(modifiers, key, command)` for each binding, in no particular
+    /// order. Keys matched implicitly (see [`Command::new`]) are not included.
+    pub fn iter(&self) -> impl Iterator<Item = (ModifiersState, VirtualKeyCode, Command)> + '_ {
+        (self.map.iter()).flat_map(|(modifiers, map)| {
+            map.iter().map(move |(vkey, cmd)| (*modifiers, *vkey, *cmd))
+        })
+    }
 }
 
 #[cfg(feature = "config")]
@@ -10,10 +10,12 @@ use serde::{Deserialize, Serialize};
 
 #[allow(unused)]
 use super::{EventMgr, EventState, GrabMode, Response, SendEvent}; // for doc-links
-use super::{MouseButton, UpdateHandle, VirtualKeyCode};
+use super::{ModifiersState, MouseButton, UpdateHandle, VirtualKeyCode};
 
+use crate::cast::CastFloat;
 use crate::geom::{Coord, DVec2, Offset};
 use crate::{dir::Direction, WidgetId, WindowId};
+use std::time::Instant;
 
 /// Events addressed to a widget
 #[non_exhaustive]
@@ -51,7 +53,9 @@ pub enum Event {
     /// Widget receives a character of text input
     ///
     /// This is only received by a widget with character focus (see
-    /// [`EventState::request_char_focus`]). There is no overlap with
+    /// [`EventState::request_char_focus`]) or, when no widget has character
+    /// focus, by the parent of the top-most pop-up (see
+    /// [`EventMgr::add_popup`]). There is no overlap with
     /// [`Event::Command`]: key presses result in at most one of these events
     /// being sent to a widget.
     ReceivedCharacter(char),
@@ -168,6 +172,21 @@ pub enum Event {
         coord: Coord,
         success: bool,
     },
+    /// A touch press was held without movement
+    ///
+    /// This is sent to the widget under a touch press (`start_id` of
+    /// [`Event::PressStart`]) once the press has been held for the configured
+    /// delay (see [`WindowConfig::long_press_delay`]) without moving further
+    /// than the pan threshold. The payload is the coordinate of the press.
+    ///
+    /// Widgets with a context menu should open it on this event (as on a
+    /// secondary mouse-button press). If the event is used, haptic feedback
+    /// is requested (where supported) and any grab of the touch is cancelled
+    /// (the grab owner receives [`Event::PressEnd`] with `success: false`),
+    /// thus releasing the press does not also activate the widget.
+    ///
+    /// [`WindowConfig::long_press_delay`]: super::config::WindowConfig::long_press_delay
+    LongPress(Coord),
     /// Update from a timer
     ///
     /// This event is received after requesting timed wake-up(s)
@@ -176,6 +195,16 @@ pub enum Event {
     /// The `u64` payload may be used to identify the corresponding
     /// [`EventState::update_on_timer`] call.
     TimerUpdate(u64),
+    /// A frame has been presented
+    ///
+    /// This event is received once after requesting a frame callback (see
+    /// [`EventState::request_frame_callback`]), when the next frame of the
+    /// window has been presented. The payload is the presentation time (as
+    /// measured by the shell).
+    ///
+    /// Widgets driving an animation may request another callback on receipt
+    /// of this event, thus updating in step with the display's refresh rate.
+    FrameComplete(Instant),
     /// Update triggerred via an [`UpdateHandle`]
     ///
     /// This event may be received after registering an [`UpdateHandle`] via
@@ -207,6 +236,46 @@ pub enum Event {
     /// *before* calling `handle_generic`, which might require a custom
     /// implementation of [`SendEvent`].
     NavFocus(bool),
+    /// A key press captured via [`EventState::request_key_capture`]
+    ///
+    /// The key press is not otherwise handled (e.g. as a [`Command`] or
+    /// accelerator key). Modifier keys pressed alone are not captured, but
+    /// the current modifier state is included.
+    KeyCapture(VirtualKeyCode, ModifiersState),
+    /// Modifier state changed while capturing a key press
+    ///
+    /// This is sent to the widget which requested key capture (see
+    /// [`EventState::request_key_capture`]), allowing it to display the
+    /// modifiers held so far.
+    KeyCaptureModifiers(ModifiersState),
+    /// Key capture was cancelled
+    ///
+    /// This is sent to the widget which requested key capture (see
+    /// [`EventState::request_key_capture`]) when the request is cancelled
+    /// other than by that widget, e.g. because the window lost focus.
+    KeyCaptureCancelled,
+    /// The window gained (`true`) or lost (`false`) input focus
+    ///
+    /// This is sent to the root widget of the window. Navigation focus is
+    /// cleared when the window loses focus and restored when it regains focus
+    /// (unless set in the mean time).
+    WindowFocus(bool),
+    /// The mouse cursor entered the widget
+    ///
+    /// This is sent when the widget becomes the "hovered" widget, but only to
+    /// widgets for which [`WidgetConfig::hover_events`] returns true. The
+    /// hovered widget is determined by [`Layout::find_id`], thus moving the
+    /// mouse over a child widget counts as leaving the parent.
+    ///
+    /// [`WidgetConfig::hover_events`]: crate::WidgetConfig::hover_events
+    /// [`Layout::find_id`]: crate::Layout::find_id
+    MouseEnter,
+    /// The mouse cursor left the widget
+    ///
+    /// This is sent (under the same conditions as [`Event::MouseEnter`]) when
+    /// the widget is no longer the hovered widget. It is delivered before any
+    /// [`Event::MouseEnter`] sent to the newly hovered widget.
+    MouseLeave,
 }
 
 /// Command input ([`Event::Command`])
@@ -295,6 +364,10 @@ pub enum Command {
     Deselect,
     /// Select all contents
     SelectAll,
+    /// Add a selection at the next occurrence of the selected text
+    ///
+    /// If nothing is selected, the word at the cursor may be selected instead.
+    SelectNext,
 
     /// Find (start)
     Find,
@@ -367,6 +440,24 @@ pub enum Command {
     /// Make view fullscreen
     Fullscreen,
 
+    /// Increase text size (zoom in)
+    ZoomIn,
+    /// Decrease text size (zoom out)
+    ZoomOut,
+    /// Reset text size
+    ZoomReset,
+
+    /// Move the window via the keyboard
+    ///
+    /// This is handled by the window widget (if not used by another widget);
+    /// see [`EventMgr::start_window_key_op`].
+    WindowMove,
+    /// Resize the window via the keyboard
+    ///
+    /// This is handled by the window widget (if not used by another widget);
+    /// see [`EventMgr::start_window_key_op`].
+    WindowResize,
+
     /// Close window/tab/popup
     Close,
     /// Exit program (e.g. Ctrl+Q)
@@ -466,6 +557,10 @@ impl PressSource {
     ///
     /// This is 1 for a single-click and all touch events, 2 for a double-click,
     /// 3 for a triple-click, etc. For `PressMove` without a grab this is 0.
+    ///
+    /// Clicks are counted as repeats when using the same button within
+    /// [`super::config::WindowConfig::double_click_timeout`] and
+    /// [`super::config::WindowConfig::double_click_dist_thresh`] of the previous click.
     #[inline]
     pub fn repetitions(self) -> u32 {
         match self {
@@ -476,6 +571,13 @@ impl PressSource {
 }
 
 /// Type used by [`Event::Scroll`]
+///
+/// Positive values indicate that content should move right and down (thus
+/// revealing more content to the left and above).
+///
+/// Deltas from a mouse wheel or touchpad have the configured direction,
+/// per-axis multipliers and Shift+wheel behaviour already applied; see
+/// [`super::config::WindowConfig::scroll_natural`].
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ScrollDelta {
     /// Scroll a given number of lines
@@ -483,3 +585,18 @@ pub enum ScrollDelta {
     /// Scroll a given number of pixels
     PixelDelta(Offset),
 }
+
+impl ScrollDelta {
+    /// Convert to an offset in pixels, given the distance of one line
+    ///
+    /// The result should be subtracted from the scroll offset.
+    pub fn as_offset(self, line_dist: f32) -> Offset {
+        match self {
+            ScrollDelta::LineDelta(x, y) => Offset(
+                (line_dist * x).cast_nearest(),
+                (line_dist * y).cast_nearest(),
+            ),
+            ScrollDelta::PixelDelta(d) => d,
+        }
+    }
+}
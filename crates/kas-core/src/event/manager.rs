@@ -9,26 +9,30 @@
 #![cfg_attr(not(feature = "winit"), allow(unused))]
 
 use linear_map::{set::LinearSet, LinearMap};
-use log::trace;
+use log::{debug, trace};
 use smallvec::SmallVec;
+use std::any::Any;
 use std::cell::RefCell;
 use std::collections::{BTreeMap, HashMap};
 use std::ops::{Deref, DerefMut};
 use std::rc::Rc;
-use std::time::Instant;
+use std::time::{Duration, Instant};
 use std::u16;
 
 use super::config::WindowConfig;
 use super::*;
-use crate::cast::Cast;
-use crate::geom::{Coord, Offset};
+use crate::cast::{Cast, CastFloat};
+use crate::geom::{Coord, Offset, Size};
 #[allow(unused)]
 use crate::WidgetConfig; // for doc-links
 use crate::{ShellWindow, TkAction, Widget, WidgetId, WindowId};
 
+mod compose;
 mod mgr_pub;
 mod mgr_shell;
 
+use compose::{ComposeStep, Composer};
+
 /// Controls the types of events delivered by [`EventMgr::grab_press`]
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum GrabMode {
@@ -44,6 +48,115 @@ pub enum GrabMode {
     PanOnly,
 }
 
+/// A keyboard-driven window operation
+///
+/// See [`EventMgr::start_window_key_op`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WindowKeyOp {
+    /// Move the window
+    Move,
+    /// Resize the window (by moving its bottom-right corner)
+    Resize,
+}
+
+/// A window edge or corner
+///
+/// See [`WindowRegion::Resize`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WindowEdge {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl WindowEdge {
+    /// The cursor icon used for this edge
+    pub fn cursor_icon(self) -> CursorIcon {
+        match self {
+            WindowEdge::North => CursorIcon::NResize,
+            WindowEdge::NorthEast => CursorIcon::NeResize,
+            WindowEdge::East => CursorIcon::EResize,
+            WindowEdge::SouthEast => CursorIcon::SeResize,
+            WindowEdge::South => CursorIcon::SResize,
+            WindowEdge::SouthWest => CursorIcon::SwResize,
+            WindowEdge::West => CursorIcon::WResize,
+            WindowEdge::NorthWest => CursorIcon::NwResize,
+        }
+    }
+
+    // Direction of movement of the edge on each axis: -1, 0 or 1
+    fn signs(self) -> (i32, i32) {
+        match self {
+            WindowEdge::North => (0, -1),
+            WindowEdge::NorthEast => (1, -1),
+            WindowEdge::East => (1, 0),
+            WindowEdge::SouthEast => (1, 1),
+            WindowEdge::South => (0, 1),
+            WindowEdge::SouthWest => (-1, 1),
+            WindowEdge::West => (-1, 0),
+            WindowEdge::NorthWest => (-1, -1),
+        }
+    }
+}
+
+/// Window hit-test result for a widget
+///
+/// The shell tests the widget under the mouse cursor before dispatching a
+/// primary-button press. This allows widgets to implement custom title bars
+/// and resize borders. See [`WidgetConfig::window_region`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WindowRegion {
+    /// Normal content: events are sent to the widget
+    Client,
+    /// Dragging with the primary mouse button moves the window
+    ///
+    /// Repeated clicks (e.g. double-click) are sent to the widget as usual.
+    Drag,
+    /// Dragging with the primary mouse button resizes the window from this edge
+    ///
+    /// The edge's cursor icon is used on hover. Repeated clicks are sent to
+    /// the widget as usual. The resize ends when the button is released, the
+    /// cursor leaves the window or the window loses focus.
+    Resize(WindowEdge),
+}
+
+impl Default for WindowRegion {
+    fn default() -> Self {
+        WindowRegion::Client
+    }
+}
+
+/// Policy for restoring navigation focus after a reconfigure
+///
+/// Widget identifiers are paths, thus after a reconfigure the
+/// [navigation focus](EventState::nav_focus) is kept on the widget at the
+/// same path, if this is still navigable. This enum controls what happens if
+/// the widget at this path has been removed, disabled or no longer supports
+/// keyboard navigation (see [`WidgetConfig::key_nav`]).
+///
+/// See [`EventState::set_nav_focus_fallback`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NavFocusFallback {
+    /// Clear navigation focus
+    Clear,
+    /// Focus the first navigable widget within the nearest remaining ancestor
+    ///
+    /// If no ancestor (below the root) contains a navigable widget, focus is
+    /// cleared.
+    Nearest,
+}
+
+impl Default for NavFocusFallback {
+    fn default() -> Self {
+        NavFocusFallback::Nearest
+    }
+}
+
 #[derive(Clone, Debug)]
 struct MouseGrab {
     button: MouseButton,
@@ -106,6 +219,37 @@ impl TouchGrab {
 }
 
 const MAX_PAN_GRABS: usize = 2;
+const VALUE_TOOLTIP_DURATION: Duration = Duration::from_millis(1500);
+/// Step used by window move/resize via the keyboard (virtual pixels)
+const WINDOW_KEY_STEP: f32 = 16.0;
+
+// Window resize via a resize border, driven by the mouse
+#[derive(Clone, Debug)]
+struct WindowResizeState {
+    edge: WindowEdge,
+    /// Mouse coordinate (relative to the window) of the edge
+    coord: Coord,
+    size: Size,
+}
+
+// A touch press which may become a long press
+#[derive(Clone, Debug)]
+struct LongPressState {
+    touch_id: u64,
+    id: WidgetId,
+    coord: Coord,
+    time: Instant,
+}
+
+#[derive(Clone, Debug)]
+struct WindowKeyState {
+    op: WindowKeyOp,
+    /// Total movement (used to cancel)
+    offset: Offset,
+    /// Initial size (used to cancel)
+    start_size: Size,
+    size: Size,
+}
 
 #[derive(Clone, Debug)]
 struct PanGrab {
@@ -117,11 +261,31 @@ struct PanGrab {
 }
 
 #[derive(Clone, Debug)]
-#[allow(clippy::enum_variant_names)] // they all happen to be about Focus
 enum Pending {
     LostCharFocus(WidgetId),
     LostSelFocus(WidgetId),
     SetNavFocus(WidgetId, bool),
+    MouseEnter(WidgetId),
+    MouseLeave(WidgetId),
+}
+
+/// Rate limit on delivery of updates via an [`UpdateHandle`]
+#[derive(Clone, Debug)]
+struct UpdateThrottle {
+    interval: Duration,
+    last: Option<Instant>,
+    /// Payload of the last deferred update
+    deferred: Option<u64>,
+}
+
+impl UpdateThrottle {
+    /// Time at which deferred updates may be delivered
+    fn resume_time(&self) -> Option<Instant> {
+        // Updates are only deferred after a delivery, thus last is set
+        self.deferred
+            .and(self.last)
+            .map(|last| last + self.interval)
+    }
 }
 
 type AccelLayer = (bool, HashMap<VirtualKeyCode, WidgetId>);
@@ -154,26 +318,53 @@ pub struct EventState {
     char_focus: bool,
     sel_focus: Option<WidgetId>,
     nav_focus: Option<WidgetId>,
+    /// Whether nav_focus was last set via the keyboard
+    nav_focus_key: bool,
     nav_fallback: Option<WidgetId>,
+    nav_focus_fallback: NavFocusFallback,
+    /// Navigation focus (and whether char focus was held) when the window
+    /// last lost focus
+    saved_focus: Option<(WidgetId, bool)>,
+    zoom_enabled: bool,
+    text_zoom: f32,
+    shortcut_help_enabled: bool,
+    shortcut_help: bool,
+    key_capture: Option<WidgetId>,
+    window_key_op: Option<WindowKeyState>,
+    window_resize: Option<WindowResizeState>,
+    value_tooltips: bool,
+    value_announcement: Option<(WidgetId, String, Instant)>,
     hover: Option<WidgetId>,
     hover_icon: CursorIcon,
     key_depress: LinearMap<u32, WidgetId>,
+    composer: Option<Composer>,
+    /// Scancodes of keys currently held, for key-repeat detection
+    keys_down: LinearSet<u32>,
+    /// Whether the last key press was a filtered repeat
+    key_repeat_filtered: bool,
     last_mouse_coord: Coord,
     last_click_button: MouseButton,
+    last_click_coord: Coord,
     last_click_repetitions: u32,
     last_click_timeout: Instant,
     mouse_grab: Option<MouseGrab>,
+    pointer_capture: Option<WidgetId>,
     touch_grab: SmallVec<[TouchGrab; 8]>,
+    long_press: Option<LongPressState>,
     pan_grab: SmallVec<[PanGrab; 4]>,
     accel_layers: BTreeMap<WidgetId, AccelLayer>,
+    accel_labels: HashMap<WidgetId, String>,
     // For each: (WindowId of popup, popup descriptor, old nav focus)
     popups: SmallVec<[(WindowId, crate::Popup, Option<WidgetId>); 16]>,
     popup_removed: SmallVec<[(WidgetId, WindowId); 16]>,
     time_updates: Vec<(Instant, WidgetId, u64)>,
+    frame_callbacks: SmallVec<[WidgetId; 4]>,
     // TODO(opt): consider other containers, e.g. C++ multimap
     // or sorted Vec with binary search yielding a range
     handle_updates: HashMap<UpdateHandle, LinearSet<WidgetId>>,
+    update_throttles: HashMap<UpdateHandle, UpdateThrottle>,
     pending: SmallVec<[Pending; 8]>,
+    reconfigure: SmallVec<[WidgetId; 4]>,
     #[cfg_attr(not(feature = "internal_doc"), doc(hidden))]
     #[cfg_attr(doc_cfg, doc(cfg(internal_doc)))]
     pub action: TkAction,
@@ -190,6 +381,21 @@ impl EventState {
         }
     }
 
+    // Apply scroll configuration to a mouse-wheel or touchpad delta
+    fn adjust_scroll(&self, mut x: f32, mut y: f32) -> (f32, f32) {
+        if self.config.scroll_shift_horizontal() && self.modifiers.shift() && x == 0.0 {
+            x = y;
+            y = 0.0;
+        }
+        let (mul_x, mul_y) = self.config.scroll_multiplier();
+        let sign = if self.config.scroll_natural() {
+            -1.0
+        } else {
+            1.0
+        };
+        (sign * mul_x * x, sign * mul_y * y)
+    }
+
     fn set_pan_on(
         &mut self,
         id: WidgetId,
@@ -320,6 +526,11 @@ impl EventState {
         if let Some(id) = self.char_focus() {
             // If widget has char focus, this is lost
             self.char_focus = false;
+            self.composer = None;
+            if self.modifiers.alt() {
+                // Accelerator key labels may now be shown
+                self.send_action(TkAction::REDRAW);
+            }
             self.pending.push(Pending::LostCharFocus(id));
         }
     }
@@ -334,6 +545,11 @@ impl EventState {
         // The widget probably already has nav focus, but anyway:
         self.set_nav_focus(wid.clone(), true);
 
+        if self.modifiers.alt() && char_focus != self.char_focus {
+            // Accelerator key labels may now be shown or hidden
+            self.send_action(TkAction::REDRAW);
+        }
+
         if wid == self.sel_focus {
             self.char_focus = self.char_focus || char_focus;
             return;
@@ -351,6 +567,7 @@ impl EventState {
 
         self.char_focus = char_focus;
         self.sel_focus = Some(wid);
+        self.composer = None;
     }
 }
 
@@ -364,11 +581,23 @@ impl EventState {
 /// It exposes two interfaces: one aimed at users implementing widgets and UIs
 /// and one aimed at shells. The latter is hidden
 /// from documentation unless the `internal_doc` feature is enabled.
+///
+/// ### Type-erased messages
+///
+/// As an alternative to returning messages via [`Response::Msg`] (which
+/// requires the message type to be threaded through each parent's
+/// [`Handler::Msg`]), a widget may push a message of any type onto a stack
+/// with [`EventMgr::push_msg`]. Messages stay on the stack while the event is
+/// returned through ancestors; any ancestor may then take a message with
+/// [`EventMgr::try_pop_msg`] (usually via the `any_msg` field attribute of
+/// the [`widget`](crate::macros::widget) macro). Messages not handled by the
+/// time the event returns to the window are discarded (with a log message).
 #[must_use]
 pub struct EventMgr<'a> {
     state: &'a mut EventState,
     shell: &'a mut dyn ShellWindow,
     action: TkAction,
+    messages: Vec<(Box<dyn Any>, &'static str)>,
 }
 
 impl<'a> Deref for EventMgr<'a> {
@@ -385,16 +614,30 @@ impl<'a> DerefMut for EventMgr<'a> {
 
 /// Internal methods
 impl<'a> EventMgr<'a> {
-    fn set_hover<W: Widget + ?Sized>(&mut self, widget: &W, w_id: Option<WidgetId>) {
+    fn set_hover<W: Widget + ?Sized>(&mut self, widget: &W, mut w_id: Option<WidgetId>) {
+        if let Some(id) = self.state.pointer_capture.as_ref() {
+            w_id = Some(id.clone());
+        }
         if self.state.hover != w_id {
             trace!("EventMgr: hover = {:?}", w_id);
+            // Pending events are handled in reverse order: push enter before
+            // leave so that the latter is delivered first
+            if let Some(id) = w_id.as_ref() {
+                if let Some(w) = widget.find_widget(id) {
+                    if w.hover_events() {
+                        self.pending.push(Pending::MouseEnter(id.clone()));
+                    }
+                }
+            }
+
             if let Some(id) = self.state.hover.take() {
-                if widget
-                    .find_widget(&id)
-                    .map(|w| w.hover_highlight())
-                    .unwrap_or(false)
-                {
-                    self.redraw(id);
+                if let Some(w) = widget.find_widget(&id) {
+                    if w.hover_events() {
+                        self.pending.push(Pending::MouseLeave(id.clone()));
+                    }
+                    if w.hover_highlight() {
+                        self.redraw(id);
+                    }
                 }
             }
             self.state.hover = w_id.clone();
@@ -405,7 +648,10 @@ impl<'a> EventMgr<'a> {
                     if w.hover_highlight() {
                         self.redraw(id);
                     }
-                    icon = w.cursor_icon();
+                    icon = match w.window_region() {
+                        WindowRegion::Resize(edge) => edge.cursor_icon(),
+                        _ => w.cursor_icon(),
+                    };
                 }
                 if icon != self.state.hover_icon {
                     self.state.hover_icon = icon;
@@ -431,6 +677,35 @@ impl<'a> EventMgr<'a> {
         use VirtualKeyCode as VK;
         let shift = self.state.modifiers.shift();
 
+        if self.state.window_key_op.is_some() {
+            self.window_key_op_key(vkey);
+            return;
+        }
+
+        if self.state.char_focus && self.compose_key(widget, vkey) {
+            return;
+        }
+
+        if let Some(id) = self.state.key_capture.clone() {
+            let is_modifier = matches!(
+                vkey,
+                VK::LShift
+                    | VK::RShift
+                    | VK::LControl
+                    | VK::RControl
+                    | VK::LAlt
+                    | VK::RAlt
+                    | VK::LWin
+                    | VK::RWin
+            );
+            if !is_modifier {
+                self.state.key_capture = None;
+                let modifiers = self.state.modifiers;
+                self.send_event(widget, id, Event::KeyCapture(vkey, modifiers));
+                return;
+            }
+        }
+
         let opt_command = self
             .state
             .config
@@ -472,6 +747,26 @@ impl<'a> EventMgr<'a> {
                     return;
                 }
             }
+
+            if matches!(cmd, Command::WindowMove | Command::WindowResize) {
+                let id = widget.id();
+                if self.try_send_event(widget, id, Event::Command(cmd, shift)) {
+                    return;
+                }
+            }
+
+            if self.state.zoom_enabled
+                && matches!(cmd, Command::ZoomIn | Command::ZoomOut | Command::ZoomReset)
+            {
+                self.zoom(cmd);
+                return;
+            }
+
+            if self.state.shortcut_help_enabled && cmd == Command::Help {
+                self.state.shortcut_help = !self.state.shortcut_help;
+                self.send_action(TkAction::REDRAW);
+                return;
+            }
         }
 
         // Next priority goes to accelerator keys when Alt is held or alt_bypass is true
@@ -516,6 +811,9 @@ impl<'a> EventMgr<'a> {
         } else if vkey == VK::Tab {
             self.clear_char_focus();
             self.next_nav_focus(widget.as_widget_mut(), shift, true);
+        } else if vkey == VK::Escape && self.state.shortcut_help {
+            self.state.shortcut_help = false;
+            self.send_action(TkAction::REDRAW);
         } else if vkey == VK::Escape {
             if let Some(id) = self.state.popups.last().map(|(id, _, _)| *id) {
                 self.close_window(id, true);
@@ -530,6 +828,206 @@ impl<'a> EventMgr<'a> {
         }
     }
 
+    // Adjusts this window's text zoom in response to a zoom command
+    fn zoom(&mut self, cmd: Command) {
+        let step = self.state.config.zoom_step();
+        let zoom = match cmd {
+            Command::ZoomIn => self.state.text_zoom * step,
+            Command::ZoomOut => self.state.text_zoom / step,
+            _ => 1.0,
+        };
+        trace!("EventMgr: zoom: text zoom = {}", zoom);
+        self.state.text_zoom = zoom;
+        self.shell.set_text_zoom(zoom);
+        self.send_action(TkAction::RESIZE);
+    }
+
+    // Start or update text composition; returns true if the key is consumed
+    fn compose_key<W>(&mut self, widget: &mut W, vkey: VirtualKeyCode) -> bool
+    where
+        W: Widget<Msg = VoidMsg> + ?Sized,
+    {
+        use VirtualKeyCode as VK;
+        let composer = match self.state.composer.as_mut() {
+            Some(composer) => composer,
+            None => {
+                let config = &self.state.config;
+                let m = self.state.modifiers;
+                self.state.composer = match vkey {
+                    VK::Compose if config.compose_input() => Some(Composer::Compose(String::new())),
+                    VK::U if m.ctrl() && m.shift() && !m.alt() && config.unicode_hex_input() => {
+                        Some(Composer::Hex(String::new()))
+                    }
+                    _ => return false,
+                };
+                // Redraw to show the composition (see EventState::composition)
+                self.state.send_action(TkAction::REDRAW);
+                return true;
+            }
+        };
+
+        // Characters are received via ReceivedCharacter
+        match vkey {
+            VK::Escape => self.state.composer = None,
+            VK::Back => composer.pop(),
+            VK::Return | VK::NumpadEnter => {
+                let step = composer.finish();
+                self.compose_step(widget, step);
+            }
+            _ => return false,
+        }
+        self.state.send_action(TkAction::REDRAW);
+        true
+    }
+
+    fn compose_step<W>(&mut self, widget: &mut W, step: ComposeStep)
+    where
+        W: Widget<Msg = VoidMsg> + ?Sized,
+    {
+        self.state.send_action(TkAction::REDRAW);
+        match step {
+            ComposeStep::Pending => return,
+            ComposeStep::Commit(c) => {
+                if let Some(id) = self.state.char_focus() {
+                    self.send_event(widget, id, Event::ReceivedCharacter(c));
+                }
+            }
+            ComposeStep::Cancel => (),
+        }
+        self.state.composer = None;
+    }
+
+    // Handle a key press during a keyboard window move/resize
+    fn window_key_op_key(&mut self, vkey: VirtualKeyCode) {
+        use VirtualKeyCode as VK;
+        let step = match self.state.modifiers.ctrl() {
+            true => 1,
+            false => (WINDOW_KEY_STEP * self.state.scale_factor).cast_nearest(),
+        };
+        let delta = match vkey {
+            VK::Left => Offset(-step, 0),
+            VK::Right => Offset(step, 0),
+            VK::Up => Offset(0, -step),
+            VK::Down => Offset(0, step),
+            VK::Return | VK::NumpadEnter | VK::Space => {
+                self.state.window_key_op = None;
+                self.send_action(TkAction::REDRAW);
+                return;
+            }
+            VK::Escape => {
+                if let Some(state) = self.state.window_key_op.take() {
+                    let offset = Offset::ZERO - state.offset;
+                    self.shell.resize_window(offset, state.start_size);
+                }
+                self.send_action(TkAction::REDRAW);
+                return;
+            }
+            _ => return,
+        };
+
+        let state = self.state.window_key_op.as_mut().unwrap();
+        let offset = match state.op {
+            WindowKeyOp::Move => {
+                state.offset += delta;
+                delta
+            }
+            WindowKeyOp::Resize => {
+                let size = &mut state.size;
+                *size = Size((size.0 + delta.0).max(1), (size.1 + delta.1).max(1));
+                Offset::ZERO
+            }
+        };
+        let size = state.size;
+        self.shell.resize_window(offset, size);
+    }
+
+    // Hit-test before dispatch of a primary mouse button press
+    //
+    // Returns true if the press was consumed by a window drag or resize.
+    fn window_hit_test<W: Widget + ?Sized>(&mut self, widget: &W, coord: Coord) -> bool {
+        if self.state.pointer_capture.is_some() || !self.state.popups.is_empty() {
+            return false;
+        }
+        let region = match self.state.hover.as_ref() {
+            Some(id) => match widget.find_widget(id) {
+                Some(w) => w.window_region(),
+                None => return false,
+            },
+            None => return false,
+        };
+        match region {
+            WindowRegion::Client => false,
+            WindowRegion::Drag => {
+                if self.state.last_click_repetitions > 1 {
+                    return false;
+                }
+                self.shell.drag_window();
+                true
+            }
+            WindowRegion::Resize(edge) => {
+                if self.state.last_click_repetitions > 1 {
+                    return false;
+                }
+                let size = self.shell.inner_size();
+                if size == Size::ZERO {
+                    return false;
+                }
+                trace!("EventMgr: start window resize from {:?}", edge);
+                self.state.window_resize = Some(WindowResizeState { edge, coord, size });
+                true
+            }
+        }
+    }
+
+    // Handle mouse motion during a window resize
+    fn window_resize_move(&mut self, coord: Coord) {
+        let state = self.state.window_resize.as_mut().unwrap();
+        let (sx, sy) = state.edge.signs();
+        let Offset(dx, dy) = coord - state.coord;
+        let size = &mut state.size;
+        let mut offset = Offset::ZERO;
+        // Moving the west/north edge moves the window origin, thus the mouse
+        // coordinate (relative to the window) of the edge remains unchanged.
+        if sx < 0 {
+            let dx = dx.min(size.0 - 1);
+            offset.0 = dx;
+            size.0 -= dx;
+        } else if sx > 0 {
+            size.0 = (size.0 + dx).max(1);
+            state.coord.0 = coord.0;
+        }
+        if sy < 0 {
+            let dy = dy.min(size.1 - 1);
+            offset.1 = dy;
+            size.1 -= dy;
+        } else if sy > 0 {
+            size.1 = (size.1 + dy).max(1);
+            state.coord.1 = coord.1;
+        }
+        let size = *size;
+        self.shell.resize_window(offset, size);
+    }
+
+    // Deliver a long press; if used, cancel any grab of the touch
+    fn long_press<W: Widget + ?Sized>(&mut self, widget: &mut W, state: LongPressState) {
+        let event = Event::LongPress(state.coord);
+        if !self.try_send_event(widget, state.id, event) {
+            return;
+        }
+        self.shell.haptic_feedback();
+        if let Some(grab) = self.remove_touch(state.touch_id) {
+            if grab.mode == GrabMode::Grab {
+                let event = Event::PressEnd {
+                    source: PressSource::Touch(grab.id),
+                    end_id: grab.cur_id.clone(),
+                    coord: grab.coord,
+                    success: false,
+                };
+                self.send_event(widget, grab.start_id, event);
+            }
+        }
+    }
+
     // Clears mouse grab and pan grab, resets cursor and redraws
     fn remove_mouse_grab(&mut self) -> Option<MouseGrab> {
         if let Some(grab) = self.state.mouse_grab.take() {
@@ -546,6 +1044,14 @@ impl<'a> EventMgr<'a> {
     fn send_event<W: Widget + ?Sized>(&mut self, widget: &mut W, id: WidgetId, event: Event) {
         trace!("Send to {}: {:?}", id, event);
         let _ = widget.send(self, id, event);
+        self.drop_unhandled_msgs();
+    }
+
+    // Discard messages not handled by any ancestor
+    fn drop_unhandled_msgs(&mut self) {
+        for (_, type_name) in self.messages.drain(..) {
+            debug!("EventMgr: unhandled message of type {}", type_name);
+        }
     }
 
     // Similar to send_event, but return true only if response != Response::Unused
@@ -557,6 +1063,7 @@ impl<'a> EventMgr<'a> {
     ) -> bool {
         trace!("Send to {}: {:?}", id, event);
         let r = widget.send(self, id, event);
+        self.drop_unhandled_msgs();
         !matches!(r, Response::Unused)
     }
 
@@ -571,9 +1078,10 @@ impl<'a> EventMgr<'a> {
             .map(|(wid, p, _)| (*wid, p.parent.clone()))
         {
             trace!("Send to popup parent: {}: {:?}", parent, event);
-            match widget.send(self, parent, event.clone()) {
-                Response::Unused => (),
-                _ => return,
+            let r = widget.send(self, parent, event.clone());
+            self.drop_unhandled_msgs();
+            if !matches!(r, Response::Unused) {
+                return;
             }
             self.close_window(wid, false);
         }
@@ -0,0 +1,254 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Event manager — compose-key and Unicode hex input
+
+/// Compose sequences: two input characters (in either order) and the result
+const SEQUENCES: &[(char, char, char)] = &[
+    ('`', 'a', 'à'),
+    ('`', 'e', 'è'),
+    ('`', 'i', 'ì'),
+    ('`', 'o', 'ò'),
+    ('`', 'u', 'ù'),
+    ('`', 'A', 'À'),
+    ('`', 'E', 'È'),
+    ('`', 'I', 'Ì'),
+    ('`', 'O', 'Ò'),
+    ('`', 'U', 'Ù'),
+    ('\'', 'a', 'á'),
+    ('\'', 'e', 'é'),
+    ('\'', 'i', 'í'),
+    ('\'', 'o', 'ó'),
+    ('\'', 'u', 'ú'),
+    ('\'', 'y', 'ý'),
+    ('\'', 'A', 'Á'),
+    ('\'', 'E', 'É'),
+    ('\'', 'I', 'Í'),
+    ('\'', 'O', 'Ó'),
+    ('\'', 'U', 'Ú'),
+    ('\'', 'Y', 'Ý'),
+    ('^', 'a', 'â'),
+    ('^', 'e', 'ê'),
+    ('^', 'i', 'î'),
+    ('^', 'o', 'ô'),
+    ('^', 'u', 'û'),
+    ('^', 'A', 'Â'),
+    ('^', 'E', 'Ê'),
+    ('^', 'I', 'Î'),
+    ('^', 'O', 'Ô'),
+    ('^', 'U', 'Û'),
+    ('^', '1', '¹'),
+    ('^', '2', '²'),
+    ('^', '3', '³'),
+    ('"', 'a', 'ä'),
+    ('"', 'e', 'ë'),
+    ('"', 'i', 'ï'),
+    ('"', 'o', 'ö'),
+    ('"', 'u', 'ü'),
+    ('"', 'y', 'ÿ'),
+    ('"', 'A', 'Ä'),
+    ('"', 'E', 'Ë'),
+    ('"', 'I', 'Ï'),
+    ('"', 'O', 'Ö'),
+    ('"', 'U', 'Ü'),
+    ('~', 'a', 'ã'),
+    ('~', 'n', 'ñ'),
+    ('~', 'o', 'õ'),
+    ('~', 'A', 'Ã'),
+    ('~', 'N', 'Ñ'),
+    ('~', 'O', 'Õ'),
+    (',', 'c', 'ç'),
+    (',', 'C', 'Ç'),
+    ('o', 'a', 'å'),
+    ('o', 'A', 'Å'),
+    ('/', 'o', 'ø'),
+    ('/', 'O', 'Ø'),
+    ('s', 's', 'ß'),
+    ('a', 'e', 'æ'),
+    ('A', 'E', 'Æ'),
+    ('o', 'e', 'œ'),
+    ('O', 'E', 'Œ'),
+    ('o', 'c', '©'),
+    ('o', 'r', '®'),
+    ('t', 'm', '™'),
+    ('o', 'o', '°'),
+    ('+', '-', '±'),
+    ('1', '2', '½'),
+    ('1', '4', '¼'),
+    ('3', '4', '¾'),
+    ('<', '<', '«'),
+    ('>', '>', '»'),
+    ('!', '!', '¡'),
+    ('?', '?', '¿'),
+    ('=', 'e', '€'),
+    ('-', 'L', '£'),
+    ('=', 'Y', '¥'),
+    ('|', 'c', '¢'),
+    ('m', 'u', 'µ'),
+    ('x', 'x', '×'),
+    (':', '-', '÷'),
+    ('.', '.', '…'),
+];
+
+/// Maximum number of hex digits (U+10FFFF)
+const MAX_HEX_DIGITS: usize = 6;
+
+/// In-progress text composition
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum Composer {
+    /// Compose key pressed; holds input so far
+    Compose(String),
+    /// Unicode hex input (Ctrl+Shift+U); holds digits so far
+    Hex(String),
+}
+
+/// Result of composer input
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum ComposeStep {
+    /// Composition continues
+    Pending,
+    /// Composition completed with this character
+    Commit(char),
+    /// Composition was abandoned (invalid input)
+    Cancel,
+}
+
+impl Composer {
+    /// Input a (non-control) character
+    pub fn push(&mut self, c: char) -> ComposeStep {
+        match self {
+            Composer::Compose(s) => {
+                let first = match s.chars().next() {
+                    None => {
+                        if SEQUENCES.iter().any(|(a, b, _)| *a == c || *b == c) {
+                            s.push(c);
+                            return ComposeStep::Pending;
+                        }
+                        return ComposeStep::Cancel;
+                    }
+                    Some(first) => first,
+                };
+                SEQUENCES
+                    .iter()
+                    .find(|(a, b, _)| (*a, *b) == (first, c) || (*a, *b) == (c, first))
+                    .map(|(_, _, r)| ComposeStep::Commit(*r))
+                    .unwrap_or(ComposeStep::Cancel)
+            }
+            Composer::Hex(s) => {
+                if c.is_ascii_hexdigit() && s.len() < MAX_HEX_DIGITS {
+                    s.push(c.to_ascii_lowercase());
+                    ComposeStep::Pending
+                } else if c == ' ' {
+                    self.finish()
+                } else {
+                    ComposeStep::Cancel
+                }
+            }
+        }
+    }
+
+    /// Remove the last input character
+    pub fn pop(&mut self) {
+        match self {
+            Composer::Compose(s) | Composer::Hex(s) => {
+                s.pop();
+            }
+        }
+    }
+
+    /// Complete composition (e.g. on Enter)
+    pub fn finish(&self) -> ComposeStep {
+        match self {
+            Composer::Compose(_) => ComposeStep::Cancel,
+            Composer::Hex(s) => u32::from_str_radix(s, 16)
+                .ok()
+                .and_then(char::from_u32)
+                .map(ComposeStep::Commit)
+                .unwrap_or(ComposeStep::Cancel),
+        }
+    }
+
+    /// Text describing composition state, e.g. `u1f60`
+    pub fn preedit(&self) -> String {
+        match self {
+            Composer::Compose(s) => format!("·{}", s),
+            Composer::Hex(s) => format!("u{}", s),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn compose_sequence() {
+        let mut composer = Composer::Compose(String::new());
+        assert_eq!(composer.preedit(), "·");
+        assert_eq!(composer.push('e'), ComposeStep::Pending);
+        assert_eq!(composer.preedit(), "·e");
+        assert_eq!(composer.push('\''), ComposeStep::Commit('é'));
+
+        // Either order is accepted
+        let mut composer = Composer::Compose(String::new());
+        assert_eq!(composer.push('\''), ComposeStep::Pending);
+        assert_eq!(composer.push('e'), ComposeStep::Commit('é'));
+    }
+
+    #[test]
+    fn compose_invalid() {
+        let mut composer = Composer::Compose(String::new());
+        assert_eq!(composer.push('#'), ComposeStep::Cancel);
+
+        let mut composer = Composer::Compose(String::new());
+        assert_eq!(composer.push('a'), ComposeStep::Pending);
+        assert_eq!(composer.push('q'), ComposeStep::Cancel);
+
+        let composer = Composer::Compose("a".to_string());
+        assert_eq!(composer.finish(), ComposeStep::Cancel);
+    }
+
+    #[test]
+    fn compose_pop() {
+        let mut composer = Composer::Compose(String::new());
+        assert_eq!(composer.push('a'), ComposeStep::Pending);
+        composer.pop();
+        assert_eq!(composer.preedit(), "·");
+        assert_eq!(composer.push('o'), ComposeStep::Pending);
+        assert_eq!(composer.push('c'), ComposeStep::Commit('©'));
+    }
+
+    #[test]
+    fn hex_input() {
+        let mut composer = Composer::Hex(String::new());
+        assert_eq!(composer.preedit(), "u");
+        for c in "1F60".chars() {
+            assert_eq!(composer.push(c), ComposeStep::Pending);
+        }
+        assert_eq!(composer.preedit(), "u1f60");
+        assert_eq!(composer.push('0'), ComposeStep::Pending);
+        assert_eq!(composer.finish(), ComposeStep::Commit('😀'));
+        assert_eq!(composer.push(' '), ComposeStep::Commit('😀'));
+
+        composer.pop();
+        assert_eq!(composer.preedit(), "u1f60");
+    }
+
+    #[test]
+    fn hex_invalid() {
+        let mut composer = Composer::Hex(String::new());
+        assert_eq!(composer.push('g'), ComposeStep::Cancel);
+
+        // Too many digits
+        let mut composer = Composer::Hex("10ffff".to_string());
+        assert_eq!(composer.push('0'), ComposeStep::Cancel);
+
+        // Not a valid char: surrogate, out of range, empty
+        for digits in ["d800", "110000", ""] {
+            let composer = Composer::Hex(digits.to_string());
+            assert_eq!(composer.finish(), ComposeStep::Cancel);
+        }
+    }
+}
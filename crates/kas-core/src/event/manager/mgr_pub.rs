@@ -11,12 +11,15 @@ use std::u16;
 
 use super::*;
 use crate::draw::DrawShared;
-use crate::geom::{Coord, Offset, Vec2};
+use crate::event::config::AccelLabels;
+use crate::geom::{Coord, Offset, Size, Vec2};
 use crate::layout::SetRectMgr;
+use crate::shell::Displays;
+use crate::text::AccelString;
 use crate::theme::{SizeMgr, ThemeControl};
-#[allow(unused)]
-use crate::WidgetConfig; // for doc-links
-use crate::{CoreData, TkAction, WidgetExt, WidgetId, WindowId};
+use crate::{
+    CoreData, TkAction, WidgetAction, WidgetConfig, WidgetExt, WidgetId, WindowFlags, WindowId,
+};
 
 impl<'a> std::ops::BitOrAssign<TkAction> for EventMgr<'a> {
     #[inline]
@@ -25,16 +28,29 @@ impl<'a> std::ops::BitOrAssign<TkAction> for EventMgr<'a> {
     }
 }
 
+impl<'a> std::ops::BitOrAssign<WidgetAction> for EventMgr<'a> {
+    #[inline]
+    fn bitor_assign(&mut self, action: WidgetAction) {
+        self.send_widget_action(action);
+    }
+}
+
 /// Public API (around event manager state)
 impl EventState {
     /// True when accelerator key labels should be shown
     ///
-    /// (True when Alt is held and no widget has character focus.)
+    /// This depends on the [`AccelLabels`] configuration; by default, this is
+    /// true when Alt is held and no widget has character focus. Windows are
+    /// redrawn (but not resized) when the result changes.
     ///
     /// This is a fast check.
     #[inline]
     pub fn show_accel_labels(&self) -> bool {
-        self.modifiers.alt() && !self.char_focus
+        match self.config.accel_labels() {
+            AccelLabels::Never => false,
+            AccelLabels::WithAlt => self.modifiers.alt() && !self.char_focus,
+            AccelLabels::Always => true,
+        }
     }
 
     /// Get whether this widget has `(char_focus, sel_focus)`
@@ -49,6 +65,17 @@ impl EventState {
         (sel_focus && self.char_focus, sel_focus)
     }
 
+    /// Get the state of in-progress text composition, if any
+    ///
+    /// While a compose sequence or Unicode hex input (see
+    /// [`WindowConfig::compose_input`], [`WindowConfig::unicode_hex_input`])
+    /// is in progress, the widget with character focus receives no
+    /// [`Event::ReceivedCharacter`] events. It may use this text (e.g. `u1f6`)
+    /// to display feedback.
+    pub fn composition(&self) -> Option<String> {
+        self.composer.as_ref().map(|c| c.preedit())
+    }
+
     /// Get whether this widget has keyboard navigation focus
     #[inline]
     pub fn has_nav_focus(&self, w_id: &WidgetId) -> bool {
@@ -111,6 +138,9 @@ impl EventState {
         }
         if self.has_nav_focus(&core.id) {
             state |= InputState::NAV_FOCUS;
+            if self.nav_focus_key {
+                state |= InputState::KEY_FOCUS;
+            }
         }
         if char_focus {
             state |= InputState::CHAR_FOCUS;
@@ -127,6 +157,15 @@ impl EventState {
         self.modifiers
     }
 
+    /// Get the last known mouse position
+    ///
+    /// This is the position of the most recent mouse-motion event received by
+    /// the window, in window coordinates.
+    #[inline]
+    pub fn mouse_coord(&self) -> Coord {
+        self.last_mouse_coord
+    }
+
     /// Access event-handling configuration
     #[inline]
     pub fn config(&self) -> &WindowConfig {
@@ -207,6 +246,22 @@ impl EventState {
         self.time_updates.sort_by(|a, b| b.0.cmp(&a.0)); // reverse sort
     }
 
+    /// Request a callback after the next frame is presented
+    ///
+    /// The widget `w_id` will receive [`Event::FrameComplete`] once, after the
+    /// next frame of this window has been presented. This also requests a
+    /// redraw (thus a frame is guaranteed).
+    ///
+    /// Unlike [`EventState::update_on_timer`], this allows animations to be
+    /// synchronised to the actual display refresh. To receive a callback on
+    /// every frame, request another callback when handling the event.
+    pub fn request_frame_callback(&mut self, w_id: WidgetId) {
+        if !self.frame_callbacks.contains(&w_id) {
+            self.frame_callbacks.push(w_id);
+        }
+        self.send_action(TkAction::REDRAW);
+    }
+
     /// Subscribe to an update handle
     ///
     /// All widgets subscribed to an update handle will be sent
@@ -226,6 +281,35 @@ impl EventState {
             .insert(w_id);
     }
 
+    /// Limit the rate of updates via an update handle
+    ///
+    /// Updates triggered on `handle` are delivered to subscribed widgets of
+    /// this window at most once per `min_interval`. Updates triggered sooner
+    /// are coalesced: only the last is delivered, once the interval has
+    /// elapsed. This is useful for data updated at high frequency (e.g.
+    /// streaming telemetry), where refreshing views on every update is
+    /// wasteful. A zero `min_interval` removes the limit.
+    ///
+    /// Independent of this, identical updates (same handle and payload)
+    /// triggered multiple times before the next frame are delivered once.
+    ///
+    /// This may be called from [`WidgetConfig::configure`].
+    pub fn throttle_updates(&mut self, handle: UpdateHandle, min_interval: Duration) {
+        if min_interval == Duration::ZERO {
+            self.update_throttles.remove(&handle);
+            return;
+        }
+        let throttle = self
+            .update_throttles
+            .entry(handle)
+            .or_insert_with(|| UpdateThrottle {
+                interval: min_interval,
+                last: None,
+                deferred: None,
+            });
+        throttle.interval = min_interval;
+    }
+
     /// Notify that a widget must be redrawn
     ///
     /// Currently the entire window is redrawn on any redraw request and the
@@ -251,6 +335,41 @@ impl EventState {
         self.action |= action;
     }
 
+    /// Notify that a [`TkAction`] should happen to a widget
+    ///
+    /// See [`TkAction::Widget`]. Calling `mgr.send_widget_action(action)` is
+    /// equivalent to `*mgr |= action`.
+    pub fn send_widget_action(&mut self, action: WidgetAction) {
+        let WidgetAction { id, mut kind } = action;
+        if kind.contains(TkAction::RECONFIGURE) {
+            kind.remove(TkAction::RECONFIGURE);
+            self.reconfigure_subtree(id.clone());
+        }
+        if kind.contains(TkAction::REDRAW) {
+            kind.remove(TkAction::REDRAW);
+            self.redraw(id);
+        }
+        self.send_action(kind);
+    }
+
+    /// Reconfigure the widget `id` and its descendants
+    ///
+    /// This is a cheaper alternative to [`TkAction::RECONFIGURE`] for use when
+    /// only part of the widget tree has changed (e.g. children were added to,
+    /// removed from or replaced within the widget `id`): other widgets keep
+    /// their identifiers and state. The widget `id` itself must still exist
+    /// (with the same identifier).
+    ///
+    /// Reconfiguration happens after event handling (and is followed by a
+    /// resize). It is skipped if a full reconfigure is pending.
+    pub fn reconfigure_subtree(&mut self, id: WidgetId) {
+        if self.reconfigure.iter().any(|r| r.is_ancestor_of(&id)) {
+            return;
+        }
+        self.reconfigure.retain(|r| !id.is_ancestor_of(r));
+        self.reconfigure.push(id);
+    }
+
     /// Attempts to set a fallback to receive [`Event::Command`]
     ///
     /// In case a navigation key is pressed (see [`Command`]) but no widget has
@@ -268,6 +387,25 @@ impl EventState {
         }
     }
 
+    /// Remove the navigation fallback if it is `id` or a descendant
+    ///
+    /// Pop-up widgets are configured alongside the rest of the window but
+    /// should not act as a fallback for the window (see
+    /// [`EventState::register_nav_fallback`]). The pop-up or its parent may
+    /// call this during configure to remove any fallback registered by the
+    /// pop-up's descendants.
+    pub fn remove_nav_fallback(&mut self, id: &WidgetId) {
+        if self
+            .nav_fallback
+            .as_ref()
+            .map(|fb| id.is_ancestor_of(fb))
+            .unwrap_or(false)
+        {
+            debug!("EventMgr: nav_fallback = None");
+            self.nav_fallback = None;
+        }
+    }
+
     fn accel_layer_for_id(&mut self, id: &WidgetId) -> Option<&mut AccelLayer> {
         let root = &WidgetId::ROOT;
         for (k, v) in self.accel_layers.range_mut(root..=id).rev() {
@@ -332,6 +470,116 @@ impl EventState {
         }
     }
 
+    /// Add accelerator keys and a label for a widget
+    ///
+    /// This is equivalent to [`EventState::add_accel_keys`] using the keys of
+    /// `string`, but also records the text of `string` as the label of widget
+    /// `id` (see [`EventState::accel_label`]).
+    ///
+    /// This should only be called from [`WidgetConfig::configure`].
+    pub fn add_accel_string(&mut self, id: &WidgetId, string: &AccelString) {
+        self.add_accel_keys(id, string.keys());
+        if !string.keys().is_empty() {
+            let label = string.text().to_string();
+            self.accel_labels.insert(id.clone(), label);
+        }
+    }
+
+    /// Get the label registered for widget `id`, if any
+    ///
+    /// See [`EventState::add_accel_string`].
+    pub fn accel_label(&self, id: &WidgetId) -> Option<&str> {
+        self.accel_labels.get(id).map(|s| s.as_str())
+    }
+
+    /// Request capture of the next key press
+    ///
+    /// The next key press (other than a modifier key) is sent to widget `id`
+    /// as [`Event::KeyCapture`] instead of being handled as usual. This may be
+    /// used e.g. to edit shortcut bindings. Only one widget may capture keys;
+    /// a new request replaces any previous request.
+    ///
+    /// While waiting, changes to the modifier state are reported via
+    /// [`Event::KeyCaptureModifiers`]. The request is cancelled when the
+    /// window loses focus, which is reported via [`Event::KeyCaptureCancelled`].
+    #[inline]
+    pub fn request_key_capture(&mut self, id: WidgetId) {
+        self.key_capture = Some(id);
+    }
+
+    /// Cancel a request to capture a key press
+    ///
+    /// Does nothing unless widget `id` has an outstanding request.
+    #[inline]
+    pub fn cancel_key_capture(&mut self, id: &WidgetId) {
+        if *id == self.key_capture {
+            self.key_capture = None;
+        }
+    }
+
+    /// Get the active keyboard window operation, if any
+    ///
+    /// See [`EventMgr::start_window_key_op`].
+    #[inline]
+    pub fn window_key_op(&self) -> Option<WindowKeyOp> {
+        self.window_key_op.as_ref().map(|state| state.op)
+    }
+
+    /// Get whether widget `id` is waiting to capture a key press
+    #[inline]
+    pub fn is_capturing_keys(&self, id: &WidgetId) -> bool {
+        *id == self.key_capture
+    }
+
+    /// Announce a change to widget `id`'s value
+    ///
+    /// Widgets representing a value (e.g. a slider) should call this when
+    /// the value is changed by user input, passing the new
+    /// [`WidgetConfig::value_description`]. The announcement may be read by
+    /// an accessibility layer via [`EventState::value_announcement`] and, if
+    /// enabled (see [`EventState::enable_value_tooltips`]), is shown briefly
+    /// in a tooltip beside the widget.
+    pub fn announce_value(&mut self, id: &WidgetId, description: String) {
+        let expiry = Instant::now() + VALUE_TOOLTIP_DURATION;
+        self.value_announcement = Some((id.clone(), description, expiry));
+        if self.value_tooltips {
+            self.send_action(TkAction::REDRAW);
+        }
+    }
+
+    /// Get the most recent value announcement, if any
+    ///
+    /// Returns the widget's identifier and the description passed to
+    /// [`EventState::announce_value`].
+    #[inline]
+    pub fn value_announcement(&self) -> Option<(&WidgetId, &str)> {
+        (self.value_announcement.as_ref()).map(|(id, desc, _)| (id, desc.as_str()))
+    }
+
+    /// Enable or disable value tooltips
+    ///
+    /// When enabled, values announced via [`EventState::announce_value`] are
+    /// shown briefly in a tooltip beside the widget. Drawing the tooltip is the
+    /// responsibility of the shell (see [`EventState::value_tooltip`]).
+    ///
+    /// This is disabled by default.
+    #[inline]
+    pub fn enable_value_tooltips(&mut self, enable: bool) {
+        self.value_tooltips = enable;
+    }
+
+    /// Get the value tooltip to draw, if any
+    ///
+    /// Returns the widget's identifier, the description and the time at which
+    /// the tooltip expires (after which it should no longer be drawn).
+    pub fn value_tooltip(&self) -> Option<(&WidgetId, &str, Instant)> {
+        let (id, desc, expiry) = self.value_announcement.as_ref()?;
+        if !self.value_tooltips || *expiry <= Instant::now() {
+            return None;
+        }
+        Some((id, desc.as_str(), *expiry))
+    }
+
     /// Request character-input focus
     ///
     /// Returns true on success or when the widget already had char focus.
@@ -429,6 +677,139 @@ impl EventState {
         self.nav_focus.as_ref()
     }
 
+    /// Get whether navigation focus was set via the keyboard
+    ///
+    /// This is false if there is no navigation focus or focus was set in
+    /// response to mouse or touch input (see [`EventState::set_nav_focus`]).
+    /// Widgets are drawn with [`InputState::KEY_FOCUS`] in this case.
+    ///
+    /// [`InputState::KEY_FOCUS`]: crate::theme::InputState::KEY_FOCUS
+    #[inline]
+    pub fn nav_focus_is_key(&self) -> bool {
+        self.nav_focus.is_some() && self.nav_focus_key
+    }
+
+    /// Get the widget capturing pointer input, if any
+    ///
+    /// See [`EventState::capture_pointer`].
+    #[inline]
+    pub fn pointer_capture(&self) -> Option<&WidgetId> {
+        self.pointer_capture.as_ref()
+    }
+
+    /// Capture pointer input
+    ///
+    /// While captured, all mouse and touch input is routed to widget `id`
+    /// regardless of the coordinate: the widget is considered hovered, and
+    /// receives [`Event::PressStart`] for any new press and [`Event::Scroll`]
+    /// for wheel input. Mouse motion is sent as [`Event::PressMove`] (with
+    /// `cur_id` being the widget under the cursor), as is leaving the window
+    /// (with `cur_id: None`).
+    ///
+    /// Unlike [`EventMgr::grab_press`], capture is not tied to the lifetime of
+    /// a press: it remains until released via [`EventState::release_pointer`]
+    /// or the widget is removed. Existing press grabs take precedence.
+    /// Capture by another widget replaces any existing capture.
+    pub fn capture_pointer(&mut self, id: WidgetId) {
+        debug!("EventMgr: pointer_capture = {}", id);
+        self.hover = Some(id.clone());
+        self.pointer_capture = Some(id);
+        self.send_action(TkAction::REDRAW);
+    }
+
+    /// Release pointer capture
+    ///
+    /// This does nothing unless `id` holds the capture.
+    /// Hover state is updated on the next mouse motion.
+    pub fn release_pointer(&mut self, id: &WidgetId) {
+        if self.pointer_capture.as_ref() == Some(id) {
+            debug!("EventMgr: pointer_capture = None");
+            self.pointer_capture = None;
+        }
+    }
+
+    /// Set the policy used to restore navigation focus after a reconfigure
+    ///
+    /// By default, [`NavFocusFallback::Nearest`] is used.
+    #[inline]
+    pub fn set_nav_focus_fallback(&mut self, fallback: NavFocusFallback) {
+        self.nav_focus_fallback = fallback;
+    }
+
+    /// Enable or disable handling of zoom commands
+    ///
+    /// When enabled, [`Command::ZoomIn`], [`Command::ZoomOut`] and
+    /// [`Command::ZoomReset`] not used by any widget scale the font size of
+    /// this window by a factor of [`WindowConfig::zoom_step`]. Zoom is stored
+    /// per window and does not affect the theme's font size (see
+    /// [`ThemeControl::set_font_size`]). Reset restores the theme's size.
+    ///
+    /// This is disabled by default.
+    #[inline]
+    pub fn enable_zoom(&mut self, enable: bool) {
+        self.zoom_enabled = enable;
+    }
+
+    /// Enable or disable the shortcut help overlay
+    ///
+    /// When enabled, [`Command::Help`] (by default F1 or Ctrl+?) not used by
+    /// any widget toggles visibility of an overlay listing active accelerator
+    /// keys and shortcuts (see [`EventState::accel_keys`] and
+    /// [`Shortcuts::iter`]). Escape closes the overlay. Drawing the overlay is
+    /// the responsibility of the shell.
+    ///
+    /// This is disabled by default.
+    ///
+    /// [`Shortcuts::iter`]: crate::event::config::Shortcuts::iter
+    #[inline]
+    pub fn enable_shortcut_help(&mut self, enable: bool) {
+        self.shortcut_help_enabled = enable;
+        if !enable {
+            self.shortcut_help = false;
+        }
+    }
+
+    /// True when the shortcut help overlay should be drawn
+    ///
+    /// See [`EventState::enable_shortcut_help`].
+    #[inline]
+    pub fn shortcut_help_visible(&self) -> bool {
+        self.shortcut_help
+    }
+
+    /// Show or hide the shortcut help overlay
+    ///
+    /// This has no effect unless enabled via
+    /// [`EventState::enable_shortcut_help`].
+    pub fn set_shortcut_help_visible(&mut self, visible: bool) {
+        let visible = visible && self.shortcut_help_enabled;
+        if visible != self.shortcut_help {
+            self.shortcut_help = visible;
+            self.send_action(TkAction::REDRAW);
+        }
+    }
+
+    /// Get the currently active accelerator keys
+    ///
+    /// Yields `(alt_required, key, id)` for each key, where `id` is the widget
+    /// activated by `key` and `alt_required` is false if the key's layer has
+    /// `alt_bypass` enabled (see [`EventState::new_accel_layer`]).
+    ///
+    /// Active layers are those belonging to the parents of open pop-ups and
+    /// the window's root layer. Layers are visited in order of priority (top
+    /// pop-up first); keys within a layer are in no particular order. A key
+    /// may be listed more than once, in which case the first has priority.
+    pub fn accel_keys(&self) -> impl Iterator<Item = (bool, VirtualKeyCode, &WidgetId)> + '_ {
+        let root = self.accel_layers.get(&WidgetId::ROOT);
+        (self.popups.iter().rev())
+            .filter_map(|(_, popup, _)| self.accel_layers.get(&popup.parent))
+            .chain(root)
+            .flat_map(|layer| {
+                let alt_required = !layer.0;
+                (layer.1.iter()).map(move |(vkey, id)| (alt_required, *vkey, id))
+            })
+    }
+
     /// Clear keyboard navigation focus
     pub fn clear_nav_focus(&mut self) {
         if let Some(id) = self.nav_focus.clone() {
@@ -451,7 +832,12 @@ impl EventState {
     /// be true if focussing in response to keyboard input, false if reacting to
     /// mouse or touch input.
     pub fn set_nav_focus(&mut self, id: WidgetId, key_focus: bool) {
-        if id != self.nav_focus {
+        if id == self.nav_focus && key_focus && !self.nav_focus_key {
+            // Focus was already here but is now (also) from the keyboard
+            self.nav_focus_key = true;
+            self.redraw(id);
+        } else if id != self.nav_focus {
+            self.nav_focus_key = key_focus;
             self.redraw(id.clone());
             if id != self.sel_focus {
                 self.clear_char_focus();
@@ -465,6 +851,40 @@ impl EventState {
 
 /// Public API (around toolkit and shell functionality)
 impl<'a> EventMgr<'a> {
+    /// Push a type-erased message onto the message stack
+    ///
+    /// The message may be handled by any ancestor (see
+    /// [`EventMgr::try_pop_msg`]). See also [type-erased
+    /// messages](EventMgr#type-erased-messages).
+    pub fn push_msg<M: 'static>(&mut self, msg: M) {
+        let type_name = std::any::type_name::<M>();
+        trace!("EventMgr::push_msg: {}", type_name);
+        self.messages.push((Box::new(msg), type_name));
+    }
+
+    /// True if the message stack is not empty
+    #[inline]
+    pub fn has_msg(&self) -> bool {
+        !self.messages.is_empty()
+    }
+
+    /// Try observing the last message on the stack without popping it
+    pub fn try_observe_msg<M: 'static>(&self) -> Option<&M> {
+        self.messages.last().and_then(|(msg, _)| msg.downcast_ref())
+    }
+
+    /// Try popping the last message from the stack, if of type `M`
+    ///
+    /// Returns `None` (leaving the stack unchanged) if the stack is empty or
+    /// the last message has a different type.
+    pub fn try_pop_msg<M: 'static>(&mut self) -> Option<M> {
+        if self.try_observe_msg::<M>().is_none() {
+            return None;
+        }
+        let (msg, _) = self.messages.pop().unwrap();
+        msg.downcast().ok().map(|msg| *msg)
+    }
+
     /// Add an overlay (pop-up)
     ///
     /// A pop-up is a box used for things like tool-tips and menus which is
@@ -485,6 +905,24 @@ impl<'a> EventMgr<'a> {
     #[inline]
     pub fn add_popup(&mut self, popup: crate::Popup) -> Option<WindowId> {
         trace!("Manager::add_popup({:?})", popup);
+        let opt_id = self.push_popup(popup);
+        self.clear_nav_focus();
+        opt_id
+    }
+
+    /// Add an overlay (pop-up) without taking focus
+    ///
+    /// This is identical to [`EventMgr::add_popup`] except that navigation
+    /// and character focus are not cleared. This is intended for pop-ups
+    /// controlled from their parent, such as a list of completions shown
+    /// below a text field while typing.
+    #[inline]
+    pub fn add_popup_keep_focus(&mut self, popup: crate::Popup) -> Option<WindowId> {
+        trace!("Manager::add_popup_keep_focus({:?})", popup);
+        self.push_popup(popup)
+    }
+
+    fn push_popup(&mut self, popup: crate::Popup) -> Option<WindowId> {
         let new_id = &popup.id;
         while let Some((_, popup, _)) = self.state.popups.last() {
             if popup.parent.is_ancestor_of(new_id) {
@@ -502,7 +940,6 @@ impl<'a> EventMgr<'a> {
                 .popups
                 .push((id, popup, self.state.nav_focus.clone()));
         }
-        self.clear_nav_focus();
         opt_id
     }
 
@@ -561,10 +998,29 @@ impl<'a> EventMgr<'a> {
         self.shell.close_window(id);
     }
 
+    /// Send an action to another window
+    ///
+    /// Actions sent via [`EventMgr::send_action`] (or `*mgr |= action`) apply
+    /// only to the current window. This method instead applies `action` to
+    /// window `id` (only), for example to redraw a window showing data
+    /// changed by the current window.
+    ///
+    /// [`TkAction::CLOSE`] closes window `id`, which may be a pop-up. Other
+    /// actions sent to a pop-up apply to its parent window.
+    /// [`TkAction::EXIT`] has its usual (global) effect.
+    #[inline]
+    pub fn send_action_to(&mut self, id: WindowId, action: TkAction) {
+        self.shell.send_action_to(id, action);
+    }
+
     /// Updates all subscribed widgets
     ///
     /// All widgets subscribed to the given [`UpdateHandle`], across all
     /// windows, will receive an update.
+    ///
+    /// Updates are delivered on the next frame; repeated calls with the same
+    /// `handle` and `payload` before then are coalesced. Rate limits may be
+    /// applied via [`EventState::throttle_updates`].
     #[inline]
     pub fn trigger_update(&mut self, handle: UpdateHandle, payload: u64) {
         debug!("trigger_update: handle={:?}, payload={}", handle, payload);
@@ -592,6 +1048,105 @@ impl<'a> EventMgr<'a> {
         self.shell.adjust_theme(&mut f);
     }
 
+    /// Save configuration
+    ///
+    /// Event configuration may be adjusted via [`WindowConfig::update_base`]
+    /// and theme configuration via [`EventMgr::adjust_theme`]. Changes are
+    /// usually saved on exit (depending on the shell's config mode); this
+    /// method saves immediately.
+    #[inline]
+    pub fn save_config(&mut self) {
+        self.shell.save_config();
+    }
+
+    /// Start moving the window with the mouse
+    ///
+    /// This is used to implement client-side decorations (see
+    /// [`crate::Decorations::Kas`]) and should only be called in response to
+    /// a mouse button press ([`Event::PressStart`]).
+    #[inline]
+    pub fn drag_window(&mut self) {
+        self.shell.drag_window();
+    }
+
+    /// Move and resize the window
+    ///
+    /// The window's position is adjusted by `offset` (where supported by the
+    /// platform) and its inner size set to `size`.
+    #[inline]
+    pub fn resize_window(&mut self, offset: Offset, size: Size) {
+        self.shell.resize_window(offset, size);
+    }
+
+    /// Get the window's inner size
+    ///
+    /// Units are physical pixels. Returns [`Size::ZERO`] if unknown.
+    #[inline]
+    pub fn window_size(&self) -> Size {
+        self.shell.inner_size()
+    }
+
+    /// Start moving or resizing the window via the keyboard
+    ///
+    /// Until the operation ends, key presses are not sent to widgets. Arrow
+    /// keys move the window (or its bottom-right corner, when resizing) by a
+    /// step, or by a single pixel with <kbd>Ctrl</kbd> held.
+    /// <kbd>Enter</kbd> or <kbd>Space</kbd> ends the operation while
+    /// <kbd>Escape</kbd> also restores the window's original position and size.
+    /// The shell should provide visual feedback (see
+    /// [`EventState::window_key_op`]).
+    ///
+    /// The window's current (inner) `size` must be given. This is usually
+    /// called by the window widget in response to [`Command::WindowMove`] or
+    /// [`Command::WindowResize`].
+    pub fn start_window_key_op(&mut self, op: WindowKeyOp, size: Size) {
+        self.state.window_key_op = Some(WindowKeyState {
+            op,
+            offset: Offset::ZERO,
+            start_size: size,
+            size,
+        });
+        self.send_action(TkAction::REDRAW);
+    }
+
+    /// Set whether the window is minimized
+    #[inline]
+    pub fn set_minimized(&mut self, minimized: bool) {
+        self.shell.set_minimized(minimized);
+    }
+
+    /// Set whether the window is maximized
+    #[inline]
+    pub fn set_maximized(&mut self, maximized: bool) {
+        self.shell.set_maximized(maximized);
+    }
+
+    /// Whether the window is maximized
+    #[inline]
+    pub fn is_maximized(&self) -> bool {
+        self.shell.is_maximized()
+    }
+
+    /// Enumerate displays (monitors)
+    ///
+    /// This returns a snapshot of available monitors, including resolution,
+    /// scale factor and work area, and which monitor shows the current
+    /// window. The list is empty if the shell does not support enumeration.
+    #[inline]
+    pub fn displays(&self) -> Displays {
+        self.shell.displays()
+    }
+
+    /// Set window level and behaviour flags
+    ///
+    /// Only [`WindowFlags::ALWAYS_ON_TOP`] may be changed at run-time; other
+    /// flags are only respected when the window is created (see
+    /// [`crate::Window::flags`]).
+    #[inline]
+    pub fn set_window_flags(&mut self, flags: WindowFlags) {
+        self.shell.set_window_flags(flags);
+    }
+
     /// Access a [`SizeMgr`]
     pub fn size_mgr<F: FnMut(SizeMgr) -> T, T>(&mut self, mut f: F) -> T {
         let mut result = None;
@@ -621,6 +1176,27 @@ impl<'a> EventMgr<'a> {
         result.expect("ShellWindow::draw_shared impl failed to call function argument")
     }
 
+    /// Render a widget to an image
+    ///
+    /// The `widget` is laid out within a rect of the given `size` (in physical
+    /// pixels) and drawn offscreen using the window's theme, for example to
+    /// generate a thumbnail. Returns the image size and RGBA (sRGB) data,
+    /// suitable for [`DrawShared::image_upload`], or `None` on failure.
+    ///
+    /// The `widget` must be configured; a detached widget may be configured via
+    /// [`SetRectMgr::configure`] (see [`Self::set_rect_mgr`]). Since this
+    /// assigns a new layout to `widget`, [`TkAction::RESIZE`] is sent to
+    /// restore the window's layout afterwards.
+    pub fn render_image(
+        &mut self,
+        widget: &mut dyn WidgetConfig,
+        size: Size,
+    ) -> Option<((u32, u32), Vec<u8>)> {
+        let result = self.shell.render_image(widget, size, self.state);
+        self.send_action(TkAction::RESIZE);
+        result
+    }
+
     /// Grab "press" events for `source` (a mouse or finger)
     ///
     /// When a "press" source is "grabbed", events for this source will be sent
@@ -859,6 +1435,7 @@ impl<'a> EventMgr<'a> {
 
         trace!("EventMgr: nav_focus = {:?}", opt_id);
         self.state.nav_focus = opt_id.clone();
+        self.state.nav_focus_key = key_focus;
 
         if let Some(id) = opt_id {
             if id != self.state.sel_focus {
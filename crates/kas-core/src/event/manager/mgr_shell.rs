@@ -8,19 +8,17 @@
 use log::*;
 use smallvec::SmallVec;
 use std::collections::HashMap;
-use std::time::{Duration, Instant};
+use std::time::Instant;
 
 use super::*;
-use crate::cast::Conv;
+use crate::cast::{Conv, ConvFloat};
+use crate::event::config::KeyRepeat;
 use crate::geom::{Coord, DVec2, Offset};
 use crate::layout::SetRectMgr;
 #[allow(unused)]
 use crate::WidgetConfig; // for doc-links
 use crate::{ShellWindow, TkAction, Widget, WidgetId};
 
-// TODO: this should be configurable or derived from the system
-const DOUBLE_CLICK_TIMEOUT: Duration = Duration::from_secs(1);
-
 const FAKE_MOUSE_BUTTON: MouseButton = MouseButton::Other(0);
 
 /// Shell API
@@ -39,23 +37,45 @@ impl EventState {
             char_focus: false,
             sel_focus: None,
             nav_focus: None,
+            nav_focus_key: false,
             nav_fallback: None,
+            nav_focus_fallback: Default::default(),
+            saved_focus: None,
+            zoom_enabled: false,
+            text_zoom: 1.0,
+            shortcut_help_enabled: false,
+            shortcut_help: false,
+            key_capture: None,
+            window_key_op: None,
+            window_resize: None,
+            value_tooltips: false,
+            value_announcement: None,
             hover: None,
             hover_icon: CursorIcon::Default,
             key_depress: Default::default(),
+            composer: None,
+            keys_down: Default::default(),
+            key_repeat_filtered: false,
             last_mouse_coord: Coord::ZERO,
             last_click_button: FAKE_MOUSE_BUTTON,
+            last_click_coord: Coord::ZERO,
             last_click_repetitions: 0,
             last_click_timeout: Instant::now(), // unimportant value
             mouse_grab: None,
+            pointer_capture: None,
             touch_grab: Default::default(),
+            long_press: None,
             pan_grab: SmallVec::new(),
             accel_layers: Default::default(),
+            accel_labels: Default::default(),
             popups: Default::default(),
             popup_removed: Default::default(),
             time_updates: vec![],
+            frame_callbacks: SmallVec::new(),
             handle_updates: HashMap::new(),
+            update_throttles: HashMap::new(),
             pending: SmallVec::new(),
+            reconfigure: SmallVec::new(),
             action: TkAction::empty(),
         }
     }
@@ -125,9 +145,11 @@ impl EventState {
     {
         debug!("EventMgr::configure");
         self.action.remove(TkAction::RECONFIGURE);
+        self.reconfigure.clear();
 
         // These are recreated during configure:
         self.accel_layers.clear();
+        self.accel_labels.clear();
         self.nav_fallback = None;
 
         self.new_accel_layer(WidgetId::ROOT, false);
@@ -137,6 +159,133 @@ impl EventState {
             Self::configure(&mut mgr, WidgetId::ROOT, widget.as_widget_mut());
         });
 
+        self.restore_focus(widget.as_widget());
+
+        let hover = widget.find_id(self.last_mouse_coord);
+        self.with(shell, |mgr| mgr.set_hover(widget, hover));
+    }
+
+    /// Restore focus and pointer capture after a reconfigure
+    ///
+    /// Sel focus and pointer capture are removed if their widget no longer
+    /// exists. Nav focus is restored according to [`NavFocusFallback`].
+    fn restore_focus(&mut self, widget: &dyn WidgetConfig) {
+        fn navigable(w: &dyn WidgetConfig) -> bool {
+            w.key_nav() && !w.is_disabled()
+        }
+
+        fn first_navigable(w: &dyn WidgetConfig) -> Option<WidgetId> {
+            if w.is_disabled() {
+                return None;
+            }
+            if w.key_nav() {
+                return Some(w.id());
+            }
+            (0..w.num_children())
+                .filter_map(|i| w.get_child(i))
+                .find_map(first_navigable)
+        }
+
+        if let Some(id) = self.pointer_capture.as_ref() {
+            if widget.find_widget(id).is_none() {
+                self.pointer_capture = None;
+            }
+        }
+
+        // Sel focus is kept only if the widget still exists
+        if let Some(id) = self.sel_focus.as_ref() {
+            if widget.find_widget(id).is_none() {
+                self.sel_focus = None;
+                self.char_focus = false;
+                self.composer = None;
+            }
+        }
+
+        let id = match self.nav_focus.clone() {
+            Some(id) => id,
+            None => return,
+        };
+
+        // Find the widget at path id, or the nearest existing ancestor:
+        let mut ancestors = vec![widget];
+        for index in id.iter_path() {
+            match ancestors.last().unwrap().get_child(index) {
+                Some(child) => ancestors.push(child),
+                None => break,
+            }
+        }
+
+        let target = ancestors.last().unwrap();
+        if *target.id_ref() == id && navigable(*target) {
+            trace!("EventMgr: restored nav_focus = {}", id);
+            return;
+        }
+
+        let new_id = match self.nav_focus_fallback {
+            NavFocusFallback::Clear => None,
+            NavFocusFallback::Nearest => ancestors[1..]
+                .iter()
+                .rev()
+                .find_map(|w| first_navigable(*w)),
+        };
+
+        match new_id {
+            Some(new_id) => {
+                trace!("EventMgr: nav_focus {} moved to {}", id, new_id);
+                self.nav_focus = None;
+                self.set_nav_focus(new_id, self.nav_focus_key);
+            }
+            None => self.clear_nav_focus(),
+        }
+    }
+
+    /// Reconfigure sub-trees queued by [`EventState::reconfigure_subtree`]
+    ///
+    /// This is called by [`EventState::update`] but may also be called by the
+    /// toolkit directly, e.g. before further event handling.
+    pub fn configure_subtrees<W>(&mut self, shell: &mut dyn ShellWindow, widget: &mut W)
+    where
+        W: Widget<Msg = VoidMsg> + ?Sized,
+    {
+        if self.reconfigure.is_empty() || self.action.contains(TkAction::RECONFIGURE) {
+            return;
+        }
+
+        for id in std::mem::take(&mut self.reconfigure) {
+            let child = match widget.find_widget_mut(&id) {
+                Some(child) => child,
+                None => {
+                    warn!("EventMgr::configure_subtrees: widget {} not found", id);
+                    continue;
+                }
+            };
+            debug!("EventMgr::configure_subtrees: {}", id);
+
+            // These are recreated during configure:
+            let layers: Vec<_> = self
+                .accel_layers
+                .keys()
+                .filter(|k| **k != WidgetId::ROOT && id.is_ancestor_of(k))
+                .cloned()
+                .collect();
+            for k in layers {
+                self.accel_layers.remove(&k);
+            }
+            for layer in self.accel_layers.values_mut() {
+                layer.1.retain(|_, v| !id.is_ancestor_of(v));
+            }
+            self.accel_labels.retain(|k, _| !id.is_ancestor_of(k));
+            self.remove_nav_fallback(&id);
+
+            shell.size_and_draw_shared(&mut |size_handle, draw_shared| {
+                let mut mgr = SetRectMgr::new(size_handle, draw_shared, self);
+                Self::configure(&mut mgr, id.clone(), &mut *child);
+            });
+        }
+
+        self.restore_focus(widget.as_widget());
+
+        self.send_action(TkAction::RESIZE);
         let hover = widget.find_id(self.last_mouse_coord);
         self.with(shell, |mgr| mgr.set_hover(widget, hover));
     }
@@ -161,7 +310,14 @@ impl EventState {
 
     /// Get the next resume time
     pub fn next_resume(&self) -> Option<Instant> {
-        self.time_updates.last().map(|time| time.0)
+        let throttles = self.update_throttles.values();
+        let time = self.time_updates.last().map(|time| time.0);
+        let long_press = self.long_press.as_ref().map(|lp| lp.time);
+        throttles
+            .filter_map(|throttle| throttle.resume_time())
+            .chain(time)
+            .chain(long_press)
+            .min()
     }
 
     /// Construct a [`EventMgr`] referring to this state
@@ -176,6 +332,7 @@ impl EventState {
             state: self,
             shell,
             action: TkAction::empty(),
+            messages: vec![],
         };
         f(&mut mgr);
         let action = mgr.action;
@@ -188,10 +345,13 @@ impl EventState {
     where
         W: Widget<Msg = VoidMsg> + ?Sized,
     {
+        self.configure_subtrees(shell, widget);
+
         let mut mgr = EventMgr {
             state: self,
             shell,
             action: TkAction::empty(),
+            messages: vec![],
         };
 
         while let Some((parent, wid)) = mgr.state.popup_removed.pop() {
@@ -259,6 +419,8 @@ impl EventState {
                 Pending::LostCharFocus(id) => (id, Event::LostCharFocus),
                 Pending::LostSelFocus(id) => (id, Event::LostSelFocus),
                 Pending::SetNavFocus(id, key_focus) => (id, Event::NavFocus(key_focus)),
+                Pending::MouseEnter(id) => (id, Event::MouseEnter),
+                Pending::MouseLeave(id) => (id, Event::MouseLeave),
             };
             mgr.send_event(widget, id, event);
         }
@@ -269,6 +431,50 @@ impl EventState {
     }
 }
 
+// Long-press recognition
+impl EventState {
+    // Track a new touch press, which may become a long press
+    //
+    // Only a lone touch may become a long press: a second concurrent touch
+    // cancels recognition.
+    fn long_press_start(&mut self, touch_id: u64, id: WidgetId, coord: Coord, now: Instant) {
+        let delay = self.config.long_press_delay();
+        self.long_press = match self.long_press.take() {
+            Some(_) => None,
+            None => delay.map(|delay| LongPressState {
+                touch_id,
+                id,
+                coord,
+                time: now + delay,
+            }),
+        };
+    }
+
+    // Cancel recognition if the touch moves beyond the pan threshold
+    fn long_press_move(&mut self, touch_id: u64, coord: Coord) {
+        if let Some(lp) = self.long_press.as_ref() {
+            if lp.touch_id == touch_id && self.config_test_pan_thresh(coord - lp.coord) {
+                self.long_press = None;
+            }
+        }
+    }
+
+    // Cancel recognition on release of the touch
+    fn long_press_end(&mut self, touch_id: u64) {
+        if self.long_press.as_ref().map(|lp| lp.touch_id) == Some(touch_id) {
+            self.long_press = None;
+        }
+    }
+
+    // Take the pending long press if due at time `now`
+    fn long_press_due(&mut self, now: Instant) -> Option<LongPressState> {
+        match self.long_press {
+            Some(ref lp) if lp.time <= now => self.long_press.take(),
+            _ => None,
+        }
+    }
+}
+
 /// Shell API
 #[cfg_attr(not(feature = "internal_doc"), doc(hidden))]
 #[cfg_attr(doc_cfg, doc(cfg(internal_doc)))]
@@ -277,6 +483,10 @@ impl<'a> EventMgr<'a> {
     pub fn update_timer<W: Widget + ?Sized>(&mut self, widget: &mut W) {
         let now = Instant::now();
 
+        if let Some(state) = self.state.long_press_due(now) {
+            self.long_press(widget, state);
+        }
+
         // assumption: time_updates are sorted in reverse order
         while !self.state.time_updates.is_empty() {
             if self.state.time_updates.last().unwrap().0 > now {
@@ -288,14 +498,61 @@ impl<'a> EventMgr<'a> {
         }
 
         self.state.time_updates.sort_by(|a, b| b.0.cmp(&a.0)); // reverse sort
+
+        // Deliver throttled updates which are now due
+        let mut due = SmallVec::<[(UpdateHandle, u64); 4]>::new();
+        for (handle, throttle) in self.state.update_throttles.iter_mut() {
+            if matches!(throttle.resume_time(), Some(time) if time <= now) {
+                throttle.last = Some(now);
+                due.extend(throttle.deferred.take().map(|payload| (*handle, payload)));
+            }
+        }
+        for (handle, payload) in due {
+            self.send_handle_update(widget, handle, payload);
+        }
+    }
+
+    /// Notify widgets that a frame has been presented
+    ///
+    /// This delivers [`Event::FrameComplete`] to widgets which requested a
+    /// frame callback; `time` is the time of presentation.
+    pub fn frame_complete<W: Widget + ?Sized>(&mut self, widget: &mut W, time: Instant) {
+        let callbacks = std::mem::take(&mut self.state.frame_callbacks);
+        for id in callbacks {
+            self.send_event(widget, id, Event::FrameComplete(time));
+        }
     }
 
     /// Update widgets due to handle
+    ///
+    /// If the handle is throttled (see [`EventState::throttle_updates`]), the
+    /// update may be deferred.
     pub fn update_handle<W: Widget + ?Sized>(
         &mut self,
         widget: &mut W,
         handle: UpdateHandle,
         payload: u64,
+    ) {
+        if let Some(throttle) = self.state.update_throttles.get_mut(&handle) {
+            let now = Instant::now();
+            if let Some(last) = throttle.last {
+                if now < last + throttle.interval {
+                    trace!("EventMgr::update_handle: deferring update on {:?}", handle);
+                    throttle.deferred = Some(payload);
+                    return;
+                }
+            }
+            throttle.last = Some(now);
+            throttle.deferred = None;
+        }
+        self.send_handle_update(widget, handle, payload);
+    }
+
+    fn send_handle_update<W: Widget + ?Sized>(
+        &mut self,
+        widget: &mut W,
+        handle: UpdateHandle,
+        payload: u64,
     ) {
         // NOTE: to avoid borrow conflict, we must clone values!
         if let Some(mut values) = self.state.handle_updates.get(&handle).cloned() {
@@ -330,22 +587,68 @@ impl<'a> EventMgr<'a> {
             HoveredFile(path) => ,
             HoveredFileCancelled => ,
             */
+            ReceivedCharacter(_) if self.state.key_repeat_filtered => (),
             ReceivedCharacter(c) => {
-                if let Some(id) = self.state.char_focus() {
+                // Without char focus, the owner of the top-most pop-up may
+                // use input (e.g. for type-ahead search).
+                let opt_id = self
+                    .state
+                    .char_focus()
+                    .or_else(|| self.state.popups.last().map(|(_, p, _)| p.parent.clone()));
+                if let Some(id) = opt_id {
                     // Filter out control codes (Unicode 5.11). These may be
                     // generated from combinations such as Ctrl+C by some other
                     // layer. We use our own shortcut system instead.
                     if c >= '\x20' && !('\x7f'..='\u{9f}').contains(&c) {
-                        let event = Event::ReceivedCharacter(c);
-                        self.send_event(widget, id, event);
+                        if let Some(composer) = self.state.composer.as_mut() {
+                            let step = composer.push(c);
+                            self.compose_step(widget, step);
+                        } else {
+                            let event = Event::ReceivedCharacter(c);
+                            self.send_event(widget, id, event);
+                        }
                     }
                 }
             }
             Focused(false) => {
+                // Releases are not reported while unfocused
+                self.state.keys_down.clear();
+                self.state.window_resize = None;
+                if self.state.modifiers.alt() {
+                    // E.g. after Alt+Tab: hide accelerator key labels
+                    let accel_labels = self.state.show_accel_labels();
+                    self.state.modifiers = ModifiersState::empty();
+                    if self.state.show_accel_labels() != accel_labels {
+                        self.state.send_action(TkAction::REDRAW);
+                    }
+                }
+                // Key presses cannot be captured while unfocused
+                if let Some(id) = self.state.key_capture.take() {
+                    self.send_event(widget, id, Event::KeyCaptureCancelled);
+                }
                 // Window focus lost: close all popups
                 while let Some(id) = self.state.popups.last().map(|(id, _, _)| *id) {
                     self.close_window(id, true);
                 }
+                // Remember navigation focus, to be restored with window focus
+                if let Some(id) = self.state.nav_focus.clone() {
+                    let char_focus = self.state.char_focus().is_some();
+                    self.state.saved_focus = Some((id, char_focus));
+                    self.clear_nav_focus();
+                }
+                self.send_event(widget, widget.id(), Event::WindowFocus(false));
+            }
+            Focused(true) => {
+                if let Some((id, char_focus)) = self.state.saved_focus.take() {
+                    // Skip if focus was set meanwhile or the widget was removed
+                    if self.state.nav_focus.is_none() && widget.find_widget(&id).is_some() {
+                        self.set_nav_focus(id.clone(), false);
+                        if char_focus {
+                            self.request_char_focus(id);
+                        }
+                    }
+                }
+                self.send_event(widget, widget.id(), Event::WindowFocus(true));
             }
             KeyboardInput {
                 input,
@@ -353,23 +656,54 @@ impl<'a> EventMgr<'a> {
                 ..
             } => {
                 if input.state == ElementState::Pressed && !is_synthetic {
-                    if let Some(vkey) = input.virtual_keycode {
+                    // The shell does not report repeats, but we may detect
+                    // these since no release occurs in between.
+                    let repeat = !self.state.keys_down.insert(input.scancode);
+                    let allow = !repeat || {
+                        let modifiers = self.state.modifiers;
+                        let is_command = input.virtual_keycode.map(|vkey| {
+                            let config = &self.state.config;
+                            config.shortcuts(|s| s.get(modifiers, vkey)).is_some()
+                        });
+                        let repeat = self.state.config.key_repeat();
+                        repeat.allows(is_command.unwrap_or(false))
+                    };
+                    // ReceivedCharacter follows; suppress if this is a repeat
+                    // and characters are not repeated.
+                    self.state.key_repeat_filtered =
+                        repeat && self.state.config.key_repeat() != KeyRepeat::Always;
+                    if let Some(vkey) = input.virtual_keycode.filter(|_| allow) {
                         self.start_key_event(widget, vkey, input.scancode);
                     }
                 } else if input.state == ElementState::Released {
+                    self.state.keys_down.remove(&input.scancode);
                     self.end_key_event(input.scancode);
                 }
             }
             ModifiersChanged(state) => {
-                if state.alt() != self.state.modifiers.alt() {
-                    // This controls drawing of accelerator key indicators
+                let accel_labels = self.state.show_accel_labels();
+                self.state.modifiers = state;
+                if self.state.show_accel_labels() != accel_labels {
                     self.state.send_action(TkAction::REDRAW);
                 }
-                self.state.modifiers = state;
+                if let Some(id) = self.state.key_capture.clone() {
+                    self.send_event(widget, id, Event::KeyCaptureModifiers(state));
+                }
             }
             CursorMoved { position, .. } => {
-                self.state.last_click_button = FAKE_MOUSE_BUTTON;
-                let coord = position.into();
+                let coord: Coord = position.into();
+                let click_delta = coord - self.state.last_click_coord;
+                let click_dist = click_delta.0.abs().max(click_delta.1.abs());
+                let thresh = self.state.config.double_click_dist_thresh();
+                if click_dist > i32::conv_nearest(thresh) {
+                    self.state.last_click_button = FAKE_MOUSE_BUTTON;
+                }
+
+                if self.state.window_resize.is_some() {
+                    self.window_resize_move(coord);
+                    self.state.last_mouse_coord = coord;
+                    return;
+                }
 
                 // Update hovered widget
                 let cur_id = widget.find_id(coord);
@@ -386,7 +720,11 @@ impl<'a> EventMgr<'a> {
                     {
                         pan.coords[usize::conv(grab.pan_grab.1)].1 = coord;
                     }
-                } else if let Some(id) = self.state.popups.last().map(|(_, p, _)| p.parent.clone())
+                } else if let Some(id) = self
+                    .state
+                    .pointer_capture
+                    .clone()
+                    .or_else(|| self.state.popups.last().map(|(_, p, _)| p.parent.clone()))
                 {
                     let source = PressSource::Mouse(FAKE_MOUSE_BUTTON, 0);
                     let event = Event::PressMove {
@@ -405,6 +743,8 @@ impl<'a> EventMgr<'a> {
             // CursorEntered { .. },
             CursorLeft { .. } => {
                 self.state.last_click_button = FAKE_MOUSE_BUTTON;
+                // The button release may not be reported
+                self.state.window_resize = None;
 
                 if self.mouse_grab().is_none() {
                     // If there's a mouse grab, we will continue to receive
@@ -412,6 +752,17 @@ impl<'a> EventMgr<'a> {
                     self.state.last_mouse_coord = Coord(-1, -1);
                     self.set_hover(widget, None);
                 }
+                if let Some(id) = self.state.pointer_capture.clone() {
+                    let source = PressSource::Mouse(FAKE_MOUSE_BUTTON, 0);
+                    let coord = self.state.last_mouse_coord;
+                    let event = Event::PressMove {
+                        source,
+                        cur_id: None,
+                        coord,
+                        delta: Offset::ZERO,
+                    };
+                    self.send_event(widget, id, event);
+                }
             }
             MouseWheel { delta, .. } => {
                 if let Some((id, event)) = self.mouse_grab().and_then(|g| g.flush_move()) {
@@ -420,13 +771,19 @@ impl<'a> EventMgr<'a> {
 
                 self.state.last_click_button = FAKE_MOUSE_BUTTON;
 
-                let event = Event::Scroll(match delta {
-                    MouseScrollDelta::LineDelta(x, y) => ScrollDelta::LineDelta(x, y),
+                let (x, y) = match delta {
+                    MouseScrollDelta::LineDelta(x, y) => (x, y),
                     MouseScrollDelta::PixelDelta(pos) => {
-                        // The delta is given as a PhysicalPosition, so we need
-                        // to convert to our vector type (Offset) here.
+                        // The delta is given as a PhysicalPosition
                         let coord = Coord::from(pos);
-                        ScrollDelta::PixelDelta(Offset(coord.0, coord.1))
+                        (f32::conv(coord.0), f32::conv(coord.1))
+                    }
+                };
+                let (x, y) = self.state.adjust_scroll(x, y);
+                let event = Event::Scroll(match delta {
+                    MouseScrollDelta::LineDelta(..) => ScrollDelta::LineDelta(x, y),
+                    MouseScrollDelta::PixelDelta(_) => {
+                        ScrollDelta::PixelDelta(Offset(i32::conv_nearest(x), i32::conv_nearest(y)))
                     }
                 });
                 if let Some(id) = self.state.hover.clone() {
@@ -440,6 +797,14 @@ impl<'a> EventMgr<'a> {
 
                 let coord = self.state.last_mouse_coord;
 
+                if self.state.window_resize.is_some() {
+                    // Any button release ends the resize
+                    if state == ElementState::Released {
+                        self.state.window_resize = None;
+                    }
+                    return;
+                }
+
                 if state == ElementState::Pressed {
                     let now = Instant::now();
                     if button != self.state.last_click_button || self.state.last_click_timeout < now
@@ -448,7 +813,9 @@ impl<'a> EventMgr<'a> {
                         self.state.last_click_repetitions = 0;
                     }
                     self.state.last_click_repetitions += 1;
-                    self.state.last_click_timeout = now + DOUBLE_CLICK_TIMEOUT;
+                    self.state.last_click_coord = coord;
+                    let timeout = self.state.config.double_click_timeout();
+                    self.state.last_click_timeout = now + timeout;
                 }
 
                 if let Some(grab) = self.remove_mouse_grab() {
@@ -466,6 +833,13 @@ impl<'a> EventMgr<'a> {
                     // Pan events do not receive Start/End notifications
                 }
 
+                if state == ElementState::Pressed
+                    && button == MouseButton::Left
+                    && self.window_hit_test(widget, coord)
+                {
+                    return;
+                }
+
                 if state == ElementState::Pressed {
                     if let Some(start_id) = self.state.hover.clone() {
                         // No mouse grab but have a hover target
@@ -494,7 +868,10 @@ impl<'a> EventMgr<'a> {
                 let coord = touch.location.into();
                 match touch.phase {
                     TouchPhase::Started => {
-                        let start_id = widget.find_id(coord);
+                        let start_id = match self.state.pointer_capture.clone() {
+                            Some(id) => Some(id),
+                            None => widget.find_id(coord),
+                        };
                         if let Some(id) = start_id.as_ref() {
                             if self.state.config.touch_nav_focus() {
                                 if let Some(w) = widget.find_widget(id) {
@@ -504,6 +881,10 @@ impl<'a> EventMgr<'a> {
                                 }
                             }
 
+                            let now = Instant::now();
+                            self.state
+                                .long_press_start(touch.id, id.clone(), coord, now);
+
                             let event = Event::PressStart {
                                 source,
                                 start_id: start_id.clone(),
@@ -515,6 +896,8 @@ impl<'a> EventMgr<'a> {
                     TouchPhase::Moved => {
                         let cur_id = widget.find_id(coord);
 
+                        self.state.long_press_move(touch.id, coord);
+
                         let mut redraw = false;
                         let mut pan_grab = None;
                         if let Some(grab) = self.get_touch(touch.id) {
@@ -543,6 +926,7 @@ impl<'a> EventMgr<'a> {
                         }
                     }
                     ev @ (TouchPhase::Ended | TouchPhase::Cancelled) => {
+                        self.state.long_press_end(touch.id);
                         if let Some(mut grab) = self.remove_touch(touch.id) {
                             if let Some((id, event)) = grab.flush_move() {
                                 self.send_event(widget, id, event);
@@ -565,3 +949,66 @@ impl<'a> EventMgr<'a> {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn new_state(long_press_ms: u32) -> EventState {
+        let mut config = Config::default();
+        config.long_press_ms = long_press_ms;
+        EventState::new(Rc::new(RefCell::new(config)), 1.0)
+    }
+
+    #[test]
+    fn long_press_timer() {
+        let mut state = new_state(500);
+        let id = WidgetId::ROOT.make_child(0);
+        let now = Instant::now();
+        state.long_press_start(1, id.clone(), Coord(10, 10), now);
+
+        let time = now + Duration::from_millis(500);
+        assert_eq!(state.next_resume(), Some(time));
+        assert!(state.long_press_due(now).is_none());
+
+        // Small movement (within the pan threshold) is allowed
+        state.long_press_move(1, Coord(11, 10));
+        // Movement and release of other touches is ignored
+        state.long_press_move(2, Coord(50, 50));
+        state.long_press_end(2);
+
+        let lp = state.long_press_due(time).unwrap();
+        assert_eq!((lp.touch_id, lp.id, lp.coord), (1, id, Coord(10, 10)));
+        assert!(state.long_press_due(time).is_none());
+        assert_eq!(state.next_resume(), None);
+    }
+
+    #[test]
+    fn long_press_cancel() {
+        let id = WidgetId::ROOT.make_child(0);
+        let now = Instant::now();
+        let later = now + Duration::from_secs(1);
+
+        // Movement beyond the pan threshold cancels
+        let mut state = new_state(500);
+        state.long_press_start(1, id.clone(), Coord(10, 10), now);
+        state.long_press_move(1, Coord(20, 10));
+        assert!(state.long_press_due(later).is_none());
+
+        // Release cancels
+        state.long_press_start(1, id.clone(), Coord(10, 10), now);
+        state.long_press_end(1);
+        assert!(state.long_press_due(later).is_none());
+
+        // A second touch cancels
+        state.long_press_start(1, id.clone(), Coord(10, 10), now);
+        state.long_press_start(2, id.clone(), Coord(30, 10), now);
+        assert!(state.long_press_due(later).is_none());
+        assert_eq!(state.next_resume(), None);
+
+        // Zero delay disables recognition
+        let mut state = new_state(0);
+        state.long_press_start(1, id, Coord(10, 10), now);
+        assert!(state.long_press_due(later).is_none());
+    }
+}
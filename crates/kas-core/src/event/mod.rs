@@ -89,7 +89,8 @@ pub use config::Config;
 pub use enums::{CursorIcon, ModifiersState, MouseButton, VirtualKeyCode};
 pub use events::*;
 pub use handler::{Handler, SendEvent};
-pub use manager::{EventMgr, EventState, GrabMode};
+pub use manager::{EventMgr, EventState, GrabMode, NavFocusFallback};
+pub use manager::{WindowEdge, WindowKeyOp, WindowRegion};
 pub use response::Response;
 pub use update::UpdateHandle;
 
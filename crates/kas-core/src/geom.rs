@@ -11,7 +11,7 @@ use crate::dir::Directional;
 use winit::dpi::{LogicalPosition, PhysicalPosition, PhysicalSize, Pixel};
 
 mod vector;
-pub use vector::{DVec2, Quad, Vec2, Vec3};
+pub use vector::{DVec2, PixelAlign, Quad, Vec2, Vec3};
 
 macro_rules! impl_common {
     ($T:ty) => {
@@ -131,6 +131,83 @@ impl From<Rect> for Quad {
     }
 }
 
+/// Alignment of drawn geometry to physical pixels
+///
+/// All geometry passed to draw operations uses physical pixels, but lengths
+/// derived from sizes in virtual pixels (e.g. a frame thickness of `2.4 *
+/// scale_factor`) are usually fractional at scale factors like 1.25 or 1.5.
+/// Edges at fractional positions are anti-aliased across two pixels, thus
+/// appear blurry. This type rounds lengths and edges to whole pixels.
+///
+/// Obtain via [`SizeMgr::pixel_align`](crate::theme::SizeMgr::pixel_align)
+/// or [`DrawMgr::pixel_align`](crate::theme::DrawMgr::pixel_align).
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PixelAlign {
+    scale_factor: f32,
+}
+
+impl PixelAlign {
+    /// Construct for the given scale factor
+    #[inline]
+    pub fn new(scale_factor: f32) -> Self {
+        PixelAlign { scale_factor }
+    }
+
+    /// Get the scale factor
+    #[inline]
+    pub fn scale_factor(self) -> f32 {
+        self.scale_factor
+    }
+
+    /// Round a length in physical pixels to whole pixels
+    ///
+    /// Positive lengths are rounded to at least one pixel, thus thin lines
+    /// never disappear; other lengths are rounded to zero.
+    #[inline]
+    pub fn length(self, len: f32) -> f32 {
+        if len > 0.0 {
+            len.round().max(1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// Convert a length in virtual pixels to whole physical pixels
+    ///
+    /// This is [`Self::length`] applied after scaling by the scale factor.
+    #[inline]
+    pub fn length_from_virtual(self, len: f32) -> f32 {
+        self.length(len * self.scale_factor)
+    }
+
+    /// Round a coordinate to the nearest pixel edge
+    #[inline]
+    pub fn coord(self, v: Vec2) -> Vec2 {
+        v.round()
+    }
+
+    /// Round the corners of a quad to the nearest pixel edges
+    #[inline]
+    pub fn quad(self, quad: Quad) -> Quad {
+        Quad::from_coords(self.coord(quad.a), self.coord(quad.b))
+    }
+
+    /// Round the corners of a quad to pixel edges, yielding a [`Rect`]
+    #[inline]
+    pub fn rect(self, quad: Quad) -> Rect {
+        let quad = self.quad(quad);
+        Rect::new(quad.a.into(), quad.size().into())
+    }
+
+    /// Shrink `quad` by `value` rounded to whole pixels (see [`Self::length`])
+    ///
+    /// If `quad` is pixel-aligned, the result is also pixel-aligned.
+    #[inline]
+    pub fn shrink(self, quad: Quad, value: f32) -> Quad {
+        quad.shrink(self.length(value))
+    }
+}
+
 /// 2D vector
 ///
 /// Usually used as either a coordinate or a difference of coordinates, but
@@ -522,3 +599,35 @@ impl Vec3 {
         Vec3(v.0, v.1, z)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn pixel_align_length() {
+        let px = PixelAlign::new(1.25);
+        assert_eq!(px.length(0.0), 0.0);
+        assert_eq!(px.length(-1.5), 0.0);
+        assert_eq!(px.length(0.2), 1.0);
+        assert_eq!(px.length(1.4), 1.0);
+        assert_eq!(px.length(1.6), 2.0);
+        assert_eq!(px.length_from_virtual(0.0), 0.0);
+        assert_eq!(px.length_from_virtual(0.5), 1.0);
+        assert_eq!(px.length_from_virtual(2.0), 3.0);
+        assert_eq!(px.length_from_virtual(2.4), 3.0);
+    }
+
+    #[test]
+    fn pixel_align_quad() {
+        let px = PixelAlign::new(1.5);
+        let quad = Quad::from_coords(Vec2(0.4, 1.6), Vec2(10.5, 20.2));
+        let aligned = px.quad(quad);
+        assert_eq!(aligned, Quad::from_coords(Vec2(0.0, 2.0), Vec2(11.0, 20.0)));
+        assert_eq!(px.rect(quad), Rect::new(Coord(0, 2), Size(11, 18)));
+
+        let inner = px.shrink(aligned, 1.5);
+        assert_eq!(inner, Quad::from_coords(Vec2(2.0, 4.0), Vec2(9.0, 18.0)));
+        assert_eq!(px.quad(inner), inner);
+    }
+}
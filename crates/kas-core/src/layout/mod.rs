@@ -37,6 +37,7 @@
 
 mod align;
 mod grid_solver;
+pub mod profile;
 mod row_solver;
 mod single_solver;
 mod size_rules;
@@ -60,7 +61,7 @@ pub use size_rules::SizeRules;
 pub use size_types::{
     AspectScaling, FrameRules, MarginSelector, Margins, SpriteDisplay, SpriteScaling, Stretch,
 };
-pub use sizer::{solve_size_rules, RulesSetter, RulesSolver, SolveCache};
+pub use sizer::{solve_size_rules, RulesCache, RulesSetter, RulesSolver, SolveCache};
 pub use storage::{
     DynGridStorage, DynRowStorage, FixedGridStorage, FixedRowStorage, GridStorage, RowStorage,
     RowTemp, Storage,
@@ -195,6 +196,17 @@ impl<'a> SetRectMgr<'a> {
     pub fn configure(&mut self, id: WidgetId, widget: &mut dyn WidgetConfig) {
         EventState::configure(self, id, widget);
     }
+
+    /// Configure a root widget
+    ///
+    /// This assigns the identifier used for the root of a window. Shells may
+    /// use this to configure a widget tree outside of a window.
+    #[cfg_attr(not(feature = "internal_doc"), doc(hidden))]
+    #[cfg_attr(doc_cfg, doc(cfg(internal_doc)))]
+    #[inline]
+    pub fn configure_root(&mut self, widget: &mut dyn WidgetConfig) {
+        EventState::configure(self, WidgetId::ROOT, widget);
+    }
 }
 
 impl<'a> std::ops::BitOrAssign<TkAction> for SetRectMgr<'a> {
@@ -0,0 +1,238 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Layout profiling
+//!
+//! This is an opt-in profiler for the layout system. When enabled (see
+//! [`set_enabled`]), statistics are collected on each thread:
+//!
+//! -   calls to [`Layout::size_rules`] and [`Layout::set_rect`] and time spent
+//!     in each, per widget (inclusive of the widget's subtree)
+//! -   the number of solver runs ([`SolveCache::find_constraints`] and
+//!     [`SolveCache::apply_rect`]) and how often cached size rules could be
+//!     reused (see [`RulesCache`])
+//!
+//! Only children laid out via the [`Layout::layout`] visitor are profiled
+//! individually; widgets calling child methods directly are included in the
+//! parent's time.
+//!
+//! Statistics may be retrieved via [`take_stats`] and reported to the log via
+//! [`LayoutStats::log_report`]. While enabled, a summary is also logged after
+//! each solver run (target `kas_perf`, level `debug`).
+//!
+//! [`Layout::size_rules`]: crate::Layout::size_rules
+//! [`Layout::set_rect`]: crate::Layout::set_rect
+//! [`Layout::layout`]: crate::Layout::layout
+//! [`RulesCache`]: super::RulesCache
+//! [`SolveCache::find_constraints`]: super::SolveCache::find_constraints
+//! [`SolveCache::apply_rect`]: super::SolveCache::apply_rect
+
+use super::{AlignHints, AxisInfo, SetRectMgr, SizeRules};
+use crate::geom::Rect;
+use crate::theme::SizeMgr;
+use crate::{WidgetConfig, WidgetId};
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+thread_local! {
+    static ENABLED: Cell<bool> = Cell::new(false);
+    static STATS: RefCell<LayoutStats> = RefCell::new(LayoutStats::default());
+}
+
+/// Enable or disable layout profiling (on the current thread)
+///
+/// Statistics are not reset by this method; see [`take_stats`].
+pub fn set_enabled(enabled: bool) {
+    ENABLED.with(|e| e.set(enabled));
+}
+
+/// True if layout profiling is enabled (on the current thread)
+#[inline]
+pub fn is_enabled() -> bool {
+    ENABLED.with(|e| e.get())
+}
+
+/// Take statistics collected so far, resetting them
+pub fn take_stats() -> LayoutStats {
+    STATS.with(|s| std::mem::take(&mut *s.borrow_mut()))
+}
+
+/// Per-widget layout statistics
+///
+/// Times are inclusive of the widget's subtree.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct WidgetStats {
+    /// Number of calls to `size_rules`
+    pub size_rules_calls: u32,
+    /// Total time spent in `size_rules`
+    pub size_rules_time: Duration,
+    /// Number of calls to `set_rect`
+    pub set_rect_calls: u32,
+    /// Total time spent in `set_rect`
+    pub set_rect_time: Duration,
+}
+
+impl WidgetStats {
+    /// Total time spent in layout methods
+    pub fn total_time(&self) -> Duration {
+        self.size_rules_time + self.set_rect_time
+    }
+}
+
+/// Layout statistics
+#[derive(Clone, Debug, Default)]
+pub struct LayoutStats {
+    /// Number of solver runs
+    pub solver_runs: u32,
+    /// Number of [`super::RulesCache`] lookups able to reuse cached rules
+    pub cache_hits: u32,
+    /// Number of [`super::RulesCache`] lookups requiring recomputation
+    pub cache_misses: u32,
+    /// Total time spent in solver runs
+    pub solver_time: Duration,
+    /// Per-widget statistics
+    pub widgets: HashMap<WidgetId, WidgetStats>,
+}
+
+impl LayoutStats {
+    /// Fraction of cache lookups able to reuse cached size rules
+    ///
+    /// Returns `None` if no cache lookups occurred.
+    pub fn cache_hit_rate(&self) -> Option<f32> {
+        let total = self.cache_hits + self.cache_misses;
+        if total == 0 {
+            return None;
+        }
+        Some(self.cache_hits as f32 / total as f32)
+    }
+
+    /// Total number of `size_rules` calls over all widgets
+    pub fn size_rules_calls(&self) -> u64 {
+        self.widgets
+            .values()
+            .map(|w| u64::from(w.size_rules_calls))
+            .sum()
+    }
+
+    /// Total number of `set_rect` calls over all widgets
+    pub fn set_rect_calls(&self) -> u64 {
+        self.widgets
+            .values()
+            .map(|w| u64::from(w.set_rect_calls))
+            .sum()
+    }
+
+    /// Get the `n` widgets with largest total time, in descending order
+    pub fn slowest(&self, n: usize) -> Vec<(&WidgetId, &WidgetStats)> {
+        let mut list: Vec<_> = self.widgets.iter().collect();
+        list.sort_by(|a, b| b.1.total_time().cmp(&a.1.total_time()));
+        list.truncate(n);
+        list
+    }
+
+    /// Write a report to the log (target `kas_perf`, level `info`)
+    ///
+    /// Includes the `n` slowest widgets.
+    pub fn log_report(&self, n: usize) {
+        log::info!(
+            target: "kas_perf",
+            "layout: {} solver runs in {}ms; cache hit rate: {}; {} size_rules and {} set_rect calls",
+            self.solver_runs,
+            self.solver_time.as_millis(),
+            match self.cache_hit_rate() {
+                Some(rate) => format!("{:.0}%", rate * 100.0),
+                None => "n/a".to_string(),
+            },
+            self.size_rules_calls(),
+            self.set_rect_calls(),
+        );
+        for (id, stats) in self.slowest(n) {
+            log::info!(
+                target: "kas_perf",
+                "layout: {}: size_rules {}× {}µs, set_rect {}× {}µs",
+                id,
+                stats.size_rules_calls,
+                stats.size_rules_time.as_micros(),
+                stats.set_rect_calls,
+                stats.set_rect_time.as_micros(),
+            );
+        }
+    }
+}
+
+fn with_widget<F: FnOnce(&mut WidgetStats)>(id: WidgetId, f: F) {
+    STATS.with(|s| f(s.borrow_mut().widgets.entry(id).or_default()));
+}
+
+/// Call `widget.size_rules`, profiling if enabled
+#[inline]
+pub(crate) fn size_rules(widget: &mut dyn WidgetConfig, mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
+    if !is_enabled() {
+        return widget.size_rules(mgr, axis);
+    }
+    let start = Instant::now();
+    let rules = widget.size_rules(mgr, axis);
+    let elapsed = start.elapsed();
+    with_widget(widget.id(), |s| {
+        s.size_rules_calls += 1;
+        s.size_rules_time += elapsed;
+    });
+    rules
+}
+
+/// Call `widget.set_rect`, profiling if enabled
+#[inline]
+pub(crate) fn set_rect(
+    widget: &mut dyn WidgetConfig,
+    mgr: &mut SetRectMgr,
+    rect: Rect,
+    align: AlignHints,
+) {
+    if !is_enabled() {
+        return widget.set_rect(mgr, rect, align);
+    }
+    let start = Instant::now();
+    widget.set_rect(mgr, rect, align);
+    let elapsed = start.elapsed();
+    with_widget(widget.id(), |s| {
+        s.set_rect_calls += 1;
+        s.set_rect_time += elapsed;
+    });
+}
+
+/// Record a [`super::RulesCache`] lookup
+pub(crate) fn cache_lookup(hit: bool) {
+    if !is_enabled() {
+        return;
+    }
+    STATS.with(|s| {
+        let mut s = s.borrow_mut();
+        match hit {
+            true => s.cache_hits += 1,
+            false => s.cache_misses += 1,
+        }
+    });
+}
+
+/// Record a solver run
+pub(crate) fn solver_run(elapsed: Duration) {
+    if !is_enabled() {
+        return;
+    }
+    STATS.with(|s| {
+        let mut s = s.borrow_mut();
+        s.solver_runs += 1;
+        s.solver_time += elapsed;
+        log::debug!(
+            target: "kas_perf",
+            "layout: {} solver runs in {}ms, {} cache hits, {} misses",
+            s.solver_runs,
+            s.solver_time.as_millis(),
+            s.cache_hits,
+            s.cache_misses,
+        );
+    });
+}
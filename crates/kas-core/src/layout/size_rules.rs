@@ -240,6 +240,12 @@ impl SizeRules {
         self.stretch = stretch;
     }
 
+    /// Set the `(pre, post)` margin sizes
+    #[inline]
+    pub fn set_margins(&mut self, margins: (u16, u16)) {
+        self.m = margins;
+    }
+
     /// Set margins to max of own margins and given margins
     pub fn include_margins(&mut self, margins: (u16, u16)) {
         self.m.0 = self.m.0.max(margins.0);
@@ -8,7 +8,7 @@
 use super::{Align, AlignHints, AxisInfo, SizeRules};
 use crate::cast::{Cast, CastFloat, Conv, ConvFloat};
 use crate::dir::Directional;
-use crate::geom::{Rect, Size, Vec2};
+use crate::geom::{Offset, Rect, Size, Vec2};
 
 // for doc use
 #[allow(unused)]
@@ -168,14 +168,23 @@ impl Default for SpriteScaling {
 }
 
 /// Scaling of image sprite within allocation
+///
+/// These correspond to the CSS `object-fit` modes `none`, `contain`, `fill`
+/// and `cover` respectively.
 #[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash)]
 pub enum AspectScaling {
     /// Align sprite within available space without further scaling
     None,
-    /// Scale sprite to available space with fixed aspect ratio
+    /// Scale sprite to fit available space with fixed aspect ratio
     Fixed,
-    /// Scale sprite freely
+    /// Scale sprite freely, filling available space
     Free,
+    /// Scale sprite to cover available space with fixed aspect ratio
+    ///
+    /// The sprite may exceed its allocation on one axis; it should be drawn
+    /// within a clip region of the allocated rect (see
+    /// [`SpriteDisplay::sprite_rect`]).
+    Cover,
     // TODO: we could add repeat (tile) and mirrored repeat modes here
 }
 
@@ -214,13 +223,32 @@ impl SpriteDisplay {
             SpriteScaling::Integer => i32::conv_nearest(mgr.scale_factor()) * size,
             SpriteScaling::Real => (mgr.scale_factor() * f32::conv(size)).cast_nearest(),
         };
-        SizeRules::new(size, size, margins, self.stretch)
+        // A covering sprite is clipped, thus may be allocated less space
+        let min = match self.aspect {
+            AspectScaling::Cover => 0,
+            _ => size,
+        };
+        SizeRules::new(min, size, margins, self.stretch)
     }
 
     /// Aligns `rect` according to stretch policy
     ///
-    /// Assign the result to `self.core_data_mut().rect`.
+    /// Assign the result to `self.core_data_mut().rect`. With
+    /// [`AspectScaling::Cover`] this is the whole of `rect`; use
+    /// [`Self::sprite_rect`] to get the rect in which to draw the sprite.
     pub fn align_rect(&mut self, rect: Rect, align: AlignHints) -> Rect {
+        match self.aspect {
+            AspectScaling::Cover => rect,
+            _ => self.sprite_rect(rect, align),
+        }
+    }
+
+    /// Get the rect in which to draw the sprite, given allocation `rect`
+    ///
+    /// This is identical to [`Self::align_rect`] except in the case of
+    /// [`AspectScaling::Cover`], where the result may exceed `rect` on one
+    /// axis (thus drawing should be clipped to `rect`).
+    pub fn sprite_rect(&self, rect: Rect, align: AlignHints) -> Rect {
         let ideal = match self.aspect {
             AspectScaling::None => self.size,
             AspectScaling::Fixed => {
@@ -237,6 +265,29 @@ impl SpriteDisplay {
                 }
             }
             AspectScaling::Free => rect.size,
+            AspectScaling::Cover => {
+                let size = Vec2::from(self.size);
+                let ratio = Vec2::from(rect.size) / size;
+                // Use larger ratio, provided that it is finite
+                let ideal = if ratio.0 > ratio.1 && ratio.0.is_finite() {
+                    Size(rect.size.0, i32::conv_nearest(ratio.0 * size.1))
+                } else if ratio.1 > ratio.0 && ratio.1.is_finite() {
+                    Size(i32::conv_nearest(ratio.1 * size.0), rect.size.1)
+                } else {
+                    rect.size
+                };
+
+                // Align the overflowing axis; aligned_rect only handles excess space
+                let (halign, valign) = align.unwrap_or(Align::Center, Align::Center);
+                let offset = |align, excess: i32| match align {
+                    Align::Center => excess / 2,
+                    Align::BR => excess,
+                    Align::Default | Align::TL | Align::Stretch => 0,
+                };
+                let x = offset(halign, rect.size.0 - ideal.0);
+                let y = offset(valign, rect.size.1 - ideal.1);
+                return Rect::new(rect.pos + Offset(x, y), ideal);
+            }
         };
         align
             .complete(Align::Center, Align::Center)
@@ -8,7 +8,7 @@
 use log::trace;
 use std::fmt;
 
-use super::{AlignHints, AxisInfo, Margins, SetRectMgr, SizeRules};
+use super::{profile, AlignHints, AxisInfo, Margins, SetRectMgr, SizeRules};
 use crate::geom::{Rect, Size};
 use crate::theme::SizeMgr;
 use crate::{Widget, WidgetConfig};
@@ -84,6 +84,58 @@ pub fn solve_size_rules<W: Widget>(
     widget.size_rules(size_mgr.re(), AxisInfo::new(true, x_size));
 }
 
+/// A cache of [`SizeRules`] keyed by axis and the size of the other axis
+///
+/// Only the most recent result for each axis is kept: widgets may store state
+/// from their last call to `size_rules` (e.g. the rules of each child), and
+/// reusing an older result would leave this state inconsistent. Since the
+/// vertical rules may depend on the horizontal solution, recomputing the
+/// horizontal rules drops any cached vertical rules.
+///
+/// Cached rules remain valid until [`RulesCache::invalidate`] is called, which
+/// must happen whenever the widget's size requirements may have changed.
+#[derive(Clone, Debug, Default)]
+pub struct RulesCache {
+    entries: [Option<(Option<i32>, SizeRules)>; 2],
+}
+
+impl RulesCache {
+    /// Get cached rules for `axis`, if any
+    pub fn get(&self, axis: AxisInfo) -> Option<SizeRules> {
+        match self.entries[usize::from(axis.is_vertical())] {
+            Some((other, rules)) if other == axis.other() => Some(rules),
+            _ => None,
+        }
+    }
+
+    /// Insert rules for `axis`
+    pub fn insert(&mut self, axis: AxisInfo, rules: SizeRules) {
+        if axis.is_horizontal() {
+            self.entries[1] = None;
+        }
+        self.entries[usize::from(axis.is_vertical())] = Some((axis.other(), rules));
+    }
+
+    /// Get cached rules for `axis` or compute and insert using `f`
+    pub fn get_or_insert_with<F>(&mut self, axis: AxisInfo, f: F) -> SizeRules
+    where
+        F: FnOnce(AxisInfo) -> SizeRules,
+    {
+        let cached = self.get(axis);
+        profile::cache_lookup(cached.is_some());
+        cached.unwrap_or_else(|| {
+            let rules = f(axis);
+            self.insert(axis, rules);
+            rules
+        })
+    }
+
+    /// Invalidate all cached rules
+    pub fn invalidate(&mut self) {
+        self.entries = [None, None];
+    }
+}
+
 /// Size solver
 ///
 /// This struct is used to solve widget layout, read size constraints and
@@ -100,8 +152,7 @@ pub struct SolveCache {
     min: Size,
     ideal: Size,
     margins: Margins,
-    refresh_rules: bool,
-    last_width: i32,
+    rules: RulesCache,
 }
 
 impl SolveCache {
@@ -136,28 +187,30 @@ impl SolveCache {
     pub fn find_constraints(widget: &mut dyn WidgetConfig, size_mgr: SizeMgr) -> Self {
         let start = std::time::Instant::now();
 
-        let w = widget.size_rules(size_mgr.re(), AxisInfo::new(false, None));
-        let h = widget.size_rules(size_mgr.re(), AxisInfo::new(true, Some(w.ideal_size())));
+        let mut rules = RulesCache::default();
+        let mut size_rules = |axis| profile::size_rules(widget, size_mgr.re(), axis);
+        let w = rules.get_or_insert_with(AxisInfo::new(false, None), &mut size_rules);
+        let h = AxisInfo::new(true, Some(w.ideal_size()));
+        let h = rules.get_or_insert_with(h, &mut size_rules);
 
         let min = Size(w.min_size(), h.min_size());
         let ideal = Size(w.ideal_size(), h.ideal_size());
         let margins = Margins::hv(w.margins(), h.margins());
 
-        trace!(target: "kas_perf", "layout::find_constraints: {}ms", start.elapsed().as_millis());
+        let elapsed = start.elapsed();
+        profile::solver_run(elapsed);
+        trace!(target: "kas_perf", "layout::find_constraints: {}ms", elapsed.as_millis());
         trace!(
             "layout::solve: min={:?}, ideal={:?}, margins={:?}",
             min,
             ideal,
             margins
         );
-        let refresh_rules = false;
-        let last_width = ideal.0;
         SolveCache {
             min,
             ideal,
             margins,
-            refresh_rules,
-            last_width,
+            rules,
         }
     }
 
@@ -167,7 +220,7 @@ impl SolveCache {
     /// forces [`SolveCache::apply_rect`] to recompute these rules when next
     /// called.
     pub fn invalidate_rule_cache(&mut self) {
-        self.refresh_rules = true;
+        self.rules.invalidate();
     }
 
     /// Apply layout solution to a widget
@@ -176,10 +229,12 @@ impl SolveCache {
     /// If `inner_margin` is true, margins are internal to this `rect`; if not,
     /// the caller is responsible for handling margins.
     ///
-    /// If [`SolveCache::invalidate_rule_cache`] was called since rules were
-    /// last calculated then this method will recalculate all rules; otherwise
-    /// it will only do so if necessary (when dimensions do not match those
-    /// last used).
+    /// Rules are cached (see [`RulesCache`]): if
+    /// [`SolveCache::invalidate_rule_cache`] was called since rules were last
+    /// calculated then this method will recalculate all rules; otherwise it
+    /// will only do so if necessary (when dimensions do not match those last
+    /// used). In particular, moving a widget without resizing it does not
+    /// recalculate rules.
     pub fn apply_rect(
         &mut self,
         widget: &mut dyn WidgetConfig,
@@ -189,43 +244,43 @@ impl SolveCache {
     ) {
         let start = std::time::Instant::now();
 
+        // We call size_rules not because we want the result, but because our
+        // spec requires that we do so before calling set_rect. Cached rules
+        // are still valid since the widget's state is unchanged.
+        let size_mgr = mgr.size_mgr();
+        let mut size_rules = |axis| profile::size_rules(widget, size_mgr.re(), axis);
+        let w = AxisInfo::new(false, None);
+        let w = self.rules.get_or_insert_with(w, &mut size_rules);
+        self.min.0 = w.min_size();
+        self.ideal.0 = w.ideal_size();
+        self.margins.horiz = w.margins();
+
         let mut width = rect.size.0;
         if inner_margin {
             width -= self.margins.sum_horiz();
         }
 
-        // We call size_rules not because we want the result, but because our
-        // spec requires that we do so before calling set_rect.
-        if self.refresh_rules || width != self.last_width {
-            if self.refresh_rules {
-                let w = widget.size_rules(mgr.size_mgr(), AxisInfo::new(false, None));
-                self.min.0 = w.min_size();
-                self.ideal.0 = w.ideal_size();
-                self.margins.horiz = w.margins();
-            }
-
-            let h = widget.size_rules(mgr.size_mgr(), AxisInfo::new(true, Some(width)));
-            self.min.1 = h.min_size();
-            self.ideal.1 = h.ideal_size();
-            self.margins.vert = h.margins();
-            self.last_width = width;
-        }
+        let h = AxisInfo::new(true, Some(width));
+        let h = self.rules.get_or_insert_with(h, &mut size_rules);
+        self.min.1 = h.min_size();
+        self.ideal.1 = h.ideal_size();
+        self.margins.vert = h.margins();
 
         if inner_margin {
             rect.pos += Size::from((self.margins.horiz.0, self.margins.vert.0));
             rect.size.0 = width;
             rect.size.1 -= self.margins.sum_vert();
         }
-        widget.set_rect(mgr, rect, AlignHints::NONE);
+        profile::set_rect(widget, mgr, rect, AlignHints::NONE);
 
-        trace!(target: "kas_perf", "layout::apply_rect: {}ms", start.elapsed().as_millis());
+        let elapsed = start.elapsed();
+        profile::solver_run(elapsed);
+        trace!(target: "kas_perf", "layout::apply_rect: {}ms", elapsed.as_millis());
         trace!(
             "layout::apply_rect: size={:?}, hierarchy:{}",
             rect.size,
             WidgetHeirarchy(widget, 0),
         );
-
-        self.refresh_rules = false;
     }
 }
 
@@ -247,3 +302,47 @@ impl<'a> fmt::Display for WidgetHeirarchy<'a> {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn rules_cache() {
+        let mut cache = RulesCache::default();
+        let w = AxisInfo::new(false, None);
+        let h100 = AxisInfo::new(true, Some(100));
+        let h200 = AxisInfo::new(true, Some(200));
+        assert_eq!(cache.get(w), None);
+
+        cache.insert(w, SizeRules::fixed(100, (0, 0)));
+        cache.insert(h100, SizeRules::fixed(20, (0, 0)));
+        assert_eq!(cache.get(w), Some(SizeRules::fixed(100, (0, 0))));
+        assert_eq!(cache.get(h100), Some(SizeRules::fixed(20, (0, 0))));
+        assert_eq!(cache.get(h200), None);
+
+        let mut calls = 0;
+        let mut f = |_| {
+            calls += 1;
+            SizeRules::fixed(10, (0, 0))
+        };
+        assert_eq!(
+            cache.get_or_insert_with(h100, &mut f),
+            SizeRules::fixed(20, (0, 0))
+        );
+        assert_eq!(
+            cache.get_or_insert_with(h200, &mut f),
+            SizeRules::fixed(10, (0, 0))
+        );
+        assert_eq!(calls, 1);
+        // Only the latest entry per axis is kept
+        assert_eq!(cache.get(h100), None);
+
+        // Recomputing horizontal rules drops vertical rules
+        cache.insert(w, SizeRules::fixed(120, (0, 0)));
+        assert_eq!(cache.get(h200), None);
+
+        cache.invalidate();
+        assert_eq!(cache.get(w), None);
+    }
+}
@@ -5,7 +5,9 @@
 
 //! Layout visitor
 
-use super::{AlignHints, AxisInfo, RulesSetter, RulesSolver, SetRectMgr, SizeRules, Storage};
+use super::{
+    profile, AlignHints, AxisInfo, RulesSetter, RulesSolver, SetRectMgr, SizeRules, Storage,
+};
 use super::{DynRowStorage, RowPositionSolver, RowSetter, RowSolver, RowStorage};
 use super::{GridChildInfo, GridDimensions, GridSetter, GridSolver, GridStorage};
 use crate::draw::color::Rgb;
@@ -212,8 +214,8 @@ impl<'a> Layout<'a> {
     fn size_rules_(&mut self, mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
         match &mut self.layout {
             LayoutType::None => SizeRules::EMPTY,
-            LayoutType::Single(child) => child.size_rules(mgr, axis),
-            LayoutType::AlignSingle(child, _) => child.size_rules(mgr, axis),
+            LayoutType::Single(child) => profile::size_rules(*child, mgr, axis),
+            LayoutType::AlignSingle(child, _) => profile::size_rules(*child, mgr, axis),
             LayoutType::AlignLayout(layout, _) => layout.size_rules_(mgr, axis),
             LayoutType::Frame(child, storage) => {
                 let frame_rules = mgr.frame(axis.is_vertical());
@@ -251,10 +253,10 @@ impl<'a> Layout<'a> {
     fn set_rect_(&mut self, mgr: &mut SetRectMgr, mut rect: Rect, align: AlignHints) {
         match &mut self.layout {
             LayoutType::None => (),
-            LayoutType::Single(child) => child.set_rect(mgr, rect, align),
+            LayoutType::Single(child) => profile::set_rect(*child, mgr, rect, align),
             LayoutType::AlignSingle(child, hints) => {
                 let align = hints.combine(align);
-                child.set_rect(mgr, rect, align);
+                profile::set_rect(*child, mgr, rect, align);
             }
             LayoutType::AlignLayout(layout, hints) => {
                 let align = hints.combine(align);
@@ -395,7 +397,9 @@ impl<'a, W: WidgetConfig, D: Directional> Visitor for Slice<'a, W, D> {
         let dim = (self.direction, self.children.len());
         let mut solver = RowSolver::new(axis, dim, self.data);
         for (n, child) in self.children.iter_mut().enumerate() {
-            solver.for_child(self.data, n, |axis| child.size_rules(mgr.re(), axis));
+            solver.for_child(self.data, n, |axis| {
+                profile::size_rules(child, mgr.re(), axis)
+            });
         }
         solver.finish(self.data)
     }
@@ -405,7 +409,7 @@ impl<'a, W: WidgetConfig, D: Directional> Visitor for Slice<'a, W, D> {
         let mut setter = RowSetter::<D, Vec<i32>, _>::new(rect, dim, align, self.data);
 
         for (n, child) in self.children.iter_mut().enumerate() {
-            child.set_rect(mgr, setter.child_rect(self.data, n), align);
+            profile::set_rect(child, mgr, setter.child_rect(self.data, n), align);
         }
     }
 
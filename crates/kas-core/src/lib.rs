@@ -33,6 +33,7 @@ pub mod event;
 pub mod geom;
 pub mod layout;
 pub mod prelude;
+pub mod shell;
 pub mod text;
 pub mod theme;
 pub mod updatable;
@@ -40,3 +40,5 @@ pub use crate::WidgetId;
 pub use crate::{Boxed, TkAction};
 #[doc(no_inline)]
 pub use crate::{Layout, Widget, WidgetChildren, WidgetConfig, WidgetCore, WidgetExt};
+#[doc(no_inline)]
+pub use crate::{SaveState, StateValue};
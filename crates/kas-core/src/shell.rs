@@ -0,0 +1,92 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Information provided by the shell
+//!
+//! Types in this module describe the environment in which windows are shown.
+//! They are constructed by the shell; see [`crate::event::EventMgr::displays`].
+
+use crate::geom::{Coord, Rect, Size};
+
+/// A display (monitor)
+///
+/// All coordinates are in physical pixels, relative to the desktop origin.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Monitor {
+    /// The monitor's name, if available
+    pub name: Option<String>,
+    /// Position and size (current resolution)
+    pub rect: Rect,
+    /// The area available to windows
+    ///
+    /// This excludes e.g. task bars. Where the shell or platform does not
+    /// report a work area, this equals [`Monitor::rect`]; in particular, the
+    /// `kas-wgpu` shell does not currently report work areas (winit provides no
+    /// such API), thus there this always equals [`Monitor::rect`].
+    pub work_area: Rect,
+    /// The monitor's scale factor
+    pub scale_factor: f64,
+    /// Supported resolutions, largest first
+    pub resolutions: Vec<Size>,
+}
+
+/// Available displays
+///
+/// This is a snapshot taken when constructed.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Displays {
+    /// All available monitors
+    pub monitors: Vec<Monitor>,
+    /// Index of the primary monitor, if known
+    pub primary: Option<usize>,
+    /// Index of the monitor showing the current window, if known
+    pub current: Option<usize>,
+    /// The current window's outer position and size, if known
+    pub window: Option<Rect>,
+}
+
+impl Displays {
+    /// Get the number of monitors
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.monitors.len()
+    }
+
+    /// True if no monitors are known
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.monitors.is_empty()
+    }
+
+    /// Get monitor `index`
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&Monitor> {
+        self.monitors.get(index)
+    }
+
+    /// Get the primary monitor, if known
+    #[inline]
+    pub fn primary_monitor(&self) -> Option<&Monitor> {
+        self.primary.and_then(|index| self.get(index))
+    }
+
+    /// Get the monitor showing the current window, if known
+    #[inline]
+    pub fn current_monitor(&self) -> Option<&Monitor> {
+        self.current.and_then(|index| self.get(index))
+    }
+
+    /// Get the position at which a window of (outer) `size` is centred on
+    /// monitor `index`
+    ///
+    /// The window is centred within the monitor's work area (see
+    /// [`Monitor::work_area`], which may equal the whole monitor), but placed
+    /// at the work area's top-left corner if larger.
+    pub fn centre_window(&self, index: usize, size: Size) -> Option<Coord> {
+        let area = self.get(index)?.work_area;
+        let excess = area.size - size.min(area.size);
+        Some(area.pos + excess / 2)
+    }
+}
@@ -14,11 +14,16 @@ use unicode_segmentation::UnicodeSegmentation;
 /// This struct holds an "edit pos" and a "selection pos", which together form
 /// a range. There is no requirement on the order of these two positions. Each
 /// may be adjusted independently.
+///
+/// Additionally, this struct may hold secondary cursors (each an edit pos and
+/// selection pos), supporting multi-cursor editing. Most methods only affect
+/// the primary cursor; those affecting all cursors say so.
 #[derive(Clone, Debug, Default)]
 pub struct SelectionHelper {
     edit_pos: usize,
     sel_pos: usize,
     anchor_pos: usize,
+    extra: Vec<(usize, usize)>,
 }
 
 impl SelectionHelper {
@@ -31,10 +36,13 @@ impl SelectionHelper {
             edit_pos,
             sel_pos,
             anchor_pos,
+            extra: vec![],
         }
     }
 
     /// Reset to the default state
+    ///
+    /// This removes all secondary cursors.
     pub fn clear(&mut self) {
         *self = Self::default();
     }
@@ -84,6 +92,123 @@ impl SelectionHelper {
         range
     }
 
+    /// True if there are secondary cursors
+    pub fn has_extra(&self) -> bool {
+        !self.extra.is_empty()
+    }
+
+    /// Get the number of cursors (including the primary cursor)
+    pub fn num_cursors(&self) -> usize {
+        1 + self.extra.len()
+    }
+
+    /// Remove all secondary cursors
+    pub fn clear_extra(&mut self) {
+        self.extra.clear();
+    }
+
+    /// Add a cursor, which becomes the primary cursor
+    ///
+    /// The old primary cursor becomes a secondary cursor. Cursors which
+    /// overlap are merged.
+    pub fn add_cursor(&mut self, edit_pos: usize, sel_pos: usize) {
+        self.extra.push((self.edit_pos, self.sel_pos));
+        self.edit_pos = edit_pos;
+        self.sel_pos = sel_pos;
+        self.anchor_pos = sel_pos;
+        self.merge_overlapping();
+    }
+
+    /// Iterate over `(edit_pos, sel_pos)` of all cursors
+    ///
+    /// The primary cursor is first; others are in no particular order.
+    pub fn cursors(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        std::iter::once((self.edit_pos, self.sel_pos)).chain(self.extra.iter().cloned())
+    }
+
+    /// Set all cursors from `(edit_pos, sel_pos)` pairs
+    ///
+    /// The first item is the primary cursor. Does nothing if `cursors` is
+    /// empty. Cursors which overlap are merged.
+    pub fn set_cursors<I: IntoIterator<Item = (usize, usize)>>(&mut self, cursors: I) {
+        let mut iter = cursors.into_iter();
+        if let Some((edit_pos, sel_pos)) = iter.next() {
+            self.edit_pos = edit_pos;
+            self.sel_pos = sel_pos;
+            self.extra.clear();
+            self.extra.extend(iter);
+            self.merge_overlapping();
+        }
+    }
+
+    /// Set the selection pos to the edit pos for all cursors
+    pub fn set_all_empty(&mut self) {
+        self.sel_pos = self.edit_pos;
+        for c in &mut self.extra {
+            c.1 = c.0;
+        }
+        self.merge_overlapping();
+    }
+
+    /// Get the (non-empty) selected ranges of all cursors
+    ///
+    /// Ranges are sorted and do not overlap.
+    pub fn ranges(&self) -> Vec<Range<usize>> {
+        let mut ranges: Vec<_> = self
+            .cursors()
+            .map(|(a, b)| a.min(b)..a.max(b))
+            .filter(|r| r.start < r.end)
+            .collect();
+        ranges.sort_by_key(|r| r.start);
+        ranges
+    }
+
+    /// Merge overlapping cursors
+    ///
+    /// Cursors at the same position and overlapping selections are merged.
+    fn merge_overlapping(&mut self) {
+        if self.extra.is_empty() {
+            return;
+        }
+
+        let mut all: Vec<((usize, usize), bool)> = self
+            .extra
+            .drain(..)
+            .map(|c| (c, false))
+            .chain(std::iter::once(((self.edit_pos, self.sel_pos), true)))
+            .collect();
+        all.sort_by_key(|(c, _)| c.0.min(c.1));
+
+        let mut merged: Vec<((usize, usize), bool)> = Vec::with_capacity(all.len());
+        for (c, primary) in all {
+            if let Some((last, last_primary)) = merged.last_mut() {
+                let (start, end) = (c.0.min(c.1), c.0.max(c.1));
+                let last_end = last.0.max(last.1);
+                let touching = start == last_end && (c.0 == c.1 || last.0 == last.1);
+                if start < last_end || touching {
+                    let last_start = last.0.min(last.1);
+                    let end = end.max(last_end);
+                    *last = match last.0 < last.1 {
+                        true => (last_start, end),
+                        false => (end, last_start),
+                    };
+                    *last_primary |= primary;
+                    continue;
+                }
+            }
+            merged.push((c, primary));
+        }
+
+        for (c, primary) in merged {
+            if primary {
+                self.edit_pos = c.0;
+                self.sel_pos = c.1;
+            } else {
+                self.extra.push(c);
+            }
+        }
+    }
+
     /// Set the anchor position from the edit position
     pub fn set_anchor(&mut self) {
         self.anchor_pos = self.edit_pos;
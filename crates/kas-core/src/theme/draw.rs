@@ -9,12 +9,14 @@ use std::convert::AsRef;
 use std::ops::{Bound, Deref, DerefMut, Range, RangeBounds};
 
 use crate::dir::Direction;
-use crate::draw::{color::Rgb, Draw, DrawShared, ImageId, PassType};
+use crate::draw::color::{Rgb, Rgba};
+use crate::draw::{Affine, Draw, DrawShared, ImageId, PassType};
 use crate::event::EventState;
-use crate::geom::{Coord, Offset, Rect};
+use crate::geom::{Coord, Offset, PixelAlign, Rect};
 use crate::layout::SetRectMgr;
 use crate::text::{AccelString, Text, TextApi, TextDisplay};
-use crate::theme::{InputState, SizeHandle, SizeMgr, TextClass};
+use crate::theme::{icons, IconName};
+use crate::theme::{CheckState, InputState, SizeHandle, SizeMgr, SymbolClass, TextClass};
 use crate::{CoreData, TkAction};
 
 /// Draw interface
@@ -27,6 +29,7 @@ pub struct DrawMgr<'a> {
     h: &'a mut dyn DrawHandle,
     ev: &'a mut EventState,
     disabled: bool,
+    forced: Option<InputState>,
 }
 
 impl<'a> DrawMgr<'a> {
@@ -34,7 +37,35 @@ impl<'a> DrawMgr<'a> {
     #[cfg_attr(not(feature = "internal_doc"), doc(hidden))]
     #[cfg_attr(doc_cfg, doc(cfg(internal_doc)))]
     pub fn new(h: &'a mut dyn DrawHandle, ev: &'a mut EventState, disabled: bool) -> Self {
-        DrawMgr { h, ev, disabled }
+        DrawMgr {
+            h,
+            ev,
+            disabled,
+            forced: None,
+        }
+    }
+
+    /// Draw without interactive state (chain style)
+    ///
+    /// Widgets drawn through the result (and their descendants) are drawn
+    /// without hover, depress and focus highlighting, e.g. for previews.
+    /// Disabled state is still shown. See also `kas_widgets::Inert`.
+    #[inline]
+    #[must_use]
+    pub fn inert(self) -> Self {
+        self.with_state(InputState::empty())
+    }
+
+    /// Draw with a fixed interactive state (chain style)
+    ///
+    /// Like [`Self::inert`], but widgets are drawn with the given `state`
+    /// (e.g. [`InputState::HOVER`]) in place of their actual interactive
+    /// state. This is intended for previews such as a theme gallery.
+    #[inline]
+    #[must_use]
+    pub fn with_state(mut self, state: InputState) -> Self {
+        self.forced = Some(state);
+        self
     }
 
     /// Access event-management state
@@ -47,6 +78,11 @@ impl<'a> DrawMgr<'a> {
         SizeMgr::new(self.h.size_and_draw_shared().0)
     }
 
+    /// Get a helper to align geometry to physical pixels
+    pub fn pixel_align(&mut self) -> PixelAlign {
+        PixelAlign::new(self.h.size_and_draw_shared().0.scale_factor())
+    }
+
     /// Access a [`SetRectMgr`]
     pub fn set_rect_mgr<F: FnMut(&mut SetRectMgr) -> T, T>(&mut self, mut f: F) -> T {
         let (sh, ds) = self.h.size_and_draw_shared();
@@ -70,10 +106,20 @@ impl<'a> DrawMgr<'a> {
 
     /// Add context to allow draw operations
     pub fn with_core<'b>(&'b mut self, core: &CoreData) -> DrawCtx<'b> {
-        let state = self.ev.draw_state(core, self.disabled);
+        let mut state = self.ev.draw_state(core, self.disabled);
+        if let Some(forced) = self.forced {
+            state = (state & InputState::DISABLED) | forced;
+        }
         let (h, ev) = (&mut *self.h, &mut *self.ev);
         let wid = core.id.as_u64();
-        DrawCtx { h, ev, wid, state }
+        let forced = self.forced;
+        DrawCtx {
+            h,
+            ev,
+            wid,
+            forced,
+            state,
+        }
     }
 }
 
@@ -88,6 +134,7 @@ pub struct DrawCtx<'a> {
     h: &'a mut dyn DrawHandle,
     ev: &'a mut EventState,
     wid: u64,
+    forced: Option<InputState>,
     /// Input state for drawn objects
     ///
     /// Normally this is derived automatically, but it may be adjusted.
@@ -109,6 +156,7 @@ impl<'a> DrawCtx<'a> {
             h: self.h,
             ev: self.ev,
             disabled: self.state.contains(InputState::DISABLED),
+            forced: self.forced,
         }
     }
 
@@ -125,6 +173,7 @@ impl<'a> DrawCtx<'a> {
             h: self.h,
             ev: self.ev,
             wid: self.wid,
+            forced: self.forced,
             state: self.state,
         }
     }
@@ -139,6 +188,11 @@ impl<'a> DrawCtx<'a> {
         SizeMgr::new(self.h.size_and_draw_shared().0)
     }
 
+    /// Get a helper to align geometry to physical pixels
+    pub fn pixel_align(&mut self) -> PixelAlign {
+        PixelAlign::new(self.h.size_and_draw_shared().0.scale_factor())
+    }
+
     /// Access a [`SetRectMgr`]
     pub fn set_rect_mgr<F: FnMut(&mut SetRectMgr) -> T, T>(&mut self, mut f: F) -> T {
         let (sh, ds) = self.h.size_and_draw_shared();
@@ -166,11 +220,48 @@ impl<'a> DrawCtx<'a> {
     /// clipped to `rect` and translated by `offset.
     pub fn with_clip_region<F: FnMut(DrawCtx)>(&mut self, rect: Rect, offset: Offset, mut f: F) {
         let ev = &mut *self.ev;
-        let wid = self.wid;
+        let (wid, forced) = (self.wid, self.forced);
         let state = self.state;
-        self.h.new_pass(rect, offset, PassType::Clip, &mut |h| {
-            f(DrawCtx { h, ev, wid, state })
-        });
+        self.h
+            .new_pass(rect, offset, Affine::IDENTITY, PassType::Clip, &mut |h| {
+                f(DrawCtx {
+                    h,
+                    ev,
+                    wid,
+                    forced,
+                    state,
+                })
+            });
+    }
+
+    /// Draw to a new pass with clipping, offset and transform (e.g. for zoom)
+    ///
+    /// Adds a new draw pass of type [`PassType::Clip`], with draw operations
+    /// clipped to `rect`, translated by `offset`, then transformed by
+    /// `transform` (see [`crate::draw::DrawIface::new_pass`]).
+    ///
+    /// Note: event coordinates are not transformed; the widget is responsible
+    /// for mapping these (e.g. via [`Affine::inverse`]) where required.
+    pub fn with_transform<F: FnMut(DrawCtx)>(
+        &mut self,
+        rect: Rect,
+        offset: Offset,
+        transform: Affine,
+        mut f: F,
+    ) {
+        let ev = &mut *self.ev;
+        let (wid, forced) = (self.wid, self.forced);
+        let state = self.state;
+        self.h
+            .new_pass(rect, offset, transform, PassType::Clip, &mut |h| {
+                f(DrawCtx {
+                    h,
+                    ev,
+                    wid,
+                    forced,
+                    state,
+                })
+            });
     }
 
     /// Draw to a new pass as an overlay (e.g. for pop-up menus)
@@ -183,12 +274,50 @@ impl<'a> DrawCtx<'a> {
     /// [`DrawCtx::get_clip_rect`] may be larger than expected.
     pub fn with_overlay<F: FnMut(DrawCtx)>(&mut self, rect: Rect, mut f: F) {
         let ev = &mut *self.ev;
-        let wid = self.wid;
+        let (wid, forced) = (self.wid, self.forced);
         let state = self.state;
-        self.h
-            .new_pass(rect, Offset::ZERO, PassType::Overlay, &mut |h| {
-                f(DrawCtx { h, ev, wid, state })
-            });
+        self.h.new_pass(
+            rect,
+            Offset::ZERO,
+            Affine::IDENTITY,
+            PassType::Overlay,
+            &mut |h| {
+                f(DrawCtx {
+                    h,
+                    ev,
+                    wid,
+                    forced,
+                    state,
+                })
+            },
+        );
+    }
+
+    /// Draw to a new pass elevated above siblings (e.g. for drag previews)
+    ///
+    /// Adds a new draw pass of type [`PassType::Elevated`]: drawing is relative
+    /// to the current pass (including any offset and transform) but is clipped
+    /// only to `rect` and the window, and happens after all non-elevated
+    /// passes, including those of later siblings.
+    pub fn with_elevated<F: FnMut(DrawCtx)>(&mut self, rect: Rect, mut f: F) {
+        let ev = &mut *self.ev;
+        let (wid, forced) = (self.wid, self.forced);
+        let state = self.state;
+        self.h.new_pass(
+            rect,
+            Offset::ZERO,
+            Affine::IDENTITY,
+            PassType::Elevated,
+            &mut |h| {
+                f(DrawCtx {
+                    h,
+                    ev,
+                    wid,
+                    forced,
+                    state,
+                })
+            },
+        );
     }
 
     /// Target area for drawing
@@ -238,6 +367,26 @@ impl<'a> DrawCtx<'a> {
         self.h.text(pos, text, class, self.state);
     }
 
+    /// Draw text rotated about `pos`
+    ///
+    /// The text is laid out as by [`DrawCtx::text`], then rotated about `pos`
+    /// by `angle` (radians, clockwise on screen). For example, use
+    /// `-FRAC_PI_2` for vertical text reading bottom-to-top, in which case
+    /// the text occupies the rect from `pos - (0, width)` to
+    /// `pos + (height, 0)` where `(width, height)` are the text's bounds.
+    ///
+    /// This draws to a new pass (see [`DrawCtx::with_transform`]), thus the
+    /// text is drawn in front of all other content of the current pass.
+    pub fn text_rotated(&mut self, pos: Coord, text: &TextDisplay, class: TextClass, angle: f32) {
+        let rect = self.h.get_clip_rect();
+        let transform = Affine::rotate_about(angle, pos.into());
+        let state = self.state;
+        self.h
+            .new_pass(rect, Offset::ZERO, transform, PassType::Clip, &mut |h| {
+                h.text(pos, text, class, state)
+            });
+    }
+
     /// Draw text with effects
     ///
     /// [`DrawCtx::text`] already supports *font* effects: bold,
@@ -293,6 +442,36 @@ impl<'a> DrawCtx<'a> {
             .text_selected_range(pos, text.as_ref(), range, class, self.state);
     }
 
+    /// Draw some text using the standard font, with multiple ranges selected
+    ///
+    /// This is identical to [`DrawCtx::text_selected`], except that multiple
+    /// ranges may be highlighted. `ranges` must be sorted and may not overlap.
+    pub fn text_selected_ranges<T: AsRef<TextDisplay>>(
+        &mut self,
+        pos: Coord,
+        text: T,
+        ranges: &[Range<usize>],
+        class: TextClass,
+    ) {
+        self.h
+            .text_selected_ranges(pos, text.as_ref(), ranges, class, self.state);
+    }
+
+    /// Draw a wavy underline beneath `ranges` of `text`
+    ///
+    /// This is used to mark errors such as misspelled words. It does not draw
+    /// the text itself. `ranges` must be sorted and may not overlap.
+    pub fn text_squiggle<T: AsRef<TextDisplay>>(
+        &mut self,
+        pos: Coord,
+        text: T,
+        ranges: &[Range<usize>],
+        class: TextClass,
+    ) {
+        self.h
+            .text_squiggle(pos, text.as_ref(), ranges, class, self.state);
+    }
+
     /// Draw an edit marker at the given `byte` index on this `text`
     ///
     /// [`SizeMgr::text_bound`] should be called prior to this method to
@@ -301,6 +480,21 @@ impl<'a> DrawCtx<'a> {
         self.h.text_cursor(self.wid, pos, text, class, byte);
     }
 
+    /// Draw multiple edit markers on this `text`
+    ///
+    /// The first index of `bytes` is considered the primary edit position;
+    /// all markers blink together. Otherwise this is identical to
+    /// [`DrawCtx::text_cursor`].
+    pub fn text_cursors(
+        &mut self,
+        pos: Coord,
+        text: &TextDisplay,
+        class: TextClass,
+        bytes: &[usize],
+    ) {
+        self.h.text_cursors(self.wid, pos, text, class, bytes);
+    }
+
     /// Draw the background of a menu entry
     pub fn menu_entry(&mut self, rect: Rect) {
         self.h.menu_entry(rect, self.state);
@@ -330,7 +524,10 @@ impl<'a> DrawCtx<'a> {
     /// The checkbox is a small, usually square, box with or without a check
     /// mark. A checkbox widget may include a text label, but that label is not
     /// part of this element.
-    pub fn checkbox(&mut self, rect: Rect, checked: bool) {
+    ///
+    /// The check mark is drawn according to `checked`; an indeterminate state
+    /// is drawn with a "mixed" mark.
+    pub fn checkbox(&mut self, rect: Rect, checked: CheckState) {
         self.h.checkbox(self.wid, rect, checked, self.state);
     }
 
@@ -341,6 +538,15 @@ impl<'a> DrawCtx<'a> {
         self.h.radiobox(self.wid, rect, checked, self.state);
     }
 
+    /// Draw UI element: switch
+    ///
+    /// A switch is an on/off toggle drawn as a track with a sliding thumb.
+    /// The thumb is at the start of the track when off and at the end when
+    /// `on`; themes may animate the transition.
+    pub fn switch(&mut self, rect: Rect, on: bool) {
+        self.h.switch(self.wid, rect, on, self.state);
+    }
+
     /// Draw UI element: scrollbar
     ///
     /// -   `rect`: area of whole widget (slider track)
@@ -371,10 +577,39 @@ impl<'a> DrawCtx<'a> {
         self.h.progress_bar(rect, dir, self.state, value);
     }
 
+    /// Draw UI element: badge
+    ///
+    /// A badge is a small, highlighted, rounded background (e.g. for a
+    /// notification count). Any text must be drawn separately.
+    pub fn badge(&mut self, rect: Rect) {
+        self.h.badge(rect, self.state);
+    }
+
     /// Draw an image
     pub fn image(&mut self, id: ImageId, rect: Rect) {
         self.h.image(id, rect);
     }
+
+    /// Get the colour of a symbolic icon
+    ///
+    /// Symbolic icons are single-colour images; their colour depends on the
+    /// theme, the `class` and the widget's state.
+    pub fn symbol_color(&self, class: SymbolClass) -> Rgba {
+        self.h.symbol_color(class, self.state)
+    }
+
+    /// Draw a named icon
+    ///
+    /// The icon is resolved (see [`icons::get`]) at the size of `rect`, which
+    /// should be square (see [`SizeMgr::icon`]). Symbolic icons use the colour
+    /// of [`SymbolClass::Text`]. Nothing is drawn if the icon cannot be loaded.
+    pub fn icon(&mut self, name: &IconName, rect: Rect) {
+        let col = self.symbol_color(SymbolClass::Text);
+        let side = rect.size.0.min(rect.size.1);
+        if let Some(id) = icons::get(self.draw_shared(), name, side, col) {
+            self.image(id, rect);
+        }
+    }
 }
 
 impl<'a> std::ops::BitOrAssign<TkAction> for DrawMgr<'a> {
@@ -406,10 +641,13 @@ pub trait DrawHandle {
     fn draw_device(&mut self) -> &mut dyn Draw;
 
     /// Construct a new pass
+    ///
+    /// See [`crate::draw::DrawIface::new_pass`].
     fn new_pass(
         &mut self,
         rect: Rect,
         offset: Offset,
+        transform: Affine,
         class: PassType,
         f: &mut dyn FnMut(&mut dyn DrawHandle),
     );
@@ -484,6 +722,26 @@ pub trait DrawHandle {
         state: InputState,
     );
 
+    /// Method used to implement [`DrawCtx::text_selected_ranges`]
+    fn text_selected_ranges(
+        &mut self,
+        pos: Coord,
+        text: &TextDisplay,
+        ranges: &[Range<usize>],
+        class: TextClass,
+        state: InputState,
+    );
+
+    /// Method used to implement [`DrawCtx::text_squiggle`]
+    fn text_squiggle(
+        &mut self,
+        pos: Coord,
+        text: &TextDisplay,
+        ranges: &[Range<usize>],
+        class: TextClass,
+        state: InputState,
+    );
+
     /// Draw an edit marker at the given `byte` index on this `text`
     ///
     /// [`SizeMgr::text_bound`] should be called prior to this method to
@@ -497,6 +755,16 @@ pub trait DrawHandle {
         byte: usize,
     );
 
+    /// Method used to implement [`DrawCtx::text_cursors`]
+    fn text_cursors(
+        &mut self,
+        wid: u64,
+        pos: Coord,
+        text: &TextDisplay,
+        class: TextClass,
+        bytes: &[usize],
+    );
+
     /// Draw the background of a menu entry
     fn menu_entry(&mut self, rect: Rect, state: InputState);
 
@@ -514,13 +782,20 @@ pub trait DrawHandle {
     /// The checkbox is a small, usually square, box with or without a check
     /// mark. A checkbox widget may include a text label, but that label is not
     /// part of this element.
-    fn checkbox(&mut self, wid: u64, rect: Rect, checked: bool, state: InputState);
+    fn checkbox(&mut self, wid: u64, rect: Rect, checked: CheckState, state: InputState);
 
     /// Draw UI element: radiobox
     ///
     /// This is similar in appearance to a checkbox.
     fn radiobox(&mut self, wid: u64, rect: Rect, checked: bool, state: InputState);
 
+    /// Draw UI element: switch
+    ///
+    /// A switch is an on/off toggle drawn as a track with a sliding thumb.
+    /// The thumb is at the start of the track when off and at the end when
+    /// `on`; themes may animate the transition.
+    fn switch(&mut self, wid: u64, rect: Rect, on: bool, state: InputState);
+
     /// Draw UI element: scrollbar
     ///
     /// -   `rect`: area of whole widget (slider track)
@@ -545,8 +820,17 @@ pub trait DrawHandle {
     /// -   `value`: progress value, between 0.0 and 1.0
     fn progress_bar(&mut self, rect: Rect, dir: Direction, state: InputState, value: f32);
 
+    /// Draw UI element: badge
+    ///
+    /// A badge is a small, highlighted, rounded background (e.g. for a
+    /// notification count). Any text must be drawn separately.
+    fn badge(&mut self, rect: Rect, state: InputState);
+
     /// Draw an image
     fn image(&mut self, id: ImageId, rect: Rect);
+
+    /// Get the colour of a symbolic icon
+    fn symbol_color(&self, class: SymbolClass, state: InputState) -> Rgba;
 }
 
 macro_rules! impl_ {
@@ -562,10 +846,11 @@ macro_rules! impl_ {
                 &mut self,
                 rect: Rect,
                 offset: Offset,
+                transform: Affine,
                 class: PassType,
                 f: &mut dyn FnMut(&mut dyn DrawHandle),
             ) {
-                self.deref_mut().new_pass(rect, offset, class, f);
+                self.deref_mut().new_pass(rect, offset, transform, class, f);
             }
             fn get_clip_rect(&self) -> Rect {
                 self.deref().get_clip_rect()
@@ -615,9 +900,34 @@ macro_rules! impl_ {
                 self.deref_mut()
                     .text_selected_range(pos, text, range, class, state);
             }
+            fn text_selected_ranges(
+                &mut self,
+                pos: Coord,
+                text: &TextDisplay,
+                ranges: &[Range<usize>],
+                class: TextClass,
+                state: InputState,
+            ) {
+                self.deref_mut()
+                    .text_selected_ranges(pos, text, ranges, class, state);
+            }
+            fn text_squiggle(
+                &mut self,
+                pos: Coord,
+                text: &TextDisplay,
+                ranges: &[Range<usize>],
+                class: TextClass,
+                state: InputState,
+            ) {
+                self.deref_mut()
+                    .text_squiggle(pos, text, ranges, class, state);
+            }
             fn text_cursor(&mut self, wid: u64, pos: Coord, text: &TextDisplay, class: TextClass, byte: usize) {
                 self.deref_mut().text_cursor(wid, pos, text, class, byte)
             }
+            fn text_cursors(&mut self, wid: u64, pos: Coord, text: &TextDisplay, class: TextClass, bytes: &[usize]) {
+                self.deref_mut().text_cursors(wid, pos, text, class, bytes)
+            }
             fn menu_entry(&mut self, rect: Rect, state: InputState) {
                 self.deref_mut().menu_entry(rect, state)
             }
@@ -627,12 +937,15 @@ macro_rules! impl_ {
             fn edit_box(&mut self, rect: Rect, state: InputState) {
                 self.deref_mut().edit_box(rect, state)
             }
-            fn checkbox(&mut self, wid: u64, rect: Rect, checked: bool, state: InputState) {
+            fn checkbox(&mut self, wid: u64, rect: Rect, checked: CheckState, state: InputState) {
                 self.deref_mut().checkbox(wid, rect, checked, state)
             }
             fn radiobox(&mut self, wid: u64, rect: Rect, checked: bool, state: InputState) {
                 self.deref_mut().radiobox(wid, rect, checked, state)
             }
+            fn switch(&mut self, wid: u64, rect: Rect, on: bool, state: InputState) {
+                self.deref_mut().switch(wid, rect, on, state)
+            }
             fn scrollbar(&mut self, rect: Rect, h_rect: Rect, dir: Direction, state: InputState) {
                 self.deref_mut().scrollbar(rect, h_rect, dir, state)
             }
@@ -642,13 +955,20 @@ macro_rules! impl_ {
             fn progress_bar(&mut self, rect: Rect, dir: Direction, state: InputState, value: f32) {
                 self.deref_mut().progress_bar(rect, dir, state, value);
             }
+            fn badge(&mut self, rect: Rect, state: InputState) {
+                self.deref_mut().badge(rect, state);
+            }
             fn image(&mut self, id: ImageId, rect: Rect) {
                 self.deref_mut().image(id, rect);
             }
+            fn symbol_color(&self, class: SymbolClass, state: InputState) -> Rgba {
+                self.deref().symbol_color(class, state)
+            }
         }
     };
 }
 
+impl_! { ('a, H: DrawHandle + ?Sized) DrawHandle for &'a mut H }
 impl_! { (H: DrawHandle) DrawHandle for Box<H> }
 #[cfg(feature = "stack_dst")]
 impl_! {
@@ -0,0 +1,482 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Icon registry
+//!
+//! Icons are identified by name (see [`IconName`]). A built-in set provides
+//! each [`StandardIcon`]; applications may [`register`] additional icons or
+//! replace built-in ones (for example, to match a platform icon theme).
+//!
+//! Icons are resolved to images on demand via [`get`] (or drawn via
+//! [`DrawCtx::icon`]) at a size chosen by the theme (see [`SizeMgr::icon`]).
+//! Sources may be SVG (requires feature `svg`) or encoded bitmaps (e.g. PNG)
+//! in one or more sizes. *Symbolic* icons use only the alpha channel of the
+//! source and are coloured by the theme (see [`DrawCtx::symbol_color`]); all
+//! built-in icons are symbolic.
+//!
+//! Resolved images are cached (up to [`MAX_IMAGES`], evicting the least
+//! recently used). The registry is thread-local and should be used from the
+//! UI thread.
+
+#[allow(unused)]
+use super::{DrawCtx, SizeMgr};
+use crate::cast::Conv;
+use crate::draw::color::{Rgba, Rgba8Srgb};
+use crate::draw::{DrawShared, ImageError, ImageFormat, ImageId};
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use thiserror::Error;
+
+/// Maximum number of resolved images to cache
+///
+/// When exceeded, the least recently used image is freed.
+pub const MAX_IMAGES: usize = 256;
+
+/// Standard icons
+///
+/// Names follow the [freedesktop.org icon naming specification](https://specifications.freedesktop.org/icon-naming-spec/latest/).
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum StandardIcon {
+    /// `document-open`
+    Open,
+    /// `document-save`
+    Save,
+    /// `window-close`
+    Close,
+    /// `edit-find`
+    Search,
+    /// `dialog-warning`
+    Warning,
+    /// `dialog-error`
+    Error,
+    /// `dialog-information`
+    Information,
+    /// `dialog-question`
+    Question,
+    /// `list-add`
+    Add,
+    /// `list-remove`
+    Remove,
+    /// `edit-undo`
+    Undo,
+    /// `edit-redo`
+    Redo,
+}
+
+impl StandardIcon {
+    /// All standard icons
+    pub const ALL: [StandardIcon; 12] = [
+        StandardIcon::Open,
+        StandardIcon::Save,
+        StandardIcon::Close,
+        StandardIcon::Search,
+        StandardIcon::Warning,
+        StandardIcon::Error,
+        StandardIcon::Information,
+        StandardIcon::Question,
+        StandardIcon::Add,
+        StandardIcon::Remove,
+        StandardIcon::Undo,
+        StandardIcon::Redo,
+    ];
+
+    /// Get the icon's name
+    pub fn name(self) -> &'static str {
+        match self {
+            StandardIcon::Open => "document-open",
+            StandardIcon::Save => "document-save",
+            StandardIcon::Close => "window-close",
+            StandardIcon::Search => "edit-find",
+            StandardIcon::Warning => "dialog-warning",
+            StandardIcon::Error => "dialog-error",
+            StandardIcon::Information => "dialog-information",
+            StandardIcon::Question => "dialog-question",
+            StandardIcon::Add => "list-add",
+            StandardIcon::Remove => "list-remove",
+            StandardIcon::Undo => "edit-undo",
+            StandardIcon::Redo => "edit-redo",
+        }
+    }
+
+    /// Built-in SVG data
+    fn builtin(self) -> &'static [u8] {
+        match self {
+            StandardIcon::Open => include_bytes!("../../res/icons/document-open.svg"),
+            StandardIcon::Save => include_bytes!("../../res/icons/document-save.svg"),
+            StandardIcon::Close => include_bytes!("../../res/icons/window-close.svg"),
+            StandardIcon::Search => include_bytes!("../../res/icons/edit-find.svg"),
+            StandardIcon::Warning => include_bytes!("../../res/icons/dialog-warning.svg"),
+            StandardIcon::Error => include_bytes!("../../res/icons/dialog-error.svg"),
+            StandardIcon::Information => {
+                include_bytes!("../../res/icons/dialog-information.svg")
+            }
+            StandardIcon::Question => include_bytes!("../../res/icons/dialog-question.svg"),
+            StandardIcon::Add => include_bytes!("../../res/icons/list-add.svg"),
+            StandardIcon::Remove => include_bytes!("../../res/icons/list-remove.svg"),
+            StandardIcon::Undo => include_bytes!("../../res/icons/edit-undo.svg"),
+            StandardIcon::Redo => include_bytes!("../../res/icons/edit-redo.svg"),
+        }
+    }
+}
+
+/// Icon name
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct IconName(Cow<'static, str>);
+
+impl IconName {
+    /// Get the name as a `str`
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<&'static str> for IconName {
+    #[inline]
+    fn from(name: &'static str) -> Self {
+        IconName(Cow::Borrowed(name))
+    }
+}
+
+impl From<String> for IconName {
+    #[inline]
+    fn from(name: String) -> Self {
+        IconName(Cow::Owned(name))
+    }
+}
+
+impl From<StandardIcon> for IconName {
+    #[inline]
+    fn from(icon: StandardIcon) -> Self {
+        IconName(Cow::Borrowed(icon.name()))
+    }
+}
+
+impl fmt::Display for IconName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Icon size class
+///
+/// Actual sizes are chosen by the theme; see [`SizeMgr::icon`].
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum IconSize {
+    /// Size for use within buttons, menus and alongside text
+    Small,
+    /// Size for tool bars and similar
+    Medium,
+    /// Size for use in dialogs
+    Large,
+}
+
+/// Default size: Small
+impl Default for IconSize {
+    fn default() -> Self {
+        IconSize::Small
+    }
+}
+
+/// Source data for an icon
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum IconSource {
+    /// SVG data
+    ///
+    /// Requires feature `svg`; without this, SVG sources are ignored.
+    Svg(Cow<'static, [u8]>),
+    /// Encoded bitmap data (any format supported by the `image` crate)
+    ///
+    /// The nominal `side` length (in pixels) is used to select the best
+    /// source for the required size; the image is scaled when drawn.
+    Bitmap { side: u32, data: Cow<'static, [u8]> },
+}
+
+/// An icon definition: one or more sources
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct IconData {
+    sources: Vec<IconSource>,
+    symbolic: bool,
+}
+
+impl IconData {
+    /// Construct with a single source
+    pub fn new(source: IconSource) -> Self {
+        IconData {
+            sources: vec![source],
+            symbolic: false,
+        }
+    }
+
+    /// Add a source (chain style)
+    ///
+    /// When feature `svg` is enabled, SVG sources are preferred. Otherwise,
+    /// the smallest bitmap at least as large as required is used (or the
+    /// largest bitmap if none is large enough).
+    #[must_use]
+    pub fn with_source(mut self, source: IconSource) -> Self {
+        self.sources.push(source);
+        self
+    }
+
+    /// Set whether the icon is symbolic (chain style)
+    ///
+    /// Symbolic icons use only the alpha channel of the source.
+    #[must_use]
+    pub fn with_symbolic(mut self, symbolic: bool) -> Self {
+        self.symbolic = symbolic;
+        self
+    }
+
+    /// True if symbolic
+    #[inline]
+    pub fn is_symbolic(&self) -> bool {
+        self.symbolic
+    }
+
+    /// Select a source for the given side length
+    fn select(&self, side: u32) -> Option<&IconSource> {
+        let mut best: Option<(u32, &IconSource)> = None;
+        for source in &self.sources {
+            match source {
+                IconSource::Svg(_) if cfg!(feature = "svg") => return Some(source),
+                IconSource::Svg(_) => (),
+                IconSource::Bitmap { side: s, .. } => {
+                    let better = match best {
+                        None => true,
+                        Some((b, _)) if b < side => *s > b,
+                        Some((b, _)) => side <= *s && *s < b,
+                    };
+                    if better {
+                        best = Some((*s, source));
+                    }
+                }
+            }
+        }
+        best.map(|(_, source)| source)
+    }
+}
+
+impl From<IconSource> for IconData {
+    #[inline]
+    fn from(source: IconSource) -> Self {
+        IconData::new(source)
+    }
+}
+
+#[derive(Error, Debug)]
+enum LoadError {
+    #[error("no usable source")]
+    NoSource,
+    #[error(transparent)]
+    Image(#[from] image::ImageError),
+    #[cfg(feature = "svg")]
+    #[error(transparent)]
+    Svg(#[from] usvg::Error),
+    #[cfg(feature = "svg")]
+    #[error("failed to render SVG")]
+    Render,
+    #[error(transparent)]
+    Alloc(#[from] ImageError),
+}
+
+type ImageKey = (IconName, u32, Option<Rgba8Srgb>);
+
+struct CachedImage {
+    /// Image; `None` records a load failure
+    id: Option<ImageId>,
+    /// Value of [`Registry::tick`] when last used
+    last_used: u64,
+}
+
+struct Registry {
+    icons: HashMap<IconName, IconData>,
+    images: HashMap<ImageKey, CachedImage>,
+    /// Images to free
+    stale: Vec<ImageId>,
+    /// Incremented on each call to [`get`]
+    tick: u64,
+}
+
+impl Registry {
+    fn new() -> Self {
+        let icons = StandardIcon::ALL
+            .iter()
+            .map(|icon| {
+                let source = IconSource::Svg(Cow::Borrowed(icon.builtin()));
+                let data = IconData::new(source).with_symbolic(true);
+                (IconName::from(*icon), data)
+            })
+            .collect();
+        Registry {
+            icons,
+            images: HashMap::new(),
+            stale: vec![],
+            tick: 0,
+        }
+    }
+}
+
+thread_local! {
+    static REGISTRY: RefCell<Registry> = RefCell::new(Registry::new());
+}
+
+/// Register an icon
+///
+/// This replaces any existing icon of the same `name`. Images resolved from
+/// the replaced icon are freed on the next call to [`get`].
+pub fn register<N: Into<IconName>>(name: N, data: IconData) {
+    let name = name.into();
+    REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        let Registry {
+            icons,
+            images,
+            stale,
+            ..
+        } = &mut *registry;
+        images.retain(|key, image| {
+            if key.0 == name {
+                stale.extend(image.id.take());
+                false
+            } else {
+                true
+            }
+        });
+        icons.insert(name, data);
+    });
+}
+
+/// Register an icon under a name derived from its content
+///
+/// This is useful for one-off icons, e.g. an SVG image shown on a single
+/// button. The generated name is returned. Registering equal `data` again
+/// returns the same name (without adding a new entry).
+pub fn register_unique(data: IconData) -> IconName {
+    let mut hasher = DefaultHasher::new();
+    data.hash(&mut hasher);
+    let mut hash = hasher.finish();
+    REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        loop {
+            let name = IconName::from(format!("kas-unique-{:016x}", hash));
+            match registry.icons.get(&name) {
+                Some(existing) if *existing == data => return name,
+                // Hash collision: try the next name
+                Some(_) => hash = hash.wrapping_add(1),
+                None => {
+                    registry.icons.insert(name.clone(), data);
+                    return name;
+                }
+            }
+        }
+    })
+}
+
+/// True if an icon with this `name` is registered
+pub fn contains(name: &IconName) -> bool {
+    REGISTRY.with(|registry| registry.borrow().icons.contains_key(name))
+}
+
+/// Get an image of icon `name` with the given `side` length (in pixels)
+///
+/// Symbolic icons are coloured with `col`; for other icons this is ignored.
+/// Images are cached and shared between users. The returned image may be
+/// freed by a later call (see [`MAX_IMAGES`]), thus should not be retained
+/// beyond the current frame.
+///
+/// Returns `None` if no icon of this name is registered or if loading fails
+/// (in which case a warning is logged once).
+pub fn get(ds: &mut dyn DrawShared, name: &IconName, side: i32, col: Rgba) -> Option<ImageId> {
+    let side = u32::conv(side.max(1));
+    REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        let Registry {
+            icons,
+            images,
+            stale,
+            tick,
+            ..
+        } = &mut *registry;
+        for id in stale.drain(..) {
+            ds.image_free(id);
+        }
+        *tick += 1;
+
+        let data = icons.get(name)?;
+        let col = data.symbolic.then(|| Rgba8Srgb::from(col));
+        let key = (name.clone(), side, col);
+        if let Some(image) = images.get_mut(&key) {
+            image.last_used = *tick;
+            return image.id;
+        }
+
+        if images.len() >= MAX_IMAGES {
+            let lru = images
+                .iter()
+                .min_by_key(|(_, image)| image.last_used)
+                .map(|(key, _)| key.clone());
+            if let Some(image) = lru.and_then(|key| images.remove(&key)) {
+                if let Some(id) = image.id {
+                    ds.image_free(id);
+                }
+            }
+        }
+
+        let id = match load(ds, data, side, col) {
+            Ok(id) => Some(id),
+            Err(e) => {
+                log::warn!("icons: failed to load \"{}\": {}", name, e);
+                None
+            }
+        };
+        let last_used = *tick;
+        images.insert(key, CachedImage { id, last_used });
+        id
+    })
+}
+
+fn load(
+    ds: &mut dyn DrawShared,
+    data: &IconData,
+    side: u32,
+    col: Option<Rgba8Srgb>,
+) -> Result<ImageId, LoadError> {
+    let (size, mut rgba) = match data.select(side) {
+        #[cfg(feature = "svg")]
+        Some(IconSource::Svg(svg)) => raster_svg(svg, side)?,
+        Some(IconSource::Bitmap { data, .. }) => {
+            let image = image::load_from_memory(data)?.into_rgba8();
+            (image.dimensions(), image.into_raw())
+        }
+        _ => return Err(LoadError::NoSource),
+    };
+
+    if let Some(col) = col {
+        let col: [u8; 4] = col.into();
+        for pixel in rgba.chunks_exact_mut(4) {
+            let a = u16::from(pixel[3]) * u16::from(col[3]) / 255;
+            pixel.copy_from_slice(&[col[0], col[1], col[2], u8::conv(a)]);
+        }
+    }
+
+    let id = ds.image_alloc(size)?;
+    ds.image_upload(id, &rgba, ImageFormat::Rgba8);
+    Ok(id)
+}
+
+#[cfg(feature = "svg")]
+fn raster_svg(data: &[u8], side: u32) -> Result<((u32, u32), Vec<u8>), LoadError> {
+    let opts = usvg::Options::default();
+    let tree = usvg::Tree::from_data(data, &opts.to_ref())?;
+    let mut pixmap = tiny_skia::Pixmap::new(side, side).ok_or(LoadError::Render)?;
+    resvg::render(&tree, usvg::FitTo::Height(side), pixmap.as_mut()).ok_or(LoadError::Render)?;
+    Ok(((side, side), pixmap.take()))
+}
@@ -6,9 +6,11 @@
 //! Theme APIs
 
 mod draw;
+pub mod icons;
 mod size;
 
 pub use draw::{DrawCtx, DrawHandle, DrawMgr};
+pub use icons::{IconName, IconSize, StandardIcon};
 pub use size::{SizeHandle, SizeMgr};
 
 #[allow(unused)]
@@ -43,6 +45,12 @@ bitflags! {
         /// "Selection focus" allows things such as text to be selected. Selection
         /// focus implies that the widget also has character focus.
         const SEL_FOCUS = 1 << 6;
+        /// Navigation focus was set via the keyboard
+        ///
+        /// This is only set together with `NAV_FOCUS`. Themes may use this to
+        /// show focus indicators only on keyboard navigation (like the CSS
+        /// `:focus-visible` selector).
+        const KEY_FOCUS = 1 << 7;
     }
 }
 
@@ -77,6 +85,12 @@ impl InputState {
         self.contains(InputState::NAV_FOCUS)
     }
 
+    /// Extract `KEY_FOCUS` bit
+    #[inline]
+    pub fn key_focus(self) -> bool {
+        self.contains(InputState::KEY_FOCUS)
+    }
+
     /// Extract `CHAR_FOCUS` bit
     #[inline]
     pub fn char_focus(self) -> bool {
@@ -90,6 +104,53 @@ impl InputState {
     }
 }
 
+/// State of a checkbox
+///
+/// In addition to the usual checked and unchecked states, a checkbox may be
+/// *indeterminate* (mixed), e.g. for a "select all" control over a partial
+/// selection.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum CheckState {
+    /// Not checked
+    Unchecked,
+    /// Checked
+    Checked,
+    /// Indeterminate (mixed)
+    Indeterminate,
+}
+
+impl CheckState {
+    /// True if `Checked`
+    #[inline]
+    pub fn is_checked(self) -> bool {
+        self == CheckState::Checked
+    }
+
+    /// True if `Indeterminate`
+    #[inline]
+    pub fn is_indeterminate(self) -> bool {
+        self == CheckState::Indeterminate
+    }
+}
+
+/// Default state: Unchecked
+impl Default for CheckState {
+    fn default() -> Self {
+        CheckState::Unchecked
+    }
+}
+
+impl From<bool> for CheckState {
+    #[inline]
+    fn from(checked: bool) -> Self {
+        match checked {
+            false => CheckState::Unchecked,
+            true => CheckState::Checked,
+        }
+    }
+}
+
 /// Class of text drawn
 ///
 /// Themes choose font, font size, colour, and alignment based on this.
@@ -124,6 +185,26 @@ impl Default for TextClass {
     }
 }
 
+/// Colour class of a symbolic (single-colour) icon
+///
+/// Themes choose the colour of symbolic icons based on this and the
+/// widget's state.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Ord, PartialOrd, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SymbolClass {
+    /// Icon uses the text colour
+    Text,
+    /// Icon uses the accent colour
+    Accent,
+}
+
+/// Default class: Text
+impl Default for SymbolClass {
+    fn default() -> Self {
+        SymbolClass::Text
+    }
+}
+
 /// Interface through which a theme can be adjusted at run-time
 ///
 /// All methods return a [`TkAction`] to enable correct action when a theme
@@ -7,10 +7,10 @@
 
 use std::ops::Deref;
 
-use super::TextClass;
 #[allow(unused)]
 use super::{DrawCtx, DrawMgr};
-use crate::geom::Size;
+use super::{IconSize, TextClass};
+use crate::geom::{PixelAlign, Size};
 use crate::layout::{AxisInfo, FrameRules, Margins, SizeRules};
 use crate::text::TextApi;
 // for doc use
@@ -73,6 +73,13 @@ impl<'a> SizeMgr<'a> {
         px * self.scale_factor()
     }
 
+    /// Get a helper to align geometry to physical pixels
+    ///
+    /// Use this to round line thicknesses calculated from virtual pixels.
+    pub fn pixel_align(&self) -> PixelAlign {
+        PixelAlign::new(self.scale_factor())
+    }
+
     /// Convert a size in font Points to physical pixels
     pub fn pixels_from_points(&self, pt: f32) -> f32 {
         self.0.pixels_from_points(pt)
@@ -190,6 +197,11 @@ impl<'a> SizeMgr<'a> {
         self.0.radiobox()
     }
 
+    /// Size of the element drawn by [`DrawCtx::switch`].
+    pub fn switch(&self) -> Size {
+        self.0.switch()
+    }
+
     /// Dimensions for a scrollbar
     ///
     /// Returns:
@@ -224,6 +236,13 @@ impl<'a> SizeMgr<'a> {
     pub fn progress_bar(&self) -> Size {
         self.0.progress_bar()
     }
+
+    /// Side length of a (square) icon of the given `size`
+    ///
+    /// See [`crate::theme::icons`].
+    pub fn icon(&self, size: IconSize) -> i32 {
+        self.0.icon(size)
+    }
 }
 
 /// A handle to the active theme, used for sizing
@@ -311,6 +330,9 @@ pub trait SizeHandle {
     /// Size of the element drawn by [`DrawCtx::radiobox`].
     fn radiobox(&self) -> Size;
 
+    /// Size of the element drawn by [`DrawCtx::switch`].
+    fn switch(&self) -> Size;
+
     /// Dimensions for a scrollbar
     ///
     /// Returns:
@@ -339,6 +361,9 @@ pub trait SizeHandle {
     /// that the width is adjustable while the height is (preferably) not.
     /// For a vertical bar, the values are swapped.
     fn progress_bar(&self) -> Size;
+
+    /// Side length of a (square) icon of the given `size`
+    fn icon(&self, size: IconSize) -> i32;
 }
 
 macro_rules! impl_ {
@@ -402,6 +427,9 @@ macro_rules! impl_ {
             fn radiobox(&self) -> Size {
                 self.deref().radiobox()
             }
+            fn switch(&self) -> Size {
+                self.deref().switch()
+            }
             fn scrollbar(&self) -> (Size, i32) {
                 self.deref().scrollbar()
             }
@@ -411,6 +439,9 @@ macro_rules! impl_ {
             fn progress_bar(&self) -> Size {
                 self.deref().progress_bar()
             }
+            fn icon(&self, size: IconSize) -> i32 {
+                self.deref().icon(size)
+            }
         }
     };
 }
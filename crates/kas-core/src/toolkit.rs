@@ -15,7 +15,9 @@
 use crate::draw::DrawShared;
 use crate::event;
 use crate::event::UpdateHandle;
+use crate::geom::{Offset, Size};
 use crate::theme::{SizeHandle, ThemeControl};
+use crate::WidgetId;
 use std::num::NonZeroU32;
 
 /// Identifier for a window or pop-up
@@ -48,6 +50,11 @@ bitflags! {
     /// Users receiving a value of this type from a widget update method should
     /// generally call `*mgr |= action;` during event handling. Prior to
     /// starting the event loop (`toolkit.run()`), these values can be ignored.
+    ///
+    /// Actions are scoped to a single window: those sent via an
+    /// [`event::EventMgr`] apply only to the window being handled. Use
+    /// [`event::EventMgr::send_action_to`] to target another window, or
+    /// [`TkAction::Widget`] to scope an action to a single widget.
     #[must_use]
     #[derive(Default)]
     pub struct TkAction: u32 {
@@ -74,6 +81,14 @@ bitflags! {
         const RESIZE = 1 << 9;
         /// Update theme memory
         const THEME_UPDATE = 1 << 10;
+        /// Resize the window to its content
+        ///
+        /// Size requirements are recalculated and the window is resized to
+        /// its ideal size (unless maximized), for example after content was
+        /// expanded or collapsed.
+        ///
+        /// Implies [`TkAction::RESIZE`].
+        const SIZE_TO_CONTENT = 1 << 11;
         /// Window requires reconfiguring
         ///
         /// *Configuring* widgets assigns [`WidgetId`] identifiers and calls
@@ -88,6 +103,34 @@ bitflags! {
     }
 }
 
+impl TkAction {
+    /// Scope action `kind` to widget `id`
+    ///
+    /// The result is applied via `*mgr |= TkAction::Widget(id, kind)`; see
+    /// [`WidgetAction`].
+    #[allow(non_snake_case)]
+    #[inline]
+    pub fn Widget(id: WidgetId, kind: TkAction) -> WidgetAction {
+        WidgetAction { id, kind }
+    }
+}
+
+/// A [`TkAction`] scoped to a widget
+///
+/// This is constructed via [`TkAction::Widget`] and applied to the widget's
+/// window via [`event::EventState::send_widget_action`] (or `*mgr |= action`).
+/// [`TkAction::REDRAW`] is sent via [`event::EventState::redraw`] and
+/// [`TkAction::RECONFIGURE`] is narrowed to the widget's sub-tree (see
+/// [`event::EventState::reconfigure_subtree`]); other actions apply to the
+/// whole window.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WidgetAction {
+    /// The target widget
+    pub id: WidgetId,
+    /// The action
+    pub kind: TkAction,
+}
+
 /// Shell-specific window management and style interface.
 ///
 /// This is implemented by a KAS shell, per window.
@@ -118,6 +161,13 @@ pub trait ShellWindow {
     /// Close a window
     fn close_window(&mut self, id: WindowId);
 
+    /// Send an action to a window
+    ///
+    /// The `action` applies only to window `id`. If `id` is a pop-up,
+    /// [`TkAction::CLOSE`] closes the pop-up while other actions apply to its
+    /// parent window.
+    fn send_action_to(&mut self, id: WindowId, action: TkAction);
+
     /// Updates all subscribed widgets
     ///
     /// All widgets subscribed to the given [`UpdateHandle`], across all
@@ -139,6 +189,18 @@ pub trait ShellWindow {
     /// returned from the closure.
     fn adjust_theme(&mut self, f: &mut dyn FnMut(&mut dyn ThemeControl) -> TkAction);
 
+    /// Set the text zoom factor of this window
+    ///
+    /// Font sizes in this window are the theme's font size multiplied by
+    /// `zoom`. The caller should resize the window afterwards.
+    fn set_text_zoom(&mut self, zoom: f32);
+
+    /// Save configuration
+    ///
+    /// Writes event and theme configuration to disk, where supported and
+    /// enabled by the shell. This normally happens on exit anyway.
+    fn save_config(&mut self);
+
     /// Access a [`SizeHandle`] and a [`DrawShared`]
     ///
     /// Implementations should call the given function argument once; not doing
@@ -146,6 +208,61 @@ pub trait ShellWindow {
     /// User-code *must not* depend on `f` being called for memory safety.
     fn size_and_draw_shared(&mut self, f: &mut dyn FnMut(&mut dyn SizeHandle, &mut dyn DrawShared));
 
+    /// Render a widget to an image
+    ///
+    /// The `widget` (which must be configured) is laid out within a rect of
+    /// the given `size` and drawn offscreen using this window's theme and
+    /// scale factor. Returns the image size and RGBA (sRGB) data, or `None` on
+    /// failure or if unsupported.
+    fn render_image(
+        &mut self,
+        widget: &mut dyn crate::WidgetConfig,
+        size: Size,
+        state: &mut event::EventState,
+    ) -> Option<((u32, u32), Vec<u8>)>;
+
     /// Set the mouse cursor
     fn set_cursor_icon(&mut self, icon: event::CursorIcon);
+
+    /// Start moving the window with the mouse
+    ///
+    /// The window follows the mouse until the button is released. This should
+    /// only be called in response to a mouse button press.
+    fn drag_window(&mut self);
+
+    /// Move and resize the window
+    ///
+    /// The window's position is adjusted by `offset` (where supported) and its
+    /// inner size set to `size`. Units are physical pixels.
+    fn resize_window(&mut self, offset: Offset, size: Size);
+
+    /// Get the window's inner size
+    ///
+    /// Units are physical pixels. Returns [`Size::ZERO`] if unknown.
+    fn inner_size(&self) -> Size;
+
+    /// Set whether the window is minimized
+    fn set_minimized(&mut self, minimized: bool);
+
+    /// Set whether the window is maximized
+    fn set_maximized(&mut self, maximized: bool);
+
+    /// Whether the window is maximized
+    fn is_maximized(&self) -> bool;
+
+    /// Enumerate displays
+    ///
+    /// Returns an empty list if not supported.
+    fn displays(&self) -> crate::shell::Displays;
+
+    /// Set window level and behaviour flags
+    ///
+    /// Flags which cannot be changed after window creation are ignored.
+    fn set_window_flags(&mut self, flags: crate::WindowFlags);
+
+    /// Request haptic feedback (e.g. a short vibration)
+    ///
+    /// This is used to confirm recognition of a long press. Implementations
+    /// should do nothing where unsupported.
+    fn haptic_feedback(&mut self);
 }
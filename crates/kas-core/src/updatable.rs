@@ -14,11 +14,18 @@
 //!     other data may request updates; may also implement self-updates
 //! -   [`UpdatableHandler`]: allows data updates from widget messages (or
 //!     potentially from other message sources)
+//!
+//! Derived data nodes ([`Map`], [`Zip`], [`Computed`]) compute a value from
+//! other shared data.
 
 mod data_impls;
 mod data_traits;
+mod derived;
 pub mod filter;
+mod load;
 mod shared_rc;
+mod sorted;
+mod table;
 
 use crate::event::UpdateHandle;
 #[allow(unused)] // doc links
@@ -29,7 +36,11 @@ use std::ops::Deref;
 pub use data_traits::{
     ListData, ListDataMut, MatrixData, MatrixDataMut, SingleData, SingleDataMut,
 };
+pub use derived::{Computed, Map, Zip};
+pub use load::{LoadOnDemand, LoadState};
 pub use shared_rc::SharedRc;
+pub use sorted::{SortDirection, SortKey, SortedData};
+pub use table::{Cell, Column, ColumnType, CsvError, Table};
 
 /// Shared (data) objects which may notify of updates
 pub trait Updatable: Debug {
@@ -40,6 +51,15 @@ pub trait Updatable: Debug {
     /// other users of the data of the update, and return that here.
     /// If the data is constant (not updatable) this may simply return `None`.
     fn update_handle(&self) -> Option<UpdateHandle>;
+
+    /// Get all update handles used to notify of updates
+    ///
+    /// Data derived from multiple sources may use multiple handles; users
+    /// should subscribe to each. By default, this returns the result of
+    /// [`Updatable::update_handle`] (if any).
+    fn update_handles(&self) -> Vec<UpdateHandle> {
+        self.update_handle().into_iter().collect()
+    }
 }
 
 /// Trait for data objects which can handle messages
@@ -76,6 +96,17 @@ impl<T: Debug, M> UpdatableHandler<usize, M> for [T] {
     }
 }
 
+impl<T: Debug> Updatable for Vec<T> {
+    fn update_handle(&self) -> Option<UpdateHandle> {
+        None
+    }
+}
+impl<T: Debug, M> UpdatableHandler<usize, M> for Vec<T> {
+    fn handle(&self, _: &usize, _: &M) -> Option<UpdateHandle> {
+        None
+    }
+}
+
 impl<K: Ord + Eq + Clone + Debug, T: Clone + Debug> Updatable for std::collections::BTreeMap<K, T> {
     fn update_handle(&self) -> Option<UpdateHandle> {
         None
@@ -95,6 +126,9 @@ macro_rules! impl_via_deref {
             fn update_handle(&self) -> Option<UpdateHandle> {
                 self.deref().update_handle()
             }
+            fn update_handles(&self) -> Vec<UpdateHandle> {
+                self.deref().update_handles()
+            }
         }
         impl<K, M, $t: UpdatableHandler<K, M> + ?Sized> UpdatableHandler<K, M> for $derived {
             fn handle(&self, key: &K, msg: &M) -> Option<UpdateHandle> {
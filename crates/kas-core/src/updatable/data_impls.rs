@@ -8,7 +8,7 @@
 use super::*;
 use crate::event::UpdateHandle;
 use std::fmt::Debug;
-use std::ops::{Deref, DerefMut};
+use std::ops::{Deref, DerefMut, Range};
 
 impl<T: Clone + Debug> ListData for [T] {
     type Key = usize;
@@ -54,6 +54,45 @@ impl<T: Clone + Debug> ListDataMut for [T] {
     }
 }
 
+impl<T: Clone + Debug> ListData for Vec<T> {
+    type Key = usize;
+    type Item = T;
+
+    fn version(&self) -> u64 {
+        0
+    }
+
+    fn len(&self) -> usize {
+        (*self).len()
+    }
+
+    fn contains_key(&self, key: &Self::Key) -> bool {
+        *key < self.len()
+    }
+
+    fn get_cloned(&self, key: &usize) -> Option<Self::Item> {
+        self.get(*key).cloned()
+    }
+
+    fn update(&self, _: &Self::Key, _: Self::Item) -> Option<UpdateHandle> {
+        // Note: plain Vec<T> does not support update, but SharedRc<Vec<T>> does.
+        None
+    }
+
+    fn iter_vec(&self, limit: usize) -> Vec<(Self::Key, Self::Item)> {
+        self.as_slice().iter_vec(limit)
+    }
+
+    fn iter_vec_from(&self, start: usize, limit: usize) -> Vec<(Self::Key, Self::Item)> {
+        self.as_slice().iter_vec_from(start, limit)
+    }
+}
+impl<T: Clone + Debug> ListDataMut for Vec<T> {
+    fn set(&mut self, key: &Self::Key, item: Self::Item) {
+        self[*key] = item;
+    }
+}
+
 impl<K: Ord + Eq + Clone + Debug, T: Clone + Debug> ListData for std::collections::BTreeMap<K, T> {
     type Key = K;
     type Item = T;
@@ -143,6 +182,13 @@ macro_rules! impl_via_deref {
             fn iter_vec_from(&self, start: usize, limit: usize) -> Vec<(Self::Key, Self::Item)> {
                 self.deref().iter_vec_from(start, limit)
             }
+
+            fn request_range(&self, range: Range<usize>) {
+                self.deref().request_range(range)
+            }
+            fn load_state(&self, key: &Self::Key) -> LoadState {
+                self.deref().load_state(key)
+            }
         }
 
         impl<$t: MatrixData + ?Sized> MatrixData for $derived {
@@ -189,6 +235,13 @@ macro_rules! impl_via_deref {
             fn make_key(row: &Self::RowKey, col: &Self::ColKey) -> Self::Key {
                 <$t>::make_key(row, col)
             }
+
+            fn request_range(&self, cols: Range<usize>, rows: Range<usize>) {
+                self.deref().request_range(cols, rows)
+            }
+            fn load_state(&self, key: &Self::Key) -> LoadState {
+                self.deref().load_state(key)
+            }
         }
     };
     ($t: ident: $derived:ty, $($dd:ty),+) => {
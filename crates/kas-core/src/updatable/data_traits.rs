@@ -6,11 +6,13 @@
 //! Traits for shared data objects
 
 use crate::event::UpdateHandle;
+use crate::updatable::LoadState;
 #[allow(unused)] // doc links
-use crate::updatable::Updatable;
+use crate::updatable::{LoadOnDemand, Updatable};
 #[allow(unused)] // doc links
 use std::cell::RefCell;
 use std::fmt::Debug;
+use std::ops::Range;
 
 /// Trait for viewable single data items
 // Note: we require Debug + 'static to allow widgets using this to implement
@@ -110,6 +112,22 @@ pub trait ListData: Debug {
     ///
     /// The result is the same as `self.iter_vec(start + limit).skip(start)`.
     fn iter_vec_from(&self, start: usize, limit: usize) -> Vec<(Self::Key, Self::Item)>;
+
+    /// Notify of the range of items about to be viewed
+    ///
+    /// Views call this with a range of indices (as used by
+    /// [`Self::iter_vec_from`]) before retrieving items. Data sources which load
+    /// items on demand (e.g. [`LoadOnDemand`]) may use this to begin fetching
+    /// missing items. The default implementation does nothing.
+    fn request_range(&self, _range: Range<usize>) {}
+
+    /// Get the load state of an item
+    ///
+    /// Items which are not [`LoadState::Ready`] may be placeholders. The
+    /// default implementation returns [`LoadState::Ready`].
+    fn load_state(&self, _key: &Self::Key) -> LoadState {
+        LoadState::Ready
+    }
 }
 
 /// Trait for writable data lists
@@ -201,6 +219,23 @@ pub trait MatrixData: Debug {
 
     /// Make a key from parts
     fn make_key(row: &Self::RowKey, col: &Self::ColKey) -> Self::Key;
+
+    /// Notify of the ranges of columns and rows about to be viewed
+    ///
+    /// Views call this with ranges of indices (as used by
+    /// [`Self::col_iter_vec_from`] and [`Self::row_iter_vec_from`]) before
+    /// retrieving items. Data sources which load items on demand may use this
+    /// to begin fetching missing items. The default implementation does
+    /// nothing.
+    fn request_range(&self, _cols: Range<usize>, _rows: Range<usize>) {}
+
+    /// Get the load state of an item
+    ///
+    /// Items which are not [`LoadState::Ready`] may be placeholders. The
+    /// default implementation returns [`LoadState::Ready`].
+    fn load_state(&self, _key: &Self::Key) -> LoadState {
+        LoadState::Ready
+    }
 }
 
 /// Trait for writable data matrices
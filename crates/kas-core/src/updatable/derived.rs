@@ -0,0 +1,218 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Derived (computed) data nodes
+//!
+//! These types derive a value from one or more shared sources. Update handles
+//! of all sources are propagated via [`Updatable::update_handles`], thus a view
+//! over a derived node is updated whenever any source changes.
+//!
+//! Derived nodes are read-only: [`SingleData::update`] does nothing and
+//! returns `None`.
+
+use crate::event::UpdateHandle;
+use crate::macros::autoimpl;
+use crate::updatable::*;
+use std::cell::RefCell;
+
+/// Derived data: maps the value of a source through a function
+///
+/// The function is evaluated on every call to [`SingleData::get_cloned`]; for
+/// expensive functions, use [`Computed`] instead.
+///
+/// For example, text derived from two shared counters:
+/// ```
+/// use kas_core::updatable::{Map, SharedRc, SingleData, Zip};
+/// let selected = SharedRc::new(2usize);
+/// let total = SharedRc::new(5usize);
+/// let text = Map::new(Zip::new(selected.clone(), total), |(x, y)| {
+///     format!("{} of {} selected", x, y)
+/// });
+/// assert_eq!(text.get_cloned(), "2 of 5 selected");
+/// selected.update(3);
+/// assert_eq!(text.get_cloned(), "3 of 5 selected");
+/// ```
+#[autoimpl(Clone where D: Clone)]
+#[autoimpl(Debug skip self.f)]
+pub struct Map<D: SingleData, T> {
+    data: D,
+    f: fn(D::Item) -> T,
+}
+
+impl<D: SingleData, T> Map<D, T> {
+    /// Construct over `data` with mapping function `f`
+    pub fn new(data: D, f: fn(D::Item) -> T) -> Self {
+        Map { data, f }
+    }
+
+    /// Access the source data
+    pub fn inner(&self) -> &D {
+        &self.data
+    }
+}
+
+impl<D: SingleData + Updatable, T> Updatable for Map<D, T> {
+    fn update_handle(&self) -> Option<UpdateHandle> {
+        self.data.update_handle()
+    }
+
+    fn update_handles(&self) -> Vec<UpdateHandle> {
+        self.data.update_handles()
+    }
+}
+
+impl<D: SingleData + Updatable, T, K, M> UpdatableHandler<K, M> for Map<D, T> {
+    fn handle(&self, _: &K, _: &M) -> Option<UpdateHandle> {
+        None
+    }
+}
+
+impl<D: SingleData, T: Clone> SingleData for Map<D, T> {
+    type Item = T;
+
+    fn version(&self) -> u64 {
+        self.data.version()
+    }
+
+    fn get_cloned(&self) -> Self::Item {
+        (self.f)(self.data.get_cloned())
+    }
+
+    fn update(&self, _: Self::Item) -> Option<UpdateHandle> {
+        None
+    }
+}
+
+/// Derived data: pairs the values of two sources
+///
+/// The item type is `(A::Item, B::Item)`. Combine with [`Map`] or [`Computed`]
+/// to derive a value from both, or nest to combine more than two sources.
+#[derive(Clone, Debug)]
+pub struct Zip<A: SingleData, B: SingleData> {
+    a: A,
+    b: B,
+}
+
+impl<A: SingleData, B: SingleData> Zip<A, B> {
+    /// Construct over sources `a` and `b`
+    pub fn new(a: A, b: B) -> Self {
+        Zip { a, b }
+    }
+
+    /// Access the source data
+    pub fn inner(&self) -> (&A, &B) {
+        (&self.a, &self.b)
+    }
+}
+
+impl<A: SingleData + Updatable, B: SingleData + Updatable> Updatable for Zip<A, B> {
+    /// Returns the first source's handle, if any
+    ///
+    /// Use [`Updatable::update_handles`] to get the handles of all sources.
+    fn update_handle(&self) -> Option<UpdateHandle> {
+        self.a.update_handle().or_else(|| self.b.update_handle())
+    }
+
+    fn update_handles(&self) -> Vec<UpdateHandle> {
+        let mut handles = self.a.update_handles();
+        for handle in self.b.update_handles() {
+            if !handles.contains(&handle) {
+                handles.push(handle);
+            }
+        }
+        handles
+    }
+}
+
+impl<A: SingleData + Updatable, B: SingleData + Updatable, K, M> UpdatableHandler<K, M>
+    for Zip<A, B>
+{
+    fn handle(&self, _: &K, _: &M) -> Option<UpdateHandle> {
+        None
+    }
+}
+
+impl<A: SingleData, B: SingleData> SingleData for Zip<A, B> {
+    type Item = (A::Item, B::Item);
+
+    fn version(&self) -> u64 {
+        self.a.version() + self.b.version()
+    }
+
+    fn get_cloned(&self) -> Self::Item {
+        (self.a.get_cloned(), self.b.get_cloned())
+    }
+
+    fn update(&self, _: Self::Item) -> Option<UpdateHandle> {
+        None
+    }
+}
+
+/// Derived data: a cached computation over a source
+///
+/// This is like [`Map`], except that the result is cached and only recomputed
+/// when the source's [`SingleData::version`] changes. Use [`Zip`] to compute
+/// over multiple sources.
+#[autoimpl(Clone where D: Clone, T: Clone)]
+#[autoimpl(Debug skip self.f, self.cache)]
+pub struct Computed<D: SingleData, T> {
+    data: D,
+    f: fn(D::Item) -> T,
+    cache: RefCell<Option<(u64, T)>>,
+}
+
+impl<D: SingleData, T> Computed<D, T> {
+    /// Construct over `data` with function `f`
+    pub fn new(data: D, f: fn(D::Item) -> T) -> Self {
+        let cache = RefCell::new(None);
+        Computed { data, f, cache }
+    }
+
+    /// Access the source data
+    pub fn inner(&self) -> &D {
+        &self.data
+    }
+}
+
+impl<D: SingleData + Updatable, T> Updatable for Computed<D, T> {
+    fn update_handle(&self) -> Option<UpdateHandle> {
+        self.data.update_handle()
+    }
+
+    fn update_handles(&self) -> Vec<UpdateHandle> {
+        self.data.update_handles()
+    }
+}
+
+impl<D: SingleData + Updatable, T, K, M> UpdatableHandler<K, M> for Computed<D, T> {
+    fn handle(&self, _: &K, _: &M) -> Option<UpdateHandle> {
+        None
+    }
+}
+
+impl<D: SingleData, T: Clone> SingleData for Computed<D, T> {
+    type Item = T;
+
+    fn version(&self) -> u64 {
+        self.data.version()
+    }
+
+    fn get_cloned(&self) -> Self::Item {
+        let version = self.data.version();
+        let mut cache = self.cache.borrow_mut();
+        match cache.as_ref() {
+            Some((v, value)) if *v == version => value.clone(),
+            _ => {
+                let value = (self.f)(self.data.get_cloned());
+                *cache = Some((version, value.clone()));
+                value
+            }
+        }
+    }
+
+    fn update(&self, _: Self::Item) -> Option<UpdateHandle> {
+        None
+    }
+}
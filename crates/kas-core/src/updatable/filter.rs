@@ -18,6 +18,17 @@ pub trait Filter<T>: Updatable + 'static {
     fn matches(&self, item: T) -> bool;
 }
 
+/// A filter which may be evaluated on another thread
+///
+/// Since filters are usually shared (non-[`Send`]) objects, evaluation on a
+/// worker thread uses a snapshot of the filter's current state.
+pub trait AsyncFilter<T>: Filter<T> {
+    /// Get a thread-safe predicate equivalent to [`Filter::matches`]
+    ///
+    /// The result does not track later changes to the filter.
+    fn snapshot(&self) -> Box<dyn Fn(T) -> bool + Send>;
+}
+
 /// Filter: target contains self (case-sensitive string match)
 #[derive(Debug, Default, Clone)]
 pub struct ContainsString(Rc<(UpdateHandle, RefCell<(String, u64)>)>);
@@ -78,6 +89,18 @@ impl Filter<String> for ContainsString {
         Filter::<&str>::matches(self, &item)
     }
 }
+impl<'a> AsyncFilter<&'a str> for ContainsString {
+    fn snapshot(&self) -> Box<dyn Fn(&'a str) -> bool + Send> {
+        let s = self.get_cloned();
+        Box::new(move |item| item.contains(&s))
+    }
+}
+impl AsyncFilter<String> for ContainsString {
+    fn snapshot(&self) -> Box<dyn Fn(String) -> bool + Send> {
+        let s = self.get_cloned();
+        Box::new(move |item| item.contains(&s))
+    }
+}
 
 /// Filter: target contains self (case-insensitive string match)
 ///
@@ -149,3 +172,15 @@ impl Filter<String> for ContainsCaseInsensitive {
         item.to_uppercase().contains(&(self.0).1.borrow().1)
     }
 }
+impl<'a> AsyncFilter<&'a str> for ContainsCaseInsensitive {
+    fn snapshot(&self) -> Box<dyn Fn(&'a str) -> bool + Send> {
+        let u = (self.0).1.borrow().1.clone();
+        Box::new(move |item| item.to_uppercase().contains(&u))
+    }
+}
+impl AsyncFilter<String> for ContainsCaseInsensitive {
+    fn snapshot(&self) -> Box<dyn Fn(String) -> bool + Send> {
+        let u = (self.0).1.borrow().1.clone();
+        Box::new(move |item| item.to_uppercase().contains(&u))
+    }
+}
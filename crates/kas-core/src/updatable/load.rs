@@ -0,0 +1,261 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Data loaded on demand
+
+use crate::event::UpdateHandle;
+use crate::updatable::*;
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Range;
+use std::sync::{Arc, Mutex, MutexGuard};
+
+/// Load state of a data item
+///
+/// See [`ListData::load_state`] and [`MatrixData::load_state`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LoadState {
+    /// Data is available
+    Ready,
+    /// Data is being loaded; a placeholder is available
+    Loading,
+    /// Loading failed; a placeholder is available
+    Failed,
+}
+
+#[derive(Clone, Debug)]
+enum Slot<T> {
+    Loading,
+    Ready(T),
+    Failed,
+}
+
+type Loader<T> = Arc<dyn Fn(&LoadOnDemand<T>, Range<usize>) + Send + Sync>;
+
+struct Inner<T> {
+    len: usize,
+    placeholder: T,
+    items: HashMap<usize, Slot<T>>,
+    loader: Loader<T>,
+    version: u64,
+}
+
+/// A list of data loaded on demand
+///
+/// This is a [`ListData`] (with key type `usize`) over items which are fetched
+/// only when viewed, e.g. from a remote source or slow storage. When a view
+/// requests a range of items (see [`ListData::request_range`]), missing items
+/// are marked as [`LoadState::Loading`] and the `loader` is called with each
+/// contiguous range of missing indices. Until loaded, the `placeholder` is
+/// returned in place of these items.
+///
+/// The loader should begin fetching the requested items (e.g. on a worker
+/// thread) then, once available, call [`LoadOnDemand::insert_range`] (or
+/// [`LoadOnDemand::set_failed`]). This type is thread-safe; since updates are
+/// not made via an `EventMgr`, the caller must notify views of the update,
+/// e.g. via `ToolkitProxy::trigger_update` with the returned handle.
+///
+/// For infinite-scroll feeds, the loader may call [`LoadOnDemand::set_len`] to
+/// extend the list when items near the end are requested.
+///
+/// Example:
+/// ```
+/// use kas_core::updatable::{ListData, LoadOnDemand, LoadState};
+/// let data = LoadOnDemand::new(100, String::new(), |data, range| {
+///     // Usually one would fetch items asynchronously
+///     let items: Vec<_> = range.clone().map(|i| format!("Item {}", i)).collect();
+///     let _ = data.insert_range(range.start, items);
+/// });
+/// assert_eq!(data.load_state(&5), LoadState::Loading);
+/// data.request_range(0..10);
+/// assert_eq!(data.get_cloned(&5), Some("Item 5".to_string()));
+/// assert_eq!(data.get_cloned(&50), Some(String::new()));
+/// ```
+pub struct LoadOnDemand<T>(Arc<(UpdateHandle, Mutex<Inner<T>>)>);
+
+impl<T> Clone for LoadOnDemand<T> {
+    fn clone(&self) -> Self {
+        LoadOnDemand(self.0.clone())
+    }
+}
+
+impl<T> fmt::Debug for LoadOnDemand<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let inner = self.lock();
+        f.debug_struct("LoadOnDemand")
+            .field("handle", &(self.0).0)
+            .field("len", &inner.len)
+            .field("loaded", &inner.items.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> LoadOnDemand<T> {
+    fn lock(&self) -> MutexGuard<Inner<T>> {
+        (self.0).1.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl<T: Clone + Send + 'static> LoadOnDemand<T> {
+    /// Construct with initial length `len`
+    ///
+    /// The `placeholder` is returned for items not (yet) loaded. The `loader`
+    /// is called with the list and a range of indices to load.
+    pub fn new<F>(len: usize, placeholder: T, loader: F) -> Self
+    where
+        F: Fn(&LoadOnDemand<T>, Range<usize>) + Send + Sync + 'static,
+    {
+        let inner = Inner {
+            len,
+            placeholder,
+            items: HashMap::new(),
+            loader: Arc::new(loader),
+            version: 0,
+        };
+        LoadOnDemand(Arc::new((UpdateHandle::new(), Mutex::new(inner))))
+    }
+
+    /// Set the number of items
+    ///
+    /// Items beyond the new length are discarded.
+    pub fn set_len(&self, len: usize) -> UpdateHandle {
+        let mut inner = self.lock();
+        inner.len = len;
+        inner.items.retain(|index, _| *index < len);
+        inner.version += 1;
+        (self.0).0
+    }
+
+    /// Insert a loaded item
+    pub fn insert(&self, index: usize, item: T) -> UpdateHandle {
+        self.insert_range(index, std::iter::once(item))
+    }
+
+    /// Insert loaded items, starting from index `start`
+    ///
+    /// Items beyond the list's length are ignored.
+    pub fn insert_range<I: IntoIterator<Item = T>>(&self, start: usize, items: I) -> UpdateHandle {
+        let mut inner = self.lock();
+        let len = inner.len;
+        for (index, item) in (start..len).zip(items) {
+            inner.items.insert(index, Slot::Ready(item));
+        }
+        inner.version += 1;
+        (self.0).0
+    }
+
+    /// Mark items in `range` as failed to load
+    pub fn set_failed(&self, range: Range<usize>) -> UpdateHandle {
+        let mut inner = self.lock();
+        for index in range {
+            inner.items.insert(index, Slot::Failed);
+        }
+        inner.version += 1;
+        (self.0).0
+    }
+
+    /// Discard items in `range`
+    ///
+    /// These items (including those which failed to load) will be requested
+    /// again when next viewed.
+    pub fn invalidate(&self, range: Range<usize>) -> UpdateHandle {
+        let mut inner = self.lock();
+        inner.items.retain(|index, _| !range.contains(index));
+        inner.version += 1;
+        (self.0).0
+    }
+}
+
+impl<T> Updatable for LoadOnDemand<T> {
+    fn update_handle(&self) -> Option<UpdateHandle> {
+        Some((self.0).0)
+    }
+}
+
+impl<T, K, M> UpdatableHandler<K, M> for LoadOnDemand<T> {
+    fn handle(&self, _: &K, _: &M) -> Option<UpdateHandle> {
+        None
+    }
+}
+
+impl<T: Clone + Send + 'static> ListData for LoadOnDemand<T> {
+    type Key = usize;
+    type Item = T;
+
+    fn version(&self) -> u64 {
+        self.lock().version
+    }
+
+    fn len(&self) -> usize {
+        self.lock().len
+    }
+
+    fn contains_key(&self, key: &Self::Key) -> bool {
+        *key < self.len()
+    }
+
+    fn get_cloned(&self, key: &Self::Key) -> Option<Self::Item> {
+        let inner = self.lock();
+        if *key >= inner.len {
+            return None;
+        }
+        Some(match inner.items.get(key) {
+            Some(Slot::Ready(item)) => item.clone(),
+            _ => inner.placeholder.clone(),
+        })
+    }
+
+    fn update(&self, key: &Self::Key, value: Self::Item) -> Option<UpdateHandle> {
+        if *key >= self.len() {
+            return None;
+        }
+        Some(self.insert(*key, value))
+    }
+
+    fn iter_vec_from(&self, start: usize, limit: usize) -> Vec<(Self::Key, Self::Item)> {
+        let inner = self.lock();
+        let end = inner.len.min(start.saturating_add(limit));
+        (start..end)
+            .map(|index| {
+                let item = match inner.items.get(&index) {
+                    Some(Slot::Ready(item)) => item.clone(),
+                    _ => inner.placeholder.clone(),
+                };
+                (index, item)
+            })
+            .collect()
+    }
+
+    fn request_range(&self, range: Range<usize>) {
+        let mut requests: Vec<Range<usize>> = vec![];
+        let loader = {
+            let mut inner = self.lock();
+            let end = range.end.min(inner.len);
+            for index in range.start..end {
+                if inner.items.contains_key(&index) {
+                    continue;
+                }
+                inner.items.insert(index, Slot::Loading);
+                match requests.last_mut() {
+                    Some(last) if last.end == index => last.end += 1,
+                    _ => requests.push(index..index + 1),
+                }
+            }
+            inner.loader.clone()
+        };
+        // Note: the lock is released, thus the loader may insert items
+        for range in requests {
+            loader(self, range);
+        }
+    }
+
+    fn load_state(&self, key: &Self::Key) -> LoadState {
+        match self.lock().items.get(key) {
+            Some(Slot::Ready(_)) => LoadState::Ready,
+            Some(Slot::Failed) => LoadState::Failed,
+            Some(Slot::Loading) | None => LoadState::Loading,
+        }
+    }
+}
@@ -16,6 +16,7 @@ use crate::event::UpdateHandle;
 use crate::updatable::*;
 use std::cell::RefCell;
 use std::fmt::Debug;
+use std::ops::Range;
 use std::rc::Rc;
 
 /// Wrapper for single-thread shared data
@@ -105,6 +106,14 @@ impl<T: ListDataMut> ListData for SharedRc<T> {
     fn iter_vec_from(&self, start: usize, limit: usize) -> Vec<(Self::Key, Self::Item)> {
         (self.0).1.borrow().0.iter_vec_from(start, limit)
     }
+
+    fn request_range(&self, range: Range<usize>) {
+        (self.0).1.borrow().0.request_range(range)
+    }
+
+    fn load_state(&self, key: &Self::Key) -> LoadState {
+        (self.0).1.borrow().0.load_state(key)
+    }
 }
 impl<T: ListDataMut> ListDataMut for SharedRc<T> {
     fn set(&mut self, key: &Self::Key, item: Self::Item) {
@@ -160,6 +169,14 @@ impl<T: MatrixDataMut> MatrixData for SharedRc<T> {
     fn make_key(row: &Self::RowKey, col: &Self::ColKey) -> Self::Key {
         T::make_key(row, col)
     }
+
+    fn request_range(&self, cols: Range<usize>, rows: Range<usize>) {
+        (self.0).1.borrow().0.request_range(cols, rows)
+    }
+
+    fn load_state(&self, key: &Self::Key) -> LoadState {
+        (self.0).1.borrow().0.load_state(key)
+    }
 }
 impl<T: MatrixDataMut> MatrixDataMut for SharedRc<T> {
     fn set(&mut self, key: &Self::Key, item: Self::Item) {
@@ -0,0 +1,481 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Sorted data adapter
+
+use crate::event::UpdateHandle;
+use crate::updatable::*;
+#[cfg(feature = "config")]
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::cmp::Ordering;
+use std::fmt;
+
+/// Sort direction
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "config", derive(Serialize, Deserialize))]
+pub enum SortDirection {
+    /// Smallest values first
+    Ascending,
+    /// Largest values first
+    Descending,
+}
+
+impl SortDirection {
+    /// Get the opposite direction
+    pub fn reversed(self) -> Self {
+        match self {
+            SortDirection::Ascending => SortDirection::Descending,
+            SortDirection::Descending => SortDirection::Ascending,
+        }
+    }
+}
+
+/// A sort key: a column and direction
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "config", derive(Serialize, Deserialize))]
+pub struct SortKey<C> {
+    /// The column sorted by
+    pub col: C,
+    /// The direction
+    pub direction: SortDirection,
+}
+
+impl<C> SortKey<C> {
+    /// Construct an ascending sort key
+    pub fn ascending(col: C) -> Self {
+        let direction = SortDirection::Ascending;
+        SortKey { col, direction }
+    }
+
+    /// Construct a descending sort key
+    pub fn descending(col: C) -> Self {
+        let direction = SortDirection::Descending;
+        SortKey { col, direction }
+    }
+}
+
+/// An in-progress sort: rows and the values of sort columns extracted so far
+struct SortJob<T: MatrixData> {
+    rows: Vec<T::RowKey>,
+    values: Vec<Vec<Option<T::Item>>>,
+}
+
+struct SortState<T: MatrixData> {
+    keys: Vec<SortKey<T::ColKey>>,
+    /// Sorted row keys
+    order: Vec<T::RowKey>,
+    /// Data version at the start of the last sort
+    data_version: u64,
+    version: u64,
+    job: Option<SortJob<T>>,
+}
+
+/// Sorted data adapter over a [`MatrixData`] (e.g. for a table)
+///
+/// Rows are sorted by the values of one or more columns, each ascending or
+/// descending: the first [`SortKey`] is the primary key, the next is used to
+/// order rows equal under the primary key, and so on. Sorting is stable, thus
+/// rows equal under all keys retain their order in the underlying data. Item
+/// values are compared via [`PartialOrd`] (incomparable values compare equal).
+///
+/// Sort state is "sticky": when the underlying data changes (as indicated by
+/// its [`MatrixData::version`]), rows are sorted again by the same keys.
+///
+/// View headers may use [`SortedData::toggle_sort`] to adjust sort keys and
+/// [`SortedData::sort_position`] to show the current sort state. The sort keys
+/// may be saved with [`SortedData::sort_keys`] (these types support `serde`
+/// with the `config` feature) and restored with [`SortedData::set_sort_keys`].
+///
+/// By default, sorting is performed immediately. For large data sets, use
+/// [`SortedData::with_incremental`] to sort over multiple steps, keeping the
+/// UI responsive: the previous order remains visible until the new order is
+/// complete.
+///
+/// Methods adjusting the sort return an [`UpdateHandle`]; an update should be
+/// triggered with this (e.g. via `EventMgr::trigger_update`).
+pub struct SortedData<T: MatrixData> {
+    data: T,
+    handle: UpdateHandle,
+    chunk_size: usize,
+    state: RefCell<SortState<T>>,
+}
+
+impl<T: MatrixData> fmt::Debug for SortedData<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("SortedData")
+            .field("data", &self.data)
+            .field("handle", &self.handle)
+            .field("keys", &self.state.borrow().keys)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T: MatrixData> SortedData<T>
+where
+    T::Item: PartialOrd,
+{
+    /// Construct over `data`
+    ///
+    /// Initially, no sort keys are set, thus rows are in data order.
+    pub fn new(data: T) -> Self {
+        let state = SortState {
+            keys: vec![],
+            order: data.row_iter_vec(usize::MAX),
+            data_version: data.version(),
+            version: 0,
+            job: None,
+        };
+        SortedData {
+            data,
+            handle: UpdateHandle::new(),
+            chunk_size: 0,
+            state: RefCell::new(state),
+        }
+    }
+
+    /// Set the sort keys (inline)
+    #[must_use]
+    pub fn with_sort_keys(self, keys: Vec<SortKey<T::ColKey>>) -> Self {
+        let _ = self.set_sort_keys(keys);
+        self
+    }
+
+    /// Enable incremental sorting (inline)
+    ///
+    /// If `chunk_size > 0`, then values for sorting are read from (up to)
+    /// `chunk_size` rows per call to [`SortedData::sort_step`], which must be
+    /// called repeatedly (e.g. on a timer) until sorting is complete. Only the
+    /// final ordering of (cached) values is done in a single step.
+    #[must_use]
+    pub fn with_incremental(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+
+    /// Access the underlying data
+    pub fn inner(&self) -> &T {
+        &self.data
+    }
+
+    /// Get the current sort keys
+    pub fn sort_keys(&self) -> Vec<SortKey<T::ColKey>> {
+        self.state.borrow().keys.clone()
+    }
+
+    /// Set the sort keys
+    ///
+    /// An empty list restores data order.
+    pub fn set_sort_keys(&self, keys: Vec<SortKey<T::ColKey>>) -> UpdateHandle {
+        self.state.borrow_mut().keys = keys;
+        self.begin_sort();
+        self.handle
+    }
+
+    /// Get the sort position and direction of column `col`, if sorted by
+    ///
+    /// Position 0 is the primary sort key.
+    pub fn sort_position(&self, col: &T::ColKey) -> Option<(usize, SortDirection)> {
+        let state = self.state.borrow();
+        let mut iter = state.keys.iter().enumerate();
+        iter.find(|(_, key)| key.col == *col)
+            .map(|(i, key)| (i, key.direction))
+    }
+
+    /// Toggle sorting by column `col`, e.g. on click of a header
+    ///
+    /// If `additive` (e.g. when Shift is held), `col` is added as the least
+    /// significant sort key or, if already a sort key, its direction is
+    /// reversed. Otherwise, `col` is made the primary sort key (retaining
+    /// other keys as secondary) or, if already primary, its direction is
+    /// reversed.
+    pub fn toggle_sort(&self, col: T::ColKey, additive: bool) -> UpdateHandle {
+        let mut keys = self.sort_keys();
+        match keys.iter().position(|key| key.col == col) {
+            Some(i) if additive || i == 0 => {
+                keys[i].direction = keys[i].direction.reversed();
+            }
+            Some(i) => {
+                let key = keys.remove(i);
+                keys.insert(0, key);
+            }
+            None if additive => keys.push(SortKey::ascending(col)),
+            None => keys.insert(0, SortKey::ascending(col)),
+        }
+        self.set_sort_keys(keys)
+    }
+
+    /// Remove all sort keys, restoring data order
+    pub fn clear_sort(&self) -> UpdateHandle {
+        self.set_sort_keys(vec![])
+    }
+
+    /// True while an incremental sort is in progress
+    pub fn is_sorting(&self) -> bool {
+        self.state.borrow().job.is_some()
+    }
+
+    /// Re-sort rows
+    ///
+    /// This is done automatically when the data's version changes; call this
+    /// if the data was changed without updating its version.
+    pub fn refresh(&self) -> UpdateHandle {
+        self.begin_sort();
+        self.handle
+    }
+
+    /// Perform a step of an incremental sort
+    ///
+    /// Returns an [`UpdateHandle`] when the sort completes (thus the order
+    /// changes). Does nothing if no sort is in progress.
+    pub fn sort_step(&self) -> Option<UpdateHandle> {
+        self.step(self.chunk_size.max(1)).then(|| self.handle)
+    }
+
+    fn begin_sort(&self) {
+        let rows = self.data.row_iter_vec(usize::MAX);
+        {
+            let mut state = self.state.borrow_mut();
+            state.data_version = self.data.version();
+            let values = Vec::with_capacity(rows.len());
+            state.job = Some(SortJob { rows, values });
+        }
+        if self.chunk_size == 0 {
+            self.step(usize::MAX);
+        }
+    }
+
+    /// Sort again if the data changed
+    fn check_version(&self) {
+        let state = self.state.borrow();
+        if state.job.is_none() && state.data_version != self.data.version() {
+            drop(state);
+            self.begin_sort();
+        }
+    }
+
+    // Returns true on completion
+    fn step(&self, limit: usize) -> bool {
+        let mut state = self.state.borrow_mut();
+        let state = &mut *state;
+        let job = match state.job.as_mut() {
+            Some(job) => job,
+            None => return false,
+        };
+
+        let start = job.values.len();
+        let end = job.rows.len().min(start.saturating_add(limit));
+        for row in &job.rows[start..end] {
+            let values = state.keys.iter().map(|key| {
+                let key = T::make_key(row, &key.col);
+                self.data.get_cloned(&key)
+            });
+            job.values.push(values.collect());
+        }
+        if end < job.rows.len() {
+            return false;
+        }
+
+        let job = state.job.take().unwrap();
+        let keys = &state.keys;
+        let mut indices: Vec<usize> = (0..job.rows.len()).collect();
+        // Note: sort_by is stable
+        indices.sort_by(|a, b| compare(keys, &job.values[*a], &job.values[*b]));
+        state.order = indices.into_iter().map(|i| job.rows[i].clone()).collect();
+        state.version += 1;
+        true
+    }
+}
+
+fn compare<C, I: PartialOrd>(keys: &[SortKey<C>], a: &[Option<I>], b: &[Option<I>]) -> Ordering {
+    for (key, (x, y)) in keys.iter().zip(a.iter().zip(b.iter())) {
+        let ord = x.partial_cmp(y).unwrap_or(Ordering::Equal);
+        let ord = match key.direction {
+            SortDirection::Ascending => ord,
+            SortDirection::Descending => ord.reverse(),
+        };
+        if ord != Ordering::Equal {
+            return ord;
+        }
+    }
+    Ordering::Equal
+}
+
+impl<T: MatrixData + Updatable> Updatable for SortedData<T> {
+    fn update_handle(&self) -> Option<UpdateHandle> {
+        Some(self.handle)
+    }
+
+    fn update_handles(&self) -> Vec<UpdateHandle> {
+        let mut handles = vec![self.handle];
+        handles.extend(self.data.update_handles());
+        handles
+    }
+}
+
+impl<K, M, T: MatrixData + UpdatableHandler<K, M>> UpdatableHandler<K, M> for SortedData<T> {
+    fn handle(&self, key: &K, msg: &M) -> Option<UpdateHandle> {
+        self.data.handle(key, msg)
+    }
+}
+
+impl<T: MatrixData> MatrixData for SortedData<T>
+where
+    T::Item: PartialOrd,
+{
+    type ColKey = T::ColKey;
+    type RowKey = T::RowKey;
+    type Key = T::Key;
+    type Item = T::Item;
+
+    fn version(&self) -> u64 {
+        self.data.version() + self.state.borrow().version
+    }
+
+    fn col_len(&self) -> usize {
+        self.data.col_len()
+    }
+    fn row_len(&self) -> usize {
+        self.check_version();
+        self.state.borrow().order.len()
+    }
+    fn contains(&self, key: &Self::Key) -> bool {
+        self.data.contains(key)
+    }
+    fn get_cloned(&self, key: &Self::Key) -> Option<Self::Item> {
+        self.data.get_cloned(key)
+    }
+
+    fn update(&self, key: &Self::Key, value: Self::Item) -> Option<UpdateHandle> {
+        self.data.update(key, value)
+    }
+
+    fn col_iter_vec_from(&self, start: usize, limit: usize) -> Vec<Self::ColKey> {
+        self.data.col_iter_vec_from(start, limit)
+    }
+
+    fn row_iter_vec_from(&self, start: usize, limit: usize) -> Vec<Self::RowKey> {
+        self.check_version();
+        let state = self.state.borrow();
+        let order = &state.order;
+        let end = order.len().min(start.saturating_add(limit));
+        order[start.min(end)..end].to_vec()
+    }
+
+    fn make_key(row: &Self::RowKey, col: &Self::ColKey) -> Self::Key {
+        T::make_key(row, col)
+    }
+
+    fn load_state(&self, key: &Self::Key) -> LoadState {
+        self.data.load_state(key)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn table() -> Table {
+        // Rows 0..4; column 0 is text, column 1 is integer
+        Table::from_csv("name,n\nb,2\na,1\nc,2\na,2\n").unwrap()
+    }
+
+    #[test]
+    fn compare_keys() {
+        use SortDirection::*;
+        let asc = [SortKey::ascending(0)];
+        let desc = [SortKey::descending(0)];
+        assert_eq!(compare(&asc, &[Some(1)], &[Some(2)]), Ordering::Less);
+        assert_eq!(compare(&desc, &[Some(1)], &[Some(2)]), Ordering::Greater);
+        assert_eq!(compare(&asc, &[Some(1)], &[Some(1)]), Ordering::Equal);
+        assert_eq!(compare(&asc, &[None], &[Some(1)]), Ordering::Less);
+        assert_eq!(compare::<usize, i32>(&[], &[], &[]), Ordering::Equal);
+
+        // The secondary key orders rows equal under the primary key
+        let keys = [
+            SortKey::ascending(0),
+            SortKey {
+                col: 1,
+                direction: Descending,
+            },
+        ];
+        let (a, b) = ([Some(1), Some(1)], [Some(1), Some(2)]);
+        assert_eq!(compare(&keys, &a, &b), Ordering::Greater);
+        let (a, b) = ([Some(0), Some(1)], [Some(1), Some(2)]);
+        assert_eq!(compare(&keys, &a, &b), Ordering::Less);
+
+        // Incomparable values compare equal
+        let (a, b) = ([Some(f64::NAN), Some(1.0)], [Some(0.0), Some(2.0)]);
+        assert_eq!(compare(&keys, &a, &b), Ordering::Greater);
+    }
+
+    #[test]
+    fn toggle_sort() {
+        use SortDirection::*;
+        let data = SortedData::new(table());
+        assert_eq!(data.sort_position(&0), None);
+
+        let _ = data.toggle_sort(1, false);
+        assert_eq!(data.sort_keys(), vec![SortKey::ascending(1)]);
+        let _ = data.toggle_sort(1, false);
+        assert_eq!(data.sort_keys(), vec![SortKey::descending(1)]);
+
+        // A new non-additive key becomes primary, retaining others
+        let _ = data.toggle_sort(0, false);
+        let keys = vec![SortKey::ascending(0), SortKey::descending(1)];
+        assert_eq!(data.sort_keys(), keys);
+        assert_eq!(data.sort_position(&0), Some((0, Ascending)));
+        assert_eq!(data.sort_position(&1), Some((1, Descending)));
+
+        // An existing secondary key is moved to primary without reversal
+        let _ = data.toggle_sort(1, false);
+        let keys = vec![SortKey::descending(1), SortKey::ascending(0)];
+        assert_eq!(data.sort_keys(), keys);
+
+        // Additive toggling reverses existing keys in place
+        let _ = data.toggle_sort(0, true);
+        let keys = vec![SortKey::descending(1), SortKey::descending(0)];
+        assert_eq!(data.sort_keys(), keys);
+
+        let _ = data.clear_sort();
+        let _ = data.toggle_sort(0, true);
+        let _ = data.toggle_sort(1, true);
+        let keys = vec![SortKey::ascending(0), SortKey::ascending(1)];
+        assert_eq!(data.sort_keys(), keys);
+    }
+
+    #[test]
+    fn sort_order() {
+        let data = SortedData::new(table());
+        assert_eq!(data.row_iter_vec(usize::MAX), vec![0, 1, 2, 3]);
+
+        // Sorting is stable: rows 0, 2 and 3 are equal under this key
+        let _ = data.set_sort_keys(vec![SortKey::ascending(1)]);
+        assert_eq!(data.row_iter_vec(usize::MAX), vec![1, 0, 2, 3]);
+
+        let _ = data.toggle_sort(0, true);
+        assert_eq!(data.row_iter_vec(usize::MAX), vec![1, 3, 0, 2]);
+
+        let _ = data.toggle_sort(1, false);
+        assert_eq!(data.row_iter_vec(usize::MAX), vec![3, 0, 2, 1]);
+
+        let _ = data.clear_sort();
+        assert_eq!(data.row_iter_vec(usize::MAX), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn sort_incremental() {
+        let data = SortedData::new(table()).with_incremental(3);
+        let _ = data.set_sort_keys(vec![SortKey::descending(0)]);
+        assert!(data.is_sorting());
+        // The previous order is shown until sorting completes
+        assert_eq!(data.row_iter_vec(usize::MAX), vec![0, 1, 2, 3]);
+        assert_eq!(data.sort_step(), None);
+        assert!(data.sort_step().is_some());
+        assert!(!data.is_sorting());
+        assert_eq!(data.sort_step(), None);
+        assert_eq!(data.row_iter_vec(usize::MAX), vec![2, 0, 1, 3]);
+    }
+}
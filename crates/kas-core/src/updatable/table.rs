@@ -0,0 +1,529 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Tabular data with CSV import and export
+
+use crate::event::UpdateHandle;
+use crate::updatable::*;
+#[cfg(feature = "config")]
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::mem::take;
+use thiserror::Error;
+
+/// Column type of a [`Table`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "config", derive(Serialize, Deserialize))]
+pub enum ColumnType {
+    /// Text (any value)
+    Text,
+    /// Integers (`i64`)
+    Integer,
+    /// Floating-point numbers (`f64`)
+    Float,
+    /// Booleans (`true` or `false`)
+    Bool,
+}
+
+impl ColumnType {
+    /// Infer the most specific type able to represent all `values`
+    ///
+    /// Empty values are ignored. In order of preference, the result is
+    /// [`ColumnType::Bool`], [`ColumnType::Integer`], [`ColumnType::Float`] or
+    /// [`ColumnType::Text`] (also used when all values are empty).
+    pub fn infer<'a, I: IntoIterator<Item = &'a str>>(values: I) -> ColumnType {
+        let (mut any, mut bool, mut int, mut float) = (false, true, true, true);
+        for value in values {
+            let value = value.trim();
+            if value.is_empty() {
+                continue;
+            }
+            any = true;
+            bool &= value.parse::<bool>().is_ok();
+            int &= value.parse::<i64>().is_ok();
+            float &= value.parse::<f64>().is_ok();
+        }
+        if !any {
+            ColumnType::Text
+        } else if bool {
+            ColumnType::Bool
+        } else if int {
+            ColumnType::Integer
+        } else if float {
+            ColumnType::Float
+        } else {
+            ColumnType::Text
+        }
+    }
+}
+
+/// A cell of a [`Table`]
+///
+/// Cells are ordered first by variant, then by value; thus within a typed
+/// column empty cells sort first. This is suitable for use with
+/// [`SortedData`].
+///
+/// With the `config` feature, cells (de)serialize as plain values, with
+/// [`Cell::Empty`] represented as unit (e.g. `null` in JSON).
+#[derive(Clone, Debug, PartialEq, PartialOrd)]
+#[cfg_attr(feature = "config", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "config", serde(untagged))]
+pub enum Cell {
+    /// No value
+    Empty,
+    /// Boolean
+    Bool(bool),
+    /// Integer
+    Integer(i64),
+    /// Floating-point number
+    Float(f64),
+    /// Text
+    Text(String),
+}
+
+impl Default for Cell {
+    fn default() -> Self {
+        Cell::Empty
+    }
+}
+
+impl Cell {
+    /// Parse `text` as type `ty`
+    ///
+    /// Empty (or whitespace) text yields [`Cell::Empty`]. Where `text` cannot
+    /// be parsed as `ty`, [`Cell::Text`] is returned.
+    pub fn parse(text: &str, ty: ColumnType) -> Cell {
+        let trimmed = text.trim();
+        if trimmed.is_empty() {
+            return Cell::Empty;
+        }
+        let cell = match ty {
+            ColumnType::Text => None,
+            ColumnType::Integer => trimmed.parse().ok().map(Cell::Integer),
+            ColumnType::Float => trimmed.parse().ok().map(Cell::Float),
+            ColumnType::Bool => trimmed.parse().ok().map(Cell::Bool),
+        };
+        cell.unwrap_or_else(|| Cell::Text(text.to_string()))
+    }
+
+    /// True if this is [`Cell::Empty`]
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Cell::Empty)
+    }
+}
+
+impl fmt::Display for Cell {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Cell::Empty => Ok(()),
+            Cell::Bool(v) => write!(f, "{}", v),
+            Cell::Integer(v) => write!(f, "{}", v),
+            Cell::Float(v) => write!(f, "{}", v),
+            Cell::Text(v) => write!(f, "{}", v),
+        }
+    }
+}
+
+/// A column of a [`Table`]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "config", derive(Serialize, Deserialize))]
+pub struct Column {
+    /// Column name (from the header)
+    pub name: String,
+    /// Column type
+    pub ty: ColumnType,
+}
+
+/// CSV parse error
+#[non_exhaustive]
+#[derive(Error, Debug)]
+pub enum CsvError {
+    /// IO error
+    #[error("IO error")]
+    Io(#[from] io::Error),
+    /// Input has no header row
+    #[error("input is empty (expected a header row)")]
+    Empty,
+    /// Quoted field is not terminated
+    #[error("line {0}: unterminated quoted field")]
+    UnterminatedQuote(usize),
+    /// Record has the wrong number of fields
+    #[error("line {line}: expected {expected} fields, found {found}")]
+    FieldCount {
+        /// Line number on which the record starts
+        line: usize,
+        /// Number of fields in the header
+        expected: usize,
+        /// Number of fields in the record
+        found: usize,
+    },
+}
+
+/// A table of typed data
+///
+/// This type implements [`MatrixData`] with column and row keys of type
+/// `usize` and key type `(usize, usize)` (column, row), thus may be viewed
+/// with `MatrixView`. Like `Vec<T>`, a plain `Table` is constant; for updates
+/// through views, wrap with [`SharedRc`].
+///
+/// Tables may be read from and written to CSV (see [`Table::from_csv`] and
+/// [`Table::to_csv`]). With the `config` feature, `Table` also supports
+/// `serde`, thus may be loaded from or saved to any format supported by
+/// `serde` (e.g. JSON).
+///
+/// Example:
+/// ```
+/// use kas_core::updatable::{Cell, ColumnType, MatrixData, Table};
+///
+/// let table = Table::from_csv("name,age\nAlice,30\n\"Bob, Jr\",4\n").unwrap();
+/// assert_eq!(table.columns()[1].ty, ColumnType::Integer);
+/// assert_eq!(table.get_cloned(&(0, 1)), Some(Cell::Text("Bob, Jr".into())));
+/// assert_eq!(table.get_cloned(&(1, 1)), Some(Cell::Integer(4)));
+/// assert_eq!(table.to_csv(), "name,age\nAlice,30\n\"Bob, Jr\",4\n");
+/// ```
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "config", derive(Serialize, Deserialize))]
+pub struct Table {
+    columns: Vec<Column>,
+    rows: Vec<Vec<Cell>>,
+}
+
+impl Table {
+    /// Construct an empty table with the given columns
+    pub fn new(columns: Vec<Column>) -> Self {
+        let rows = vec![];
+        Table { columns, rows }
+    }
+
+    /// Construct from a header and rows of text, inferring column types
+    ///
+    /// Rows are padded or truncated to the length of the `header`.
+    /// See [`ColumnType::infer`].
+    pub fn from_text_rows(header: Vec<String>, rows: &[Vec<String>]) -> Self {
+        let columns = header
+            .into_iter()
+            .enumerate()
+            .map(|(i, name)| {
+                let values = rows.iter().filter_map(|row| row.get(i));
+                let ty = ColumnType::infer(values.map(|s| s.as_str()));
+                Column { name, ty }
+            })
+            .collect();
+        let mut table = Table::new(columns);
+        for row in rows {
+            table.push_text_row(row);
+        }
+        table
+    }
+
+    /// Parse CSV text
+    ///
+    /// The first record is used as the header; column types are inferred.
+    /// Fields may be quoted (`"`), with `""` representing a quote character
+    /// within a quoted field. Blank lines are ignored.
+    pub fn from_csv(text: &str) -> Result<Self, CsvError> {
+        let mut records = parse_csv(text)?.into_iter();
+        let (_, header) = records.next().ok_or(CsvError::Empty)?;
+        let mut rows = vec![];
+        for (line, row) in records {
+            if row.len() != header.len() {
+                return Err(CsvError::FieldCount {
+                    line,
+                    expected: header.len(),
+                    found: row.len(),
+                });
+            }
+            rows.push(row);
+        }
+        Ok(Table::from_text_rows(header, &rows))
+    }
+
+    /// Read CSV from `reader`
+    ///
+    /// See [`Table::from_csv`].
+    pub fn read_csv<R: Read>(mut reader: R) -> Result<Self, CsvError> {
+        let mut text = String::new();
+        reader.read_to_string(&mut text)?;
+        Table::from_csv(&text)
+    }
+
+    /// Write as CSV
+    ///
+    /// The header is written first. Fields are quoted where required.
+    pub fn write_csv<W: Write>(&self, mut writer: W) -> io::Result<()> {
+        writer.write_all(self.to_csv().as_bytes())
+    }
+
+    /// Format as CSV
+    ///
+    /// The header is written first. Fields are quoted where required.
+    pub fn to_csv(&self) -> String {
+        let mut text = String::new();
+        let header = self.columns.iter().map(|col| col.name.clone());
+        write_csv_record(&mut text, header);
+        for row in &self.rows {
+            write_csv_record(&mut text, row.iter().map(|cell| cell.to_string()));
+        }
+        text
+    }
+
+    /// Get columns
+    pub fn columns(&self) -> &[Column] {
+        &self.columns
+    }
+
+    /// Set the type of column `col`
+    ///
+    /// Existing cells of this column are converted via their text
+    /// representation (see [`Cell::parse`]).
+    ///
+    /// Panics if `col` is out of range.
+    pub fn set_column_type(&mut self, col: usize, ty: ColumnType) {
+        self.columns[col].ty = ty;
+        for row in &mut self.rows {
+            let text = row[col].to_string();
+            row[col] = Cell::parse(&text, ty);
+        }
+    }
+
+    /// Get rows
+    pub fn rows(&self) -> &[Vec<Cell>] {
+        &self.rows
+    }
+
+    /// Append a row
+    ///
+    /// The row is padded with [`Cell::Empty`] or truncated to the number of
+    /// columns.
+    pub fn push_row(&mut self, mut row: Vec<Cell>) {
+        row.resize(self.columns.len(), Cell::Empty);
+        self.rows.push(row);
+    }
+
+    /// Append a row of text, parsed according to column types
+    ///
+    /// The row is padded with [`Cell::Empty`] or truncated to the number of
+    /// columns.
+    pub fn push_text_row(&mut self, row: &[String]) {
+        let mut cells = Vec::with_capacity(self.columns.len());
+        for (i, col) in self.columns.iter().enumerate() {
+            cells.push(match row.get(i) {
+                Some(text) => Cell::parse(text, col.ty),
+                None => Cell::Empty,
+            });
+        }
+        self.rows.push(cells);
+    }
+}
+
+/// Parse CSV records, each with its line number
+fn parse_csv(text: &str) -> Result<Vec<(usize, Vec<String>)>, CsvError> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let (mut line, mut start_line) = (1, 1);
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                c => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+            continue;
+        }
+
+        match c {
+            '"' if field.is_empty() => quoted = true,
+            ',' => record.push(take(&mut field)),
+            '\r' if chars.peek() == Some(&'\n') => (),
+            '\n' | '\r' => {
+                record.push(take(&mut field));
+                if record.len() > 1 || !record[0].is_empty() {
+                    records.push((start_line, take(&mut record)));
+                }
+                record.clear();
+                line += 1;
+                start_line = line;
+            }
+            c => field.push(c),
+        }
+    }
+    if quoted {
+        return Err(CsvError::UnterminatedQuote(start_line));
+    }
+    if !record.is_empty() || !field.is_empty() {
+        record.push(field);
+        records.push((start_line, record));
+    }
+    Ok(records)
+}
+
+fn write_csv_record<I: Iterator<Item = String>>(text: &mut String, fields: I) {
+    for (i, field) in fields.enumerate() {
+        if i > 0 {
+            text.push(',');
+        }
+        if field.contains(|c: char| matches!(c, ',' | '"' | '\n' | '\r')) {
+            text.push('"');
+            text.push_str(&field.replace('"', "\"\""));
+            text.push('"');
+        } else {
+            text.push_str(&field);
+        }
+    }
+    text.push('\n');
+}
+
+impl Updatable for Table {
+    fn update_handle(&self) -> Option<UpdateHandle> {
+        None
+    }
+}
+impl<M> UpdatableHandler<(usize, usize), M> for Table {
+    fn handle(&self, _: &(usize, usize), _: &M) -> Option<UpdateHandle> {
+        None
+    }
+}
+
+impl MatrixData for Table {
+    type ColKey = usize;
+    type RowKey = usize;
+    type Key = (usize, usize);
+    type Item = Cell;
+
+    fn version(&self) -> u64 {
+        0
+    }
+
+    fn col_len(&self) -> usize {
+        self.columns.len()
+    }
+    fn row_len(&self) -> usize {
+        self.rows.len()
+    }
+    fn contains(&self, key: &Self::Key) -> bool {
+        key.0 < self.columns.len() && key.1 < self.rows.len()
+    }
+    fn get_cloned(&self, key: &Self::Key) -> Option<Self::Item> {
+        self.rows.get(key.1).and_then(|row| row.get(key.0)).cloned()
+    }
+
+    fn update(&self, _: &Self::Key, _: Self::Item) -> Option<UpdateHandle> {
+        // Note: plain Table does not support update, but SharedRc<Table> does.
+        None
+    }
+
+    fn col_iter_vec_from(&self, start: usize, limit: usize) -> Vec<Self::ColKey> {
+        let end = self.columns.len().min(start.saturating_add(limit));
+        (start.min(end)..end).collect()
+    }
+
+    fn row_iter_vec_from(&self, start: usize, limit: usize) -> Vec<Self::RowKey> {
+        let end = self.rows.len().min(start.saturating_add(limit));
+        (start.min(end)..end).collect()
+    }
+
+    fn make_key(row: &Self::RowKey, col: &Self::ColKey) -> Self::Key {
+        (*col, *row)
+    }
+}
+impl MatrixDataMut for Table {
+    fn set(&mut self, key: &Self::Key, item: Self::Item) {
+        // Keys out of range are ignored
+        if let Some(cell) = self.rows.get_mut(key.1).and_then(|row| row.get_mut(key.0)) {
+            *cell = item;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn strings(fields: &[&str]) -> Vec<String> {
+        fields.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn parse_records() {
+        let records = parse_csv("a,b\n\n1,\"x, \"\"y\"\"\"\r\n,\"multi\nline\"\n3,z").unwrap();
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[0], (1, strings(&["a", "b"])));
+        assert_eq!(records[1], (3, strings(&["1", "x, \"y\""])));
+        assert_eq!(records[2], (4, strings(&["", "multi\nline"])));
+        assert_eq!(records[3], (6, strings(&["3", "z"])));
+
+        assert!(parse_csv("").unwrap().is_empty());
+        assert_eq!(parse_csv("a\n\n").unwrap(), vec![(1, strings(&["a"]))]);
+    }
+
+    #[test]
+    fn parse_errors() {
+        match parse_csv("a,b\n1,\"2\n") {
+            Err(CsvError::UnterminatedQuote(2)) => (),
+            r => panic!("unexpected result: {:?}", r),
+        }
+        match Table::from_csv("") {
+            Err(CsvError::Empty) => (),
+            r => panic!("unexpected result: {:?}", r),
+        }
+        match Table::from_csv("a,b\n1,2\n3\n") {
+            Err(CsvError::FieldCount {
+                line: 3,
+                expected: 2,
+                found: 1,
+            }) => (),
+            r => panic!("unexpected result: {:?}", r),
+        }
+    }
+
+    #[test]
+    fn infer_types() {
+        use ColumnType::*;
+        assert_eq!(ColumnType::infer([]), Text);
+        assert_eq!(ColumnType::infer([" ", ""]), Text);
+        assert_eq!(ColumnType::infer(["true", "", "false"]), Bool);
+        assert_eq!(ColumnType::infer(["1", " -2 "]), Integer);
+        assert_eq!(ColumnType::infer(["1", "2.5"]), Float);
+        assert_eq!(ColumnType::infer(["1", "true"]), Text);
+    }
+
+    #[test]
+    fn table_csv() {
+        let text = "name,n,x,ok\nb,2,,true\n\"a\nb\",-1,0.5,\n";
+        let table = Table::from_csv(text).unwrap();
+        let types: Vec<_> = table.columns().iter().map(|col| col.ty).collect();
+        use ColumnType::*;
+        assert_eq!(types, vec![Text, Integer, Float, Bool]);
+        assert_eq!(table.rows()[0][2], Cell::Empty);
+        assert_eq!(table.rows()[1][1], Cell::Integer(-1));
+        assert_eq!(table.to_csv(), text);
+    }
+
+    #[test]
+    fn table_set() {
+        let mut table = Table::from_csv("a,b\n1,2\n").unwrap();
+        table.set(&(1, 0), Cell::Integer(3));
+        assert_eq!(table.get_cloned(&(1, 0)), Some(Cell::Integer(3)));
+        // Out-of-range keys are ignored
+        table.set(&(2, 0), Cell::Integer(4));
+        table.set(&(0, 1), Cell::Integer(4));
+        assert_eq!(table.rows(), &[vec![Cell::Integer(1), Cell::Integer(3)]]);
+
+        table.set_column_type(1, ColumnType::Text);
+        assert_eq!(table.get_cloned(&(1, 0)), Some(Cell::Text("3".into())));
+    }
+}
@@ -39,6 +39,7 @@ pub struct Widget {
 
     pub core_data: Option<Member>,
     pub children: Vec<Child>,
+    pub defaults: Vec<(Member, Expr)>,
 
     pub extra_impls: Vec<ItemImpl>,
 }
@@ -115,6 +116,7 @@ impl Parse for Widget {
 
         let mut core_data = None;
         let mut children = Vec::new();
+        let mut defaults = Vec::new();
 
         for (i, field) in fields.iter_mut().enumerate() {
             let mut other_attrs = Vec::with_capacity(field.attrs.len());
@@ -127,8 +129,15 @@ impl Parse for Widget {
                     }
                 } else if attr.path == parse_quote! { widget } {
                     let ident = member(i, field.ident.clone());
-                    let args = syn::parse2(attr.tokens)?;
-                    children.push(Child { ident, args });
+                    let mut args: WidgetAttrArgs = syn::parse2(attr.tokens)?;
+                    // `#[widget(default = EXPR)]` alone does not imply a child
+                    let is_child = !args.is_default_only();
+                    if let Some(expr) = args.default.take() {
+                        defaults.push((ident.clone(), expr));
+                    }
+                    if is_child {
+                        children.push(Child { ident, args });
+                    }
                 } else {
                     other_attrs.push(attr);
                 }
@@ -172,6 +181,7 @@ impl Parse for Widget {
             semi_token,
             core_data,
             children,
+            defaults,
             extra_impls,
         })
     }
@@ -347,6 +357,7 @@ mod kw {
     custom_keyword!(use_msg);
     custom_keyword!(discard_msg);
     custom_keyword!(update);
+    custom_keyword!(any_msg);
     custom_keyword!(msg);
     custom_keyword!(generics);
     custom_keyword!(single);
@@ -360,6 +371,7 @@ mod kw {
     custom_keyword!(valign);
     custom_keyword!(key_nav);
     custom_keyword!(hover_highlight);
+    custom_keyword!(hover_events);
     custom_keyword!(cursor_icon);
     custom_keyword!(handle);
     custom_keyword!(send);
@@ -368,6 +380,7 @@ mod kw {
     custom_keyword!(column);
     custom_keyword!(draw);
     custom_keyword!(derive);
+    custom_keyword!(builder);
 }
 
 #[derive(Debug, Default)]
@@ -412,14 +425,28 @@ impl Handler {
 #[derive(Debug)]
 pub struct WidgetAttrArgs {
     pub update: Option<Ident>,
+    pub any_msg: Option<Ident>,
     pub handler: Handler,
+    pub default: Option<Expr>,
+}
+
+impl WidgetAttrArgs {
+    /// True if only a `default` value is given
+    fn is_default_only(&self) -> bool {
+        self.default.is_some()
+            && self.update.is_none()
+            && self.any_msg.is_none()
+            && self.handler.is_none()
+    }
 }
 
 impl Parse for WidgetAttrArgs {
     fn parse(input: ParseStream) -> Result<Self> {
         let mut args = WidgetAttrArgs {
             update: None,
+            any_msg: None,
             handler: Handler::None,
+            default: None,
         };
         if input.is_empty() {
             return Ok(args);
@@ -434,6 +461,10 @@ impl Parse for WidgetAttrArgs {
                 let _: kw::update = content.parse()?;
                 let _: Eq = content.parse()?;
                 args.update = Some(content.parse()?);
+            } else if args.any_msg.is_none() && lookahead.peek(kw::any_msg) {
+                let _: kw::any_msg = content.parse()?;
+                let _: Eq = content.parse()?;
+                args.any_msg = Some(content.parse()?);
             } else if args.handler.is_none() && lookahead.peek(kw::flatmap_msg) {
                 let _: kw::flatmap_msg = content.parse()?;
                 let _: Eq = content.parse()?;
@@ -449,6 +480,10 @@ impl Parse for WidgetAttrArgs {
             } else if args.handler.is_none() && lookahead.peek(kw::discard_msg) {
                 let _: kw::discard_msg = content.parse()?;
                 args.handler = Handler::Discard;
+            } else if args.default.is_none() && lookahead.peek(Token![default]) {
+                let _: Token![default] = content.parse()?;
+                let _: Eq = content.parse()?;
+                args.default = Some(content.parse()?);
             } else if lookahead.peek(kw::handler) {
                 let tok: Ident = content.parse()?;
                 return Err(Error::new(
@@ -504,10 +539,18 @@ property!(
     HoverHighlight: bool = false;
     kw::hover_highlight : input => input.parse::<syn::LitBool>()?.value;
 );
+property!(
+    HoverEvents: bool = false;
+    kw::hover_events : input => input.parse::<syn::LitBool>()?.value;
+);
 property!(
     CursorIcon: Expr = parse_quote! { ::kas::event::CursorIcon::Default };
     kw::cursor_icon : input => input.parse()?;
 );
+property!(
+    Builder: bool = false;
+    kw::builder : input => input.parse::<syn::LitBool>()?.value;
+);
 property!(
     FindId: Option<Expr> = None;
     kw::find_id : input => Some(input.parse()?);
@@ -517,20 +560,24 @@ property!(
 pub struct WidgetArgs {
     pub key_nav: KeyNav,
     pub hover_highlight: HoverHighlight,
+    pub hover_events: HoverEvents,
     pub cursor_icon: CursorIcon,
     pub derive: Option<Member>,
     pub layout: Option<make_layout::Tree>,
     pub find_id: FindId,
+    pub builder: Builder,
 }
 
 impl Parse for WidgetArgs {
     fn parse(input: ParseStream) -> Result<Self> {
         let mut key_nav = KeyNav::default();
         let mut hover_highlight = HoverHighlight::default();
+        let mut hover_events = HoverEvents::default();
         let mut cursor_icon = CursorIcon::default();
         let mut derive = None;
         let mut layout = None;
         let mut find_id = FindId::default();
+        let mut builder = Builder::default();
 
         let content;
         let _ = braced!(content in input);
@@ -541,6 +588,8 @@ impl Parse for WidgetArgs {
                 key_nav = content.parse()?;
             } else if lookahead.peek(kw::hover_highlight) && hover_highlight.span.is_none() {
                 hover_highlight = content.parse()?;
+            } else if lookahead.peek(kw::hover_events) && hover_events.span.is_none() {
+                hover_events = content.parse()?;
             } else if lookahead.peek(kw::cursor_icon) && cursor_icon.span.is_none() {
                 cursor_icon = content.parse()?;
             } else if lookahead.peek(kw::derive) && derive.is_none() {
@@ -555,6 +604,8 @@ impl Parse for WidgetArgs {
                 layout = Some(content.parse()?);
             } else if content.peek(kw::find_id) {
                 find_id = content.parse()?;
+            } else if lookahead.peek(kw::builder) && builder.span.is_none() {
+                builder = content.parse()?;
             } else {
                 return Err(lookahead.error());
             }
@@ -565,10 +616,12 @@ impl Parse for WidgetArgs {
         Ok(WidgetArgs {
             key_nav,
             hover_highlight,
+            hover_events,
             cursor_icon,
             derive,
             layout,
             find_id,
+            builder,
         })
     }
 }
@@ -724,6 +777,9 @@ impl Parse for WidgetField {
             let _ = bracketed!(inner in input);
             let _: kw::widget = inner.parse()?;
             let args = inner.parse::<WidgetAttrArgs>()?;
+            if let Some(expr) = args.default.as_ref() {
+                emit_error!(expr.span(), "`default` is not supported by `make_widget!`");
+            }
             Some(args)
         } else {
             None
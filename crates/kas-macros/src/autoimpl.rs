@@ -10,7 +10,7 @@ use quote::{quote, quote_spanned, TokenStreamExt};
 use syn::parse::{Error, Parse, ParseStream, Result};
 use syn::spanned::Spanned;
 use syn::token::Comma;
-use syn::{Field, Fields, Ident, ItemStruct, Member, Token};
+use syn::{Expr, Field, Fields, Ident, ItemStruct, Member, Token};
 
 #[allow(non_camel_case_types)]
 mod kw {
@@ -18,6 +18,7 @@ mod kw {
 
     custom_keyword!(on);
     custom_keyword!(skip);
+    custom_keyword!(using);
 }
 
 /// Traits targetting many fields
@@ -26,6 +27,9 @@ enum TraitMany {
     Clone(Span),
     Debug(Span),
     Default(Span),
+    PartialEq(Span),
+    Eq(Span),
+    Hash(Span),
 }
 /// Traits targetting one field
 #[derive(Clone, Copy)]
@@ -51,6 +55,12 @@ fn class(ident: &Ident) -> Option<Class> {
         Some(Class::Many(TraitMany::Debug(ident.span())))
     } else if ident == "Default" {
         Some(Class::Many(TraitMany::Default(ident.span())))
+    } else if ident == "PartialEq" {
+        Some(Class::Many(TraitMany::PartialEq(ident.span())))
+    } else if ident == "Eq" {
+        Some(Class::Many(TraitMany::Eq(ident.span())))
+    } else if ident == "Hash" {
+        Some(Class::Many(TraitMany::Hash(ident.span())))
     } else if ident == "Deref" {
         Some(Class::One(TraitOne::Deref(ident.span())))
     } else if ident == "DerefMut" {
@@ -74,6 +84,7 @@ enum Body {
     Many {
         targets: Vec<TraitMany>,
         skip: Vec<Member>,
+        using: Vec<(Member, Expr)>,
     },
     One {
         targets: Vec<TraitOne>,
@@ -99,11 +110,16 @@ impl Parse for AutoImpl {
         let mut clause = None;
         let mut on = None;
         let mut skip = Vec::new();
+        let mut using = Vec::new();
         let mut empty_or_trailing = true;
 
         while !input.is_empty() {
             let lookahead = input.lookahead1();
-            if lookahead.peek(Token![where]) || lookahead.peek(kw::on) || lookahead.peek(kw::skip) {
+            if lookahead.peek(Token![where])
+                || lookahead.peek(kw::on)
+                || lookahead.peek(kw::skip)
+                || lookahead.peek(kw::using)
+            {
                 break;
             }
 
@@ -168,28 +184,28 @@ impl Parse for AutoImpl {
             let _ = input.parse::<Token![.]>()?;
             on = Some(input.parse()?);
             lookahead = input.lookahead1();
-        } else if lookahead.peek(kw::skip) {
-            let _: kw::skip = input.parse()?;
-            let _ = input.parse::<Token![self]>()?;
-            let _ = input.parse::<Token![.]>()?;
-            skip.push(input.parse()?);
-            empty_or_trailing = false;
-            while !input.is_empty() {
-                let lookahead = input.lookahead1();
-                if empty_or_trailing {
-                    if lookahead.peek(Token![self]) {
-                        let _ = input.parse::<Token![self]>()?;
-                        let _ = input.parse::<Token![.]>()?;
-                        skip.push(input.parse()?);
-                        empty_or_trailing = false;
-                        continue;
+        } else {
+            if lookahead.peek(kw::skip) {
+                let _: kw::skip = input.parse()?;
+                loop {
+                    skip.push(parse_member(input)?);
+                    if !parse_separator(input)? {
+                        break;
+                    }
+                }
+                lookahead = input.lookahead1();
+            }
+            if lookahead.peek(kw::using) {
+                let _: kw::using = input.parse()?;
+                loop {
+                    let member = parse_member(input)?;
+                    let _ = input.parse::<Token![=]>()?;
+                    using.push((member, input.parse()?));
+                    if !parse_separator(input)? {
+                        break;
                     }
-                } else if lookahead.peek(Comma) {
-                    let _ = input.parse::<Comma>()?;
-                    empty_or_trailing = true;
-                    continue;
                 }
-                return Err(lookahead.error());
+                lookahead = input.lookahead1();
             }
         }
 
@@ -206,6 +222,7 @@ impl Parse for AutoImpl {
             Body::Many {
                 targets: targets_many,
                 skip,
+                using,
             }
         };
 
@@ -213,6 +230,22 @@ impl Parse for AutoImpl {
     }
 }
 
+/// Parse `self.member`
+fn parse_member(input: ParseStream) -> Result<Member> {
+    let _ = input.parse::<Token![self]>()?;
+    let _ = input.parse::<Token![.]>()?;
+    input.parse()
+}
+
+/// Parse a comma, returning true if followed by another `self.member`
+fn parse_separator(input: ParseStream) -> Result<bool> {
+    if !input.peek(Comma) {
+        return Ok(false);
+    }
+    let _ = input.parse::<Comma>()?;
+    Ok(input.peek(Token![self]))
+}
+
 pub fn autoimpl(attr: AutoImpl, item: ItemStruct) -> TokenStream {
     fn check_is_field(mem: &Member, fields: &Fields) {
         match (fields, mem) {
@@ -235,17 +268,45 @@ pub fn autoimpl(attr: AutoImpl, item: ItemStruct) -> TokenStream {
         emit_error!(mem.span(), "not a struct field");
     }
     match &attr.body {
-        Body::Many { skip, .. } => {
-            for mem in skip {
+        Body::Many {
+            targets,
+            skip,
+            using,
+        } => {
+            for mem in skip.iter().chain(using.iter().map(|(mem, _)| mem)) {
                 check_is_field(mem, &item.fields);
             }
+
+            // Only Clone (for skipped fields) and Default use `using` values
+            let clone = targets.iter().any(|t| matches!(t, TraitMany::Clone(_)));
+            let default = targets.iter().any(|t| matches!(t, TraitMany::Default(_)));
+            for (mem, _) in using {
+                if default || (clone && skip.contains(mem)) {
+                    continue;
+                }
+                if clone {
+                    emit_error!(
+                        mem.span(),
+                        "`using` value is unused: `Clone` only uses values of skipped fields"
+                    );
+                } else {
+                    emit_error!(
+                        mem.span(),
+                        "`using` value is unused: only `Clone` and `Default` use field values"
+                    );
+                }
+            }
         }
         Body::One { on, .. } => check_is_field(on, &item.fields),
     }
 
     let mut toks = TokenStream::new();
     match attr.body {
-        Body::Many { targets, skip } => autoimpl_many(targets, skip, item, &attr.clause, &mut toks),
+        Body::Many {
+            targets,
+            skip,
+            using,
+        } => autoimpl_many(targets, skip, using, item, &attr.clause, &mut toks),
         Body::One { targets, on } => autoimpl_one(targets, on, item, &attr.clause, &mut toks),
     }
     toks
@@ -254,12 +315,29 @@ pub fn autoimpl(attr: AutoImpl, item: ItemStruct) -> TokenStream {
 fn autoimpl_many(
     mut targets: Vec<TraitMany>,
     skip: Vec<Member>,
+    using: Vec<(Member, Expr)>,
     item: ItemStruct,
     clause: &Option<WhereClause>,
     toks: &mut TokenStream,
 ) {
     let no_skips = skip.is_empty();
     let skip = |item: &Member| -> bool { skip.iter().any(|mem| *mem == *item) };
+    // Value of a field where not taken from self: the `using` expr or default
+    let value = |item: &Member| -> TokenStream {
+        match using.iter().find(|(mem, _)| *mem == *item) {
+            Some((_, expr)) => quote! { #expr },
+            None => quote! { Default::default() },
+        }
+    };
+    let members: Vec<Member> = item
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match field.ident {
+            Some(ref id) => Member::from(id.clone()),
+            None => Member::from(i),
+        })
+        .collect();
     let ident = &item.ident;
     let (impl_generics, ty_generics, item_wc) = item.generics.split_for_impl();
 
@@ -276,7 +354,8 @@ fn autoimpl_many(
                     };
 
                     if skip(&mem) {
-                        inner.append_all(quote! { Default::default(), });
+                        let value = value(&mem);
+                        inner.append_all(quote! { #value, });
                     } else {
                         inner.append_all(quote! { self.#mem.clone(), });
                     }
@@ -316,19 +395,17 @@ fn autoimpl_many(
                             inner.append_all(quote! { .finish_non_exhaustive() });
                         };
                     }
-                    Fields::Unnamed(ref fields) => {
+                    Fields::Unnamed(_) => {
                         inner = quote! { f.debug_tuple(#name) };
-                        for i in 0..fields.unnamed.len() {
-                            if !skip(&i.into()) {
-                                inner.append_all(quote! {
-                                    .field(&self.#i)
-                                });
-                            }
+                        for mem in members.iter().filter(|mem| !skip(mem)) {
+                            inner.append_all(quote! {
+                                .field(&self.#mem)
+                            });
                         }
                         inner.append_all(quote! { .finish() });
                     }
                     Fields::Unit => {
-                        inner = quote! { #name };
+                        inner = quote! { f.write_str(#name) };
                     }
                 }
                 let wc = clause_to_toks(clause, item_wc, quote! { std::fmt::Debug });
@@ -341,27 +418,19 @@ fn autoimpl_many(
                 });
             }
             TraitMany::Default(span) => {
-                let mut inner;
-                match item.fields {
-                    Fields::Named(ref fields) => {
-                        inner = quote! {};
-                        for field in fields.named.iter() {
-                            let field = field.ident.as_ref().unwrap();
-                            inner.append_all(quote! { #field: Default::default(), });
-                        }
-                        inner = quote! { #ident { #inner } };
-                    }
-                    Fields::Unnamed(ref fields) => {
-                        inner = quote! {};
-                        for _ in 0..fields.unnamed.len() {
-                            inner.append_all(quote! { Default::default(), });
-                        }
-                        inner = quote! { #ident(#inner) };
-                    }
-                    Fields::Unit => {
-                        inner = quote! { #ident };
+                let mut inner = quote! {};
+                for mem in &members {
+                    let value = value(mem);
+                    match mem {
+                        Member::Named(field) => inner.append_all(quote! { #field: #value, }),
+                        Member::Unnamed(_) => inner.append_all(quote! { #value, }),
                     }
                 }
+                let inner = match &item.fields {
+                    Fields::Named(_) => quote! { #ident { #inner } },
+                    Fields::Unnamed(_) => quote! { #ident(#inner) },
+                    Fields::Unit => quote! { #ident },
+                };
                 let wc = clause_to_toks(clause, item_wc, quote! { std::default::Default });
                 toks.append_all(quote_spanned! {span=>
                     impl #impl_generics std::default::Default for #ident #ty_generics #wc {
@@ -371,6 +440,46 @@ fn autoimpl_many(
                     }
                 });
             }
+            TraitMany::PartialEq(span) => {
+                let mut inner = quote! { true };
+                for mem in members.iter().filter(|mem| !skip(mem)) {
+                    inner.append_all(quote! { && self.#mem == other.#mem });
+                }
+                if members.iter().all(|mem| skip(mem)) {
+                    inner = quote! { let _ = other; true };
+                }
+                let wc = clause_to_toks(clause, item_wc, quote! { std::cmp::PartialEq });
+                toks.append_all(quote_spanned! {span=>
+                    impl #impl_generics std::cmp::PartialEq for #ident #ty_generics #wc {
+                        fn eq(&self, other: &Self) -> bool {
+                            #inner
+                        }
+                    }
+                });
+            }
+            TraitMany::Eq(span) => {
+                let wc = clause_to_toks(clause, item_wc, quote! { std::cmp::Eq });
+                toks.append_all(quote_spanned! {span=>
+                    impl #impl_generics std::cmp::Eq for #ident #ty_generics #wc {}
+                });
+            }
+            TraitMany::Hash(span) => {
+                let mut inner = quote! {};
+                for mem in members.iter().filter(|mem| !skip(mem)) {
+                    inner.append_all(quote! { std::hash::Hash::hash(&self.#mem, state); });
+                }
+                if inner.is_empty() {
+                    inner = quote! { let _ = state; };
+                }
+                let wc = clause_to_toks(clause, item_wc, quote! { std::hash::Hash });
+                toks.append_all(quote_spanned! {span=>
+                    impl #impl_generics std::hash::Hash for #ident #ty_generics #wc {
+                        fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+                            #inner
+                        }
+                    }
+                });
+            }
         }
     }
 }
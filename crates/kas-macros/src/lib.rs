@@ -57,11 +57,21 @@ mod widget;
 ///
 /// Some trait implementations make use of all fields by default. Individual
 /// fields may be skipped via the `skip self.x, self.y` syntax (after any `where`
-/// clauses). The following traits may be derived this way:
+/// clauses). Values for fields not taken from `self` may be given via the
+/// `using self.x = expr, self.y = expr` syntax (after any `skip` list); where
+/// no value is given, `Default::default()` is used. It is an error to give a
+/// `using` value which is not used by any derived trait. The following traits
+/// may be derived this way:
 ///
 /// -   `Clone` — implements `std::clone::Clone`; skipped fields are
-///     initialised with `Default::default()`
+///     initialised with their `using` value
 /// -   `Debug` — implements `std::fmt::Debug`; skipped fields are not printed
+/// -   `Default` — implements `std::default::Default`; all fields are
+///     initialised with their `using` value
+/// -   `PartialEq` — implements `std::cmp::PartialEq`; skipped fields are not
+///     compared
+/// -   `Eq` — implements `std::cmp::Eq` (a marker trait)
+/// -   `Hash` — implements `std::hash::Hash`; skipped fields are not hashed
 ///
 /// # Single-field traits
 ///
@@ -99,6 +109,21 @@ mod widget;
 /// }
 /// ```
 ///
+/// Implement comparison and hashing ignoring a cache, and `Default` with a
+/// custom initial value:
+/// ```rust
+/// use kas_macros::autoimpl;
+/// use std::cell::Cell;
+///
+/// #[autoimpl(PartialEq, Eq, Hash where X: trait skip self.cache)]
+/// #[autoimpl(Default where X: trait using self.len = 1)]
+/// struct S<X> {
+///     x: X,
+///     len: usize,
+///     cache: Cell<Option<usize>>,
+/// }
+/// ```
+///
 /// Implement `Deref` and `DerefMut`, dereferencing to the given field:
 /// ```rust
 /// # use kas_macros::autoimpl;
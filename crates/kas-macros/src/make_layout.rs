@@ -4,10 +4,11 @@
 //     https://www.apache.org/licenses/LICENSE-2.0
 
 use proc_macro2::{Span, TokenStream as Toks};
-use quote::{quote, TokenStreamExt};
+use proc_macro_error::emit_error;
+use quote::{quote, quote_spanned, TokenStreamExt};
 use syn::parse::{Error, Parse, ParseStream, Result};
 use syn::spanned::Spanned;
-use syn::{braced, bracketed, parenthesized, Expr, LitInt, Member, Token};
+use syn::{braced, bracketed, parenthesized, Expr, ExprField, Fields, LitInt, Member, Token};
 
 #[allow(non_camel_case_types)]
 mod kw {
@@ -45,6 +46,34 @@ impl Tree {
     ) -> Result<Toks> {
         self.0.generate(Some(children))
     }
+
+    /// Check references to fields of the widget
+    ///
+    /// Emits an error for each reference to a field not in `fields`. Returns
+    /// items (see [`warning`]) warning of each reference to a field not in
+    /// `children`.
+    pub fn validate(&self, fields: &Fields, children: &[&Member]) -> Toks {
+        let mut warnings = Toks::new();
+        self.0.validate(fields, children, &mut warnings);
+        warnings
+    }
+}
+
+/// Generate an item causing a compiler warning at `span`
+///
+/// Unlike `proc_macro_error::emit_warning` (effective only on nightly), this
+/// works on stable compilers by referencing a deprecated item, thus the warning
+/// is reported as use of a deprecated constant with `note` as explanation.
+pub fn warning(span: Span, note: &str) -> Toks {
+    let name = quote_spanned! {span=> kas_macros_warning };
+    quote! {
+        const _: () = {
+            #[deprecated(note = #note)]
+            #[allow(non_upper_case_globals)]
+            const kas_macros_warning: () = ();
+            #name
+        };
+    }
 }
 
 #[derive(Debug)]
@@ -90,6 +119,7 @@ struct GridDimensions {
 }
 #[derive(Debug)]
 struct CellInfo {
+    span: Span,
     row: u32,
     row_end: u32,
     col: u32,
@@ -97,6 +127,7 @@ struct CellInfo {
 }
 impl Parse for CellInfo {
     fn parse(input: ParseStream) -> Result<Self> {
+        let span = input.span();
         let row = input.parse::<LitInt>()?.base10_parse()?;
         let row_end = if input.peek(Token![..]) {
             let _ = input.parse::<Token![..]>();
@@ -126,6 +157,7 @@ impl Parse for CellInfo {
         };
 
         Ok(CellInfo {
+            span,
             row,
             row_end,
             col,
@@ -133,6 +165,25 @@ impl Parse for CellInfo {
         })
     }
 }
+impl CellInfo {
+    fn overlaps(&self, other: &CellInfo) -> bool {
+        self.row < other.row_end
+            && other.row < self.row_end
+            && self.col < other.col_end
+            && other.col < self.col_end
+    }
+
+    /// Describe the cell, using the input syntax
+    fn describe(&self) -> String {
+        let range = |start, end| match end - start {
+            1 => format!("{}", start),
+            _ => format!("{}..{}", start, end),
+        };
+        let (row, col) = (self.row, self.col);
+        format!("{}, {}", range(row, self.row_end), range(col, self.col_end))
+    }
+}
+
 impl GridDimensions {
     fn update(&mut self, cell: &CellInfo) {
         self.rows = self.rows.max(cell.row_end);
@@ -283,7 +334,15 @@ fn parse_grid(input: ParseStream) -> Result<Layout> {
     let mut dim = GridDimensions::default();
     let mut cells = vec![];
     while !inner.is_empty() {
-        let info = inner.parse()?;
+        let info: CellInfo = inner.parse()?;
+        if let Some((other, _)) = cells.iter().find(|(other, _)| info.overlaps(other)) {
+            emit_error!(
+                info.span,
+                "grid cell `{}` overlaps cell `{}`",
+                info.describe(),
+                other.describe(),
+            );
+        }
         dim.update(&info);
         let _: Token![:] = inner.parse()?;
         let layout = inner.parse()?;
@@ -356,7 +415,63 @@ impl quote::ToTokens for GridDimensions {
     }
 }
 
+/// Check a reference to a field `self.x`, if `expr` is such
+///
+/// If `children` is given, the field is expected to be a child widget.
+fn validate_field(expr: &Expr, fields: &Fields, children: Option<&[&Member]>, warnings: &mut Toks) {
+    let member = match expr {
+        Expr::Field(ExprField { base, member, .. }) => match base.as_ref() {
+            Expr::Path(path) if path.path.is_ident("self") => member,
+            _ => return,
+        },
+        _ => return,
+    };
+    let name = match member {
+        Member::Named(ident) => ident.to_string(),
+        Member::Unnamed(index) => index.index.to_string(),
+    };
+
+    let exists = fields.iter().enumerate().any(|(i, field)| match member {
+        Member::Named(ident) => field.ident.as_ref() == Some(ident),
+        Member::Unnamed(index) => index.index as usize == i,
+    });
+    if !exists {
+        emit_error!(member.span(), "layout: no field `{}` on this widget", name);
+    } else if let Some(children) = children {
+        if !children.contains(&member) {
+            let note = format!(
+                "layout: field `{}` is not a child widget (missing `#[widget]` attribute?)",
+                name,
+            );
+            warnings.append_all(warning(member.span(), &note));
+        }
+    }
+}
+
 impl Layout {
+    fn validate(&self, fields: &Fields, children: &[&Member], warnings: &mut Toks) {
+        match self {
+            Layout::Align(layout, _) | Layout::Frame(layout) | Layout::NavFrame(layout) => {
+                layout.validate(fields, children, warnings)
+            }
+            Layout::AlignSingle(expr, _) | Layout::Widget(expr) => {
+                validate_field(expr, fields, Some(children), warnings)
+            }
+            Layout::Slice(_, expr) => validate_field(expr, fields, None, warnings),
+            Layout::Single(_) | Layout::List(_, List::Glob(_)) => (),
+            Layout::List(_, List::List(list)) => {
+                for layout in list {
+                    layout.validate(fields, children, warnings);
+                }
+            }
+            Layout::Grid(_, cells) => {
+                for (_, layout) in cells {
+                    layout.validate(fields, children, warnings);
+                }
+            }
+        }
+    }
+
     // Optionally pass in the list of children, but not when already in a
     // multi-element layout (list/slice/grid).
     fn generate<'a, I: ExactSizeIterator<Item = &'a Member>>(
@@ -369,9 +484,11 @@ impl Layout {
                 quote! { ::kas::layout::Layout::align(#inner, #align) }
             }
             Layout::AlignSingle(expr, align) => {
-                quote! { ::kas::layout::Layout::align_single(#expr.as_widget_mut(), #align) }
+                quote_spanned! {expr.span()=>
+                    ::kas::layout::Layout::align_single(#expr.as_widget_mut(), #align)
+                }
             }
-            Layout::Widget(expr) => quote! {
+            Layout::Widget(expr) => quote_spanned! {expr.span()=>
                 ::kas::layout::Layout::single(#expr.as_widget_mut())
             },
             Layout::Single(span) => {
@@ -379,11 +496,14 @@ impl Layout {
                     if iter.len() != 1 {
                         return Err(Error::new(
                             *span,
-                            "layout `single`: widget does not have exactly one child",
+                            format!(
+                                "layout `single`: widget has {} children (expected exactly one)",
+                                iter.len()
+                            ),
                         ));
                     }
                     let child = iter.next().unwrap();
-                    quote! {
+                    quote_spanned! {child.span()=>
                         ::kas::layout::Layout::single(self.#child.as_widget_mut())
                     }
                 } else {
@@ -424,7 +544,7 @@ impl Layout {
                         if let Some(iter) = children {
                             len = iter.len();
                             for member in iter {
-                                items.append_all(quote! {
+                                items.append_all(quote_spanned! {member.span()=>
                                     ::kas::layout::Layout::single(self.#member.as_widget_mut()),
                                 });
                             }
@@ -459,7 +579,8 @@ impl Layout {
                     _chain = next;
                     data
                 } };
-                quote! { ::kas::layout::Layout::slice(&mut #expr, #dir, #data) }
+                let slice = quote_spanned! {expr.span()=> &mut #expr };
+                quote! { ::kas::layout::Layout::slice(#slice, #dir, #data) }
             }
             Layout::Grid(dim, cells) => {
                 let (rows, cols) = (dim.rows as usize, dim.cols as usize);
@@ -249,6 +249,7 @@ pub(crate) fn make_widget(mut args: MakeWidget) -> Result<TokenStream> {
 
         core_data: Some(syn::Member::Named(core_ident)),
         children,
+        defaults: vec![],
 
         extra_impls: args.extra_impls,
     })?;
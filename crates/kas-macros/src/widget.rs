@@ -7,9 +7,9 @@ use crate::args::{Handler, Widget};
 use crate::extend_generics;
 use proc_macro2::TokenStream;
 use proc_macro_error::{emit_call_site_warning, emit_error, emit_warning};
-use quote::{quote, TokenStreamExt};
+use quote::{quote, quote_spanned, TokenStreamExt};
 use syn::spanned::Spanned;
-use syn::{parse_quote, Result};
+use syn::{parse_quote, Fields, Ident, Member, Result, Visibility};
 
 pub(crate) fn widget(mut args: Widget) -> Result<TokenStream> {
     let mut toks = quote! { #args };
@@ -23,10 +23,22 @@ pub(crate) fn widget(mut args: Widget) -> Result<TokenStream> {
         }
     }
 
+    if args.attr_widget.builder.value {
+        toks.append_all(builder(&args));
+    } else {
+        for (_, expr) in &args.defaults {
+            emit_error!(
+                expr.span(),
+                "#[widget(default = ..)] is unused without `builder = true;`"
+            );
+        }
+    }
+
     let opt_derive = &args.attr_widget.derive;
 
     let mut impl_widget_children = true;
     let mut impl_widget_config = true;
+    let mut impl_save_state = true;
     let mut has_find_id_impl = args.attr_widget.layout.is_some();
     let mut handler_impl = None;
     let mut send_event_impl = None;
@@ -55,6 +67,17 @@ pub(crate) fn widget(mut args: Widget) -> Result<TokenStream> {
                 }
                 // TODO: if args.widget_attr.config.is_some() { warn unused }
                 impl_widget_config = false;
+            } else if *path == parse_quote! { ::kas::SaveState }
+                || *path == parse_quote! { kas::SaveState }
+                || *path == parse_quote! { SaveState }
+            {
+                if opt_derive.is_some() {
+                    emit_error!(
+                        impl_.span(),
+                        "impl conflicts with use of #[widget(derive=FIELD)]"
+                    );
+                }
+                impl_save_state = false;
             } else if *path == parse_quote! { ::kas::Layout }
                 || *path == parse_quote! { kas::Layout }
                 || *path == parse_quote! { Layout }
@@ -193,9 +216,36 @@ pub(crate) fn widget(mut args: Widget) -> Result<TokenStream> {
         });
     }
 
+    if let Some(inner) = opt_derive {
+        toks.append_all(quote! {
+            impl #impl_generics ::kas::SaveState
+                    for #name #ty_generics #where_clause
+            {
+                #[inline]
+                fn save_state(&self) -> Option<::kas::StateValue> {
+                    self.#inner.save_state()
+                }
+                #[inline]
+                fn restore_state(&mut self, state: &::kas::StateValue) -> ::kas::TkAction {
+                    self.#inner.restore_state(state)
+                }
+                #[inline]
+                fn saves_children(&self) -> bool {
+                    self.#inner.saves_children()
+                }
+            }
+        });
+    } else if impl_save_state {
+        toks.append_all(quote! {
+            impl #impl_generics ::kas::SaveState
+                    for #name #ty_generics #where_clause {}
+        });
+    }
+
     if impl_widget_config {
         let key_nav = args.attr_widget.key_nav.value;
         let hover_highlight = args.attr_widget.hover_highlight.value;
+        let hover_events = args.attr_widget.hover_events.value;
         let cursor_icon = args.attr_widget.cursor_icon.value;
 
         toks.append_all(quote! {
@@ -208,6 +258,9 @@ pub(crate) fn widget(mut args: Widget) -> Result<TokenStream> {
                 fn hover_highlight(&self) -> bool {
                     #hover_highlight
                 }
+                fn hover_events(&self) -> bool {
+                    #hover_events
+                }
                 fn cursor_icon(&self) -> ::kas::event::CursorIcon {
                     #cursor_icon
                 }
@@ -220,6 +273,9 @@ pub(crate) fn widget(mut args: Widget) -> Result<TokenStream> {
         if let Some(span) = args.attr_widget.hover_highlight.span {
             emit_warning!(span, "unused due to manual impl of `WidgetConfig`");
         }
+        if let Some(span) = args.attr_widget.hover_events.span {
+            emit_warning!(span, "unused due to manual impl of `WidgetConfig`");
+        }
         if let Some(span) = args.attr_widget.cursor_icon.span {
             emit_warning!(span, "unused due to manual impl of `WidgetConfig`");
         }
@@ -286,7 +342,9 @@ pub(crate) fn widget(mut args: Widget) -> Result<TokenStream> {
         };
 
         let core = args.core_data.as_ref().unwrap();
-        let layout = layout.generate(args.children.iter().map(|c| &c.ident))?;
+        let children: Vec<&Member> = args.children.iter().map(|c| &c.ident).collect();
+        toks.append_all(layout.validate(&args.fields, &children));
+        let layout = layout.generate(children.iter().cloned())?;
 
         toks.append_all(quote! {
             impl #impl_generics ::kas::Layout for #name #ty_generics #where_clause {
@@ -372,6 +430,15 @@ pub(crate) fn widget(mut args: Widget) -> Result<TokenStream> {
                 } else {
                     quote! {}
                 };
+                let any_msg = if let Some(f) = child.args.any_msg.as_ref() {
+                    quote! {
+                        if mgr.has_msg() {
+                            self.#f(mgr);
+                        }
+                    }
+                } else {
+                    quote! {}
+                };
                 let handler = match &child.args.handler {
                     Handler::Use(f) => quote! {
                         r.try_into().unwrap_or_else(|msg| {
@@ -406,6 +473,7 @@ pub(crate) fn widget(mut args: Widget) -> Result<TokenStream> {
                     Some(#i) => {
                         let r = self.#ident.send(mgr, #id, event);
                         #update
+                        #any_msg
                         #handler
                     }
                 });
@@ -458,3 +526,67 @@ pub(crate) fn widget(mut args: Widget) -> Result<TokenStream> {
 
     Ok(toks)
 }
+
+/// Generate a `Default` impl and `with_FIELD` methods for public data fields
+fn builder(args: &Widget) -> TokenStream {
+    let fields = match args.fields {
+        Fields::Named(ref fields) => &fields.named,
+        _ => {
+            let span = args.attr_widget.builder.span.unwrap();
+            emit_error!(span, "`builder = true;` requires named fields");
+            return TokenStream::new();
+        }
+    };
+
+    let name = &args.ident;
+    let (impl_generics, ty_generics, where_clause) = args.generics.split_for_impl();
+
+    let mut field_vals = TokenStream::new();
+    let mut methods = TokenStream::new();
+    for field in fields.iter() {
+        let ident = field.ident.as_ref().unwrap();
+        let member = Member::Named(ident.clone());
+
+        let default = args.defaults.iter().find(|(m, _)| *m == member);
+        if let Some((_, expr)) = default {
+            field_vals.append_all(quote_spanned! {expr.span()=> #ident: #expr, });
+        } else {
+            field_vals.append_all(quote! { #ident: ::std::default::Default::default(), });
+        }
+
+        let is_child = args.children.iter().any(|child| child.ident == member);
+        if args.core_data.as_ref() == Some(&member)
+            || is_child
+            || !matches!(field.vis, Visibility::Public(_))
+        {
+            continue;
+        }
+
+        let ty = &field.ty;
+        let with_ident = Ident::new(&format!("with_{}", ident), ident.span());
+        let doc = format!("Set `{}` (inline)", ident);
+        methods.append_all(quote! {
+            #[doc = #doc]
+            #[inline]
+            #[must_use]
+            pub fn #with_ident(mut self, #ident: #ty) -> Self {
+                self.#ident = #ident;
+                self
+            }
+        });
+    }
+
+    quote! {
+        impl #impl_generics ::std::default::Default for #name #ty_generics #where_clause {
+            fn default() -> Self {
+                #name {
+                    #field_vals
+                }
+            }
+        }
+
+        impl #impl_generics #name #ty_generics #where_clause {
+            #methods
+        }
+    }
+}
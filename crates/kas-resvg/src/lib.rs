@@ -23,4 +23,6 @@ pub use canvas::{Canvas, CanvasProgram};
 #[cfg(feature = "svg")]
 mod svg;
 #[cfg(feature = "svg")]
-pub use svg::Svg;
+mod tint;
+#[cfg(feature = "svg")]
+pub use svg::{LoadError, Svg};
@@ -5,61 +5,278 @@
 
 //! SVG widget
 
-// TODO: error handling (unwrap)
-
+use kas::draw::color::Rgba;
 use kas::draw::{ImageFormat, ImageId};
 use kas::geom::Vec2;
-use kas::layout::MarginSelector;
+use kas::layout::{AspectScaling, MarginSelector, SpriteDisplay};
 use kas::prelude::*;
-use std::path::PathBuf;
+use kas::theme::SymbolClass;
+use std::cell::RefCell;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc};
+use std::time::{Duration, Instant};
+use thiserror::Error;
 use tiny_skia::Pixmap;
+use usvg::fontdb::Database;
+
+use crate::tint::{self, TintKey};
+
+/// Images with more pixels than this are rastered on a worker thread
+const ASYNC_AREA: u32 = 256 * 256;
+
+/// Interval at which to poll for completion of asynchronous rastering
+const POLL_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Delay after the last size change before rastering asynchronously
+const DEBOUNCE_DELAY: Duration = Duration::from_millis(50);
+
+// Timer payloads
+const TIMER_POLL: u64 = 0;
+const TIMER_DEBOUNCE: u64 = 1;
+
+/// Size of the placeholder drawn when loading fails (virtual pixels)
+const PLACEHOLDER_SIZE: f32 = 32.0;
+
+/// SVG loading error
+#[derive(Error, Debug)]
+pub enum LoadError {
+    #[error("IO error")]
+    IOError(#[from] std::io::Error),
+    #[error(transparent)]
+    Svg(#[from] usvg::Error),
+}
+
+/// SVG data
+#[derive(Clone, Debug)]
+enum Data {
+    Static(&'static [u8]),
+    Shared(Arc<[u8]>),
+}
+
+impl AsRef<[u8]> for Data {
+    fn as_ref(&self) -> &[u8] {
+        match self {
+            Data::Static(data) => data,
+            Data::Shared(data) => data,
+        }
+    }
+}
+
+impl Data {
+    /// Hash of the content, used as a cache key
+    fn key(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.as_ref().hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+thread_local! {
+    static FONT_DB: RefCell<Option<Arc<Database>>> = RefCell::new(None);
+}
+
+/// Get the font database shared by all `Svg` widgets
+///
+/// This is a copy of the KAS font database, made on first use.
+fn font_db() -> Arc<Database> {
+    FONT_DB.with(|db| {
+        let mut db = db.borrow_mut();
+        db.get_or_insert_with(|| {
+            let fonts_db = kas::text::fonts::fonts().read_db();
+            Arc::new(fonts_db.db().clone())
+        })
+        .clone()
+    })
+}
+
+/// Parse `data` using `opts` and the font database `fontdb`
+fn parse(data: &[u8], opts: &usvg::Options, fontdb: &Database) -> Result<usvg::Tree, usvg::Error> {
+    let mut opts = opts.to_ref();
+    opts.fontdb = fontdb;
+    usvg::Tree::from_data(data, &opts)
+}
+
+/// A worker thread rastering an image at the latest requested size
+///
+/// The thread exits when this is dropped (after completing any raster in
+/// progress).
+struct Worker {
+    sender: mpsc::Sender<(u32, u32)>,
+    receiver: mpsc::Receiver<((u32, u32), Option<Pixmap>)>,
+}
+
+impl Worker {
+    fn spawn(data: Data, opts: Arc<usvg::Options>, fontdb: Arc<Database>) -> std::io::Result<Self> {
+        let (sender, requests) = mpsc::channel();
+        let (results, receiver) = mpsc::channel();
+        std::thread::Builder::new()
+            .name("kas-resvg raster".to_string())
+            .spawn(move || {
+                // usvg::Tree is not Send, thus we must parse on this thread
+                let tree = match parse(data.as_ref(), &opts, &fontdb) {
+                    Ok(tree) => tree,
+                    Err(_) => return,
+                };
+                while let Ok(mut size) = requests.recv() {
+                    // Skip requests superseded while busy
+                    while let Ok(next) = requests.try_recv() {
+                        size = next;
+                    }
+                    let pixmap = raster(&tree, size);
+                    if results.send((size, pixmap)).is_err() {
+                        break;
+                    }
+                }
+            })?;
+        Ok(Worker { sender, receiver })
+    }
+}
+
+/// Raster `tree` to a pixmap of the given `size`
+fn raster(tree: &usvg::Tree, size: (u32, u32)) -> Option<Pixmap> {
+    let mut pixmap = Pixmap::new(size.0, size.1)?;
+    // alas, we cannot tell resvg to skip the aspect-ratio-scaling!
+    resvg::render(tree, usvg::FitTo::Height(size.1), pixmap.as_mut())?;
+    Some(pixmap)
+}
 
 widget! {
-    /// An SVG image loaded from a path
+    /// An SVG image
+    ///
+    /// The image may be loaded from a path or from data (e.g. embedded with
+    /// `include_bytes!`). Data is parsed when the widget is configured; on
+    /// error, the error is logged and a placeholder drawn instead. Use
+    /// [`Svg::load`] to read and check a file eagerly.
+    ///
+    /// Large images are rastered on a worker thread (one per widget, started
+    /// on first use). Size changes are debounced and only the latest size is
+    /// rastered; until this completes, any previous raster is drawn (scaled).
+    ///
+    /// Single-colour (symbolic) icons may be tinted with a theme colour; see
+    /// [`Svg::with_symbolic`].
+    ///
+    /// The image is scaled within its allocation according to its fit mode;
+    /// see [`Svg::with_aspect`].
+    ///
+    /// Clones do not share the raster and must be configured before use.
     #[cfg_attr(doc_cfg, doc(cfg(feature = "svg")))]
-    #[autoimpl(Debug skip self.tree)]
-    #[derive(Clone)]
+    #[autoimpl(Clone, Debug skip self.tree, self.opts, self.fontdb, self.pixmap, self.image_id,
+        self.worker, self.requested, self.debounce, self.tinted)]
     pub struct Svg {
         #[widget_core]
         core: CoreData,
-        path: PathBuf,
+        path: Option<PathBuf>,
+        data: Option<Data>,
+        data_key: u64,
         tree: Option<usvg::Tree>,
+        opts: Option<Arc<usvg::Options>>,
+        fontdb: Option<Arc<Database>>,
+        failed: bool,
         margins: MarginSelector,
         min_size_factor: f32,
         ideal_size_factor: f32,
         min_size: Size,
         ideal_size: Size,
         stretch: Stretch,
+        aspect: AspectScaling,
+        image_rect: Rect,
+        symbolic: Option<SymbolClass>,
         pixmap: Option<Pixmap>,
         image_id: Option<ImageId>,
+        worker: Option<Worker>,
+        // Size requested of the worker, if not yet received
+        requested: Option<(u32, u32)>,
+        // Size to request of the worker, after the given time
+        debounce: Option<((u32, u32), Instant)>,
+        tinted: Option<(TintKey, ImageId)>,
     }
 
     impl Svg {
-        /// Construct with a path and size factors
-        ///
-        /// An SVG image has an embedded "original" size. This constructor
-        /// multiplies that size by the given factors to obtain minimum and ideal
-        /// sizes (see [`SizeRules`] for a description of min / ideal sizes).
-        pub fn from_path_and_factors<P: Into<PathBuf>>(
-            path: P,
+        fn new(
+            path: Option<PathBuf>,
+            data: Option<Data>,
             min_size_factor: f32,
             ideal_size_factor: f32,
         ) -> Self {
+            let data_key = data.as_ref().map(Data::key).unwrap_or(0);
             Svg {
                 core: Default::default(),
-                path: path.into(),
+                path,
+                data,
+                data_key,
                 tree: None,
+                opts: None,
+                fontdb: None,
+                failed: false,
                 margins: MarginSelector::Outer,
                 min_size_factor,
                 ideal_size_factor,
                 min_size: Size::ZERO,
                 ideal_size: Size::ZERO,
                 stretch: Stretch::Low,
+                aspect: AspectScaling::Fixed,
+                image_rect: Rect::ZERO,
+                symbolic: None,
                 pixmap: None,
                 image_id: None,
+                worker: None,
+                requested: None,
+                debounce: None,
+                tinted: None,
             }
         }
 
+        /// Construct with a path and size factors
+        ///
+        /// An SVG image has an embedded "original" size. This constructor
+        /// multiplies that size by the given factors to obtain minimum and ideal
+        /// sizes (see [`SizeRules`] for a description of min / ideal sizes).
+        ///
+        /// The file is read when the widget is configured.
+        pub fn from_path_and_factors<P: Into<PathBuf>>(
+            path: P,
+            min_size_factor: f32,
+            ideal_size_factor: f32,
+        ) -> Self {
+            Svg::new(Some(path.into()), None, min_size_factor, ideal_size_factor)
+        }
+
+        /// Construct from data and size factors
+        ///
+        /// This is intended for use with `include_bytes!`. Note that relative
+        /// paths to external resources cannot be resolved.
+        ///
+        /// Size factors are as for [`Svg::from_path_and_factors`].
+        pub fn from_bytes_and_factors(
+            data: &'static [u8],
+            min_size_factor: f32,
+            ideal_size_factor: f32,
+        ) -> Self {
+            let data = Some(Data::Static(data));
+            Svg::new(None, data, min_size_factor, ideal_size_factor)
+        }
+
+        /// Load from a path, with size factors
+        ///
+        /// Unlike [`Svg::from_path_and_factors`], this reads and parses the
+        /// file immediately, returning any error.
+        pub fn load<P: Into<PathBuf>>(
+            path: P,
+            min_size_factor: f32,
+            ideal_size_factor: f32,
+        ) -> Result<Self, LoadError> {
+            let path = path.into();
+            let data = std::fs::read(&path)?;
+            let mut opts = usvg::Options::default();
+            opts.resources_dir = path.parent().map(|dir| dir.to_owned());
+            // Check that data parses; we parse again when configured
+            let _ = usvg::Tree::from_data(&data, &opts.to_ref())?;
+            let data = Some(Data::Shared(data.into()));
+            Ok(Svg::new(Some(path), data, min_size_factor, ideal_size_factor))
+        }
+
         /// Set margins
         #[must_use]
         pub fn with_margins(mut self, margins: MarginSelector) -> Self {
@@ -74,6 +291,38 @@ widget! {
             self
         }
 
+        /// Set the fit mode
+        ///
+        /// This controls how the image is scaled within its allocation:
+        ///
+        /// -   [`AspectScaling::None`]: draw at ideal size
+        /// -   [`AspectScaling::Fixed`]: scale to fit, keeping aspect ratio
+        ///     (letterboxing); this is the default
+        /// -   [`AspectScaling::Free`]: scale to fill, ignoring aspect ratio
+        /// -   [`AspectScaling::Cover`]: scale to cover, keeping aspect ratio;
+        ///     the image is clipped to the allocated rect
+        ///
+        /// With `None` the minimum size is the ideal size, while with `Cover`
+        /// the minimum size is zero.
+        #[must_use]
+        pub fn with_aspect(mut self, aspect: AspectScaling) -> Self {
+            self.aspect = aspect;
+            self
+        }
+
+        /// Draw as a symbolic icon
+        ///
+        /// Only the alpha channel of the image is used; colour is taken from
+        /// the theme according to `class` (see
+        /// [`DrawHandle::symbol_color`](kas::theme::DrawHandle::symbol_color)),
+        /// thus the icon adapts to light and dark themes. Tinted images are
+        /// shared between widgets using the same data, colour and size.
+        #[must_use]
+        pub fn with_symbolic(mut self, class: SymbolClass) -> Self {
+            self.symbolic = Some(class);
+            self
+        }
+
         /// Set margins
         pub fn set_margins(&mut self, margins: MarginSelector) {
             self.margins = margins;
@@ -83,44 +332,213 @@ widget! {
         pub fn set_stretch(&mut self, stretch: Stretch) {
             self.stretch = stretch;
         }
+
+        /// Set the fit mode
+        ///
+        /// See [`Svg::with_aspect`].
+        pub fn set_aspect(&mut self, aspect: AspectScaling) -> TkAction {
+            self.aspect = aspect;
+            TkAction::RESIZE
+        }
+
+        /// Set whether to draw as a symbolic icon
+        ///
+        /// See [`Svg::with_symbolic`]. This should be called before the widget
+        /// is sized (e.g. before the window is created).
+        pub fn set_symbolic(&mut self, class: Option<SymbolClass>) {
+            self.symbolic = class;
+        }
+
+        /// True if loading or parsing failed
+        pub fn is_failed(&self) -> bool {
+            self.failed
+        }
+
+        fn load_tree(&mut self, mgr: &mut SetRectMgr) -> Result<(), LoadError> {
+            if self.data.is_none() {
+                if let Some(path) = self.path.as_ref() {
+                    let data = Data::Shared(std::fs::read(path)?.into());
+                    self.data_key = data.key();
+                    self.data = Some(data);
+                }
+            }
+            let data = match self.data.as_ref() {
+                Some(data) => data,
+                None => return Ok(()),
+            };
+
+            let scale_factor = mgr.scale_factor();
+            let def_size = 100.0 * f64::conv(scale_factor);
+            let font_family = kas::text::fonts::fonts()
+                .read_db()
+                .font_family_from_alias("SERIF")
+                .unwrap_or_default();
+            let font_size = mgr.size_mgr().pixels_from_em(1.0) as f64;
+
+            let resources_dir = self.path.as_deref().and_then(Path::parent);
+
+            // TODO: some options here should be configurable
+            let opts = usvg::Options {
+                resources_dir: resources_dir.map(|dir| dir.to_owned()),
+                dpi: 96.0 * f64::conv(scale_factor),
+                font_family,
+                font_size,
+                languages: vec![],
+                default_size: usvg::Size::new(def_size, def_size).unwrap(),
+                // The shared database is used instead (see parse)
+                fontdb: Database::new(),
+                ..Default::default()
+            };
+
+            let fontdb = font_db();
+            let tree = parse(data.as_ref(), &opts, &fontdb)?;
+            let size = tree.svg_node().size.to_screen_size().dimensions();
+            self.tree = Some(tree);
+            self.opts = Some(Arc::new(opts));
+            self.fontdb = Some(fontdb);
+            let size = Vec2(size.0.cast(), size.1.cast());
+            self.min_size = Size::from(size * self.min_size_factor * scale_factor);
+            self.ideal_size = Size::from(size * self.ideal_size_factor * scale_factor);
+            Ok(())
+        }
+
+        /// Start the worker thread if required, returning true if available
+        fn start_worker(&mut self) -> bool {
+            if self.worker.is_some() {
+                return true;
+            }
+            let (data, opts, fontdb) = match (&self.data, &self.opts, &self.fontdb) {
+                (Some(data), Some(opts), Some(fontdb)) => {
+                    (data.clone(), opts.clone(), fontdb.clone())
+                }
+                _ => return false,
+            };
+            match Worker::spawn(data, opts, fontdb) {
+                Ok(worker) => {
+                    self.worker = Some(worker);
+                    true
+                }
+                Err(e) => {
+                    log::warn!("Svg: failed to spawn thread: {}", e);
+                    false
+                }
+            }
+        }
+
+        /// Request a raster from the worker, polling for the result
+        fn request_raster(&mut self, ev: &mut EventState, size: (u32, u32)) {
+            if self.requested == Some(size) {
+                return;
+            }
+            let worker = match self.worker.as_ref() {
+                Some(worker) => worker,
+                None => return,
+            };
+            if worker.sender.send(size).is_err() {
+                // The worker failed to parse the image
+                self.worker = None;
+                self.requested = None;
+                return;
+            }
+            self.requested = Some(size);
+            ev.update_on_timer(POLL_INTERVAL, self.id(), TIMER_POLL);
+        }
+
+        /// Receive the requested raster, if ready
+        fn poll_worker(&mut self, mgr: &mut EventMgr) {
+            let (worker, requested) = match (self.worker.as_ref(), self.requested) {
+                (Some(worker), Some(size)) => (worker, size),
+                _ => return,
+            };
+            loop {
+                match worker.receiver.try_recv() {
+                    // Results for superseded requests are discarded
+                    Ok((size, mut pixmap)) if size == requested => {
+                        self.requested = None;
+                        mgr.draw_shared(|ds| self.set_pixmap(ds, pixmap.take()));
+                        mgr.redraw(self.id());
+                        return;
+                    }
+                    Ok(_) => (),
+                    Err(mpsc::TryRecvError::Empty) => {
+                        mgr.update_on_timer(POLL_INTERVAL, self.id(), TIMER_POLL);
+                        return;
+                    }
+                    Err(mpsc::TryRecvError::Disconnected) => {
+                        self.worker = None;
+                        self.requested = None;
+                        return;
+                    }
+                }
+            }
+        }
+
+        /// Replace the pixmap and upload
+        fn set_pixmap(&mut self, ds: &mut dyn DrawShared, pixmap: Option<Pixmap>) {
+            if let Some(id) = self.image_id.take() {
+                ds.image_free(id);
+            }
+            self.pixmap = pixmap;
+            if self.symbolic.is_some() {
+                // Tinted images are uploaded when drawn
+                return;
+            }
+            if let Some(pm) = self.pixmap.as_ref() {
+                match ds.image_alloc((pm.width(), pm.height())) {
+                    Ok(id) => {
+                        ds.image_upload(id, pm.data(), ImageFormat::Rgba8);
+                        self.image_id = Some(id);
+                    }
+                    Err(e) => log::warn!("Svg: failed to allocate image: {}", e),
+                }
+            }
+        }
+
+        /// Get the tinted image for colour `col`, updating if required
+        fn tinted_image(&mut self, ds: &mut dyn DrawShared, col: Rgba) -> Option<ImageId> {
+            let pm = match (self.pixmap.as_ref(), self.data.is_some()) {
+                (Some(pm), true) => pm,
+                _ => return None,
+            };
+            let key = TintKey {
+                icon: self.data_key,
+                col: col.into(),
+                size: (pm.width(), pm.height()),
+            };
+            if let Some((k, id)) = self.tinted {
+                if k == key {
+                    return Some(id);
+                }
+                tint::release(ds, &k);
+                self.tinted = None;
+            }
+            let id = tint::acquire(ds, key, pm)?;
+            self.tinted = Some((key, id));
+            Some(id)
+        }
     }
 
     impl WidgetConfig for Svg {
         fn configure(&mut self, mgr: &mut SetRectMgr) {
-            if self.tree.is_none() {
-                // TODO: maybe we should use a singleton to deduplicate loading by
-                // path? Probably not much use for duplicate SVG widgets however.
-                let data = std::fs::read(&self.path).unwrap();
-                let scale_factor = mgr.scale_factor();
-                let def_size = 100.0 * f64::conv(scale_factor);
-                let fonts_db = kas::text::fonts::fonts().read_db();
-                let fontdb = fonts_db.db();
-                let font_family = fonts_db
-                    .font_family_from_alias("SERIF")
-                    .unwrap_or_default();
-                let font_size = mgr.size_mgr().pixels_from_em(1.0) as f64;
-
-                // TODO: some options here should be configurable
-                let opts = usvg::OptionsRef {
-                    resources_dir: self.path.parent(),
-                    dpi: 96.0 * f64::conv(scale_factor),
-                    font_family: &font_family,
-                    font_size,
-                    languages: &[],
-                    shape_rendering: usvg::ShapeRendering::default(),
-                    text_rendering: usvg::TextRendering::default(),
-                    image_rendering: usvg::ImageRendering::default(),
-                    keep_named_groups: false,
-                    default_size: usvg::Size::new(def_size, def_size).unwrap(),
-                    fontdb,
-                };
+            if self.tree.is_none() && !self.failed {
+                if let Err(e) = self.load_tree(mgr) {
+                    match self.path.as_ref() {
+                        Some(path) => log::warn!("Svg: failed to load {}: {}", path.display(), e),
+                        None => log::warn!("Svg: failed to load: {}", e),
+                    }
+                    self.failed = true;
+                    let size = i32::conv_nearest(PLACEHOLDER_SIZE * mgr.scale_factor());
+                    self.min_size = Size::splat(size);
+                    self.ideal_size = Size::splat(size);
+                }
+            }
 
-                let tree = usvg::Tree::from_data(&data, &opts).unwrap();
-                let size = tree.svg_node().size.to_screen_size().dimensions();
-                self.tree = Some(tree);
-                let size = Vec2(size.0.cast(), size.1.cast());
-                self.min_size = Size::from(size * self.min_size_factor * scale_factor);
-                self.ideal_size = Size::from(size * self.ideal_size_factor * scale_factor);
+            // Timers are cleared on reconfigure
+            if self.requested.is_some() {
+                mgr.ev_state().update_on_timer(POLL_INTERVAL, self.id(), TIMER_POLL);
+            }
+            if self.debounce.is_some() {
+                mgr.ev_state().update_on_timer(DEBOUNCE_DELAY, self.id(), TIMER_DEBOUNCE);
             }
         }
     }
@@ -128,65 +546,119 @@ widget! {
     impl Layout for Svg {
         fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
             let margins = self.margins.select(size_mgr);
+            let min_size = match self.aspect {
+                AspectScaling::None => self.ideal_size,
+                AspectScaling::Cover => Size::ZERO,
+                _ => self.min_size,
+            };
             if axis.is_horizontal() {
-                SizeRules::new(
-                    self.min_size.0,
-                    self.ideal_size.0,
-                    margins.horiz,
-                    self.stretch,
-                )
+                SizeRules::new(min_size.0, self.ideal_size.0, margins.horiz, self.stretch)
             } else {
-                SizeRules::new(
-                    self.min_size.1,
-                    self.ideal_size.1,
-                    margins.vert,
-                    self.stretch,
-                )
+                SizeRules::new(min_size.1, self.ideal_size.1, margins.vert, self.stretch)
             }
         }
 
         fn set_rect(&mut self, mgr: &mut SetRectMgr, rect: Rect, align: AlignHints) {
-            let size = match self.ideal_size.aspect_scale_to(rect.size) {
-                Some(size) => {
-                    self.core_data_mut().rect = align
-                        .complete(Align::Center, Align::Center)
-                        .aligned_rect(size, rect);
-                    Into::<(u32, u32)>::into(size)
-                }
+            let sprite = SpriteDisplay {
+                size: self.ideal_size,
+                aspect: self.aspect,
+                ..Default::default()
+            };
+            self.image_rect = sprite.sprite_rect(rect, align);
+            self.core_data_mut().rect = match self.aspect {
+                AspectScaling::Cover => rect,
+                _ => self.image_rect,
+            };
+
+            // The raster preserves aspect ratio; with AspectScaling::Free the
+            // result is stretched when drawn.
+            let size = match self.ideal_size.aspect_scale_to(self.image_rect.size) {
+                Some(size) => Into::<(u32, u32)>::into(size),
                 None => {
-                    self.core_data_mut().rect = rect;
-                    self.pixmap = None;
-                    self.image_id = None;
+                    self.set_pixmap(mgr.draw_shared(), None);
+                    self.requested = None;
+                    self.debounce = None;
                     return;
                 }
             };
 
             let pm_size = self.pixmap.as_ref().map(|pm| (pm.width(), pm.height()));
-            if pm_size.unwrap_or((0, 0)) != size {
-                if let Some(id) = self.image_id {
-                    mgr.draw_shared().image_free(id);
-                }
-                self.pixmap = Pixmap::new(size.0, size.1);
-                if let Some(tree) = self.tree.as_ref() {
-                    self.image_id = self.pixmap.as_mut().map(|pm| {
-                        let (w, h) = (pm.width(), pm.height());
-
-                        // alas, we cannot tell resvg to skip the aspect-ratio-scaling!
-                        resvg::render(tree, usvg::FitTo::Height(h), pm.as_mut());
+            if pm_size == Some(size) {
+                self.requested = None;
+                self.debounce = None;
+                return;
+            }
 
-                        let id = mgr.draw_shared().image_alloc((w, h)).unwrap();
-                        mgr.draw_shared().image_upload(id, pm.data(), ImageFormat::Rgba8);
-                        id
-                    });
+            if size.0.saturating_mul(size.1) > ASYNC_AREA && self.start_worker() {
+                if self.pixmap.is_none() {
+                    self.debounce = None;
+                    self.request_raster(mgr.ev_state(), size);
+                } else {
+                    // Draw the old raster (scaled) until size changes settle
+                    self.debounce = Some((size, Instant::now() + DEBOUNCE_DELAY));
+                    mgr.ev_state().update_on_timer(DEBOUNCE_DELAY, self.id(), TIMER_DEBOUNCE);
                 }
+            } else if let Some(tree) = self.tree.as_ref() {
+                self.requested = None;
+                self.debounce = None;
+                let pixmap = raster(tree, size);
+                self.set_pixmap(mgr.draw_shared(), pixmap);
             }
         }
 
         fn draw(&mut self, mut draw: DrawMgr) {
             let mut draw = draw.with_core(self.core_data());
-            if let Some(id) = self.image_id {
-                draw.image(id, self.rect());
+            let mut id = self.image_id;
+            if let Some(class) = self.symbolic {
+                let col = draw.symbol_color(class);
+                id = self.tinted_image(draw.draw_shared(), col).or(id);
             }
+            if let Some(id) = id {
+                let rect = self.image_rect;
+                if self.aspect == AspectScaling::Cover {
+                    draw.with_clip_region(self.core.rect, Offset::ZERO, |mut draw| {
+                        draw.image(id, rect);
+                    });
+                } else {
+                    draw.image(id, rect);
+                }
+            } else if self.failed {
+                draw.outer_frame(self.rect());
+            }
+        }
+    }
+
+    impl Handler for Svg {
+        type Msg = VoidMsg;
+
+        fn handle(&mut self, mgr: &mut EventMgr, event: Event) -> Response<Self::Msg> {
+            match event {
+                Event::TimerUpdate(TIMER_DEBOUNCE) => {
+                    if let Some((size, time)) = self.debounce {
+                        let now = Instant::now();
+                        if now < time {
+                            mgr.update_on_timer(time - now, self.id(), TIMER_DEBOUNCE);
+                        } else {
+                            self.debounce = None;
+                            self.request_raster(mgr, size);
+                        }
+                    }
+                    Response::Used
+                }
+                Event::TimerUpdate(_) => {
+                    self.poll_worker(mgr);
+                    Response::Used
+                }
+                _ => Response::Unused,
+            }
+        }
+    }
+}
+
+impl Drop for Svg {
+    fn drop(&mut self) {
+        if let Some((key, _)) = self.tinted.take() {
+            tint::release_deferred(&key);
         }
     }
 }
@@ -0,0 +1,106 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Cache of tinted (symbolic) icons
+//!
+//! Symbolic icons use only the alpha channel of the rastered image; colour is
+//! supplied by the theme. Images are shared between all widgets drawing the
+//! same icon data in the same colour and at the same size.
+
+use kas::draw::color::{Rgba, Rgba8Srgb};
+use kas::draw::{DrawShared, ImageFormat, ImageId};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use tiny_skia::Pixmap;
+
+/// Key identifying a tinted icon
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TintKey {
+    /// Identity of the icon data (a hash of the content)
+    pub icon: u64,
+    pub col: Rgba8Srgb,
+    pub size: (u32, u32),
+}
+
+#[derive(Debug)]
+struct Entry {
+    id: ImageId,
+    users: u32,
+}
+
+thread_local! {
+    static CACHE: RefCell<HashMap<TintKey, Entry>> = RefCell::new(HashMap::new());
+}
+
+/// Get a tinted image, creating if required
+///
+/// The `mask` must be a raster of the icon at `key.size`. Each successful
+/// call must be matched by a call to [`release`].
+pub fn acquire(ds: &mut dyn DrawShared, key: TintKey, mask: &Pixmap) -> Option<ImageId> {
+    CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if let Some(entry) = cache.get_mut(&key) {
+            entry.users += 1;
+            return Some(entry.id);
+        }
+        free_unused(&mut cache, ds);
+
+        let id = match ds.image_alloc(key.size) {
+            Ok(id) => id,
+            Err(e) => {
+                log::warn!("Svg: failed to allocate image: {}", e);
+                return None;
+            }
+        };
+        ds.image_upload(id, &tint(mask, key.col.into()), ImageFormat::Rgba8);
+        cache.insert(key, Entry { id, users: 1 });
+        Some(id)
+    })
+}
+
+/// Release a tinted image, freeing when no longer used
+pub fn release(ds: &mut dyn DrawShared, key: &TintKey) {
+    CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if let Some(entry) = cache.get_mut(key) {
+            entry.users = entry.users.saturating_sub(1);
+        }
+        free_unused(&mut cache, ds);
+    });
+}
+
+/// Release a tinted image where no [`DrawShared`] is available (e.g. on drop)
+///
+/// The image is freed by the next call to [`acquire`] or [`release`] unless
+/// re-acquired first.
+pub fn release_deferred(key: &TintKey) {
+    CACHE.with(|cache| {
+        if let Some(entry) = cache.borrow_mut().get_mut(key) {
+            entry.users = entry.users.saturating_sub(1);
+        }
+    });
+}
+
+fn free_unused(cache: &mut HashMap<TintKey, Entry>, ds: &mut dyn DrawShared) {
+    cache.retain(|_, entry| {
+        if entry.users == 0 {
+            ds.image_free(entry.id);
+        }
+        entry.users > 0
+    });
+}
+
+/// Colour `mask` with `col`, using the mask's alpha channel only
+///
+/// Output is RGBA (sRGB, straight alpha).
+fn tint(mask: &Pixmap, col: Rgba) -> Vec<u8> {
+    let rgba: [u8; 4] = Rgba8Srgb::from(col).into();
+    let mut data = Vec::with_capacity(mask.data().len());
+    for pixel in mask.data().chunks_exact(4) {
+        let a = f32::from(pixel[3]) * col.a;
+        data.extend_from_slice(&[rgba[0], rgba[1], rgba[2], a.round() as u8]);
+    }
+    data
+}
@@ -5,6 +5,7 @@
 
 //! Animation helpers
 
+use crate::ColorsLinear;
 use kas::draw::DrawImpl;
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
@@ -12,6 +13,8 @@ use std::marker::PhantomData;
 use std::time::{Duration, Instant};
 
 const TIMEOUT: Duration = Duration::from_secs(8);
+const MARCH_STEP: Duration = Duration::from_millis(80);
+const SCHEME_FADE: Duration = Duration::from_millis(200);
 
 #[derive(Debug)]
 struct Config {
@@ -23,6 +26,7 @@ struct Config {
 #[derive(Debug)]
 pub struct AnimState<D> {
     c: Config,
+    start: Instant,
     now: Instant, // frame start time
     time_next_gc: Instant,
     text_cursor: HashMap<u64, TextCursor>,
@@ -39,6 +43,7 @@ impl<D> AnimState<D> {
         let now = Instant::now();
         AnimState {
             c,
+            start: now,
             now,
             time_next_gc: now + TIMEOUT,
             text_cursor: Default::default(),
@@ -141,3 +146,48 @@ impl<D: DrawImpl> AnimState<D> {
         !out_state as u8 as f32
     }
 }
+
+impl<D: DrawImpl> AnimState<D> {
+    /// Offset for a "marching ants" stroke pattern
+    ///
+    /// Returns an offset in the range `0..period` which advances by one unit
+    /// every 80ms, and schedules the next update.
+    pub fn march(&mut self, draw: &mut D, period: f32) -> f32 {
+        let t = (self.now - self.start).as_secs_f64() / MARCH_STEP.as_secs_f64();
+        let n = t.floor();
+        draw.animate_at(self.now + MARCH_STEP.mul_f64(n + 1.0 - t));
+        n.rem_euclid(period.max(1.0).into()) as f32
+    }
+}
+
+/// Transition between colour schemes
+///
+/// Unlike [`AnimState`], this is not per-window state: it is held by the
+/// theme and shared by all windows.
+#[derive(Clone, Debug)]
+pub struct SchemeTransition {
+    from: ColorsLinear,
+    start: Instant,
+}
+
+impl SchemeTransition {
+    /// Start a transition from colours `from`
+    pub fn new(from: ColorsLinear) -> Self {
+        SchemeTransition {
+            from,
+            start: Instant::now(),
+        }
+    }
+
+    /// Get colours blended from the old scheme towards `to`
+    ///
+    /// Returns `None` once the transition is complete.
+    pub fn blend(&self, to: &ColorsLinear) -> Option<ColorsLinear> {
+        let elapsed = self.start.elapsed();
+        if elapsed >= SCHEME_FADE {
+            return None;
+        }
+        let t = elapsed.as_secs_f32() / SCHEME_FADE.as_secs_f32();
+        Some(self.from.blend(to, t))
+    }
+}
@@ -6,7 +6,7 @@
 //! Colour schemes
 
 use kas::draw::color::{Rgba, Rgba8Srgb};
-use kas::theme::InputState;
+use kas::theme::{InputState, SymbolClass};
 use std::str::FromStr;
 
 const MULT_DEPRESS: f32 = 0.75;
@@ -230,6 +230,15 @@ impl ColorsLinear {
         Self::adjust_for_state(self.accent, state)
     }
 
+    /// Get colour of a symbol (e.g. a symbolic icon), depending on state
+    pub fn symbol_state(&self, class: SymbolClass, state: InputState) -> Rgba {
+        match class {
+            SymbolClass::Text if state.disabled() => self.text_disabled,
+            SymbolClass::Text => self.text,
+            SymbolClass::Accent => self.accent_state(state),
+        }
+    }
+
     /// Get background highlight colour of a menu entry, if any
     pub fn menu_entry(&self, state: InputState) -> Option<Rgba> {
         if state.depress() || state.nav_focus() {
@@ -239,6 +248,36 @@ impl ColorsLinear {
         }
     }
 
+    /// Blend towards `other`
+    ///
+    /// Colours are interpolated linearly with factor `t` (where `0.0` yields
+    /// `self` and `1.0` yields `other`); `is_dark` switches at the midpoint.
+    pub fn blend(&self, other: &Self, t: f32) -> Self {
+        let mix = |a: Rgba, b: Rgba| {
+            Rgba::rgba(
+                a.r + (b.r - a.r) * t,
+                a.g + (b.g - a.g) * t,
+                a.b + (b.b - a.b) * t,
+                a.a + (b.a - a.a) * t,
+            )
+        };
+        Colors {
+            is_dark: if t < 0.5 { self.is_dark } else { other.is_dark },
+            background: mix(self.background, other.background),
+            frame: mix(self.frame, other.frame),
+            accent: mix(self.accent, other.accent),
+            accent_soft: mix(self.accent_soft, other.accent_soft),
+            nav_focus: mix(self.nav_focus, other.nav_focus),
+            edit_bg: mix(self.edit_bg, other.edit_bg),
+            edit_bg_disabled: mix(self.edit_bg_disabled, other.edit_bg_disabled),
+            edit_bg_error: mix(self.edit_bg_error, other.edit_bg_error),
+            text: mix(self.text, other.text),
+            text_invert: mix(self.text_invert, other.text_invert),
+            text_disabled: mix(self.text_disabled, other.text_disabled),
+            text_sel_bg: mix(self.text_sel_bg, other.text_sel_bg),
+        }
+    }
+
     /// Get appropriate text colour over the given background
     pub fn text_over(&self, bg: Rgba) -> Rgba {
         let bg_sum = bg.sum();
@@ -12,6 +12,17 @@ use kas::TkAction;
 use std::collections::BTreeMap;
 use std::time::Duration;
 
+/// Colour emoji font families, in order of preference
+///
+/// These are used as fallback fonts for generic families (see
+/// [`Config`]) and by renderers to locate colour glyph bitmaps.
+pub const EMOJI_FAMILIES: &[&str] = &[
+    "Noto Color Emoji",
+    "Apple Color Emoji",
+    "Segoe UI Emoji",
+    "Twemoji",
+];
+
 /// Event handling configuration
 #[derive(Clone, Debug, PartialEq)]
 #[cfg_attr(feature = "config", derive(serde::Serialize, serde::Deserialize))]
@@ -34,7 +45,10 @@ pub struct Config {
     color_schemes: BTreeMap<String, ColorsSrgb>,
 
     /// Font aliases, used when searching for a font family matching the key.
-    #[cfg_attr(feature = "config", serde(default))]
+    ///
+    /// By default, [`EMOJI_FAMILIES`] are appended to generic families as
+    /// fallbacks, allowing emoji to be drawn.
+    #[cfg_attr(feature = "config", serde(default = "defaults::font_aliases"))]
     font_aliases: BTreeMap<String, FontAliases>,
 
     /// Standard fonts
@@ -52,6 +66,14 @@ pub struct Config {
     /// Text glyph rastering settings
     #[cfg_attr(feature = "config", serde(default))]
     raster: RasterConfig,
+
+    /// Cache rastered theme elements (e.g. button frames) as images
+    #[cfg_attr(feature = "config", serde(default))]
+    chrome_cache: bool,
+
+    /// Focus ring (navigation highlight) style
+    #[cfg_attr(feature = "config", serde(default))]
+    focus_ring: FocusRingConfig,
 }
 
 impl Default for Config {
@@ -61,15 +83,59 @@ impl Default for Config {
             font_size: defaults::font_size(),
             active_scheme: Default::default(),
             color_schemes: defaults::color_schemes(),
-            font_aliases: Default::default(),
+            font_aliases: defaults::font_aliases(),
             fonts: defaults::fonts(),
             cursor_blink_rate_ms: defaults::cursor_blink_rate_ms(),
             transition_fade_ms: defaults::transition_fade_ms(),
             raster: Default::default(),
+            chrome_cache: false,
+            focus_ring: Default::default(),
         }
     }
 }
 
+/// Placement of a focus ring relative to the focussed element
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "config", derive(serde::Serialize, serde::Deserialize))]
+pub enum FocusPlacement {
+    /// Draw the ring inside the element's frame
+    Inside,
+    /// Draw the ring around the outside of the element
+    ///
+    /// Space for the ring is reserved as an outer margin.
+    Outside,
+}
+
+impl Default for FocusPlacement {
+    fn default() -> Self {
+        FocusPlacement::Inside
+    }
+}
+
+/// Focus ring settings
+///
+/// These control the navigation highlight drawn by `NavFrame` and (where
+/// applicable) the highlighting of focussed controls.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "config", derive(serde::Serialize, serde::Deserialize))]
+pub struct FocusRingConfig {
+    /// Placement of the ring drawn by `NavFrame`
+    #[cfg_attr(feature = "config", serde(default))]
+    pub placement: FocusPlacement,
+    /// Ring thickness (multiplied by the DPI factor)
+    ///
+    /// If zero, the theme's inner margin is used.
+    #[cfg_attr(feature = "config", serde(default))]
+    pub thickness: f32,
+    /// Only show focus when set via the keyboard
+    ///
+    /// When true, navigation focus set by mouse or touch input is not
+    /// highlighted (like the CSS `:focus-visible` selector). See
+    /// `InputState::KEY_FOCUS`.
+    #[cfg_attr(feature = "config", serde(default))]
+    pub keyboard_only: bool,
+}
+
 /// Font raster settings
 ///
 /// These are not used by the theme, but passed through to the rendering
@@ -179,6 +245,21 @@ impl Config {
     pub fn transition_fade_duration(&self) -> Duration {
         Duration::from_millis(self.transition_fade_ms as u64)
     }
+
+    /// Whether to cache rastered theme elements
+    ///
+    /// When enabled, frequently repeated elements such as button frames are
+    /// rastered once per size and state and subsequently drawn as images.
+    #[inline]
+    pub fn chrome_cache(&self) -> bool {
+        self.chrome_cache
+    }
+
+    /// Focus ring settings
+    #[inline]
+    pub fn focus_ring(&self) -> &FocusRingConfig {
+        &self.focus_ring
+    }
 }
 
 /// Setters
@@ -194,6 +275,18 @@ impl Config {
         self.dirty = true;
         self.active_scheme = scheme.to_string();
     }
+
+    /// Enable or disable caching of rastered theme elements
+    pub fn set_chrome_cache(&mut self, enable: bool) {
+        self.dirty = true;
+        self.chrome_cache = enable;
+    }
+
+    /// Set focus ring style
+    pub fn set_focus_ring(&mut self, focus_ring: FocusRingConfig) {
+        self.dirty = true;
+        self.focus_ring = focus_ring;
+    }
 }
 
 /// Other functions
@@ -201,8 +294,10 @@ impl Config {
     /// Currently this is just "set". Later, maybe some type of merge.
     #[allow(clippy::float_cmp)]
     pub fn apply_config(&mut self, other: &Config) -> TkAction {
-        let action = if self.font_size != other.font_size {
+        let action = if self.font_size != other.font_size || self.focus_ring != other.focus_ring {
             TkAction::RESIZE | TkAction::THEME_UPDATE
+        } else if self.chrome_cache != other.chrome_cache {
+            TkAction::REDRAW | TkAction::THEME_UPDATE
         } else if self != other {
             TkAction::REDRAW
         } else {
@@ -263,6 +358,17 @@ mod defaults {
         10.0
     }
 
+    pub fn font_aliases() -> BTreeMap<String, FontAliases> {
+        let emoji = FontAliases {
+            mode: AddMode::Append,
+            list: EMOJI_FAMILIES.iter().map(|s| s.to_string()).collect(),
+        };
+        ["SERIF", "SANS_SERIF", "MONOSPACE"]
+            .iter()
+            .map(|family| (family.to_string(), emoji.clone()))
+            .collect()
+    }
+
     pub fn color_schemes() -> BTreeMap<String, ColorsSrgb> {
         let mut schemes = BTreeMap::new();
         schemes.insert("light".to_string(), ColorsSrgb::light());
@@ -11,16 +11,19 @@ use std::f32;
 use std::rc::Rc;
 
 use crate::anim::AnimState;
+use crate::raster_cache::RasterCache;
+use crate::{FocusPlacement, FocusRingConfig};
 use kas::cast::{Cast, CastFloat, ConvFloat};
-use kas::geom::{Size, Vec2};
+use kas::geom::{PixelAlign, Size, Vec2};
 use kas::layout::{AxisInfo, FrameRules, Margins, SizeRules, Stretch};
 use kas::text::{fonts::FontId, TextApi, TextApiExt};
-use kas::theme::{SizeHandle, TextClass};
+use kas::theme::{IconSize, InputState, SizeHandle, TextClass};
 
 /// Parameterisation of [`Dimensions`]
 ///
 /// All dimensions are multiplied by the DPI factor, then rounded to the
-/// nearest integer. Example: `(2.0 * 1.25).round() = 3.0`.
+/// nearest integer. Example: `(2.0 * 1.25).round() = 3.0`. Line thicknesses
+/// (frames and markers) are at least one pixel unless zero.
 #[derive(Clone, Debug)]
 pub struct Parameters {
     /// Space between elements
@@ -47,6 +50,8 @@ pub struct Parameters {
     pub shadow_size: Vec2,
     /// Proportional offset of shadow (range: -1..=1)
     pub shadow_rel_offset: Vec2,
+    /// Icon sizes: small, medium, large
+    pub icon_sizes: [f32; 3],
 }
 
 /// Dimensions available within [`Window`]
@@ -70,6 +75,7 @@ pub struct Dimensions {
     pub progress_bar: Size,
     pub shadow_a: Vec2,
     pub shadow_b: Vec2,
+    pub icon_sizes: [i32; 3],
 }
 
 impl Dimensions {
@@ -87,7 +93,8 @@ impl Dimensions {
         let inner_margin = (params.inner_margin * scale_factor).cast_nearest();
         let frame_margin = (params.frame_margin * scale_factor).cast_nearest();
         let text_margin = (params.text_margin * scale_factor).cast_nearest();
-        let frame = (params.frame_size * scale_factor).cast_nearest();
+        let px = PixelAlign::new(scale_factor);
+        let frame = px.length_from_virtual(params.frame_size).cast_nearest();
 
         let shadow_size = params.shadow_size * scale_factor;
         let shadow_offset = shadow_size * params.shadow_rel_offset;
@@ -96,7 +103,7 @@ impl Dimensions {
             scale_factor,
             dpp,
             pt_size,
-            font_marker_width: (1.6 * scale_factor).round().max(1.0),
+            font_marker_width: px.length_from_virtual(1.6),
             line_height,
             min_line_length: (8.0 * dpem).cast_nearest(),
             outer_margin,
@@ -104,7 +111,7 @@ impl Dimensions {
             frame_margin,
             text_margin,
             frame,
-            button_frame: (params.button_frame * scale_factor).cast_nearest(),
+            button_frame: px.length_from_virtual(params.button_frame).cast_nearest(),
             checkbox: i32::conv_nearest(params.checkbox_inner * dpp)
                 + 2 * (i32::from(inner_margin) + frame),
             scrollbar: Size::from(params.scrollbar_size * scale_factor),
@@ -112,8 +119,14 @@ impl Dimensions {
             progress_bar: Size::from(params.progress_bar * scale_factor),
             shadow_a: shadow_offset - shadow_size,
             shadow_b: shadow_offset + shadow_size,
+            icon_sizes: params.icon_sizes.map(|s| (s * scale_factor).cast_nearest()),
         }
     }
+
+    /// Get a helper to align geometry to physical pixels
+    pub fn pixel_align(&self) -> PixelAlign {
+        PixelAlign::new(self.scale_factor)
+    }
 }
 
 /// A convenient implementation of [`crate::Window`]
@@ -121,6 +134,9 @@ pub struct Window<D> {
     pub dims: Dimensions,
     pub fonts: Rc<LinearMap<TextClass, FontId>>,
     pub anim: AnimState<D>,
+    pub focus_ring: FocusRingConfig,
+    pub(crate) raster_cache: RasterCache,
+    text_zoom: f32,
 }
 
 impl<D> Window<D> {
@@ -134,11 +150,40 @@ impl<D> Window<D> {
             dims: Dimensions::new(dims, config.font_size(), scale_factor),
             fonts,
             anim: AnimState::new(config),
+            focus_ring: config.focus_ring().clone(),
+            raster_cache: RasterCache::new(config.chrome_cache()),
+            text_zoom: 1.0,
         }
     }
 
     pub fn update(&mut self, dims: &Parameters, config: &crate::Config, scale_factor: f32) {
-        self.dims = Dimensions::new(dims, config.font_size(), scale_factor);
+        let pt_size = config.font_size() * self.text_zoom;
+        self.dims = Dimensions::new(dims, pt_size, scale_factor);
+        self.focus_ring = config.focus_ring().clone();
+        self.raster_cache.set_enabled(config.chrome_cache());
+    }
+
+    /// Focus ring thickness, in pixels
+    pub fn focus_ring_width(&self) -> f32 {
+        let px = self.dims.pixel_align();
+        let thickness = px.length_from_virtual(self.focus_ring.thickness);
+        if thickness > 0.0 {
+            thickness
+        } else {
+            self.dims.inner_margin.into()
+        }
+    }
+
+    /// Adjust `state` for drawing focus indicators
+    ///
+    /// This removes [`InputState::NAV_FOCUS`] if focus rings are configured
+    /// to show only keyboard focus and focus was not set via the keyboard.
+    pub fn focus_state(&self, state: InputState) -> InputState {
+        if self.focus_ring.keyboard_only && !state.key_focus() {
+            state - InputState::NAV_FOCUS
+        } else {
+            state
+        }
     }
 }
 
@@ -147,6 +192,10 @@ impl<D: 'static> crate::Window for Window<D> {
         self
     }
 
+    fn set_text_zoom(&mut self, zoom: f32) {
+        self.text_zoom = zoom;
+    }
+
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self
     }
@@ -180,7 +229,13 @@ impl<D: 'static> SizeHandle for Window<D> {
     }
 
     fn nav_frame(&self, _vert: bool) -> FrameRules {
-        FrameRules::new_sym(self.dims.inner_margin.into(), 0, 0)
+        let size = i32::from(self.dims.inner_margin);
+        let width = i32::conv_nearest(self.focus_ring_width());
+        match self.focus_ring.placement {
+            FocusPlacement::Inside => FrameRules::new_sym(size.max(width), 0, 0),
+            // The ring is drawn around the frame: reserve space as margin
+            FocusPlacement::Outside => FrameRules::new_sym(size, 0, width.cast()),
+        }
     }
 
     fn inner_margin(&self) -> Size {
@@ -284,6 +339,11 @@ impl<D: 'static> SizeHandle for Window<D> {
         self.checkbox()
     }
 
+    fn switch(&self) -> Size {
+        let h = self.dims.checkbox;
+        Size(2 * h, h)
+    }
+
     fn scrollbar(&self) -> (Size, i32) {
         let size = self.dims.scrollbar;
         (size, 3 * size.0)
@@ -297,4 +357,13 @@ impl<D: 'static> SizeHandle for Window<D> {
     fn progress_bar(&self) -> Size {
         self.dims.progress_bar
     }
+
+    fn icon(&self, size: IconSize) -> i32 {
+        let index = match size {
+            IconSize::Small => 0,
+            IconSize::Medium => 1,
+            IconSize::Large => 2,
+        };
+        self.dims.icon_sizes[index]
+    }
 }
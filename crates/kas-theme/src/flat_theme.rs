@@ -8,34 +8,42 @@
 //! Widget size and appearance can be modified through themes.
 
 use linear_map::LinearMap;
+use std::borrow::Cow;
 use std::f32;
 use std::ops::Range;
 use std::rc::Rc;
 
-use crate::{dim, ColorsLinear, Config, Theme};
+use crate::anim::SchemeTransition;
+use crate::raster_cache::CacheKey;
+use crate::{dim, ColorsLinear, Config, FocusPlacement, Theme};
 use kas::cast::Cast;
 use kas::dir::{Direction, Directional};
-use kas::draw::{color::Rgba, *};
+use kas::draw::{color::Rgba, color::Rgba8Srgb, *};
 use kas::geom::*;
 use kas::text::format::FormattableText;
 use kas::text::{fonts, AccelString, Effect, Text, TextApi, TextDisplay};
-use kas::theme::{self, InputState, SizeHandle, TextClass, ThemeControl};
+use kas::theme::{self, CheckState, InputState, SizeHandle, SymbolClass, TextClass, ThemeControl};
 use kas::TkAction;
 
 // Used to ensure a rectangular background is inside a circular corner.
 // Also the maximum inner radius of circular borders to overlap with this rect.
-const BG_SHRINK_FACTOR: f32 = 1.0 - std::f32::consts::FRAC_1_SQRT_2;
+pub(crate) const BG_SHRINK_FACTOR: f32 = 1.0 - std::f32::consts::FRAC_1_SQRT_2;
 
+// Darkening of button backgrounds at the bottom edge
+const BG_GRADIENT_FACTOR: f32 = 0.92;
 // Shadow enlargement on hover
 const SHADOW_HOVER: f32 = 1.1;
 // Shadow enlargement for pop-ups
 const SHADOW_POPUP: f32 = 1.2;
+// Colour of wavy underlines (e.g. spelling errors)
+const SQUIGGLE_COL: Rgba = Rgba::rgb(0.85, 0.05, 0.05);
 
 /// A theme with flat (unshaded) rendering
 #[derive(Clone, Debug)]
 pub struct FlatTheme {
     pub(crate) config: Config,
     pub(crate) cols: ColorsLinear,
+    transition: Option<SchemeTransition>,
     dims: dim::Parameters,
     pub(crate) fonts: Option<Rc<LinearMap<TextClass, fonts::FontId>>>,
 }
@@ -58,6 +66,7 @@ impl FlatTheme {
         FlatTheme {
             config: Default::default(),
             cols,
+            transition: None,
             dims,
             fonts: None,
         }
@@ -101,6 +110,20 @@ impl FlatTheme {
         self.cols = cols;
         action
     }
+
+    /// Get colours for drawing
+    ///
+    /// During a colour scheme transition these are blended (and `draw` is
+    /// requested to animate).
+    pub(crate) fn draw_cols<D: Draw + ?Sized>(&self, draw: &mut D) -> Cow<ColorsLinear> {
+        match self.transition.as_ref().and_then(|t| t.blend(&self.cols)) {
+            Some(cols) => {
+                draw.animate();
+                Cow::Owned(cols)
+            }
+            None => Cow::Borrowed(&self.cols),
+        }
+    }
 }
 
 const DIMS: dim::Parameters = dim::Parameters {
@@ -117,6 +140,7 @@ const DIMS: dim::Parameters = dim::Parameters {
     progress_bar: Vec2::splat(8.0),
     shadow_size: Vec2(4.0, 4.0),
     shadow_rel_offset: Vec2(0.2, 0.3),
+    icon_sizes: [16.0, 24.0, 48.0],
 };
 const DARK_SHADOW_SIZE: Vec2 = Vec2::splat(5.0);
 const DARK_SHADOW_OFFSET: Vec2 = Vec2::ZERO;
@@ -124,7 +148,7 @@ const DARK_SHADOW_OFFSET: Vec2 = Vec2::ZERO;
 pub struct DrawHandle<'a, DS: DrawSharedImpl> {
     pub(crate) draw: DrawIface<'a, DS>,
     pub(crate) w: &'a mut dim::Window<DS::Draw>,
-    pub(crate) cols: &'a ColorsLinear,
+    pub(crate) cols: Cow<'a, ColorsLinear>,
 }
 
 impl<DS: DrawSharedImpl> Theme<DS> for FlatTheme
@@ -174,8 +198,13 @@ where
     }
 
     #[cfg(not(feature = "gat"))]
-    unsafe fn draw_handle(&self, draw: DrawIface<DS>, w: &mut Self::Window) -> Self::DrawHandle {
+    unsafe fn draw_handle(
+        &self,
+        mut draw: DrawIface<DS>,
+        w: &mut Self::Window,
+    ) -> Self::DrawHandle {
         w.anim.update();
+        w.raster_cache.frame_start(draw.shared);
 
         unsafe fn extend_lifetime<'b, T: ?Sized>(r: &'b T) -> &'static T {
             std::mem::transmute::<&'b T, &'static T>(r)
@@ -183,6 +212,10 @@ where
         unsafe fn extend_lifetime_mut<'b, T: ?Sized>(r: &'b mut T) -> &'static mut T {
             std::mem::transmute::<&'b mut T, &'static mut T>(r)
         }
+        let cols = match self.draw_cols(&mut draw) {
+            Cow::Borrowed(cols) => Cow::Borrowed(extend_lifetime(cols)),
+            Cow::Owned(cols) => Cow::Owned(cols),
+        };
         DrawHandle {
             draw: DrawIface {
                 draw: extend_lifetime_mut(draw.draw),
@@ -190,26 +223,27 @@ where
                 pass: draw.pass,
             },
             w: extend_lifetime_mut(w),
-            cols: extend_lifetime(&self.cols),
+            cols,
         }
     }
     #[cfg(feature = "gat")]
     fn draw_handle<'a>(
         &'a self,
-        draw: DrawIface<'a, DS>,
+        mut draw: DrawIface<'a, DS>,
         w: &'a mut Self::Window,
     ) -> Self::DrawHandle<'a> {
         w.anim.update();
+        w.raster_cache.frame_start(draw.shared);
 
-        DrawHandle {
-            draw,
-            w,
-            cols: &self.cols,
-        }
+        let cols = self.draw_cols(&mut draw);
+        DrawHandle { draw, w, cols }
     }
 
     fn clear_color(&self) -> Rgba {
-        self.cols.background
+        match self.transition.as_ref().and_then(|t| t.blend(&self.cols)) {
+            Some(cols) => cols.background,
+            None => self.cols.background,
+        }
     }
 }
 
@@ -229,8 +263,13 @@ impl ThemeControl for FlatTheme {
     fn set_scheme(&mut self, name: &str) -> TkAction {
         if name != self.config.active_scheme() {
             if let Some(scheme) = self.config.get_color_scheme(name) {
+                // Fade from the current (possibly blended) colours
+                let from = self.transition.as_ref().and_then(|t| t.blend(&self.cols));
+                let from = from.unwrap_or_else(|| self.cols.clone());
                 self.config.set_active_scheme(name);
-                return self.set_colors(scheme.into());
+                let action = self.set_colors(scheme.into());
+                self.transition = Some(SchemeTransition::new(from));
+                return action;
             }
         }
         TkAction::empty()
@@ -241,6 +280,18 @@ impl<'a, DS: DrawSharedImpl> DrawHandle<'a, DS>
 where
     DS::Draw: DrawRoundedImpl,
 {
+    /// Draw a cached element over `outer`, if available
+    ///
+    /// Returns false if the caller should draw the element directly.
+    fn draw_cached(&mut self, outer: Quad, key: CacheKey) -> bool {
+        if let Some(id) = self.w.raster_cache.get(self.draw.shared, key) {
+            self.draw.image_background(id, outer);
+            true
+        } else {
+            false
+        }
+    }
+
     fn button_frame(
         &mut self,
         outer: Quad,
@@ -268,8 +319,22 @@ where
                 .rounded_frame_2col(shadow_outer, inner, col1, col2);
         }
 
-        let bgr = outer.shrink(self.w.dims.button_frame as f32 * BG_SHRINK_FACTOR);
-        self.draw.rect(bgr, col_bg);
+        let col_bottom = col_bg.multiply(BG_GRADIENT_FACTOR);
+        let key = CacheKey::ButtonFrame {
+            size: Size::from(outer.size()).into(),
+            frame: self.w.dims.button_frame.cast(),
+            col_frame: col_frame.into(),
+            col_top: col_bg.into(),
+            col_bottom: col_bottom.into(),
+        };
+        if self.draw_cached(outer, key) {
+            return inner;
+        }
+
+        let px = self.w.dims.pixel_align();
+        let bgr = px.shrink(outer, self.w.dims.button_frame as f32 * BG_SHRINK_FACTOR);
+        self.draw
+            .rect_grad(bgr, Gradient::vertical(bgr, col_bg, col_bottom));
 
         self.draw
             .rounded_frame(outer, inner, BG_SHRINK_FACTOR, col_frame);
@@ -293,6 +358,7 @@ where
         &mut self,
         inner_rect: Rect,
         offset: Offset,
+        transform: Affine,
         class: PassType,
         f: &mut dyn FnMut(&mut dyn theme::DrawHandle),
     ) {
@@ -308,7 +374,7 @@ where
             let b = shadow.b.ceil();
             outer_rect = Rect::new(a.into(), (b - a).into());
         }
-        let mut draw = self.draw.new_pass(outer_rect, offset, class);
+        let mut draw = self.draw.new_pass(outer_rect, offset, transform, class);
 
         if class == PassType::Overlay {
             shadow += offset.into();
@@ -318,14 +384,15 @@ where
             draw.rounded_frame_2col(shadow, inner, Rgba::BLACK, Rgba::TRANSPARENT);
 
             draw.rounded_frame(outer, inner, BG_SHRINK_FACTOR, self.cols.frame);
-            let inner = outer.shrink(self.w.dims.frame as f32 * BG_SHRINK_FACTOR);
+            let px = self.w.dims.pixel_align();
+            let inner = px.shrink(outer, self.w.dims.frame as f32 * BG_SHRINK_FACTOR);
             draw.rect(inner, self.cols.background);
         }
 
         let mut handle = DrawHandle {
             w: self.w,
             draw,
-            cols: self.cols,
+            cols: Cow::Borrowed(&*self.cols),
         };
         f(&mut handle);
     }
@@ -336,6 +403,17 @@ where
 
     fn outer_frame(&mut self, rect: Rect) {
         let outer = Quad::from(rect);
+        let key = CacheKey::ButtonFrame {
+            size: rect.size.into(),
+            frame: self.w.dims.frame.cast(),
+            col_frame: self.cols.frame.into(),
+            col_top: Rgba8Srgb::TRANSPARENT,
+            col_bottom: Rgba8Srgb::TRANSPARENT,
+        };
+        if self.draw_cached(outer, key) {
+            return;
+        }
+
         let inner = outer.shrink(self.w.dims.frame as f32);
         self.draw
             .rounded_frame(outer, inner, BG_SHRINK_FACTOR, self.cols.frame);
@@ -347,9 +425,13 @@ where
     }
 
     fn nav_frame(&mut self, rect: Rect, state: InputState) {
-        if let Some(col) = self.cols.nav_region(state) {
-            let outer = Quad::from(rect);
-            let inner = outer.shrink(self.w.dims.inner_margin as f32);
+        if let Some(col) = self.cols.nav_region(self.w.focus_state(state)) {
+            let width = self.w.focus_ring_width();
+            let rect = Quad::from(rect);
+            let (outer, inner) = match self.w.focus_ring.placement {
+                FocusPlacement::Inside => (rect, rect.shrink(width)),
+                FocusPlacement::Outside => (rect.grow(width), rect),
+            };
             self.draw.rounded_frame(outer, inner, 0.0, col);
         }
     }
@@ -357,9 +439,12 @@ where
     fn selection_box(&mut self, rect: Rect) {
         let inner = Quad::from(rect);
         let outer = inner.grow(self.w.dims.inner_margin.into());
-        // TODO: this should use its own colour and a stippled pattern
+        // TODO: this should use its own colour
         let col = self.cols.text_sel_bg;
-        self.draw.frame(outer, inner, col);
+        let dash = (2.0 * (inner.a - outer.a).max_abs_comp()).max(2.0);
+        let offset = self.w.anim.march(self.draw.draw, 2.0 * dash);
+        let style = StrokeStyle::dashed(dash, dash).with_offset(offset);
+        self.draw.frame_styled(outer, inner, style, col);
     }
 
     fn text(&mut self, pos: Coord, text: &TextDisplay, _: TextClass, state: InputState) {
@@ -409,6 +494,17 @@ where
         pos: Coord,
         text: &TextDisplay,
         range: Range<usize>,
+        class: TextClass,
+        state: InputState,
+    ) {
+        self.text_selected_ranges(pos, text, &[range], class, state);
+    }
+
+    fn text_selected_ranges(
+        &mut self,
+        pos: Coord,
+        text: &TextDisplay,
+        ranges: &[Range<usize>],
         _: TextClass,
         state: InputState,
     ) {
@@ -421,63 +517,127 @@ where
         let sel_col = self.cols.text_over(self.cols.text_sel_bg);
 
         // Draw background:
-        for (p1, p2) in &text.highlight_lines(range.clone()) {
-            let p1 = Vec2::from(*p1);
-            let p2 = Vec2::from(*p2);
-            let quad = Quad::from_coords(pos + p1, pos + p2);
-            self.draw.rect(quad, self.cols.text_sel_bg);
+        for range in ranges {
+            for (p1, p2) in &text.highlight_lines(range.clone()) {
+                let p1 = Vec2::from(*p1);
+                let p2 = Vec2::from(*p2);
+                let quad = Quad::from_coords(pos + p1, pos + p2);
+                self.draw.rect(quad, self.cols.text_sel_bg);
+            }
         }
 
-        let effects = [
-            Effect {
-                start: 0,
-                flags: Default::default(),
-                aux: col,
-            },
-            Effect {
+        let mut effects = Vec::with_capacity(1 + 2 * ranges.len());
+        effects.push(Effect {
+            start: 0,
+            flags: Default::default(),
+            aux: col,
+        });
+        for range in ranges {
+            effects.push(Effect {
                 start: range.start.cast(),
                 flags: Default::default(),
                 aux: sel_col,
-            },
-            Effect {
+            });
+            effects.push(Effect {
                 start: range.end.cast(),
                 flags: Default::default(),
                 aux: col,
-            },
-        ];
+            });
+        }
         self.draw.text_effects(pos, text, &effects);
     }
 
-    fn text_cursor(&mut self, wid: u64, pos: Coord, text: &TextDisplay, _: TextClass, byte: usize) {
-        if !self.w.anim.text_cursor(self.draw.draw, wid, byte) {
+    fn text_squiggle(
+        &mut self,
+        pos: Coord,
+        text: &TextDisplay,
+        ranges: &[Range<usize>],
+        _: TextClass,
+        state: InputState,
+    ) {
+        if state.disabled() {
             return;
         }
 
-        let width = self.w.dims.font_marker_width;
         let pos = Vec2::from(pos);
+        let width = self.w.dims.font_marker_width;
+        let period = 4.0 * width;
+        for range in ranges {
+            for (p1, p2) in &text.highlight_lines(range.clone()) {
+                let (x1, x2) = (pos.0 + p1.0, pos.0 + p2.0);
+                let y = pos.1 + p2.1 - width;
+                let mut x = x1;
+                let mut up = false;
+                while x < x2 {
+                    let x_next = (x + 0.5 * period).min(x2);
+                    let (y1, y2) = match up {
+                        true => (y, y - width),
+                        false => (y - width, y),
+                    };
+                    let (a, b) = (Vec2(x, y1), Vec2(x_next, y2));
+                    self.draw.rounded_line(a, b, 0.5 * width, SQUIGGLE_COL);
+                    x = x_next;
+                    up = !up;
+                }
+            }
+        }
+    }
 
-        let mut col = self.cols.nav_focus;
-        for cursor in text.text_glyph_pos(byte).rev() {
-            let mut p1 = pos + Vec2::from(cursor.pos);
-            let mut p2 = p1;
-            p1.1 -= cursor.ascent;
-            p2.1 -= cursor.descent;
-            p2.0 += width;
-            let quad = Quad::from_coords(p1, p2);
-            self.draw.rect(quad, col);
+    fn text_cursor(
+        &mut self,
+        wid: u64,
+        pos: Coord,
+        text: &TextDisplay,
+        class: TextClass,
+        byte: usize,
+    ) {
+        self.text_cursors(wid, pos, text, class, &[byte]);
+    }
 
-            if cursor.embedding_level() > 0 {
-                // Add a hat to indicate directionality.
-                let height = width;
-                let quad = if cursor.is_ltr() {
-                    Quad::from_coords(Vec2(p2.0, p1.1), Vec2(p2.0 + width, p1.1 + height))
-                } else {
-                    Quad::from_coords(Vec2(p1.0 - width, p1.1), Vec2(p1.0, p1.1 + height))
-                };
+    fn text_cursors(
+        &mut self,
+        wid: u64,
+        pos: Coord,
+        text: &TextDisplay,
+        _: TextClass,
+        bytes: &[usize],
+    ) {
+        // All markers blink together, keyed on the primary marker:
+        let primary = match bytes.first() {
+            Some(byte) => *byte,
+            None => return,
+        };
+        if !self.w.anim.text_cursor(self.draw.draw, wid, primary) {
+            return;
+        }
+
+        let width = self.w.dims.font_marker_width;
+        let pos = Vec2::from(pos);
+
+        for byte in bytes {
+            let mut col = self.cols.nav_focus;
+            for cursor in text.text_glyph_pos(*byte).rev() {
+                let mut p1 = pos + Vec2::from(cursor.pos);
+                let mut p2 = p1;
+                p1.1 -= cursor.ascent;
+                p2.1 -= cursor.descent;
+                p2.0 += width;
+                let quad = Quad::from_coords(p1, p2);
                 self.draw.rect(quad, col);
+
+                if cursor.embedding_level() > 0 {
+                    // Add a hat to indicate directionality.
+                    let height = width;
+                    let quad = if cursor.is_ltr() {
+                        Quad::from_coords(Vec2(p2.0, p1.1), Vec2(p2.0 + width, p1.1 + height))
+                    } else {
+                        Quad::from_coords(Vec2(p1.0 - width, p1.1), Vec2(p1.0, p1.1 + height))
+                    };
+                    self.draw.rect(quad, col);
+                }
+                // hack to make secondary marker grey:
+                col = col.average();
             }
-            // hack to make secondary marker grey:
-            col = col.average();
         }
     }
 
@@ -497,7 +657,10 @@ where
             col.map(|c| c.into()).unwrap_or(self.cols.background)
         };
         let col_bg = ColorsLinear::adjust_for_state(col_bg, state);
-        let col_frame = self.cols.nav_region(state).unwrap_or(self.cols.frame);
+        let col_frame = self
+            .cols
+            .nav_region(self.w.focus_state(state))
+            .unwrap_or(self.cols.frame);
         self.button_frame(outer, col_frame, col_bg, state);
     }
 
@@ -506,14 +669,30 @@ where
 
         state.remove(InputState::DEPRESS);
         let col_bg = self.cols.edit_bg(state);
-        if col_bg != self.cols.background {
-            let inner = outer.shrink(self.w.dims.button_frame as f32 * BG_SHRINK_FACTOR);
-            self.draw.rect(inner, col_bg);
-        }
+        let draw_bg = col_bg != self.cols.background;
 
-        let inner = outer.shrink(self.w.dims.button_frame as f32);
-        self.draw
-            .rounded_frame(outer, inner, BG_SHRINK_FACTOR, self.cols.frame);
+        let col_bg8 = match draw_bg {
+            true => col_bg.into(),
+            false => Rgba8Srgb::TRANSPARENT,
+        };
+        let key = CacheKey::ButtonFrame {
+            size: rect.size.into(),
+            frame: self.w.dims.button_frame.cast(),
+            col_frame: self.cols.frame.into(),
+            col_top: col_bg8,
+            col_bottom: col_bg8,
+        };
+        if !self.draw_cached(outer, key) {
+            if draw_bg {
+                let px = self.w.dims.pixel_align();
+                let inner = px.shrink(outer, self.w.dims.button_frame as f32 * BG_SHRINK_FACTOR);
+                self.draw.rect(inner, col_bg);
+            }
+
+            let inner = outer.shrink(self.w.dims.button_frame as f32);
+            self.draw
+                .rounded_frame(outer, inner, BG_SHRINK_FACTOR, self.cols.frame);
+        }
 
         if !state.disabled() && (state.nav_focus() || state.hover()) {
             let r = 0.5 * self.w.dims.button_frame as f32;
@@ -539,17 +718,25 @@ where
         }
     }
 
-    fn checkbox(&mut self, wid: u64, rect: Rect, checked: bool, state: InputState) {
-        let anim_fade = self.w.anim.fade_bool_1m(self.draw.draw, wid, checked);
+    fn checkbox(&mut self, wid: u64, rect: Rect, checked: CheckState, state: InputState) {
+        let marked = checked != CheckState::Unchecked;
+        let anim_fade = self.w.anim.fade_bool_1m(self.draw.draw, wid, marked);
 
         let outer = Quad::from(rect);
 
-        let col_frame = self.cols.nav_region(state).unwrap_or(self.cols.frame);
+        let col_frame = self
+            .cols
+            .nav_region(self.w.focus_state(state))
+            .unwrap_or(self.cols.frame);
         let inner = self.button_frame(outer, col_frame, self.cols.edit_bg(state), state);
 
         if anim_fade < 1.0 {
             let inner = inner.shrink((2 * self.w.dims.inner_margin) as f32);
-            let v = inner.size() * (anim_fade / 2.0);
+            let mut v = inner.size() * (anim_fade / 2.0);
+            if checked.is_indeterminate() {
+                // Mixed mark: a horizontal bar
+                v.1 = v.1.max(inner.size().1 / 3.0);
+            }
             let inner = Quad::from_coords(inner.a + v, inner.b - v);
             let col = self.cols.check_mark_state(state);
             self.draw.rect(inner, col);
@@ -560,7 +747,10 @@ where
         let anim_fade = self.w.anim.fade_bool_1m(self.draw.draw, wid, checked);
 
         let outer = Quad::from(rect);
-        let col = self.cols.nav_region(state).unwrap_or(self.cols.frame);
+        let col = self
+            .cols
+            .nav_region(self.w.focus_state(state))
+            .unwrap_or(self.cols.frame);
 
         if !(state.disabled() || state.depress()) {
             let (mut a, mut b) = (self.w.dims.shadow_a, self.w.dims.shadow_b);
@@ -577,11 +767,20 @@ where
             self.draw.circle_2col(shadow_outer, col1, col2);
         }
 
-        self.draw.circle(outer, 0.0, self.cols.edit_bg(state));
+        let col_bg = self.cols.edit_bg(state);
+        let key = CacheKey::RadioFrame {
+            size: rect.size.into(),
+            frame: self.w.dims.button_frame.cast(),
+            col_frame: col.into(),
+            col_bg: col_bg.into(),
+        };
+        if !self.draw_cached(outer, key) {
+            self.draw.circle(outer, 0.0, col_bg);
 
-        const F: f32 = 2.0 * (1.0 - BG_SHRINK_FACTOR); // match checkbox frame
-        let r = 1.0 - F * self.w.dims.button_frame as f32 / rect.size.0 as f32;
-        self.draw.circle(outer, r, col);
+            const F: f32 = 2.0 * (1.0 - BG_SHRINK_FACTOR); // match checkbox frame
+            let r = 1.0 - F * self.w.dims.button_frame as f32 / rect.size.0 as f32;
+            self.draw.circle(outer, r, col);
+        }
 
         if anim_fade < 1.0 {
             let r = self.w.dims.button_frame + 2 * self.w.dims.inner_margin as i32;
@@ -593,6 +792,48 @@ where
         }
     }
 
+    fn switch(&mut self, wid: u64, rect: Rect, on: bool, state: InputState) {
+        let anim_fade = self.w.anim.fade_bool(self.draw.draw, wid, on);
+
+        // track: frame colour, overlaid with accent colour as switch turns on
+        let outer = Quad::from(rect);
+        let inner = outer.shrink(outer.size().min_comp() / 2.0);
+        let col = ColorsLinear::adjust_for_state(self.cols.frame, state);
+        self.draw.rounded_frame(outer, inner, 0.0, col);
+        if anim_fade > 0.0 {
+            let mut col = self.cols.accent_state(state);
+            col.a *= anim_fade;
+            self.draw.rounded_frame(outer, inner, 0.0, col);
+        }
+
+        // thumb: a circle sliding from start to end of track
+        let d = outer.size().1;
+        let x = outer.a.0 + (outer.size().0 - d) * anim_fade;
+        let thumb = Quad::from_coords(Vec2(x, outer.a.1), Vec2(x + d, outer.b.1));
+        let thumb = thumb.shrink(self.w.dims.button_frame as f32);
+        let col = ColorsLinear::adjust_for_state(self.cols.background, state);
+
+        if !(state.disabled() || state.depress()) {
+            let (mut a, mut b) = (self.w.dims.shadow_a, self.w.dims.shadow_b);
+            let mut mult = 0.6;
+            if state.hover() {
+                mult *= SHADOW_HOVER;
+            }
+            a = a * mult;
+            b = b * mult;
+            let shadow_outer = Quad::from_coords(a + thumb.a, b + thumb.b);
+            let col1 = if self.cols.is_dark { col } else { Rgba::BLACK };
+            let mut col2 = col1;
+            col2.a = 0.0;
+            self.draw.circle_2col(shadow_outer, col1, col2);
+        }
+
+        self.draw.circle(thumb, 0.0, col);
+        if let Some(col) = self.cols.nav_region(self.w.focus_state(state)) {
+            self.draw.circle(thumb, 12.0 / 16.0, col);
+        }
+    }
+
     fn scrollbar(&mut self, rect: Rect, h_rect: Rect, _dir: Direction, state: InputState) {
         // track
         let outer = Quad::from(rect);
@@ -678,7 +919,10 @@ where
         }
 
         self.draw.circle(outer, 0.0, col);
-        let col = self.cols.nav_region(state).unwrap_or(self.cols.frame);
+        let col = self
+            .cols
+            .nav_region(self.w.focus_state(state))
+            .unwrap_or(self.cols.frame);
         self.draw.circle(outer, 14.0 / 16.0, col);
     }
 
@@ -692,12 +936,54 @@ where
         } else {
             outer.b.1 = outer.a.1 + value * (outer.b.1 - outer.a.1);
         }
+        let r = outer.size().min_comp() / 2.0;
+        let (col1, col2) = (self.cols.accent_soft, self.cols.accent);
+        // Centres of the round end caps
+        let cap = Vec2(r, r);
+        let start = outer.a + cap;
+        let end = outer.b - cap;
+        let mut section = outer;
+        if dir.is_horizontal() {
+            section.a.0 = start.0;
+            section.b.0 = end.0;
+        } else {
+            section.a.1 = start.1;
+            section.b.1 = end.1;
+        }
+        if section.size().min_comp() <= 0.0 {
+            let inner = outer.shrink(r);
+            self.draw.rounded_frame(outer, inner, 0.0, col1);
+            return;
+        }
+
+        // Fill the straight section with a gradient along the bar, then
+        // draw end caps in the gradient's end colours
+        let grad = Gradient::Linear {
+            start,
+            end,
+            col1,
+            col2,
+        };
+        self.draw.rect_grad(section, grad);
+        self.draw
+            .circle(Quad::from_coords(outer.a, start + cap), 0.0, col1);
+        self.draw
+            .circle(Quad::from_coords(end - cap, outer.b), 0.0, col2);
+    }
+
+    fn badge(&mut self, rect: Rect, state: InputState) {
+        let outer = Quad::from(rect);
         let inner = outer.shrink(outer.size().min_comp() / 2.0);
-        self.draw.rounded_frame(outer, inner, 0.0, self.cols.accent);
+        let col = self.cols.accent_state(state);
+        self.draw.rounded_frame(outer, inner, 0.0, col);
     }
 
     fn image(&mut self, id: ImageId, rect: Rect) {
         let rect = Quad::from(rect);
         self.draw.image(id, rect);
     }
+
+    fn symbol_color(&self, class: SymbolClass, state: InputState) -> Rgba {
+        self.cols.symbol_state(class, state)
+    }
 }
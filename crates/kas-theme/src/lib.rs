@@ -7,7 +7,7 @@
 //!
 //! This crate provides the [`Theme`] trait, [`MultiTheme`] adapter, color
 //! schemes, some supporting items, and the themes [`FlatTheme`] and
-//! [`ShadedTheme`].
+//! [`ShadedTheme`]. A [`ThemeOverlay`] customises parts of another theme.
 //!
 //! Custom themes may be built over this crate, optionally including custom draw
 //! routines (e.g. [`DrawShaded`]), provided that the shell implements support.
@@ -25,6 +25,8 @@ mod draw_shaded;
 mod flat_theme;
 #[cfg(feature = "stack_dst")]
 mod multi;
+mod overlay;
+mod raster_cache;
 mod shaded_theme;
 #[cfg(feature = "stack_dst")]
 mod theme_dst;
@@ -33,11 +35,12 @@ mod traits;
 pub mod dim;
 
 pub use colors::{Colors, ColorsLinear, ColorsSrgb};
-pub use config::{Config, RasterConfig};
+pub use config::{Config, FocusPlacement, FocusRingConfig, RasterConfig, EMOJI_FAMILIES};
 pub use draw_shaded::{DrawShaded, DrawShadedImpl};
 pub use flat_theme::FlatTheme;
 #[cfg(feature = "stack_dst")]
 pub use multi::{MultiTheme, MultiThemeBuilder};
+pub use overlay::{Overlay, OverlayHandle, OverlayWindow, ThemeOverlay};
 pub use shaded_theme::ShadedTheme;
 #[cfg(feature = "stack_dst")]
 pub use theme_dst::{MaybeBoxed, ThemeDst};
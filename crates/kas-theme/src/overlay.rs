@@ -0,0 +1,660 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Theme overlay: customisation of an existing theme
+
+use std::any::Any;
+use std::ops::Range;
+
+use crate::{Theme, Window};
+use kas::dir::Direction;
+use kas::draw::color::{Rgb, Rgba};
+use kas::draw::{Affine, Draw, DrawIface, DrawShared, DrawSharedImpl, ImageId};
+use kas::draw::{PassType, SharedState};
+use kas::geom::{Coord, Offset, Rect, Size};
+use kas::layout::{AxisInfo, FrameRules, Margins, SizeRules};
+use kas::text::{AccelString, Text, TextApi, TextDisplay};
+use kas::theme::{CheckState, DrawHandle, IconSize, InputState, SizeHandle};
+use kas::theme::{SymbolClass, TextClass, ThemeControl};
+use kas::TkAction;
+
+/// Customisations applied over a theme by [`ThemeOverlay`]
+///
+/// Each method corresponds to a method of [`SizeHandle`] or (with prefix
+/// `draw_`) of [`DrawHandle`], and by default forwards to the `inner` theme's
+/// handle. Implement only those methods to be customised; others are
+/// unaffected. For example, to use larger checkboxes:
+/// ```
+/// use kas::geom::Size;
+/// use kas::theme::SizeHandle;
+/// use kas_theme::{FlatTheme, Overlay, ThemeOverlay};
+///
+/// #[derive(Clone, Debug)]
+/// struct BigCheckBoxes;
+/// impl Overlay for BigCheckBoxes {
+///     fn checkbox(&self, inner: &dyn SizeHandle) -> Size {
+///         inner.checkbox() * 2
+///     }
+/// }
+///
+/// let theme = ThemeOverlay::new(FlatTheme::new(), BigCheckBoxes);
+/// ```
+///
+/// Draw methods may draw the element differently (for example via
+/// [`DrawHandle::draw_device`]) or adjust parameters before forwarding.
+/// Note that size queries made while drawing are answered by the inner
+/// theme without customisation.
+pub trait Overlay: Clone + 'static {
+    /// Size of a frame around child widget(s)
+    fn frame(&self, inner: &dyn SizeHandle, vert: bool) -> FrameRules {
+        inner.frame(vert)
+    }
+
+    /// Frame/margin around a menu entry
+    fn menu_frame(&self, inner: &dyn SizeHandle, vert: bool) -> FrameRules {
+        inner.menu_frame(vert)
+    }
+
+    /// Size of a separator frame between items
+    fn separator(&self, inner: &dyn SizeHandle) -> Size {
+        inner.separator()
+    }
+
+    /// Size of a navigation highlight margin around a child widget
+    fn nav_frame(&self, inner: &dyn SizeHandle, vert: bool) -> FrameRules {
+        inner.nav_frame(vert)
+    }
+
+    /// The margin around content within a widget
+    fn inner_margin(&self, inner: &dyn SizeHandle) -> Size {
+        inner.inner_margin()
+    }
+
+    /// The margin between UI elements, where desired
+    fn outer_margins(&self, inner: &dyn SizeHandle) -> Margins {
+        inner.outer_margins()
+    }
+
+    /// The margin around frames and separators
+    fn frame_margins(&self, inner: &dyn SizeHandle) -> Margins {
+        inner.frame_margins()
+    }
+
+    /// The margin around text elements
+    fn text_margins(&self, inner: &dyn SizeHandle) -> Margins {
+        inner.text_margins()
+    }
+
+    /// Size of the sides of a button
+    fn button_surround(&self, inner: &dyn SizeHandle, vert: bool) -> FrameRules {
+        inner.button_surround(vert)
+    }
+
+    /// Size of the frame around an edit box, including margin
+    fn edit_surround(&self, inner: &dyn SizeHandle, vert: bool) -> FrameRules {
+        inner.edit_surround(vert)
+    }
+
+    /// Size of the element drawn by [`DrawHandle::checkbox`]
+    fn checkbox(&self, inner: &dyn SizeHandle) -> Size {
+        inner.checkbox()
+    }
+
+    /// Size of the element drawn by [`DrawHandle::radiobox`]
+    fn radiobox(&self, inner: &dyn SizeHandle) -> Size {
+        inner.radiobox()
+    }
+
+    /// Size of the element drawn by [`DrawHandle::switch`]
+    fn switch(&self, inner: &dyn SizeHandle) -> Size {
+        inner.switch()
+    }
+
+    /// Dimensions for a scrollbar (see [`SizeHandle::scrollbar`])
+    fn scrollbar(&self, inner: &dyn SizeHandle) -> (Size, i32) {
+        inner.scrollbar()
+    }
+
+    /// Dimensions for a slider (see [`SizeHandle::slider`])
+    fn slider(&self, inner: &dyn SizeHandle) -> (Size, i32) {
+        inner.slider()
+    }
+
+    /// Dimensions for a progress bar
+    fn progress_bar(&self, inner: &dyn SizeHandle) -> Size {
+        inner.progress_bar()
+    }
+
+    /// Side length of an icon of the given `size`
+    fn icon(&self, inner: &dyn SizeHandle, size: IconSize) -> i32 {
+        inner.icon(size)
+    }
+
+    /// Draw a frame inside the given `rect`
+    fn draw_outer_frame(&self, h: &mut dyn DrawHandle, rect: Rect) {
+        h.outer_frame(rect);
+    }
+
+    /// Draw a separator in the given `rect`
+    fn draw_separator(&self, h: &mut dyn DrawHandle, rect: Rect) {
+        h.separator(rect);
+    }
+
+    /// Draw a navigation highlight frame in the given `rect`
+    fn draw_nav_frame(&self, h: &mut dyn DrawHandle, rect: Rect, state: InputState) {
+        h.nav_frame(rect, state);
+    }
+
+    /// Draw a selection box
+    fn draw_selection_box(&self, h: &mut dyn DrawHandle, rect: Rect) {
+        h.selection_box(rect);
+    }
+
+    /// Draw the background of a menu entry
+    fn draw_menu_entry(&self, h: &mut dyn DrawHandle, rect: Rect, state: InputState) {
+        h.menu_entry(rect, state);
+    }
+
+    /// Draw button sides, background and margin-area highlight
+    fn draw_button(&self, h: &mut dyn DrawHandle, rect: Rect, col: Option<Rgb>, state: InputState) {
+        h.button(rect, col, state);
+    }
+
+    /// Draw edit box sides, background and margin-area highlight
+    fn draw_edit_box(&self, h: &mut dyn DrawHandle, rect: Rect, state: InputState) {
+        h.edit_box(rect, state);
+    }
+
+    /// Draw UI element: checkbox
+    fn draw_checkbox(
+        &self,
+        h: &mut dyn DrawHandle,
+        wid: u64,
+        rect: Rect,
+        checked: CheckState,
+        state: InputState,
+    ) {
+        h.checkbox(wid, rect, checked, state);
+    }
+
+    /// Draw UI element: radiobox
+    fn draw_radiobox(
+        &self,
+        h: &mut dyn DrawHandle,
+        wid: u64,
+        rect: Rect,
+        checked: bool,
+        state: InputState,
+    ) {
+        h.radiobox(wid, rect, checked, state);
+    }
+
+    /// Draw UI element: switch
+    fn draw_switch(
+        &self,
+        h: &mut dyn DrawHandle,
+        wid: u64,
+        rect: Rect,
+        on: bool,
+        state: InputState,
+    ) {
+        h.switch(wid, rect, on, state);
+    }
+
+    /// Draw UI element: scrollbar
+    fn draw_scrollbar(
+        &self,
+        h: &mut dyn DrawHandle,
+        rect: Rect,
+        h_rect: Rect,
+        dir: Direction,
+        state: InputState,
+    ) {
+        h.scrollbar(rect, h_rect, dir, state);
+    }
+
+    /// Draw UI element: slider
+    fn draw_slider(
+        &self,
+        h: &mut dyn DrawHandle,
+        rect: Rect,
+        h_rect: Rect,
+        dir: Direction,
+        state: InputState,
+    ) {
+        h.slider(rect, h_rect, dir, state);
+    }
+
+    /// Draw UI element: progress bar
+    fn draw_progress_bar(
+        &self,
+        h: &mut dyn DrawHandle,
+        rect: Rect,
+        dir: Direction,
+        state: InputState,
+        value: f32,
+    ) {
+        h.progress_bar(rect, dir, state, value);
+    }
+
+    /// Draw UI element: badge
+    fn draw_badge(&self, h: &mut dyn DrawHandle, rect: Rect, state: InputState) {
+        h.badge(rect, state);
+    }
+
+    /// Get the colour of a symbolic icon
+    fn symbol_color(&self, h: &dyn DrawHandle, class: SymbolClass, state: InputState) -> Rgba {
+        h.symbol_color(class, state)
+    }
+
+    /// Background colour
+    fn clear_color(&self, inner: Rgba) -> Rgba {
+        inner
+    }
+}
+
+/// A theme with customisations
+///
+/// This wraps an existing theme (for example [`crate::FlatTheme`]),
+/// customising selected sizes and draw routines via an [`Overlay`] while
+/// all other behaviour (including configuration and colour schemes) is that
+/// of the inner theme. Overlays may be nested.
+#[derive(Clone, Debug)]
+pub struct ThemeOverlay<T, O> {
+    inner: T,
+    overlay: O,
+}
+
+impl<T, O: Overlay> ThemeOverlay<T, O> {
+    /// Construct, wrapping the `inner` theme
+    pub fn new(inner: T, overlay: O) -> Self {
+        ThemeOverlay { inner, overlay }
+    }
+
+    /// Access the inner theme
+    pub fn inner(&self) -> &T {
+        &self.inner
+    }
+
+    /// Access the inner theme mutably
+    pub fn inner_mut(&mut self) -> &mut T {
+        &mut self.inner
+    }
+
+    /// Access the overlay
+    pub fn overlay(&self) -> &O {
+        &self.overlay
+    }
+}
+
+/// Per-window storage for [`ThemeOverlay`]
+pub struct OverlayWindow<W, O> {
+    inner: W,
+    overlay: O,
+}
+
+/// Draw handle of [`ThemeOverlay`]
+pub struct OverlayHandle<'a, O, H> {
+    overlay: &'a O,
+    inner: H,
+}
+
+impl<DS: DrawSharedImpl, T: Theme<DS>, O: Overlay> Theme<DS> for ThemeOverlay<T, O> {
+    type Config = <T as Theme<DS>>::Config;
+    type Window = OverlayWindow<<T as Theme<DS>>::Window, O>;
+
+    #[cfg(not(feature = "gat"))]
+    type DrawHandle = OverlayHandle<'static, O, <T as Theme<DS>>::DrawHandle>;
+    #[cfg(feature = "gat")]
+    type DrawHandle<'a>
+    where
+        T: 'a,
+    = OverlayHandle<'a, O, <T as Theme<DS>>::DrawHandle<'a>>;
+
+    fn config(&self) -> std::borrow::Cow<Self::Config> {
+        self.inner.config()
+    }
+
+    fn apply_config(&mut self, config: &Self::Config) -> TkAction {
+        self.inner.apply_config(config)
+    }
+
+    fn init(&mut self, shared: &mut SharedState<DS>) {
+        self.inner.init(shared);
+    }
+
+    fn new_window(&self, dpi_factor: f32) -> Self::Window {
+        OverlayWindow {
+            inner: self.inner.new_window(dpi_factor),
+            overlay: self.overlay.clone(),
+        }
+    }
+
+    fn update_window(&self, window: &mut Self::Window, dpi_factor: f32) {
+        self.inner.update_window(&mut window.inner, dpi_factor);
+    }
+
+    #[cfg(not(feature = "gat"))]
+    unsafe fn draw_handle(
+        &self,
+        draw: DrawIface<DS>,
+        window: &mut Self::Window,
+    ) -> Self::DrawHandle {
+        unsafe fn extend_lifetime<'b, T: ?Sized>(r: &'b T) -> &'static T {
+            std::mem::transmute::<&'b T, &'static T>(r)
+        }
+        OverlayHandle {
+            overlay: extend_lifetime(&self.overlay),
+            inner: self.inner.draw_handle(draw, &mut window.inner),
+        }
+    }
+    #[cfg(feature = "gat")]
+    fn draw_handle<'a>(
+        &'a self,
+        draw: DrawIface<'a, DS>,
+        window: &'a mut Self::Window,
+    ) -> Self::DrawHandle<'a> {
+        OverlayHandle {
+            overlay: &self.overlay,
+            inner: self.inner.draw_handle(draw, &mut window.inner),
+        }
+    }
+
+    fn clear_color(&self) -> Rgba {
+        self.overlay.clear_color(self.inner.clear_color())
+    }
+}
+
+impl<T: ThemeControl, O> ThemeControl for ThemeOverlay<T, O> {
+    fn set_font_size(&mut self, pt_size: f32) -> TkAction {
+        self.inner.set_font_size(pt_size)
+    }
+    fn set_scheme(&mut self, scheme: &str) -> TkAction {
+        self.inner.set_scheme(scheme)
+    }
+    fn list_schemes(&self) -> Vec<&str> {
+        self.inner.list_schemes()
+    }
+    fn set_theme(&mut self, theme: &str) -> TkAction {
+        self.inner.set_theme(theme)
+    }
+}
+
+impl<W: Window, O: Overlay> Window for OverlayWindow<W, O> {
+    fn size_handle(&self) -> &dyn SizeHandle {
+        self
+    }
+
+    fn set_text_zoom(&mut self, zoom: f32) {
+        self.inner.set_text_zoom(zoom);
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+impl<W: Window, O: Overlay> SizeHandle for OverlayWindow<W, O> {
+    fn scale_factor(&self) -> f32 {
+        self.inner.size_handle().scale_factor()
+    }
+    fn pixels_from_points(&self, pt: f32) -> f32 {
+        self.inner.size_handle().pixels_from_points(pt)
+    }
+    fn pixels_from_em(&self, em: f32) -> f32 {
+        self.inner.size_handle().pixels_from_em(em)
+    }
+
+    fn frame(&self, vert: bool) -> FrameRules {
+        self.overlay.frame(self.inner.size_handle(), vert)
+    }
+    fn menu_frame(&self, vert: bool) -> FrameRules {
+        self.overlay.menu_frame(self.inner.size_handle(), vert)
+    }
+    fn separator(&self) -> Size {
+        self.overlay.separator(self.inner.size_handle())
+    }
+    fn nav_frame(&self, vert: bool) -> FrameRules {
+        self.overlay.nav_frame(self.inner.size_handle(), vert)
+    }
+    fn inner_margin(&self) -> Size {
+        self.overlay.inner_margin(self.inner.size_handle())
+    }
+    fn outer_margins(&self) -> Margins {
+        self.overlay.outer_margins(self.inner.size_handle())
+    }
+    fn frame_margins(&self) -> Margins {
+        self.overlay.frame_margins(self.inner.size_handle())
+    }
+    fn text_margins(&self) -> Margins {
+        self.overlay.text_margins(self.inner.size_handle())
+    }
+
+    fn line_height(&self, class: TextClass) -> i32 {
+        self.inner.size_handle().line_height(class)
+    }
+    fn text_bound(&self, text: &mut dyn TextApi, class: TextClass, axis: AxisInfo) -> SizeRules {
+        self.inner.size_handle().text_bound(text, class, axis)
+    }
+    fn text_cursor_width(&self) -> f32 {
+        self.inner.size_handle().text_cursor_width()
+    }
+
+    fn button_surround(&self, vert: bool) -> FrameRules {
+        self.overlay.button_surround(self.inner.size_handle(), vert)
+    }
+    fn edit_surround(&self, vert: bool) -> FrameRules {
+        self.overlay.edit_surround(self.inner.size_handle(), vert)
+    }
+
+    fn checkbox(&self) -> Size {
+        self.overlay.checkbox(self.inner.size_handle())
+    }
+    fn radiobox(&self) -> Size {
+        self.overlay.radiobox(self.inner.size_handle())
+    }
+    fn switch(&self) -> Size {
+        self.overlay.switch(self.inner.size_handle())
+    }
+    fn scrollbar(&self) -> (Size, i32) {
+        self.overlay.scrollbar(self.inner.size_handle())
+    }
+    fn slider(&self) -> (Size, i32) {
+        self.overlay.slider(self.inner.size_handle())
+    }
+    fn progress_bar(&self) -> Size {
+        self.overlay.progress_bar(self.inner.size_handle())
+    }
+    fn icon(&self, size: IconSize) -> i32 {
+        self.overlay.icon(self.inner.size_handle(), size)
+    }
+}
+
+impl<'a, O: Overlay, H: DrawHandle> DrawHandle for OverlayHandle<'a, O, H> {
+    fn size_and_draw_shared(&mut self) -> (&dyn SizeHandle, &mut dyn DrawShared) {
+        self.inner.size_and_draw_shared()
+    }
+
+    fn draw_device(&mut self) -> &mut dyn Draw {
+        self.inner.draw_device()
+    }
+
+    fn new_pass(
+        &mut self,
+        rect: Rect,
+        offset: Offset,
+        transform: Affine,
+        class: PassType,
+        f: &mut dyn FnMut(&mut dyn DrawHandle),
+    ) {
+        let overlay = self.overlay;
+        self.inner.new_pass(
+            rect,
+            offset,
+            transform,
+            class,
+            &mut |inner: &mut dyn DrawHandle| {
+                let mut handle = OverlayHandle { overlay, inner };
+                f(&mut handle);
+            },
+        );
+    }
+
+    fn get_clip_rect(&self) -> Rect {
+        self.inner.get_clip_rect()
+    }
+
+    fn outer_frame(&mut self, rect: Rect) {
+        self.overlay.draw_outer_frame(&mut self.inner, rect);
+    }
+
+    fn separator(&mut self, rect: Rect) {
+        self.overlay.draw_separator(&mut self.inner, rect);
+    }
+
+    fn nav_frame(&mut self, rect: Rect, state: InputState) {
+        self.overlay.draw_nav_frame(&mut self.inner, rect, state);
+    }
+
+    fn selection_box(&mut self, rect: Rect) {
+        self.overlay.draw_selection_box(&mut self.inner, rect);
+    }
+
+    fn text(&mut self, pos: Coord, text: &TextDisplay, class: TextClass, state: InputState) {
+        self.inner.text(pos, text, class, state);
+    }
+
+    fn text_effects(
+        &mut self,
+        pos: Coord,
+        text: &dyn TextApi,
+        class: TextClass,
+        state: InputState,
+    ) {
+        self.inner.text_effects(pos, text, class, state);
+    }
+
+    fn text_accel(
+        &mut self,
+        pos: Coord,
+        text: &Text<AccelString>,
+        accel: bool,
+        class: TextClass,
+        state: InputState,
+    ) {
+        self.inner.text_accel(pos, text, accel, class, state);
+    }
+
+    fn text_selected_range(
+        &mut self,
+        pos: Coord,
+        text: &TextDisplay,
+        range: Range<usize>,
+        class: TextClass,
+        state: InputState,
+    ) {
+        self.inner
+            .text_selected_range(pos, text, range, class, state);
+    }
+
+    fn text_selected_ranges(
+        &mut self,
+        pos: Coord,
+        text: &TextDisplay,
+        ranges: &[Range<usize>],
+        class: TextClass,
+        state: InputState,
+    ) {
+        self.inner
+            .text_selected_ranges(pos, text, ranges, class, state);
+    }
+
+    fn text_squiggle(
+        &mut self,
+        pos: Coord,
+        text: &TextDisplay,
+        ranges: &[Range<usize>],
+        class: TextClass,
+        state: InputState,
+    ) {
+        self.inner.text_squiggle(pos, text, ranges, class, state);
+    }
+
+    fn text_cursor(
+        &mut self,
+        wid: u64,
+        pos: Coord,
+        text: &TextDisplay,
+        class: TextClass,
+        byte: usize,
+    ) {
+        self.inner.text_cursor(wid, pos, text, class, byte);
+    }
+
+    fn text_cursors(
+        &mut self,
+        wid: u64,
+        pos: Coord,
+        text: &TextDisplay,
+        class: TextClass,
+        bytes: &[usize],
+    ) {
+        self.inner.text_cursors(wid, pos, text, class, bytes);
+    }
+
+    fn menu_entry(&mut self, rect: Rect, state: InputState) {
+        self.overlay.draw_menu_entry(&mut self.inner, rect, state);
+    }
+
+    fn button(&mut self, rect: Rect, col: Option<Rgb>, state: InputState) {
+        self.overlay.draw_button(&mut self.inner, rect, col, state);
+    }
+
+    fn edit_box(&mut self, rect: Rect, state: InputState) {
+        self.overlay.draw_edit_box(&mut self.inner, rect, state);
+    }
+
+    fn checkbox(&mut self, wid: u64, rect: Rect, checked: CheckState, state: InputState) {
+        self.overlay
+            .draw_checkbox(&mut self.inner, wid, rect, checked, state);
+    }
+
+    fn radiobox(&mut self, wid: u64, rect: Rect, checked: bool, state: InputState) {
+        self.overlay
+            .draw_radiobox(&mut self.inner, wid, rect, checked, state);
+    }
+
+    fn switch(&mut self, wid: u64, rect: Rect, on: bool, state: InputState) {
+        self.overlay
+            .draw_switch(&mut self.inner, wid, rect, on, state);
+    }
+
+    fn scrollbar(&mut self, rect: Rect, h_rect: Rect, dir: Direction, state: InputState) {
+        self.overlay
+            .draw_scrollbar(&mut self.inner, rect, h_rect, dir, state);
+    }
+
+    fn slider(&mut self, rect: Rect, h_rect: Rect, dir: Direction, state: InputState) {
+        self.overlay
+            .draw_slider(&mut self.inner, rect, h_rect, dir, state);
+    }
+
+    fn progress_bar(&mut self, rect: Rect, dir: Direction, state: InputState, value: f32) {
+        self.overlay
+            .draw_progress_bar(&mut self.inner, rect, dir, state, value);
+    }
+
+    fn badge(&mut self, rect: Rect, state: InputState) {
+        self.overlay.draw_badge(&mut self.inner, rect, state);
+    }
+
+    fn image(&mut self, id: ImageId, rect: Rect) {
+        self.inner.image(id, rect);
+    }
+
+    fn symbol_color(&self, class: SymbolClass, state: InputState) -> Rgba {
+        self.overlay.symbol_color(&self.inner, class, state)
+    }
+}
@@ -0,0 +1,285 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Raster cache for theme elements
+//!
+//! Frequently repeated elements (e.g. button frames of a given size and
+//! state) may be rastered on the CPU once, uploaded to the image atlas, and
+//! subsequently drawn as a single image.
+
+use crate::flat_theme::BG_SHRINK_FACTOR as SHRINK_FACTOR;
+use kas::cast::Conv;
+use kas::draw::color::{Rgba, Rgba8Srgb};
+use kas::draw::{DrawShared, ImageFormat, ImageId};
+use std::collections::HashMap;
+
+/// Entries not used within this many frames are freed
+const RETAIN_FRAMES: u64 = 64;
+
+/// Maximum number of cached elements
+const MAX_ENTRIES: usize = 256;
+
+/// Maximum area (in pixels) of a cached element
+const MAX_AREA: u32 = 128 * 128;
+
+/// Key identifying a rastered element
+///
+/// Sizes are in physical pixels; colours are quantised to 8-bit sRGB.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CacheKey {
+    /// A rounded frame over a vertical gradient background
+    ///
+    /// This is used for buttons, check boxes, edit boxes and (with a
+    /// transparent background) outer frames.
+    ButtonFrame {
+        size: (u32, u32),
+        frame: u32,
+        col_frame: Rgba8Srgb,
+        col_top: Rgba8Srgb,
+        col_bottom: Rgba8Srgb,
+    },
+    /// A filled circle with a ring at its edge, as used by radio boxes
+    ///
+    /// The ring has width `frame * (1 - SHRINK_FACTOR)`.
+    RadioFrame {
+        size: (u32, u32),
+        frame: u32,
+        col_frame: Rgba8Srgb,
+        col_bg: Rgba8Srgb,
+    },
+}
+
+impl CacheKey {
+    fn size(&self) -> (u32, u32) {
+        match self {
+            CacheKey::ButtonFrame { size, .. } => *size,
+            CacheKey::RadioFrame { size, .. } => *size,
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Entry {
+    id: ImageId,
+    last_used: u64,
+}
+
+/// A per-window cache of rastered theme elements
+///
+/// Note: images are not freed when the cache is dropped.
+#[derive(Debug, Default)]
+pub struct RasterCache {
+    enabled: bool,
+    frame: u64,
+    entries: HashMap<CacheKey, Entry>,
+}
+
+impl RasterCache {
+    /// Construct
+    pub fn new(enabled: bool) -> Self {
+        RasterCache {
+            enabled,
+            ..Default::default()
+        }
+    }
+
+    /// Enable or disable
+    ///
+    /// When disabled, entries are freed on the next call to [`Self::frame_start`].
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Call at the start of each frame to free stale entries
+    pub fn frame_start(&mut self, shared: &mut dyn DrawShared) {
+        self.frame += 1;
+        if self.entries.is_empty() {
+            return;
+        }
+        let (enabled, frame) = (self.enabled, self.frame);
+        self.entries.retain(|_, entry| {
+            let retain = enabled && entry.last_used + RETAIN_FRAMES >= frame;
+            if !retain {
+                shared.image_free(entry.id);
+            }
+            retain
+        });
+    }
+
+    /// Get a cached element, rastering if required
+    ///
+    /// Returns `None` if the cache is disabled, full, or the element is too
+    /// large, in which case the caller should draw the element directly.
+    pub fn get(&mut self, shared: &mut dyn DrawShared, key: CacheKey) -> Option<ImageId> {
+        if !self.enabled {
+            return None;
+        }
+        if let Some(entry) = self.entries.get_mut(&key) {
+            entry.last_used = self.frame;
+            return Some(entry.id);
+        }
+
+        let size = key.size();
+        if size.0 == 0 || size.1 == 0 || size.0.saturating_mul(size.1) > MAX_AREA {
+            return None;
+        }
+        if self.entries.len() >= MAX_ENTRIES {
+            return None;
+        }
+
+        let data = raster(&key);
+        let id = shared.image_alloc(size).ok()?;
+        shared.image_upload(id, &data, ImageFormat::Rgba8);
+        let last_used = self.frame;
+        self.entries.insert(key, Entry { id, last_used });
+        Some(id)
+    }
+}
+
+/// Raster an element to RGBA (sRGB, straight alpha)
+fn raster(key: &CacheKey) -> Vec<u8> {
+    match *key {
+        CacheKey::ButtonFrame {
+            size,
+            frame,
+            col_frame,
+            col_top,
+            col_bottom,
+        } => raster_button_frame(
+            size,
+            f32::conv(frame),
+            col_frame.into(),
+            col_top.into(),
+            col_bottom.into(),
+        ),
+        CacheKey::RadioFrame {
+            size,
+            frame,
+            col_frame,
+            col_bg,
+        } => raster_radio_frame(size, f32::conv(frame), col_frame.into(), col_bg.into()),
+    }
+}
+
+/// Sample offsets within a pixel (4x multi-sampling)
+const SAMPLES: [(f32, f32); 4] = [(0.25, 0.25), (0.75, 0.25), (0.25, 0.75), (0.75, 0.75)];
+
+/// Composite a frame with coverage `frame_a` over a background with coverage
+/// `bg_a`, returning sRGB with straight alpha
+fn composite(col_frame: Rgba, frame_a: f32, col_bg: Rgba, bg_a: f32) -> [u8; 4] {
+    let frame_a = frame_a * col_frame.a;
+    let bg_a = bg_a * col_bg.a;
+    let a = frame_a + bg_a * (1.0 - frame_a);
+    let col = if a > 0.0 {
+        let f = frame_a / a;
+        let g = 1.0 - f;
+        Rgba {
+            r: col_frame.r * f + col_bg.r * g,
+            g: col_frame.g * f + col_bg.g * g,
+            b: col_frame.b * f + col_bg.b * g,
+            a,
+        }
+    } else {
+        Rgba::TRANSPARENT
+    };
+    Rgba8Srgb::from(col).into()
+}
+
+/// Raster a button frame
+///
+/// This approximates `rounded_frame(outer, outer.shrink(frame), SHRINK_FACTOR)`
+/// drawn over a vertical gradient fill of `outer.shrink(frame * SHRINK_FACTOR)`.
+fn raster_button_frame(
+    size: (u32, u32),
+    frame: f32,
+    col_frame: Rgba,
+    col_top: Rgba,
+    col_bottom: Rgba,
+) -> Vec<u8> {
+    let (w, h) = (f32::conv(size.0), f32::conv(size.1));
+    let s = frame * SHRINK_FACTOR;
+    let inner2 = SHRINK_FACTOR * SHRINK_FACTOR;
+    let frame = frame.max(f32::EPSILON);
+
+    // Normalised distance into the frame along one axis: 0 at the inner
+    // edge, 1 at the outer edge
+    let dist = |x: f32, len: f32| {
+        if x < frame {
+            (frame - x) / frame
+        } else if x > len - frame {
+            (x - (len - frame)) / frame
+        } else {
+            0.0
+        }
+    };
+    // Coverage of interval x..x+1 by s..len-s
+    let cover = |x: f32, len: f32| ((x + 1.0).min(len - s) - x.max(s)).clamp(0.0, 1.0);
+
+    let mut data = Vec::with_capacity(usize::conv(size.0 * size.1 * 4));
+    for y in 0..size.1 {
+        let y = f32::conv(y);
+        let t = ((y + 0.5 - s) / (h - 2.0 * s)).clamp(0.0, 1.0);
+        let col_bg = Rgba {
+            r: col_top.r + (col_bottom.r - col_top.r) * t,
+            g: col_top.g + (col_bottom.g - col_top.g) * t,
+            b: col_top.b + (col_bottom.b - col_top.b) * t,
+            a: col_top.a + (col_bottom.a - col_top.a) * t,
+        };
+        let cover_y = cover(y, h);
+
+        for x in 0..size.0 {
+            let x = f32::conv(x);
+            let bg_a = cover(x, w) * cover_y;
+
+            let mut frame_a = 0.0;
+            for (sx, sy) in SAMPLES {
+                let (dx, dy) = (dist(x + sx, w), dist(y + sy, h));
+                let ss = dx * dx + dy * dy;
+                if inner2 <= ss && ss <= 1.0 {
+                    frame_a += 0.25;
+                }
+            }
+
+            data.extend_from_slice(&composite(col_frame, frame_a, col_bg, bg_a));
+        }
+    }
+    data
+}
+
+/// Raster a radio box frame
+///
+/// This approximates `circle(outer, 0.0, col_bg)` followed by
+/// `circle(outer, r, col_frame)` where the ring has width
+/// `frame * (1 - SHRINK_FACTOR)`.
+fn raster_radio_frame(size: (u32, u32), frame: f32, col_frame: Rgba, col_bg: Rgba) -> Vec<u8> {
+    let (w, h) = (f32::conv(size.0), f32::conv(size.1));
+    let (rx, ry) = (0.5 * w, 0.5 * h);
+    // Inner radius of the ring relative to the outer radius
+    let inner = (1.0 - 2.0 * (1.0 - SHRINK_FACTOR) * frame / w).max(0.0);
+    let inner2 = inner * inner;
+
+    let mut data = Vec::with_capacity(usize::conv(size.0 * size.1 * 4));
+    for y in 0..size.1 {
+        let y = f32::conv(y);
+        for x in 0..size.0 {
+            let x = f32::conv(x);
+
+            let (mut bg_a, mut frame_a) = (0.0, 0.0);
+            for (sx, sy) in SAMPLES {
+                let (dx, dy) = ((x + sx - rx) / rx, (y + sy - ry) / ry);
+                let ss = dx * dx + dy * dy;
+                if ss <= 1.0 {
+                    bg_a += 0.25;
+                    if inner2 <= ss {
+                        frame_a += 0.25;
+                    }
+                }
+            }
+
+            data.extend_from_slice(&composite(col_frame, frame_a, col_bg, bg_a));
+        }
+    }
+    data
+}
@@ -5,6 +5,7 @@
 
 //! Shaded theme
 
+use std::borrow::Cow;
 use std::f32;
 use std::ops::Range;
 
@@ -14,7 +15,7 @@ use kas::dir::{Direction, Directional};
 use kas::draw::{color::Rgba, *};
 use kas::geom::*;
 use kas::text::{AccelString, Text, TextApi, TextDisplay};
-use kas::theme::{self, InputState, SizeHandle, TextClass, ThemeControl};
+use kas::theme::{self, CheckState, InputState, SizeHandle, SymbolClass, TextClass, ThemeControl};
 use kas::TkAction;
 
 /// A theme using simple shading to give apparent depth to elements
@@ -71,12 +72,13 @@ const DIMS: dim::Parameters = dim::Parameters {
     progress_bar: Vec2::splat(12.0),
     shadow_size: Vec2::splat(6.0),
     shadow_rel_offset: Vec2::ZERO,
+    icon_sizes: [16.0, 24.0, 48.0],
 };
 
 pub struct DrawHandle<'a, DS: DrawSharedImpl> {
     draw: DrawIface<'a, DS>,
     w: &'a mut dim::Window<DS::Draw>,
-    cols: &'a ColorsLinear,
+    cols: Cow<'a, ColorsLinear>,
 }
 
 impl<DS: DrawSharedImpl> Theme<DS> for ShadedTheme
@@ -113,7 +115,11 @@ where
     }
 
     #[cfg(not(feature = "gat"))]
-    unsafe fn draw_handle(&self, draw: DrawIface<DS>, w: &mut Self::Window) -> Self::DrawHandle {
+    unsafe fn draw_handle(
+        &self,
+        mut draw: DrawIface<DS>,
+        w: &mut Self::Window,
+    ) -> Self::DrawHandle {
         w.anim.update();
 
         unsafe fn extend_lifetime<'b, T: ?Sized>(r: &'b T) -> &'static T {
@@ -122,6 +128,10 @@ where
         unsafe fn extend_lifetime_mut<'b, T: ?Sized>(r: &'b mut T) -> &'static mut T {
             std::mem::transmute::<&'b mut T, &'static mut T>(r)
         }
+        let cols = match self.flat.draw_cols(&mut draw) {
+            Cow::Borrowed(cols) => Cow::Borrowed(extend_lifetime(cols)),
+            Cow::Owned(cols) => Cow::Owned(cols),
+        };
         DrawHandle {
             draw: DrawIface {
                 draw: extend_lifetime_mut(draw.draw),
@@ -129,22 +139,19 @@ where
                 pass: draw.pass,
             },
             w: extend_lifetime_mut(w),
-            cols: extend_lifetime(&self.flat.cols),
+            cols,
         }
     }
     #[cfg(feature = "gat")]
     fn draw_handle<'a>(
         &'a self,
-        draw: DrawIface<'a, DS>,
+        mut draw: DrawIface<'a, DS>,
         w: &'a mut Self::Window,
     ) -> Self::DrawHandle<'a> {
         w.anim.update();
 
-        DrawHandle {
-            draw,
-            w,
-            cols: &self.flat.cols,
-        }
+        let cols = self.flat.draw_cols(&mut draw);
+        DrawHandle { draw, w, cols }
     }
 
     fn clear_color(&self) -> Rgba {
@@ -179,7 +186,7 @@ where
         super::flat_theme::DrawHandle {
             draw: self.draw.re(),
             w: self.w,
-            cols: self.cols,
+            cols: Cow::Borrowed(&*self.cols),
         }
     }
 
@@ -190,8 +197,9 @@ where
     /// - `bg_col`: colour of background
     /// - `nav_col`: colour of navigation highlight, if visible
     fn draw_edit_box(&mut self, outer: Rect, bg_col: Rgba, nav_col: Option<Rgba>) -> Quad {
+        let px = self.w.dims.pixel_align();
         let mut outer = Quad::from(outer);
-        let mut inner = outer.shrink(self.w.dims.frame as f32);
+        let mut inner = px.shrink(outer, self.w.dims.frame as f32);
 
         let col = self.cols.background;
         self.draw
@@ -199,7 +207,7 @@ where
 
         if let Some(col) = nav_col {
             outer = inner;
-            inner = outer.shrink(self.w.dims.inner_margin as f32);
+            inner = px.shrink(outer, self.w.dims.inner_margin as f32);
             self.draw.frame(outer, inner, col);
         }
 
@@ -215,7 +223,7 @@ where
         let col = self.cols.accent_soft_state(state);
         self.draw.shaded_round_frame(outer, inner, (0.0, 0.6), col);
 
-        if let Some(col) = self.cols.nav_region(state) {
+        if let Some(col) = self.cols.nav_region(self.w.focus_state(state)) {
             let outer = outer.shrink(thickness / 4.0);
             self.draw.rounded_frame(outer, inner, 0.6, col);
         }
@@ -238,6 +246,7 @@ where
         &mut self,
         inner_rect: Rect,
         offset: Offset,
+        transform: Affine,
         class: PassType,
         f: &mut dyn FnMut(&mut dyn theme::DrawHandle),
     ) {
@@ -251,7 +260,7 @@ where
             let b = shadow.b.ceil();
             outer_rect = Rect::new(a.into(), (b - a).into());
         }
-        let mut draw = self.draw.new_pass(outer_rect, offset, class);
+        let mut draw = self.draw.new_pass(outer_rect, offset, transform, class);
 
         if class == PassType::Overlay {
             shadow += offset.into();
@@ -264,7 +273,7 @@ where
         let mut handle = DrawHandle {
             w: self.w,
             draw,
-            cols: self.cols,
+            cols: Cow::Borrowed(&*self.cols),
         };
         f(&mut handle);
     }
@@ -274,8 +283,9 @@ where
     }
 
     fn outer_frame(&mut self, rect: Rect) {
+        let px = self.w.dims.pixel_align();
         let outer = Quad::from(rect);
-        let inner = outer.shrink(self.w.dims.frame as f32);
+        let inner = px.shrink(outer, self.w.dims.frame as f32);
         let norm = (0.7, -0.7);
         let col = self.cols.background;
         self.draw.shaded_round_frame(outer, inner, norm, col);
@@ -334,6 +344,18 @@ where
             .text_selected_range(pos, text, range, class, state);
     }
 
+    fn text_selected_ranges(
+        &mut self,
+        pos: Coord,
+        text: &TextDisplay,
+        ranges: &[Range<usize>],
+        class: TextClass,
+        state: InputState,
+    ) {
+        self.as_flat()
+            .text_selected_ranges(pos, text, ranges, class, state);
+    }
+
     fn text_cursor(
         &mut self,
         wid: u64,
@@ -345,21 +367,45 @@ where
         self.as_flat().text_cursor(wid, pos, text, class, byte);
     }
 
+    fn text_squiggle(
+        &mut self,
+        pos: Coord,
+        text: &TextDisplay,
+        ranges: &[Range<usize>],
+        class: TextClass,
+        state: InputState,
+    ) {
+        self.as_flat()
+            .text_squiggle(pos, text, ranges, class, state);
+    }
+
+    fn text_cursors(
+        &mut self,
+        wid: u64,
+        pos: Coord,
+        text: &TextDisplay,
+        class: TextClass,
+        bytes: &[usize],
+    ) {
+        self.as_flat().text_cursors(wid, pos, text, class, bytes);
+    }
+
     fn menu_entry(&mut self, rect: Rect, state: InputState) {
         self.as_flat().menu_entry(rect, state);
     }
 
     fn button(&mut self, rect: Rect, col: Option<color::Rgb>, state: InputState) {
+        let px = self.w.dims.pixel_align();
         let outer = Quad::from(rect);
-        let inner = outer.shrink(self.w.dims.button_frame as f32);
+        let inner = px.shrink(outer, self.w.dims.button_frame as f32);
         let col = col.map(|c| c.into()).unwrap_or(self.cols.accent_soft);
         let col = ColorsLinear::adjust_for_state(col, state);
 
         self.draw.shaded_round_frame(outer, inner, (0.0, 0.6), col);
         self.draw.rect(inner, col);
 
-        if let Some(col) = self.cols.nav_region(state) {
-            let outer = outer.shrink(self.w.dims.inner_margin as f32);
+        if let Some(col) = self.cols.nav_region(self.w.focus_state(state)) {
+            let outer = px.shrink(outer, self.w.dims.inner_margin as f32);
             self.draw.rounded_frame(outer, inner, 0.6, col);
         }
     }
@@ -367,19 +413,31 @@ where
     fn edit_box(&mut self, rect: Rect, mut state: InputState) {
         state.remove(InputState::DEPRESS);
         let bg_col = self.cols.edit_bg(state);
-        self.draw_edit_box(rect, bg_col, self.cols.nav_region(state));
+        self.draw_edit_box(
+            rect,
+            bg_col,
+            self.cols.nav_region(self.w.focus_state(state)),
+        );
     }
 
-    fn checkbox(&mut self, wid: u64, rect: Rect, checked: bool, state: InputState) {
-        let anim_fade = self.w.anim.fade_bool_1m(self.draw.draw, wid, checked);
+    fn checkbox(&mut self, wid: u64, rect: Rect, checked: CheckState, state: InputState) {
+        let marked = checked != CheckState::Unchecked;
+        let anim_fade = self.w.anim.fade_bool_1m(self.draw.draw, wid, marked);
 
         let bg_col = self.cols.edit_bg(state);
-        let nav_col = self.cols.nav_region(state).or(Some(bg_col));
+        let nav_col = self
+            .cols
+            .nav_region(self.w.focus_state(state))
+            .or(Some(bg_col));
 
         let inner = self.draw_edit_box(rect, bg_col, nav_col);
 
         if anim_fade < 1.0 {
-            let v = inner.size() * (anim_fade / 2.0);
+            let mut v = inner.size() * (anim_fade / 2.0);
+            if checked.is_indeterminate() {
+                // Mixed mark: a horizontal bar
+                v.1 = v.1.max(inner.size().1 / 3.0);
+            }
             let inner = Quad::from_coords(inner.a + v, inner.b - v);
             let col = self.cols.check_mark_state(state);
             self.draw.shaded_square(inner, (0.0, 0.4), col);
@@ -390,7 +448,10 @@ where
         let anim_fade = self.w.anim.fade_bool_1m(self.draw.draw, wid, checked);
 
         let bg_col = self.cols.edit_bg(state);
-        let nav_col = self.cols.nav_region(state).or(Some(bg_col));
+        let nav_col = self
+            .cols
+            .nav_region(self.w.focus_state(state))
+            .or(Some(bg_col));
 
         let inner = self.draw_edit_box(rect, bg_col, nav_col);
 
@@ -402,6 +463,34 @@ where
         }
     }
 
+    fn switch(&mut self, wid: u64, rect: Rect, on: bool, state: InputState) {
+        let anim_fade = self.w.anim.fade_bool(self.draw.draw, wid, on);
+
+        // track: background colour, overlaid with accent colour as switch turns on
+        let outer = Quad::from(rect);
+        let inner = outer.shrink(outer.size().min_comp() / 2.0);
+        let norm = (0.0, -0.7);
+        let col = ColorsLinear::adjust_for_state(self.cols.background, state);
+        self.draw.shaded_round_frame(outer, inner, norm, col);
+        if anim_fade > 0.0 {
+            let mut col = self.cols.accent_state(state);
+            col.a *= anim_fade;
+            self.draw.shaded_round_frame(outer, inner, norm, col);
+        }
+
+        // thumb: a circle sliding from start to end of track
+        let d = outer.size().1;
+        let x = outer.a.0 + (outer.size().0 - d) * anim_fade;
+        let thumb = Quad::from_coords(Vec2(x, outer.a.1), Vec2(x + d, outer.b.1));
+        let thumb = thumb.shrink(self.w.dims.button_frame as f32);
+        let col = self.cols.accent_soft_state(state);
+        self.draw.shaded_circle(thumb, (0.0, 0.6), col);
+
+        if let Some(col) = self.cols.nav_region(self.w.focus_state(state)) {
+            self.draw.circle(thumb, 12.0 / 16.0, col);
+        }
+    }
+
     fn scrollbar(&mut self, rect: Rect, h_rect: Rect, _dir: Direction, state: InputState) {
         // track
         let outer = Quad::from(rect);
@@ -448,7 +537,18 @@ where
         self.draw.shaded_round_frame(outer, inner, (0.0, 0.6), col);
     }
 
+    fn badge(&mut self, rect: Rect, state: InputState) {
+        let outer = Quad::from(rect);
+        let inner = outer.shrink(outer.size().min_comp() / 2.0);
+        let col = self.cols.accent_state(state);
+        self.draw.shaded_round_frame(outer, inner, (0.0, 0.6), col);
+    }
+
     fn image(&mut self, id: ImageId, rect: Rect) {
         self.as_flat().image(id, rect);
     }
+
+    fn symbol_color(&self, class: SymbolClass, state: InputState) -> Rgba {
+        self.cols.symbol_state(class, state)
+    }
 }
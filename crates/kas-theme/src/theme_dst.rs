@@ -209,6 +209,10 @@ impl Window for StackDst<dyn Window> {
         self.deref().size_handle()
     }
 
+    fn set_text_zoom(&mut self, zoom: f32) {
+        self.deref_mut().set_text_zoom(zoom);
+    }
+
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self.deref_mut().as_any_mut()
     }
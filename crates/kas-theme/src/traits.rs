@@ -135,6 +135,12 @@ pub trait Window: 'static {
     /// Construct a [`SizeHandle`] object
     fn size_handle(&self) -> &dyn SizeHandle;
 
+    /// Set the text zoom factor
+    ///
+    /// Font sizes in this window are multiplied by `zoom`. This takes effect
+    /// on the next call to [`Theme::update_window`].
+    fn set_text_zoom(&mut self, zoom: f32);
+
     fn as_any_mut(&mut self) -> &mut dyn Any;
 }
 
@@ -195,6 +201,10 @@ impl<W: Window> Window for Box<W> {
         self.deref().size_handle()
     }
 
+    fn set_text_zoom(&mut self, zoom: f32) {
+        self.deref_mut().set_text_zoom(zoom);
+    }
+
     fn as_any_mut(&mut self) -> &mut dyn Any {
         self.deref_mut().as_any_mut()
     }
@@ -16,6 +16,8 @@ use kas::draw::{ImageError, PassId};
 use kas::geom::{Quad, Size, Vec2};
 use kas::macros::autoimpl;
 
+use super::stats::AtlasStats;
+
 fn to_vec2(p: guillotiere::Point) -> Vec2 {
     Vec2(p.x.cast(), p.y.cast())
 }
@@ -33,7 +35,8 @@ impl From<AllocError> for ImageError {
 }
 
 pub struct Atlas {
-    alloc: AtlasAllocator,
+    // None for imported textures (these are not shared)
+    alloc: Option<AtlasAllocator>,
     tex: wgpu::Texture,
     bg: wgpu::BindGroup,
 }
@@ -67,6 +70,17 @@ impl Atlas {
             usage: wgpu::TextureUsages::TEXTURE_BINDING | wgpu::TextureUsages::COPY_DST,
         });
 
+        Self::with_texture(Some(alloc), tex, device, bg_tex_layout, sampler)
+    }
+
+    /// Construct over an existing texture
+    fn with_texture(
+        alloc: Option<AtlasAllocator>,
+        tex: wgpu::Texture,
+        device: &wgpu::Device,
+        bg_tex_layout: &wgpu::BindGroupLayout,
+        sampler: &wgpu::Sampler,
+    ) -> Self {
         let view = tex.create_view(&wgpu::TextureViewDescriptor::default());
 
         let bg = device.create_bind_group(&wgpu::BindGroupDescriptor {
@@ -94,8 +108,10 @@ pub struct Pipeline<I: bytemuck::Pod> {
     tex_format: wgpu::TextureFormat,
     bg_tex_layout: wgpu::BindGroupLayout,
     render_pipeline: wgpu::RenderPipeline,
-    atlases: Vec<Atlas>,
+    // Entries are None only where an imported texture has been removed
+    atlases: Vec<Option<Atlas>>,
     new_aa: Vec<AtlasAllocator>,
+    allocated: u64,
     sampler: wgpu::Sampler,
     _pd: std::marker::PhantomData<I>,
 }
@@ -106,15 +122,19 @@ impl<I: bytemuck::Pod> Pipeline<I> {
     /// Configuration:
     ///
     /// -   `tex_size`: side length of square texture atlases
-    /// -   `tex_format`: texture format
+    /// -   `tex_format`: texture format (must be filterable when `filter` is
+    ///     [`wgpu::FilterMode::Linear`])
+    /// -   `filter`: texture filtering mode
     pub fn new(
         device: &wgpu::Device,
         bg_common: &wgpu::BindGroupLayout,
         tex_size: i32,
         tex_format: wgpu::TextureFormat,
+        filter: wgpu::FilterMode,
         vertex: wgpu::VertexState,
         fragment: wgpu::FragmentState,
     ) -> Self {
+        let filtering = filter == wgpu::FilterMode::Linear;
         let bg_tex_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: Some("atlas texture bind group layout"),
             entries: &[
@@ -122,7 +142,9 @@ impl<I: bytemuck::Pod> Pipeline<I> {
                     binding: 0,
                     visibility: wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Texture {
-                        sample_type: wgpu::TextureSampleType::Float { filterable: false },
+                        sample_type: wgpu::TextureSampleType::Float {
+                            filterable: filtering,
+                        },
                         view_dimension: wgpu::TextureViewDimension::D2,
                         multisampled: false,
                     },
@@ -132,7 +154,7 @@ impl<I: bytemuck::Pod> Pipeline<I> {
                     binding: 1,
                     visibility: wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Sampler {
-                        filtering: false,
+                        filtering,
                         comparison: false,
                     },
                     count: None,
@@ -166,8 +188,8 @@ impl<I: bytemuck::Pod> Pipeline<I> {
 
         let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
             label: Some("image sampler"),
-            mag_filter: wgpu::FilterMode::Nearest,
-            min_filter: wgpu::FilterMode::Nearest,
+            mag_filter: filter,
+            min_filter: filter,
             ..Default::default()
         });
 
@@ -178,6 +200,7 @@ impl<I: bytemuck::Pod> Pipeline<I> {
             render_pipeline,
             atlases: vec![],
             new_aa: vec![],
+            allocated: 0,
             sampler,
             _pd: Default::default(),
         }
@@ -191,7 +214,8 @@ impl<I: bytemuck::Pod> Pipeline<I> {
         let size2d = size.into();
         let mut atlas = 0;
         while atlas < self.atlases.len() {
-            if let Some(alloc) = self.atlases[atlas].alloc.allocate(size2d) {
+            let aa = self.atlases[atlas].as_mut().and_then(|a| a.alloc.as_mut());
+            if let Some(alloc) = aa.and_then(|aa| aa.allocate(size2d)) {
                 return Ok((atlas.cast(), alloc, tex_size));
             }
             atlas += 1;
@@ -240,6 +264,7 @@ impl<I: bytemuck::Pod> Pipeline<I> {
         }
 
         let (atlas, alloc, tex_size) = self.allocate_space((size.0.cast(), size.1.cast()))?;
+        self.allocated += u64::from(size.0) * u64::from(size.1);
 
         let origin = (alloc.rectangle.min.x.cast(), alloc.rectangle.min.y.cast());
 
@@ -252,8 +277,77 @@ impl<I: bytemuck::Pod> Pipeline<I> {
         Ok((atlas, alloc.id, origin, tex_quad))
     }
 
-    pub fn deallocate(&mut self, atlas: u32, alloc: AllocId) {
-        self.atlases[usize::conv(atlas)].alloc.deallocate(alloc);
+    /// Free an allocation
+    ///
+    /// The `size` must equal that passed to [`Self::allocate`].
+    pub fn deallocate(&mut self, atlas: u32, alloc: AllocId, size: (u32, u32)) {
+        let atlas = self.atlases[usize::conv(atlas)].as_mut();
+        if let Some(aa) = atlas.and_then(|a| a.alloc.as_mut()) {
+            aa.deallocate(alloc);
+            self.allocated -= u64::from(size.0) * u64::from(size.1);
+        }
+    }
+
+    /// Import an existing texture as a dedicated atlas
+    ///
+    /// The texture must support [`wgpu::TextureUsages::TEXTURE_BINDING`] and
+    /// have a float sample type. It is not shared with other allocations.
+    ///
+    /// On success, returns the `atlas` number and texture coordinates.
+    pub fn import(
+        &mut self,
+        device: &wgpu::Device,
+        tex: wgpu::Texture,
+        size: (u32, u32),
+    ) -> Result<(u32, Quad), AllocError> {
+        if size.0 == 0 || size.1 == 0 {
+            return Err(AllocError);
+        }
+
+        // Assign atlas numbers to pending allocators first
+        self.prepare(device);
+
+        let atlas = Atlas::with_texture(None, tex, device, &self.bg_tex_layout, &self.sampler);
+        let index = match self.atlases.iter().position(|a| a.is_none()) {
+            Some(index) => {
+                self.atlases[index] = Some(atlas);
+                index
+            }
+            None => {
+                self.atlases.push(Some(atlas));
+                self.atlases.len() - 1
+            }
+        };
+
+        let tex_quad = Quad::from_coords(Vec2::ZERO, Vec2::splat(1.0));
+        Ok((index.cast(), tex_quad))
+    }
+
+    /// Remove an imported texture
+    ///
+    /// The texture is dropped; its `atlas` number may be reused.
+    pub fn remove_import(&mut self, atlas: u32) {
+        let atlas = &mut self.atlases[usize::conv(atlas)];
+        debug_assert!(atlas.as_ref().map(|a| a.alloc.is_none()).unwrap_or(false));
+        *atlas = None;
+    }
+
+    /// Get atlas occupancy
+    pub fn stats(&self) -> AtlasStats {
+        let allocators = self.atlases.iter().flatten().flat_map(|a| a.alloc.as_ref());
+        let sizes: Vec<_> = allocators
+            .chain(self.new_aa.iter())
+            .map(|aa| aa.size())
+            .collect();
+        let capacity = sizes
+            .iter()
+            .map(|size| u64::conv(size.width) * u64::conv(size.height))
+            .sum();
+        AtlasStats {
+            atlases: sizes.len().cast(),
+            allocated: self.allocated,
+            capacity,
+        }
     }
 
     /// Prepare textures
@@ -266,12 +360,13 @@ impl<I: bytemuck::Pod> Pipeline<I> {
                 &self.sampler,
                 self.tex_format,
             );
-            self.atlases.push(atlas);
+            self.atlases.push(Some(atlas));
         }
     }
 
     pub fn get_texture(&self, atlas: u32) -> &wgpu::Texture {
-        &self.atlases[usize::conv(atlas)].tex
+        let atlas = self.atlases[usize::conv(atlas)].as_ref();
+        &atlas.expect("removed atlas").tex
     }
 
     /// Enqueue render commands
@@ -291,8 +386,11 @@ impl<I: bytemuck::Pod> Pipeline<I> {
                 rpass.set_bind_group(0, bg_common, &[]);
                 rpass.set_vertex_buffer(0, buffer.slice(pass.data_range.clone()));
                 for (a, atlas) in pass.atlases.iter().enumerate() {
-                    if !atlas.range.is_empty() {
-                        rpass.set_bind_group(1, &self.atlases[a].bg, &[]);
+                    if atlas.range.is_empty() {
+                        continue;
+                    }
+                    if let Some(Some(tex_atlas)) = self.atlases.get(a) {
+                        rpass.set_bind_group(1, &tex_atlas.bg, &[]);
                         rpass.draw(0..4, atlas.range.clone());
                     }
                 }
@@ -398,6 +496,21 @@ impl<I: bytemuck::Pod> Window<I> {
         }
     }
 
+    /// Get `(instances, draw_calls)` as of the last call to `write_buffers`
+    pub fn stats(&self) -> (u32, u32) {
+        let mut instances = 0;
+        let mut draw_calls = 0;
+        for pass in &self.passes {
+            for atlas in &pass.atlases {
+                if !atlas.range.is_empty() {
+                    instances += atlas.range.len();
+                    draw_calls += 1;
+                }
+            }
+        }
+        (instances.cast(), draw_calls)
+    }
+
     /// Add a rectangle to the buffer
     pub fn rect(&mut self, pass: PassId, atlas: u32, instance: I) {
         let pass = pass.pass();
@@ -0,0 +1,301 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Colour glyph rasterization
+//!
+//! Colour emoji fonts store glyphs either as embedded bitmaps (CBDT, sbix) or
+//! as layers of coloured outlines (COLR version 0). Before outline rastering,
+//! we look up the face among loaded colour fonts; if the glyph has colour data
+//! we decode the bitmap (PNG only) or raster and composite its layers.
+
+use ab_glyph_rasterizer::{point, Point, Rasterizer};
+use kas::cast::*;
+use kas::geom::Vec2;
+use kas::text::fonts::{fonts, FaceId};
+use kas::text::GlyphId;
+use kas_theme::EMOJI_FAMILIES;
+use rustc_hash::FxHashMap as HashMap;
+
+/// Characters used to match a [`FaceId`] to a loaded colour font
+const PROBES: [char; 4] = ['\u{1F600}', '\u{1F44D}', '\u{1F389}', '\u{2764}'];
+
+/// A rastered colour glyph
+pub struct ColorRaster {
+    pub size: (u32, u32),
+    pub offset: Vec2,
+    /// RGBA data (sRGB, not pre-multiplied)
+    pub data: Vec<u8>,
+}
+
+struct ColorFace {
+    data: Vec<u8>,
+    index: u32,
+    probes: [u16; PROBES.len()],
+}
+
+/// Colour glyph rasterizer
+#[derive(Default)]
+pub struct ColorGlyphs {
+    loaded: bool,
+    faces: Vec<ColorFace>,
+    matches: HashMap<FaceId, Option<usize>>,
+}
+
+impl ColorGlyphs {
+    /// Raster a colour glyph
+    ///
+    /// Returns `None` if `face` is not a known colour font or the glyph has no
+    /// supported bitmap.
+    pub fn raster(&mut self, face: FaceId, dpem: f32, glyph: GlyphId) -> Option<ColorRaster> {
+        let index = self.match_face(face)?;
+        let cf = &self.faces[index];
+        let face = ttf_parser::Face::from_slice(&cf.data, cf.index).ok()?;
+        let glyph = ttf_parser::GlyphId(glyph.0);
+
+        if face.is_color_glyph(glyph) {
+            return raster_colr(&face, dpem, glyph);
+        }
+
+        // Select the smallest strike not smaller than the target size
+        let ppem = u16::conv_nearest(dpem.min(f32::from(u16::MAX)));
+        let img = face.glyph_raster_image(glyph, ppem)?;
+        if img.format != ttf_parser::RasterImageFormat::PNG {
+            log::debug!("color_glyphs: unsupported image format for {:?}", glyph);
+            return None;
+        }
+        let image = match image::load_from_memory_with_format(img.data, image::ImageFormat::Png) {
+            Ok(image) => image.into_rgba8(),
+            Err(error) => {
+                log::warn!("color_glyphs: failed to decode glyph image: {}", error);
+                return None;
+            }
+        };
+
+        let scale = dpem / f32::from(img.pixels_per_em.max(1));
+        let w = u32::conv_nearest(f32::conv(image.width()) * scale).max(1);
+        let h = u32::conv_nearest(f32::conv(image.height()) * scale).max(1);
+        let image = match (w, h) == image.dimensions() {
+            true => image,
+            false => image::imageops::resize(&image, w, h, image::imageops::FilterType::Triangle),
+        };
+
+        // Offsets are relative to the glyph origin with y-axis upwards
+        let x = f32::from(img.x) * scale;
+        let y = -(f32::from(img.y) + f32::from(img.height)) * scale;
+        Some(ColorRaster {
+            size: (w, h),
+            offset: Vec2(x, y).round(),
+            data: image.into_raw(),
+        })
+    }
+
+    fn match_face(&mut self, face: FaceId) -> Option<usize> {
+        if let Some(index) = self.matches.get(&face) {
+            return *index;
+        }
+
+        let face_ref = fonts().get_face(face);
+        let mut probes = [0; PROBES.len()];
+        for (p, c) in probes.iter_mut().zip(PROBES.iter()) {
+            *p = face_ref.glyph_index(*c).0;
+        }
+        // Only load colour fonts once a face with emoji is used
+        let index = match probes.iter().any(|p| *p != 0) {
+            true => {
+                if !self.loaded {
+                    self.load();
+                }
+                self.faces.iter().position(|cf| cf.probes == probes)
+            }
+            false => None,
+        };
+        self.matches.insert(face, index);
+        index
+    }
+
+    fn load(&mut self) {
+        self.loaded = true;
+        let fonts_db = fonts().read_db();
+        let db = fonts_db.db();
+        for info in db.faces() {
+            if !EMOJI_FAMILIES.iter().any(|f| info.family == *f) {
+                continue;
+            }
+            let result = db.with_face_data(info.id, |data, index| {
+                let face = ttf_parser::Face::from_slice(data, index).ok()?;
+                let mut probes = [0; PROBES.len()];
+                for (p, c) in probes.iter_mut().zip(PROBES.iter()) {
+                    *p = face.glyph_index(*c).map(|id| id.0).unwrap_or(0);
+                }
+                let data = data.to_vec();
+                Some(ColorFace {
+                    data,
+                    index,
+                    probes,
+                })
+            });
+            if let Some(face) = result.flatten() {
+                log::debug!("color_glyphs: loaded colour font {}", info.family);
+                self.faces.push(face);
+            }
+        }
+    }
+}
+
+/// Collects the layers of a COLR glyph
+///
+/// Colour `None` represents the text foreground colour.
+#[derive(Default)]
+struct Layers {
+    current: Option<ttf_parser::GlyphId>,
+    layers: Vec<(ttf_parser::GlyphId, Option<ttf_parser::RgbaColor>)>,
+}
+
+impl ttf_parser::colr::Painter for Layers {
+    fn outline(&mut self, glyph_id: ttf_parser::GlyphId) {
+        self.current = Some(glyph_id);
+    }
+
+    fn paint_foreground(&mut self) {
+        self.layers.extend(self.current.take().map(|id| (id, None)));
+    }
+
+    fn paint_color(&mut self, color: ttf_parser::RgbaColor) {
+        self.layers
+            .extend(self.current.take().map(|id| (id, Some(color))));
+    }
+}
+
+/// Feeds glyph outlines to a rasterizer, transforming from font units
+struct Outline {
+    rasterizer: Rasterizer,
+    scale: f32,
+    offset: Vec2,
+    start: Point,
+    last: Point,
+}
+
+impl Outline {
+    fn point(&self, x: f32, y: f32) -> Point {
+        point(
+            x * self.scale - self.offset.0,
+            -y * self.scale - self.offset.1,
+        )
+    }
+}
+
+impl ttf_parser::OutlineBuilder for Outline {
+    fn move_to(&mut self, x: f32, y: f32) {
+        self.start = self.point(x, y);
+        self.last = self.start;
+    }
+
+    fn line_to(&mut self, x: f32, y: f32) {
+        let p = self.point(x, y);
+        self.rasterizer.draw_line(self.last, p);
+        self.last = p;
+    }
+
+    fn quad_to(&mut self, x1: f32, y1: f32, x: f32, y: f32) {
+        let (p1, p) = (self.point(x1, y1), self.point(x, y));
+        self.rasterizer.draw_quad(self.last, p1, p);
+        self.last = p;
+    }
+
+    fn curve_to(&mut self, x1: f32, y1: f32, x2: f32, y2: f32, x: f32, y: f32) {
+        let (p1, p2, p) = (self.point(x1, y1), self.point(x2, y2), self.point(x, y));
+        self.rasterizer.draw_cubic(self.last, p1, p2, p);
+        self.last = p;
+    }
+
+    fn close(&mut self) {
+        if self.last != self.start {
+            self.rasterizer.draw_line(self.last, self.start);
+        }
+        self.last = self.start;
+    }
+}
+
+/// Raster a COLR (version 0) glyph
+///
+/// Layers are composited in order using the first palette. Layers painted in
+/// the foreground colour use opaque black since glyphs are cached
+/// independently of text colour.
+fn raster_colr(
+    face: &ttf_parser::Face,
+    dpem: f32,
+    glyph: ttf_parser::GlyphId,
+) -> Option<ColorRaster> {
+    let mut layers = Layers::default();
+    face.paint_color_glyph(glyph, 0, &mut layers)?;
+
+    let bounds = layers
+        .layers
+        .iter()
+        .filter_map(|(id, _)| face.glyph_bounding_box(*id))
+        .reduce(|a, b| ttf_parser::Rect {
+            x_min: a.x_min.min(b.x_min),
+            y_min: a.y_min.min(b.y_min),
+            x_max: a.x_max.max(b.x_max),
+            y_max: a.y_max.max(b.y_max),
+        })?;
+
+    let scale = dpem / f32::from(face.units_per_em());
+    let x0 = (f32::from(bounds.x_min) * scale).floor();
+    let y0 = (-f32::from(bounds.y_max) * scale).floor();
+    let x1 = (f32::from(bounds.x_max) * scale).ceil();
+    let y1 = (-f32::from(bounds.y_min) * scale).ceil();
+    let (w, h) = (usize::conv_nearest(x1 - x0), usize::conv_nearest(y1 - y0));
+    if w == 0 || h == 0 {
+        return None;
+    }
+
+    // Composite in premultiplied sRGB
+    let mut buf = vec![[0f32; 4]; w * h];
+    for (id, color) in layers.layers {
+        let color = color.unwrap_or(ttf_parser::RgbaColor {
+            red: 0,
+            green: 0,
+            blue: 0,
+            alpha: 255,
+        });
+        let alpha = f32::from(color.alpha) / 255.0;
+        let rgb = [color.red, color.green, color.blue].map(|c| f32::from(c) / 255.0);
+
+        let mut outline = Outline {
+            rasterizer: Rasterizer::new(w, h),
+            scale,
+            offset: Vec2(x0, y0),
+            start: point(0.0, 0.0),
+            last: point(0.0, 0.0),
+        };
+        if face.outline_glyph(id, &mut outline).is_none() {
+            continue;
+        }
+        outline.rasterizer.for_each_pixel(|index, coverage| {
+            let a = coverage.min(1.0) * alpha;
+            let px = &mut buf[index];
+            for (p, c) in px.iter_mut().zip(rgb.iter().chain(std::iter::once(&1.0))) {
+                *p = c * a + *p * (1.0 - a);
+            }
+        });
+    }
+
+    let mut data = Vec::with_capacity(w * h * 4);
+    for px in buf {
+        let a = px[3];
+        for c in &px[0..3] {
+            let v = if a > 0.0 { c / a } else { 0.0 };
+            data.push(u8::conv_nearest(v * 255.0));
+        }
+        data.push(u8::conv_nearest(a * 255.0));
+    }
+
+    Some(ColorRaster {
+        size: (w.cast(), h.cast()),
+        offset: Vec2(x0, y0),
+        data,
+    })
+}
@@ -113,9 +113,49 @@ impl<V: bytemuck::Pod> Window<V> {
         }
     }
 
+    /// Enqueue render commands, treating each element as an instance
+    ///
+    /// Each instance is drawn as `vertices` vertices (usually a triangle strip).
+    pub fn render_instanced<'a>(
+        &'a self,
+        pass: usize,
+        rpass: &mut wgpu::RenderPass<'a>,
+        pipeline: &'a wgpu::RenderPipeline,
+        bg_common: &'a wgpu::BindGroup,
+        vertices: u32,
+    ) {
+        if let Some(buffer) = self.buffer.as_ref() {
+            if let Some(pass) = self.passes.get(pass) {
+                if pass.data_range.is_empty() {
+                    return;
+                }
+                rpass.set_pipeline(pipeline);
+                rpass.set_bind_group(0, bg_common, &[]);
+                rpass.set_vertex_buffer(0, buffer.slice(pass.data_range.clone()));
+                rpass.draw(0..vertices, 0..pass.count);
+            }
+        }
+    }
+
+    /// Get `(elements, draw_calls)` as of the last call to `write_buffers`
+    pub fn stats(&self) -> (u32, u32) {
+        let mut elements = 0;
+        let mut draw_calls = 0;
+        for pass in &self.passes {
+            if !pass.data_range.is_empty() {
+                elements += pass.count;
+                draw_calls += 1;
+            }
+        }
+        (elements, draw_calls)
+    }
+
     pub fn add_vertices(&mut self, pass: usize, slice: &[V]) {
         debug_assert_eq!(slice.len() % 3, 0);
+        self.add_instances(pass, slice);
+    }
 
+    pub fn add_instances(&mut self, pass: usize, slice: &[V]) {
         if self.passes.len() <= pass {
             // We only need one more, but no harm in adding extra
             self.passes.resize(pass + 8, Default::default());
@@ -32,8 +32,10 @@ pub trait CustomPipeBuilder {
     /// Build a pipe
     ///
     /// A "common" bind group with layout `bgl_common` is available, supplying
-    /// window sizing and theme lighting information (refer to existing pipes
-    /// and shaders for usage). Usage is optional.
+    /// window sizing, pass transform and theme lighting information (refer to
+    /// existing pipes and shaders for usage). Usage is optional. Shaders
+    /// which ignore the `transform` and `translate` fields only support
+    /// offset (untransformed) passes.
     ///
     /// The given texture format should be used to construct a
     /// compatible [`wgpu::RenderPipeline`].
@@ -5,14 +5,16 @@
 
 //! Drawing API for `kas_wgpu`
 
+use std::any::Any;
 use std::f32::consts::FRAC_PI_2;
+use std::time::Instant;
 use wgpu::util::DeviceExt;
 
 use super::*;
-use kas::cast::Cast;
+use kas::cast::{Cast, Conv};
 use kas::draw::color::Rgba;
 use kas::draw::*;
-use kas::geom::{Quad, Rect, Size, Vec2};
+use kas::geom::{Offset, Quad, Rect, Size, Vec2};
 use kas::text::{Effect, TextDisplay};
 use kas_theme::DrawShadedImpl;
 
@@ -72,7 +74,9 @@ impl<C: CustomPipe> DrawPipe<C> {
         });
 
         let images = images::Images::new(&device, &shaders, &bgl_common);
+        let instanced_rect = instanced_rect::Pipeline::new(&device, &shaders, &bgl_common);
         let shaded_square = shaded_square::Pipeline::new(&device, &shaders, &bgl_common);
+        let gradient = gradient::Pipeline::new(&device, &shaders, &bgl_common);
         let shaded_round = shaded_round::Pipeline::new(&device, &shaders, &bgl_common);
         let flat_round = flat_round::Pipeline::new(&device, &shaders, &bgl_common);
         let round_2col = round_2col::Pipeline::new(&device, &shaders, &bgl_common);
@@ -88,7 +92,9 @@ impl<C: CustomPipe> DrawPipe<C> {
             light_norm_buf,
             bg_common: vec![],
             images,
+            instanced_rect,
             shaded_square,
+            gradient,
             shaded_round,
             flat_round,
             round_2col,
@@ -106,12 +112,16 @@ impl<C: CustomPipe> DrawPipe<C> {
             scale: Default::default(),
             clip_regions: vec![Default::default()],
             images: Default::default(),
+            bg_images: Default::default(),
+            instanced_rect: Default::default(),
             shaded_square: Default::default(),
+            gradient: Default::default(),
             shaded_round: Default::default(),
             flat_round: Default::default(),
             round_2col: Default::default(),
             custom,
             text: Default::default(),
+            stats: Default::default(),
         }
     }
 
@@ -137,17 +147,16 @@ impl<C: CustomPipe> DrawPipe<C> {
         frame_view: &wgpu::TextureView,
         clear_color: wgpu::Color,
     ) {
+        let start = Instant::now();
+
         // Update all bind groups. We use a separate bind group for each clip
         // region and update on each render, although they don't always change.
         // NOTE: we could use push constants instead.
-        let mut scale = window.scale;
-        let base_offset = (scale[0], scale[1]);
+        let scale = window.scale;
         for (region, bg) in window.clip_regions.iter().zip(self.bg_common.iter()) {
-            let offset = Vec2::from(region.1);
-            scale[0] = base_offset.0 - offset.0;
-            scale[1] = base_offset.1 - offset.1;
+            let uniform = pass_uniform(scale, &region.1);
             self.queue
-                .write_buffer(&bg.0, 0, bytemuck::cast_slice(&scale));
+                .write_buffer(&bg.0, 0, bytemuck::cast_slice(&uniform));
         }
         let device = &self.device;
         let bg_len = self.bg_common.len();
@@ -155,12 +164,10 @@ impl<C: CustomPipe> DrawPipe<C> {
             let (bgl_common, light_norm_buf) = (&self.bgl_common, &self.light_norm_buf);
             self.bg_common
                 .extend(window.clip_regions[bg_len..].iter().map(|region| {
-                    let offset = Vec2::from(region.1);
-                    scale[0] = base_offset.0 - offset.0;
-                    scale[1] = base_offset.1 - offset.1;
+                    let uniform = pass_uniform(scale, &region.1);
                     let scale_buf = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
                         label: Some("scale_buf"),
-                        contents: bytemuck::cast_slice(&scale),
+                        contents: bytemuck::cast_slice(&uniform),
                         usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
                     });
                     let bg_common = device.create_bind_group(&wgpu::BindGroupDescriptor {
@@ -202,9 +209,21 @@ impl<C: CustomPipe> DrawPipe<C> {
             &mut self.staging_belt,
             &mut encoder,
         );
+        self.images.prepare(
+            &mut window.bg_images,
+            &self.device,
+            &mut self.staging_belt,
+            &mut encoder,
+        );
+        window
+            .instanced_rect
+            .write_buffers(&self.device, &mut self.staging_belt, &mut encoder);
         window
             .shaded_square
             .write_buffers(&self.device, &mut self.staging_belt, &mut encoder);
+        window
+            .gradient
+            .write_buffers(&self.device, &mut self.staging_belt, &mut encoder);
         window
             .shaded_round
             .write_buffers(&self.device, &mut self.staging_belt, &mut encoder);
@@ -225,6 +244,8 @@ impl<C: CustomPipe> DrawPipe<C> {
             .text
             .write_buffers(&self.device, &mut self.staging_belt, &mut encoder);
 
+        window.stats = self.stats(window);
+
         let mut color_attachments = [wgpu::RenderPassColorAttachment {
             view: frame_view,
             resolve_target: None,
@@ -234,8 +255,13 @@ impl<C: CustomPipe> DrawPipe<C> {
             },
         }];
 
+        // Elevated passes are drawn last (a stable sort preserves order otherwise)
+        let mut order: Vec<usize> = (0..window.clip_regions.len()).collect();
+        order.sort_by_key(|pass| window.clip_regions[*pass].2);
+
         // We use a separate render pass for each clipped region.
-        for (pass, (rect, _)) in window.clip_regions.iter().enumerate() {
+        for pass in order {
+            let rect = window.clip_regions[pass].0;
             if rect.size.0 == 0 || rect.size.1 == 0 {
                 continue;
             }
@@ -254,8 +280,15 @@ impl<C: CustomPipe> DrawPipe<C> {
                     rect.size.1.cast(),
                 );
 
+                self.gradient
+                    .render(&window.gradient, pass, &mut rpass, bg_common);
                 self.round_2col
                     .render(&window.round_2col, pass, &mut rpass, bg_common);
+                // Cached theme elements are drawn over their shadows
+                self.images
+                    .render(&window.bg_images, pass, &mut rpass, bg_common);
+                self.instanced_rect
+                    .render(&window.instanced_rect, pass, &mut rpass, bg_common);
                 self.shaded_square
                     .render(&window.shaded_square, pass, &mut rpass, bg_common);
                 self.images
@@ -299,6 +332,131 @@ impl<C: CustomPipe> DrawPipe<C> {
             .spawn(self.staging_belt.recall())
             .expect("Recall staging belt");
         self.local_pool.run_until_stalled();
+
+        window.stats.cpu_time = start.elapsed();
+    }
+
+    /// Render batched draw instructions to an image
+    ///
+    /// Renders to an offscreen texture of the window's size (see
+    /// [`Self::resize`]), then reads back the result. Returns the image size
+    /// and RGBA (sRGB) data, or `None` if read-back fails.
+    pub fn render_image(
+        &mut self,
+        window: &mut DrawWindow<C::Window>,
+        clear_color: wgpu::Color,
+    ) -> Option<((u32, u32), Vec<u8>)> {
+        let size = window.clip_regions[0].0.size;
+        let (width, height): (u32, u32) = (size.0.cast(), size.1.cast());
+        let extent = wgpu::Extent3d {
+            width,
+            height,
+            depth_or_array_layers: 1,
+        };
+        let texture = self.device.create_texture(&wgpu::TextureDescriptor {
+            label: Some("offscreen target"),
+            size: extent,
+            mip_level_count: 1,
+            sample_count: 1,
+            dimension: wgpu::TextureDimension::D2,
+            format: RENDER_TEX_FORMAT,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+        });
+        let view = texture.create_view(&Default::default());
+        self.render(window, &view, clear_color);
+
+        // Rows of the copy destination must be aligned
+        let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT;
+        let row_len = 4 * width;
+        let padded_row_len = (row_len + align - 1) / align * align;
+        let buffer = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("offscreen readback"),
+            size: u64::from(padded_row_len) * u64::from(height),
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("offscreen readback"),
+            });
+        encoder.copy_texture_to_buffer(
+            wgpu::ImageCopyTexture {
+                texture: &texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::ImageCopyBuffer {
+                buffer: &buffer,
+                layout: wgpu::ImageDataLayout {
+                    offset: 0,
+                    bytes_per_row: std::num::NonZeroU32::new(padded_row_len),
+                    rows_per_image: None,
+                },
+            },
+            extent,
+        );
+        self.queue.submit(std::iter::once(encoder.finish()));
+
+        let slice = buffer.slice(..);
+        let map = slice.map_async(wgpu::MapMode::Read);
+        self.device.poll(wgpu::Maintain::Wait);
+        let mut data = Vec::with_capacity(usize::conv(row_len) * usize::conv(height));
+        if let Err(e) = futures::executor::block_on(map) {
+            log::error!("render_image: failed to map buffer: {}", e);
+            return None;
+        }
+        {
+            let mapped = slice.get_mapped_range();
+            for row in mapped.chunks(usize::conv(padded_row_len)) {
+                // Convert from BGRA (RENDER_TEX_FORMAT) to RGBA
+                for px in row[..usize::conv(row_len)].chunks_exact(4) {
+                    data.extend_from_slice(&[px[2], px[1], px[0], px[3]]);
+                }
+            }
+        }
+        buffer.unmap();
+
+        Some(((width, height), data))
+    }
+
+    /// Collect statistics (after buffers are written)
+    fn stats(&self, window: &DrawWindow<C::Window>) -> RenderStats {
+        let vertex_pipe = |name, (vertices, draw_calls): (u32, u32)| PipeStats {
+            name,
+            vertices,
+            instances: 0,
+            draw_calls,
+        };
+        let instance_pipe = |name, (instances, draw_calls): (u32, u32)| PipeStats {
+            name,
+            vertices: 4 * instances,
+            instances,
+            draw_calls,
+        };
+        let (glyphs, color_glyphs) = window.text.stats();
+        let pipes = vec![
+            vertex_pipe("round_2col", window.round_2col.stats()),
+            vertex_pipe("gradient", window.gradient.stats()),
+            instance_pipe("bg_images", window.bg_images.stats()),
+            instance_pipe("instanced_rect", window.instanced_rect.stats()),
+            vertex_pipe("shaded_square", window.shaded_square.stats()),
+            instance_pipe("images", window.images.stats()),
+            vertex_pipe("shaded_round", window.shaded_round.stats()),
+            vertex_pipe("flat_round", window.flat_round.stats()),
+            instance_pipe("glyphs", glyphs),
+            instance_pipe("color_glyphs", color_glyphs),
+        ];
+        let (glyph_atlases, color_glyph_atlases) = self.text.stats();
+        RenderStats {
+            passes: window.clip_regions.len().cast(),
+            pipes,
+            image_atlases: self.images.stats(),
+            glyph_atlases,
+            color_glyph_atlases,
+            cpu_time: Default::default(),
+        }
     }
 }
 
@@ -316,6 +474,17 @@ impl<C: CustomPipe> DrawSharedImpl for DrawPipe<C> {
             .upload(&self.device, &self.queue, id, data, format);
     }
 
+    fn import_texture(
+        &mut self,
+        texture: Box<dyn Any>,
+        size: (u32, u32),
+    ) -> Result<ImageId, ImageError> {
+        match texture.downcast::<wgpu::Texture>() {
+            Ok(texture) => self.images.import(&self.device, *texture, size),
+            Err(_) => Err(ImageError::Unsupported),
+        }
+    }
+
     #[inline]
     fn image_free(&mut self, id: ImageId) {
         self.images.free(id);
@@ -333,6 +502,13 @@ impl<C: CustomPipe> DrawSharedImpl for DrawPipe<C> {
         };
     }
 
+    #[inline]
+    fn draw_image_background(&self, draw: &mut Self::Draw, pass: PassId, id: ImageId, rect: Quad) {
+        if let Some((atlas, tex)) = self.images.get_im_atlas_coords(id) {
+            draw.bg_images.rect(pass, atlas, tex, rect);
+        };
+    }
+
     #[inline]
     fn draw_text(
         &mut self,
@@ -389,24 +565,37 @@ impl<CW: CustomWindow> DrawImpl for DrawWindow<CW> {
         parent_pass: PassId,
         rect: Rect,
         offset: Offset,
+        transform: Affine,
         class: PassType,
     ) -> PassId {
-        let parent = match class {
-            PassType::Clip => &self.clip_regions[parent_pass.pass()],
-            PassType::Overlay => &self.clip_regions[0],
+        let base = &self.clip_regions[parent_pass.pass()];
+        let window = &self.clip_regions[0];
+        let mut elevation = base.2;
+        let (parent, clip) = match class {
+            PassType::Clip => (base, base.0),
+            PassType::Overlay => (window, window.0),
+            PassType::Elevated => {
+                elevation += 1;
+                (base, window.0)
+            }
         };
-        let rect = rect - parent.1;
-        let offset = offset + parent.1;
-        let rect = rect.intersection(&parent.0).unwrap_or(Rect::ZERO);
+        let rect = bounding_rect(parent.1.transform_bounds(rect.into()));
+        let rect = rect.intersection(&clip).unwrap_or(Rect::ZERO);
+        let transform = Affine::translate(-Vec2::from(offset))
+            .then(transform)
+            .then(parent.1);
         let pass = self.clip_regions.len().cast();
-        self.clip_regions.push((rect, offset));
+        self.clip_regions.push((rect, transform, elevation));
         PassId::new(pass)
     }
 
     #[inline]
     fn get_clip_rect(&self, pass: PassId) -> Rect {
         let region = &self.clip_regions[pass.pass()];
-        region.0 + region.1
+        match region.1.inverse() {
+            Some(inv) => bounding_rect(inv.transform_bounds(region.0.into())),
+            None => Rect::ZERO,
+        }
     }
 
     #[inline]
@@ -414,10 +603,38 @@ impl<CW: CustomWindow> DrawImpl for DrawWindow<CW> {
         self.shaded_square.rect(pass, rect, col);
     }
 
+    #[inline]
+    fn rect_instances(&mut self, pass: PassId, size: Vec2, positions: &[Vec2], col: Rgba) {
+        self.instanced_rect.rects(pass, size, positions, col);
+    }
+
     #[inline]
     fn frame(&mut self, pass: PassId, outer: Quad, inner: Quad, col: Rgba) {
         self.shaded_square.frame(pass, outer, inner, col);
     }
+
+    #[inline]
+    fn frame_styled(
+        &mut self,
+        pass: PassId,
+        outer: Quad,
+        inner: Quad,
+        style: StrokeStyle,
+        col: Rgba,
+    ) {
+        self.shaded_square
+            .frame_styled(pass, outer, inner, style, col);
+    }
+
+    #[inline]
+    fn rect_grad(&mut self, pass: PassId, rect: Quad, grad: Gradient) {
+        self.gradient.rect(pass, rect, grad);
+    }
+
+    #[inline]
+    fn frame_grad(&mut self, pass: PassId, outer: Quad, inner: Quad, grad: Gradient) {
+        self.gradient.frame(pass, outer, inner, grad);
+    }
 }
 
 impl<CW: CustomWindow> DrawRoundedImpl for DrawWindow<CW> {
@@ -426,6 +643,20 @@ impl<CW: CustomWindow> DrawRoundedImpl for DrawWindow<CW> {
         self.flat_round.line(pass, p1, p2, radius, col);
     }
 
+    #[inline]
+    fn rounded_line_styled(
+        &mut self,
+        pass: PassId,
+        p1: Vec2,
+        p2: Vec2,
+        radius: f32,
+        style: StrokeStyle,
+        col: Rgba,
+    ) {
+        self.flat_round
+            .line_styled(pass, p1, p2, radius, style, col);
+    }
+
     #[inline]
     fn circle(&mut self, pass: PassId, rect: Quad, inner_radius: f32, col: Rgba) {
         self.flat_round.circle(pass, rect, inner_radius, col);
@@ -447,6 +678,20 @@ impl<CW: CustomWindow> DrawRoundedImpl for DrawWindow<CW> {
     }
 }
 
+impl<CW: CustomWindow> DrawPathImpl for DrawWindow<CW> {
+    fn polygon(&mut self, pass: PassId, points: &[Vec2], col: Rgba) {
+        let mut triangles = vec![];
+        path::polygon(points, &mut triangles);
+        self.shaded_square.triangles(pass, &triangles, col);
+    }
+
+    fn polyline(&mut self, pass: PassId, points: &[Vec2], width: f32, join: LineJoin, col: Rgba) {
+        let mut triangles = vec![];
+        path::polyline(points, width, join, &mut triangles);
+        self.shaded_square.triangles(pass, &triangles, col);
+    }
+}
+
 impl<CW: CustomWindow> DrawShadedImpl for DrawWindow<CW> {
     #[inline]
     fn shaded_square(&mut self, pass: PassId, rect: Quad, norm: (f32, f32), col: Rgba) {
@@ -486,3 +731,29 @@ impl<CW: CustomWindow> DrawShadedImpl for DrawWindow<CW> {
             .shaded_frame(pass, outer, inner, Vec2::from(norm), col);
     }
 }
+
+/// Compute vertex uniform data for a pass
+///
+/// The vertex shader computes `scale * (transform * (pos + offset) + translate)`
+/// where `transform` is the linear part of the pass's window transform.
+#[rustfmt::skip]
+fn pass_uniform(scale: Scale, transform: &Affine) -> PassUniform {
+    let base_offset = Vec2(scale[0], scale[1]);
+    let offset = transform.t + base_offset;
+    let translate = offset - transform.transform_vector(offset);
+    let (a, b) = (transform.a, transform.b);
+    [
+        offset.0, offset.1, scale[2], scale[3],
+        a.0, b.0, a.1, b.1,
+        translate.0, translate.1, 0.0, 0.0,
+    ]
+}
+
+/// Smallest pixel-aligned rect containing `quad`
+fn bounding_rect(quad: Quad) -> Rect {
+    // Limit to a range safely representable as `i32`
+    let (min, max) = (Vec2::splat(-1.0e9), Vec2::splat(1.0e9));
+    let a = quad.a.floor().max(min).min(max);
+    let b = quad.b.ceil().max(min).min(max);
+    Rect::new(a.into(), (b - a).max(Vec2::ZERO).into())
+}
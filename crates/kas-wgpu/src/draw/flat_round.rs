@@ -7,7 +7,7 @@
 
 use super::common;
 use crate::draw::ShaderManager;
-use kas::draw::{color::Rgba, PassId};
+use kas::draw::{color::Rgba, PassId, StrokeStyle};
 use kas::geom::{Quad, Vec2};
 use std::mem::size_of;
 
@@ -173,6 +173,36 @@ impl Window {
         ]);
     }
 
+    /// Add a dashed or dotted line to the buffer
+    pub fn line_styled(
+        &mut self,
+        pass: PassId,
+        p1: Vec2,
+        p2: Vec2,
+        radius: f32,
+        style: StrokeStyle,
+        col: Rgba,
+    ) {
+        if style == StrokeStyle::Solid {
+            return self.line(pass, p1, p2, radius, col);
+        }
+
+        let v = p2 - p1;
+        let len = v.sum_square().sqrt();
+        let u = if len > 0.0 { v / len } else { Vec2::ZERO };
+        // Pattern positions are measured from the outside of the first end
+        style.for_each_dash(2.0 * radius, 0.0, len + 2.0 * radius, |a, b| {
+            if b - a > 2.0 * radius {
+                let (a, b) = (p1 + u * a, p1 + u * (b - 2.0 * radius));
+                self.line(pass, a, b, radius, col);
+            } else {
+                let p = p1 + u * (0.5 * (a + b) - radius);
+                let rect = Quad::from_coords(p - radius, p + radius);
+                self.circle(pass, rect, 0.0, col);
+            }
+        });
+    }
+
     /// Bounds on input: `0 ≤ inner_radius ≤ 1`.
     pub fn circle(&mut self, pass: PassId, rect: Quad, inner_radius: f32, col: Rgba) {
         let aa = rect.a;
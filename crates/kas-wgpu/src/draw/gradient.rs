@@ -0,0 +1,204 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Gradient-fill pipeline
+
+use super::common;
+use crate::draw::ShaderManager;
+use kas::draw::{color::Rgba, Gradient, PassId};
+use kas::geom::{Quad, Vec2};
+use std::mem::size_of;
+
+/// Vertex
+///
+/// -   `screen_pos: Vec2` — screen coordinate
+/// -   `col1: Rgba` — colour at gradient start
+/// -   `col2: Rgba` — colour at gradient end
+/// -   `param: Vec2` — linear: position along gradient (`x` only); radial:
+///     position relative to center, scaled by radius
+/// -   `radial: f32` — 1 if radial, 0 if linear
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct Vertex(Vec2, Rgba, Rgba, Vec2, f32);
+unsafe impl bytemuck::Zeroable for Vertex {}
+unsafe impl bytemuck::Pod for Vertex {}
+
+impl Vertex {
+    fn new(pos: Vec2, grad: &Gradient) -> Self {
+        match *grad {
+            Gradient::Linear {
+                start,
+                end,
+                col1,
+                col2,
+            } => {
+                let d = end - start;
+                let len2 = d.sum_square();
+                let t = if len2 > 0.0 {
+                    ((pos - start) * d).sum() / len2
+                } else {
+                    0.0
+                };
+                Vertex(pos, col1, col2, Vec2(t, 0.0), 0.0)
+            }
+            Gradient::Radial {
+                center,
+                radius,
+                col1,
+                col2,
+            } => {
+                let radius = radius.max(Vec2::splat(f32::EPSILON));
+                Vertex(pos, col1, col2, (pos - center) / radius, 1.0)
+            }
+        }
+    }
+}
+
+pub type Window = common::Window<Vertex>;
+
+/// A pipeline for rendering gradient fills
+///
+/// Does not use anti-aliasing since edges are usually pixel-aligned.
+pub struct Pipeline {
+    render_pipeline: wgpu::RenderPipeline,
+}
+
+impl Pipeline {
+    /// Construct
+    pub fn new(
+        device: &wgpu::Device,
+        shaders: &ShaderManager,
+        bgl_common: &wgpu::BindGroupLayout,
+    ) -> Self {
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("GR pipeline_layout"),
+            bind_group_layouts: &[bgl_common],
+            push_constant_ranges: &[],
+        });
+
+        let render_pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("GR render_pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shaders.vert_gradient,
+                entry_point: "main",
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: size_of::<Vertex>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Vertex,
+                    attributes: &wgpu::vertex_attr_array![
+                        0 => Float32x2,
+                        1 => Float32x4,
+                        2 => Float32x4,
+                        3 => Float32x2,
+                        4 => Float32,
+                    ],
+                }],
+            },
+            primitive: wgpu::PrimitiveState {
+                topology: wgpu::PrimitiveTopology::TriangleList,
+                strip_index_format: None,
+                front_face: wgpu::FrontFace::Cw,
+                cull_mode: Some(wgpu::Face::Back), // not required
+                clamp_depth: false,
+                polygon_mode: wgpu::PolygonMode::Fill,
+                conservative: false,
+            },
+            depth_stencil: None,
+            multisample: Default::default(),
+            fragment: Some(wgpu::FragmentState {
+                module: &shaders.frag_gradient,
+                entry_point: "main",
+                targets: &[wgpu::ColorTargetState {
+                    format: super::RENDER_TEX_FORMAT,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                }],
+            }),
+        });
+
+        Pipeline { render_pipeline }
+    }
+
+    /// Enqueue render commands
+    pub fn render<'a>(
+        &'a self,
+        window: &'a Window,
+        pass: usize,
+        rpass: &mut wgpu::RenderPass<'a>,
+        bg_common: &'a wgpu::BindGroup,
+    ) {
+        window.render(pass, rpass, &self.render_pipeline, bg_common);
+    }
+}
+
+impl Window {
+    /// Add a rectangle to the buffer
+    pub fn rect(&mut self, pass: PassId, rect: Quad, grad: Gradient) {
+        let aa = rect.a;
+        let bb = rect.b;
+
+        if !aa.lt(bb) {
+            // zero / negative size: nothing to draw
+            return;
+        }
+
+        let ab = Vec2(aa.0, bb.1);
+        let ba = Vec2(bb.0, aa.1);
+
+        let v = |pos| Vertex::new(pos, &grad);
+        #[rustfmt::skip]
+        self.add_vertices(pass.pass(), &[
+            v(aa), v(ba), v(ab),
+            v(ab), v(ba), v(bb),
+        ]);
+    }
+
+    /// Add a frame to the buffer, defined by two outer corners, `aa` and `bb`,
+    /// and two inner corners, `cc` and `dd`.
+    ///
+    /// Bounds on input: `aa < cc < dd < bb`.
+    pub fn frame(&mut self, pass: PassId, outer: Quad, inner: Quad, grad: Gradient) {
+        let aa = outer.a;
+        let bb = outer.b;
+        let mut cc = inner.a;
+        let mut dd = inner.b;
+
+        if !aa.lt(bb) {
+            // zero / negative size: nothing to draw
+            return;
+        }
+        if !aa.le(cc) || !cc.le(bb) {
+            cc = aa;
+        }
+        if !aa.le(dd) || !dd.le(bb) {
+            dd = bb;
+        }
+        if !cc.le(dd) {
+            dd = cc;
+        }
+
+        let ab = Vec2(aa.0, bb.1);
+        let ba = Vec2(bb.0, aa.1);
+        let cd = Vec2(cc.0, dd.1);
+        let dc = Vec2(dd.0, cc.1);
+
+        let v = |pos| Vertex::new(pos, &grad);
+        #[rustfmt::skip]
+        self.add_vertices(pass.pass(), &[
+            // top bar: ba - dc - cc - aa
+            v(ba), v(dc), v(aa),
+            v(aa), v(dc), v(cc),
+            // left bar: aa - cc - cd - ab
+            v(aa), v(cc), v(ab),
+            v(ab), v(cc), v(cd),
+            // bottom bar: ab - cd - dd - bb
+            v(ab), v(cd), v(bb),
+            v(bb), v(cd), v(dd),
+            // right bar: bb - dd - dc - ba
+            v(bb), v(dd), v(ba),
+            v(ba), v(dd), v(dc),
+        ]);
+    }
+}
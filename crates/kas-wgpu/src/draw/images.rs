@@ -10,7 +10,7 @@ use std::collections::HashMap;
 use std::mem::size_of;
 use std::num::NonZeroU32;
 
-use super::{atlases, ShaderManager};
+use super::{atlases, stats::AtlasStats, ShaderManager};
 use kas::cast::Conv;
 use kas::draw::{ImageError, ImageFormat, ImageId, PassId};
 use kas::geom::{Quad, Vec2};
@@ -18,7 +18,8 @@ use kas::geom::{Quad, Vec2};
 #[derive(Debug)]
 struct Image {
     atlas: u32,
-    alloc: AllocId,
+    // None for imported textures
+    alloc: Option<AllocId>,
     size: (u32, u32),
     origin: (u32, u32),
     tex_quad: Quad,
@@ -92,6 +93,7 @@ impl Images {
             bgl_common,
             2048,
             wgpu::TextureFormat::Rgba8UnormSrgb,
+            wgpu::FilterMode::Nearest,
             wgpu::VertexState {
                 module: &shaders.vert_image,
                 entry_point: "main",
@@ -135,7 +137,7 @@ impl Images {
         let (atlas, alloc, origin, tex_quad) = self.atlas_pipe.allocate(size)?;
         let image = Image {
             atlas,
-            alloc,
+            alloc: Some(alloc),
             size,
             origin,
             tex_quad,
@@ -144,6 +146,26 @@ impl Images {
         Ok(id)
     }
 
+    /// Import an existing texture
+    pub fn import(
+        &mut self,
+        device: &wgpu::Device,
+        texture: wgpu::Texture,
+        size: (u32, u32),
+    ) -> Result<ImageId, ImageError> {
+        let (atlas, tex_quad) = self.atlas_pipe.import(device, texture, size)?;
+        let id = self.next_image_id();
+        let image = Image {
+            atlas,
+            alloc: None,
+            size,
+            origin: (0, 0),
+            tex_quad,
+        };
+        self.images.insert(id, image);
+        Ok(id)
+    }
+
     /// Upload an image to the GPU
     pub fn upload(
         &mut self,
@@ -161,6 +183,10 @@ impl Images {
         }
 
         if let Some(image) = self.images.get_mut(&id) {
+            if image.alloc.is_none() {
+                log::warn!("Images::upload: cannot upload to imported texture");
+                return;
+            }
             image.upload(&self.atlas_pipe, queue, data);
         }
     }
@@ -168,7 +194,10 @@ impl Images {
     /// Free an image allocation
     pub fn free(&mut self, id: ImageId) {
         if let Some(im) = self.images.remove(&id) {
-            self.atlas_pipe.deallocate(im.atlas, im.alloc);
+            match im.alloc {
+                Some(alloc) => self.atlas_pipe.deallocate(im.atlas, alloc, im.size),
+                None => self.atlas_pipe.remove_import(im.atlas),
+            }
         }
     }
 
@@ -188,6 +217,11 @@ impl Images {
         window.write_buffers(device, staging_belt, encoder);
     }
 
+    /// Get atlas occupancy
+    pub fn stats(&self) -> AtlasStats {
+        self.atlas_pipe.stats()
+    }
+
     /// Get atlas and texture coordinates for an image
     pub fn get_im_atlas_coords(&self, id: ImageId) -> Option<(u32, Quad)> {
         self.images.get(&id).map(|im| (im.atlas, im.tex_quad))
@@ -222,6 +256,11 @@ impl Window {
         self.atlas.write_buffers(device, staging_belt, encoder);
     }
 
+    /// Get `(instances, draw_calls)`
+    pub fn stats(&self) -> (u32, u32) {
+        self.atlas.stats()
+    }
+
     /// Add a rectangle to the buffer
     pub fn rect(&mut self, pass: PassId, atlas: u32, tex: Quad, rect: Quad) {
         if !rect.a.lt(rect.b) {
@@ -8,23 +8,29 @@
 //! Extensions to the API of [`kas::draw`], plus some utility types.
 
 mod atlases;
+mod color_glyphs;
 mod common;
 mod custom;
 mod draw_pipe;
 mod flat_round;
+mod gradient;
 mod images;
+mod instanced_rect;
+pub(crate) mod path;
 mod round_2col;
 mod shaded_round;
 mod shaded_square;
 mod shaders;
+mod stats;
 mod text_pipe;
 
-use kas::draw::AnimationState;
-use kas::geom::{Offset, Rect};
+use kas::draw::{Affine, AnimationState};
+use kas::geom::Rect;
 use shaders::ShaderManager;
 use wgpu::TextureFormat;
 
 pub use custom::{CustomPipe, CustomPipeBuilder, CustomWindow, DrawCustom};
+pub use stats::{AtlasStats, PipeStats, RenderStats};
 
 /// Output format
 ///
@@ -34,6 +40,10 @@ pub(crate) const RENDER_TEX_FORMAT: TextureFormat = TextureFormat::Bgra8UnormSrg
 
 type Scale = [f32; 4];
 
+/// Per-pass vertex uniform: `offset`, `scale`, `transform` and `translate`
+/// (see `VertexCommon` in shaders), padded to a multiple of 16 bytes
+type PassUniform = [f32; 12];
+
 /// Shared pipeline data
 pub struct DrawPipe<C> {
     pub(crate) device: wgpu::Device,
@@ -44,7 +54,9 @@ pub struct DrawPipe<C> {
     light_norm_buf: wgpu::Buffer,
     bg_common: Vec<(wgpu::Buffer, wgpu::BindGroup)>,
     images: images::Images,
+    instanced_rect: instanced_rect::Pipeline,
     shaded_square: shaded_square::Pipeline,
+    gradient: gradient::Pipeline,
     shaded_round: shaded_round::Pipeline,
     flat_round: flat_round::Pipeline,
     round_2col: round_2col::Pipeline,
@@ -56,12 +68,27 @@ pub struct DrawPipe<C> {
 pub struct DrawWindow<CW: CustomWindow> {
     pub(crate) animation: AnimationState,
     scale: Scale,
-    clip_regions: Vec<(Rect, Offset)>,
+    // Clip rect, transform and elevation of each pass
+    clip_regions: Vec<(Rect, Affine, u32)>,
     images: images::Window,
+    bg_images: images::Window,
+    instanced_rect: instanced_rect::Window,
     shaded_square: shaded_square::Window,
+    gradient: gradient::Window,
     shaded_round: shaded_round::Window,
     flat_round: flat_round::Window,
     round_2col: round_2col::Window,
     custom: CW,
     pub(crate) text: text_pipe::Window,
+    stats: RenderStats,
+}
+
+impl<CW: CustomWindow> DrawWindow<CW> {
+    /// Get statistics concerning the last frame rendered
+    ///
+    /// This may be accessed from a widget's draw method via
+    /// [`kas::draw::DrawIface::downcast_from`].
+    pub fn render_stats(&self) -> &RenderStats {
+        &self.stats
+    }
 }
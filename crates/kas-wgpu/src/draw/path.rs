@@ -0,0 +1,276 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Triangulation of polygons and polylines
+//!
+//! Output triangles are appended to a list of vertices (three per triangle)
+//! in arbitrary winding order.
+
+use kas::cast::{Conv, ConvFloat};
+use kas::draw::LineJoin;
+use kas::geom::Vec2;
+use std::f32::consts::PI;
+
+/// Miter joins longer than this multiple of the half-width use a bevel
+const MITER_LIMIT: f32 = 4.0;
+
+/// Points closer than this are considered equal
+const EPSILON: f32 = 1e-4;
+
+#[inline]
+fn cross(a: Vec2, b: Vec2) -> f32 {
+    a.0 * b.1 - a.1 * b.0
+}
+
+#[inline]
+fn dot(a: Vec2, b: Vec2) -> f32 {
+    (a * b).sum()
+}
+
+#[inline]
+fn len(a: Vec2) -> f32 {
+    a.sum_square().sqrt()
+}
+
+// Remove consecutive duplicate points
+fn dedup(points: &[Vec2]) -> Vec<Vec2> {
+    let mut v: Vec<Vec2> = Vec::with_capacity(points.len());
+    for p in points {
+        if v.last()
+            .map(|q| (*p - *q).max_abs_comp() > EPSILON)
+            .unwrap_or(true)
+        {
+            v.push(*p);
+        }
+    }
+    v
+}
+
+/// Triangulate a simple polygon by ear clipping
+///
+/// The polygon may be concave. Self-intersecting polygons are only partially
+/// filled.
+pub fn polygon(points: &[Vec2], out: &mut Vec<Vec2>) {
+    let mut points = dedup(points);
+    if points.len() > 1 && (points[0] - points[points.len() - 1]).max_abs_comp() <= EPSILON {
+        points.pop();
+    }
+    let n = points.len();
+    if n < 3 {
+        return;
+    }
+
+    // Twice the signed area gives the orientation
+    let area: f32 = (0..n).map(|i| cross(points[i], points[(i + 1) % n])).sum();
+    if area == 0.0 {
+        return;
+    }
+    let sign = area.signum();
+
+    let mut index: Vec<usize> = (0..n).collect();
+    let mut i = 0;
+    let mut fails = 0;
+    while index.len() > 3 {
+        let m = index.len();
+        let (a, b, c) = (index[(i + m - 1) % m], index[i], index[(i + 1) % m]);
+        let (pa, pb, pc) = (points[a], points[b], points[c]);
+
+        let convex = cross(pb - pa, pc - pb) * sign > 0.0;
+        let is_ear = convex
+            && !index
+                .iter()
+                .any(|&j| j != a && j != b && j != c && in_triangle(points[j], pa, pb, pc, sign));
+
+        if is_ear {
+            out.extend_from_slice(&[pa, pb, pc]);
+            index.remove(i);
+            if i >= index.len() {
+                i = 0;
+            }
+            fails = 0;
+        } else {
+            i = (i + 1) % m;
+            fails += 1;
+            if fails > m {
+                // No ear found: polygon is self-intersecting or degenerate
+                return;
+            }
+        }
+    }
+    out.extend(index.iter().map(|i| points[*i]));
+
+    fn in_triangle(p: Vec2, a: Vec2, b: Vec2, c: Vec2, sign: f32) -> bool {
+        cross(b - a, p - a) * sign >= 0.0
+            && cross(c - b, p - b) * sign >= 0.0
+            && cross(a - c, p - c) * sign >= 0.0
+    }
+}
+
+/// Triangulate a line of the given `width` through `points`
+pub fn polyline(points: &[Vec2], width: f32, join: LineJoin, out: &mut Vec<Vec2>) {
+    let points = dedup(points);
+    let hw = 0.5 * width;
+    if points.len() < 2 || hw.is_nan() || hw <= 0.0 {
+        return;
+    }
+
+    // Offset to the "left" of each segment
+    let normal = |a: Vec2, b: Vec2| {
+        let d = b - a;
+        Vec2(-d.1, d.0) * (hw / len(d))
+    };
+
+    for i in 0..points.len() - 1 {
+        let (a, b) = (points[i], points[i + 1]);
+        let n = normal(a, b);
+        out.extend_from_slice(&[a + n, b + n, a - n, a - n, b + n, b - n]);
+
+        if i + 2 < points.len() {
+            let c = points[i + 2];
+            let turn = cross(b - a, c - b);
+            if turn.abs() <= EPSILON * len(b - a) * len(c - b) && dot(b - a, c - b) > 0.0 {
+                continue; // straight: no join required
+            }
+            // The join is on the outside of the turn
+            let s = if turn > 0.0 { -1.0 } else { 1.0 };
+            let o0 = n * s;
+            let o1 = normal(b, c) * s;
+            add_join(b, o0, o1, hw, join, out);
+        }
+    }
+}
+
+fn add_join(p: Vec2, o0: Vec2, o1: Vec2, hw: f32, join: LineJoin, out: &mut Vec<Vec2>) {
+    match join {
+        LineJoin::Miter => {
+            let mid = o0 + o1;
+            let mid_len = len(mid);
+            if mid_len > EPSILON {
+                let m = mid / mid_len;
+                // Distance to miter tip is hw / cos(half-angle)
+                let cos_half = dot(m, o0) / hw;
+                if cos_half * MITER_LIMIT > 1.0 {
+                    let tip = p + m * (hw / cos_half);
+                    out.extend_from_slice(&[p, p + o0, tip, p, tip, p + o1]);
+                    return;
+                }
+            }
+            out.extend_from_slice(&[p, p + o0, p + o1]);
+        }
+        LineJoin::Bevel => {
+            out.extend_from_slice(&[p, p + o0, p + o1]);
+        }
+        LineJoin::Round => {
+            let angle = (dot(o0, o1) / (hw * hw)).clamp(-1.0, 1.0).acos();
+            let angle = if cross(o0, o1) < 0.0 { -angle } else { angle };
+            // Steps are chosen such that deviation from a circle is small
+            let max_step = (1.0 - 0.25 / hw.max(0.25)).clamp(-1.0, 1.0).acos() * 2.0;
+            let steps = u32::conv_nearest((angle.abs() / max_step.max(PI / 32.0)).ceil()).max(1);
+            let step = angle / f32::conv(steps);
+            let rot = Vec2(step.cos(), step.sin());
+            let mut v = o0;
+            for _ in 0..steps {
+                let next = v.complex_mul(rot);
+                out.extend_from_slice(&[p, p + v, p + next]);
+                v = next;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // Total area of triangles (overlapping areas are counted repeatedly)
+    fn area(triangles: &[Vec2]) -> f32 {
+        assert_eq!(triangles.len() % 3, 0);
+        let area = |t: &[Vec2]| 0.5 * cross(t[1] - t[0], t[2] - t[0]).abs();
+        triangles.chunks(3).map(area).sum()
+    }
+
+    fn approx_eq(a: f32, b: f32) -> bool {
+        (a - b).abs() < 1e-4
+    }
+
+    fn square() -> Vec<Vec2> {
+        vec![
+            Vec2(0.0, 0.0),
+            Vec2(2.0, 0.0),
+            Vec2(2.0, 2.0),
+            Vec2(0.0, 2.0),
+        ]
+    }
+
+    #[test]
+    fn polygon_convex() {
+        let mut out = vec![];
+        polygon(&square(), &mut out);
+        assert_eq!(out.len(), 6);
+        assert!(approx_eq(area(&out), 4.0));
+
+        // Explicit closure and reversed winding are allowed
+        let mut points = square();
+        points.push(points[0]);
+        points.reverse();
+        let mut out = vec![];
+        polygon(&points, &mut out);
+        assert_eq!(out.len(), 6);
+        assert!(approx_eq(area(&out), 4.0));
+    }
+
+    #[test]
+    fn polygon_concave() {
+        let points = [
+            Vec2(0.0, 0.0),
+            Vec2(4.0, 0.0),
+            Vec2(4.0, 4.0),
+            Vec2(2.0, 1.0),
+            Vec2(0.0, 4.0),
+        ];
+        let mut out = vec![];
+        polygon(&points, &mut out);
+        assert_eq!(out.len(), 9);
+        // Triangles do not overlap or cover the notch
+        assert!(approx_eq(area(&out), 10.0));
+    }
+
+    #[test]
+    fn polygon_degenerate() {
+        let mut out = vec![];
+        polygon(&[Vec2(0.0, 0.0), Vec2(1.0, 1.0), Vec2(2.0, 2.0)], &mut out);
+        polygon(&[Vec2(0.0, 0.0), Vec2(2.0, 0.0), Vec2(1.0, 0.0)], &mut out);
+        polygon(&[Vec2(0.0, 0.0), Vec2(0.0, 0.0), Vec2(1.0, 1.0)], &mut out);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn closed_stroke() {
+        // A closed square as passed by DrawPath::stroke_path
+        let mut points = square();
+        points.push(points[0]);
+        let mid = (points[0] + points[1]) * 0.5;
+        points[0] = mid;
+        points.push(mid);
+
+        let mut out = vec![];
+        polyline(&points, 0.5, LineJoin::Bevel, &mut out);
+        // Five segments and four corner joins
+        assert_eq!(out.len(), 5 * 6 + 4 * 3);
+        // Line area plus a triangle of side 0.25 per corner
+        assert!(approx_eq(area(&out), 8.0 * 0.5 + 4.0 * 0.03125));
+
+        let mut out = vec![];
+        polyline(&points, 0.5, LineJoin::Miter, &mut out);
+        assert_eq!(out.len(), 5 * 6 + 4 * 6);
+        for v in &out {
+            assert!(v.0 >= -0.25 - EPSILON && v.0 <= 2.25 + EPSILON);
+            assert!(v.1 >= -0.25 - EPSILON && v.1 <= 2.25 + EPSILON);
+        }
+        // Miter joins reach the outer corners
+        let corner = Vec2(2.25, -0.25);
+        assert!(out.iter().any(|v| (*v - corner).max_abs_comp() < 1e-4));
+    }
+}
@@ -7,7 +7,7 @@
 
 use super::common;
 use crate::draw::ShaderManager;
-use kas::draw::{color::Rgba, PassId};
+use kas::draw::{color::Rgba, PassId, StrokeStyle};
 use kas::geom::{Quad, Vec2};
 use std::mem::size_of;
 
@@ -116,6 +116,33 @@ impl Window {
         ]);
     }
 
+    /// Add triangles to the buffer
+    ///
+    /// Each three consecutive `points` define a triangle. Winding order is
+    /// corrected as required; degenerate triangles are skipped.
+    pub fn triangles(&mut self, pass: PassId, points: &[Vec2], col: Rgba) {
+        let t = Vec2(0.0, 0.0);
+        let mut vertices = Vec::with_capacity(points.len());
+        for tri in points.chunks_exact(3) {
+            let (a, b, c) = (tri[0], tri[1], tri[2]);
+            let area = (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0);
+            if area > 0.0 {
+                vertices.extend_from_slice(&[
+                    Vertex(a, col, t),
+                    Vertex(b, col, t),
+                    Vertex(c, col, t),
+                ]);
+            } else if area < 0.0 {
+                vertices.extend_from_slice(&[
+                    Vertex(a, col, t),
+                    Vertex(c, col, t),
+                    Vertex(b, col, t),
+                ]);
+            }
+        }
+        self.add_vertices(pass.pass(), &vertices);
+    }
+
     /// Add a rect to the buffer, defined by two outer corners, `aa` and `bb`.
     ///
     /// Bounds on input: `aa < cc` and `-1 ≤ norm ≤ 1`.
@@ -155,6 +182,71 @@ impl Window {
         self.shaded_frame(pass, outer, inner, norm, col, col);
     }
 
+    /// Add a dashed or dotted frame to the buffer
+    ///
+    /// The pattern runs clockwise from the top-left corner.
+    pub fn frame_styled(
+        &mut self,
+        pass: PassId,
+        outer: Quad,
+        inner: Quad,
+        style: StrokeStyle,
+        col: Rgba,
+    ) {
+        let aa = outer.a;
+        let bb = outer.b;
+        let mut cc = inner.a;
+        let mut dd = inner.b;
+
+        if !aa.lt(bb) {
+            // zero / negative size: nothing to draw
+            return;
+        }
+        if !aa.le(cc) || !cc.le(bb) {
+            cc = aa;
+        }
+        if !aa.le(dd) || !dd.le(bb) {
+            dd = bb;
+        }
+        if !cc.le(dd) {
+            dd = cc;
+        }
+
+        let width = (cc - aa).max(bb - dd).max_abs_comp();
+        let mut dist = 0.0;
+
+        // top bar, left to right
+        let len = bb.0 - aa.0;
+        style.for_each_dash(width, dist, len, |a, b| {
+            let rect = Quad::from_coords(Vec2(aa.0 + a, aa.1), Vec2(aa.0 + b, cc.1));
+            self.rect(pass, rect, col);
+        });
+        dist += len;
+
+        // right bar, top to bottom
+        let len = bb.1 - cc.1;
+        style.for_each_dash(width, dist, len, |a, b| {
+            let rect = Quad::from_coords(Vec2(dd.0, cc.1 + a), Vec2(bb.0, cc.1 + b));
+            self.rect(pass, rect, col);
+        });
+        dist += len;
+
+        // bottom bar, right to left
+        let len = dd.0 - aa.0;
+        style.for_each_dash(width, dist, len, |a, b| {
+            let rect = Quad::from_coords(Vec2(dd.0 - b, dd.1), Vec2(dd.0 - a, bb.1));
+            self.rect(pass, rect, col);
+        });
+        dist += len;
+
+        // left bar, bottom to top
+        let len = dd.1 - cc.1;
+        style.for_each_dash(width, dist, len, |a, b| {
+            let rect = Quad::from_coords(Vec2(aa.0, dd.1 - b), Vec2(cc.0, dd.1 - a));
+            self.rect(pass, rect, col);
+        });
+    }
+
     /// Add a frame to the buffer, defined by two outer corners, `aa` and `bb`,
     /// and two inner corners, `cc` and `dd` with colours `outer_col`, `inner_col`.
     ///
@@ -12,12 +12,15 @@ pub struct ShaderManager {
     pub vert_flat_round: ShaderModule,
     pub vert_round_2col: ShaderModule,
     pub vert_shaded_square: ShaderModule,
+    pub vert_instanced_rect: ShaderModule,
+    pub vert_gradient: ShaderModule,
     pub vert_shaded_round: ShaderModule,
     pub vert_image: ShaderModule,
     pub vert_glyph: ShaderModule,
     pub frag_flat_round: ShaderModule,
     pub frag_round_2col: ShaderModule,
     pub frag_shaded_square: ShaderModule,
+    pub frag_gradient: ShaderModule,
     pub frag_shaded_round: ShaderModule,
     pub frag_image: ShaderModule,
     pub frag_glyph: ShaderModule,
@@ -34,6 +37,8 @@ impl ShaderManager {
         let vert_flat_round = create!(device, "shaders/flat_round.vert.spv");
         let vert_round_2col = create!(device, "shaders/round_2col.vert.spv");
         let vert_shaded_square = create!(device, "shaders/shaded_square.vert.spv");
+        let vert_instanced_rect = create!(device, "shaders/instanced_rect.vert.spv");
+        let vert_gradient = create!(device, "shaders/gradient.vert.spv");
         let vert_shaded_round = create!(device, "shaders/shaded_round.vert.spv");
         let vert_image = create!(device, "shaders/image.vert.spv");
         let vert_glyph = create!(device, "shaders/glyph.vert.spv");
@@ -41,6 +46,7 @@ impl ShaderManager {
         let frag_flat_round = create!(device, "shaders/flat_round.frag.spv");
         let frag_round_2col = create!(device, "shaders/round_2col.frag.spv");
         let frag_shaded_square = create!(device, "shaders/shaded_square.frag.spv");
+        let frag_gradient = create!(device, "shaders/gradient.frag.spv");
         let frag_shaded_round = create!(device, "shaders/shaded_round.frag.spv");
         let frag_image = create!(device, "shaders/image.frag.spv");
         let frag_glyph = create!(device, "shaders/glyph.frag.spv");
@@ -51,10 +57,13 @@ impl ShaderManager {
             vert_flat_round,
             vert_round_2col,
             vert_shaded_square,
+            vert_instanced_rect,
+            vert_gradient,
             vert_shaded_round,
             frag_flat_round,
             frag_round_2col,
             frag_shaded_square,
+            frag_gradient,
             frag_shaded_round,
             frag_image,
             frag_glyph,
@@ -0,0 +1,23 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+#version 450
+#extension GL_ARB_separate_shader_objects : enable
+
+precision mediump float;
+
+layout(location = 0) flat in vec4 col1;
+layout(location = 1) flat in vec4 col2;
+// Linear: x is the position along the gradient
+// Radial: position relative to the center, scaled by the radius
+layout(location = 2) in vec2 param;
+layout(location = 3) flat in float radial;
+
+layout(location = 0) out vec4 outColor;
+
+void main() {
+    float t = radial > 0.5 ? length(param) : param.x;
+    outColor = mix(col1, col2, clamp(t, 0.0, 1.0));
+}
@@ -18,6 +18,8 @@ layout(location = 0) out vec2 tex_pos;
 layout(set = 0, binding = 0) uniform VertexCommon {
     vec2 offset;
     vec2 scale;
+    vec4 transform;
+    vec2 translate;
 };
 
 void main() {
@@ -35,11 +37,13 @@ void main() {
             pos = vec2(pos_a.x, pos_b.y);
             tex_pos = vec2(tex_a.x, tex_b.y);
             break;
-        case 3:
+        default:
             pos = pos_b;
             tex_pos = tex_b;
             break;
     }
 
-    gl_Position = vec4(scale * (pos.xy + offset), 0.0, 1.0);
+    vec2 p = pos.xy + offset;
+    p = vec2(dot(transform.xy, p), dot(transform.zw, p)) + translate;
+    gl_Position = vec4(scale * p, 0.0, 1.0);
 }
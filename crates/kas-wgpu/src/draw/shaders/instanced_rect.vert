@@ -0,0 +1,47 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+#version 450
+#extension GL_ARB_separate_shader_objects : enable
+
+precision mediump float;
+
+layout(location = 0) in vec2 pos_a;
+layout(location = 1) in vec2 pos_b;
+layout(location = 2) in vec4 a_col;
+
+layout(location = 0) out vec4 b_col;
+layout(location = 1) out vec2 b1;
+
+layout(set = 0, binding = 0) uniform VertexCommon {
+    vec2 offset;
+    vec2 scale;
+    vec4 transform;
+    vec2 translate;
+};
+
+void main() {
+    vec2 pos;
+    switch (gl_VertexIndex) {
+        case 0:
+            pos = pos_a;
+            break;
+        case 1:
+            pos = vec2(pos_b.x, pos_a.y);
+            break;
+        case 2:
+            pos = vec2(pos_a.x, pos_b.y);
+            break;
+        default:
+            pos = pos_b;
+            break;
+    }
+
+    vec2 p = pos.xy + offset;
+    p = vec2(dot(transform.xy, p), dot(transform.zw, p)) + translate;
+    gl_Position = vec4(scale * p, 0.0, 1.0);
+    b_col = a_col;
+    b1 = vec2(0.0, 0.0);
+}
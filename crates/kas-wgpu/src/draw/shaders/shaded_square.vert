@@ -18,10 +18,14 @@ layout(location = 1) out vec2 b1;
 layout(set = 0, binding = 0) uniform VertexCommon {
     vec2 offset;
     vec2 scale;
+    vec4 transform;
+    vec2 translate;
 };
 
 void main() {
-    gl_Position = vec4(scale * (a_pos.xy + offset), 0.0, 1.0);
+    vec2 p = a_pos.xy + offset;
+    p = vec2(dot(transform.xy, p), dot(transform.zw, p)) + translate;
+    gl_Position = vec4(scale * p, 0.0, 1.0);
     b_col = a_col;
     b1 = a1;
 }
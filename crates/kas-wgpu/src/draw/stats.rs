@@ -0,0 +1,119 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Render statistics
+
+use std::fmt;
+use std::time::Duration;
+
+/// Statistics for a single pipeline
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PipeStats {
+    /// Pipeline name
+    pub name: &'static str,
+    /// Number of vertices drawn
+    ///
+    /// For instanced pipelines, this counts the vertices generated from
+    /// instances.
+    pub vertices: u32,
+    /// Number of instances drawn (zero for non-instanced pipelines)
+    pub instances: u32,
+    /// Number of draw calls
+    pub draw_calls: u32,
+}
+
+/// Occupancy of a set of texture atlases
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AtlasStats {
+    /// Number of atlas textures
+    pub atlases: u32,
+    /// Area allocated (in pixels)
+    pub allocated: u64,
+    /// Total area of all atlas textures (in pixels)
+    pub capacity: u64,
+}
+
+impl AtlasStats {
+    /// Fraction of capacity allocated, in the range `0..=1`
+    pub fn occupancy(&self) -> f32 {
+        if self.capacity == 0 {
+            return 0.0;
+        }
+        (self.allocated as f64 / self.capacity as f64) as f32
+    }
+}
+
+/// Statistics concerning the last frame rendered
+///
+/// Obtain via [`super::DrawWindow::render_stats`]. Custom pipes (see
+/// [`super::CustomPipe`]) are not included. GPU execution time is not
+/// measured (this would require timestamp queries).
+#[derive(Clone, Debug, Default)]
+pub struct RenderStats {
+    /// Number of draw passes (clip regions and overlays, including the window)
+    pub passes: u32,
+    /// Per-pipeline statistics
+    pub pipes: Vec<PipeStats>,
+    /// Image atlas occupancy (shared by all windows)
+    pub image_atlases: AtlasStats,
+    /// Glyph atlas occupancy (shared by all windows)
+    pub glyph_atlases: AtlasStats,
+    /// Colour glyph atlas occupancy (shared by all windows)
+    pub color_glyph_atlases: AtlasStats,
+    /// CPU time taken to prepare buffers and encode render commands
+    pub cpu_time: Duration,
+}
+
+impl RenderStats {
+    /// Total number of vertices drawn
+    pub fn vertices(&self) -> u32 {
+        self.pipes.iter().map(|p| p.vertices).sum()
+    }
+
+    /// Total number of draw calls
+    pub fn draw_calls(&self) -> u32 {
+        self.pipes.iter().map(|p| p.draw_calls).sum()
+    }
+}
+
+impl fmt::Display for RenderStats {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(
+            f,
+            "{} passes, {} draw calls, {} vertices, {}µs",
+            self.passes,
+            self.draw_calls(),
+            self.vertices(),
+            self.cpu_time.as_micros()
+        )?;
+        for pipe in &self.pipes {
+            if pipe.draw_calls == 0 {
+                continue;
+            }
+            write!(f, "{}: {} vertices", pipe.name, pipe.vertices)?;
+            if pipe.instances > 0 {
+                write!(f, " ({} instances)", pipe.instances)?;
+            }
+            writeln!(f, ", {} draw calls", pipe.draw_calls)?;
+        }
+        let atlases = [
+            ("images", &self.image_atlases),
+            ("glyphs", &self.glyph_atlases),
+            ("colour glyphs", &self.color_glyph_atlases),
+        ];
+        for (i, (name, stats)) in atlases.iter().enumerate() {
+            let sep = if i > 0 { "\n" } else { "" };
+            write!(
+                f,
+                "{}{} atlases: {}, {:.1}% occupied",
+                sep,
+                name,
+                stats.atlases,
+                stats.occupancy() * 100.0
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -5,7 +5,8 @@
 
 //! Text drawing pipeline
 
-use super::{atlases, ShaderManager};
+use super::color_glyphs::ColorGlyphs;
+use super::{atlases, stats::AtlasStats, ShaderManager};
 use kas::cast::*;
 use kas::draw::{color::Rgba, PassId};
 use kas::geom::{Quad, Vec2};
@@ -28,6 +29,7 @@ struct Sprite {
     size: Vec2,
     offset: Vec2,
     tex_quad: Quad,
+    color: bool,
 }
 
 /// Screen and texture coordinates
@@ -43,13 +45,29 @@ pub struct Instance {
 unsafe impl bytemuck::Zeroable for Instance {}
 unsafe impl bytemuck::Pod for Instance {}
 
+/// Screen and texture coordinates (colour glyphs)
+#[repr(C)]
+#[derive(Clone, Copy, Debug)]
+pub struct ColorInstance {
+    a: Vec2,
+    b: Vec2,
+    ta: Vec2,
+    tb: Vec2,
+}
+unsafe impl bytemuck::Zeroable for ColorInstance {}
+unsafe impl bytemuck::Pod for ColorInstance {}
+
+type Upload = (u32, (u32, u32), (u32, u32), Vec<u8>);
+
 /// A pipeline for rendering text
 pub struct Pipeline {
     config: Config,
     atlas_pipe: atlases::Pipeline<Instance>,
+    color_pipe: atlases::Pipeline<ColorInstance>,
+    color_glyphs: ColorGlyphs,
     glyphs: HashMap<SpriteDescriptor, Option<Sprite>>,
-    #[allow(clippy::type_complexity)]
-    prepare: Vec<(u32, (u32, u32), (u32, u32), Vec<u8>)>,
+    prepare: Vec<Upload>,
+    prepare_color: Vec<Upload>,
 }
 
 impl Pipeline {
@@ -64,6 +82,8 @@ impl Pipeline {
             bgl_common,
             512,
             wgpu::TextureFormat::R8Unorm,
+            // Linear filtering supports transformed (e.g. rotated) text
+            wgpu::FilterMode::Linear,
             wgpu::VertexState {
                 module: &shaders.vert_glyph,
                 entry_point: "main",
@@ -89,6 +109,37 @@ impl Pipeline {
                 }],
             },
         );
+        // Colour glyphs use an RGBA atlas and the image shaders
+        let color_pipe = atlases::Pipeline::new(
+            device,
+            bgl_common,
+            1024,
+            wgpu::TextureFormat::Rgba8UnormSrgb,
+            wgpu::FilterMode::Linear,
+            wgpu::VertexState {
+                module: &shaders.vert_image,
+                entry_point: "main",
+                buffers: &[wgpu::VertexBufferLayout {
+                    array_stride: size_of::<ColorInstance>() as wgpu::BufferAddress,
+                    step_mode: wgpu::VertexStepMode::Instance,
+                    attributes: &wgpu::vertex_attr_array![
+                        0 => Float32x2,
+                        1 => Float32x2,
+                        2 => Float32x2,
+                        3 => Float32x2,
+                    ],
+                }],
+            },
+            wgpu::FragmentState {
+                module: &shaders.frag_image,
+                entry_point: "main",
+                targets: &[wgpu::ColorTargetState {
+                    format: super::RENDER_TEX_FORMAT,
+                    blend: Some(wgpu::BlendState::ALPHA_BLENDING),
+                    write_mask: wgpu::ColorWrites::ALL,
+                }],
+            },
+        );
         Pipeline {
             config: Config::new(
                 config.mode,
@@ -97,48 +148,40 @@ impl Pipeline {
                 config.subpixel_steps,
             ),
             atlas_pipe,
+            color_pipe,
+            color_glyphs: Default::default(),
             glyphs: Default::default(),
             prepare: Default::default(),
+            prepare_color: Default::default(),
         }
     }
 
     /// Write to textures
     pub fn prepare(&mut self, device: &wgpu::Device, queue: &wgpu::Queue) {
         self.atlas_pipe.prepare(device);
+        self.color_pipe.prepare(device);
 
-        if !self.prepare.is_empty() {
+        if !self.prepare.is_empty() || !self.prepare_color.is_empty() {
             log::trace!(
                 "Pipeline::prepare: uploading {} sprites",
-                self.prepare.len()
+                self.prepare.len() + self.prepare_color.len()
             );
         }
         for (atlas, origin, size, data) in self.prepare.drain(..) {
-            queue.write_texture(
-                wgpu::ImageCopyTexture {
-                    texture: self.atlas_pipe.get_texture(atlas),
-                    mip_level: 0,
-                    origin: wgpu::Origin3d {
-                        x: origin.0,
-                        y: origin.1,
-                        z: 0,
-                    },
-                    aspect: wgpu::TextureAspect::All,
-                },
-                &data,
-                wgpu::ImageDataLayout {
-                    offset: 0,
-                    bytes_per_row: NonZeroU32::new(size.0),
-                    rows_per_image: NonZeroU32::new(size.1),
-                },
-                wgpu::Extent3d {
-                    width: size.0,
-                    height: size.1,
-                    depth_or_array_layers: 1,
-                },
-            );
+            let texture = self.atlas_pipe.get_texture(atlas);
+            upload(queue, texture, origin, size, 1, &data);
+        }
+        for (atlas, origin, size, data) in self.prepare_color.drain(..) {
+            let texture = self.color_pipe.get_texture(atlas);
+            upload(queue, texture, origin, size, 4, &data);
         }
     }
 
+    /// Get occupancy of glyph and colour glyph atlases
+    pub fn stats(&self) -> (AtlasStats, AtlasStats) {
+        (self.atlas_pipe.stats(), self.color_pipe.stats())
+    }
+
     /// Enqueue render commands
     pub fn render<'a>(
         &'a self,
@@ -149,6 +192,8 @@ impl Pipeline {
     ) {
         self.atlas_pipe
             .render(&window.atlas, pass, rpass, bg_common);
+        self.color_pipe
+            .render(&window.color_atlas, pass, rpass, bg_common);
     }
 
     /// Get a rendered sprite
@@ -162,15 +207,37 @@ impl Pipeline {
         } else {
             // NOTE: this branch is *rare*. We don't use HashMap::entry and push
             // rastering to another function to optimise for the common case.
-            self.raster_glyph(desc)
+            self.raster_glyph(desc, dpem)
         }
     }
 
-    fn raster_glyph(&mut self, desc: SpriteDescriptor) -> Option<Sprite> {
+    fn raster_glyph(&mut self, desc: SpriteDescriptor, dpem: f32) -> Option<Sprite> {
         // NOTE: we only need the allocation and coordinates now; the
         // rendering could be offloaded (though this may not be useful).
         let mut sprite = None;
-        if let Some(rs) = raster(&self.config, desc) {
+        // Colour glyphs take precedence: colour fonts may also have outlines
+        if let Some(rs) = self.color_glyphs.raster(desc.face(), dpem, desc.glyph()) {
+            match self.color_pipe.allocate(rs.size) {
+                Ok((atlas, _, origin, tex_quad)) => {
+                    let s = Sprite {
+                        atlas,
+                        size: Vec2(rs.size.0.cast(), rs.size.1.cast()),
+                        offset: rs.offset,
+                        tex_quad,
+                        color: true,
+                    };
+
+                    self.prepare_color.push((s.atlas, origin, rs.size, rs.data));
+                    sprite = Some(s);
+                }
+                Err(_) => {
+                    log::warn!(
+                        "text_pipe: failed to allocate colour glyph with size {:?}",
+                        rs.size
+                    );
+                }
+            };
+        } else if let Some(rs) = raster(&self.config, desc) {
             match self.atlas_pipe.allocate(rs.size) {
                 Ok((atlas, _, origin, tex_quad)) => {
                     let s = Sprite {
@@ -178,6 +245,7 @@ impl Pipeline {
                         size: Vec2(rs.size.0.cast(), rs.size.1.cast()),
                         offset: Vec2(rs.offset.0.cast(), rs.offset.1.cast()),
                         tex_quad,
+                        color: false,
                     };
 
                     self.prepare.push((s.atlas, origin, rs.size, rs.data));
@@ -204,10 +272,44 @@ impl Pipeline {
     }
 }
 
+fn upload(
+    queue: &wgpu::Queue,
+    texture: &wgpu::Texture,
+    origin: (u32, u32),
+    size: (u32, u32),
+    bytes_per_pixel: u32,
+    data: &[u8],
+) {
+    queue.write_texture(
+        wgpu::ImageCopyTexture {
+            texture,
+            mip_level: 0,
+            origin: wgpu::Origin3d {
+                x: origin.0,
+                y: origin.1,
+                z: 0,
+            },
+            aspect: wgpu::TextureAspect::All,
+        },
+        data,
+        wgpu::ImageDataLayout {
+            offset: 0,
+            bytes_per_row: NonZeroU32::new(bytes_per_pixel * size.0),
+            rows_per_image: NonZeroU32::new(size.1),
+        },
+        wgpu::Extent3d {
+            width: size.0,
+            height: size.1,
+            depth_or_array_layers: 1,
+        },
+    );
+}
+
 /// Per-window state
 #[derive(Debug, Default)]
 pub struct Window {
     atlas: atlases::Window<Instance>,
+    color_atlas: atlases::Window<ColorInstance>,
     duration: std::time::Duration,
 }
 
@@ -220,6 +322,13 @@ impl Window {
         encoder: &mut wgpu::CommandEncoder,
     ) {
         self.atlas.write_buffers(device, staging_belt, encoder);
+        self.color_atlas
+            .write_buffers(device, staging_belt, encoder);
+    }
+
+    /// Get `(instances, draw_calls)` for glyphs and colour glyphs
+    pub fn stats(&self) -> ((u32, u32), (u32, u32)) {
+        (self.atlas.stats(), self.color_atlas.stats())
     }
 
     /// Get microseconds used for text during since last call
@@ -229,6 +338,21 @@ impl Window {
         micros
     }
 
+    fn push_sprite(&mut self, pass: PassId, pos: Vec2, glyph: Glyph, sprite: Sprite, col: Rgba) {
+        let pos = pos + Vec2::from(glyph.position).floor();
+        let a = pos + sprite.offset;
+        let b = a + sprite.size;
+        let (ta, tb) = (sprite.tex_quad.a, sprite.tex_quad.b);
+        // TODO(opt): avoid calling repeatedly?
+        if sprite.color {
+            let instance = ColorInstance { a, b, ta, tb };
+            self.color_atlas.rect(pass, sprite.atlas, instance);
+        } else {
+            let instance = Instance { a, b, ta, tb, col };
+            self.atlas.rect(pass, sprite.atlas, instance);
+        }
+    }
+
     pub fn text(
         &mut self,
         pipe: &mut Pipeline,
@@ -242,13 +366,7 @@ impl Window {
 
         let for_glyph = |face: FaceId, dpem: f32, glyph: Glyph| {
             if let Some(sprite) = pipe.get_glyph(face, dpem, glyph) {
-                let pos = pos + Vec2::from(glyph.position).floor();
-                let a = pos + sprite.offset;
-                let b = a + sprite.size;
-                let (ta, tb) = (sprite.tex_quad.a, sprite.tex_quad.b);
-                let instance = Instance { a, b, ta, tb, col };
-                // TODO(opt): avoid calling repeatedly?
-                self.atlas.rect(pass, sprite.atlas, instance);
+                self.push_sprite(pass, pos, glyph, sprite, col);
             }
         };
         text.glyphs(for_glyph);
@@ -282,13 +400,7 @@ impl Window {
 
         let mut for_glyph = |face: FaceId, dpem: f32, glyph: Glyph, _: usize, _: ()| {
             if let Some(sprite) = pipe.get_glyph(face, dpem, glyph) {
-                let pos = pos + Vec2::from(glyph.position).floor();
-                let a = pos + sprite.offset;
-                let b = a + sprite.size;
-                let (ta, tb) = (sprite.tex_quad.a, sprite.tex_quad.b);
-                let instance = Instance { a, b, ta, tb, col };
-                // TODO(opt): avoid calling repeatedly?
-                self.atlas.rect(pass, sprite.atlas, instance);
+                self.push_sprite(pass, pos, glyph, sprite, col);
             }
         };
 
@@ -339,13 +451,7 @@ impl Window {
 
         let for_glyph = |face: FaceId, dpem: f32, glyph: Glyph, _, col: Rgba| {
             if let Some(sprite) = pipe.get_glyph(face, dpem, glyph) {
-                let pos = pos + Vec2::from(glyph.position).floor();
-                let a = pos + sprite.offset;
-                let b = a + sprite.size;
-                let (ta, tb) = (sprite.tex_quad.a, sprite.tex_quad.b);
-                let instance = Instance { a, b, ta, tb, col };
-                // TODO(opt): avoid calling repeatedly?
-                self.atlas.rect(pass, sprite.atlas, instance);
+                self.push_sprite(pass, pos, glyph, sprite, col);
             }
         };
 
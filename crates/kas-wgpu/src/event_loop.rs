@@ -53,6 +53,20 @@ where
         }
     }
 
+    /// True if input to window `id` is blocked by a modal window
+    ///
+    /// Input to native pop-ups is blocked along with input to their owner.
+    fn block_input(&self, id: ww::WindowId) -> bool {
+        let owner = if self.windows.contains_key(&id) {
+            id
+        } else if let Some(w) = self.windows.values().find(|w| w.has_popup(id)) {
+            w.window.id()
+        } else {
+            return false;
+        };
+        self.windows.values().any(|w| w.modal_parent == Some(owner))
+    }
+
     pub(crate) fn handle(
         &mut self,
         event: Event<ProxyAction>,
@@ -63,8 +77,15 @@ where
 
         match event {
             WindowEvent { window_id, event } => {
+                if is_input(&event) && self.block_input(window_id) {
+                    return;
+                }
                 if let Some(window) = self.windows.get_mut(&window_id) {
                     window.handle_event(&mut self.shared, event);
+                } else if let Some(window) =
+                    self.windows.values_mut().find(|w| w.has_popup(window_id))
+                {
+                    window.handle_popup_event(&mut self.shared, window_id, event);
                 }
             }
 
@@ -83,9 +104,7 @@ where
                     }
                 }
                 ProxyAction::Update(handle, payload) => {
-                    self.shared
-                        .pending
-                        .push(PendingAction::Update(handle, payload));
+                    self.shared.trigger_update(handle, payload);
                 }
             },
 
@@ -184,6 +203,8 @@ where
                     if window.do_draw(&mut self.shared) {
                         *control_flow = ControlFlow::Poll;
                     }
+                } else if let Some(window) = self.windows.values_mut().find(|w| w.has_popup(id)) {
+                    window.do_draw_popup(&mut self.shared, id);
                 }
             }
 
@@ -211,19 +232,23 @@ where
         while let Some(pending) = self.shared.pending.pop() {
             match pending {
                 PendingAction::AddPopup(parent_id, id, popup) => {
-                    debug!("Adding overlay");
-                    // TODO: support pop-ups as a special window, where available
+                    debug!("Adding pop-up");
                     self.windows.get_mut(&parent_id).unwrap().add_popup(
                         &mut self.shared,
+                        elwt,
                         id,
                         popup,
                     );
                     self.id_map.insert(id, parent_id);
                 }
-                PendingAction::AddWindow(id, widget) => {
+                PendingAction::AddWindow(id, widget, parent) => {
                     debug!("Adding window {}", widget.title());
+                    let modal = widget.modal();
                     match Window::new(&mut self.shared, elwt, id, widget) {
-                        Ok(window) => {
+                        Ok(mut window) => {
+                            if modal {
+                                window.modal_parent = parent;
+                            }
                             let wid = window.window.id();
                             self.id_map.insert(id, wid);
                             self.windows.insert(wid, window);
@@ -241,6 +266,43 @@ where
                         self.id_map.remove(&id);
                     }
                 }
+                PendingAction::WindowAction(id, action) => {
+                    let wwid = match self.id_map.get(&id) {
+                        Some(wwid) => *wwid,
+                        None => continue,
+                    };
+                    if action.contains(TkAction::EXIT) {
+                        for (_, window) in self.windows.drain() {
+                            let _ = window.handle_closure(&mut self.shared);
+                        }
+                        *control_flow = ControlFlow::Poll;
+                    } else if action.contains(TkAction::CLOSE) {
+                        let is_window = match self.windows.get_mut(&wwid) {
+                            Some(window) if window.window_id == id => true,
+                            Some(window) => {
+                                // Close only the pop-up
+                                window.send_close(&mut self.shared, id);
+                                false
+                            }
+                            None => false,
+                        };
+                        self.id_map.remove(&id);
+                        if is_window {
+                            let window = self.windows.remove(&wwid).unwrap();
+                            if window
+                                .handle_closure(&mut self.shared)
+                                .contains(TkAction::EXIT)
+                            {
+                                for (_, window) in self.windows.drain() {
+                                    let _ = window.handle_closure(&mut self.shared);
+                                }
+                            }
+                            *control_flow = ControlFlow::Poll;
+                        }
+                    } else if let Some(window) = self.windows.get_mut(&wwid) {
+                        window.handle_action(&mut self.shared, action);
+                    }
+                }
                 PendingAction::TkAction(action) => {
                     if action.contains(TkAction::CLOSE | TkAction::EXIT) {
                         for (_, window) in self.windows.drain() {
@@ -262,3 +324,21 @@ where
         }
     }
 }
+
+/// True for events representing user input
+fn is_input(event: &winit::event::WindowEvent) -> bool {
+    use winit::event::WindowEvent::*;
+    matches!(
+        event,
+        DroppedFile(_)
+            | HoveredFile(_)
+            | ReceivedCharacter(_)
+            | KeyboardInput { .. }
+            | CursorMoved { .. }
+            | MouseWheel { .. }
+            | MouseInput { .. }
+            | TouchpadPressure { .. }
+            | AxisMotion { .. }
+            | Touch(_)
+    )
+}
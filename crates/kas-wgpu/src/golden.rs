@@ -0,0 +1,126 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Golden image comparison (for regression tests)
+//!
+//! Images rendered via [`Printer::render_image`] are compared against
+//! reference ("golden") PNG images:
+//! ```no_run
+//! # fn example(widget: &mut dyn kas::WidgetConfig) {
+//! use kas::geom::Size;
+//! use kas_wgpu::print::{PageSetup, Printer};
+//!
+//! let mut printer = Printer::new(kas_theme::FlatTheme::new(), PageSetup::A4);
+//! let (size, data) = printer.render_image(widget, Size(400, 300));
+//! kas_wgpu::golden::check("tests/golden/widget.png", size, &data).unwrap();
+//! # }
+//! ```
+//!
+//! If the environment variable `KAS_BLESS_GOLDEN` is set, the image is written
+//! instead; a missing golden image is otherwise an error. On mismatch, the
+//! rendered image is written alongside the golden image with extension
+//! `.actual.png`.
+//!
+//! [`Printer::render_image`]: crate::print::Printer::render_image
+
+use kas::cast::Conv;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Environment variable: when set, golden images are (re)written
+pub const BLESS_VAR: &str = "KAS_BLESS_GOLDEN";
+
+/// Maximum difference per colour channel considered equal
+pub const CHANNEL_TOLERANCE: u8 = 2;
+
+/// Maximum fraction of pixels which may differ
+pub const PIXEL_TOLERANCE: f64 = 0.001;
+
+/// Golden image comparison failure
+#[non_exhaustive]
+#[derive(Error, Debug)]
+pub enum Error {
+    /// Failed to load or save an image
+    #[error("image load/save error")]
+    Image(#[from] image::ImageError),
+    /// The golden image does not exist
+    #[error("golden image {0} not found (set KAS_BLESS_GOLDEN to create)")]
+    Missing(PathBuf),
+    /// Image size differs
+    #[error("image size {actual:?} does not match golden image size {expected:?}")]
+    Size {
+        expected: (u32, u32),
+        actual: (u32, u32),
+    },
+    /// Image content differs
+    #[error("{count} of {total} pixels differ from the golden image")]
+    Pixels { count: usize, total: usize },
+}
+
+/// Compare an image against the golden image at `path`
+///
+/// The image has the given `size` and RGBA (sRGB) `data`, as returned by
+/// [`Printer::render_image`](crate::print::Printer::render_image). See the
+/// [module documentation](self).
+pub fn check<P: AsRef<Path>>(path: P, size: (u32, u32), data: &[u8]) -> Result<(), Error> {
+    let path = path.as_ref();
+    assert_eq!(data.len(), 4 * usize::conv(size.0) * usize::conv(size.1));
+
+    if std::env::var_os(BLESS_VAR).is_some() {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(image::ImageError::IoError)?;
+        }
+        save(path, size, data)?;
+        log::info!("golden::check: wrote {}", path.display());
+        return Ok(());
+    }
+    if !path.exists() {
+        return Err(Error::Missing(path.to_path_buf()));
+    }
+
+    let golden = image::open(path)?.to_rgba8();
+    let result = compare(golden.dimensions(), golden.as_raw(), size, data);
+    if result.is_err() {
+        let actual = path.with_extension("actual.png");
+        save(&actual, size, data)?;
+        log::warn!("golden::check: mismatch; wrote {}", actual.display());
+    }
+    result
+}
+
+/// Compare two RGBA images, with tolerance
+pub fn compare(
+    expected_size: (u32, u32),
+    expected: &[u8],
+    size: (u32, u32),
+    data: &[u8],
+) -> Result<(), Error> {
+    if size != expected_size {
+        return Err(Error::Size {
+            expected: expected_size,
+            actual: size,
+        });
+    }
+
+    let total = data.len() / 4;
+    let count = expected
+        .chunks_exact(4)
+        .zip(data.chunks_exact(4))
+        .filter(|(a, b)| {
+            a.iter()
+                .zip(b.iter())
+                .any(|(x, y)| (i16::from(*x) - i16::from(*y)).abs() > CHANNEL_TOLERANCE.into())
+        })
+        .count();
+    if f64::conv(count) > PIXEL_TOLERANCE * f64::conv(total) {
+        return Err(Error::Pixels { count, total });
+    }
+    Ok(())
+}
+
+fn save(path: &Path, size: (u32, u32), data: &[u8]) -> Result<(), Error> {
+    image::save_buffer(path, data, size.0, size.1, image::ColorType::Rgba8)?;
+    Ok(())
+}
@@ -26,7 +26,9 @@
 
 pub mod draw;
 mod event_loop;
+pub mod golden;
 pub mod options;
+pub mod print;
 mod shared;
 mod window;
 
@@ -34,8 +36,10 @@ use std::cell::RefCell;
 use std::rc::Rc;
 use thiserror::Error;
 
-use kas::event::UpdateHandle;
-use kas::WindowId;
+use kas::event::{EventState, UpdateHandle};
+use kas::geom::Size;
+use kas::layout::SetRectMgr;
+use kas::{WidgetConfig, WindowId};
 use kas_theme::Theme;
 use winit::error::OsError;
 use winit::event_loop::{EventLoop, EventLoopProxy, EventLoopWindowTarget};
@@ -195,6 +199,41 @@ where
         &mut self.shared.theme
     }
 
+    /// Set a handler for haptic feedback requests
+    ///
+    /// `winit` does not support haptic feedback (e.g. vibration), thus
+    /// requests (e.g. on recognition of [`kas::event::Event::LongPress`]) are
+    /// ignored unless a handler is set. Applications may use this to call a
+    /// platform API.
+    pub fn set_haptic_feedback<F: FnMut() + 'static>(&mut self, f: F) {
+        self.shared.haptic_feedback = Some(Box::new(f));
+    }
+
+    /// Render `widget` to an image without a window
+    ///
+    /// This is a headless render: `widget` is configured, laid out within a
+    /// rect of the given `size` (in physical pixels) and drawn offscreen using
+    /// the toolkit's theme at `scale_factor`. It may be used before
+    /// [`Toolkit::run`], e.g. to generate thumbnails. Returns the image size
+    /// and RGBA (sRGB) data, or `None` on failure.
+    ///
+    /// Within a running UI, use [`kas::event::EventMgr::render_image`]. For a
+    /// software render, see [`print::Printer::render_image`].
+    pub fn render_image(
+        &mut self,
+        widget: &mut dyn WidgetConfig,
+        size: Size,
+        scale_factor: f32,
+    ) -> Option<((u32, u32), Vec<u8>)> {
+        use kas_theme::Window as _;
+        let shared = &mut self.shared;
+        let mut theme_window = shared.theme.new_window(scale_factor);
+        let mut state = EventState::new(shared.config.clone(), scale_factor);
+        let mut mgr = SetRectMgr::new(theme_window.size_handle(), &mut shared.draw, &mut state);
+        mgr.configure_root(widget);
+        shared.render_image(&mut theme_window, widget, size, &mut state)
+    }
+
     /// Assume ownership of and display a window
     ///
     /// This is a convenience wrapper around [`Toolkit::add_boxed`].
@@ -48,6 +48,17 @@ pub struct Options {
     pub backends: Backends,
     /// WGPU's API tracing path
     pub wgpu_trace_path: Option<PathBuf>,
+    /// Show pop-ups which do not fit within their window as native windows.
+    /// Where unsupported, overlays are used. Default: true.
+    pub native_popups: bool,
+    /// Draw render statistics over each window. Default: false.
+    pub render_stats: bool,
+    /// Toggle a shortcut help overlay on [`kas::event::Command::Help`]
+    /// (F1 or Ctrl+?). Default: false.
+    pub shortcut_help: bool,
+    /// Briefly show values changed via sliders and scroll bars in a tooltip
+    /// (see [`kas::event::EventState::announce_value`]). Default: false.
+    pub value_tooltips: bool,
 }
 
 impl Default for Options {
@@ -60,6 +71,10 @@ impl Default for Options {
             power_preference: PowerPreference::LowPower,
             backends: Backends::all(),
             wgpu_trace_path: None,
+            native_popups: true,
+            render_stats: false,
+            shortcut_help: false,
+            value_tooltips: false,
         }
     }
 }
@@ -125,6 +140,20 @@ impl Options {
     /// export KAS_WGPU_TRACE_PATH="api_trace"
     /// ```
     ///
+    /// # Other options
+    ///
+    /// The `KAS_NATIVE_POPUPS` variable may be set to `0` to always draw
+    /// pop-ups as overlays within their parent window.
+    ///
+    /// The `KAS_RENDER_STATS` variable may be set to `1` to draw render
+    /// statistics (see [`crate::draw::RenderStats`]) over each window.
+    ///
+    /// The `KAS_SHORTCUT_HELP` variable may be set to `1` to enable the
+    /// shortcut help overlay (see [`kas::event::EventState::enable_shortcut_help`]).
+    ///
+    /// The `KAS_VALUE_TOOLTIPS` variable may be set to `1` to enable value
+    /// tooltips (see [`kas::event::EventState::enable_value_tooltips`]).
+    ///
     /// [API tracing]: https://github.com/gfx-rs/wgpu/wiki/Debugging-wgpu-Applications#tracing-infrastructure
     pub fn from_env() -> Self {
         let mut options = Options::default();
@@ -198,6 +227,34 @@ impl Options {
             options.wgpu_trace_path = Some(v.into());
         }
 
+        if let Ok(v) = var("KAS_NATIVE_POPUPS") {
+            match v.parse::<u32>() {
+                Ok(x) => options.native_popups = x != 0,
+                Err(e) => error!("Bad env var: KAS_NATIVE_POPUPS={}: {}", v, e),
+            }
+        }
+
+        if let Ok(v) = var("KAS_RENDER_STATS") {
+            match v.parse::<u32>() {
+                Ok(x) => options.render_stats = x != 0,
+                Err(e) => error!("Bad env var: KAS_RENDER_STATS={}: {}", v, e),
+            }
+        }
+
+        if let Ok(v) = var("KAS_SHORTCUT_HELP") {
+            match v.parse::<u32>() {
+                Ok(x) => options.shortcut_help = x != 0,
+                Err(e) => error!("Bad env var: KAS_SHORTCUT_HELP={}: {}", v, e),
+            }
+        }
+
+        if let Ok(v) = var("KAS_VALUE_TOOLTIPS") {
+            match v.parse::<u32>() {
+                Ok(x) => options.value_tooltips = x != 0,
+                Err(e) => error!("Bad env var: KAS_VALUE_TOOLTIPS={}: {}", v, e),
+            }
+        }
+
         options
     }
 
@@ -0,0 +1,626 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Vector draw backend
+//!
+//! Draw operations are recorded as filled shapes (in pass-local coordinates)
+//! for later output by a vector writer or software rasteriser.
+
+use crate::draw::path;
+use kas::cast::*;
+use kas::draw::color::Rgba;
+use kas::draw::*;
+use kas::geom::{Offset, Quad, Rect, Vec2};
+use kas::text::fonts::FaceId;
+use kas::text::{Effect, Glyph, TextDisplay};
+use kas_text::raster::{raster, Config, SpriteDescriptor};
+use kas_theme::{DrawShadedImpl, RasterConfig};
+use std::collections::HashMap;
+use std::f32::consts::FRAC_PI_2;
+use std::rc::Rc;
+
+/// Number of line segments used to approximate a quarter-circle
+const ARC_SEGMENTS: u32 = 8;
+
+/// A bitmap image
+///
+/// Data is either RGBA (sRGB, 4 bytes per pixel) or an alpha mask (1 byte
+/// per pixel), depending on usage.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Bitmap {
+    pub size: (u32, u32),
+    pub data: Vec<u8>,
+}
+
+/// Paint used to fill a shape
+#[derive(Clone, Debug)]
+pub(crate) enum Paint {
+    Solid(Rgba),
+    Gradient(Gradient),
+    /// An RGBA image mapped to the given quad
+    Image(Rc<Bitmap>, Quad),
+    /// An alpha mask mapped to the given quad, painted with a colour
+    Mask(Rc<Bitmap>, Quad, Rgba),
+}
+
+/// A filled shape
+///
+/// The shape is composed of one or more closed paths, filled using the
+/// even-odd rule.
+#[derive(Clone, Debug)]
+pub(crate) struct Fill {
+    pub paths: Vec<Vec<Vec2>>,
+    pub paint: Paint,
+}
+
+impl Fill {
+    fn quad(quad: Quad, paint: Paint) -> Self {
+        Fill {
+            paths: vec![quad_path(quad)],
+            paint,
+        }
+    }
+}
+
+/// A draw pass
+#[derive(Clone, Debug)]
+pub(crate) struct Pass {
+    /// Clip region, in target coordinates
+    pub clip: Rect,
+    /// Transform from pass coordinates to target coordinates
+    pub transform: Affine,
+    /// Passes are drawn in order of elevation (see [`PassType::Elevated`])
+    pub elevation: u32,
+    /// Items drawn before others
+    pub background: Vec<Fill>,
+    pub items: Vec<Fill>,
+}
+
+impl Pass {
+    fn new(clip: Rect, transform: Affine, elevation: u32) -> Self {
+        Pass {
+            clip,
+            transform,
+            elevation,
+            background: vec![],
+            items: vec![],
+        }
+    }
+
+    /// Iterate over items in draw order
+    pub fn iter(&self) -> impl Iterator<Item = &Fill> {
+        self.background.iter().chain(self.items.iter())
+    }
+}
+
+/// Per-page draw state of the vector backend
+///
+/// See [`super::Printer`].
+#[derive(Debug)]
+pub struct VectorDraw {
+    animation: AnimationState,
+    passes: Vec<Pass>,
+}
+
+impl VectorDraw {
+    pub(crate) fn new(rect: Rect) -> Self {
+        VectorDraw {
+            animation: AnimationState::None,
+            passes: vec![Pass::new(rect, Affine::IDENTITY, 0)],
+        }
+    }
+
+    /// Get passes, in draw order
+    pub(crate) fn into_passes(mut self) -> Vec<Pass> {
+        // Stable sort: otherwise passes are drawn in order of creation
+        self.passes.sort_by_key(|pass| pass.elevation);
+        self.passes
+    }
+
+    fn push(&mut self, pass: PassId, fill: Fill) {
+        self.passes[pass.pass()].items.push(fill);
+    }
+
+    fn push_background(&mut self, pass: PassId, fill: Fill) {
+        self.passes[pass.pass()].background.push(fill);
+    }
+
+    fn solid(&mut self, pass: PassId, paths: Vec<Vec<Vec2>>, col: Rgba) {
+        let paint = Paint::Solid(col);
+        self.push(pass, Fill { paths, paint });
+    }
+}
+
+impl DrawImpl for VectorDraw {
+    fn animation_mut(&mut self) -> &mut AnimationState {
+        &mut self.animation
+    }
+
+    fn new_pass(
+        &mut self,
+        parent_pass: PassId,
+        rect: Rect,
+        offset: Offset,
+        transform: Affine,
+        class: PassType,
+    ) -> PassId {
+        let (base, page) = (&self.passes[parent_pass.pass()], &self.passes[0]);
+        let mut elevation = base.elevation;
+        let (parent, clip) = match class {
+            PassType::Clip => (base, base.clip),
+            PassType::Overlay => (page, page.clip),
+            PassType::Elevated => {
+                elevation += 1;
+                (base, page.clip)
+            }
+        };
+        let rect = bounding_rect(parent.transform.transform_bounds(rect.into()));
+        let rect = rect.intersection(&clip).unwrap_or(Rect::ZERO);
+        let transform = Affine::translate(-Vec2::from(offset))
+            .then(transform)
+            .then(parent.transform);
+        let pass = self.passes.len().cast();
+        self.passes.push(Pass::new(rect, transform, elevation));
+        PassId::new(pass)
+    }
+
+    fn get_clip_rect(&self, pass: PassId) -> Rect {
+        let pass = &self.passes[pass.pass()];
+        match pass.transform.inverse() {
+            Some(inv) => bounding_rect(inv.transform_bounds(pass.clip.into())),
+            None => Rect::ZERO,
+        }
+    }
+
+    fn rect(&mut self, pass: PassId, rect: Quad, col: Rgba) {
+        self.solid(pass, vec![quad_path(rect)], col);
+    }
+
+    fn frame(&mut self, pass: PassId, outer: Quad, inner: Quad, col: Rgba) {
+        self.solid(pass, vec![quad_path(outer), quad_path(inner)], col);
+    }
+
+    fn frame_styled(
+        &mut self,
+        pass: PassId,
+        outer: Quad,
+        inner: Quad,
+        style: StrokeStyle,
+        col: Rgba,
+    ) {
+        if style == StrokeStyle::Solid {
+            return self.frame(pass, outer, inner, col);
+        }
+
+        // Sides are traversed clockwise from the top-left corner
+        let (a, b) = (outer.a, outer.b);
+        let (w1, w2) = (inner.a - a, b - inner.b);
+        let len = b - a;
+        let mut quads = vec![];
+        let mut start = 0.0;
+        style.for_each_dash(w1.1, start, len.0, |x1, x2| {
+            quads.push(Quad::from_coords(
+                Vec2(a.0 + x1, a.1),
+                Vec2(a.0 + x2, inner.a.1),
+            ));
+        });
+        start += len.0;
+        style.for_each_dash(w2.0, start, len.1, |y1, y2| {
+            quads.push(Quad::from_coords(
+                Vec2(inner.b.0, a.1 + y1),
+                Vec2(b.0, a.1 + y2),
+            ));
+        });
+        start += len.1;
+        style.for_each_dash(w2.1, start, len.0, |x1, x2| {
+            quads.push(Quad::from_coords(
+                Vec2(b.0 - x2, inner.b.1),
+                Vec2(b.0 - x1, b.1),
+            ));
+        });
+        start += len.0;
+        style.for_each_dash(w1.0, start, len.1, |y1, y2| {
+            quads.push(Quad::from_coords(
+                Vec2(a.0, b.1 - y2),
+                Vec2(inner.a.0, b.1 - y1),
+            ));
+        });
+
+        for quad in quads {
+            self.rect(pass, quad, col);
+        }
+    }
+
+    fn rect_grad(&mut self, pass: PassId, rect: Quad, grad: Gradient) {
+        self.push(pass, Fill::quad(rect, Paint::Gradient(grad)));
+    }
+
+    fn frame_grad(&mut self, pass: PassId, outer: Quad, inner: Quad, grad: Gradient) {
+        let paths = vec![quad_path(outer), quad_path(inner)];
+        let paint = Paint::Gradient(grad);
+        self.push(pass, Fill { paths, paint });
+    }
+}
+
+impl DrawRoundedImpl for VectorDraw {
+    fn rounded_line(&mut self, pass: PassId, p1: Vec2, p2: Vec2, radius: f32, col: Rgba) {
+        self.solid(pass, vec![capsule(p1, p2, radius)], col);
+    }
+
+    fn rounded_line_styled(
+        &mut self,
+        pass: PassId,
+        p1: Vec2,
+        p2: Vec2,
+        radius: f32,
+        style: StrokeStyle,
+        col: Rgba,
+    ) {
+        let d = p2 - p1;
+        let len = d.sum_square().sqrt();
+        if len == 0.0 {
+            return self.rounded_line(pass, p1, p2, radius, col);
+        }
+        let dir = d / len;
+        let mut paths = vec![];
+        // Each dash includes its rounded ends
+        style.for_each_dash(2.0 * radius, 0.0, len, |a, b| {
+            let mid = 0.5 * (a + b);
+            let (a, b) = ((a + radius).min(mid), (b - radius).max(mid));
+            paths.push(capsule(p1 + dir * a, p1 + dir * b, radius));
+        });
+        for path in paths {
+            self.solid(pass, vec![path], col);
+        }
+    }
+
+    fn circle(&mut self, pass: PassId, rect: Quad, inner_radius: f32, col: Rgba) {
+        let radius = rect.size() * 0.5;
+        let centre = Quad::from_coords(rect.a + radius, rect.a + radius);
+        let mut paths = vec![rounded_rect(centre, radius, radius)];
+        if inner_radius > 0.0 {
+            let radius = radius * inner_radius;
+            paths.push(rounded_rect(centre, radius, radius));
+        }
+        self.solid(pass, paths, col);
+    }
+
+    fn circle_2col(&mut self, pass: PassId, rect: Quad, col1: Rgba, col2: Rgba) {
+        let radius = rect.size() * 0.5;
+        let center = rect.a + radius;
+        let centre = Quad::from_coords(center, center);
+        let fill = Fill {
+            paths: vec![rounded_rect(centre, radius, radius)],
+            paint: Paint::Gradient(Gradient::Radial {
+                center,
+                radius,
+                col1,
+                col2,
+            }),
+        };
+        self.push_background(pass, fill);
+    }
+
+    fn rounded_frame(&mut self, pass: PassId, outer: Quad, inner: Quad, r1: f32, col: Rgba) {
+        let (ra, rb) = (inner.a - outer.a, outer.b - inner.b);
+        let paths = vec![
+            rounded_rect(inner, ra, rb),
+            rounded_rect(inner, ra * r1, rb * r1),
+        ];
+        self.solid(pass, paths, col);
+    }
+
+    /// Approximated with a uniform colour between `c1` and `c2`
+    fn rounded_frame_2col(&mut self, pass: PassId, outer: Quad, inner: Quad, c1: Rgba, c2: Rgba) {
+        let (ra, rb) = (inner.a - outer.a, outer.b - inner.b);
+        let fill = Fill {
+            paths: vec![rounded_rect(inner, ra, rb), quad_path(inner)],
+            paint: Paint::Solid(mix(c1, c2, 0.5)),
+        };
+        self.push_background(pass, fill);
+    }
+}
+
+impl DrawPathImpl for VectorDraw {
+    fn polygon(&mut self, pass: PassId, points: &[Vec2], col: Rgba) {
+        if points.len() >= 3 {
+            self.solid(pass, vec![points.to_vec()], col);
+        }
+    }
+
+    fn polyline(&mut self, pass: PassId, points: &[Vec2], width: f32, join: LineJoin, col: Rgba) {
+        let mut triangles = vec![];
+        path::polyline(points, width, join, &mut triangles);
+        // Triangles have arbitrary winding, thus cannot share a path
+        for triangle in triangles.chunks_exact(3) {
+            self.solid(pass, vec![triangle.to_vec()], col);
+        }
+    }
+}
+
+/// Shading is not reproduced: shapes are drawn flat
+impl DrawShadedImpl for VectorDraw {
+    fn shaded_square(&mut self, pass: PassId, rect: Quad, _: (f32, f32), col: Rgba) {
+        self.rect(pass, rect, col);
+    }
+
+    fn shaded_circle(&mut self, pass: PassId, rect: Quad, _: (f32, f32), col: Rgba) {
+        self.circle(pass, rect, 0.0, col);
+    }
+
+    fn shaded_square_frame(
+        &mut self,
+        pass: PassId,
+        outer: Quad,
+        inner: Quad,
+        _: (f32, f32),
+        outer_col: Rgba,
+        _: Rgba,
+    ) {
+        self.frame(pass, outer, inner, outer_col);
+    }
+
+    fn shaded_round_frame(
+        &mut self,
+        pass: PassId,
+        outer: Quad,
+        inner: Quad,
+        _: (f32, f32),
+        col: Rgba,
+    ) {
+        self.rounded_frame(pass, outer, inner, 0.0, col);
+    }
+}
+
+/// A rastered glyph
+#[derive(Debug)]
+struct Sprite {
+    offset: Vec2,
+    size: Vec2,
+    mask: Rc<Bitmap>,
+}
+
+/// Shared draw state of the vector backend
+///
+/// See [`super::Printer`].
+pub struct VectorShared {
+    config: Config,
+    glyphs: HashMap<SpriteDescriptor, Option<Rc<Sprite>>>,
+    images: HashMap<ImageId, Rc<Bitmap>>,
+    next_image: u32,
+}
+
+impl VectorShared {
+    /// Construct
+    pub fn new(config: &RasterConfig) -> Self {
+        VectorShared {
+            config: Config::new(
+                config.mode,
+                config.scale_steps,
+                config.subpixel_threshold,
+                config.subpixel_steps,
+            ),
+            glyphs: Default::default(),
+            images: Default::default(),
+            next_image: 0,
+        }
+    }
+
+    fn get_glyph(&mut self, face: FaceId, dpem: f32, glyph: Glyph) -> Option<Rc<Sprite>> {
+        let desc = SpriteDescriptor::new(&self.config, face, glyph, dpem);
+        let config = &self.config;
+        let entry = self.glyphs.entry(desc).or_insert_with(|| {
+            // Colour glyphs are not supported
+            let rs = raster(config, desc)?;
+            Some(Rc::new(Sprite {
+                offset: Vec2(rs.offset.0.cast(), rs.offset.1.cast()),
+                size: Vec2(rs.size.0.cast(), rs.size.1.cast()),
+                mask: Rc::new(Bitmap {
+                    size: rs.size,
+                    data: rs.data,
+                }),
+            }))
+        });
+        entry.clone()
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn glyph(
+        &mut self,
+        draw: &mut VectorDraw,
+        pass: PassId,
+        pos: Vec2,
+        face: FaceId,
+        dpem: f32,
+        glyph: Glyph,
+        col: Rgba,
+    ) {
+        if let Some(sprite) = self.get_glyph(face, dpem, glyph) {
+            let a = pos + Vec2::from(glyph.position).floor() + sprite.offset;
+            let quad = Quad::from_coords(a, a + sprite.size);
+            let paint = Paint::Mask(sprite.mask.clone(), quad, col);
+            draw.push(pass, Fill::quad(quad, paint));
+        }
+    }
+}
+
+impl DrawSharedImpl for VectorShared {
+    type Draw = VectorDraw;
+
+    fn image_alloc(&mut self, size: (u32, u32)) -> Result<ImageId, ImageError> {
+        self.next_image += 1;
+        let id = ImageId::try_new(self.next_image).expect("exhausted image IDs");
+        let len = usize::conv(size.0) * usize::conv(size.1) * 4;
+        let data = vec![0; len];
+        self.images.insert(id, Rc::new(Bitmap { size, data }));
+        Ok(id)
+    }
+
+    fn image_upload(&mut self, id: ImageId, data: &[u8], format: ImageFormat) {
+        match format {
+            ImageFormat::Rgba8 => (),
+        }
+        if let Some(image) = self.images.get_mut(&id) {
+            if data.len() == image.data.len() {
+                let size = image.size;
+                let data = data.to_vec();
+                *image = Rc::new(Bitmap { size, data });
+            } else {
+                log::warn!("VectorShared::image_upload: incorrect data length");
+            }
+        }
+    }
+
+    fn image_free(&mut self, id: ImageId) {
+        self.images.remove(&id);
+    }
+
+    fn image_size(&self, id: ImageId) -> Option<(u32, u32)> {
+        self.images.get(&id).map(|image| image.size)
+    }
+
+    fn draw_image(&self, draw: &mut VectorDraw, pass: PassId, id: ImageId, rect: Quad) {
+        if let Some(image) = self.images.get(&id) {
+            draw.push(pass, Fill::quad(rect, Paint::Image(image.clone(), rect)));
+        }
+    }
+
+    fn draw_image_background(&self, draw: &mut VectorDraw, pass: PassId, id: ImageId, rect: Quad) {
+        if let Some(image) = self.images.get(&id) {
+            let fill = Fill::quad(rect, Paint::Image(image.clone(), rect));
+            draw.push_background(pass, fill);
+        }
+    }
+
+    fn draw_text(
+        &mut self,
+        draw: &mut VectorDraw,
+        pass: PassId,
+        pos: Vec2,
+        text: &TextDisplay,
+        col: Rgba,
+    ) {
+        let pos = pos.round();
+        text.glyphs(|face, dpem, glyph| self.glyph(draw, pass, pos, face, dpem, glyph, col));
+    }
+
+    fn draw_text_col_effects(
+        &mut self,
+        draw: &mut VectorDraw,
+        pass: PassId,
+        pos: Vec2,
+        text: &TextDisplay,
+        col: Rgba,
+        effects: &[Effect<()>],
+    ) {
+        let pos = pos.round();
+        let mut rects = vec![];
+        let for_glyph = |face: FaceId, dpem: f32, glyph: Glyph, _: usize, _: ()| {
+            self.glyph(draw, pass, pos, face, dpem, glyph, col);
+        };
+        let for_rect = |x1, x2, y: f32, h: f32, _, _| {
+            let y = y.ceil();
+            let y2 = y + h.ceil();
+            rects.push(Quad::from_coords(pos + Vec2(x1, y), pos + Vec2(x2, y2)));
+        };
+        text.glyphs_with_effects(effects, (), for_glyph, for_rect);
+
+        for quad in rects {
+            draw.rect(pass, quad, col);
+        }
+    }
+
+    fn draw_text_effects(
+        &mut self,
+        draw: &mut VectorDraw,
+        pass: PassId,
+        pos: Vec2,
+        text: &TextDisplay,
+        effects: &[Effect<Rgba>],
+    ) {
+        let pos = pos.round();
+        let mut rects = vec![];
+        let for_glyph = |face: FaceId, dpem: f32, glyph: Glyph, _: usize, col: Rgba| {
+            self.glyph(draw, pass, pos, face, dpem, glyph, col);
+        };
+        let for_rect = |x1, x2, y: f32, h: f32, _, col: Rgba| {
+            let y = y.ceil();
+            let y2 = y + h.ceil();
+            let quad = Quad::from_coords(pos + Vec2(x1, y), pos + Vec2(x2, y2));
+            rects.push((quad, col));
+        };
+        text.glyphs_with_effects(effects, Rgba::BLACK, for_glyph, for_rect);
+
+        for (quad, col) in rects {
+            draw.rect(pass, quad, col);
+        }
+    }
+}
+
+/// Linear blend between colours
+pub(crate) fn mix(c1: Rgba, c2: Rgba, t: f32) -> Rgba {
+    let s = 1.0 - t;
+    Rgba {
+        r: s * c1.r + t * c2.r,
+        g: s * c1.g + t * c2.g,
+        b: s * c1.b + t * c2.b,
+        a: s * c1.a + t * c2.a,
+    }
+}
+
+fn quad_path(quad: Quad) -> Vec<Vec2> {
+    vec![
+        quad.a,
+        Vec2(quad.b.0, quad.a.1),
+        quad.b,
+        Vec2(quad.a.0, quad.b.1),
+    ]
+}
+
+/// Rect with elliptic corners centred on the corners of `inner`
+///
+/// Radii `ra` apply to the top and left edges, `rb` to the bottom and right.
+fn rounded_rect(inner: Quad, ra: Vec2, rb: Vec2) -> Vec<Vec2> {
+    let corners = [
+        (Vec2(inner.a.0, inner.a.1), Vec2(ra.0, ra.1)),
+        (Vec2(inner.b.0, inner.a.1), Vec2(rb.0, ra.1)),
+        (Vec2(inner.b.0, inner.b.1), Vec2(rb.0, rb.1)),
+        (Vec2(inner.a.0, inner.b.1), Vec2(ra.0, rb.1)),
+    ];
+    let mut points = Vec::with_capacity(4 * usize::conv(ARC_SEGMENTS + 1));
+    // Corners are traversed clockwise starting from the left of the top-left
+    for (i, (centre, radius)) in corners.iter().enumerate() {
+        let start = f32::conv(i + 2) * FRAC_PI_2;
+        for j in 0..=ARC_SEGMENTS {
+            let angle = start + FRAC_PI_2 * f32::conv(j) / f32::conv(ARC_SEGMENTS);
+            points.push(*centre + *radius * Vec2(angle.cos(), angle.sin()));
+        }
+    }
+    points
+}
+
+/// Line from `p1` to `p2` with semicircular ends
+fn capsule(p1: Vec2, p2: Vec2, radius: f32) -> Vec<Vec2> {
+    let d = p2 - p1;
+    let base = d.1.atan2(d.0) + FRAC_PI_2;
+    let n = 2 * ARC_SEGMENTS;
+    let mut points = Vec::with_capacity(2 * usize::conv(n + 1));
+    for (centre, start) in [(p2, base - 2.0 * FRAC_PI_2), (p1, base)] {
+        for j in 0..=n {
+            let angle = start + 2.0 * FRAC_PI_2 * f32::conv(j) / f32::conv(n);
+            points.push(centre + Vec2(angle.cos(), angle.sin()) * radius);
+        }
+    }
+    points
+}
+
+/// Smallest pixel-aligned rect containing `quad`
+fn bounding_rect(quad: Quad) -> Rect {
+    // Limit to a range safely representable as `i32`
+    let (min, max) = (Vec2::splat(-1.0e9), Vec2::splat(1.0e9));
+    let a = quad.a.floor().max(min).min(max);
+    let b = quad.b.ceil().max(min).min(max);
+    Rect::new(a.into(), (b - a).max(Vec2::ZERO).into())
+}
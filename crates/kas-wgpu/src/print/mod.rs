@@ -0,0 +1,379 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Printing and PDF export (experimental)
+//!
+//! A [`Printer`] renders widgets to [`Page`]s using a vector draw backend
+//! ([`VectorShared`] and [`VectorDraw`]) in place of the GPU pipeline. Pages
+//! may be written to PDF via [`Printer::write_pdf`] or shown in a
+//! [`PrintPreview`].
+//!
+//! ```no_run
+//! # fn example(widget: &mut dyn kas::WidgetConfig) -> std::io::Result<()> {
+//! use kas_wgpu::print::{PageSetup, Printer};
+//!
+//! let mut printer = Printer::new(kas_theme::FlatTheme::new(), PageSetup::A4);
+//! printer.print_paginated(widget);
+//! printer.save_pdf("output.pdf")
+//! # }
+//! ```
+//!
+//! Widgets are configured and laid out anew by the printer (at the page's
+//! resolution, [`PageSetup::dpi`]). The widget tree should therefore be
+//! dedicated to printing (a copy of the displayed UI or a separate print
+//! layout); if a displayed widget tree is printed, its window must afterwards
+//! be reconfigured via [`TkAction::RECONFIGURE`].
+//!
+//! Limitations: glyphs are embedded as images (at the layout resolution),
+//! colour glyphs are not supported, gradient transparency is ignored and
+//! shaded theme elements are drawn flat. There is no support for native
+//! print dialogs.
+
+mod draw;
+mod pdf;
+mod preview;
+mod raster;
+
+pub use draw::{VectorDraw, VectorShared};
+pub use preview::PrintPreview;
+
+use draw::Pass;
+use kas::cast::*;
+use kas::draw::{DrawIface, PassId, SharedState};
+use kas::event::{self, EventState};
+use kas::geom::{Coord, Rect, Size, Vec2};
+use kas::layout::{SetRectMgr, SolveCache};
+use kas::theme::{DrawMgr, SizeMgr};
+#[allow(unused)]
+use kas::TkAction; // for doc-links
+use kas::WidgetConfig;
+use kas_theme::{Theme, ThemeConfig, Window};
+use std::cell::RefCell;
+use std::io::{self, Write};
+use std::path::Path;
+use std::rc::Rc;
+
+/// Page geometry and resolution
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct PageSetup {
+    /// Page size, in points (1/72 inch)
+    pub size: Vec2,
+    /// Margin on each side of the page, in points
+    pub margin: f32,
+    /// Layout resolution, in pixels per inch
+    ///
+    /// Widgets are laid out and drawn as on a screen of this resolution; the
+    /// theme's scale factor is `dpi / 96`.
+    pub dpi: f32,
+}
+
+impl Default for PageSetup {
+    fn default() -> Self {
+        PageSetup::A4
+    }
+}
+
+impl PageSetup {
+    /// ISO A4, portrait, with 0.5 inch margins
+    pub const A4: PageSetup = PageSetup {
+        size: Vec2(595.28, 841.89),
+        margin: 36.0,
+        dpi: 192.0,
+    };
+
+    /// US Letter, portrait, with 0.5 inch margins
+    pub const LETTER: PageSetup = PageSetup {
+        size: Vec2(612.0, 792.0),
+        margin: 36.0,
+        dpi: 192.0,
+    };
+
+    /// Swap width and height (chain style)
+    #[must_use]
+    pub fn landscape(mut self) -> Self {
+        self.size = Vec2(self.size.1, self.size.0);
+        self
+    }
+
+    /// Set the margin, in points (chain style)
+    #[must_use]
+    pub fn with_margin(mut self, margin: f32) -> Self {
+        self.margin = margin;
+        self
+    }
+
+    /// Set the layout resolution, in pixels per inch (chain style)
+    #[must_use]
+    pub fn with_dpi(mut self, dpi: f32) -> Self {
+        self.dpi = dpi;
+        self
+    }
+
+    /// Scale factor used for layout
+    #[inline]
+    pub fn scale_factor(&self) -> f32 {
+        self.dpi / 96.0
+    }
+
+    /// Size of the content area (within margins), in pixels
+    pub fn content_size(&self) -> Size {
+        let size = (self.size - 2.0 * self.margin).max(Vec2::ZERO) * (self.dpi / 72.0);
+        Size(size.0.cast_floor(), size.1.cast_floor())
+    }
+}
+
+/// A printed page
+///
+/// Pages may share draw data: when a widget is paginated, each page shows a
+/// different region of the same drawing.
+#[derive(Clone, Debug)]
+pub struct Page {
+    setup: PageSetup,
+    passes: Rc<Vec<Pass>>,
+    /// Position of the page's content area within the drawing, in pixels
+    offset: Vec2,
+}
+
+impl Page {
+    /// Page setup
+    #[inline]
+    pub fn setup(&self) -> &PageSetup {
+        &self.setup
+    }
+
+    /// Render to an image
+    ///
+    /// Returns the image size and RGBA (sRGB) data. `scale` is the number of
+    /// pixels per point. This uses a simple software rasteriser, intended for
+    /// previews.
+    pub fn render(&self, scale: f32) -> ((u32, u32), Vec<u8>) {
+        let bitmap = raster::render(self, scale);
+        (bitmap.size, bitmap.data)
+    }
+}
+
+/// Renders widgets to pages
+///
+/// Widgets are drawn using theme `T` over the vector backend. See the
+/// [module documentation](self).
+pub struct Printer<T: Theme<VectorShared>> {
+    theme: T,
+    shared: SharedState<VectorShared>,
+    config: Rc<RefCell<event::Config>>,
+    setup: PageSetup,
+    pages: Vec<Page>,
+}
+
+impl<T: Theme<VectorShared>> Printer<T> {
+    /// Construct
+    pub fn new(mut theme: T, setup: PageSetup) -> Self {
+        let raster_config = theme.config().raster().clone();
+        let mut shared = SharedState::new(VectorShared::new(&raster_config));
+        theme.init(&mut shared);
+        Printer {
+            theme,
+            shared,
+            config: Default::default(),
+            setup,
+            pages: vec![],
+        }
+    }
+
+    /// Get the page setup
+    #[inline]
+    pub fn setup(&self) -> &PageSetup {
+        &self.setup
+    }
+
+    /// Set the page setup
+    ///
+    /// This affects pages printed subsequently.
+    #[inline]
+    pub fn set_setup(&mut self, setup: PageSetup) {
+        self.setup = setup;
+    }
+
+    /// Access printed pages
+    #[inline]
+    pub fn pages(&self) -> &[Page] {
+        &self.pages
+    }
+
+    /// Remove and return printed pages
+    pub fn take_pages(&mut self) -> Vec<Page> {
+        std::mem::take(&mut self.pages)
+    }
+
+    /// Print `widget` to a single page
+    ///
+    /// The widget is sized to the page's content area; content which does not
+    /// fit is clipped.
+    pub fn print_page(&mut self, widget: &mut dyn WidgetConfig) {
+        self.print_pages(widget, |_, _, index| index == 0);
+    }
+
+    /// Print `widget` over as many pages as required
+    ///
+    /// The widget is sized to the page width and its ideal height for this
+    /// width, then split into page-sized slices. Slices are cut at page
+    /// boundaries without regard for content.
+    ///
+    /// Widgets which only draw visible content (e.g. `ListView`) should
+    /// instead be printed via [`Printer::print_pages`].
+    pub fn print_paginated(&mut self, widget: &mut dyn WidgetConfig) {
+        let (mut window, mut ev_state) = self.configure(widget);
+        let size = self.setup.content_size();
+
+        let mut cache = SolveCache::find_constraints(widget, SizeMgr::new(window.size_handle()));
+        self.layout(widget, &mut window, &mut ev_state, &mut cache, size);
+        let height = cache.ideal(true).1.max(size.1);
+        let full = Size(size.0, height);
+        if height > size.1 {
+            self.layout(widget, &mut window, &mut ev_state, &mut cache, full);
+        }
+
+        let passes = Rc::new(self.draw(widget, &mut window, &mut ev_state, full));
+        let mut y = 0;
+        while y < height {
+            self.pages.push(Page {
+                setup: self.setup,
+                passes: passes.clone(),
+                offset: Vec2(0.0, y.cast()),
+            });
+            y += size.1.max(1);
+        }
+    }
+
+    /// Print `widget` to one page per call to `prepare`
+    ///
+    /// This is a pagination hook: before each page, `prepare` is called with
+    /// the widget, a [`SetRectMgr`] and the page index; it should update the
+    /// widget's state for this page (e.g. the scroll offset of a `ListView`,
+    /// which may be set via [`kas::SaveState::restore_state`]) or return
+    /// `false` when all pages have been printed. The widget is then laid out
+    /// to the page's content area (of height
+    /// [`PageSetup::content_size`]`().1`) and drawn.
+    pub fn print_pages<W, F>(&mut self, widget: &mut W, mut prepare: F)
+    where
+        W: WidgetConfig + ?Sized,
+        F: FnMut(&mut W, &mut SetRectMgr, usize) -> bool,
+    {
+        let (mut window, mut ev_state) = self.configure(widget.as_widget_mut());
+        let size = self.setup.content_size();
+        let size_mgr = SizeMgr::new(window.size_handle());
+        let mut cache = SolveCache::find_constraints(widget.as_widget_mut(), size_mgr);
+
+        let mut index = 0;
+        loop {
+            let size_handle = window.size_handle();
+            let mut mgr = SetRectMgr::new(size_handle, &mut self.shared, &mut ev_state);
+            if !prepare(widget, &mut mgr, index) {
+                break;
+            }
+
+            let widget = widget.as_widget_mut();
+            cache.invalidate_rule_cache();
+            self.layout(widget, &mut window, &mut ev_state, &mut cache, size);
+            let passes = self.draw(widget, &mut window, &mut ev_state, size);
+            self.pages.push(Page {
+                setup: self.setup,
+                passes: Rc::new(passes),
+                offset: Vec2::ZERO,
+            });
+            index += 1;
+        }
+    }
+
+    /// Render `widget` to an image of the given `size` (in pixels)
+    ///
+    /// This is a headless render (without a GPU or window), drawn at the
+    /// resolution of the current [`PageSetup::dpi`] over the theme's clear
+    /// colour. Page size and margins are ignored. Output is deterministic,
+    /// thus this may be used for regression tests (see [`crate::golden`]).
+    ///
+    /// Returns the image size and RGBA (sRGB) data.
+    pub fn render_image(
+        &mut self,
+        widget: &mut dyn WidgetConfig,
+        size: Size,
+    ) -> ((u32, u32), Vec<u8>) {
+        let (mut window, mut ev_state) = self.configure(widget);
+        let mut cache = SolveCache::find_constraints(widget, SizeMgr::new(window.size_handle()));
+        self.layout(widget, &mut window, &mut ev_state, &mut cache, size);
+        let passes = self.draw(widget, &mut window, &mut ev_state, size);
+
+        let size = (size.0.cast(), size.1.cast());
+        let bitmap = raster::render_pixels(&passes, size, self.theme.clear_color());
+        (bitmap.size, bitmap.data)
+    }
+
+    /// Write printed pages as a PDF document
+    pub fn write_pdf<W: Write>(&self, out: W) -> io::Result<()> {
+        pdf::write_pdf(&self.pages, out)
+    }
+
+    /// Write printed pages to a PDF file at `path`
+    pub fn save_pdf<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let file = std::fs::File::create(path)?;
+        self.write_pdf(io::BufWriter::new(file))
+    }
+
+    /// Construct a preview of printed pages
+    ///
+    /// The result is a dialog window; show it via [`kas::event::EventMgr::add_window`].
+    pub fn preview(&self) -> PrintPreview {
+        PrintPreview::new(&self.pages)
+    }
+
+    fn configure(&mut self, widget: &mut dyn WidgetConfig) -> (T::Window, EventState) {
+        let scale_factor = self.setup.scale_factor();
+        let window = self.theme.new_window(scale_factor);
+        let mut ev_state = EventState::new(self.config.clone(), scale_factor);
+        let mut mgr = SetRectMgr::new(window.size_handle(), &mut self.shared, &mut ev_state);
+        mgr.configure_root(widget);
+        (window, ev_state)
+    }
+
+    fn layout(
+        &mut self,
+        widget: &mut dyn WidgetConfig,
+        window: &mut T::Window,
+        ev_state: &mut EventState,
+        cache: &mut SolveCache,
+        size: Size,
+    ) {
+        let mut mgr = SetRectMgr::new(window.size_handle(), &mut self.shared, ev_state);
+        cache.apply_rect(widget, &mut mgr, Rect::new(Coord::ZERO, size), true);
+    }
+
+    fn draw(
+        &mut self,
+        widget: &mut dyn WidgetConfig,
+        window: &mut T::Window,
+        ev_state: &mut EventState,
+        size: Size,
+    ) -> Vec<Pass> {
+        let mut draw = VectorDraw::new(Rect::new(Coord::ZERO, size));
+        let iface = DrawIface {
+            draw: &mut draw,
+            shared: &mut self.shared,
+            pass: PassId::new(0),
+        };
+
+        #[cfg(not(feature = "gat"))]
+        unsafe {
+            // Safety: lifetimes do not escape the returned draw_handle value.
+            let mut draw_handle = self.theme.draw_handle(iface, window);
+            widget.draw(DrawMgr::new(&mut draw_handle, ev_state, false));
+        }
+        #[cfg(feature = "gat")]
+        {
+            let mut draw_handle = self.theme.draw_handle(iface, window);
+            widget.draw(DrawMgr::new(&mut draw_handle, ev_state, false));
+        }
+
+        draw.into_passes()
+    }
+}
@@ -0,0 +1,498 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Minimal PDF writer
+//!
+//! Output is uncompressed PDF 1.4. Shapes are written as vector paths while
+//! glyphs and images are embedded as (soft-masked) images.
+
+use super::draw::{Bitmap, Fill, Paint};
+use super::Page;
+use kas::draw::color::{Rgba, Rgba8Srgb};
+use kas::draw::{Affine, Gradient};
+use kas::geom::{Quad, Vec2};
+use std::collections::{BTreeSet, HashMap};
+use std::io::{self, Write};
+use std::rc::Rc;
+
+/// Write `pages` as a PDF document
+pub(crate) fn write_pdf<W: Write>(pages: &[Page], out: W) -> io::Result<()> {
+    let mut doc = Document::default();
+    let catalog = doc.reserve();
+    let tree = doc.reserve();
+
+    let mut kids = vec![];
+    for page in pages {
+        kids.push(format!("{} 0 R", doc.page(page, tree)?));
+    }
+    let dict = format!(
+        "<< /Type /Pages /Kids [{}] /Count {} >>",
+        kids.join(" "),
+        kids.len()
+    );
+    doc.set(tree, dict.into_bytes());
+    let dict = format!("<< /Type /Catalog /Pages {} 0 R >>", tree);
+    doc.set(catalog, dict.into_bytes());
+
+    doc.write(catalog, out)
+}
+
+/// Resources used by a page
+#[derive(Default)]
+struct Resources {
+    xobjects: BTreeSet<usize>,
+    states: BTreeSet<usize>,
+    shadings: BTreeSet<usize>,
+}
+
+impl Resources {
+    fn dict(&self) -> String {
+        let list = |prefix: &str, ids: &BTreeSet<usize>| {
+            let refs: Vec<_> = ids
+                .iter()
+                .map(|id| format!("/{}{} {} 0 R", prefix, id, id))
+                .collect();
+            refs.join(" ")
+        };
+        format!(
+            "<< /XObject << {} >> /ExtGState << {} >> /Shading << {} >> >>",
+            list("Im", &self.xobjects),
+            list("GS", &self.states),
+            list("Sh", &self.shadings),
+        )
+    }
+}
+
+/// Document objects under construction
+///
+/// Object identifiers start from 1. Images are shared between pages; since
+/// pages hold a reference to each image, its address serves as a key.
+#[derive(Default)]
+struct Document {
+    objects: Vec<Vec<u8>>,
+    images: HashMap<*const Bitmap, usize>,
+    masks: HashMap<*const Bitmap, usize>,
+    mask_images: HashMap<(*const Bitmap, [u8; 3]), usize>,
+    states: HashMap<u8, usize>,
+}
+
+impl Document {
+    fn reserve(&mut self) -> usize {
+        self.add(vec![])
+    }
+
+    fn set(&mut self, id: usize, object: Vec<u8>) {
+        self.objects[id - 1] = object;
+    }
+
+    fn add(&mut self, object: Vec<u8>) -> usize {
+        self.objects.push(object);
+        self.objects.len()
+    }
+
+    fn add_stream(&mut self, dict: &str, data: &[u8]) -> usize {
+        let mut object = format!("<< {} /Length {} >>\nstream\n", dict, data.len()).into_bytes();
+        object.extend_from_slice(data);
+        object.extend_from_slice(b"\nendstream");
+        self.add(object)
+    }
+
+    fn page(&mut self, page: &Page, parent: usize) -> io::Result<usize> {
+        let setup = page.setup();
+        let mut res = Resources::default();
+        let mut s = vec![];
+
+        // Map pixels (y-down) to points (y-up), with origin at the top-left
+        // of the content area
+        let scale = 72.0 / setup.dpi;
+        let top = setup.size.1 - setup.margin;
+        writeln!(
+            s,
+            "q {} 0 0 {} {} {} cm",
+            num(scale),
+            num(-scale),
+            num(setup.margin),
+            num(top)
+        )?;
+        let content = Vec2::from(setup.content_size());
+        writeln!(s, "0 0 {} {} re W n", num(content.0), num(content.1))?;
+        writeln!(
+            s,
+            "1 0 0 1 {} {} cm",
+            num(-page.offset.0),
+            num(-page.offset.1)
+        )?;
+
+        for pass in page.passes.iter() {
+            let clip = pass.clip;
+            let (pos, size) = (clip.pos, clip.size);
+            writeln!(s, "q {} {} {} {} re W n", pos.0, pos.1, size.0, size.1)?;
+            if pass.transform != Affine::IDENTITY {
+                writeln!(s, "{} cm", matrix(&pass.transform))?;
+            }
+            for fill in pass.iter() {
+                self.fill(&mut s, &mut res, fill)?;
+            }
+            writeln!(s, "Q")?;
+        }
+        writeln!(s, "Q")?;
+
+        let contents = self.add_stream("", &s);
+        let dict = format!(
+            "<< /Type /Page /Parent {} 0 R /MediaBox [0 0 {} {}] /Resources {} /Contents {} 0 R >>",
+            parent,
+            num(setup.size.0),
+            num(setup.size.1),
+            res.dict(),
+            contents,
+        );
+        Ok(self.add(dict.into_bytes()))
+    }
+
+    fn fill(&mut self, s: &mut Vec<u8>, res: &mut Resources, fill: &Fill) -> io::Result<()> {
+        writeln!(s, "q")?;
+        for path in fill.paths.iter().filter(|path| !path.is_empty()) {
+            writeln!(s, "{} {} m", num(path[0].0), num(path[0].1))?;
+            for p in &path[1..] {
+                writeln!(s, "{} {} l", num(p.0), num(p.1))?;
+            }
+            writeln!(s, "h")?;
+        }
+
+        match &fill.paint {
+            Paint::Solid(col) => {
+                let col = srgba(*col);
+                if col[3] == 0 {
+                    writeln!(s, "n")?;
+                } else {
+                    self.alpha(s, res, col[3])?;
+                    writeln!(s, "{} rg f*", rgb(col))?;
+                }
+            }
+            Paint::Gradient(grad) => {
+                writeln!(s, "W* n")?;
+                if let Gradient::Radial { center, radius, .. } = *grad {
+                    if !(radius.0 > 0.0 && radius.1 > 0.0) {
+                        return writeln!(s, "Q");
+                    }
+                    let (r, c) = (radius, center);
+                    writeln!(
+                        s,
+                        "{} 0 0 {} {} {} cm",
+                        num(r.0),
+                        num(r.1),
+                        num(c.0),
+                        num(c.1)
+                    )?;
+                }
+                let id = self.shading(grad);
+                res.shadings.insert(id);
+                writeln!(s, "/Sh{} sh", id)?;
+            }
+            Paint::Image(image, quad) => {
+                writeln!(s, "W* n")?;
+                let id = self.image(image);
+                res.xobjects.insert(id);
+                writeln!(s, "{} cm /Im{} Do", place(*quad), id)?;
+            }
+            Paint::Mask(mask, quad, col) => {
+                writeln!(s, "W* n")?;
+                let col = srgba(*col);
+                self.alpha(s, res, col[3])?;
+                let id = self.mask_image(mask, col);
+                res.xobjects.insert(id);
+                writeln!(s, "{} cm /Im{} Do", place(*quad), id)?;
+            }
+        }
+        writeln!(s, "Q")
+    }
+
+    /// Set fill alpha via a graphics state
+    fn alpha(&mut self, s: &mut Vec<u8>, res: &mut Resources, alpha: u8) -> io::Result<()> {
+        if alpha == 255 {
+            return Ok(());
+        }
+        let id = match self.states.get(&alpha) {
+            Some(id) => *id,
+            None => {
+                let a = num(f32::from(alpha) / 255.0);
+                let dict = format!("<< /Type /ExtGState /ca {} /CA {} >>", a, a);
+                let id = self.add(dict.into_bytes());
+                self.states.insert(alpha, id);
+                id
+            }
+        };
+        res.states.insert(id);
+        writeln!(s, "/GS{} gs", id)
+    }
+
+    /// Add a shading
+    ///
+    /// Radial gradients are defined over the unit circle. Alpha is ignored.
+    fn shading(&mut self, grad: &Gradient) -> usize {
+        let (kind, coords, col1, col2) = match *grad {
+            Gradient::Linear {
+                start,
+                end,
+                col1,
+                col2,
+            } => {
+                let coords = [start.0, start.1, end.0, end.1].map(num).join(" ");
+                (2, coords, col1, col2)
+            }
+            Gradient::Radial { col1, col2, .. } => (3, "0 0 0 0 0 1".to_string(), col1, col2),
+        };
+        let dict = format!(
+            "<< /ShadingType {} /ColorSpace /DeviceRGB /Coords [{}] /Function << /FunctionType 2 /Domain [0 1] /C0 [{}] /C1 [{}] /N 1 >> /Extend [true true] >>",
+            kind,
+            coords,
+            rgb(srgba(col1)),
+            rgb(srgba(col2)),
+        );
+        self.add(dict.into_bytes())
+    }
+
+    /// Add an RGBA image
+    fn image(&mut self, image: &Rc<Bitmap>) -> usize {
+        let key = Rc::as_ptr(image);
+        if let Some(id) = self.images.get(&key) {
+            return *id;
+        }
+        let rgb: Vec<u8> = image
+            .data
+            .chunks_exact(4)
+            .flat_map(|p| [p[0], p[1], p[2]])
+            .collect();
+        let alpha: Vec<u8> = image.data.chunks_exact(4).map(|p| p[3]).collect();
+        let smask = self.add_stream(&image_dict(image.size, "DeviceGray"), &alpha);
+        let dict = format!(
+            "{} /SMask {} 0 R",
+            image_dict(image.size, "DeviceRGB"),
+            smask
+        );
+        let id = self.add_stream(&dict, &rgb);
+        self.images.insert(key, id);
+        id
+    }
+
+    /// Add an alpha mask as a uniformly coloured image
+    fn mask_image(&mut self, mask: &Rc<Bitmap>, col: [u8; 4]) -> usize {
+        let key = (Rc::as_ptr(mask), [col[0], col[1], col[2]]);
+        if let Some(id) = self.mask_images.get(&key) {
+            return *id;
+        }
+        let smask = match self.masks.get(&key.0) {
+            Some(id) => *id,
+            None => {
+                let id = self.add_stream(&image_dict(mask.size, "DeviceGray"), &mask.data);
+                self.masks.insert(key.0, id);
+                id
+            }
+        };
+        let rgb = key.1.repeat(mask.data.len());
+        let dict = format!(
+            "{} /SMask {} 0 R",
+            image_dict(mask.size, "DeviceRGB"),
+            smask
+        );
+        let id = self.add_stream(&dict, &rgb);
+        self.mask_images.insert(key, id);
+        id
+    }
+
+    fn write<W: Write>(&self, root: usize, mut out: W) -> io::Result<()> {
+        let header = b"%PDF-1.4\n%\xE2\xE3\xCF\xD3\n";
+        out.write_all(header)?;
+        let mut offset = header.len();
+
+        let mut offsets = Vec::with_capacity(self.objects.len());
+        for (i, object) in self.objects.iter().enumerate() {
+            offsets.push(offset);
+            let head = format!("{} 0 obj\n", i + 1);
+            let tail = b"\nendobj\n";
+            out.write_all(head.as_bytes())?;
+            out.write_all(object)?;
+            out.write_all(tail)?;
+            offset += head.len() + object.len() + tail.len();
+        }
+
+        // Cross-reference entries must be exactly 20 bytes
+        let size = self.objects.len() + 1;
+        writeln!(out, "xref\n0 {}\n0000000000 65535 f ", size)?;
+        for offset in offsets {
+            writeln!(out, "{:010} 00000 n ", offset)?;
+        }
+        writeln!(
+            out,
+            "trailer\n<< /Size {} /Root {} 0 R >>\nstartxref\n{}\n%%EOF",
+            size, root, offset
+        )
+    }
+}
+
+fn image_dict(size: (u32, u32), color_space: &str) -> String {
+    format!(
+        "/Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /{} /BitsPerComponent 8",
+        size.0, size.1, color_space
+    )
+}
+
+/// Format a number compactly
+fn num(x: f32) -> String {
+    let s = format!("{:.3}", x);
+    match s.trim_end_matches('0').trim_end_matches('.') {
+        "" | "-" | "-0" => "0".to_string(),
+        s => s.to_string(),
+    }
+}
+
+fn srgba(col: Rgba) -> [u8; 4] {
+    Rgba8Srgb::from(col).into()
+}
+
+fn rgb(col: [u8; 4]) -> String {
+    let c = |i: usize| num(f32::from(col[i]) / 255.0);
+    format!("{} {} {}", c(0), c(1), c(2))
+}
+
+fn matrix(t: &Affine) -> String {
+    [t.a.0, t.a.1, t.b.0, t.b.1, t.t.0, t.t.1]
+        .map(num)
+        .join(" ")
+}
+
+/// Matrix mapping the unit square to `quad`, with image row 0 at the top
+///
+/// (Page coordinates are y-down.)
+fn place(quad: Quad) -> String {
+    let size = quad.size();
+    let (a, b) = (quad.a, quad.b);
+    [size.0, 0.0, 0.0, -size.1, a.0, b.1].map(num).join(" ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::print::draw::Pass;
+    use crate::print::PageSetup;
+    use kas::geom::{Coord, Rect, Size};
+
+    fn page(items: Vec<Fill>) -> Page {
+        let pass = Pass {
+            clip: Rect::new(Coord::ZERO, Size(100, 100)),
+            transform: Affine::IDENTITY,
+            elevation: 0,
+            background: vec![],
+            items,
+        };
+        Page {
+            setup: PageSetup::A4,
+            passes: Rc::new(vec![pass]),
+            offset: Vec2::ZERO,
+        }
+    }
+
+    fn square(paint: Paint) -> Fill {
+        let (a, b) = (Vec2(10.0, 10.0), Vec2(20.0, 20.0));
+        Fill {
+            paths: vec![vec![a, Vec2(b.0, a.1), b, Vec2(a.0, b.1)]],
+            paint,
+        }
+    }
+
+    fn write(pages: &[Page]) -> Vec<u8> {
+        let mut out = vec![];
+        write_pdf(pages, &mut out).unwrap();
+        out
+    }
+
+    // The binary comment in the header is not UTF-8
+    fn text(pdf: &[u8]) -> String {
+        String::from_utf8_lossy(pdf).into_owned()
+    }
+
+    #[test]
+    fn num_format() {
+        assert_eq!(num(0.0), "0");
+        assert_eq!(num(-0.0001), "0");
+        assert_eq!(num(1.0), "1");
+        assert_eq!(num(-2.5), "-2.5");
+        assert_eq!(num(0.12345), "0.123");
+        assert_eq!(num(100.0), "100");
+    }
+
+    #[test]
+    fn structure() {
+        let fill = square(Paint::Solid(Rgba::BLACK));
+        let bytes = write(&[page(vec![fill.clone()]), page(vec![fill])]);
+        let pdf = text(&bytes);
+
+        assert!(pdf.starts_with("%PDF-1.4\n"));
+        assert!(pdf.ends_with("%%EOF\n"));
+        assert!(pdf.contains("/Type /Catalog"));
+        assert!(pdf.contains("/Count 2"));
+        assert_eq!(pdf.matches("/Type /Page ").count(), 2);
+        assert!(pdf.contains("0 0 0 rg f*"));
+
+        // startxref gives the byte offset of the cross-reference table, and
+        // each entry the byte offset of its object
+        let tail = pdf.rsplit("startxref\n").next().unwrap();
+        let xref: usize = tail.lines().next().unwrap().parse().unwrap();
+        let table = std::str::from_utf8(&bytes[xref..]).unwrap();
+        let mut lines = table.lines();
+        assert_eq!(lines.next(), Some("xref"));
+        let size: usize = lines.next().unwrap()[2..].parse().unwrap();
+        assert!(table.contains(&format!("/Size {}", size)));
+        assert_eq!(lines.next(), Some("0000000000 65535 f "));
+        for i in 1..size {
+            let line = lines.next().unwrap();
+            assert_eq!(line.len(), 19);
+            let offset: usize = line[..10].parse().unwrap();
+            let head = format!("{} 0 obj\n", i);
+            assert!(bytes[offset..].starts_with(head.as_bytes()));
+        }
+        assert_eq!(lines.next(), Some("trailer"));
+    }
+
+    #[test]
+    fn alpha_states() {
+        let half = square(Paint::Solid(Rgba::ga(0.0, 0.5)));
+        let clear = square(Paint::Solid(Rgba::TRANSPARENT));
+        let pdf = text(&write(&[page(vec![half.clone(), half, clear])]));
+
+        // One shared graphics state; transparent fills are not painted
+        assert_eq!(pdf.matches("/Type /ExtGState").count(), 1);
+        assert_eq!(pdf.matches(" gs\n").count(), 2);
+        assert_eq!(pdf.matches("rg f*").count(), 2);
+        assert!(pdf.contains("h\nn\n"));
+    }
+
+    #[test]
+    fn shared_images() {
+        let image = Rc::new(Bitmap {
+            size: (2, 1),
+            data: vec![255, 0, 0, 255, 0, 0, 255, 128],
+        });
+        let mask = Rc::new(Bitmap {
+            size: (1, 1),
+            data: vec![200],
+        });
+        let quad = Quad::from_coords(Vec2(0.0, 0.0), Vec2(2.0, 1.0));
+        let fills = || {
+            vec![
+                square(Paint::Image(image.clone(), quad)),
+                square(Paint::Mask(mask.clone(), quad, Rgba::BLACK)),
+                square(Paint::Mask(mask.clone(), quad, Rgba::WHITE)),
+            ]
+        };
+        let pdf = text(&write(&[page(fills()), page(fills())]));
+
+        // Image: RGB + soft mask. Mask: one shared alpha + one RGB per colour.
+        assert_eq!(pdf.matches("/Subtype /Image").count(), 5);
+        assert_eq!(pdf.matches("/ColorSpace /DeviceGray").count(), 2);
+        assert_eq!(pdf.matches(" Do\n").count(), 6);
+        assert!(pdf.contains("/Width 2 /Height 1"));
+    }
+}
@@ -0,0 +1,233 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Print preview widget
+
+use super::draw::Bitmap;
+use super::{raster, Page};
+use kas::draw::{ImageFormat, ImageId};
+use kas::event::{Command, ScrollDelta};
+use kas::layout::{SpriteDisplay, SpriteScaling};
+use kas::prelude::*;
+use kas::WindowId;
+
+/// Resolution of preview images, in pixels per point
+const PREVIEW_SCALE: f32 = 1.0;
+
+widget! {
+    /// A print preview dialog
+    ///
+    /// Displays one page at a time, scaled to fit. With navigation focus, the
+    /// page may be changed with the arrow keys, <kbd>PageUp</kbd>,
+    /// <kbd>PageDown</kbd>, <kbd>Home</kbd> and <kbd>End</kbd>, or by
+    /// scrolling over the widget. A containing widget may additionally provide
+    /// buttons using [`PrintPreview::set_page`].
+    ///
+    /// This is a (modal) [`kas::Window`]; <kbd>Escape</kbd> closes it. The
+    /// preview image is freed when the window is closed. When embedded within
+    /// another window instead, call [`PrintPreview::free_image`] on removal.
+    ///
+    /// Pages are rendered by a software rasteriser on construction; the
+    /// preview is thus only an approximation of printed output.
+    #[derive(Clone, Debug)]
+    #[widget{
+        key_nav = true;
+    }]
+    pub struct PrintPreview {
+        #[widget_core]
+        core: CoreData,
+        title: String,
+        sprite: SpriteDisplay,
+        pages: Vec<Bitmap>,
+        page: usize,
+        image: Option<(ImageId, (u32, u32))>,
+        shown: Option<usize>,
+    }
+
+    impl Self {
+        /// Construct from printed pages
+        ///
+        /// See also [`super::Printer::preview`].
+        pub fn new(pages: &[Page]) -> Self {
+            let pages: Vec<_> = pages
+                .iter()
+                .map(|page| raster::render(page, PREVIEW_SCALE))
+                .collect();
+            let size = pages
+                .first()
+                .map(|page| Size(page.size.0.cast(), page.size.1.cast()))
+                .unwrap_or(Size::ZERO);
+            PrintPreview {
+                core: Default::default(),
+                title: "Print preview".to_string(),
+                sprite: SpriteDisplay {
+                    margins: Default::default(),
+                    size,
+                    scaling: SpriteScaling::Real,
+                    aspect: Default::default(),
+                    stretch: Stretch::High,
+                },
+                pages,
+                page: 0,
+                image: None,
+                shown: None,
+            }
+        }
+
+        /// Set the window title (chain style)
+        #[must_use]
+        pub fn with_title<A: ToString>(mut self, title: A) -> Self {
+            self.title = title.to_string();
+            self
+        }
+
+        /// Get the number of pages
+        #[inline]
+        pub fn num_pages(&self) -> usize {
+            self.pages.len()
+        }
+
+        /// Get the index of the displayed page
+        #[inline]
+        pub fn page(&self) -> usize {
+            self.page
+        }
+
+        /// Set the displayed page
+        ///
+        /// The index is clamped to the available pages.
+        pub fn set_page(&mut self, page: usize) -> TkAction {
+            let page = page.min(self.pages.len().saturating_sub(1));
+            if page == self.page {
+                return TkAction::empty();
+            }
+            self.page = page;
+            TkAction::REDRAW
+        }
+
+        /// Free the preview image
+        ///
+        /// This is called automatically when the preview's window is closed.
+        /// The image is re-allocated if the preview is drawn again.
+        pub fn free_image(&mut self, mgr: &mut EventMgr) {
+            if let Some((id, _)) = self.image.take() {
+                mgr.draw_shared(|ds| ds.image_free(id));
+            }
+            self.shown = None;
+        }
+
+        fn upload(&mut self, mgr: &mut SetRectMgr) {
+            self.shown = Some(self.page);
+            let bitmap = match self.pages.get(self.page) {
+                Some(bitmap) => bitmap,
+                None => return,
+            };
+            let ds = mgr.draw_shared();
+            if let Some((id, size)) = self.image {
+                if size != bitmap.size {
+                    ds.image_free(id);
+                    self.image = None;
+                }
+            }
+            if self.image.is_none() {
+                match ds.image_alloc(bitmap.size) {
+                    Ok(id) => self.image = Some((id, bitmap.size)),
+                    Err(e) => {
+                        log::warn!("PrintPreview: failed to allocate image: {}", e);
+                        return;
+                    }
+                }
+            }
+            if let Some((id, _)) = self.image {
+                ds.image_upload(id, &bitmap.data, ImageFormat::Rgba8);
+            }
+        }
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
+            self.sprite.size_rules(size_mgr, axis)
+        }
+
+        fn set_rect(&mut self, _: &mut SetRectMgr, rect: Rect, align: AlignHints) {
+            self.core.rect = self.sprite.align_rect(rect, align);
+        }
+
+        fn draw(&mut self, mut draw: DrawMgr) {
+            let mut draw = draw.with_core(self.core_data());
+            if self.shown != Some(self.page) {
+                draw.set_rect_mgr(|mgr| self.upload(mgr));
+            }
+            if let Some((id, _)) = self.image {
+                draw.image(id, self.rect());
+            }
+        }
+    }
+
+    impl Handler for Self {
+        type Msg = VoidMsg;
+
+        fn handle(&mut self, mgr: &mut EventMgr, event: Event) -> Response<Self::Msg> {
+            let prev = self.page.saturating_sub(1);
+            let next = self.page + 1;
+            let page = match event {
+                Event::Command(cmd, _) => match cmd {
+                    Command::Left | Command::Up | Command::PageUp => prev,
+                    Command::Right | Command::Down | Command::PageDown => next,
+                    Command::Home | Command::DocHome => 0,
+                    Command::End | Command::DocEnd => usize::MAX,
+                    Command::Escape => {
+                        mgr.send_action(TkAction::CLOSE);
+                        return Response::Used;
+                    }
+                    _ => return Response::Unused,
+                },
+                Event::Scroll(delta) => {
+                    let y = match delta {
+                        ScrollDelta::LineDelta(_, y) => y,
+                        ScrollDelta::PixelDelta(d) => d.1.cast(),
+                    };
+                    match y {
+                        y if y > 0.0 => prev,
+                        y if y < 0.0 => next,
+                        _ => return Response::Used,
+                    }
+                }
+                _ => return Response::Unused,
+            };
+            *mgr |= self.set_page(page);
+            Response::Used
+        }
+    }
+
+    impl kas::Window for Self {
+        fn title(&self) -> &str {
+            &self.title
+        }
+
+        fn icon(&self) -> Option<kas::Icon> {
+            None
+        }
+
+        fn restrict_dimensions(&self) -> (bool, bool) {
+            (true, false)
+        }
+
+        fn modal(&self) -> bool {
+            true
+        }
+
+        fn add_popup(&mut self, _: &mut EventMgr, _: WindowId, _: kas::Popup) {
+            panic!("PrintPreview does not support pop-ups");
+        }
+
+        fn remove_popup(&mut self, _: &mut EventMgr, _: WindowId) {}
+        fn resize_popups(&mut self, _: &mut SetRectMgr) {}
+
+        fn handle_closure(&mut self, mgr: &mut EventMgr) {
+            self.free_image(mgr);
+        }
+    }
+}
@@ -0,0 +1,256 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Software rasteriser for recorded pages
+//!
+//! This is intended for previews: output is not gamma-correct and
+//! anti-aliasing is limited to 2×2 super-sampling.
+
+use super::draw::{mix, Bitmap, Fill, Paint, Pass};
+use super::Page;
+use kas::cast::*;
+use kas::draw::color::{Rgba, Rgba8Srgb};
+use kas::draw::{Affine, Gradient};
+use kas::geom::{Quad, Vec2};
+
+/// Super-sampling factor per axis
+const SS: u32 = 2;
+
+/// Render a page to an RGBA bitmap with `scale` pixels per point
+pub(crate) fn render(page: &Page, scale: f32) -> Bitmap {
+    let setup = page.setup();
+    let size = setup.size * scale;
+    let size = (u32::conv_ceil(size.0).max(1), u32::conv_ceil(size.1).max(1));
+
+    let k = scale * f32::conv(SS);
+    let margin = Vec2::splat(setup.margin * k);
+    let content = setup.content_size();
+    let px = k * 72.0 / setup.dpi;
+    let page_clip = Quad::from_coords(margin, margin + Vec2::from(content) * px);
+    let to_page = Affine {
+        a: Vec2(px, 0.0),
+        b: Vec2(0.0, px),
+        t: margin - page.offset * px,
+    };
+
+    render_passes(&page.passes, size, to_page, page_clip, [255; 4])
+}
+
+/// Render passes to an RGBA bitmap of `size`, one pixel per pass pixel
+///
+/// The bitmap is first filled with `background`.
+pub(crate) fn render_pixels(passes: &[Pass], size: (u32, u32), background: Rgba) -> Bitmap {
+    let k = f32::conv(SS);
+    let to_target = Affine {
+        a: Vec2(k, 0.0),
+        b: Vec2(0.0, k),
+        t: Vec2::ZERO,
+    };
+    let clip = Quad::from_coords(Vec2::ZERO, Vec2(size.0.cast(), size.1.cast()) * k);
+    render_passes(passes, size, to_target, clip, srgba(background))
+}
+
+/// Render passes, mapped to super-sampled target coordinates by `to_target`
+fn render_passes(
+    passes: &[Pass],
+    size: (u32, u32),
+    to_target: Affine,
+    clip: Quad,
+    background: [u8; 4],
+) -> Bitmap {
+    let mut target = Target::new((size.0 * SS, size.1 * SS), background);
+
+    for pass in passes {
+        let pass_clip = to_target.transform_bounds(pass.clip.into());
+        let pass_clip = match pass_clip.intersection(&clip) {
+            Some(clip) => clip,
+            None => continue,
+        };
+        let transform = pass.transform.then(to_target);
+        let inverse = match transform.inverse() {
+            Some(inv) => inv,
+            None => continue,
+        };
+        for fill in pass.iter() {
+            target.fill(fill, &transform, &inverse, pass_clip);
+        }
+    }
+
+    target.downsample(size)
+}
+
+struct Target {
+    size: (u32, u32),
+    data: Vec<u8>,
+}
+
+impl Target {
+    fn new(size: (u32, u32), background: [u8; 4]) -> Self {
+        let len = usize::conv(size.0) * usize::conv(size.1);
+        Target {
+            size,
+            data: background.repeat(len),
+        }
+    }
+
+    fn fill(&mut self, fill: &Fill, transform: &Affine, inverse: &Affine, clip: Quad) {
+        let edges: Vec<(Vec2, Vec2)> = fill
+            .paths
+            .iter()
+            .flat_map(|path| {
+                let n = path.len();
+                (0..n).map(move |i| (path[i], path[(i + 1) % n]))
+            })
+            .map(|(p, q)| (transform.transform_point(p), transform.transform_point(q)))
+            .collect();
+        if edges.is_empty() {
+            return;
+        }
+
+        let mut bounds = Quad::from_coords(edges[0].0, edges[0].0);
+        for (p, _) in &edges {
+            bounds.a = bounds.a.min(*p);
+            bounds.b = bounds.b.max(*p);
+        }
+        let bounds = match bounds.intersection(&clip) {
+            Some(b) => b,
+            None => return,
+        };
+        let max = Vec2(self.size.0.cast(), self.size.1.cast());
+        let (a, b) = (bounds.a.max(Vec2::ZERO), bounds.b.min(max));
+        let (x0, x1) = (u32::conv_floor(a.0), u32::conv_ceil(b.0));
+        let (y0, y1) = (u32::conv_floor(a.1), u32::conv_ceil(b.1));
+
+        let mut crossings = vec![];
+        for y in y0..y1 {
+            let yc = f32::conv(y) + 0.5;
+            crossings.clear();
+            for (p, q) in &edges {
+                if (p.1 <= yc) != (q.1 <= yc) {
+                    let x = p.0 + (yc - p.1) * (q.0 - p.0) / (q.1 - p.1);
+                    // Skip NaN (from non-finite input) so that sorting is total
+                    if !x.is_nan() {
+                        crossings.push(x);
+                    }
+                }
+            }
+            crossings.sort_by(|a, b| a.partial_cmp(b).unwrap());
+
+            // Even-odd rule: fill between pairs of crossings
+            for span in crossings.chunks_exact(2) {
+                let start = (span[0] - 0.5).ceil().max(f32::conv(x0));
+                let end = (span[1] - 0.5).ceil().min(f32::conv(x1));
+                if start >= end {
+                    continue;
+                }
+                for x in u32::conv_floor(start)..u32::conv_floor(end) {
+                    let p = Vec2(f32::conv(x) + 0.5, yc);
+                    if let Some(col) = sample(&fill.paint, inverse.transform_point(p)) {
+                        self.blend(x, y, col);
+                    }
+                }
+            }
+        }
+    }
+
+    fn blend(&mut self, x: u32, y: u32, col: [u8; 4]) {
+        let i = 4 * (usize::conv(y) * usize::conv(self.size.0) + usize::conv(x));
+        let alpha = u32::from(col[3]);
+        for (dst, src) in self.data[i..i + 3].iter_mut().zip(&col[..3]) {
+            let v = (u32::from(*src) * alpha + u32::from(*dst) * (255 - alpha) + 127) / 255;
+            *dst = v.cast();
+        }
+    }
+
+    fn downsample(self, size: (u32, u32)) -> Bitmap {
+        let mut data = Vec::with_capacity(usize::conv(size.0) * usize::conv(size.1) * 4);
+        let n = SS * SS;
+        for y in 0..size.1 {
+            for x in 0..size.0 {
+                let mut sum = [0u32; 3];
+                for sy in 0..SS {
+                    for sx in 0..SS {
+                        let (px, py) = (x * SS + sx, y * SS + sy);
+                        let i = 4 * usize::conv(py * self.size.0 + px);
+                        for (s, v) in sum.iter_mut().zip(&self.data[i..i + 3]) {
+                            *s += u32::from(*v);
+                        }
+                    }
+                }
+                data.extend(sum.iter().map(|s| u8::conv((s + n / 2) / n)));
+                data.push(255);
+            }
+        }
+        Bitmap { size, data }
+    }
+}
+
+/// Sample `paint` at point `p` (in pass coordinates), yielding sRGBA
+fn sample(paint: &Paint, p: Vec2) -> Option<[u8; 4]> {
+    Some(match paint {
+        Paint::Solid(col) => srgba(*col),
+        Paint::Gradient(grad) => srgba(gradient_at(grad, p)),
+        Paint::Image(image, quad) => texel(image, *quad, p, 4)?,
+        Paint::Mask(mask, quad, col) => {
+            let alpha = texel(mask, *quad, p, 1)?[0];
+            let mut col = srgba(*col);
+            col[3] = u8::conv((u32::from(col[3]) * u32::from(alpha) + 127) / 255);
+            col
+        }
+    })
+}
+
+/// Nearest texel of `image` mapped to `quad`
+fn texel(image: &Bitmap, quad: Quad, p: Vec2, bytes: usize) -> Option<[u8; 4]> {
+    let size = Vec2(image.size.0.cast(), image.size.1.cast());
+    let uv = (p - quad.a) / quad.size() * size;
+    if !(uv.0 >= 0.0 && uv.1 >= 0.0 && uv.0 < size.0 && uv.1 < size.1) {
+        return None;
+    }
+    let (x, y) = (usize::conv_floor(uv.0), usize::conv_floor(uv.1));
+    let i = bytes * (y * usize::conv(image.size.0) + x);
+    let mut texel = [0; 4];
+    texel[..bytes].copy_from_slice(image.data.get(i..i + bytes)?);
+    Some(texel)
+}
+
+fn gradient_at(grad: &Gradient, p: Vec2) -> Rgba {
+    let (t, col1, col2) = match *grad {
+        Gradient::Linear {
+            start,
+            end,
+            col1,
+            col2,
+        } => {
+            let d = end - start;
+            let len2 = d.sum_square();
+            let t = if len2 > 0.0 {
+                ((p - start) * d).sum() / len2
+            } else {
+                0.0
+            };
+            (t, col1, col2)
+        }
+        Gradient::Radial {
+            center,
+            radius,
+            col1,
+            col2,
+        } => {
+            let t = ((p - center) / radius).sum_square().sqrt();
+            (t, col1, col2)
+        }
+    };
+    let t = if t.is_finite() {
+        t.clamp(0.0, 1.0)
+    } else {
+        1.0
+    };
+    mix(col1, col2, t)
+}
+
+fn srgba(col: Rgba) -> [u8; 4] {
+    Rgba8Srgb::from(col).into()
+}
@@ -14,10 +14,13 @@ use std::time::Duration;
 use crate::draw::{CustomPipe, CustomPipeBuilder, DrawPipe, DrawWindow};
 use crate::{warn_about_error, Error, Options, WindowId};
 use kas::cast::Conv;
-use kas::draw;
-use kas::event::UpdateHandle;
-use kas::TkAction;
-use kas_theme::{Theme, ThemeConfig};
+use kas::draw::{self, DrawIface, PassId};
+use kas::event::{EventState, UpdateHandle};
+use kas::geom::{Coord, Rect, Size};
+use kas::layout::{SetRectMgr, SolveCache};
+use kas::theme::{DrawMgr, SizeMgr};
+use kas::{TkAction, WidgetConfig};
+use kas_theme::{Theme, ThemeConfig, Window as _};
 
 #[cfg(feature = "clipboard")]
 use window_clipboard::Clipboard;
@@ -35,6 +38,16 @@ pub struct SharedState<C: CustomPipe, T> {
     /// created. This is used to estimate ideal window size.
     pub scale_factor: f64,
     pub frame_dur: Duration,
+    /// Whether pop-ups may use native windows
+    pub native_popups: bool,
+    /// Whether to draw render statistics over windows
+    pub render_stats: bool,
+    /// Whether to enable the shortcut help overlay
+    pub shortcut_help: bool,
+    /// Whether to enable value tooltips
+    pub value_tooltips: bool,
+    /// Haptic feedback handler (see [`crate::Toolkit::set_haptic_feedback`])
+    pub haptic_feedback: Option<Box<dyn FnMut()>>,
     window_id: u32,
     options: Options,
 }
@@ -85,6 +98,11 @@ where
             pending: vec![],
             scale_factor,
             frame_dur,
+            native_popups: options.native_popups,
+            render_stats: options.render_stats,
+            shortcut_help: options.shortcut_help,
+            value_tooltips: options.value_tooltips,
+            haptic_feedback: None,
             window_id: 0,
             options,
         })
@@ -118,6 +136,48 @@ where
         self.draw.draw.render(window, frame_view, clear_color);
     }
 
+    /// Render `widget` offscreen to an image
+    ///
+    /// The `widget` must be configured. It is laid out within a rect of the
+    /// given `size` and drawn using `theme_window`. This does not require a
+    /// window surface.
+    pub fn render_image(
+        &mut self,
+        theme_window: &mut T::Window,
+        widget: &mut dyn WidgetConfig,
+        size: Size,
+        state: &mut EventState,
+    ) -> Option<((u32, u32), Vec<u8>)> {
+        let size = size.max(Size(1, 1));
+        let size_mgr = SizeMgr::new(theme_window.size_handle());
+        let mut solve_cache = SolveCache::find_constraints(widget, size_mgr);
+        let mut mgr = SetRectMgr::new(theme_window.size_handle(), &mut self.draw, state);
+        solve_cache.apply_rect(widget, &mut mgr, Rect::new(Coord::ZERO, size), true);
+
+        let mut window = self.draw.draw.new_window();
+        self.draw.draw.resize(&mut window, size);
+        let draw = DrawIface {
+            draw: &mut window,
+            shared: &mut self.draw,
+            pass: PassId::new(0),
+        };
+
+        #[cfg(not(feature = "gat"))]
+        unsafe {
+            // Safety: lifetimes do not escape the returned draw_handle value.
+            let mut draw_handle = self.theme.draw_handle(draw, theme_window);
+            widget.draw(DrawMgr::new(&mut draw_handle, state, false));
+        }
+        #[cfg(feature = "gat")]
+        {
+            let mut draw_handle = self.theme.draw_handle(draw, theme_window);
+            widget.draw(DrawMgr::new(&mut draw_handle, state, false));
+        }
+
+        let clear_color = crate::window::to_wgpu_color(self.theme.clear_color());
+        self.draw.draw.render_image(&mut window, clear_color)
+    }
+
     #[inline]
     pub fn get_clipboard(&mut self) -> Option<String> {
         #[cfg(feature = "clipboard")]
@@ -145,11 +205,28 @@ where
         }
     }
 
+    pub fn haptic_feedback(&mut self) {
+        if let Some(f) = self.haptic_feedback.as_mut() {
+            f();
+        }
+    }
+
     pub fn trigger_update(&mut self, handle: UpdateHandle, payload: u64) {
-        self.pending.push(PendingAction::Update(handle, payload));
+        // Coalesce identical updates within a frame
+        let is_queued = self.pending.iter().any(|action| match action {
+            PendingAction::Update(h, p) => (*h, *p) == (handle, payload),
+            _ => false,
+        });
+        if !is_queued {
+            self.pending.push(PendingAction::Update(handle, payload));
+        }
     }
 
     pub fn on_exit(&self) {
+        self.save_config();
+    }
+
+    pub fn save_config(&self) {
         match self
             .options
             .write_config(&self.config.borrow(), &self.theme)
@@ -162,8 +239,13 @@ where
 
 pub enum PendingAction {
     AddPopup(winit::window::WindowId, WindowId, kas::Popup),
-    AddWindow(WindowId, Box<dyn kas::Window>),
+    AddWindow(
+        WindowId,
+        Box<dyn kas::Window>,
+        Option<winit::window::WindowId>,
+    ),
     CloseWindow(WindowId),
+    WindowAction(WindowId, TkAction),
     Update(kas::event::UpdateHandle, u64),
     TkAction(TkAction),
 }
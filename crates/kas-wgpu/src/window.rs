@@ -5,22 +5,28 @@
 
 //! `Window` and `WindowList` types
 
-use log::{debug, error, info, trace};
+use log::{debug, error, info, trace, warn};
+use std::fmt::Write;
 use std::time::Instant;
 
 use kas::cast::Cast;
-use kas::draw::{AnimationState, DrawIface, DrawShared, PassId};
-use kas::event::{CursorIcon, EventState, UpdateHandle};
-use kas::geom::{Coord, Rect, Size};
-use kas::layout::{SetRectMgr, SolveCache};
-use kas::theme::{DrawMgr, SizeHandle, SizeMgr, ThemeControl};
-use kas::{TkAction, WindowId};
+use kas::draw::color::Rgba;
+use kas::draw::{Affine, AnimationState, Draw, DrawIface, DrawShared, PassId, PassType};
+use kas::event::config::Shortcuts;
+use kas::event::{CursorIcon, EventState, ModifiersState, UpdateHandle, WindowKeyOp};
+use kas::geom::{Coord, Offset, Rect, Size, Vec2};
+use kas::layout::{AxisInfo, SetRectMgr, SolveCache};
+use kas::shell::{Displays, Monitor};
+use kas::text::{Environment, Text, TextApi};
+use kas::theme::{DrawMgr, SizeHandle, SizeMgr, TextClass, ThemeControl};
+use kas::{Decorations, TkAction, WidgetChildren, WidgetConfig, WidgetCore, WindowFlags, WindowId};
 use kas_theme::{Theme, Window as _};
-use winit::dpi::PhysicalSize;
+use winit::dpi::{PhysicalPosition, PhysicalSize};
 use winit::error::OsError;
 use winit::event::WindowEvent;
 use winit::event_loop::EventLoopWindowTarget;
-use winit::window::WindowBuilder;
+use winit::monitor::MonitorHandle;
+use winit::window::{self as ww, WindowBuilder};
 
 use crate::draw::{CustomPipe, DrawPipe, DrawWindow};
 use crate::shared::{PendingAction, SharedState};
@@ -40,6 +46,29 @@ pub(crate) struct Window<C: CustomPipe, T: Theme<DrawPipe<C>>> {
     theme_window: T::Window,
     next_avail_frame_time: Instant,
     queued_frame_time: Option<Instant>,
+    popups: Vec<PopupWindow<C>>,
+    /// Focus was lost by this window or a native pop-up and not (yet) passed
+    /// to another of these; resolved in [`Window::update`]
+    focus_lost: bool,
+    /// For a modal window, the window whose input is blocked
+    pub(crate) modal_parent: Option<winit::window::WindowId>,
+    /// Prepared render statistics text and its size
+    stats_text: Option<(Text<String>, Vec2)>,
+}
+
+/// A pop-up shown in a native window
+///
+/// The pop-up widget is owned by the parent [`Window`], which also handles
+/// events and drawing.
+struct PopupWindow<C: CustomPipe> {
+    id: WindowId,
+    /// Pop-up rect relative to the parent window
+    rect: Rect,
+    // Note: surface must be dropped before the window
+    surface: wgpu::Surface,
+    sc_desc: wgpu::SurfaceConfiguration,
+    draw: DrawWindow<C::Window>,
+    window: winit::window::Window,
 }
 
 // Public functions, for use by the toolkit
@@ -57,6 +86,8 @@ impl<C: CustomPipe, T: Theme<DrawPipe<C>>> Window<C, T> {
         let mut theme_window = shared.theme.new_window(scale_factor);
 
         let mut ev_state = EventState::new(shared.config.clone(), scale_factor);
+        ev_state.enable_shortcut_help(shared.shortcut_help);
+        ev_state.enable_value_tooltips(shared.value_tooltips);
         let mut tkw = TkWindow::new(shared, None, &mut theme_window);
         ev_state.full_configure(&mut tkw, &mut *widget);
 
@@ -73,11 +104,21 @@ impl<C: CustomPipe, T: Theme<DrawPipe<C>>> Window<C, T> {
         if restrict_dimensions.1 {
             builder = builder.with_max_inner_size(ideal);
         }
+        builder = apply_flags(builder, widget.flags());
         let window = builder
             .with_title(widget.title())
             .with_window_icon(widget.icon())
+            .with_decorations(widget.decorations() == Decorations::Server)
             .build(elwt)?;
 
+        if let Some(index) = widget.monitor() {
+            let size = window.outer_size().into();
+            match displays(&window).centre_window(index, size) {
+                Some(pos) => window.set_outer_position(PhysicalPosition::new(pos.0, pos.1)),
+                None => warn!("Window::new: no monitor with index {}", index),
+            }
+        }
+
         shared.init_clipboard(&window);
 
         let scale_factor = window.scale_factor();
@@ -110,6 +151,10 @@ impl<C: CustomPipe, T: Theme<DrawPipe<C>>> Window<C, T> {
             theme_window,
             next_avail_frame_time: time,
             queued_frame_time: Some(time),
+            popups: vec![],
+            focus_lost: false,
+            modal_parent: None,
+            stats_text: None,
         };
         r.apply_size(shared);
 
@@ -123,6 +168,10 @@ impl<C: CustomPipe, T: Theme<DrawPipe<C>>> Window<C, T> {
         match event {
             WindowEvent::Destroyed => (),
             WindowEvent::Resized(size) => self.do_resize(shared, size),
+            // Focus may have passed to a native pop-up; this is resolved in
+            // update (after the pop-up's focus event, if any)
+            WindowEvent::Focused(false) if !self.popups.is_empty() => self.focus_lost = true,
+            WindowEvent::Focused(true) if self.focus_lost => self.focus_lost = false,
             WindowEvent::ScaleFactorChanged {
                 scale_factor,
                 new_inner_size,
@@ -148,6 +197,10 @@ impl<C: CustomPipe, T: Theme<DrawPipe<C>>> Window<C, T> {
                     // Reconfigure must happen before further event handling
                     self.reconfigure(shared);
                     self.ev_state.action.remove(TkAction::RECONFIGURE);
+                } else {
+                    let mut tkw = TkWindow::new(shared, Some(&self.window), &mut self.theme_window);
+                    self.ev_state
+                        .configure_subtrees(&mut tkw, &mut *self.widget);
                 }
             }
         }
@@ -156,6 +209,14 @@ impl<C: CustomPipe, T: Theme<DrawPipe<C>>> Window<C, T> {
     /// Update, after receiving all events
     pub fn update(&mut self, shared: &mut SharedState<C, T>) -> (TkAction, Option<Instant>) {
         let mut tkw = TkWindow::new(shared, Some(&self.window), &mut self.theme_window);
+        if self.focus_lost {
+            // Neither this window nor a pop-up has focus: close pop-ups
+            self.focus_lost = false;
+            let widget = &mut *self.widget;
+            self.ev_state.with(&mut tkw, |mgr| {
+                mgr.handle_winit(widget, WindowEvent::Focused(false));
+            });
+        }
         let action = self.ev_state.update(&mut tkw, &mut *self.widget);
         drop(tkw);
 
@@ -189,7 +250,9 @@ impl<C: CustomPipe, T: Theme<DrawPipe<C>>> Window<C, T> {
                 .theme
                 .update_window(&mut self.theme_window, scale_factor);
         }
-        if action.contains(TkAction::RESIZE) {
+        if action.contains(TkAction::SIZE_TO_CONTENT) {
+            self.size_to_content(shared);
+        } else if action.contains(TkAction::RESIZE) {
             self.solve_cache.invalidate_rule_cache();
             self.apply_size(shared);
         } else if action.contains(TkAction::SET_SIZE) {
@@ -240,12 +303,71 @@ impl<C: CustomPipe, T: Theme<DrawPipe<C>>> Window<C, T> {
         });
     }
 
-    pub fn add_popup(&mut self, shared: &mut SharedState<C, T>, id: WindowId, popup: kas::Popup) {
+    /// Handle an event sent to a native pop-up window
+    ///
+    /// Input events are translated to parent window coordinates and handled
+    /// by the parent window.
+    pub fn handle_popup_event(
+        &mut self,
+        shared: &mut SharedState<C, T>,
+        window_id: ww::WindowId,
+        mut event: WindowEvent,
+    ) {
+        let pos = match self.popups.iter().find(|p| p.window.id() == window_id) {
+            Some(popup) => popup.rect.pos,
+            None => return,
+        };
+        match event {
+            WindowEvent::CursorMoved {
+                ref mut position, ..
+            } => {
+                position.x += f64::from(pos.0);
+                position.y += f64::from(pos.1);
+            }
+            WindowEvent::Touch(ref mut touch) => {
+                touch.location.x += f64::from(pos.0);
+                touch.location.y += f64::from(pos.1);
+            }
+            // Focus passing between the parent and its pop-ups is ignored
+            WindowEvent::Focused(focused) => {
+                self.focus_lost = !focused;
+                return;
+            }
+            // Window-level events are not forwarded. Cursor enter/leave are
+            // tracked by the parent window.
+            WindowEvent::Resized(_)
+            | WindowEvent::Moved(_)
+            | WindowEvent::CloseRequested
+            | WindowEvent::Destroyed
+            | WindowEvent::ScaleFactorChanged { .. }
+            | WindowEvent::CursorEntered { .. }
+            | WindowEvent::CursorLeft { .. } => return,
+            _ => (),
+        }
+        self.handle_event(shared, event);
+    }
+
+    /// True if `window_id` is a native pop-up of this window
+    pub fn has_popup(&self, window_id: ww::WindowId) -> bool {
+        self.popups.iter().any(|p| p.window.id() == window_id)
+    }
+
+    pub fn add_popup(
+        &mut self,
+        shared: &mut SharedState<C, T>,
+        elwt: &EventLoopWindowTarget<ProxyAction>,
+        id: WindowId,
+        popup: kas::Popup,
+    ) {
         let window = &mut *self.widget;
         let mut tkw = TkWindow::new(shared, Some(&self.window), &mut self.theme_window);
         self.ev_state.with(&mut tkw, |mgr| {
             kas::Window::add_popup(window, mgr, id, popup);
         });
+
+        if shared.native_popups {
+            self.add_native_popup(shared, elwt, id);
+        }
     }
 
     pub fn send_action(&mut self, action: TkAction) {
@@ -261,6 +383,7 @@ impl<C: CustomPipe, T: Theme<DrawPipe<C>>> Window<C, T> {
             self.ev_state.with(&mut tkw, |mgr| {
                 widget.remove_popup(mgr, id);
             });
+            self.popups.retain(|popup| popup.id != id);
         }
     }
 }
@@ -272,6 +395,78 @@ impl<C: CustomPipe, T: Theme<DrawPipe<C>>> Window<C, T> {
         Size::new(self.sc_desc.width.cast(), self.sc_desc.height.cast())
     }
 
+    /// Move pop-up `id` to a native window if it does not fit in this window
+    ///
+    /// On failure, the pop-up remains an overlay.
+    fn add_native_popup(
+        &mut self,
+        shared: &mut SharedState<C, T>,
+        elwt: &EventLoopWindowTarget<ProxyAction>,
+        id: WindowId,
+    ) {
+        // Window position is not available on all platforms (e.g. Wayland)
+        let inner = match self.window.inner_position() {
+            Ok(pos) => pos,
+            Err(_) => return,
+        };
+        // Clamp to the current monitor's work area
+        let area = match displays(&self.window).current_monitor() {
+            Some(monitor) => monitor.work_area,
+            None => return,
+        };
+        let bounds = Rect::new(area.pos - Offset(inner.x, inner.y), area.size);
+
+        let mut mgr = SetRectMgr::new(
+            self.theme_window.size_handle(),
+            &mut shared.draw,
+            &mut self.ev_state,
+        );
+        let rect = match self.widget.set_popup_bounds(&mut mgr, id, Some(bounds)) {
+            Some(rect) => rect,
+            None => return,
+        };
+
+        let position = PhysicalPosition::new(inner.x + rect.pos.0, inner.y + rect.pos.1);
+        let window = match popup_builder(rect.size, position).build(elwt) {
+            Ok(window) => window,
+            Err(error) => {
+                warn!("Unable to create pop-up window: {}", error);
+                let mut mgr = SetRectMgr::new(
+                    self.theme_window.size_handle(),
+                    &mut shared.draw,
+                    &mut self.ev_state,
+                );
+                self.widget.set_popup_bounds(&mut mgr, id, None);
+                return;
+            }
+        };
+        debug!("Constructed native pop-up window with rect {:?}", rect);
+
+        let size: Size = window.inner_size().into();
+        let mut draw = shared.draw.draw.new_window();
+        shared.draw.draw.resize(&mut draw, size);
+
+        let surface = unsafe { shared.instance.create_surface(&window) };
+        let sc_desc = wgpu::SurfaceConfiguration {
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+            format: crate::draw::RENDER_TEX_FORMAT,
+            width: size.0.cast(),
+            height: size.1.cast(),
+            present_mode: wgpu::PresentMode::Mailbox,
+        };
+        surface.configure(&shared.draw.draw.device, &sc_desc);
+
+        self.popups.push(PopupWindow {
+            id,
+            rect,
+            surface,
+            sc_desc,
+            draw,
+            window,
+        });
+        self.ev_state.send_action(TkAction::REGION_MOVED);
+    }
+
     fn reconfigure(&mut self, shared: &mut SharedState<C, T>) {
         let time = Instant::now();
         debug!("Window::reconfigure");
@@ -310,9 +505,26 @@ impl<C: CustomPipe, T: Theme<DrawPipe<C>>> Window<C, T> {
         };
 
         self.window.request_redraw();
+        for popup in &self.popups {
+            popup.window.request_redraw();
+        }
         trace!("apply_size completed in {}µs", time.elapsed().as_micros());
     }
 
+    fn size_to_content(&mut self, shared: &mut SharedState<C, T>) {
+        let size_mgr = SizeMgr::new(self.theme_window.size_handle());
+        self.solve_cache = SolveCache::find_constraints(self.widget.as_widget_mut(), size_mgr);
+        // Apply first: this updates size restrictions before resizing
+        self.apply_size(shared);
+
+        if !self.window.is_maximized() {
+            let ideal = self.solve_cache.ideal(true).max(Size(1, 1));
+            debug!("Window::size_to_content: size = {:?}", ideal);
+            // On success, we will receive a resize event
+            self.window.set_inner_size(ideal);
+        }
+    }
+
     fn do_resize(&mut self, shared: &mut SharedState<C, T>, size: PhysicalSize<u32>) {
         let time = Instant::now();
         let size = size.into();
@@ -342,12 +554,16 @@ impl<C: CustomPipe, T: Theme<DrawPipe<C>>> Window<C, T> {
         let start = Instant::now();
         self.next_avail_frame_time = start + shared.frame_dur;
 
+        // Keyboard window move/resize feedback takes priority over help
+        let mut help = (self.window_key_op_text()).or_else(|| self.shortcut_help_text());
+        let mut tooltip = self.value_tooltip();
         {
             let draw = DrawIface {
                 draw: &mut self.draw,
                 shared: &mut shared.draw,
                 pass: PassId::new(0),
             };
+            let rect = self.widget.rect();
 
             #[cfg(not(feature = "gat"))]
             unsafe {
@@ -355,15 +571,35 @@ impl<C: CustomPipe, T: Theme<DrawPipe<C>>> Window<C, T> {
                 let mut draw_handle = shared.theme.draw_handle(draw, &mut self.theme_window);
                 let draw_mgr = DrawMgr::new(&mut draw_handle, &mut self.ev_state, false);
                 self.widget.draw(draw_mgr);
+                if let Some(text) = help.as_mut() {
+                    let draw_mgr = DrawMgr::new(&mut draw_handle, &mut self.ev_state, false);
+                    draw_centred_text(draw_mgr, rect, text);
+                }
+                if let Some(tooltip) = tooltip.as_mut() {
+                    let draw_mgr = DrawMgr::new(&mut draw_handle, &mut self.ev_state, false);
+                    draw_value_tooltip(draw_mgr, rect, tooltip);
+                }
             }
             #[cfg(feature = "gat")]
             {
                 let mut draw_handle = shared.theme.draw_handle(draw, &mut self.theme_window);
                 let draw_mgr = DrawMgr::new(&mut draw_handle, &mut self.ev_state, false);
                 self.widget.draw(draw_mgr);
+                if let Some(text) = help.as_mut() {
+                    let draw_mgr = DrawMgr::new(&mut draw_handle, &mut self.ev_state, false);
+                    draw_centred_text(draw_mgr, rect, text);
+                }
+                if let Some(tooltip) = tooltip.as_mut() {
+                    let draw_mgr = DrawMgr::new(&mut draw_handle, &mut self.ev_state, false);
+                    draw_value_tooltip(draw_mgr, rect, tooltip);
+                }
             }
         }
 
+        if shared.render_stats {
+            self.draw_render_stats(shared);
+        }
+
         self.queued_frame_time = match self.draw.animation {
             AnimationState::None => None,
             AnimationState::Animate => Some(self.next_avail_frame_time),
@@ -394,6 +630,18 @@ impl<C: CustomPipe, T: Theme<DrawPipe<C>>> Window<C, T> {
 
         frame.present();
 
+        for popup in &self.popups {
+            popup.window.request_redraw();
+        }
+
+        let mut tkw = TkWindow::new(shared, Some(&self.window), &mut self.theme_window);
+        let widget = &mut *self.widget;
+        let presented = Instant::now();
+        self.ev_state.with(&mut tkw, |mgr| {
+            mgr.frame_complete(widget, presented);
+        });
+        drop(tkw);
+
         let end = Instant::now();
         // Explanation: 'text' is the time to prepare positioned glyphs, 'frame-
         // swap' is mostly about sync, 'render' is time to feed the GPU.
@@ -404,7 +652,174 @@ impl<C: CustomPipe, T: Theme<DrawPipe<C>>> Window<C, T> {
             self.draw.text.dur_micros(),
             (end - time2).as_micros()
         );
-        false
+        // Frame callbacks may have requested further actions
+        !self.ev_state.action.is_empty()
+    }
+
+    /// Draw render statistics (of the previous frame) over the window
+    fn draw_render_stats(&mut self, shared: &mut SharedState<C, T>) {
+        let dpp = self.window.scale_factor() as f32 * (96.0 / 72.0);
+        if self.stats_text.as_ref().map(|(text, _)| text.env().dpp) != Some(dpp) {
+            let mut env = Environment::default();
+            env.set_dpp(dpp);
+            env.set_pt_size(9.0);
+            self.stats_text = Some((Text::new(env, String::new()), Vec2::ZERO));
+        }
+        let (text, size) = self.stats_text.as_mut().unwrap();
+
+        // Re-prepare only when the statistics change
+        let stats = self.draw.render_stats().to_string();
+        if text.as_str() != stats {
+            text.set_text(stats);
+            if let Some(req) = text.prepare() {
+                *size = Vec2::from(req);
+            }
+        }
+        let size = *size;
+
+        let margin = Vec2::splat(4.0);
+        let rect = Rect::new(Coord::ZERO, (size + margin * 2.0).ceil().into());
+        let mut draw = DrawIface {
+            draw: &mut self.draw,
+            shared: &mut shared.draw,
+            pass: PassId::new(0),
+        };
+        let mut draw = draw.new_pass(rect, Offset::ZERO, Affine::IDENTITY, PassType::Overlay);
+        draw.rect(rect.into(), Rgba::ga(0.0, 0.75));
+        draw.text(margin, text.as_ref(), Rgba::WHITE);
+    }
+
+    /// Build the shortcut help text, if visible
+    ///
+    /// Lists active accelerator keys with the label of their target (or the
+    /// widget's type name where no label is known), then shortcut bindings
+    /// grouped by command.
+    fn shortcut_help_text(&self) -> Option<Text<String>> {
+        if !self.ev_state.shortcut_help_visible() {
+            return None;
+        }
+
+        let mut accels: Vec<(String, String)> = vec![];
+        for (alt_required, vkey, id) in self.ev_state.accel_keys() {
+            let mods = match alt_required {
+                true => ModifiersState::ALT,
+                false => ModifiersState::empty(),
+            };
+            let key = Shortcuts::key_name(mods, vkey);
+            // Keys in higher-priority layers mask those in lower layers
+            if accels.iter().any(|(k, _)| *k == key) {
+                continue;
+            }
+            let label = match self.ev_state.accel_label(id) {
+                Some(label) => label.to_string(),
+                None => match self.widget.find_widget(id) {
+                    Some(w) => w.widget_name().to_string(),
+                    None => continue,
+                },
+            };
+            accels.push((key, label));
+        }
+        accels.sort();
+
+        let mut commands: Vec<(String, Vec<String>)> = vec![];
+        self.ev_state.config().shortcuts(|shortcuts| {
+            for (mods, vkey, cmd) in shortcuts.iter() {
+                let cmd = format!("{:?}", cmd);
+                let key = Shortcuts::key_name(mods, vkey);
+                match commands.iter_mut().find(|(c, _)| *c == cmd) {
+                    Some((_, keys)) => keys.push(key),
+                    None => commands.push((cmd, vec![key])),
+                }
+            }
+        });
+        commands.sort();
+
+        let mut s = String::from("Accelerator keys");
+        if accels.is_empty() {
+            s.push_str("\n    (none)");
+        }
+        for (key, target) in accels {
+            let _ = write!(s, "\n    {}: {}", key, target);
+        }
+        s.push_str("\n\nShortcuts");
+        for (cmd, mut keys) in commands {
+            keys.sort();
+            let _ = write!(s, "\n    {}: {}", cmd, keys.join(", "));
+        }
+        Some(Text::new(Environment::default(), s))
+    }
+
+    /// Build the keyboard window move/resize instructions, if active
+    fn window_key_op_text(&self) -> Option<Text<String>> {
+        let action = match self.ev_state.window_key_op()? {
+            WindowKeyOp::Move => "Move window",
+            WindowKeyOp::Resize => "Resize window",
+        };
+        let s = format!(
+            "{}\n\nArrow keys: adjust (hold Ctrl for fine steps)\nEnter: confirm\nEscape: cancel",
+            action
+        );
+        Some(Text::new(Environment::default(), s))
+    }
+
+    /// Prepare the value tooltip, if any
+    ///
+    /// Returns the target widget's rect, the expiry time and the text.
+    fn value_tooltip(&self) -> Option<(Rect, Instant, Text<String>)> {
+        let (id, description, expiry) = self.ev_state.value_tooltip()?;
+        let rect = self.widget.find_widget(id)?.rect();
+        let text = Text::new(Environment::default(), description.to_string());
+        Some((rect, expiry, text))
+    }
+
+    /// Draw a native pop-up window
+    pub(crate) fn do_draw_popup(
+        &mut self,
+        shared: &mut SharedState<C, T>,
+        window_id: ww::WindowId,
+    ) {
+        let popup = match self.popups.iter_mut().find(|p| p.window.id() == window_id) {
+            Some(popup) => popup,
+            None => return,
+        };
+
+        {
+            let draw = DrawIface {
+                draw: &mut popup.draw,
+                shared: &mut shared.draw,
+                pass: PassId::new(0),
+            };
+
+            #[cfg(not(feature = "gat"))]
+            unsafe {
+                // Safety: lifetimes do not escape the returned draw_handle value.
+                let mut draw_handle = shared.theme.draw_handle(draw, &mut self.theme_window);
+                let draw_mgr = DrawMgr::new(&mut draw_handle, &mut self.ev_state, false);
+                self.widget.draw_popup(draw_mgr, popup.id);
+            }
+            #[cfg(feature = "gat")]
+            {
+                let mut draw_handle = shared.theme.draw_handle(draw, &mut self.theme_window);
+                let draw_mgr = DrawMgr::new(&mut draw_handle, &mut self.ev_state, false);
+                self.widget.draw_popup(draw_mgr, popup.id);
+            }
+        }
+        // Animation is driven by the parent window's redraws
+        popup.draw.animation = AnimationState::None;
+
+        let frame = match popup.surface.get_current_texture() {
+            Ok(frame) => frame,
+            Err(e) => {
+                error!("Failed to get frame texture: {}", e);
+                return;
+            }
+        };
+        let view = frame.texture.create_view(&Default::default());
+
+        let clear_color = to_wgpu_color(shared.theme.clear_color());
+        shared.render(&mut popup.draw, &view, clear_color);
+
+        frame.present();
     }
 
     pub(crate) fn next_resume(&self) -> Option<Instant> {
@@ -417,7 +832,7 @@ impl<C: CustomPipe, T: Theme<DrawPipe<C>>> Window<C, T> {
     }
 }
 
-fn to_wgpu_color(c: kas::draw::color::Rgba) -> wgpu::Color {
+pub(crate) fn to_wgpu_color(c: kas::draw::color::Rgba) -> wgpu::Color {
     wgpu::Color {
         r: c.r as f64,
         g: c.g as f64,
@@ -476,9 +891,10 @@ where
         // handled to create the winit window here or use statics to generate
         // errors now, but user code can't do much with this error anyway.
         let id = self.shared.next_window_id();
+        let parent = self.window.map(|w| w.id());
         self.shared
             .pending
-            .push(PendingAction::AddWindow(id, widget));
+            .push(PendingAction::AddWindow(id, widget, parent));
         id
     }
 
@@ -486,6 +902,11 @@ where
         self.shared.pending.push(PendingAction::CloseWindow(id));
     }
 
+    fn send_action_to(&mut self, id: WindowId, action: TkAction) {
+        let pending = PendingAction::WindowAction(id, action);
+        self.shared.pending.push(pending);
+    }
+
     fn trigger_update(&mut self, handle: UpdateHandle, payload: u64) {
         self.shared.trigger_update(handle, payload);
     }
@@ -505,6 +926,19 @@ where
         self.shared.pending.push(PendingAction::TkAction(action));
     }
 
+    fn set_text_zoom(&mut self, zoom: f32) {
+        use kas_theme::Window;
+        self.theme_window.set_text_zoom(zoom);
+        let scale_factor = self.theme_window.size_handle().scale_factor();
+        self.shared
+            .theme
+            .update_window(self.theme_window, scale_factor);
+    }
+
+    fn save_config(&mut self) {
+        self.shared.save_config();
+    }
+
     fn size_and_draw_shared(
         &mut self,
         f: &mut dyn FnMut(&mut dyn SizeHandle, &mut dyn DrawShared),
@@ -514,10 +948,230 @@ where
         f(&mut size_handle, &mut self.shared.draw);
     }
 
+    fn render_image(
+        &mut self,
+        widget: &mut dyn WidgetConfig,
+        size: Size,
+        state: &mut EventState,
+    ) -> Option<((u32, u32), Vec<u8>)> {
+        self.shared
+            .render_image(self.theme_window, widget, size, state)
+    }
+
     #[inline]
     fn set_cursor_icon(&mut self, icon: CursorIcon) {
         if let Some(window) = self.window {
             window.set_cursor_icon(icon);
         }
     }
+
+    fn drag_window(&mut self) {
+        if let Some(window) = self.window {
+            if let Err(error) = window.drag_window() {
+                warn!("Failed to drag window: {}", error);
+            }
+        }
+    }
+
+    fn resize_window(&mut self, offset: Offset, size: Size) {
+        if let Some(window) = self.window {
+            if offset != Offset::ZERO {
+                match window.outer_position() {
+                    Ok(pos) => {
+                        let pos = PhysicalPosition::new(pos.x + offset.0, pos.y + offset.1);
+                        window.set_outer_position(pos);
+                    }
+                    Err(error) => debug!("Unable to move window: {}", error),
+                }
+            }
+            window.set_inner_size(size.max(Size(1, 1)));
+        }
+    }
+
+    #[inline]
+    fn inner_size(&self) -> Size {
+        self.window
+            .map(|w| w.inner_size().into())
+            .unwrap_or(Size::ZERO)
+    }
+
+    #[inline]
+    fn set_minimized(&mut self, minimized: bool) {
+        if let Some(window) = self.window {
+            window.set_minimized(minimized);
+        }
+    }
+
+    #[inline]
+    fn set_maximized(&mut self, maximized: bool) {
+        if let Some(window) = self.window {
+            window.set_maximized(maximized);
+        }
+    }
+
+    #[inline]
+    fn is_maximized(&self) -> bool {
+        self.window.map(|w| w.is_maximized()).unwrap_or(false)
+    }
+
+    fn displays(&self) -> Displays {
+        self.window.map(displays).unwrap_or_default()
+    }
+
+    fn set_window_flags(&mut self, flags: WindowFlags) {
+        if let Some(window) = self.window {
+            window.set_always_on_top(flags.contains(WindowFlags::ALWAYS_ON_TOP));
+        }
+    }
+
+    #[inline]
+    fn haptic_feedback(&mut self) {
+        self.shared.haptic_feedback();
+    }
+}
+
+/// Describe a monitor
+///
+/// Note: winit does not report work areas, thus these equal the monitor rect.
+/// Computing the work area would require platform-specific APIs.
+fn monitor_info(handle: &MonitorHandle) -> Monitor {
+    let pos = handle.position();
+    let rect = Rect::new(Coord(pos.x, pos.y), handle.size().into());
+    let mut resolutions: Vec<Size> = handle.video_modes().map(|m| m.size().into()).collect();
+    resolutions.sort_by_key(|size| std::cmp::Reverse((size.0, size.1)));
+    resolutions.dedup();
+    Monitor {
+        name: handle.name(),
+        rect,
+        work_area: rect,
+        scale_factor: handle.scale_factor(),
+        resolutions,
+    }
+}
+
+/// Enumerate displays, relative to `window`
+fn displays(window: &ww::Window) -> Displays {
+    let handles: Vec<MonitorHandle> = window.available_monitors().collect();
+    let index_of = |handle: Option<MonitorHandle>| {
+        let handle = handle?;
+        handles.iter().position(|h| *h == handle)
+    };
+    let primary = index_of(window.primary_monitor());
+    let current = index_of(window.current_monitor());
+    let rect = window.outer_position().ok().map(|pos| {
+        let size: Size = window.outer_size().into();
+        Rect::new(Coord(pos.x, pos.y), size)
+    });
+    Displays {
+        monitors: handles.iter().map(monitor_info).collect(),
+        primary,
+        current,
+        window: rect,
+    }
+}
+
+/// Construct a window builder for a native pop-up
+fn popup_builder(size: Size, position: PhysicalPosition<i32>) -> WindowBuilder {
+    let builder = WindowBuilder::new()
+        .with_inner_size(size)
+        .with_position(position)
+        .with_decorations(false)
+        .with_resizable(false)
+        .with_always_on_top(true);
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    let builder = {
+        use winit::platform::unix::{WindowBuilderExtUnix, XWindowType};
+        // Bypass the X11 window manager, as is usual for menus
+        builder
+            .with_x11_window_type(vec![XWindowType::PopupMenu])
+            .with_override_redirect(true)
+    };
+
+    builder
+}
+
+/// Apply [`WindowFlags`] to a window builder
+fn apply_flags(builder: WindowBuilder, flags: WindowFlags) -> WindowBuilder {
+    let builder = builder.with_always_on_top(flags.contains(WindowFlags::ALWAYS_ON_TOP));
+
+    #[cfg(any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+    ))]
+    {
+        use winit::platform::unix::{WindowBuilderExtUnix, XWindowType};
+        // These are only hints (to the X11 window manager)
+        let window_type = if flags.contains(WindowFlags::NO_FOCUS) {
+            Some(XWindowType::Notification)
+        } else if flags.contains(WindowFlags::SKIP_TASKBAR) {
+            Some(XWindowType::Utility)
+        } else {
+            None
+        };
+        if let Some(window_type) = window_type {
+            return builder.with_x11_window_type(vec![window_type]);
+        }
+    }
+
+    if flags.intersects(WindowFlags::SKIP_TASKBAR | WindowFlags::NO_FOCUS) {
+        debug!("Window flags {:?} not supported on this platform", flags);
+    }
+    builder
+}
+
+/// Draw `text` in an overlay, centred over `window`
+fn draw_centred_text(mut draw: DrawMgr, window: Rect, text: &mut Text<String>) {
+    let class = TextClass::Label;
+    let size = {
+        let size_mgr = draw.size_mgr();
+        let w = size_mgr.text_bound(text, class, AxisInfo::new(false, None));
+        let w = w.ideal_size().min(window.size.0);
+        let h = size_mgr.text_bound(text, class, AxisInfo::new(true, Some(w)));
+        Size(w, h.ideal_size().min(window.size.1))
+    };
+    let rect = Rect::new(window.pos + (window.size - size) / 2, size);
+    text.update_env(|env| env.set_bounds(size.into()));
+    draw.with_overlay(rect, |mut draw| {
+        draw.text(rect.pos, text.as_ref(), class);
+    });
+}
+
+/// Draw a value tooltip below `target` (or above, if there is no space)
+fn draw_value_tooltip(
+    mut draw: DrawMgr,
+    window: Rect,
+    (target, expiry, text): &mut (Rect, Instant, Text<String>),
+) {
+    let class = TextClass::Label;
+    let size = {
+        let size_mgr = draw.size_mgr();
+        let w = size_mgr.text_bound(text, class, AxisInfo::new(false, None));
+        let w = w.ideal_size().min(window.size.0);
+        let h = size_mgr.text_bound(text, class, AxisInfo::new(true, Some(w)));
+        Size(w, h.ideal_size().min(window.size.1))
+    };
+    let window_end = window.pos + window.size;
+    let mut pos = target.pos + Size((target.size.0 - size.0) / 2, target.size.1);
+    if pos.1 + size.1 > window_end.1 {
+        pos.1 = target.pos.1 - size.1;
+    }
+    pos.0 = pos.0.min(window_end.0 - size.0).max(window.pos.0);
+    pos.1 = pos.1.max(window.pos.1);
+    let rect = Rect::new(pos, size);
+    text.update_env(|env| env.set_bounds(size.into()));
+    // Redraw on expiry to remove the tooltip
+    draw.draw_device().animate_at(*expiry);
+    draw.with_overlay(rect, |mut draw| {
+        draw.text(rect.pos, text.as_ref(), class);
+    });
 }
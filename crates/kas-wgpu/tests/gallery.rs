@@ -0,0 +1,38 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Golden image tests over the widget gallery
+//!
+//! Golden images are committed in `tests/golden`; a missing golden image is
+//! an error. Set `KAS_BLESS_GOLDEN=1` to (re)write them after intended
+//! changes.
+
+use kas::geom::Size;
+use kas_theme::{FlatTheme, ShadedTheme, Theme};
+use kas_wgpu::golden;
+use kas_wgpu::print::{PageSetup, Printer, VectorShared};
+use kas_widgets::Gallery;
+
+fn check<T: Theme<VectorShared>>(theme: T, name: &str) {
+    let setup = PageSetup::A4.with_dpi(96.0);
+    let mut printer = Printer::new(theme, setup);
+    let mut gallery = Gallery::new();
+    let (size, data) = printer.render_image(&mut gallery, Size(1200, 4800));
+
+    let path = format!("{}/tests/golden/{}.png", env!("CARGO_MANIFEST_DIR"), name);
+    if let Err(err) = golden::check(&path, size, &data) {
+        panic!("{}: {}", path, err);
+    }
+}
+
+#[test]
+fn gallery_flat() {
+    check(FlatTheme::new(), "gallery-flat");
+}
+
+#[test]
+fn gallery_shaded() {
+    check(ShadedTheme::new(), "gallery-shaded");
+}
@@ -0,0 +1,252 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Badge overlay
+
+use kas::text::util::set_string_and_prepare;
+use kas::theme::TextClass;
+use kas::{layout, prelude::*};
+
+/// A corner of a widget's rect
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Corner {
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl Default for Corner {
+    fn default() -> Self {
+        Corner::TopRight
+    }
+}
+
+widget! {
+    /// A wrapper widget which overlays a badge on its child
+    ///
+    /// The badge is either a small dot or a count, drawn over one corner of
+    /// the inner widget. The badge does not affect layout: size requirements
+    /// are those of the inner widget and the badge is drawn in its own pass
+    /// over the inner widget's rect. The badge does not receive input events.
+    ///
+    /// The badge is initially hidden; use [`Badge::with_count`],
+    /// [`Badge::with_dot`] or [`Badge::set_count`] to show it.
+    #[autoimpl(Deref, DerefMut on self.inner)]
+    #[autoimpl(class_traits where W: trait on self.inner)]
+    #[derive(Clone, Debug)]
+    #[handler(msg = <W as Handler>::Msg)]
+    pub struct Badge<W: Widget> {
+        #[widget_core]
+        core: CoreData,
+        #[widget]
+        pub inner: W,
+        corner: Corner,
+        visible: bool,
+        count: Option<u32>,
+        max_count: u32,
+        text: Text<String>,
+        text_size: Size,
+        badge_rect: Rect,
+    }
+
+    impl Self {
+        /// Construct, with the badge hidden
+        #[inline]
+        pub fn new(inner: W) -> Self {
+            Badge {
+                core: Default::default(),
+                inner,
+                corner: Corner::default(),
+                visible: false,
+                count: None,
+                max_count: 99,
+                text: Text::new_single(String::new()),
+                text_size: Size::ZERO,
+                badge_rect: Rect::ZERO,
+            }
+        }
+
+        /// Set the corner of the inner widget over which the badge is drawn
+        ///
+        /// By default this is [`Corner::TopRight`].
+        #[inline]
+        #[must_use]
+        pub fn with_corner(mut self, corner: Corner) -> Self {
+            self.corner = corner;
+            self
+        }
+
+        /// Set the maximum count displayed
+        ///
+        /// Larger counts are displayed as `max+`. By default this is 99.
+        #[inline]
+        #[must_use]
+        pub fn with_max_count(mut self, max: u32) -> Self {
+            self.max_count = max;
+            if let Some(count) = self.count {
+                self.text.set_string(Self::format_count(count, max));
+            }
+            self
+        }
+
+        /// Show the badge as a count
+        #[inline]
+        #[must_use]
+        pub fn with_count(mut self, count: u32) -> Self {
+            self.visible = true;
+            self.count = Some(count);
+            self.text.set_string(Self::format_count(count, self.max_count));
+            self
+        }
+
+        /// Show the badge as a dot
+        #[inline]
+        #[must_use]
+        pub fn with_dot(mut self) -> Self {
+            self.visible = true;
+            self.count = None;
+            self.text.set_string(String::new());
+            self
+        }
+
+        /// Get the corner over which the badge is drawn
+        #[inline]
+        pub fn corner(&self) -> Corner {
+            self.corner
+        }
+
+        /// Get the displayed count, if any
+        ///
+        /// Returns `None` when the badge is a dot. (This does not indicate
+        /// whether the badge is visible.)
+        #[inline]
+        pub fn count(&self) -> Option<u32> {
+            self.count
+        }
+
+        /// Show the badge as a count
+        pub fn set_count(&mut self, count: u32) -> TkAction {
+            let s = Self::format_count(count, self.max_count);
+            // Badge size depends on visibility, kind and text length
+            let resize = !self.visible || self.count.is_none() || s.len() != self.text.as_str().len();
+            self.visible = true;
+            self.count = Some(count);
+            if resize {
+                self.text.set_string(s);
+                TkAction::RESIZE
+            } else {
+                set_string_and_prepare(&mut self.text, s, self.badge_rect.size) | TkAction::REDRAW
+            }
+        }
+
+        /// Show the badge as a dot
+        pub fn set_dot(&mut self) -> TkAction {
+            if self.visible && self.count.is_none() {
+                return TkAction::empty();
+            }
+            self.visible = true;
+            self.count = None;
+            self.text.set_string(String::new());
+            TkAction::RESIZE
+        }
+
+        /// True if the badge is visible
+        #[inline]
+        pub fn is_visible(&self) -> bool {
+            self.visible
+        }
+
+        /// Show or hide the badge
+        ///
+        /// The badge's value (count or dot) is unaffected.
+        pub fn set_visible(&mut self, visible: bool) -> TkAction {
+            if visible == self.visible {
+                return TkAction::empty();
+            }
+            self.visible = visible;
+            // Layout may be stale if the value was adjusted while hidden
+            TkAction::RESIZE
+        }
+
+        /// Show the badge
+        #[inline]
+        pub fn show(&mut self) -> TkAction {
+            self.set_visible(true)
+        }
+
+        /// Hide the badge
+        #[inline]
+        pub fn hide(&mut self) -> TkAction {
+            self.set_visible(false)
+        }
+
+        fn format_count(count: u32, max: u32) -> String {
+            if count > max {
+                format!("{}+", max)
+            } else {
+                count.to_string()
+            }
+        }
+    }
+
+    impl Layout for Self {
+        fn layout(&mut self) -> layout::Layout<'_> {
+            layout::Layout::single(&mut self.inner)
+        }
+
+        fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
+            let len = if axis.is_horizontal() {
+                size_mgr.text_bound(&mut self.text, TextClass::Label, axis).min_size()
+            } else {
+                size_mgr.line_height(TextClass::Label)
+            };
+            self.text_size.set_component(axis, len);
+            self.inner.size_rules(size_mgr, axis)
+        }
+
+        fn set_rect(&mut self, mgr: &mut SetRectMgr, rect: Rect, align: AlignHints) {
+            self.core.rect = rect;
+            self.inner.set_rect(mgr, rect, align);
+
+            // The badge is placed inside the inner widget's rect (which may be
+            // smaller than ours due to alignment).
+            let inner = self.inner.rect();
+            let h = self.text_size.1;
+            let size = match self.count {
+                None => Size::splat(h / 2),
+                Some(_) => Size((self.text_size.0 + h / 2).max(h), h),
+            };
+            let size = Size(size.0.min(inner.size.0), size.1.min(inner.size.1));
+            let mut pos = inner.pos;
+            if matches!(self.corner, Corner::TopRight | Corner::BottomRight) {
+                pos.0 += inner.size.0 - size.0;
+            }
+            if matches!(self.corner, Corner::BottomLeft | Corner::BottomRight) {
+                pos.1 += inner.size.1 - size.1;
+            }
+            self.badge_rect = Rect::new(pos, size);
+            self.text.update_env(|env| {
+                env.set_bounds(size.into());
+                env.set_align((Align::Center, Align::Center));
+            });
+        }
+
+        fn draw(&mut self, mut draw: DrawMgr) {
+            self.inner.draw(draw.re());
+            if self.visible {
+                let rect = self.badge_rect;
+                let text = self.count.map(|_| &self.text);
+                let mut draw = draw.with_core(self.core_data());
+                draw.with_clip_region(rect, Offset::ZERO, |mut draw| {
+                    draw.badge(rect);
+                    if let Some(text) = text {
+                        draw.text(rect.pos, text.as_ref(), TextClass::Label);
+                    }
+                });
+            }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Event filter widget
+
+use kas::prelude::*;
+
+/// Result of an event filter
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum FilterAction {
+    /// Pass the event to the child widget
+    Pass,
+    /// Consume the event: the child does not see it ([`Response::Used`])
+    Consume,
+    /// Reject the event: the child does not see it, but the event is returned
+    /// as [`Response::Unused`], thus parents may handle it
+    Reject,
+}
+
+widget! {
+    /// Wrapper to filter events sent to the inner widget
+    ///
+    /// Each event sent to the inner widget or any of its descendants is first
+    /// passed to the closure `F`, which decides whether the event is passed
+    /// on, consumed or rejected. For example, rejecting [`Event::Scroll`]
+    /// allows a parent scroll region to handle scrolling over an embedded
+    /// slider, while consuming [`Event::Command`] with a specific [`Command`]
+    /// disables a keyboard shortcut.
+    ///
+    /// Note: [`Event::PressMove`] and [`Event::PressEnd`] are delivered only
+    /// to the widget which grabbed the press; filtering [`Event::PressStart`]
+    /// prevents a grab.
+    ///
+    /// [`Command`]: kas::event::Command
+    #[autoimpl(Debug skip self.filter)]
+    #[autoimpl(Deref, DerefMut on self.inner)]
+    #[autoimpl(class_traits where W: trait on self.inner)]
+    #[derive(Clone)]
+    #[widget{
+        layout = single;
+    }]
+    #[handler(msg = <W as Handler>::Msg)]
+    pub struct Filter<W: Widget, F: Fn(&mut EventMgr, &Event) -> FilterAction + 'static> {
+        #[widget_core]
+        core: kas::CoreData,
+        #[widget]
+        inner: W,
+        filter: F,
+    }
+
+    impl Self {
+        /// Construct
+        ///
+        /// All events sent to `child` (or its descendants) are first passed to
+        /// the closure `filter`.
+        pub fn new(child: W, filter: F) -> Self {
+            Filter {
+                core: Default::default(),
+                inner: child,
+                filter,
+            }
+        }
+
+        /// Deconstruct, returning the inner widget
+        pub fn into_inner(self) -> W {
+            self.inner
+        }
+    }
+
+    impl SendEvent for Self {
+        fn send(&mut self, mgr: &mut EventMgr, id: WidgetId, event: Event) -> Response<Self::Msg> {
+            if self.is_disabled() {
+                return Response::Unused;
+            }
+
+            if self.eq_id(&id) {
+                return self.handle(mgr, event);
+            }
+
+            match (self.filter)(mgr, &event) {
+                FilterAction::Pass => self.inner.send(mgr, id, event),
+                FilterAction::Consume => Response::Used,
+                FilterAction::Reject => Response::Unused,
+            }
+        }
+    }
+}
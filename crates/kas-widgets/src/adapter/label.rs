@@ -9,6 +9,14 @@ use kas::text::util::set_text_and_prepare;
 use kas::theme::TextClass;
 use kas::{event, layout, prelude::*};
 
+/// A labelled row, as used in forms
+///
+/// This is a [`WithLabel`] with the label on the left. Construct with
+/// [`FormRow::new_row`] to right-align the label against the widget.
+///
+/// To align labels across multiple rows, use [`crate::Form`] instead.
+pub type FormRow<W> = WithLabel<W, kas::dir::Left>;
+
 widget! {
     /// A wrapper widget with a label
     ///
@@ -28,6 +36,7 @@ widget! {
         layout_store: layout::FixedRowStorage<2>,
         label_store: layout::TextStorage,
         label: Text<AccelString>,
+        label_align: AlignHints,
     }
 
     impl Self where D: Default {
@@ -49,9 +58,20 @@ widget! {
                 layout_store: Default::default(),
                 label_store: Default::default(),
                 label: Text::new_multi(label.into()),
+                label_align: AlignHints::NONE,
             }
         }
 
+        /// Set alignment of the label
+        ///
+        /// By default, the label uses alignment hints from the parent.
+        #[inline]
+        #[must_use]
+        pub fn with_label_align(mut self, align: AlignHints) -> Self {
+            self.label_align = align;
+            self
+        }
+
         /// Get the direction
         #[inline]
         pub fn direction(&self) -> Direction {
@@ -80,15 +100,16 @@ widget! {
 
     impl WidgetConfig for Self {
         fn configure(&mut self, mgr: &mut SetRectMgr) {
-            mgr.add_accel_keys(self.inner.id_ref(), self.keys());
+            mgr.add_accel_string(self.inner.id_ref(), self.label.text());
         }
     }
 
     impl Layout for Self {
         fn layout(&mut self) -> layout::Layout<'_> {
+            let label = layout::Layout::text(&mut self.label_store, &mut self.label, TextClass::Label);
             let arr = [
                 layout::Layout::single(&mut self.inner),
-                layout::Layout::text(&mut self.label_store, &mut self.label, TextClass::Label),
+                layout::Layout::align(label, self.label_align),
             ];
             layout::Layout::list(arr.into_iter(), self.dir, &mut self.layout_store)
         }
@@ -117,3 +138,14 @@ widget! {
         }
     }
 }
+
+impl<W: Widget> FormRow<W> {
+    /// Construct a form row from `inner` widget and `label`
+    ///
+    /// The label is placed to the left of the widget and right-aligned.
+    #[inline]
+    pub fn new_row<T: Into<AccelString>>(inner: W, label: T) -> Self {
+        let align = AlignHints::new(Some(Align::BR), None);
+        Self::new(inner, label).with_label_align(align)
+    }
+}
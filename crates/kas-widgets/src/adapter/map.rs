@@ -71,3 +71,86 @@ widget! {
         }
     }
 }
+
+/// Parameterisation of [`MapMsg`] converting messages via [`Into`]
+///
+/// Construct with `MapMsg::new_into`.
+pub type MapInto<W, M> = MapMsg<W, M, fn(&mut EventMgr, <W as Handler>::Msg) -> M>;
+
+widget! {
+    /// Wrapper to convert messages from the inner widget
+    ///
+    /// This is similar to [`MapResponse`], but uses static dispatch and
+    /// supports only message conversion: each message from the inner widget
+    /// is converted to a message of type `M` by the closure `F`. All other
+    /// responses are passed through unchanged.
+    #[autoimpl(Debug skip self.map)]
+    #[autoimpl(Deref, DerefMut on self.inner)]
+    #[autoimpl(class_traits where W: trait on self.inner)]
+    #[derive(Clone)]
+    #[widget{
+        layout = single;
+    }]
+    #[handler(msg=M)]
+    pub struct MapMsg<W: Widget, M: 'static, F: Fn(&mut EventMgr, W::Msg) -> M + 'static> {
+        #[widget_core]
+        core: kas::CoreData,
+        #[widget]
+        inner: W,
+        map: F,
+        _pd: std::marker::PhantomData<M>,
+    }
+
+    impl Self {
+        /// Construct
+        ///
+        /// Any response from the child widget with a message payload is mapped
+        /// through the closure `f`.
+        pub fn new(child: W, f: F) -> Self {
+            MapMsg {
+                core: Default::default(),
+                inner: child,
+                map: f,
+                _pd: Default::default(),
+            }
+        }
+
+        /// Deconstruct, returning the inner widget
+        pub fn into_inner(self) -> W {
+            self.inner
+        }
+    }
+
+    impl SendEvent for Self {
+        fn send(&mut self, mgr: &mut EventMgr, id: WidgetId, event: Event) -> Response<Self::Msg> {
+            if self.is_disabled() {
+                return Response::Unused;
+            }
+
+            if self.eq_id(&id) {
+                self.handle(mgr, event)
+            } else {
+                let r = self.inner.send(mgr, id.clone(), event);
+                r.try_into().unwrap_or_else(|msg| {
+                    log::trace!(
+                        "Received by {} from {}: {:?}",
+                        self.id(),
+                        id,
+                        kas::util::TryFormat(&msg)
+                    );
+                    Response::Msg((self.map)(mgr, msg))
+                })
+            }
+        }
+    }
+}
+
+impl<W: Widget, M: 'static> MapInto<W, M>
+where
+    W::Msg: Into<M>,
+{
+    /// Construct, converting messages via [`Into`]
+    pub fn new_into(child: W) -> Self {
+        MapMsg::new(child, |_, msg| msg.into())
+    }
+}
@@ -0,0 +1,122 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Margin override
+
+use kas::layout::{self, Margins};
+use kas::prelude::*;
+
+/// Selects margins, either explicitly or from the theme
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MarginSelector {
+    /// No margins
+    None,
+    /// Margins between widgets ([`SizeMgr::outer_margins`])
+    Outer,
+    /// Inner margins ([`SizeMgr::inner_margin`])
+    Inner,
+    /// Margins around frames ([`SizeMgr::frame_margins`])
+    Frame,
+    /// Margins around text ([`SizeMgr::text_margins`])
+    Text,
+    /// Explicit margins, in physical pixels
+    Pixels(Margins),
+    /// Equal margins on each edge, in virtual pixels
+    ///
+    /// This is scaled by [`SizeMgr::scale_factor`].
+    ScaledPx(f32),
+    /// Equal margins on each edge, in font Em
+    Em(f32),
+}
+
+impl Default for MarginSelector {
+    fn default() -> Self {
+        MarginSelector::Outer
+    }
+}
+
+impl From<Margins> for MarginSelector {
+    #[inline]
+    fn from(m: Margins) -> Self {
+        MarginSelector::Pixels(m)
+    }
+}
+
+impl MarginSelector {
+    /// Resolve to margins in physical pixels
+    pub fn select(self, size_mgr: SizeMgr) -> Margins {
+        match self {
+            MarginSelector::None => Margins::ZERO,
+            MarginSelector::Outer => size_mgr.outer_margins(),
+            MarginSelector::Inner => {
+                let size = size_mgr.inner_margin();
+                Margins::hv_splat(size.0.cast(), size.1.cast())
+            }
+            MarginSelector::Frame => size_mgr.frame_margins(),
+            MarginSelector::Text => size_mgr.text_margins(),
+            MarginSelector::Pixels(m) => m,
+            MarginSelector::ScaledPx(px) => {
+                Margins::splat(size_mgr.pixels_from_virtual(px).cast_nearest())
+            }
+            MarginSelector::Em(em) => Margins::splat(size_mgr.pixels_from_em(em).cast_nearest()),
+        }
+    }
+}
+
+widget! {
+    /// A wrapper widget overriding the margins of its child
+    ///
+    /// Size requirements are those of the inner widget, except that margins
+    /// (space reserved between this and adjacent widgets) are replaced by
+    /// those of the [`MarginSelector`].
+    #[autoimpl(Deref, DerefMut on self.inner)]
+    #[autoimpl(class_traits where W: trait on self.inner)]
+    #[derive(Clone, Default, Debug)]
+    #[handler(msg = <W as Handler>::Msg)]
+    pub struct WithMargins<W: Widget> {
+        #[widget_core]
+        core: CoreData,
+        #[widget]
+        pub inner: W,
+        margins: MarginSelector,
+    }
+
+    impl Self {
+        /// Construct
+        #[inline]
+        pub fn new<M: Into<MarginSelector>>(inner: W, margins: M) -> Self {
+            WithMargins {
+                core: Default::default(),
+                inner,
+                margins: margins.into(),
+            }
+        }
+
+        /// Get the margin selector
+        #[inline]
+        pub fn margins(&self) -> MarginSelector {
+            self.margins
+        }
+
+        /// Set the margin selector
+        pub fn set_margins<M: Into<MarginSelector>>(&mut self, margins: M) -> TkAction {
+            self.margins = margins.into();
+            TkAction::RESIZE
+        }
+    }
+
+    impl Layout for Self {
+        fn layout(&mut self) -> layout::Layout<'_> {
+            layout::Layout::single(&mut self.inner)
+        }
+
+        fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
+            let margins = self.margins.select(size_mgr.re()).extract(axis);
+            let mut rules = self.inner.size_rules(size_mgr, axis);
+            rules.set_margins(margins);
+            rules
+        }
+    }
+}
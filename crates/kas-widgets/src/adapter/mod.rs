@@ -5,12 +5,20 @@
 
 //! Adapter widgets (wrappers)
 
+mod badge;
+mod filter;
 mod label;
 mod map;
+mod margins;
 mod reserve;
+mod size;
 mod widget_ext;
 
-pub use label::WithLabel;
-pub use map::MapResponse;
-pub use reserve::{Reserve, ReserveP};
+pub use badge::{Badge, Corner};
+pub use filter::{Filter, FilterAction};
+pub use label::{FormRow, WithLabel};
+pub use map::{MapInto, MapMsg, MapResponse};
+pub use margins::{MarginSelector, WithMargins};
+pub use reserve::{Reserve, ReserveB, ReserveP};
+pub use size::{LogicalSize, MaxSize, MinSize};
 pub use widget_ext::*;
@@ -7,6 +7,7 @@
 
 use kas::layout;
 use kas::prelude::*;
+use kas::theme::TextClass;
 
 /// Parameterisation of [`Reserve`] using a function pointer
 ///
@@ -16,6 +17,11 @@ use kas::prelude::*;
 /// a function pointer may be preferred.
 pub type ReserveP<W> = Reserve<W, fn(SizeMgr, AxisInfo) -> SizeRules>;
 
+/// Parameterisation of [`Reserve`] using a boxed closure
+///
+/// This is the type constructed by `Reserve::new_str`.
+pub type ReserveB<W> = Reserve<W, Box<dyn FnMut(SizeMgr, AxisInfo) -> SizeRules>>;
+
 widget! {
     /// A generic widget for size reservations
     ///
@@ -85,3 +91,26 @@ widget! {
         }
     }
 }
+
+impl<W: Widget> ReserveB<W> {
+    /// Construct a reserve sized to fit the given text
+    ///
+    /// The reservation is the size of `text` when displayed as `class`,
+    /// regardless of the inner widget's current content. This is useful
+    /// where content changes rapidly (e.g. a timer or counter), to avoid
+    /// layout changes each time the length of text changes:
+    ///```
+    /// use kas_widgets::adapter::Reserve;
+    /// use kas_widgets::Label;
+    /// use kas::theme::TextClass;
+    ///
+    /// let label = Reserve::new_str(Label::new("0"), "99999", TextClass::Label);
+    ///```
+    pub fn new_str<S: ToString>(inner: W, text: S, class: TextClass) -> Self {
+        let mut text = Text::new_single(text.to_string());
+        Reserve::new(
+            inner,
+            Box::new(move |size_mgr: SizeMgr, axis| size_mgr.text_bound(&mut text, class, axis)),
+        )
+    }
+}
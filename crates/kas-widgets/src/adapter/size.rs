@@ -0,0 +1,140 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Size limits
+
+use kas::layout;
+use kas::prelude::*;
+
+/// A size, resolved against the theme during layout
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LogicalSize {
+    /// Width and height in virtual pixels
+    ///
+    /// This is scaled by [`SizeMgr::scale_factor`].
+    ScaledPx(f32, f32),
+    /// Width and height in font Em
+    Em(f32, f32),
+}
+
+impl LogicalSize {
+    /// Resolve one component to physical pixels
+    pub fn extract<D: Directional>(self, dir: D, size_mgr: SizeMgr) -> i32 {
+        let (w, h, px) = match self {
+            LogicalSize::ScaledPx(w, h) => (w, h, size_mgr.scale_factor()),
+            LogicalSize::Em(w, h) => (w, h, size_mgr.pixels_from_em(1.0)),
+        };
+        let v = if dir.is_vertical() { h } else { w };
+        // Note: `as` conversion saturates, thus f32::INFINITY may be used
+        (v * px).max(0.0) as i32
+    }
+}
+
+widget! {
+    /// A wrapper widget enforcing a minimum size
+    ///
+    /// Size requirements are those of the inner widget, except that the
+    /// minimum and ideal sizes are at least the given size.
+    #[autoimpl(Deref, DerefMut on self.inner)]
+    #[autoimpl(class_traits where W: trait on self.inner)]
+    #[derive(Clone, Debug)]
+    #[handler(msg = <W as Handler>::Msg)]
+    pub struct MinSize<W: Widget> {
+        #[widget_core]
+        core: CoreData,
+        #[widget]
+        pub inner: W,
+        size: LogicalSize,
+    }
+
+    impl Self {
+        /// Construct
+        #[inline]
+        pub fn new(inner: W, size: LogicalSize) -> Self {
+            MinSize {
+                core: Default::default(),
+                inner,
+                size,
+            }
+        }
+    }
+
+    impl Layout for Self {
+        fn layout(&mut self) -> layout::Layout<'_> {
+            layout::Layout::single(&mut self.inner)
+        }
+
+        fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
+            let min = self.size.extract(axis, size_mgr.re());
+            let rules = self.inner.size_rules(size_mgr, axis);
+            if rules.min_size() >= min {
+                return rules;
+            }
+            SizeRules::new(min, rules.ideal_size(), rules.margins(), rules.stretch())
+        }
+    }
+}
+
+widget! {
+    /// A wrapper widget enforcing a maximum size
+    ///
+    /// Size requirements are those of the inner widget, except that the
+    /// minimum and ideal sizes are at most the given size and the widget does
+    /// not stretch beyond this size. When given more space, the inner widget
+    /// is aligned within the available area.
+    #[autoimpl(Deref, DerefMut on self.inner)]
+    #[autoimpl(class_traits where W: trait on self.inner)]
+    #[derive(Clone, Debug)]
+    #[handler(msg = <W as Handler>::Msg)]
+    pub struct MaxSize<W: Widget> {
+        #[widget_core]
+        core: CoreData,
+        #[widget]
+        pub inner: W,
+        size: LogicalSize,
+        max: Size,
+    }
+
+    impl Self {
+        /// Construct
+        #[inline]
+        pub fn new(inner: W, size: LogicalSize) -> Self {
+            MaxSize {
+                core: Default::default(),
+                inner,
+                size,
+                max: Size::ZERO,
+            }
+        }
+    }
+
+    impl Layout for Self {
+        fn layout(&mut self) -> layout::Layout<'_> {
+            layout::Layout::single(&mut self.inner)
+        }
+
+        fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
+            let max = self.size.extract(axis, size_mgr.re());
+            self.max.set_component(axis, max);
+            let rules = self.inner.size_rules(size_mgr, axis);
+            let unconstrained = max == i32::MAX || rules.stretch() == Stretch::None;
+            if rules.ideal_size() <= max && unconstrained {
+                return rules;
+            }
+            let min = rules.min_size().min(max);
+            let ideal = rules.ideal_size().min(max);
+            SizeRules::new(min, ideal, rules.margins(), Stretch::None)
+        }
+
+        fn set_rect(&mut self, mgr: &mut SetRectMgr, rect: Rect, align: AlignHints) {
+            self.core.rect = rect;
+            let size = Size(rect.size.0.min(self.max.0), rect.size.1.min(self.max.1));
+            let rect = align
+                .complete(Align::Default, Align::Default)
+                .aligned_rect(size, rect);
+            self.inner.set_rect(mgr, rect, align);
+        }
+    }
+}
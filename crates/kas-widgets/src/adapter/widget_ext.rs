@@ -5,12 +5,16 @@
 
 //! Widget extension traits
 
-use super::{MapResponse, Reserve, WithLabel};
+use super::{
+    Badge, Filter, FilterAction, LogicalSize, MapInto, MapResponse, MarginSelector, MaxSize,
+    MinSize, Reserve,
+};
+use super::{ReserveB, WithLabel, WithMargins};
 use kas::dir::Directional;
-use kas::event::{EventMgr, Response};
+use kas::event::{Event, EventMgr, Response};
 use kas::layout::{AxisInfo, SizeRules};
 use kas::text::AccelString;
-use kas::theme::SizeMgr;
+use kas::theme::{SizeMgr, TextClass};
 #[allow(unused)]
 use kas::Layout;
 use kas::Widget;
@@ -29,6 +33,18 @@ pub trait WidgetExt: Widget {
         MapResponse::new(self, move |mgr, msg| Response::Msg(f(mgr, msg)))
     }
 
+    /// Construct a wrapper widget which converts messages from this widget
+    ///
+    /// Messages from this widget are converted via [`Into`].
+    #[must_use]
+    fn map_into<M>(self) -> MapInto<Self, M>
+    where
+        Self::Msg: Into<M>,
+        Self: Sized,
+    {
+        MapInto::new_into(self)
+    }
+
     /// Construct a wrapper widget which discards messages from this widget
     ///
     /// Responses from this widget with a message payload are mapped to
@@ -53,6 +69,19 @@ pub trait WidgetExt: Widget {
         MapResponse::new(self, f)
     }
 
+    /// Construct a wrapper widget which filters events sent to this widget
+    ///
+    /// Each event sent to this widget or its descendants is first passed to
+    /// `filter`, which decides whether to pass the event on. See [`Filter`].
+    #[must_use]
+    fn with_filter<F>(self, filter: F) -> Filter<Self, F>
+    where
+        F: Fn(&mut EventMgr, &Event) -> FilterAction + 'static,
+        Self: Sized,
+    {
+        Filter::new(self, filter)
+    }
+
     /// Construct a wrapper widget which reserves extra space
     ///
     /// The closure `reserve` should generate `SizeRules` on request, just like
@@ -89,6 +118,24 @@ pub trait WidgetExt: Widget {
         Reserve::new(self, r)
     }
 
+    /// Construct a wrapper widget reserving space for the given text
+    ///
+    /// The widget is sized to fit at least `text` (as a label), regardless of
+    /// current content. See `Reserve::new_str`:
+    ///```
+    /// # use kas_widgets::adapter::WidgetExt;
+    /// use kas_widgets::Label;
+    ///
+    /// let counter = Label::new("0").with_reserve_str("99999");
+    ///```
+    #[must_use]
+    fn with_reserve_str<S: ToString>(self, text: S) -> ReserveB<Self>
+    where
+        Self: Sized,
+    {
+        ReserveB::new_str(self, text, TextClass::Label)
+    }
+
     /// Construct a wrapper widget adding a label
     #[must_use]
     fn with_label<D, T>(self, direction: D, label: T) -> WithLabel<Self, D>
@@ -99,5 +146,68 @@ pub trait WidgetExt: Widget {
     {
         WithLabel::new_with_direction(direction, self, label)
     }
+
+    /// Construct a wrapper widget overriding margins
+    ///
+    /// Margins of this widget are replaced with `margins`, which may be a
+    /// [`MarginSelector`] or explicit [`kas::layout::Margins`].
+    #[must_use]
+    fn with_margins<S: Into<MarginSelector>>(self, margins: S) -> WithMargins<Self>
+    where
+        Self: Sized,
+    {
+        WithMargins::new(self, margins)
+    }
+
+    /// Construct a wrapper widget enforcing a minimum size, in font Em
+    #[must_use]
+    fn with_min_size_em(self, width: f32, height: f32) -> MinSize<Self>
+    where
+        Self: Sized,
+    {
+        MinSize::new(self, LogicalSize::Em(width, height))
+    }
+
+    /// Construct a wrapper widget enforcing a minimum size, in virtual pixels
+    #[must_use]
+    fn with_min_size_px(self, width: f32, height: f32) -> MinSize<Self>
+    where
+        Self: Sized,
+    {
+        MinSize::new(self, LogicalSize::ScaledPx(width, height))
+    }
+
+    /// Construct a wrapper widget enforcing a maximum size, in font Em
+    ///
+    /// Use `f32::INFINITY` to leave one axis unconstrained.
+    #[must_use]
+    fn with_max_size_em(self, width: f32, height: f32) -> MaxSize<Self>
+    where
+        Self: Sized,
+    {
+        MaxSize::new(self, LogicalSize::Em(width, height))
+    }
+
+    /// Construct a wrapper widget enforcing a maximum size, in virtual pixels
+    ///
+    /// Use `f32::INFINITY` to leave one axis unconstrained.
+    #[must_use]
+    fn with_max_size_px(self, width: f32, height: f32) -> MaxSize<Self>
+    where
+        Self: Sized,
+    {
+        MaxSize::new(self, LogicalSize::ScaledPx(width, height))
+    }
+
+    /// Construct a wrapper widget able to overlay a badge
+    ///
+    /// The badge is initially hidden. See [`Badge`].
+    #[must_use]
+    fn with_badge(self) -> Badge<Self>
+    where
+        Self: Sized,
+    {
+        Badge::new(self)
+    }
 }
 impl<W: Widget + ?Sized> WidgetExt for W {}
@@ -5,11 +5,13 @@
 
 //! Push-buttons
 
+use crate::Icon;
 use kas::draw::color::Rgb;
 use kas::event::{self, VirtualKeyCode, VirtualKeyCodes};
 use kas::layout;
 use kas::prelude::*;
-use kas::theme::TextClass;
+use kas::theme::{IconName, TextClass};
+use std::borrow::Cow;
 use std::rc::Rc;
 
 widget! {
@@ -87,6 +89,34 @@ widget! {
         }
     }
 
+    impl Button<Icon, VoidMsg> {
+        /// Construct an icon-only button
+        ///
+        /// The icon is sized relative to the line height of button text (see
+        /// [`Icon`]). To use other custom content, construct with
+        /// [`Button::new`] instead.
+        #[inline]
+        pub fn image(id: ImageId) -> Self {
+            Button::new(Icon::new(id))
+        }
+
+        /// Construct an icon-only button showing an SVG image
+        ///
+        /// See [`Icon::svg`].
+        #[inline]
+        pub fn svg<D: Into<Cow<'static, [u8]>>>(data: D) -> Self {
+            Button::new(Icon::svg(data))
+        }
+
+        /// Construct an icon-only button showing a named icon
+        ///
+        /// See [`kas::theme::icons`].
+        #[inline]
+        pub fn named_icon<N: Into<IconName>>(name: N) -> Self {
+            Button::new(Icon::named(name))
+        }
+    }
+
     impl Self {
         /// Construct a button with a given `inner` widget and event handler `f`
         ///
@@ -175,6 +205,9 @@ widget! {
     /// Default alignment of the button is to stretch horizontally and centre
     /// vertically. The text label is always centred (irrespective of alignment
     /// parameters).
+    ///
+    /// Optionally, an [`Icon`] may be shown alongside the label; by default this
+    /// is placed before (left of) the label.
     #[autoimpl(Debug skip self.on_push)]
     #[derive(Clone)]
     pub struct TextButton<M: 'static> {
@@ -183,7 +216,11 @@ widget! {
         keys1: VirtualKeyCodes,
         layout_frame: layout::FrameStorage,
         layout_text: layout::TextStorage,
+        layout_list: layout::FixedRowStorage<2>,
         color: Option<Rgb>,
+        #[widget]
+        icon: Icon,
+        icon_pos: Direction,
         label: Text<AccelString>,
         on_push: Option<Rc<dyn Fn(&mut EventMgr) -> Option<M>>>,
     }
@@ -191,7 +228,7 @@ widget! {
     impl WidgetConfig for Self {
         fn configure(&mut self, mgr: &mut SetRectMgr) {
             mgr.add_accel_keys(self.id_ref(), &self.keys1);
-            mgr.add_accel_keys(self.id_ref(), self.label.text().keys());
+            mgr.add_accel_string(self.id_ref(), self.label.text());
         }
 
         fn key_nav(&self) -> bool {
@@ -204,7 +241,15 @@ widget! {
 
     impl Layout for Self {
         fn layout(&mut self) -> layout::Layout<'_> {
-            let inner = layout::Layout::text(&mut self.layout_text, &mut self.label, TextClass::Button);
+            let text = layout::Layout::text(&mut self.layout_text, &mut self.label, TextClass::Button);
+            let inner = if self.icon.is_empty() {
+                text
+            } else {
+                let icon = layout::Layout::single(&mut self.icon);
+                // The icon is first; list direction is away from the icon
+                let dir = self.icon_pos.reversed();
+                layout::Layout::list([icon, text].into_iter(), dir, &mut self.layout_list)
+            };
             layout::Layout::button(&mut self.layout_frame, inner, self.color)
         }
     }
@@ -220,7 +265,10 @@ widget! {
                 keys1: Default::default(),
                 layout_frame: Default::default(),
                 layout_text: Default::default(),
+                layout_list: Default::default(),
                 color: None,
+                icon: Default::default(),
+                icon_pos: Direction::Left,
                 label: text,
                 on_push: None,
             }
@@ -242,7 +290,10 @@ widget! {
                 keys1: self.keys1,
                 layout_frame: self.layout_frame,
                 layout_text: self.layout_text,
+                layout_list: self.layout_list,
                 color: self.color,
+                icon: self.icon,
+                icon_pos: self.icon_pos,
                 label: self.label,
                 on_push: Some(Rc::new(f)),
             }
@@ -297,6 +348,67 @@ widget! {
             self.color = Some(color);
             self
         }
+
+        /// Set an icon (chain style)
+        ///
+        /// The image must have been loaded already; see [`Icon`].
+        #[must_use]
+        pub fn with_icon(mut self, id: ImageId) -> Self {
+            let _ = self.icon.set_image(Some(id));
+            self
+        }
+
+        /// Set or clear the icon
+        pub fn set_icon(&mut self, id: Option<ImageId>) -> TkAction {
+            self.icon.set_image(id)
+        }
+
+        /// Set a named icon (chain style)
+        ///
+        /// See [`kas::theme::icons`].
+        #[must_use]
+        pub fn with_named_icon<N: Into<IconName>>(mut self, name: N) -> Self {
+            let _ = self.icon.set_name(Some(name.into()));
+            self
+        }
+
+        /// Set or clear the named icon
+        pub fn set_named_icon(&mut self, name: Option<IconName>) -> TkAction {
+            self.icon.set_name(name)
+        }
+
+        /// Set an SVG icon (chain style)
+        ///
+        /// See [`Icon::svg`].
+        #[must_use]
+        pub fn with_svg_icon<D: Into<Cow<'static, [u8]>>>(mut self, data: D) -> Self {
+            self.icon = Icon::svg(data);
+            self
+        }
+
+        /// Set the position of the icon relative to the label (chain style)
+        ///
+        /// By default this is [`Direction::Left`].
+        #[must_use]
+        pub fn with_icon_position(mut self, pos: Direction) -> Self {
+            self.icon_pos = pos;
+            self
+        }
+
+        /// Set the position of the icon relative to the label
+        pub fn set_icon_position(&mut self, pos: Direction) -> TkAction {
+            self.icon_pos = pos;
+            TkAction::RESIZE
+        }
+
+        /// Set the icon size relative to line height (chain style)
+        ///
+        /// By default this is `1.0`.
+        #[must_use]
+        pub fn with_icon_scale(mut self, scale: f32) -> Self {
+            let _ = self.icon.set_scale(scale);
+            self
+        }
     }
 
     impl HasStr for Self {
@@ -331,4 +443,18 @@ widget! {
             }
         }
     }
+
+    impl SendEvent for Self {
+        fn send(&mut self, mgr: &mut EventMgr, id: WidgetId, event: Event) -> Response<M> {
+            if self.is_disabled() {
+                return Response::Unused;
+            }
+            if self.eq_id(&id) {
+                EventMgr::handle_generic(self, mgr, event)
+            } else {
+                debug_assert!(self.icon.id().is_ancestor_of(&id));
+                self.icon.send(mgr, id, event).void_into()
+            }
+        }
+    }
 }
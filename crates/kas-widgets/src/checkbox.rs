@@ -6,11 +6,16 @@
 //! Toggle widgets
 
 use super::AccelLabel;
+use kas::theme::CheckState;
 use kas::{event, prelude::*};
 use std::rc::Rc;
 
 widget! {
     /// A bare checkbox (no label)
+    ///
+    /// In addition to checked and unchecked states, the checkbox supports an
+    /// indeterminate state (see [`CheckState`]). This may only be set
+    /// programmatically; activation of an indeterminate checkbox checks it.
     #[autoimpl(Debug skip self.on_toggle)]
     #[derive(Clone, Default)]
     #[widget{
@@ -20,8 +25,8 @@ widget! {
     pub struct CheckBoxBare<M: 'static> {
         #[widget_core]
         core: CoreData,
-        state: bool,
-        on_toggle: Option<Rc<dyn Fn(&mut EventMgr, bool) -> Option<M>>>,
+        state: CheckState,
+        on_toggle: Option<Rc<dyn Fn(&mut EventMgr, CheckState) -> Option<M>>>,
     }
 
     impl Layout for Self {
@@ -50,7 +55,7 @@ widget! {
         pub fn new() -> Self {
             CheckBoxBare {
                 core: Default::default(),
-                state: false,
+                state: CheckState::Unchecked,
                 on_toggle: None,
             }
         }
@@ -65,6 +70,21 @@ widget! {
         pub fn on_toggle<M, F>(self, f: F) -> CheckBoxBare<M>
         where
             F: Fn(&mut EventMgr, bool) -> Option<M> + 'static,
+        {
+            self.on_state_change(move |mgr, state| f(mgr, state.is_checked()))
+        }
+
+        /// Set event handler `f`, receiving the full [`CheckState`]
+        ///
+        /// On toggle (through user input events or [`Event::Activate`]) the
+        /// closure `f` is called with the new state. The result of `f` is
+        /// converted to [`Response::Msg`] or [`Response::Update`] and returned
+        /// to the parent.
+        #[inline]
+        #[must_use]
+        pub fn on_state_change<M, F>(self, f: F) -> CheckBoxBare<M>
+        where
+            F: Fn(&mut EventMgr, CheckState) -> Option<M> + 'static,
         {
             CheckBoxBare {
                 core: self.core,
@@ -94,19 +114,52 @@ widget! {
         #[inline]
         #[must_use]
         pub fn with_state(mut self, state: bool) -> Self {
+            self.state = state.into();
+            self
+        }
+
+        /// Set the initial state of the checkbox, which may be indeterminate
+        #[inline]
+        #[must_use]
+        pub fn with_check_state(mut self, state: CheckState) -> Self {
             self.state = state;
             self
         }
+
+        /// Get the state of the checkbox
+        #[inline]
+        pub fn check_state(&self) -> CheckState {
+            self.state
+        }
+
+        /// Set the state of the checkbox
+        pub fn set_check_state(&mut self, state: CheckState) -> TkAction {
+            self.state = state;
+            TkAction::REDRAW
+        }
     }
 
     impl HasBool for Self {
+        /// True if checked (not if indeterminate)
         fn get_bool(&self) -> bool {
-            self.state
+            self.state.is_checked()
         }
 
         fn set_bool(&mut self, state: bool) -> TkAction {
-            self.state = state;
-            TkAction::REDRAW
+            self.set_check_state(state.into())
+        }
+    }
+
+    impl SaveState for Self {
+        fn save_state(&self) -> Option<StateValue> {
+            Some(StateValue::Bool(self.state.is_checked()))
+        }
+
+        fn restore_state(&mut self, state: &StateValue) -> TkAction {
+            match state.as_bool() {
+                Some(state) => self.set_bool(state),
+                None => TkAction::empty(),
+            }
         }
     }
 
@@ -121,7 +174,7 @@ widget! {
         fn handle(&mut self, mgr: &mut EventMgr, event: Event) -> Response<M> {
             match event {
                 Event::Activate => {
-                    self.state = !self.state;
+                    self.state = (!self.state.is_checked()).into();
                     mgr.redraw(self.id());
                     Response::update_or_msg(self.on_toggle.as_ref().and_then(|f| f(mgr, self.state)))
                 }
@@ -151,7 +204,7 @@ widget! {
 
     impl WidgetConfig for Self {
         fn configure(&mut self, mgr: &mut SetRectMgr) {
-            mgr.add_accel_keys(self.checkbox.id_ref(), self.label.keys());
+            mgr.add_accel_string(self.checkbox.id_ref(), self.label.accel_string());
         }
     }
 
@@ -190,6 +243,25 @@ widget! {
                 label: self.label,
             }
         }
+
+        /// Set event handler `f`, receiving the full [`CheckState`]
+        ///
+        /// On toggle (through user input events or [`Event::Activate`]) the
+        /// closure `f` is called with the new state. The result of `f` is
+        /// converted to [`Response::Msg`] or [`Response::Update`] and returned
+        /// to the parent.
+        #[inline]
+        #[must_use]
+        pub fn on_state_change<M, F>(self, f: F) -> CheckBox<M>
+        where
+            F: Fn(&mut EventMgr, CheckState) -> Option<M> + 'static,
+        {
+            CheckBox {
+                core: self.core,
+                checkbox: self.checkbox.on_state_change(f),
+                label: self.label,
+            }
+        }
     }
 
     impl Self {
@@ -216,5 +288,25 @@ widget! {
             self.checkbox = self.checkbox.with_state(state);
             self
         }
+
+        /// Set the initial state of the checkbox, which may be indeterminate
+        #[inline]
+        #[must_use]
+        pub fn with_check_state(mut self, state: CheckState) -> Self {
+            self.checkbox = self.checkbox.with_check_state(state);
+            self
+        }
+
+        /// Get the state of the checkbox
+        #[inline]
+        pub fn check_state(&self) -> CheckState {
+            self.checkbox.check_state()
+        }
+
+        /// Set the state of the checkbox
+        #[inline]
+        pub fn set_check_state(&mut self, state: CheckState) -> TkAction {
+            self.checkbox.set_check_state(state)
+        }
     }
 }
@@ -5,18 +5,30 @@
 
 //! Combobox
 
-use super::{IndexedColumn, MenuEntry};
-use kas::event::{self, Command};
+use super::{MenuEntry, ScrollBars};
+use crate::view::{Driver, ListView, SelectionMode};
+use kas::event::{self, ChildMsg};
 use kas::layout;
 use kas::prelude::*;
 use kas::theme::TextClass;
 use kas::WindowId;
 use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+/// Type-ahead input is reset after this delay without input
+const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_secs(1);
 
 widget! {
     /// A pop-up multiple choice menu
     ///
     /// A combobox presents a menu with a fixed set of choices when clicked.
+    ///
+    /// The pop-up menu is a virtualised [`ListView`], thus only visible entries
+    /// are represented by widgets and very long lists of choices are
+    /// supported. The height of the pop-up is limited (see
+    /// [`ComboBox::with_max_visible`]); further entries are reached by
+    /// scrolling. While the pop-up is open, typing selects the first entry
+    /// starting with the typed text.
     #[autoimpl(Debug skip self.on_select)]
     #[derive(Clone)]
     #[widget{
@@ -34,6 +46,9 @@ widget! {
         active: usize,
         opening: bool,
         popup_id: Option<WindowId>,
+        search: String,
+        search_pos: usize,
+        search_time: Instant,
         on_select: Option<Rc<dyn Fn(&mut EventMgr, usize) -> Option<M>>>,
     }
 
@@ -57,6 +72,24 @@ widget! {
         }
     }
 
+    impl SaveState for Self {
+        fn save_state(&self) -> Option<StateValue> {
+            Some(StateValue::Int(self.active.cast()))
+        }
+
+        fn restore_state(&mut self, state: &StateValue) -> TkAction {
+            match state.as_int().and_then(|i| usize::try_from(i).ok()) {
+                Some(index) => self.set_active(index),
+                None => TkAction::empty(),
+            }
+        }
+
+        fn saves_children(&self) -> bool {
+            // The pop-up's selection follows our active choice
+            false
+        }
+    }
+
     impl event::Handler for Self {
         type Msg = M;
 
@@ -67,9 +100,10 @@ widget! {
                     parent: s.id(),
                     direction: Direction::Down,
                 });
-                if let Some(id) = s.popup.inner.get_child(s.active).map(|w| w.id()) {
-                    mgr.set_nav_focus(id, key_focus);
-                }
+                s.search.clear();
+                s.search_pos = s.active;
+                // The list is only sized once the pop-up is shown
+                s.popup.pending_focus = Some((s.active, key_focus));
             };
             match event {
                 Event::Activate => {
@@ -80,12 +114,23 @@ widget! {
                     }
                     Response::Used
                 }
+                Event::ReceivedCharacter(c) if self.popup_id.is_some() => {
+                    self.type_ahead(mgr, c);
+                    Response::Used
+                }
                 Event::PressStart {
                     source,
                     start_id,
                     coord,
                 } => {
-                    if start_id.as_ref().map(|id| self.is_ancestor_of(id)).unwrap_or(false) {
+                    if let Some(id) = start_id.clone().filter(|id| {
+                        self.popup_id.is_some() && self.popup.is_ancestor_of(id) && !self.popup.is_entry(id)
+                    }) {
+                        // Allow use of the pop-up's scroll bar
+                        let event = Event::PressStart { source, start_id, coord };
+                        let r = self.popup.send(mgr, id, event);
+                        self.map_response(mgr, r)
+                    } else if start_id.as_ref().map(|id| self.is_ancestor_of(id)).unwrap_or(false) {
                         if source.is_primary() {
                             mgr.grab_press_unique(self.id(), source, coord, None);
                             mgr.set_grab_depress(source, start_id);
@@ -108,11 +153,13 @@ widget! {
                     if self.popup_id.is_none() {
                         open_popup(self, mgr, false);
                     }
-                    let cond = self.popup.inner.rect().contains(coord);
+                    let cond = self.popup.rect().contains(coord);
                     let target = if cond { cur_id } else { None };
                     mgr.set_grab_depress(source, target.clone());
                     if let Some(id) = target {
-                        mgr.set_nav_focus(id, false);
+                        if self.popup.is_entry(&id) {
+                            mgr.set_nav_focus(id, false);
+                        }
                     }
                     Response::Used
                 }
@@ -127,7 +174,7 @@ widget! {
                             }
                         } else if self.popup_id.is_some() && self.popup.is_ancestor_of(id) {
                             let r = self.popup.send(mgr, id.clone(), Event::Activate);
-                            return self.map_response(mgr, id.clone(), event, r);
+                            return self.map_response(mgr, r);
                         }
                     }
                     if let Some(id) = self.popup_id {
@@ -167,8 +214,8 @@ widget! {
                     return Response::Used;
                 }
 
-                let r = self.popup.send(mgr, id.clone(), event.clone());
-                self.map_response(mgr, id, event, r)
+                let r = self.popup.send(mgr, id, event);
+                self.map_response(mgr, r)
             }
         }
     }
@@ -185,33 +232,29 @@ impl ComboBox<VoidMsg> {
     /// ```
     #[inline]
     pub fn new<T: Into<AccelString>, I: IntoIterator<Item = T>>(iter: I, active: usize) -> Self {
-        let entries = iter
-            .into_iter()
-            .map(|label| MenuEntry::new(label, ()))
-            .collect();
-        Self::new_entries(entries, active)
+        let entries = iter.into_iter().map(|label| label.into()).collect();
+        Self::new_vec(entries, active)
     }
 
-    /// Construct a combobox with the given menu entries
+    /// Construct a combobox from a list of labels
     ///
     /// A combobox presents a menu with a fixed set of choices when clicked,
     /// with the `active` choice selected (0-based index).
-    #[inline]
-    pub fn new_entries(entries: Vec<MenuEntry<()>>, active: usize) -> Self {
-        let label = entries.get(active).map(|entry| entry.get_string());
-        let label = Text::new_single(label.unwrap_or("".to_string()));
+    pub fn new_vec(entries: Vec<AccelString>, active: usize) -> Self {
+        let label = entries.get(active).map(|entry| entry.text().to_string());
+        let label = Text::new_single(label.unwrap_or_default());
         ComboBox {
             core: Default::default(),
             label,
             layout_frame: Default::default(),
             layout_text: Default::default(),
-            popup: ComboPopup {
-                core: Default::default(),
-                inner: IndexedColumn::new(entries),
-            },
+            popup: ComboPopup::new(entries, active),
             active,
             opening: false,
             popup_id: None,
+            search: String::new(),
+            search_pos: 0,
+            search_time: Instant::now(),
             on_select: None,
         }
     }
@@ -236,12 +279,34 @@ impl ComboBox<VoidMsg> {
             active: self.active,
             opening: self.opening,
             popup_id: self.popup_id,
+            search: self.search,
+            search_pos: self.search_pos,
+            search_time: self.search_time,
             on_select: Some(Rc::new(f)),
         }
     }
 }
 
 impl<M: 'static> ComboBox<M> {
+    /// Set the maximum number of entries visible in the pop-up (inline)
+    ///
+    /// The pop-up is sized to show up to this many entries; further entries
+    /// may be reached by scrolling. The default is 12.
+    #[inline]
+    #[must_use]
+    pub fn with_max_visible(mut self, number: usize) -> Self {
+        self.popup.max_visible = number.max(1);
+        self
+    }
+
+    /// Set the maximum number of entries visible in the pop-up
+    ///
+    /// See [`ComboBox::with_max_visible`].
+    pub fn set_max_visible(&mut self, number: usize) -> TkAction {
+        self.popup.max_visible = number.max(1);
+        TkAction::RESIZE
+    }
+
     /// Get the index of the active choice
     ///
     /// This index is normally less than the number of choices (`self.len()`),
@@ -254,15 +319,13 @@ impl<M: 'static> ComboBox<M> {
     /// Set the active choice
     #[inline]
     pub fn set_active(&mut self, index: usize) -> TkAction {
-        if self.active != index && index < self.popup.inner.len() {
+        if self.active != index && index < self.len() {
             self.active = index;
-            let string = if index < self.len() {
-                self.popup.inner[index].get_string()
-            } else {
-                "".to_string()
-            };
+            let string = self.popup.entries()[index].text().to_string();
+            let list = self.popup.list_mut();
+            let action = list.clear_selected() | list.select(index).unwrap_or_default();
             let avail = self.core.rect.size.clamped_sub(self.layout_frame.size);
-            kas::text::util::set_text_and_prepare(&mut self.label, string, avail)
+            action | kas::text::util::set_text_and_prepare(&mut self.label, string, avail)
         } else {
             TkAction::empty()
         }
@@ -271,136 +334,239 @@ impl<M: 'static> ComboBox<M> {
     /// Get the number of entries
     #[inline]
     pub fn len(&self) -> usize {
-        self.popup.inner.len()
+        self.popup.entries().len()
     }
 
     /// True if the box contains no entries
     #[inline]
     pub fn is_empty(&self) -> bool {
-        self.popup.inner.is_empty()
+        self.popup.entries().is_empty()
     }
 
     /// Remove all choices
-    ///
-    /// Triggers a [reconfigure action](EventState::send_action).
     pub fn clear(&mut self) -> TkAction {
-        self.popup.inner.clear()
+        self.popup.update_entries(|entries| entries.clear())
     }
 
     /// Add a choice to the combobox, in last position
-    ///
-    /// Triggers a [reconfigure action](EventState::send_action).
     pub fn push<T: Into<AccelString>>(&mut self, label: T) -> TkAction {
-        let column = &mut self.popup.inner;
-        column.push(MenuEntry::new(label, ()))
-        // TODO: localised reconfigure
+        let label = label.into();
+        self.popup.update_entries(|entries| entries.push(label))
     }
 
     /// Pops the last choice from the combobox
-    ///
-    /// Triggers a [reconfigure action](EventState::send_action).
     pub fn pop(&mut self) -> (Option<()>, TkAction) {
-        let r = self.popup.inner.pop();
-        (r.0.map(|_| ()), r.1)
+        let mut r = None;
+        let action = self.popup.update_entries(|entries| r = entries.pop());
+        (r.map(|_| ()), action)
     }
 
     /// Add a choice at position `index`
     ///
     /// Panics if `index > len`.
-    ///
-    /// Triggers a [reconfigure action](EventState::send_action).
     pub fn insert<T: Into<AccelString>>(&mut self, index: usize, label: T) -> TkAction {
-        let column = &mut self.popup.inner;
-        column.insert(index, MenuEntry::new(label, ()))
-        // TODO: localised reconfigure
+        let label = label.into();
+        self.popup
+            .update_entries(|entries| entries.insert(index, label))
     }
 
     /// Removes the choice at position `index`
     ///
     /// Panics if `index` is out of bounds.
-    ///
-    /// Triggers a [reconfigure action](EventState::send_action).
     pub fn remove(&mut self, index: usize) -> TkAction {
-        self.popup.inner.remove(index).1
+        self.popup.update_entries(|entries| {
+            entries.remove(index);
+        })
     }
 
     /// Replace the choice at `index`
     ///
     /// Panics if `index` is out of bounds.
     pub fn replace<T: Into<AccelString>>(&mut self, index: usize, label: T) -> TkAction {
-        self.popup.inner[index].set_accel(label)
+        let label = label.into();
+        let mut action = TkAction::empty();
+        if index == self.active {
+            let avail = self.core.rect.size.clamped_sub(self.layout_frame.size);
+            let string = label.text().to_string();
+            action = kas::text::util::set_text_and_prepare(&mut self.label, string, avail);
+        }
+        action | self.popup.update_entries(|entries| entries[index] = label)
     }
 }
 
 impl<M: 'static> ComboBox<M> {
+    fn select(&mut self, mgr: &mut EventMgr, index: usize) -> Response<M> {
+        if let Some(id) = self.popup_id {
+            mgr.close_window(id, true);
+        }
+        if index == self.active {
+            return Response::Used;
+        }
+        *mgr |= self.set_active(index);
+        if let Some(ref f) = self.on_select {
+            Response::update_or_msg((f)(mgr, index))
+        } else {
+            Response::Update
+        }
+    }
+
     fn map_response(
         &mut self,
         mgr: &mut EventMgr,
-        id: WidgetId,
-        event: Event,
-        r: Response<(usize, ())>,
+        r: Response<ChildMsg<usize, ()>>,
     ) -> Response<M> {
         match r {
-            Response::Unused => match event {
-                Event::Command(cmd, _) => {
-                    let next = |mgr: &mut EventMgr, s, clr, rev| {
-                        if clr {
-                            mgr.clear_nav_focus();
-                        }
-                        mgr.next_nav_focus(s, rev, true);
-                        Response::Used
-                    };
-                    match cmd {
-                        Command::Up => next(mgr, self, false, true),
-                        Command::Down => next(mgr, self, false, false),
-                        Command::Home => next(mgr, self, true, false),
-                        Command::End => next(mgr, self, true, true),
-                        _ => Response::Unused,
-                    }
-                }
-                _ => Response::Unused,
-            },
-            Response::Update | Response::Select => {
-                if let Some(id) = self.popup_id {
-                    mgr.close_window(id, true);
-                }
-                if let Some(index) = self.popup.inner.find_child_index(&id) {
-                    if index != self.active {
-                        *mgr |= self.set_active(index);
-                        return if let Some(ref f) = self.on_select {
-                            Response::update_or_msg((f)(mgr, index))
-                        } else {
-                            Response::Update
-                        };
-                    }
-                }
-                Response::Used
-            }
-            r => r.try_into().unwrap_or_else(|(index, ())| {
-                *mgr |= self.set_active(index);
-                if let Some(id) = self.popup_id {
-                    mgr.close_window(id, true);
-                }
-                if let Some(ref f) = self.on_select {
-                    Response::update_or_msg((f)(mgr, index))
-                } else {
-                    Response::Update
-                }
+            Response::Unused => Response::Unused,
+            // Scrolling and focus changes are internal to the pop-up
+            Response::Pan(_) | Response::Scrolled | Response::Focus(_) => Response::Used,
+            Response::Update | Response::Select => Response::Used,
+            r => r.try_into().unwrap_or_else(|msg| match msg {
+                ChildMsg::Select(index) | ChildMsg::Child(index, ()) => self.select(mgr, index),
+                ChildMsg::Deselect(_) => Response::Used,
             }),
         }
     }
+
+    // Jump to the next entry matching the type-ahead input
+    fn type_ahead(&mut self, mgr: &mut EventMgr, c: char) {
+        let now = Instant::now();
+        if now > self.search_time + TYPE_AHEAD_TIMEOUT {
+            self.search.clear();
+        }
+        if self.search.is_empty() && c.is_whitespace() {
+            return;
+        }
+        self.search_time = now;
+        self.search.extend(c.to_lowercase());
+
+        let entries = self.popup.entries();
+        let len = entries.len();
+        // Repeating a single character cycles through matching entries
+        let skip = usize::from(self.search.chars().count() == 1);
+        let found = (0..len)
+            .map(|i| (self.search_pos + skip + i) % len)
+            .find(|i| entries[*i].text().to_lowercase().starts_with(&self.search));
+        if let Some(index) = found {
+            self.search_pos = index;
+            mgr.set_rect_mgr(|mgr| self.popup.list_mut().focus_item(mgr, index, true));
+        }
+    }
+}
+
+/// Driver constructing [`MenuEntry`] widgets over labels
+#[derive(Clone, Debug, Default)]
+struct ComboDriver;
+impl Driver<AccelString> for ComboDriver {
+    type Msg = ();
+    type Widget = MenuEntry<()>;
+    fn make(&self) -> Self::Widget {
+        MenuEntry::new("", ())
+    }
+    fn set(&self, widget: &mut Self::Widget, data: AccelString) -> TkAction {
+        widget.set_accel_string(data)
+    }
+    fn get(&self, _: &Self::Widget) -> Option<AccelString> {
+        None
+    }
+    fn to_clipboard_text(&self, data: &AccelString) -> Option<String> {
+        Some(data.text().to_string())
+    }
 }
 
+type ComboList = ListView<kas::dir::Down, Vec<AccelString>, ComboDriver>;
+
 widget! {
     #[derive(Clone, Debug)]
-    #[widget{
-        layout = single;
-    }]
-    #[handler(msg=(usize, ()))]
+    #[handler(msg = ChildMsg<usize, ()>)]
     struct ComboPopup {
         #[widget_core]
         core: CoreData,
         #[widget]
-        inner: IndexedColumn<MenuEntry<()>>,
+        inner: ScrollBars<ComboList>,
+        max_visible: usize,
+        pending_focus: Option<(usize, bool)>,
+    }
+
+    impl Self {
+        fn new(entries: Vec<AccelString>, active: usize) -> Self {
+            let mut list = ComboList::new(entries).with_selection_mode(SelectionMode::Single);
+            let _ = list.select(active);
+            ComboPopup {
+                core: Default::default(),
+                inner: ScrollBars::new(list),
+                max_visible: 12,
+                pending_focus: None,
+            }
+        }
+
+        fn list_mut(&mut self) -> &mut ComboList {
+            self.inner.inner_mut()
+        }
+
+        fn entries(&self) -> &Vec<AccelString> {
+            self.inner.inner().data()
+        }
+
+        fn update_entries<F: FnOnce(&mut Vec<AccelString>)>(&mut self, f: F) -> TkAction {
+            let list = self.list_mut();
+            f(list.data_mut());
+            list.invalidate_view()
+        }
+
+        /// True if `id` is a view widget of the list
+        fn is_entry(&self, id: &WidgetId) -> bool {
+            self.inner.inner().find_child_index(id).is_some()
+        }
+    }
+
+    impl WidgetConfig for Self {
+        fn configure(&mut self, mgr: &mut SetRectMgr) {
+            mgr.remove_nav_fallback(self.id_ref());
+        }
+    }
+
+    impl Layout for Self {
+        fn layout(&mut self) -> layout::Layout<'_> {
+            layout::Layout::single(&mut self.inner)
+        }
+
+        fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
+            let num = self.entries().len().clamp(1, self.max_visible);
+            let _ = self.list_mut().set_num_visible(num.cast());
+            self.inner.size_rules(size_mgr, axis)
+        }
+
+        fn set_rect(&mut self, mgr: &mut SetRectMgr, rect: Rect, align: AlignHints) {
+            self.core.rect = rect;
+            self.inner.set_rect(mgr, rect, align);
+            if let Some((index, key_focus)) = self.pending_focus.take() {
+                if self.list_mut().focus_item(mgr, index, key_focus).is_some() {
+                    // Update scroll bar position
+                    self.inner.set_rect(mgr, rect, align);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn state() {
+        let mut combo = ComboBox::new(["a", "b", "c"], 2);
+        let state = combo.save_state().unwrap();
+        assert_eq!(state, StateValue::Int(2));
+
+        let _ = combo.set_active(0);
+        let _ = combo.restore_state(&state);
+        assert_eq!(combo.active(), 2);
+        assert!(combo.popup.list_mut().is_selected(&2));
+
+        // Invalid choices are ignored
+        let _ = combo.restore_state(&StateValue::Int(5));
+        let _ = combo.restore_state(&StateValue::Int(-1));
+        assert_eq!(combo.active(), 2);
     }
 }
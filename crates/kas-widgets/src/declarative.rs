@@ -0,0 +1,355 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Declarative UI definitions (experimental)
+//!
+//! A [`UiNode`] describes a tree of built-in widgets. It may be written by hand
+//! or loaded from a file in any format supported by [`kas::config::Format`]
+//! (e.g. RON, with feature `ron`):
+//!
+//! ```ron
+//! Column([
+//!     Label("Hello, world!"),
+//!     EditBox(text: "name", msg: Some("name")),
+//!     Row([
+//!         CheckBox(label: "Shout", msg: Some("shout")),
+//!         Button(label: "&Greet", msg: "greet"),
+//!     ]),
+//! ])
+//! ```
+//!
+//! [`DeclarativeUi`] builds widgets from this description. Instead of typed
+//! messages, widgets emit a [`UiMsg`] identified by name; handlers are
+//! registered by name via [`DeclarativeUi::on`].
+//!
+//! In debug builds, a [`DeclarativeUi`] loaded from a file watches that file
+//! and reloads on change. Only those children of the root which changed are
+//! rebuilt (and lose their state).
+
+use crate::adapter::WidgetExt;
+use crate::{BoxColumn, BoxRow, CheckBox, EditBox, Frame, Label, List, Separator, TextButton};
+use kas::dir::Direction;
+use kas::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+#[cfg(debug_assertions)]
+use std::time::{Duration, SystemTime};
+
+#[cfg(debug_assertions)]
+const TIMER_RELOAD: u64 = 1 << 60;
+#[cfg(debug_assertions)]
+const RELOAD_POLL: Duration = Duration::from_millis(500);
+
+/// Description of a widget tree
+///
+/// Only built-in widgets are supported. Widgets which emit messages take a
+/// message name (`msg`); see [`UiMsg`].
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum UiNode {
+    /// A text [`Label`]
+    Label(String),
+    /// A [`TextButton`], emitting `msg` with [`UiValue::None`] when pressed
+    Button { label: String, msg: String },
+    /// A [`CheckBox`], emitting `msg` with [`UiValue::Bool`] when toggled
+    CheckBox {
+        label: String,
+        #[serde(default)]
+        state: bool,
+        #[serde(default)]
+        msg: Option<String>,
+    },
+    /// An [`EditBox`], emitting `msg` with [`UiValue::Text`] on activation
+    EditBox {
+        #[serde(default)]
+        text: String,
+        #[serde(default)]
+        msg: Option<String>,
+    },
+    /// A [`Separator`]
+    Separator,
+    /// A [`Frame`] around content
+    Frame(Box<UiNode>),
+    /// A column of widgets
+    Column(Vec<UiNode>),
+    /// A row of widgets
+    Row(Vec<UiNode>),
+}
+
+impl UiNode {
+    /// Read a description from `path`
+    ///
+    /// The format is guessed from the path's extension (see
+    /// [`kas::config::Format`]).
+    pub fn read_path(path: &Path) -> Result<Self, kas::config::Error> {
+        kas::config::Format::guess_and_read_path(path)
+    }
+
+    /// Build a widget from this description
+    pub fn build(&self) -> Box<dyn Widget<Msg = UiMsg>> {
+        match self {
+            UiNode::Label(text) => Box::new(Label::new(text.clone()).map_into()),
+            UiNode::Button { label, msg } => {
+                let msg = UiMsg::new(msg.clone(), UiValue::None);
+                Box::new(TextButton::new_msg(label.as_str(), msg))
+            }
+            UiNode::CheckBox { label, state, msg } => {
+                let msg = msg.clone();
+                let checkbox = CheckBox::new(label.as_str()).on_toggle(move |_, state| {
+                    let name = msg.clone()?;
+                    Some(UiMsg::new(name, UiValue::Bool(state)))
+                });
+                Box::new(checkbox.with_state(*state))
+            }
+            UiNode::EditBox { text, msg } => {
+                let msg = msg.clone();
+                Box::new(EditBox::new(text).on_activate(move |text, _| {
+                    let name = msg.clone()?;
+                    Some(UiMsg::new(name, UiValue::Text(text.to_string())))
+                }))
+            }
+            UiNode::Separator => Box::new(Separator::<UiMsg>::infer()),
+            UiNode::Frame(inner) => Box::new(Frame::new(inner.build())),
+            UiNode::Column(list) => Box::new(BoxColumn::new(build_all(list))),
+            UiNode::Row(list) => Box::new(BoxRow::new(build_all(list))),
+        }
+    }
+
+    /// Direction and children of the root list
+    ///
+    /// A root node other than a row or column is placed in a column.
+    fn root_children(&self) -> (Direction, &[UiNode]) {
+        match self {
+            UiNode::Column(list) => (Direction::Down, list),
+            UiNode::Row(list) => (Direction::Right, list),
+            node => (Direction::Down, std::slice::from_ref(node)),
+        }
+    }
+}
+
+/// Value attached to a [`UiMsg`]
+#[derive(Clone, Debug, PartialEq)]
+pub enum UiValue {
+    /// No value (e.g. from a button)
+    None,
+    /// A check state
+    Bool(bool),
+    /// Text content
+    Text(String),
+}
+
+/// Message emitted by a widget built from a [`UiNode`]
+#[derive(Clone, Debug, PartialEq)]
+pub struct UiMsg {
+    /// The message name, as given in the [`UiNode`]
+    pub name: String,
+    /// An attached value
+    pub value: UiValue,
+}
+
+impl UiMsg {
+    /// Construct
+    #[inline]
+    pub fn new(name: String, value: UiValue) -> Self {
+        UiMsg { name, value }
+    }
+}
+
+impl From<VoidMsg> for UiMsg {
+    fn from(msg: VoidMsg) -> Self {
+        match msg {}
+    }
+}
+
+type UiHandler = Box<dyn FnMut(&mut EventMgr, &UiValue)>;
+
+struct Handlers(HashMap<String, UiHandler>);
+
+impl fmt::Debug for Handlers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
+
+widget! {
+    /// A widget tree built from a [`UiNode`]
+    ///
+    /// Messages ([`UiMsg`]) from child widgets are passed to the handler
+    /// registered under the message's name (see [`DeclarativeUi::on`]);
+    /// messages without a handler are logged and discarded.
+    ///
+    /// In debug builds, if constructed via [`DeclarativeUi::load`], the source
+    /// file is polled for changes and reloaded; changed children of the root
+    /// row or column are rebuilt while unchanged children keep their state.
+    /// Errors while reloading are logged and the existing UI is kept.
+    #[derive(Debug)]
+    #[widget{
+        layout = single;
+    }]
+    pub struct DeclarativeUi {
+        #[widget_core]
+        core: CoreData,
+        #[widget(use_msg = handle_msg)]
+        root: List<Direction, Box<dyn Widget<Msg = UiMsg>>>,
+        node: UiNode,
+        handlers: Handlers,
+        path: Option<PathBuf>,
+        #[cfg(debug_assertions)]
+        modified: Option<SystemTime>,
+    }
+
+    impl Self {
+        /// Construct from a description
+        pub fn new(node: UiNode) -> Self {
+            let (direction, children) = node.root_children();
+            let root = List::new_with_direction(
+                direction,
+                build_all(children),
+            );
+            DeclarativeUi {
+                core: Default::default(),
+                root,
+                node,
+                handlers: Handlers(HashMap::new()),
+                path: None,
+                #[cfg(debug_assertions)]
+                modified: None,
+            }
+        }
+
+        /// Construct from a file
+        ///
+        /// See [`UiNode::read_path`]. In debug builds, the file is reloaded on
+        /// change.
+        pub fn load<P: Into<PathBuf>>(path: P) -> Result<Self, kas::config::Error> {
+            let path = path.into();
+            #[cfg(debug_assertions)]
+            let modified = modified_time(&path);
+            let mut ui = DeclarativeUi::new(UiNode::read_path(&path)?);
+            ui.path = Some(path);
+            #[cfg(debug_assertions)]
+            {
+                ui.modified = modified;
+            }
+            Ok(ui)
+        }
+
+        /// Register a handler for messages named `name` (chain style)
+        ///
+        /// Any previous handler for `name` is replaced.
+        #[must_use]
+        pub fn on<F>(mut self, name: &str, f: F) -> Self
+        where
+            F: FnMut(&mut EventMgr, &UiValue) + 'static,
+        {
+            self.handlers.0.insert(name.to_string(), Box::new(f));
+            self
+        }
+
+        /// Access the current description
+        pub fn node(&self) -> &UiNode {
+            &self.node
+        }
+
+        /// Get the source path, if loaded from a file
+        pub fn path(&self) -> Option<&Path> {
+            self.path.as_deref()
+        }
+
+        /// Replace the description
+        ///
+        /// The new description is compared with the current one: children of
+        /// the root row or column which are unchanged are kept, while others
+        /// are rebuilt.
+        pub fn set_node(&mut self, mgr: &mut EventMgr, node: UiNode) {
+            if node == self.node {
+                return;
+            }
+
+            let (direction, children) = node.root_children();
+            let mut action = self.root.set_direction(direction);
+            let (_, old) = self.node.root_children();
+            let common = old.len().min(children.len());
+            let root = &mut self.root;
+            mgr.set_rect_mgr(|mgr| {
+                for i in 0..common {
+                    if old[i] != children[i] {
+                        root.replace(mgr, i, children[i].build());
+                    }
+                }
+            });
+            while self.root.len() > children.len() {
+                action |= self.root.pop().1;
+            }
+            action |= self.root.extend(children[common..].iter().map(UiNode::build));
+
+            mgr.send_action(action);
+            self.node = node;
+        }
+
+        /// Reload from the source file
+        ///
+        /// Does nothing if not constructed via [`DeclarativeUi::load`].
+        pub fn reload(&mut self, mgr: &mut EventMgr) -> Result<(), kas::config::Error> {
+            if let Some(path) = self.path.clone() {
+                let node = UiNode::read_path(&path)?;
+                self.set_node(mgr, node);
+            }
+            Ok(())
+        }
+
+        fn handle_msg(&mut self, mgr: &mut EventMgr, msg: UiMsg) {
+            if let Some(handler) = self.handlers.0.get_mut(&msg.name) {
+                handler(mgr, &msg.value);
+            } else {
+                log::warn!("DeclarativeUi: no handler for message {:?}", msg.name);
+            }
+        }
+    }
+
+    impl WidgetConfig for Self {
+        #[cfg_attr(not(debug_assertions), allow(unused_variables))]
+        fn configure(&mut self, mgr: &mut SetRectMgr) {
+            #[cfg(debug_assertions)]
+            if self.path.is_some() {
+                mgr.update_on_timer(RELOAD_POLL, self.id(), TIMER_RELOAD);
+            }
+        }
+    }
+
+    impl Handler for Self {
+        type Msg = VoidMsg;
+
+        fn handle(&mut self, mgr: &mut EventMgr, event: Event) -> Response<Self::Msg> {
+            match event {
+                #[cfg(debug_assertions)]
+                Event::TimerUpdate(TIMER_RELOAD) => {
+                    if self.path.is_some() {
+                        let modified = self.path.as_deref().and_then(modified_time);
+                        if modified != self.modified {
+                            self.modified = modified;
+                            if let Err(e) = self.reload(mgr) {
+                                log::warn!("DeclarativeUi: failed to reload: {}", e);
+                            }
+                        }
+                        mgr.update_on_timer(RELOAD_POLL, self.id(), TIMER_RELOAD);
+                    }
+                    Response::Used
+                }
+                _ => Response::Unused,
+            }
+        }
+    }
+}
+
+fn build_all(list: &[UiNode]) -> Vec<Box<dyn Widget<Msg = UiMsg>>> {
+    list.iter().map(UiNode::build).collect()
+}
+
+#[cfg(debug_assertions)]
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
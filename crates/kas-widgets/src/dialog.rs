@@ -7,24 +7,38 @@
 //!
 //! KAS dialog boxes are pre-configured windows, usually allowing some
 //! customisation.
+//!
+//! [`MessageBox`] is a window. Other dialogs ([`Confirm`], [`Alert`] and
+//! [`TextPrompt`]) are widgets which emit their result as a message; these may
+//! be embedded within another widget or shown in a separate window via
+//! [`DialogWindow`].
 
-use crate::{Label, TextButton};
+use crate::{EditBox, EditField, EditGuard, Icon, Label, Row, TextButton};
 use kas::event::VirtualKeyCode;
 use kas::prelude::*;
 use kas::text::format::FormattableText;
-use kas::WindowId;
+use kas::theme::{IconName, IconSize, StandardIcon};
+use kas::{Future, WindowId};
 
 widget! {
     /// A simple message box.
+    ///
+    /// Optionally, an icon may be shown beside the message; see
+    /// [`MessageBox::with_named_icon`].
     #[derive(Clone, Debug)]
     #[widget{
-        layout = column: *;
+        layout = column: [
+            row: [self.icon, self.label],
+            self.button,
+        ];
     }]
     pub struct MessageBox<T: FormattableText + 'static> {
         #[widget_core]
         core: CoreData,
         title: String,
         #[widget]
+        icon: Icon,
+        #[widget]
         label: Label<T>,
         #[widget(use_msg = handle_button)]
         button: TextButton<()>,
@@ -35,6 +49,7 @@ widget! {
             MessageBox {
                 core: Default::default(),
                 title: title.to_string(),
+                icon: Default::default(),
                 label: Label::new(message),
                 button: TextButton::new_msg("Ok", ()).with_keys(&[
                     VirtualKeyCode::Return,
@@ -44,6 +59,16 @@ widget! {
             }
         }
 
+        /// Show a named icon beside the message (chain style)
+        ///
+        /// For example, [`StandardIcon::Warning`](kas::theme::StandardIcon::Warning).
+        /// See [`kas::theme::icons`].
+        #[must_use]
+        pub fn with_named_icon<N: Into<IconName>>(mut self, name: N) -> Self {
+            self.icon = Icon::named(name).with_size(IconSize::Large);
+            self
+        }
+
         fn handle_button(&mut self, mgr: &mut EventMgr, _: ()) {
             mgr.send_action(TkAction::CLOSE);
         }
@@ -77,3 +102,329 @@ widget! {
         fn resize_popups(&mut self, _: &mut SetRectMgr) {}
     }
 }
+
+widget! {
+    /// A window presenting a dialog
+    ///
+    /// The window is closed when the `dialog` emits a message (its result).
+    /// Use [`DialogWindow::future`] to obtain this result.
+    ///
+    /// The dialog is modal: input to the window from which it was opened is
+    /// blocked until the dialog is closed.
+    #[autoimpl(Debug skip self.result, self.finish)]
+    #[widget{
+        layout = single;
+    }]
+    pub struct DialogWindow<D: Widget + 'static> {
+        #[widget_core]
+        core: CoreData,
+        title: String,
+        #[widget(use_msg = set_result)]
+        dialog: D,
+        result: Option<D::Msg>,
+        finish: Option<(Box<dyn FnMut(&mut Option<D::Msg>)>, UpdateHandle)>,
+    }
+
+    impl Self {
+        /// Construct
+        pub fn new<A: ToString>(title: A, dialog: D) -> Self {
+            DialogWindow {
+                core: Default::default(),
+                title: title.to_string(),
+                dialog,
+                result: None,
+                finish: None,
+            }
+        }
+
+        /// Get a future for the dialog's result
+        ///
+        /// The future completes when the window is closed, yielding the
+        /// dialog's message or `None` if the window was closed without one.
+        /// In order to be notified when the future completes, its owner should
+        /// call [`EventState::update_on_handle`] with the returned
+        /// [`UpdateHandle`].
+        ///
+        /// Panics if called more than once.
+        pub fn future(&mut self) -> (Future<Option<D::Msg>>, UpdateHandle) {
+            if self.finish.is_some() {
+                panic!("DialogWindow::future: attempt to call multiple times");
+            }
+            let (future, finish) =
+                Future::new_box_fnmut(Box::new(|result: &mut Option<D::Msg>| result.take()));
+            let update = UpdateHandle::new();
+            self.finish = Some((finish, update));
+            (future, update)
+        }
+
+        fn set_result(&mut self, mgr: &mut EventMgr, msg: D::Msg) {
+            self.result = Some(msg);
+            mgr.send_action(TkAction::CLOSE);
+        }
+    }
+
+    impl kas::WidgetConfig for Self {
+        fn configure(&mut self, mgr: &mut SetRectMgr) {
+            mgr.enable_alt_bypass(self.id_ref(), true);
+        }
+    }
+
+    impl kas::Window for Self {
+        fn title(&self) -> &str {
+            &self.title
+        }
+
+        fn icon(&self) -> Option<kas::Icon> {
+            None
+        }
+
+        fn restrict_dimensions(&self) -> (bool, bool) {
+            (true, true)
+        }
+
+        fn modal(&self) -> bool {
+            true
+        }
+
+        // do not support overlays (yet?)
+        fn add_popup(&mut self, _: &mut EventMgr, _: WindowId, _: kas::Popup) {
+            panic!("DialogWindow does not (currently) support pop-ups");
+        }
+
+        fn remove_popup(&mut self, _: &mut EventMgr, _: WindowId) {}
+        fn resize_popups(&mut self, _: &mut SetRectMgr) {}
+
+        fn handle_closure(&mut self, mgr: &mut EventMgr) {
+            if let Some((mut finish, update)) = self.finish.take() {
+                finish(&mut self.result);
+                mgr.trigger_update(update, 0);
+            }
+        }
+    }
+}
+
+/// Result of a [`Confirm`] dialog
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum ConfirmResult {
+    Yes,
+    No,
+    Cancel,
+}
+
+fn confirm_buttons(
+    yes: AccelString,
+    no: AccelString,
+    cancel: bool,
+) -> Row<TextButton<ConfirmResult>> {
+    let escape = [VirtualKeyCode::Escape];
+    let mut buttons = vec![TextButton::new_msg(yes, ConfirmResult::Yes)];
+    let no = TextButton::new_msg(no, ConfirmResult::No);
+    if cancel {
+        buttons.push(no);
+        buttons.push(TextButton::new_msg("&Cancel", ConfirmResult::Cancel).with_keys(&escape));
+    } else {
+        buttons.push(no.with_keys(&escape));
+    }
+    Row::new(buttons)
+}
+
+widget! {
+    /// A confirmation dialog
+    ///
+    /// Shows a message with "Yes", "No" and (optionally) "Cancel" buttons,
+    /// emitting a [`ConfirmResult`] when one is pressed. The <kbd>Escape</kbd>
+    /// key selects "Cancel" (or "No" when there is no cancel button).
+    #[derive(Clone, Debug)]
+    #[handler(msg = ConfirmResult)]
+    #[widget{
+        layout = column: [
+            row: [self.icon, self.label],
+            self.buttons,
+        ];
+    }]
+    pub struct Confirm<T: FormattableText + 'static> {
+        #[widget_core]
+        core: CoreData,
+        #[widget]
+        icon: Icon,
+        #[widget]
+        label: Label<T>,
+        #[widget]
+        buttons: Row<TextButton<ConfirmResult>>,
+        labels: (AccelString, AccelString),
+        cancel: bool,
+    }
+
+    impl Self {
+        /// Construct with a message
+        ///
+        /// By default, the [`StandardIcon::Question`] icon is shown and a
+        /// "Cancel" button is included.
+        pub fn new(message: T) -> Self {
+            let labels = (AccelString::from("&Yes"), AccelString::from("&No"));
+            Confirm {
+                core: Default::default(),
+                icon: Icon::named(StandardIcon::Question).with_size(IconSize::Large),
+                label: Label::new(message),
+                buttons: confirm_buttons(labels.0.clone(), labels.1.clone(), true),
+                labels,
+                cancel: true,
+            }
+        }
+
+        /// Set labels of the "Yes" and "No" buttons (chain style)
+        ///
+        /// For example, `with_labels("&Save", "&Discard")`.
+        #[must_use]
+        pub fn with_labels<S: Into<AccelString>>(mut self, yes: S, no: S) -> Self {
+            self.labels = (yes.into(), no.into());
+            self.buttons = confirm_buttons(self.labels.0.clone(), self.labels.1.clone(), self.cancel);
+            self
+        }
+
+        /// Set whether to include a "Cancel" button (chain style)
+        #[must_use]
+        pub fn with_cancel(mut self, cancel: bool) -> Self {
+            self.cancel = cancel;
+            self.buttons = confirm_buttons(self.labels.0.clone(), self.labels.1.clone(), cancel);
+            self
+        }
+
+        /// Set the icon (chain style)
+        #[must_use]
+        pub fn with_named_icon<N: Into<IconName>>(mut self, name: N) -> Self {
+            self.icon = Icon::named(name).with_size(IconSize::Large);
+            self
+        }
+    }
+}
+
+/// Severity of an [`Alert`]
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Severity {
+    Information,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    /// Get the icon used for this severity
+    pub fn icon(self) -> StandardIcon {
+        match self {
+            Severity::Information => StandardIcon::Information,
+            Severity::Warning => StandardIcon::Warning,
+            Severity::Error => StandardIcon::Error,
+        }
+    }
+}
+
+widget! {
+    /// An alert
+    ///
+    /// Shows a message beside an icon indicating its [`Severity`] together
+    /// with an "Ok" button, emitting `()` when dismissed.
+    #[derive(Clone, Debug)]
+    #[handler(msg = ())]
+    #[widget{
+        layout = column: [
+            row: [self.icon, self.label],
+            self.button,
+        ];
+    }]
+    pub struct Alert<T: FormattableText + 'static> {
+        #[widget_core]
+        core: CoreData,
+        severity: Severity,
+        #[widget]
+        icon: Icon,
+        #[widget]
+        label: Label<T>,
+        #[widget]
+        button: TextButton<()>,
+    }
+
+    impl Self {
+        /// Construct with a `severity` and `message`
+        pub fn new(severity: Severity, message: T) -> Self {
+            Alert {
+                core: Default::default(),
+                severity,
+                icon: Icon::named(severity.icon()).with_size(IconSize::Large),
+                label: Label::new(message),
+                button: TextButton::new_msg("Ok", ()).with_keys(&[
+                    VirtualKeyCode::Return,
+                    VirtualKeyCode::Space,
+                    VirtualKeyCode::NumpadEnter,
+                    VirtualKeyCode::Escape,
+                ]),
+            }
+        }
+
+        /// Get the severity
+        pub fn severity(&self) -> Severity {
+            self.severity
+        }
+    }
+}
+
+/// Guard for [`TextPrompt`]: emits `()` on activation
+#[derive(Clone, Debug, Default)]
+struct PromptGuard;
+impl EditGuard for PromptGuard {
+    type Msg = ();
+
+    fn activate(_: &mut EditField<Self>, _: &mut EventMgr) -> Option<()> {
+        Some(())
+    }
+}
+
+widget! {
+    /// A text prompt
+    ///
+    /// Shows a message above an edit box, with "Ok" and "Cancel" buttons.
+    /// Emits `Some(text)` when accepted (via "Ok" or <kbd>Enter</kbd>) or
+    /// `None` when cancelled (via "Cancel" or <kbd>Escape</kbd>).
+    #[derive(Clone, Debug)]
+    #[handler(msg = Option<String>)]
+    #[widget{
+        layout = column: [
+            self.label,
+            self.edit,
+            row: [self.cancel, self.ok],
+        ];
+    }]
+    pub struct TextPrompt<T: FormattableText + 'static> {
+        #[widget_core]
+        core: CoreData,
+        #[widget]
+        label: Label<T>,
+        #[widget(map_msg = accept)]
+        edit: EditBox<PromptGuard>,
+        #[widget(map_msg = finish)]
+        cancel: TextButton<bool>,
+        #[widget(map_msg = finish)]
+        ok: TextButton<bool>,
+    }
+
+    impl Self {
+        /// Construct with a `message` and initial `text`
+        pub fn new<S: ToString>(message: T, text: S) -> Self {
+            TextPrompt {
+                core: Default::default(),
+                label: Label::new(message),
+                edit: EditBox::new(text).with_guard(PromptGuard),
+                cancel: TextButton::new_msg("&Cancel", false)
+                    .with_keys(&[VirtualKeyCode::Escape]),
+                ok: TextButton::new_msg("&Ok", true),
+            }
+        }
+
+        fn accept(&mut self, _: &mut EventMgr, _: ()) -> Option<String> {
+            Some(self.edit.get_string())
+        }
+
+        fn finish(&mut self, _: &mut EventMgr, ok: bool) -> Option<String> {
+            ok.then(|| self.edit.get_string())
+        }
+    }
+}
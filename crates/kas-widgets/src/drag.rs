@@ -8,8 +8,27 @@
 use std::fmt::Debug;
 
 use kas::event::{CursorIcon, PressSource};
+use kas::geom::Vec2;
 use kas::prelude::*;
 
+/// Drag rate while Shift is held (fine adjustment)
+const FINE_ADJUST: f32 = 0.1;
+
+/// Behaviour of presses on the track of a [`DragHandle`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TrackClick {
+    /// Move the handle to the press position and start dragging
+    Jump,
+    /// Move the handle one page towards the press position
+    Page,
+}
+
+impl Default for TrackClick {
+    fn default() -> Self {
+        TrackClick::Jump
+    }
+}
+
 widget! {
     /// Draggable Handle
     ///
@@ -26,6 +45,10 @@ widget! {
     /// 3.  [`Layout::draw`] does nothing. The parent should handle all drawing.
     /// 4.  Optionally, this widget can handle clicks on the track area via
     ///     [`DragHandle::handle_press_on_track`].
+    ///
+    /// While Shift is held, dragging moves the handle at a reduced rate (fine
+    /// adjustment). The parent may read the handle's position with sub-pixel
+    /// precision via [`DragHandle::drag_offset`].
     #[derive(Clone, Debug, Default)]
     #[widget{
         hover_highlight = true;
@@ -37,6 +60,8 @@ widget! {
         // The track is the area within which this DragHandle may move
         track: Rect,
         press_coord: Coord,
+        drag_offset: Vec2,
+        dragging: bool,
     }
 
     /// This implementation is unusual in that:
@@ -68,19 +93,37 @@ widget! {
 
                     // Event delivery implies coord is over the handle.
                     self.press_coord = coord - self.offset();
+                    self.drag_offset = Vec2::from(self.offset());
+                    self.dragging = true;
                     Response::Used
                 }
-                Event::PressMove { coord, .. } => {
-                    let offset = coord - self.press_coord;
+                Event::PressMove { coord, delta, .. } => {
+                    let old_drag_offset = self.drag_offset;
+                    let max_offset = Vec2::from(self.max_offset());
+                    let offset = if mgr.modifiers().shift() {
+                        let drag_offset = self.drag_offset + Vec2::from(delta) * FINE_ADJUST;
+                        self.drag_offset = drag_offset.max(Vec2::ZERO).min(max_offset);
+                        // Re-anchor the press, thus releasing Shift does not jump
+                        let offset = Offset::from(self.drag_offset);
+                        self.press_coord = coord - offset;
+                        offset
+                    } else {
+                        let offset = coord - self.press_coord;
+                        self.drag_offset = Vec2::from(offset).max(Vec2::ZERO).min(max_offset);
+                        offset
+                    };
                     let (offset, action) = self.set_offset(offset);
-                    if action.is_empty() {
+                    if action.is_empty() && self.drag_offset == old_drag_offset {
                         Response::Used
                     } else {
                         mgr.send_action(action);
                         Response::Msg(offset)
                     }
                 }
-                Event::PressEnd { .. } => Response::Used,
+                Event::PressEnd { .. } => {
+                    self.dragging = false;
+                    Response::Used
+                }
                 _ => Response::Unused,
             }
         }
@@ -94,6 +137,8 @@ impl DragHandle {
             core: Default::default(),
             track: Default::default(),
             press_coord: Coord::ZERO,
+            drag_offset: Vec2::ZERO,
+            dragging: false,
         }
     }
 
@@ -117,6 +162,24 @@ impl DragHandle {
         self.core.rect.pos - self.track.pos
     }
 
+    /// Get the handle offset with sub-pixel precision
+    ///
+    /// While dragging with fine adjustment (see [`DragHandle`]), this may
+    /// differ from [`DragHandle::offset`] by a fraction of a pixel.
+    #[inline]
+    pub fn drag_offset(&self) -> Vec2 {
+        match self.dragging {
+            true => self.drag_offset,
+            false => Vec2::from(self.offset()),
+        }
+    }
+
+    /// True while the handle is being dragged
+    #[inline]
+    pub fn is_dragging(&self) -> bool {
+        self.dragging
+    }
+
     /// Get the maximum allowed offset
     ///
     /// This depends on size of the handle and the track.
@@ -162,6 +225,8 @@ impl DragHandle {
         let (offset, action) = self.set_offset(coord - self.press_coord);
         debug_assert!(action == TkAction::REDRAW);
         mgr.send_action(action);
+        self.drag_offset = Vec2::from(offset);
+        self.dragging = true;
         offset
     }
 }
@@ -5,16 +5,20 @@
 
 //! Text-edit field
 
-use super::Scrollable;
+use super::{Scrollable, SpellChecker};
+use crate::view::{ListView, SelectionMode};
+use kas::dir::Down;
 use kas::event::components::{TextInput, TextInputAction};
-use kas::event::{self, Command, ScrollDelta};
+use kas::event::{self, ChildMsg, Command};
 use kas::geom::Vec2;
 use kas::layout;
 use kas::prelude::*;
 use kas::text::SelectionHelper;
 use kas::theme::TextClass;
+use kas::WindowId;
 use std::fmt::Debug;
 use std::ops::Range;
+use std::rc::Rc;
 use unicode_segmentation::{GraphemeCursor, UnicodeSegmentation};
 
 #[derive(Clone, Debug, PartialEq)]
@@ -31,6 +35,28 @@ impl Default for LastEdit {
     }
 }
 
+/// When an [`EditField`] commits its contents
+///
+/// A committed value is a "known good" value: <kbd>Escape</kbd> reverts to
+/// it, and [`EditField::is_dirty`] reports whether the contents differ from
+/// it. Programmatic updates (e.g. [`HasString::set_string`]) also set the
+/// committed value. See [`EditField::with_commit_mode`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CommitMode {
+    /// No commit/revert behaviour (default)
+    Disabled,
+    /// Commit on activation (e.g. <kbd>Enter</kbd> for single-line fields)
+    Activate,
+    /// Commit on activation or when keyboard focus is lost
+    ActivateOrFocusLost,
+}
+
+impl Default for CommitMode {
+    fn default() -> Self {
+        CommitMode::Disabled
+    }
+}
+
 enum EditAction {
     None,
     Unused,
@@ -97,6 +123,15 @@ pub trait EditGuard: Debug + Sized + 'static {
         None
     }
 
+    /// Commit guard
+    ///
+    /// This function is called when contents are committed (see
+    /// [`CommitMode`]), after the committed value is updated and before
+    /// [`EditGuard::activate`] or [`EditGuard::focus_lost`] is called.
+    fn commit(edit: &mut EditField<Self>, mgr: &mut EventMgr) {
+        let _ = (edit, mgr);
+    }
+
     /// Update guard
     ///
     /// This function is called on any programmatic update to the contents
@@ -104,12 +139,55 @@ pub trait EditGuard: Debug + Sized + 'static {
     fn update(edit: &mut EditField<Self>) {
         let _ = edit;
     }
+
+    /// Completion guard
+    ///
+    /// This function is called after each edit by the user. Candidates
+    /// returned are shown in a pop-up list below the field (at most 12),
+    /// where they may be navigated with the Up/Down keys and accepted with Tab
+    /// or Enter (or with a click). Accepting a candidate replaces
+    /// [`Completions::range`] of the text and is handled like any other edit.
+    /// Escape closes the list.
+    ///
+    /// The default implementation returns no candidates.
+    fn completions(edit: &EditField<Self>) -> Completions {
+        let _ = edit;
+        Completions::default()
+    }
 }
 
 impl EditGuard for () {
     type Msg = VoidMsg;
 }
 
+/// The maximum number of completions shown by an [`EditField`]
+const MAX_COMPLETIONS: usize = 12;
+
+/// Completion candidates (see [`EditGuard::completions`])
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Completions {
+    /// The range of text replaced when a candidate is accepted
+    pub range: Range<usize>,
+    /// Candidate replacement strings, in display order
+    pub candidates: Vec<String>,
+}
+
+impl Completions {
+    /// Construct, replacing `range` with one of `candidates`
+    #[inline]
+    pub fn new(range: Range<usize>, candidates: Vec<String>) -> Self {
+        Completions { range, candidates }
+    }
+
+    /// Construct, replacing the whole of `text` with one of `candidates`
+    ///
+    /// This is appropriate for e.g. search boxes and address bars.
+    #[inline]
+    pub fn replace_all(text: &str, candidates: Vec<String>) -> Self {
+        Completions::new(0..text.len(), candidates)
+    }
+}
+
 /// An [`EditGuard`] impl which calls a closure when activated
 #[autoimpl(Debug skip self.0)]
 #[derive(Clone)]
@@ -293,6 +371,16 @@ impl<G: EditGuard> EditBox<G> {
         self
     }
 
+    /// Set the commit mode (inline)
+    ///
+    /// See [`EditField::with_commit_mode`].
+    #[inline]
+    #[must_use]
+    pub fn with_commit_mode(mut self, mode: CommitMode) -> Self {
+        self.inner = self.inner.with_commit_mode(mode);
+        self
+    }
+
     /// Get whether this `EditBox` is editable
     #[inline]
     pub fn is_editable(&self) -> bool {
@@ -313,6 +401,26 @@ impl<G: EditGuard> EditBox<G> {
         self
     }
 
+    /// Set whether lines are wrapped (default: true)
+    ///
+    /// See [`EditField::with_wrap`].
+    #[inline]
+    #[must_use]
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.inner = self.inner.with_wrap(wrap);
+        self
+    }
+
+    /// Set a spell checker
+    ///
+    /// See [`EditField::with_spell_checker`].
+    #[inline]
+    #[must_use]
+    pub fn with_spell_checker(mut self, checker: Rc<dyn SpellChecker>) -> Self {
+        self.inner = self.inner.with_spell_checker(checker);
+        self
+    }
+
     /// Get whether the widget currently has keyboard input focus
     #[inline]
     pub fn has_key_focus(&self) -> bool {
@@ -348,7 +456,23 @@ widget! {
     ///
     /// Optionally, [`EditField::multi_line`] mode can be activated (enabling
     /// line-wrapping and a larger vertical height). This mode is only recommended
-    /// for short texts for performance reasons.
+    /// for short texts for performance reasons. Line-wrapping may be disabled
+    /// with [`EditField::with_wrap`], in which case long lines may be scrolled
+    /// horizontally (e.g. using [`ScrollBars`](super::ScrollBars)).
+    ///
+    /// Multiple cursors are supported: <kbd>Ctrl</kbd>+click adds a cursor,
+    /// [`Command::SelectNext`] (usually <kbd>Ctrl</kbd>+<kbd>D</kbd>) selects
+    /// the next occurrence of the selected text and <kbd>Alt</kbd>+drag makes
+    /// a rectangular selection (one cursor per line). Insertion, deletion,
+    /// clipboard operations and horizontal movement apply to all cursors; other
+    /// commands remove secondary cursors first.
+    ///
+    /// A [`SpellChecker`] may be assigned with
+    /// [`EditField::with_spell_checker`], in which case misspelled words in the
+    /// visible part of the text are underlined.
+    ///
+    /// Completions may be offered while typing via
+    /// [`EditGuard::completions`].
     #[derive(Clone, Default, Debug)]
     #[widget{
         key_nav = true;
@@ -361,15 +485,26 @@ widget! {
         view_offset: Offset,
         editable: bool,
         multi_line: bool,
+        wrap: bool,
         text: Text<String>,
         required: Vec2,
         selection: SelectionHelper,
         edit_x_coord: Option<f32>,
+        rect_anchor: Option<Vec2>,
         old_state: Option<(String, usize, usize)>,
         last_edit: LastEdit,
         has_key_focus: bool,
         error_state: bool,
         input_handler: TextInput,
+        spell_checker: Option<Rc<dyn SpellChecker>>,
+        misspelled: Vec<Range<usize>>,
+        spell_checked: Option<Range<usize>>,
+        // Created on first use (when the guard first offers completions)
+        completion: Option<CompletionPopup>,
+        completion_id: Option<WindowId>,
+        completion_range: Range<usize>,
+        commit_mode: CommitMode,
+        committed: String,
         /// The associated [`EditGuard`] implementation
         pub guard: G,
     }
@@ -392,14 +527,15 @@ widget! {
             };
 
             self.core.rect = rect;
+            self.input_handler.set_rect(rect);
             let size = rect.size;
-            let multi_line = self.multi_line;
+            let wrap = self.multi_line && self.wrap;
             self.required = self
                 .text
                 .update_env(|env| {
                     env.set_align(align.unwrap_or(Align::Default, valign));
                     env.set_bounds(size.into());
-                    env.set_wrap(multi_line);
+                    env.set_wrap(wrap);
                 })
                 .into();
             self.set_view_offset_from_edit_pos();
@@ -410,15 +546,28 @@ widget! {
             self.scroll_offset()
         }
 
+        fn spatial_nav(&mut self, _: &mut SetRectMgr, _: bool, _: Option<usize>) -> Option<usize> {
+            // We have no child within our rect
+            None
+        }
+
         fn draw(&mut self, mut draw: DrawMgr) {
             let class = if self.multi_line {
                 TextClass::EditMulti
             } else {
                 TextClass::Edit
             };
+            self.update_spelling();
             let mut draw = draw.with_core(self.core_data());
+            let preedit = match draw.ev_state().has_char_focus(self.id_ref()).0 {
+                true => draw.ev_state().composition().map(|s| self.preedit_text(s)),
+                false => None,
+            };
             draw.with_clip_region(self.rect(), self.view_offset, |mut draw| {
-                if self.selection.is_empty() {
+                if self.selection.has_extra() {
+                    let ranges = self.selection.ranges();
+                    draw.text_selected_ranges(self.rect().pos, &self.text, &ranges, class);
+                } else if self.selection.is_empty() {
                     draw.text(self.rect().pos, self.text.as_ref(), class);
                 } else {
                     // TODO(opt): we could cache the selection rectangles here to make
@@ -431,26 +580,89 @@ widget! {
                         class,
                     );
                 }
+                if !self.misspelled.is_empty() {
+                    draw.text_squiggle(self.rect().pos, &self.text, &self.misspelled, class);
+                }
                 if draw.ev_state().has_char_focus(self.id_ref()).0 {
-                    draw.text_cursor(
-                        self.rect().pos,
-                        self.text.as_ref(),
-                        class,
-                        self.selection.edit_pos(),
-                    );
+                    if self.selection.has_extra() {
+                        let bytes: Vec<usize> = self.selection.cursors().map(|c| c.0).collect();
+                        draw.text_cursors(self.rect().pos, self.text.as_ref(), class, &bytes);
+                    } else {
+                        draw.text_cursor(
+                            self.rect().pos,
+                            self.text.as_ref(),
+                            class,
+                            self.selection.edit_pos(),
+                        );
+                    }
+                }
+                if let Some((text, size)) = preedit.as_ref() {
+                    // Show composition feedback over the text at the edit position
+                    let edit_pos = self.selection.edit_pos();
+                    if let Some(marker) = self.text.text_glyph_pos(edit_pos).next_back() {
+                        let x = marker.pos.0.cast_nearest();
+                        let y = (marker.pos.1 - marker.ascent).cast_nearest();
+                        let rect = Rect::new(self.rect().pos + Offset(x, y), *size);
+                        draw.with_elevated(rect, |mut draw| {
+                            draw.text_selected(rect.pos, text, .., class);
+                        });
+                    }
                 }
             });
         }
     }
 
+    impl WidgetChildren for Self {
+        #[inline]
+        fn num_children(&self) -> usize {
+            usize::from(self.completion.is_some())
+        }
+        #[inline]
+        fn get_child(&self, index: usize) -> Option<&dyn WidgetConfig> {
+            match index {
+                0 => self.completion.as_ref().map(|w| w.as_widget()),
+                _ => None,
+            }
+        }
+        #[inline]
+        fn get_child_mut(&mut self, index: usize) -> Option<&mut dyn WidgetConfig> {
+            match index {
+                0 => self.completion.as_mut().map(|w| w.as_widget_mut()),
+                _ => None,
+            }
+        }
+    }
+
     impl HasStr for Self {
         fn get_str(&self) -> &str {
             self.text.text()
         }
     }
 
+    impl SaveState for Self {
+        fn save_state(&self) -> Option<StateValue> {
+            // Read-only fields display program state, thus are not saved
+            if self.editable {
+                Some(StateValue::Text(self.text.text().to_string()))
+            } else {
+                None
+            }
+        }
+
+        fn restore_state(&mut self, state: &StateValue) -> TkAction {
+            match state.as_text() {
+                Some(text) if self.editable => self.set_string(text.to_string()),
+                _ => TkAction::empty(),
+            }
+        }
+    }
+
     impl HasString for Self {
         fn set_string(&mut self, string: String) -> TkAction {
+            if self.commit_mode != CommitMode::Disabled {
+                self.committed = string.clone();
+            }
+
             // TODO: make text.set_string report bool for is changed?
             if *self.text.text() == string {
                 return TkAction::empty();
@@ -458,6 +670,7 @@ widget! {
 
             self.text.set_string(string);
             self.selection.clear();
+            self.spell_checked = None;
             if kas::text::fonts::fonts().num_faces() > 0 {
                 if let Some(req) = self.text.prepare() {
                     self.required = req.into();
@@ -486,20 +699,50 @@ widget! {
                     G::focus_gained(s, mgr);
                 }
             }
+            if self.completion_id.is_some() {
+                if let Event::PressStart { start_id, .. } = &event {
+                    let on_self = start_id.as_ref().map(|id| self.eq_id(id)).unwrap_or(false);
+                    let popup = self.completion.as_mut();
+                    let target = popup.and_then(|popup| {
+                        let id = start_id.clone().filter(|id| popup.is_ancestor_of(id))?;
+                        Some((popup, id))
+                    });
+                    if let Some((popup, id)) = target {
+                        // Forward input to the completion list
+                        let r = popup.send(mgr, id, event);
+                        return r.try_into().unwrap_or_else(|msg| self.completion_msg(mgr, msg));
+                    } else if !on_self {
+                        self.close_completions(mgr);
+                        return Response::Unused;
+                    }
+                }
+            }
+
             match event {
                 Event::Activate | Event::NavFocus(true) => {
                     request_focus(self, mgr);
                     Response::Focus(self.rect())
                 }
                 Event::NavFocus(false) => Response::Used,
+                Event::PopupRemoved(id) => {
+                    if self.completion_id == Some(id) {
+                        self.completion_id = None;
+                    }
+                    Response::Used
+                }
                 Event::LostCharFocus => {
+                    self.close_completions(mgr);
                     self.has_key_focus = false;
                     mgr.redraw(self.id());
+                    if self.commit_mode == CommitMode::ActivateOrFocusLost {
+                        self.commit_with(mgr);
+                    }
                     G::focus_lost(self, mgr)
                         .map(|msg| msg.into())
                         .unwrap_or(Response::Used)
                 }
                 Event::LostSelFocus => {
+                    self.selection.clear_extra();
                     self.selection.set_empty();
                     mgr.redraw(self.id());
                     Response::Used
@@ -509,11 +752,28 @@ widget! {
                     // ensure we have focus before acting on it.
                     request_focus(self, mgr);
                     if self.has_key_focus {
-                        match self.control_key(mgr, cmd, shift) {
+                        if let Some(r) = self.completion_key(mgr, cmd) {
+                            return r;
+                        }
+                        let action = self.control_key(mgr, cmd, shift);
+                        if !matches!(action, EditAction::Edit) {
+                            self.close_completions(mgr);
+                        }
+                        match action {
                             EditAction::None => Response::Used,
                             EditAction::Unused => Response::Unused,
-                            EditAction::Activate => Response::used_or_msg(G::activate(self, mgr)),
-                            EditAction::Edit => Response::update_or_msg(G::edit(self, mgr)),
+                            EditAction::Activate => {
+                                if self.commit_mode != CommitMode::Disabled {
+                                    self.commit_with(mgr);
+                                }
+                                Response::used_or_msg(G::activate(self, mgr))
+                            }
+                            EditAction::Edit => {
+                                self.spell_checked = None;
+                                let r = Response::update_or_msg(G::edit(self, mgr));
+                                self.update_completions(mgr);
+                                r
+                            }
                         }
                     } else {
                         Response::Unused
@@ -521,18 +781,17 @@ widget! {
                 }
                 Event::ReceivedCharacter(c) => match self.received_char(mgr, c) {
                     false => Response::Unused,
-                    true => Response::update_or_msg(G::edit(self, mgr)),
+                    true => {
+                        self.spell_checked = None;
+                        let r = Response::update_or_msg(G::edit(self, mgr));
+                        self.update_completions(mgr);
+                        r
+                    }
                 },
                 Event::Scroll(delta) => {
-                    let delta2 = match delta {
-                        ScrollDelta::LineDelta(x, y) => {
-                            // We arbitrarily scroll 3 lines:
-                            let dist = 3.0 * self.text.env().height(Default::default());
-                            Offset((x * dist).cast_nearest(), (y * dist).cast_nearest())
-                        }
-                        ScrollDelta::PixelDelta(coord) => coord,
-                    };
-                    self.pan_delta(mgr, delta2)
+                    // We arbitrarily scroll 3 lines:
+                    let dist = 3.0 * self.text.env().height(Default::default());
+                    self.pan_delta(mgr, delta.as_offset(dist))
                 }
                 event => match self.input_handler.handle(mgr, self.id(), event) {
                     TextInputAction::None => Response::Used,
@@ -544,17 +803,37 @@ widget! {
                     }
                     TextInputAction::Cursor(coord, anchor, clear, repeats) => {
                         request_focus(self, mgr);
-                        if self.has_key_focus {
-                            self.set_edit_pos_from_coord(mgr, coord);
-                            if anchor {
-                                self.selection.set_anchor();
-                            }
-                            if clear {
-                                self.selection.set_empty();
-                            }
-                            if repeats > 1 {
-                                self.selection.expand(&self.text, repeats);
+                        if !self.has_key_focus {
+                            return Response::Used;
+                        }
+                        let modifiers = mgr.modifiers();
+                        if anchor {
+                            self.rect_anchor = None;
+                            if modifiers.alt() {
+                                // Start a rectangular selection
+                                self.rect_anchor = Some(self.text_coord(coord));
+                                self.selection.clear_extra();
+                            } else if modifiers.ctrl() {
+                                // Add a cursor
+                                let pos = self.text.text_index_nearest(self.text_coord(coord).into());
+                                self.selection.add_cursor(pos, pos);
+                            } else {
+                                self.selection.clear_extra();
                             }
+                        } else if let Some(rect_anchor) = self.rect_anchor {
+                            self.set_rect_selection(mgr, rect_anchor, coord);
+                            return Response::Used;
+                        }
+
+                        self.set_edit_pos_from_coord(mgr, coord);
+                        if anchor {
+                            self.selection.set_anchor();
+                        }
+                        if clear {
+                            self.selection.set_empty();
+                        }
+                        if repeats > 1 {
+                            self.selection.expand(&self.text, repeats);
                         }
                         Response::Used
                     }
@@ -563,10 +842,30 @@ widget! {
         }
     }
 
+    impl SendEvent for Self
+    where
+        G: 'static,
+    {
+        fn send(&mut self, mgr: &mut EventMgr, id: WidgetId, event: Event) -> Response<Self::Msg> {
+            if self.is_disabled() {
+                return Response::Unused;
+            }
+
+            if self.eq_id(&id) {
+                EventMgr::handle_generic(self, mgr, event)
+            } else if let Some(popup) = self.completion.as_mut() {
+                let r = popup.send(mgr, id, event);
+                r.try_into().unwrap_or_else(|msg| self.completion_msg(mgr, msg))
+            } else {
+                Response::Unused
+            }
+        }
+    }
+
     impl Scrollable for Self {
         fn scroll_axes(&self, size: Size) -> (bool, bool) {
-            let max = self.max_scroll_offset();
-            (max.0 > size.0, max.1 > size.1)
+            let size = Vec2::from(size);
+            (self.required.0 > size.0, self.required.1 > size.1)
         }
 
         fn max_scroll_offset(&self) -> Offset {
@@ -602,15 +901,25 @@ impl EditField<()> {
             view_offset: Default::default(),
             editable: true,
             multi_line: false,
+            wrap: true,
             text: Text::new(Default::default(), text),
             required: Vec2::ZERO,
             selection: SelectionHelper::new(len, len),
             edit_x_coord: None,
+            rect_anchor: None,
             old_state: None,
             last_edit: LastEdit::None,
             has_key_focus: false,
             error_state: false,
             input_handler: Default::default(),
+            spell_checker: None,
+            misspelled: vec![],
+            spell_checked: None,
+            completion: None,
+            completion_id: None,
+            completion_range: 0..0,
+            commit_mode: CommitMode::Disabled,
+            committed: String::new(),
             guard: (),
         }
     }
@@ -630,15 +939,25 @@ impl EditField<()> {
             view_offset: self.view_offset,
             editable: self.editable,
             multi_line: self.multi_line,
+            wrap: self.wrap,
             text: self.text,
             required: self.required,
             selection: self.selection,
             edit_x_coord: self.edit_x_coord,
+            rect_anchor: self.rect_anchor,
             old_state: self.old_state,
             last_edit: self.last_edit,
             has_key_focus: self.has_key_focus,
             error_state: self.error_state,
             input_handler: self.input_handler,
+            spell_checker: self.spell_checker,
+            misspelled: self.misspelled,
+            spell_checked: self.spell_checked,
+            completion: self.completion,
+            completion_id: self.completion_id,
+            completion_range: self.completion_range,
+            commit_mode: self.commit_mode,
+            committed: self.committed,
             guard,
         };
         let _ = G::update(&mut edit);
@@ -732,6 +1051,147 @@ impl<G: EditGuard> EditField<G> {
         self
     }
 
+    /// Set whether lines are wrapped (default: true)
+    ///
+    /// This only affects [`EditField::multi_line`] mode. When disabled, lines
+    /// are not wrapped and may be scrolled horizontally.
+    #[inline]
+    #[must_use]
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Set a spell checker
+    ///
+    /// Misspelled words in the visible part of the text are underlined.
+    #[inline]
+    #[must_use]
+    pub fn with_spell_checker(mut self, checker: Rc<dyn SpellChecker>) -> Self {
+        self.spell_checker = Some(checker);
+        self.spell_checked = None;
+        self
+    }
+
+    /// Get the text index nearest to `coord`
+    ///
+    /// This may be used with [`EditField::misspelled_at`], for example to
+    /// find the word under the mouse cursor when opening a context menu.
+    pub fn index_at_coord(&self, coord: Coord) -> usize {
+        self.text.text_index_nearest(self.text_coord(coord).into())
+    }
+
+    /// Get the range of the misspelled word containing `index`, if any
+    ///
+    /// Only the visible part of the text (as last drawn) is checked.
+    pub fn misspelled_at(&self, index: usize) -> Option<Range<usize>> {
+        self.misspelled
+            .iter()
+            .find(|range| range.start <= index && index <= range.end)
+            .cloned()
+    }
+
+    /// Get spelling suggestions for the misspelled word containing `index`
+    ///
+    /// This is an integration point for context menus; see also
+    /// [`EditField::replace_misspelled`].
+    pub fn spelling_suggestions(&self, index: usize) -> Vec<String> {
+        match (self.spell_checker.as_ref(), self.misspelled_at(index)) {
+            (Some(checker), Some(range)) => checker.suggest(&self.text.text()[range]),
+            _ => vec![],
+        }
+    }
+
+    /// Replace the misspelled word containing `index` with `replacement`
+    ///
+    /// This edit may be undone. [`EditGuard::update`] is called on change.
+    pub fn replace_misspelled(&mut self, index: usize, replacement: &str) -> TkAction {
+        let range = match self.misspelled_at(index) {
+            Some(range) => range,
+            None => return TkAction::empty(),
+        };
+
+        let (pos, sel_pos) = (self.selection.edit_pos(), self.selection.sel_pos());
+        self.old_state = Some((self.text.clone_string(), pos, sel_pos));
+        self.last_edit = LastEdit::None;
+        let _ = self.text.replace_range(range.clone(), replacement);
+        self.selection.clear();
+        self.selection.set_pos(range.start + replacement.len());
+        self.edit_x_coord = None;
+        if let Some(req) = self.text.prepare() {
+            self.required = req.into();
+        }
+        self.spell_checked = None;
+        let _ = G::update(self);
+        TkAction::REDRAW
+    }
+
+    /// Set the commit mode (inline)
+    ///
+    /// The current contents become the committed value. See [`CommitMode`].
+    #[inline]
+    #[must_use]
+    pub fn with_commit_mode(mut self, mode: CommitMode) -> Self {
+        self.commit_mode = mode;
+        self.committed = self.text.clone_string();
+        self
+    }
+
+    /// Get the commit mode
+    #[inline]
+    pub fn commit_mode(&self) -> CommitMode {
+        self.commit_mode
+    }
+
+    /// Get the committed value
+    ///
+    /// This is only maintained when the [`CommitMode`] is not
+    /// [`CommitMode::Disabled`].
+    #[inline]
+    pub fn committed(&self) -> &str {
+        &self.committed
+    }
+
+    /// Get whether the contents differ from the committed value
+    ///
+    /// This is always false when the [`CommitMode`] is
+    /// [`CommitMode::Disabled`]. Forms may use this to warn about unsaved
+    /// changes.
+    pub fn is_dirty(&self) -> bool {
+        self.commit_mode != CommitMode::Disabled && self.text.text() != &self.committed
+    }
+
+    /// Commit the current contents
+    ///
+    /// This does not call [`EditGuard::commit`].
+    pub fn commit(&mut self) {
+        self.committed = self.text.clone_string();
+    }
+
+    /// Revert contents to the committed value
+    ///
+    /// This is equivalent to setting the committed value programmatically,
+    /// thus [`EditGuard::update`] is called on change.
+    pub fn revert(&mut self) -> TkAction {
+        let committed = self.committed.clone();
+        self.set_string(committed)
+    }
+
+    // Revert to the committed value (as an undoable edit)
+    fn revert_edit(&mut self) {
+        let pos = self.selection.edit_pos();
+        self.old_state = Some((self.text.clone_string(), pos, self.selection.sel_pos()));
+        self.last_edit = LastEdit::None;
+        self.text.set_string(self.committed.clone());
+        self.selection.set_pos(self.text.str_len());
+        self.edit_x_coord = None;
+    }
+
+    fn commit_with(&mut self, mgr: &mut EventMgr) {
+        self.commit();
+        G::commit(self, mgr);
+    }
+
     /// Get whether the widget currently has keyboard input focus
     #[inline]
     pub fn has_key_focus(&self) -> bool {
@@ -752,12 +1212,165 @@ impl<G: EditGuard> EditField<G> {
         self.error_state = error_state;
     }
 
+    /// Get the edit (cursor) position
+    ///
+    /// This is a byte index into the text.
+    #[inline]
+    pub fn edit_pos(&self) -> usize {
+        self.selection.edit_pos()
+    }
+
+    /// Set the edit (cursor) position
+    ///
+    /// This clears any selection and secondary cursors. The position `pos` is
+    /// a byte index into the text and is clamped to the text's length.
+    pub fn set_edit_pos(&mut self, pos: usize) -> TkAction {
+        self.selection.clear();
+        self.selection.set_pos(pos.min(self.text.str_len()));
+        self.edit_x_coord = None;
+        self.set_view_offset_from_edit_pos();
+        TkAction::REDRAW
+    }
+
+    /// Get the range of the word ending at the edit position
+    ///
+    /// A word consists of alphanumeric characters and `_`. The range is empty
+    /// if the edit position does not follow a word character. This may be used
+    /// to construct [`Completions`] for code entry.
+    pub fn word_before_cursor(&self) -> Range<usize> {
+        let pos = self.selection.edit_pos();
+        let text = &self.text.text()[..pos];
+        let start = text
+            .char_indices()
+            .rev()
+            .take_while(|(_, c)| c.is_alphanumeric() || *c == '_')
+            .last()
+            .map(|(i, _)| i)
+            .unwrap_or(pos);
+        start..pos
+    }
+
+    /// True if the completion list is open
+    #[inline]
+    pub fn has_completions(&self) -> bool {
+        self.completion_id.is_some()
+    }
+
+    fn update_completions(&mut self, mgr: &mut EventMgr) {
+        let mut completions = G::completions(self);
+        completions.candidates.truncate(MAX_COMPLETIONS);
+        if completions.candidates.is_empty() {
+            self.close_completions(mgr);
+            return;
+        }
+
+        self.completion_range = completions.range;
+        let popup = match self.completion.as_mut() {
+            Some(popup) => popup,
+            None => {
+                let mut popup = CompletionPopup::new();
+                let id = self.id_ref().make_child(0);
+                mgr.set_rect_mgr(|mgr| mgr.configure(id.clone(), &mut popup));
+                self.completion.insert(popup)
+            }
+        };
+        *mgr |= popup.set_candidates(completions.candidates);
+        if self.completion_id.is_none() {
+            let id = popup.id();
+            self.completion_id = mgr.add_popup_keep_focus(kas::Popup {
+                id,
+                parent: self.id(),
+                direction: Direction::Down,
+            });
+        }
+    }
+
+    fn close_completions(&mut self, mgr: &mut EventMgr) {
+        if let Some(id) = self.completion_id.take() {
+            mgr.close_window(id, false);
+        }
+    }
+
+    // Navigate the completion list. Returns None if the command is not used.
+    fn completion_key(&mut self, mgr: &mut EventMgr, cmd: Command) -> Option<Response<G::Msg>> {
+        self.completion_id?;
+        let popup = self.completion.as_mut()?;
+        let len = popup.len();
+        let current = popup.highlighted();
+        match cmd {
+            Command::Down => {
+                let index = current.map(|i| (i + 1) % len).unwrap_or(0);
+                *mgr |= popup.highlight(index);
+                Some(Response::Used)
+            }
+            Command::Up => {
+                let index = current.map(|i| (i + len - 1) % len).unwrap_or(len - 1);
+                *mgr |= popup.highlight(index);
+                Some(Response::Used)
+            }
+            Command::Tab => Some(self.accept_completion(mgr, current.unwrap_or(0))),
+            Command::Return => current.map(|index| self.accept_completion(mgr, index)),
+            Command::Escape => {
+                self.close_completions(mgr);
+                Some(Response::Used)
+            }
+            _ => None,
+        }
+    }
+
+    fn completion_msg(
+        &mut self,
+        mgr: &mut EventMgr,
+        msg: ChildMsg<usize, VoidMsg>,
+    ) -> Response<G::Msg> {
+        match msg {
+            ChildMsg::Select(index) => self.accept_completion(mgr, index),
+            _ => Response::Used,
+        }
+    }
+
+    fn accept_completion(&mut self, mgr: &mut EventMgr, index: usize) -> Response<G::Msg> {
+        self.close_completions(mgr);
+        let replacement = match self.completion.as_ref().and_then(|p| p.get(index)) {
+            Some(s) => s.clone(),
+            None => return Response::Used,
+        };
+
+        let len = self.text.str_len();
+        let range = self.completion_range.start.min(len)..self.completion_range.end.min(len);
+        let (pos, sel_pos) = (self.selection.edit_pos(), self.selection.sel_pos());
+        self.old_state = Some((self.text.clone_string(), pos, sel_pos));
+        self.last_edit = LastEdit::None;
+        let _ = self.text.replace_range(range.clone(), &replacement);
+        self.selection.clear();
+        self.selection.set_pos(range.start + replacement.len());
+        self.edit_x_coord = None;
+        if let Some(req) = self.text.prepare() {
+            self.required = req.into();
+        }
+        self.set_view_offset_from_edit_pos();
+        self.spell_checked = None;
+        mgr.redraw(self.id());
+        Response::update_or_msg(G::edit(self, mgr))
+    }
+
     // returns true on success, false on unhandled event
     fn received_char(&mut self, mgr: &mut EventMgr, c: char) -> bool {
         if !self.editable {
             return false;
         }
 
+        if self.selection.has_extra() {
+            let mut buf = [0u8; 4];
+            let s = c.encode_utf8(&mut buf);
+            self.edit_cursors(LastEdit::Insert, s, |_, pos, sel| match sel.is_empty() {
+                true => Some(pos..pos),
+                false => Some(sel),
+            });
+            mgr.redraw(self.id());
+            return true;
+        }
+
         let pos = self.selection.edit_pos();
         let selection = self.selection.range();
         let have_sel = selection.start < selection.end;
@@ -788,6 +1401,19 @@ impl<G: EditGuard> EditField<G> {
             return EditAction::Unused;
         }
 
+        if key == Command::SelectNext {
+            self.select_next();
+            mgr.redraw(self.id());
+            return EditAction::None;
+        }
+        if self.selection.has_extra() {
+            if let Some(action) = self.control_key_multi(mgr, key, shift) {
+                return action;
+            }
+            self.selection.clear_extra();
+            mgr.redraw(self.id());
+        }
+
         let mut buf = [0u8; 4];
         let pos = self.selection.edit_pos();
         let selection = self.selection.range();
@@ -810,6 +1436,10 @@ impl<G: EditGuard> EditField<G> {
                 mgr.redraw(self.id());
                 Action::None
             }
+            Command::Escape if self.is_dirty() => {
+                self.revert_edit();
+                Action::Edit
+            }
             Command::Return if shift || !self.multi_line => Action::Activate,
             Command::Return if self.multi_line => {
                 Action::Insert('\n'.encode_utf8(&mut buf), LastEdit::Insert)
@@ -817,59 +1447,10 @@ impl<G: EditGuard> EditField<G> {
             // NOTE: we might choose to optionally handle Tab in the future,
             // but without some workaround it prevents keyboard navigation.
             // Command::Tab => Action::Insert('\t'.encode_utf8(&mut buf), LastEdit::Insert),
-            Command::Left => {
-                let mut cursor = GraphemeCursor::new(pos, self.text.str_len(), true);
-                cursor
-                    .prev_boundary(self.text.text(), 0)
-                    .unwrap()
-                    .map(|pos| Action::Move(pos, None))
-                    .unwrap_or(Action::None)
-            }
-            Command::Right => {
-                let mut cursor = GraphemeCursor::new(pos, self.text.str_len(), true);
-                cursor
-                    .next_boundary(self.text.text(), 0)
-                    .unwrap()
-                    .map(|pos| Action::Move(pos, None))
-                    .unwrap_or(Action::None)
-            }
-            Command::WordLeft => {
-                let mut iter = self.text.text()[0..pos].split_word_bound_indices();
-                let mut p = iter.next_back().map(|(index, _)| index).unwrap_or(0);
-                while self.text.text()[p..]
-                    .chars()
-                    .next()
-                    .map(|c| c.is_whitespace())
-                    .unwrap_or(false)
-                {
-                    if let Some((index, _)) = iter.next_back() {
-                        p = index;
-                    } else {
-                        break;
-                    }
-                }
-                Action::Move(p, None)
-            }
-            Command::WordRight => {
-                let mut iter = self.text.text()[pos..].split_word_bound_indices().skip(1);
-                let mut p = iter
-                    .next()
-                    .map(|(index, _)| pos + index)
-                    .unwrap_or(self.text.str_len());
-                while self.text.text()[p..]
-                    .chars()
-                    .next()
-                    .map(|c| c.is_whitespace())
-                    .unwrap_or(false)
-                {
-                    if let Some((index, _)) = iter.next() {
-                        p = pos + index;
-                    } else {
-                        break;
-                    }
-                }
-                Action::Move(p, None)
-            }
+            Command::Left | Command::Right | Command::WordLeft | Command::WordRight => self
+                .move_pos(key, pos)
+                .map(|pos| Action::Move(pos, None))
+                .unwrap_or(Action::None),
             Command::Up | Command::Down => {
                 let x = match self.edit_x_coord {
                     Some(x) => x,
@@ -897,18 +1478,10 @@ impl<G: EditGuard> EditField<G> {
                     .map(|pos| Action::Move(pos, Some(x)))
                     .unwrap_or(Action::Move(nearest_end(), None))
             }
-            Command::Home => {
-                let pos = self.text.find_line(pos).map(|r| r.1.start).unwrap_or(0);
-                Action::Move(pos, None)
-            }
-            Command::End => {
-                let pos = self
-                    .text
-                    .find_line(pos)
-                    .map(|r| r.1.end)
-                    .unwrap_or(self.text.str_len());
-                Action::Move(pos, None)
-            }
+            Command::Home | Command::End => self
+                .move_pos(key, pos)
+                .map(|pos| Action::Move(pos, None))
+                .unwrap_or(Action::None),
             Command::DocHome => Action::Move(0, None),
             Command::DocEnd => Action::Move(self.text.str_len(), None),
             Command::PageUp | Command::PageDown => {
@@ -930,40 +1503,10 @@ impl<G: EditGuard> EditField<G> {
                 Action::Move(self.text.text_index_nearest(v.into()), Some(v.0))
             }
             Command::Delete | Command::DelBack if have_sel => Action::Delete(selection.clone()),
-            Command::Delete => {
-                let mut cursor = GraphemeCursor::new(pos, self.text.str_len(), true);
-                cursor
-                    .next_boundary(self.text.text(), 0)
-                    .unwrap()
-                    .map(|next| Action::Delete(pos..next))
-                    .unwrap_or(Action::None)
-            }
-            Command::DelBack => {
-                // We always delete one code-point, not one grapheme cluster:
-                let prev = self.text.text()[0..pos]
-                    .char_indices()
-                    .rev()
-                    .next()
-                    .map(|(i, _)| i)
-                    .unwrap_or(0);
-                Action::Delete(prev..pos)
-            }
-            Command::DelWord => {
-                let next = self.text.text()[pos..]
-                    .split_word_bound_indices()
-                    .nth(1)
-                    .map(|(index, _)| pos + index)
-                    .unwrap_or(self.text.str_len());
-                Action::Delete(pos..next)
-            }
-            Command::DelWordBack => {
-                let prev = self.text.text()[0..pos]
-                    .split_word_bound_indices()
-                    .next_back()
-                    .map(|(index, _)| index)
-                    .unwrap_or(0);
-                Action::Delete(prev..pos)
-            }
+            Command::Delete | Command::DelBack | Command::DelWord | Command::DelWordBack => self
+                .delete_range(key, pos)
+                .map(Action::Delete)
+                .unwrap_or(Action::None),
             Command::SelectAll => {
                 self.selection.set_sel_pos(0);
                 shift = true; // hack
@@ -1080,8 +1623,306 @@ impl<G: EditGuard> EditField<G> {
         result
     }
 
+    // Handle a command with multiple cursors; None if unsupported
+    fn control_key_multi(
+        &mut self,
+        mgr: &mut EventMgr,
+        key: Command,
+        shift: bool,
+    ) -> Option<EditAction> {
+        let string;
+        let (edit, s) = match key {
+            Command::Escape | Command::Deselect => {
+                self.selection.clear_extra();
+                self.selection.set_empty();
+                mgr.redraw(self.id());
+                return Some(EditAction::None);
+            }
+            Command::Left
+            | Command::Right
+            | Command::WordLeft
+            | Command::WordRight
+            | Command::Home
+            | Command::End => {
+                let cursors: Vec<_> = self
+                    .selection
+                    .cursors()
+                    .map(|(edit, sel)| {
+                        let edit = self.move_pos(key, edit).unwrap_or(edit);
+                        (edit, if shift { sel } else { edit })
+                    })
+                    .collect();
+                self.selection.set_cursors(cursors);
+                self.edit_x_coord = None;
+                self.set_view_offset_from_edit_pos();
+                mgr.redraw(self.id());
+                return Some(EditAction::None);
+            }
+            Command::Delete | Command::DelBack | Command::DelWord | Command::DelWordBack => {
+                (LastEdit::Delete, "")
+            }
+            Command::Return if self.multi_line && !shift => (LastEdit::Insert, "\n"),
+            Command::Copy | Command::Cut => {
+                let text = self.text.text();
+                let parts: Vec<&str> = self
+                    .selection
+                    .ranges()
+                    .into_iter()
+                    .map(|r| &text[r])
+                    .collect();
+                if parts.is_empty() {
+                    return Some(EditAction::None);
+                }
+                mgr.set_clipboard(parts.join("\n"));
+                if key == Command::Copy {
+                    return Some(EditAction::None);
+                }
+                (LastEdit::Delete, "")
+            }
+            Command::Paste => match mgr.get_clipboard() {
+                Some(content) => {
+                    string = content;
+                    let mut end = string.len();
+                    if !self.multi_line {
+                        if let Some((i, _)) = string.char_indices().find(|(_, c)| c.is_control()) {
+                            end = i;
+                        }
+                    }
+                    (LastEdit::Paste, &string[0..end])
+                }
+                None => return Some(EditAction::None),
+            },
+            _ => return None,
+        };
+
+        self.edit_cursors(edit, s, |w, pos, sel| match sel.is_empty() {
+            true if s.is_empty() => w.delete_range(key, pos),
+            true => Some(pos..pos),
+            false => Some(sel),
+        });
+        mgr.redraw(self.id());
+        Some(EditAction::Edit)
+    }
+
+    // Replace a range at each cursor with `s`
+    //
+    // Closure `f` is passed the edit pos and selection range of each cursor
+    // and returns the range to replace, if any. Overlapping ranges are skipped.
+    fn edit_cursors<F>(&mut self, edit: LastEdit, s: &str, f: F)
+    where
+        F: Fn(&Self, usize, Range<usize>) -> Option<Range<usize>>,
+    {
+        let mut edits: Vec<(Range<usize>, bool)> = self
+            .selection
+            .cursors()
+            .enumerate()
+            .map(|(i, (pos, sel_pos))| {
+                let sel = pos.min(sel_pos)..pos.max(sel_pos);
+                (f(self, pos, sel.clone()).unwrap_or(pos..pos), i == 0)
+            })
+            .collect();
+        edits.sort_by_key(|(range, _)| range.start);
+
+        if self.last_edit != edit || edits.iter().any(|(r, _)| !r.is_empty()) {
+            let (pos, sel_pos) = (self.selection.edit_pos(), self.selection.sel_pos());
+            self.old_state = Some((self.text.clone_string(), pos, sel_pos));
+            self.last_edit = edit;
+        }
+
+        let mut cursors = Vec::with_capacity(edits.len());
+        let mut primary = None;
+        let (mut added, mut removed) = (0, 0);
+        let mut last_end = 0;
+        for (range, is_primary) in edits {
+            if range.start < last_end {
+                continue;
+            }
+            last_end = range.end;
+            let start = range.start + added - removed;
+            let end = range.end + added - removed;
+            self.text.replace_range(start..end, s);
+            added += s.len();
+            removed += range.len();
+            if is_primary {
+                primary = Some(cursors.len());
+            }
+            let pos = start + s.len();
+            cursors.push((pos, pos));
+        }
+
+        if let Some(i) = primary {
+            cursors.swap(0, i);
+        }
+        self.selection.set_cursors(cursors);
+        self.edit_x_coord = None;
+        if let Some(req) = self.text.prepare() {
+            self.required = req.into();
+        }
+        self.set_view_offset_from_edit_pos();
+    }
+
+    // Add a cursor selecting the next occurrence of the selected text
+    //
+    // If the primary selection is empty, the word at the cursor is selected.
+    fn select_next(&mut self) {
+        if self.selection.is_empty() {
+            self.selection.set_anchor();
+            self.selection.expand(&self.text, 1);
+            return;
+        }
+
+        let text = self.text.text();
+        let range = self.selection.range();
+        let needle = &text[range.clone()];
+        let found = text[range.end..]
+            .find(needle)
+            .map(|i| range.end + i)
+            .or_else(|| text[..range.start].find(needle));
+        if let Some(start) = found {
+            let end = start + needle.len();
+            if !self.selection.ranges().contains(&(start..end)) {
+                self.selection.add_cursor(end, start);
+                self.edit_x_coord = None;
+                self.set_view_offset_from_edit_pos();
+            }
+        }
+    }
+
+    // Find the new edit position after a movement command
+    //
+    // Supports horizontal movement commands only.
+    fn move_pos(&self, key: Command, pos: usize) -> Option<usize> {
+        match key {
+            Command::Left => {
+                let mut cursor = GraphemeCursor::new(pos, self.text.str_len(), true);
+                cursor.prev_boundary(self.text.text(), 0).unwrap()
+            }
+            Command::Right => {
+                let mut cursor = GraphemeCursor::new(pos, self.text.str_len(), true);
+                cursor.next_boundary(self.text.text(), 0).unwrap()
+            }
+            Command::WordLeft => {
+                let mut iter = self.text.text()[0..pos].split_word_bound_indices();
+                let mut p = iter.next_back().map(|(index, _)| index).unwrap_or(0);
+                while self.text.text()[p..]
+                    .chars()
+                    .next()
+                    .map(|c| c.is_whitespace())
+                    .unwrap_or(false)
+                {
+                    if let Some((index, _)) = iter.next_back() {
+                        p = index;
+                    } else {
+                        break;
+                    }
+                }
+                Some(p)
+            }
+            Command::WordRight => {
+                let mut iter = self.text.text()[pos..].split_word_bound_indices().skip(1);
+                let mut p = iter
+                    .next()
+                    .map(|(index, _)| pos + index)
+                    .unwrap_or(self.text.str_len());
+                while self.text.text()[p..]
+                    .chars()
+                    .next()
+                    .map(|c| c.is_whitespace())
+                    .unwrap_or(false)
+                {
+                    if let Some((index, _)) = iter.next() {
+                        p = pos + index;
+                    } else {
+                        break;
+                    }
+                }
+                Some(p)
+            }
+            Command::Home => Some(self.text.find_line(pos).map(|r| r.1.start).unwrap_or(0)),
+            Command::End => Some(
+                self.text
+                    .find_line(pos)
+                    .map(|r| r.1.end)
+                    .unwrap_or(self.text.str_len()),
+            ),
+            _ => None,
+        }
+    }
+
+    // Find the range to delete for a deletion command, without selection
+    fn delete_range(&self, key: Command, pos: usize) -> Option<Range<usize>> {
+        match key {
+            Command::Delete => {
+                let mut cursor = GraphemeCursor::new(pos, self.text.str_len(), true);
+                cursor
+                    .next_boundary(self.text.text(), 0)
+                    .unwrap()
+                    .map(|next| pos..next)
+            }
+            Command::DelBack => {
+                // We always delete one code-point, not one grapheme cluster:
+                let prev = self.text.text()[0..pos]
+                    .char_indices()
+                    .rev()
+                    .next()
+                    .map(|(i, _)| i)
+                    .unwrap_or(0);
+                Some(prev..pos)
+            }
+            Command::DelWord => {
+                let next = self.text.text()[pos..]
+                    .split_word_bound_indices()
+                    .nth(1)
+                    .map(|(index, _)| pos + index)
+                    .unwrap_or(self.text.str_len());
+                Some(pos..next)
+            }
+            Command::DelWordBack => {
+                let prev = self.text.text()[0..pos]
+                    .split_word_bound_indices()
+                    .next_back()
+                    .map(|(index, _)| index)
+                    .unwrap_or(0);
+                Some(prev..pos)
+            }
+            _ => None,
+        }
+    }
+
+    // Set a rectangular selection from `anchor` (text coordinates) to `coord`
+    fn set_rect_selection(&mut self, mgr: &mut EventMgr, anchor: Vec2, coord: Coord) {
+        let target = self.text_coord(coord);
+        let line_of = |v: Vec2| {
+            let index = self.text.text_index_nearest(v.into());
+            self.text.find_line(index).map(|r| r.0).unwrap_or(0)
+        };
+        let (l0, l1) = (line_of(anchor), line_of(target));
+        // The primary cursor is on the line under the mouse (l1):
+        let lines: Vec<usize> = match l0 <= l1 {
+            true => (l0..=l1).rev().collect(),
+            false => (l1..=l0).collect(),
+        };
+        let cursors: Vec<_> = lines
+            .into_iter()
+            .filter_map(|line| {
+                let sel = self.text.line_index_nearest(line, anchor.0)?;
+                let edit = self.text.line_index_nearest(line, target.0)?;
+                Some((edit, sel))
+            })
+            .collect();
+        self.selection.set_cursors(cursors);
+        self.edit_x_coord = None;
+        self.set_view_offset_from_edit_pos();
+        mgr.redraw(self.id());
+    }
+
+    // Translate a coordinate to the text's coordinate space
+    fn text_coord(&self, coord: Coord) -> Vec2 {
+        (coord - self.rect().pos + self.view_offset).into()
+    }
+
     fn set_edit_pos_from_coord(&mut self, mgr: &mut EventMgr, coord: Coord) {
-        let rel_pos = (coord - self.rect().pos + self.view_offset).into();
+        let rel_pos = self.text_coord(coord).into();
         self.selection
             .set_edit_pos(self.text.text_index_nearest(rel_pos));
         self.set_view_offset_from_edit_pos();
@@ -1104,10 +1945,52 @@ impl<G: EditGuard> EditField<G> {
         }
     }
 
+    // Check spelling of the visible text, if required
+    fn update_spelling(&mut self) {
+        let checker = match self.spell_checker.as_ref() {
+            Some(checker) => checker,
+            None => return,
+        };
+
+        let p1 = Vec2::from(self.view_offset);
+        let p2 = p1 + Vec2::from(self.rect().size);
+        let start = self.text.text_index_nearest(p1.into());
+        let end = self.text.text_index_nearest(p2.into());
+        let start = self.text.find_line(start).map(|r| r.1.start).unwrap_or(0);
+        let end = self
+            .text
+            .find_line(end)
+            .map(|r| r.1.end)
+            .unwrap_or(self.text.str_len());
+        let range = start..end;
+
+        if self.spell_checked.as_ref() != Some(&range) {
+            self.misspelled = checker.check(self.text.text(), range.clone());
+            self.spell_checked = Some(range);
+        }
+    }
+
+    /// Prepare composition feedback text (see [`EventState::composition`])
+    fn preedit_text(&self, preedit: String) -> (Text<String>, Size) {
+        let mut env = self.text.env().clone();
+        env.set_bounds(kas::text::Vec2::INFINITY);
+        env.set_wrap(false);
+        let mut text = Text::new(env, preedit);
+        let size = match text.prepare() {
+            Some(size) => Vec2::from(size).ceil().into(),
+            None => Size::ZERO,
+        };
+        (text, size)
+    }
+
     /// Update view_offset after edit_pos changes
     ///
     /// A redraw is assumed since edit_pos moved.
     fn set_view_offset_from_edit_pos(&mut self) {
+        if !self.text.required_action().is_ready() {
+            // Glyph positions are not yet available
+            return;
+        }
         let edit_pos = self.selection.edit_pos();
         if let Some(marker) = self.text.text_glyph_pos(edit_pos).next_back() {
             let bounds = Vec2::from(self.text.env().bounds);
@@ -1124,3 +2007,97 @@ impl<G: EditGuard> EditField<G> {
         }
     }
 }
+
+type CompletionList = ListView<Down, Vec<String>>;
+
+widget! {
+    /// The pop-up list of completions of an [`EditField`]
+    #[derive(Clone, Debug)]
+    #[handler(msg = ChildMsg<usize, VoidMsg>)]
+    #[widget{
+        layout = single;
+    }]
+    struct CompletionPopup {
+        #[widget_core]
+        core: CoreData,
+        #[widget]
+        list: CompletionList,
+    }
+
+    impl Self {
+        fn new() -> Self {
+            let list = CompletionList::new(vec![]).with_selection_mode(SelectionMode::Single);
+            CompletionPopup {
+                core: Default::default(),
+                list,
+            }
+        }
+
+        fn len(&self) -> usize {
+            self.list.data().len()
+        }
+
+        fn get(&self, index: usize) -> Option<&String> {
+            self.list.data().get(index)
+        }
+
+        fn set_candidates(&mut self, candidates: Vec<String>) -> TkAction {
+            let num = candidates.len().max(1);
+            *self.list.data_mut() = candidates;
+            let action = self.list.clear_selected() | self.list.set_num_visible(num.cast());
+            action | self.list.invalidate_view()
+        }
+
+        fn highlighted(&self) -> Option<usize> {
+            self.list.selected_iter().next().cloned()
+        }
+
+        fn highlight(&mut self, index: usize) -> TkAction {
+            self.list.clear_selected() | self.list.select(index).unwrap_or_default()
+        }
+    }
+
+    impl WidgetConfig for Self {
+        fn configure(&mut self, mgr: &mut SetRectMgr) {
+            mgr.remove_nav_fallback(self.id_ref());
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn commit_and_revert() {
+        let mut edit = EditField::new("a").with_commit_mode(CommitMode::Activate);
+        assert_eq!(edit.committed(), "a");
+        assert!(!edit.is_dirty());
+
+        // User input (as by EditField::received_char)
+        edit.text.set_string("ab".to_string());
+        assert!(edit.is_dirty());
+
+        // Escape reverts to the committed value
+        edit.revert_edit();
+        assert_eq!(edit.get_str(), "a");
+        assert_eq!(edit.edit_pos(), 1);
+        assert!(!edit.is_dirty());
+
+        edit.text.set_string("abc".to_string());
+        edit.commit();
+        assert_eq!(edit.committed(), "abc");
+        assert!(!edit.is_dirty());
+
+        // Setting the current contents programmatically also commits them
+        edit.text.set_string("abcd".to_string());
+        let _ = edit.set_string("abcd".to_string());
+        assert_eq!(edit.committed(), "abcd");
+        assert!(!edit.is_dirty());
+
+        // Without a commit mode, contents are never dirty
+        let mut edit = EditField::new("a");
+        edit.text.set_string("ab".to_string());
+        assert!(!edit.is_dirty());
+    }
+}
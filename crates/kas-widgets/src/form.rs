@@ -0,0 +1,241 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! A form widget
+
+use kas::layout::{DynGridStorage, GridChildInfo, GridDimensions, TextStorage};
+use kas::text::util::set_text_and_prepare;
+use kas::theme::TextClass;
+use kas::{event, layout, prelude::*};
+
+/// A row of a [`Form`]
+#[derive(Clone, Debug)]
+struct FormEntry<W> {
+    label: Text<AccelString>,
+    label_store: TextStorage,
+    widget: W,
+}
+
+/// A form of boxed widgets
+///
+/// This is a parameterisation of [`Form`] over the handler message type.
+pub type BoxForm<M> = Form<Box<dyn Widget<Msg = M>>>;
+
+widget! {
+    /// A form: a column of labelled widgets
+    ///
+    /// Each row consists of a label and a widget. Labels are placed in the
+    /// first column and right-aligned, thus all widgets are aligned on the
+    /// left, regardless of label lengths. Layout uses the grid solver.
+    ///
+    /// Labels support accelerator keys, which activate the row's widget.
+    /// Mouse/touch input on a label is sent to the row's widget.
+    ///
+    /// For a single labelled row, see [`crate::adapter::FormRow`].
+    #[autoimpl(Default)]
+    #[derive(Clone, Debug)]
+    #[handler(msg=<W as Handler>::Msg)]
+    pub struct Form<W: Widget> {
+        #[widget_core]
+        core: CoreData,
+        rows: Vec<FormEntry<W>>,
+        data: DynGridStorage,
+    }
+
+    impl WidgetChildren for Self {
+        #[inline]
+        fn num_children(&self) -> usize {
+            self.rows.len()
+        }
+        #[inline]
+        fn get_child(&self, index: usize) -> Option<&dyn WidgetConfig> {
+            self.rows.get(index).map(|r| r.widget.as_widget())
+        }
+        #[inline]
+        fn get_child_mut(&mut self, index: usize) -> Option<&mut dyn WidgetConfig> {
+            self.rows.get_mut(index).map(|r| r.widget.as_widget_mut())
+        }
+    }
+
+    impl WidgetConfig for Self {
+        fn configure(&mut self, mgr: &mut SetRectMgr) {
+            for row in &self.rows {
+                mgr.add_accel_string(row.widget.id_ref(), row.label.text());
+            }
+        }
+    }
+
+    impl Layout for Self {
+        fn layout(&mut self) -> layout::Layout<'_> {
+            let dim = GridDimensions {
+                rows: self.rows.len().cast(),
+                cols: 2,
+                row_spans: 0,
+                col_spans: 0,
+            };
+            let align = AlignHints::new(Some(Align::BR), None);
+            let iter = self.rows.iter_mut().enumerate().flat_map(move |(i, row)| {
+                let i = u32::conv(i);
+                let label =
+                    layout::Layout::text(&mut row.label_store, &mut row.label, TextClass::Label);
+                [
+                    (GridChildInfo::new(i, 0), layout::Layout::align(label, align)),
+                    (GridChildInfo::new(i, 1), layout::Layout::single(&mut row.widget)),
+                ]
+            });
+            layout::Layout::grid(iter, dim, &mut self.data)
+        }
+
+        fn find_id(&mut self, coord: Coord) -> Option<WidgetId> {
+            if !self.rect().contains(coord) {
+                return None;
+            }
+            for row in &mut self.rows {
+                if let Some(id) = row.widget.find_id(coord) {
+                    return Some(id);
+                }
+                // Input on the label goes to the widget
+                let rect = row.widget.rect();
+                if rect.pos.1 <= coord.1 && coord.1 < rect.pos.1 + rect.size.1 {
+                    return Some(row.widget.id());
+                }
+            }
+            Some(self.id())
+        }
+    }
+
+    impl event::SendEvent for Self {
+        fn send(&mut self, mgr: &mut EventMgr, id: WidgetId, event: Event) -> Response<Self::Msg> {
+            if !self.is_disabled() {
+                if let Some(index) = self.id().index_of_child(&id) {
+                    if let Some(row) = self.rows.get_mut(index) {
+                        let r = row.widget.send(mgr, id.clone(), event);
+                        return match Response::try_from(r) {
+                            Ok(r) => r,
+                            Err(msg) => {
+                                log::trace!(
+                                    "Received by {} from {}: {:?}",
+                                    self.id(),
+                                    id,
+                                    kas::util::TryFormat(&msg)
+                                );
+                                Response::Msg(msg)
+                            }
+                        };
+                    }
+                }
+            }
+
+            Response::Unused
+        }
+    }
+}
+
+impl<W: Widget> Form<W> {
+    /// Construct a new, empty instance
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a row (inline)
+    #[inline]
+    #[must_use]
+    pub fn with_row<T: Into<AccelString>>(mut self, label: T, widget: W) -> Self {
+        let _ = self.push(label, widget);
+        self
+    }
+
+    /// True if there are no rows
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Returns the number of rows
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Remove all rows
+    ///
+    /// Triggers a [reconfigure action](EventState::send_action).
+    pub fn clear(&mut self) -> TkAction {
+        let action = match self.rows.is_empty() {
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
+        };
+        self.rows.clear();
+        action
+    }
+
+    /// Append a row with the given `label` and `widget`
+    ///
+    /// Triggers a [reconfigure action](EventState::send_action).
+    pub fn push<T: Into<AccelString>>(&mut self, label: T, widget: W) -> TkAction {
+        self.rows.push(FormEntry {
+            label: Text::new_multi(label.into()),
+            label_store: Default::default(),
+            widget,
+        });
+        TkAction::RECONFIGURE
+    }
+
+    /// Remove the last row and return its widget, if any
+    ///
+    /// Triggers a [reconfigure action](EventState::send_action) if any row is
+    /// removed.
+    pub fn pop(&mut self) -> (Option<W>, TkAction) {
+        let action = match self.rows.is_empty() {
+            true => TkAction::empty(),
+            false => TkAction::RECONFIGURE,
+        };
+        (self.rows.pop().map(|r| r.widget), action)
+    }
+
+    /// Get the widget of a row
+    #[inline]
+    pub fn get(&self, index: usize) -> Option<&W> {
+        self.rows.get(index).map(|r| &r.widget)
+    }
+
+    /// Get the widget of a row, mutably
+    #[inline]
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut W> {
+        self.rows.get_mut(index).map(|r| &mut r.widget)
+    }
+
+    /// Get the label of a row
+    #[inline]
+    pub fn label(&self, index: usize) -> Option<&str> {
+        self.rows.get(index).map(|r| r.label.as_str())
+    }
+
+    /// Set the label of a row
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn set_label<T: Into<AccelString>>(&mut self, index: usize, label: T) -> TkAction {
+        let row = &mut self.rows[index];
+        let label = label.into();
+        let mut action = TkAction::empty();
+        if row.label.text().keys() != label.keys() {
+            action |= TkAction::RECONFIGURE;
+        }
+        action | set_text_and_prepare(&mut row.label, label, self.core.rect.size)
+    }
+
+    /// Iterate over row widgets
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = &W> {
+        self.rows.iter().map(|r| &r.widget)
+    }
+
+    /// Mutably iterate over row widgets
+    #[inline]
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = &mut W> {
+        self.rows.iter_mut().map(|r| &mut r.widget)
+    }
+}
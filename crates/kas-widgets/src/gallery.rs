@@ -0,0 +1,232 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Widget gallery
+
+use crate::adapter::WidgetExt;
+use crate::view::{FilterListView, ListView, MatrixView, SingleView};
+use crate::*;
+use kas::dir::{Down, Right};
+use kas::prelude::*;
+use kas::theme::StandardIcon;
+use kas::updatable::filter::ContainsString;
+use kas::updatable::{SharedRc, Table};
+
+/// Column titles and forced draw states
+///
+/// Focus includes `CHAR_FOCUS` so that edit fields draw their cursor.
+const STATES: [(&str, InputState); 4] = [
+    ("Normal", InputState::empty()),
+    ("Disabled", InputState::DISABLED),
+    ("Hover", InputState::HOVER),
+    ("Focus", InputState::NAV_FOCUS.union(InputState::CHAR_FOCUS)),
+];
+
+type Cell = Box<dyn Widget<Msg = VoidMsg>>;
+
+fn cell<W: Widget + 'static>(widget: W, state: InputState) -> Cell {
+    Box::new(Inert::new(widget.map_msg_discard::<VoidMsg>()).with_state(state))
+}
+
+fn build() -> BoxGrid<VoidMsg> {
+    Grid::build(|mut b| {
+        for (col, (title, _)) in STATES.iter().enumerate() {
+            b.push_cell(0, u32::conv(col) + 1, Box::new(Label::new(*title)));
+        }
+
+        let mut row = 0;
+        let mut push_row = |name: &'static str, f: &dyn Fn(InputState) -> Cell| {
+            row += 1;
+            b.push_cell(row, 0, Box::new(Label::new(name)));
+            for (col, (_, state)) in STATES.iter().enumerate() {
+                b.push_cell(row, u32::conv(col) + 1, f(*state));
+            }
+        };
+
+        push_row("Label", &|s| cell(Label::new("Text"), s));
+        push_row("TextButton", &|s| cell(TextButton::new("&Press"), s));
+        push_row("CheckBox", &|s| {
+            cell(CheckBox::new("&Check").with_state(true), s)
+        });
+        push_row("RadioBox", &|s| {
+            let group = RadioBoxGroup::new();
+            cell(RadioBox::new("&Radio", group, 0).with_state(true), s)
+        });
+        push_row("Switch", &|s| cell(Switch::new().with_state(true), s));
+        push_row("EditBox", &|s| cell(EditBox::new("Edit me"), s));
+        push_row("MaskedEdit", &|s| cell(MaskedEdit::new(Mask::ipv4()), s));
+        push_row("KeyCapture", &|s| cell(KeyCapture::new(None), s));
+        push_row("Slider", &|s| {
+            cell(Slider::<i32, Right>::new(0, 10, 1).with_value(4), s)
+        });
+        push_row("ScrollBar", &|s| {
+            let bar = ScrollBar::<Right>::new()
+                .with_limits(100, 20)
+                .with_value(40);
+            cell(bar, s)
+        });
+        push_row("ProgressBar", &|s| {
+            cell(ProgressBar::<Right>::new().with_value(0.6), s)
+        });
+        push_row("ComboBox", &|s| cell(ComboBox::new(&["One", "Two"], 0), s));
+        push_row("MenuEntry", &|s| cell(MenuEntry::new("&Entry", ()), s));
+        push_row("MenuToggle", &|s| {
+            cell(MenuToggle::new("&Toggle").with_state(true), s)
+        });
+        push_row("Paginator", &|s| cell(Paginator::new(5), s));
+        push_row("Separator", &|s| cell(Separator::new(), s));
+        push_row("Separator (labelled)", &|s| {
+            let sep = Separator::new().with_label("Advanced");
+            cell(sep.with_label_align(Align::Center), s)
+        });
+        push_row("Button", &|s| cell(Button::new(Label::new("Custom")), s));
+        push_row("CheckBoxBare", &|s| {
+            cell(CheckBoxBare::new().with_state(true), s)
+        });
+        push_row("RadioBoxBare", &|s| {
+            let group = RadioBoxGroup::new();
+            cell(RadioBoxBare::new(group, 0).with_state(true), s)
+        });
+        push_row("AccelLabel", &|s| cell(AccelLabel::new("&Accel".into()), s));
+        push_row("ScrollLabel", &|s| cell(ScrollLabel::new("Selectable"), s));
+        push_row("LogView", &|s| {
+            let mut log = LogView::new(4);
+            let _ = log.push_line("First line");
+            let _ = log.push_line("Second line");
+            cell(log, s)
+        });
+        push_row("Filler", &|s| cell(Filler::new(), s));
+        push_row("Icon", &|s| cell(Icon::named(StandardIcon::Information), s));
+        push_row("DragHandle", &|s| cell(DragHandle::new(), s));
+        push_row("TitleBar", &|s| cell(TitleBar::new("Title"), s));
+        push_row("ResizeGrip", &|s| cell(ResizeGrip::new(), s));
+        push_row("ToolBar", &|s| {
+            let bar = ToolBar::new(vec![
+                ToolItem::button("open", "&Open", ()),
+                ToolItem::toggle("bold", "&Bold", |_, _| None).with_state(true),
+            ]);
+            cell(bar, s)
+        });
+        push_row("MenuBar", &|s| {
+            let menu = SubMenu::new("&File", vec![MenuEntry::new("&Open", ())]);
+            cell(MenuBar::<_>::new(vec![menu]), s)
+        });
+        push_row("RecentFilesMenu", &|s| {
+            let files = RecentFiles::new(4);
+            cell(RecentFilesMenu::new("&Recent", files, |_| ()), s)
+        });
+        push_row("ContextMenu", &|s| {
+            let button = TextButton::new_msg("&Context", ());
+            let menu = ContextMenu::new(button, vec![MenuEntry::new("&Copy", ())]);
+            cell(menu, s)
+        });
+        push_row("Frame", &|s| cell(Frame::new(Label::new("Framed")), s));
+        push_row("NavFrame", &|s| {
+            cell(NavFrame::new(Label::new("Navigable")), s)
+        });
+        push_row("Row", &|s| {
+            let a = TextButton::new_msg("&A", ());
+            cell(Row::new(vec![a, TextButton::new_msg("&B", ())]), s)
+        });
+        push_row("Grid", &|s| {
+            let grid = Grid::build(|mut b| {
+                b.push_cell(0, 0, Label::new("1"));
+                b.push_cell(0, 1, Label::new("2"));
+                b.push_cell(1, 0, Label::new("3"));
+                b.push_cell(1, 1, Label::new("4"));
+            });
+            cell(grid, s)
+        });
+        push_row("Stack", &|s| {
+            let pages = vec![Label::new("Page 1"), Label::new("Page 2")];
+            cell(Stack::new(pages, 0), s)
+        });
+        push_row("Splitter", &|s| {
+            let panes = vec![Label::new("Left"), Label::new("Right")];
+            cell(RowSplitter::new(panes), s)
+        });
+        push_row("Form", &|s| {
+            cell(Form::new().with_row("&Name", EditBox::new("Value")), s)
+        });
+        push_row("Wizard", &|s| {
+            let pages = vec![Label::new("Step 1"), Label::new("Step 2")];
+            cell(Wizard::new(pages), s)
+        });
+        push_row("ScrollRegion", &|s| {
+            cell(ScrollRegion::new(Label::new("Scrolled")), s)
+        });
+        push_row("ScrollBarRegion", &|s| {
+            cell(ScrollBarRegion::new(Label::new("Scrolled")), s)
+        });
+        push_row("ZoomView", &|s| {
+            cell(ZoomView::new(Label::new("Zoomed")), s)
+        });
+        push_row("EditField", &|s| cell(EditField::new("Edit field"), s));
+        push_row("Confirm", &|s| cell(Confirm::new("Proceed?"), s));
+        push_row("Alert", &|s| {
+            cell(Alert::new(Severity::Warning, "Careful"), s)
+        });
+        push_row("TextPrompt", &|s| cell(TextPrompt::new("Name:", "Anne"), s));
+        push_row("Settings", &|s| cell(Settings::new(), s));
+        push_row("ListView", &|s| {
+            let data = vec!["One", "Two", "Three"];
+            cell(ListView::<Down, Vec<&str>>::new(data), s)
+        });
+        push_row("MatrixView", &|s| {
+            let data = Table::from_csv("a,b\n1,2\n3,4\n").unwrap();
+            cell(MatrixView::<Table>::new(data), s)
+        });
+        push_row("SingleView", &|s| {
+            cell(SingleView::<SharedRc<i32>>::new(SharedRc::new(7)), s)
+        });
+        push_row("FilterListView", &|s| {
+            let data = vec!["Apple", "Banana", "Cherry"];
+            let filter = ContainsString::new("an");
+            type View = FilterListView<Down, Vec<&'static str>, ContainsString>;
+            cell(View::new(data, filter), s)
+        });
+    })
+}
+
+widget! {
+    /// A gallery of widgets in each interactive state
+    ///
+    /// This shows one row per widget type (from this crate), drawn normally,
+    /// disabled, hovered and with focus, on a scrollable page. Cells are
+    /// [`Inert`] previews; they do not respond to input.
+    ///
+    /// Excluded are window types ([`Window`], [`MessageBox`] and
+    /// [`DialogWindow`]) and [`Image`] (which requires an image file).
+    ///
+    /// This serves both as a demonstration of themes and as a fixture for
+    /// regression tests (rendering is deterministic).
+    #[derive(Debug)]
+    #[handler(msg = VoidMsg)]
+    #[widget{
+        layout = single;
+    }]
+    pub struct Gallery {
+        #[widget_core]
+        core: CoreData,
+        #[widget]
+        inner: ScrollBarRegion<BoxGrid<VoidMsg>>,
+    }
+
+    impl Self {
+        /// Construct
+        pub fn new() -> Self {
+            Gallery {
+                core: Default::default(),
+                inner: ScrollBarRegion::new(build()),
+            }
+        }
+    }
+}
+
+impl Default for Gallery {
+    fn default() -> Self {
+        Gallery::new()
+    }
+}
@@ -157,6 +157,126 @@ impl<W: Widget> Grid<W> {
         self.widgets.len()
     }
 
+    /// Returns the number of rows
+    pub fn num_rows(&self) -> u32 {
+        self.dim.rows
+    }
+
+    /// Returns the number of columns
+    pub fn num_cols(&self) -> u32 {
+        self.dim.cols
+    }
+
+    /// Get the first index of a child occupying the given cell, if any
+    pub fn find_child_cell(&self, row: u32, col: u32) -> Option<usize> {
+        self.widgets.iter().position(|(info, _)| {
+            info.col <= col && col < info.col_end && info.row <= row && row < info.row_end
+        })
+    }
+
+    /// Set the child widget occupying the cell `row, col`
+    ///
+    /// If a child occupies this cell, its widget is replaced (retaining its
+    /// spans) and the old widget returned. Otherwise, the widget is added
+    /// (appearing last in navigation order) and `None` is returned.
+    ///
+    /// Triggers a [reconfigure action](EventState::send_action).
+    pub fn set_child(&mut self, row: u32, col: u32, widget: W) -> (Option<W>, TkAction) {
+        let old = if let Some(index) = self.find_child_cell(row, col) {
+            Some(std::mem::replace(&mut self.widgets[index].1, widget))
+        } else {
+            self.widgets.push((GridChildInfo::new(row, col), widget));
+            None
+        };
+        self.calc_dim();
+        (old, TkAction::RECONFIGURE)
+    }
+
+    /// Insert an empty row before row `at`
+    ///
+    /// Children at or below `at` move down one row; children spanning across
+    /// `at` have their span extended.
+    ///
+    /// Triggers a [reconfigure action](EventState::send_action).
+    pub fn insert_row(&mut self, at: u32) -> TkAction {
+        self.insert_line(at, |info| (&mut info.row, &mut info.row_end))
+    }
+
+    /// Insert an empty column before column `at`
+    ///
+    /// Children at or right of `at` move right one column; children spanning
+    /// across `at` have their span extended.
+    ///
+    /// Triggers a [reconfigure action](EventState::send_action).
+    pub fn insert_col(&mut self, at: u32) -> TkAction {
+        self.insert_line(at, |info| (&mut info.col, &mut info.col_end))
+    }
+
+    /// Remove row `index`
+    ///
+    /// Children occupying only this row are removed and returned (in list
+    /// order). Children spanning this row have their span reduced; children
+    /// below move up one row.
+    ///
+    /// Triggers a [reconfigure action](EventState::send_action).
+    pub fn remove_row(&mut self, index: u32) -> (Vec<W>, TkAction) {
+        self.remove_line(index, |info| (&mut info.row, &mut info.row_end))
+    }
+
+    /// Remove column `index`
+    ///
+    /// Children occupying only this column are removed and returned (in list
+    /// order). Children spanning this column have their span reduced;
+    /// children to the right move left one column.
+    ///
+    /// Triggers a [reconfigure action](EventState::send_action).
+    pub fn remove_col(&mut self, index: u32) -> (Vec<W>, TkAction) {
+        self.remove_line(index, |info| (&mut info.col, &mut info.col_end))
+    }
+
+    fn insert_line(
+        &mut self,
+        at: u32,
+        f: fn(&mut GridChildInfo) -> (&mut u32, &mut u32),
+    ) -> TkAction {
+        for (info, _) in &mut self.widgets {
+            let (start, end) = f(info);
+            if *start >= at {
+                *start += 1;
+                *end += 1;
+            } else if *end > at {
+                *end += 1;
+            }
+        }
+        self.calc_dim();
+        TkAction::RECONFIGURE
+    }
+
+    fn remove_line(
+        &mut self,
+        index: u32,
+        f: fn(&mut GridChildInfo) -> (&mut u32, &mut u32),
+    ) -> (Vec<W>, TkAction) {
+        let mut removed = vec![];
+        let mut i = 0;
+        while i < self.widgets.len() {
+            let (start, end) = f(&mut self.widgets[i].0);
+            if *start == index && *end == index + 1 {
+                removed.push(self.widgets.remove(i).1);
+                continue;
+            }
+            if *start > index {
+                *start -= 1;
+                *end -= 1;
+            } else if *end > index {
+                *end -= 1;
+            }
+            i += 1;
+        }
+        self.calc_dim();
+        (removed, TkAction::RECONFIGURE)
+    }
+
     /// Iterate over childern
     pub fn iter(&self) -> impl Iterator<Item = &(GridChildInfo, W)> {
         ListIter {
@@ -0,0 +1,145 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Inert wrapper
+
+use kas::event;
+use kas::prelude::*;
+use std::fmt::Debug;
+
+widget! {
+    /// A wrapper making content inert
+    ///
+    /// While inert, the inner widget is drawn normally but without hover,
+    /// depress or focus highlighting (see [`DrawMgr::inert`]), and receives no
+    /// events: its children are not found by hit-testing, are skipped by
+    /// keyboard navigation and events sent to them (e.g. via accelerator keys)
+    /// are discarded.
+    ///
+    /// This is useful for previews (e.g. a theme gallery or style editor) and
+    /// for content below a modal dialog (see [`Inert::set_inert`]). Content
+    /// may be drawn in a given state using [`Inert::with_state`].
+    #[autoimpl(Deref, DerefMut on self.inner)]
+    #[autoimpl(class_traits where W: trait on self.inner)]
+    #[derive(Clone, Debug, Default)]
+    #[handler(msg = <W as event::Handler>::Msg)]
+    pub struct Inert<W: Widget> {
+        #[widget_core]
+        core: CoreData,
+        active: bool,
+        state: InputState,
+        #[widget]
+        inner: W,
+    }
+
+    impl Self {
+        /// Construct an inert wrapper around `inner`
+        #[inline]
+        pub fn new(inner: W) -> Self {
+            Inert {
+                core: Default::default(),
+                active: false,
+                state: InputState::empty(),
+                inner,
+            }
+        }
+
+        /// Draw content with the given state (chain style)
+        ///
+        /// While inert, content is drawn with this `state` (e.g.
+        /// [`InputState::HOVER`]) in place of its actual interactive state; see
+        /// [`DrawMgr::with_state`]. By default this is empty.
+        #[inline]
+        #[must_use]
+        pub fn with_state(mut self, state: InputState) -> Self {
+            self.state = state;
+            self
+        }
+
+        /// Access inner widget directly
+        #[inline]
+        pub fn inner(&self) -> &W {
+            &self.inner
+        }
+
+        /// Access inner widget directly
+        #[inline]
+        pub fn inner_mut(&mut self) -> &mut W {
+            &mut self.inner
+        }
+
+        /// Get whether content is inert
+        #[inline]
+        pub fn is_inert(&self) -> bool {
+            !self.active
+        }
+
+        /// Set whether content is inert
+        ///
+        /// Content is inert by default; this may be used to make content
+        /// temporarily inert, e.g. while a modal dialog is shown.
+        pub fn set_inert(&mut self, inert: bool) -> TkAction {
+            if self.active == !inert {
+                return TkAction::empty();
+            }
+            self.active = !inert;
+            TkAction::REDRAW
+        }
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
+            self.inner.size_rules(size_mgr, axis)
+        }
+
+        fn set_rect(&mut self, mgr: &mut SetRectMgr, rect: Rect, align: AlignHints) {
+            self.core.rect = rect;
+            self.inner.set_rect(mgr, rect, align);
+        }
+
+        fn spatial_nav(
+            &mut self,
+            _: &mut SetRectMgr,
+            _: bool,
+            from: Option<usize>,
+        ) -> Option<usize> {
+            if self.active && from.is_none() {
+                Some(0)
+            } else {
+                None
+            }
+        }
+
+        fn find_id(&mut self, coord: Coord) -> Option<WidgetId> {
+            if !self.rect().contains(coord) {
+                return None;
+            }
+            if self.active {
+                self.inner.find_id(coord)
+            } else {
+                Some(self.id())
+            }
+        }
+
+        fn draw(&mut self, mut draw: DrawMgr) {
+            let mut draw = draw.with_core(self.core_data());
+            if self.active {
+                self.inner.draw(draw.re());
+            } else {
+                self.inner.draw(draw.re().with_state(self.state));
+            }
+        }
+    }
+
+    impl event::SendEvent for Self {
+        fn send(&mut self, mgr: &mut EventMgr, id: WidgetId, event: Event) -> Response<Self::Msg> {
+            if self.active && self.inner.id().is_ancestor_of(&id) {
+                self.inner.send(mgr, id, event)
+            } else {
+                Response::Unused
+            }
+        }
+    }
+}
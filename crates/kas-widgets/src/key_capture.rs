@@ -0,0 +1,152 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Key binding capture
+
+use kas::event::config::Shortcuts;
+use kas::event::{self, Command, ModifiersState, VirtualKeyCode};
+use kas::layout;
+use kas::prelude::*;
+use kas::theme::TextClass;
+
+widget! {
+    /// A control for entering a key binding
+    ///
+    /// Shows the current binding, if any. When activated (e.g. clicked), the
+    /// control waits for a key press (see
+    /// [`EventState::request_key_capture`]), showing any modifiers held so
+    /// far, then sets the new binding and emits it as a message.
+    /// <kbd>Escape</kbd> (without modifiers), activating the control again or
+    /// loss of window focus cancels capture.
+    ///
+    /// When not capturing, [`Command::Delete`] and [`Command::DelBack`]
+    /// (usually <kbd>Delete</kbd> and <kbd>Backspace</kbd>) clear the binding;
+    /// this emits no message but returns [`Response::Update`].
+    #[derive(Clone, Debug, Default)]
+    #[widget{
+        key_nav = true;
+        hover_highlight = true;
+    }]
+    pub struct KeyCapture {
+        #[widget_core]
+        core: CoreData,
+        label: Text<String>,
+        layout_frame: layout::FrameStorage,
+        layout_text: layout::TextStorage,
+        binding: Option<(ModifiersState, VirtualKeyCode)>,
+    }
+
+    impl Self {
+        /// Construct, with an optional initial `binding`
+        pub fn new(binding: Option<(ModifiersState, VirtualKeyCode)>) -> Self {
+            KeyCapture {
+                core: Default::default(),
+                label: Text::new_single(Self::describe(binding)),
+                layout_frame: Default::default(),
+                layout_text: Default::default(),
+                binding,
+            }
+        }
+
+        /// Get the current binding
+        #[inline]
+        pub fn binding(&self) -> Option<(ModifiersState, VirtualKeyCode)> {
+            self.binding
+        }
+
+        /// Set the binding
+        pub fn set_binding(
+            &mut self,
+            binding: Option<(ModifiersState, VirtualKeyCode)>,
+        ) -> TkAction {
+            self.binding = binding;
+            self.set_label(Self::describe(binding))
+        }
+
+        /// Clear the binding
+        ///
+        /// This is equivalent to `self.set_binding(None)`.
+        #[inline]
+        pub fn clear(&mut self) -> TkAction {
+            self.set_binding(None)
+        }
+
+        fn describe(binding: Option<(ModifiersState, VirtualKeyCode)>) -> String {
+            match binding {
+                Some((modifiers, vkey)) => Shortcuts::key_name(modifiers, vkey),
+                None => "(none)".to_string(),
+            }
+        }
+
+        fn set_label(&mut self, label: String) -> TkAction {
+            let avail = self.core.rect.size.clamped_sub(self.layout_frame.size);
+            kas::text::util::set_text_and_prepare(&mut self.label, label, avail)
+        }
+    }
+
+    impl kas::Layout for Self {
+        fn layout(&mut self) -> layout::Layout<'_> {
+            let inner = layout::Layout::text(&mut self.layout_text, &mut self.label, TextClass::Button);
+            layout::Layout::button(&mut self.layout_frame, inner, None)
+        }
+
+        fn draw(&mut self, mut draw: DrawMgr) {
+            let mut draw = draw.with_core(self.core_data());
+            if draw.ev_state().is_capturing_keys(self.id_ref()) {
+                draw.state.insert(InputState::DEPRESS);
+            }
+            self.layout().draw(draw);
+        }
+    }
+
+    impl event::Handler for Self {
+        type Msg = (ModifiersState, VirtualKeyCode);
+
+        #[inline]
+        fn activation_via_press(&self) -> bool {
+            true
+        }
+
+        fn handle(&mut self, mgr: &mut EventMgr, event: Event) -> Response<Self::Msg> {
+            match event {
+                Event::Activate => {
+                    if mgr.is_capturing_keys(self.id_ref()) {
+                        mgr.cancel_key_capture(self.id_ref());
+                        *mgr |= self.set_binding(self.binding);
+                    } else {
+                        mgr.request_key_capture(self.id());
+                        let prefix = Shortcuts::modifiers_name(mgr.modifiers());
+                        *mgr |= self.set_label(format!("{}…", prefix));
+                    }
+                    Response::Used
+                }
+                Event::KeyCaptureModifiers(modifiers) => {
+                    let prefix = Shortcuts::modifiers_name(modifiers);
+                    *mgr |= self.set_label(format!("{}…", prefix));
+                    Response::Used
+                }
+                Event::KeyCapture(VirtualKeyCode::Escape, modifiers) if modifiers.is_empty() => {
+                    *mgr |= self.set_binding(self.binding);
+                    Response::Used
+                }
+                Event::KeyCaptureCancelled => {
+                    *mgr |= self.set_binding(self.binding);
+                    Response::Used
+                }
+                Event::KeyCapture(vkey, modifiers) => {
+                    *mgr |= self.set_binding(Some((modifiers, vkey)));
+                    Response::Msg((modifiers, vkey))
+                }
+                Event::Command(Command::Delete | Command::DelBack, _)
+                    if self.binding.is_some() =>
+                {
+                    *mgr |= self.clear();
+                    Response::Update
+                }
+                _ => Response::Unused,
+            }
+        }
+    }
+}
@@ -5,8 +5,11 @@
 
 //! Text widgets
 
+use kas::event::components::{TextInput, TextInputAction};
+use kas::event::Command;
 use kas::text::format::{EditableText, FormattableText};
-use kas::theme::TextClass;
+use kas::text::SelectionHelper;
+use kas::theme::{DrawCtx, TextClass};
 use kas::{event, prelude::*};
 
 widget! {
@@ -14,11 +17,27 @@ widget! {
     ///
     /// This type is generic over the text type. Some aliases are available:
     /// [`StrLabel`], [`StringLabel`], [`AccelLabel`].
+    ///
+    /// Labels are not selectable by default; see [`Label::with_selectable`].
+    /// (For scrollable text, use [`ScrollLabel`](crate::ScrollLabel).)
     #[derive(Clone, Default, Debug)]
     pub struct Label<T: FormattableText + 'static> {
         #[widget_core]
         core: CoreData,
         label: Text<T>,
+        selectable: bool,
+        selection: SelectionHelper,
+        input_handler: TextInput,
+    }
+
+    impl WidgetConfig for Self {
+        fn cursor_icon(&self) -> event::CursorIcon {
+            if self.selectable {
+                event::CursorIcon::Text
+            } else {
+                event::CursorIcon::Default
+            }
+        }
     }
 
     impl Layout for Self {
@@ -38,12 +57,76 @@ widget! {
         #[cfg(feature = "min_spec")]
         default fn draw(&mut self, mut draw: DrawMgr) {
             let mut draw = draw.with_core(self.core_data());
-            draw.text_effects(self.core.rect.pos, &self.label, TextClass::Label);
+            if !self.draw_selection(&mut draw) {
+                draw.text_effects(self.core.rect.pos, &self.label, TextClass::Label);
+            }
         }
         #[cfg(not(feature = "min_spec"))]
         fn draw(&mut self, mut draw: DrawMgr) {
             let mut draw = draw.with_core(self.core_data());
-            draw.text_effects(self.core.rect.pos, &self.label, TextClass::Label);
+            if !self.draw_selection(&mut draw) {
+                draw.text_effects(self.core.rect.pos, &self.label, TextClass::Label);
+            }
+        }
+    }
+
+    impl event::Handler for Self {
+        type Msg = VoidMsg;
+
+        fn handle(&mut self, mgr: &mut EventMgr, event: Event) -> Response<Self::Msg> {
+            if !self.selectable {
+                return Response::Unused;
+            }
+
+            match event {
+                Event::Command(cmd, _) => match cmd {
+                    Command::Escape | Command::Deselect if !self.selection.is_empty() => {
+                        self.selection.set_empty();
+                        mgr.redraw(self.id());
+                        Response::Used
+                    }
+                    Command::SelectAll => {
+                        self.selection.set_sel_pos(0);
+                        self.selection.set_edit_pos(self.label.str_len());
+                        mgr.redraw(self.id());
+                        Response::Used
+                    }
+                    Command::Copy if !self.selection.is_empty() => {
+                        let range = self.selection.range();
+                        mgr.set_clipboard((self.label.as_str()[range]).to_string());
+                        Response::Used
+                    }
+                    _ => Response::Unused,
+                },
+                Event::LostSelFocus => {
+                    self.selection.set_empty();
+                    mgr.redraw(self.id());
+                    Response::Used
+                }
+                event => match self.input_handler.handle(mgr, self.id(), event) {
+                    TextInputAction::None | TextInputAction::Focus => Response::Used,
+                    TextInputAction::Unused => Response::Unused,
+                    TextInputAction::Pan(delta) => Response::Pan(delta),
+                    TextInputAction::Cursor(coord, anchor, clear, repeats) => {
+                        if (clear && repeats <= 1) || mgr.request_sel_focus(self.id()) {
+                            let rel_pos = (coord - self.core.rect.pos).into();
+                            let pos = self.label.text_index_nearest(rel_pos);
+                            self.selection.set_edit_pos(pos);
+                            if anchor {
+                                self.selection.set_anchor();
+                            }
+                            if clear {
+                                self.selection.set_empty();
+                            }
+                            if repeats > 1 {
+                                self.selection.expand(&self.label, repeats);
+                            }
+                            mgr.redraw(self.id());
+                        }
+                        Response::Used
+                    }
+                },
+            }
         }
     }
 
@@ -58,7 +141,8 @@ widget! {
         T: EditableText,
     {
         fn set_string(&mut self, string: String) -> TkAction {
-            kas::text::util::set_string_and_prepare(&mut self.label, string, self.core.rect.size)
+            let avail = self.core.rect.size;
+            set_string_and_clear(&mut self.label, &mut self.selection, string, avail)
         }
     }
 }
@@ -67,8 +151,10 @@ widget! {
 impl Layout for AccelLabel {
     fn draw(&mut self, mut draw: DrawMgr) {
         let mut draw = draw.with_core(self.core_data());
-        let accel = draw.ev_state().show_accel_labels();
-        draw.text_accel(self.core.rect.pos, &self.label, accel, TextClass::Label);
+        if !self.draw_selection(&mut draw) {
+            let accel = draw.ev_state().show_accel_labels();
+            draw.text_accel(self.core.rect.pos, &self.label, accel, TextClass::Label);
+        }
     }
 }
 
@@ -77,14 +163,18 @@ impl Layout for AccelLabel {
 impl<'a> Layout for Label<&'a str> {
     fn draw(&mut self, mut draw: DrawMgr) {
         let mut draw = draw.with_core(self.core_data());
-        draw.text(self.core.rect.pos, self.label.as_ref(), TextClass::Label);
+        if !self.draw_selection(&mut draw) {
+            draw.text(self.core.rect.pos, self.label.as_ref(), TextClass::Label);
+        }
     }
 }
 #[cfg(feature = "min_spec")]
 impl Layout for StringLabel {
     fn draw(&mut self, mut draw: DrawMgr) {
         let mut draw = draw.with_core(self.core_data());
-        draw.text(self.core.rect.pos, self.label.as_ref(), TextClass::Label);
+        if !self.draw_selection(&mut draw) {
+            draw.text(self.core.rect.pos, self.label.as_ref(), TextClass::Label);
+        }
     }
 }
 
@@ -115,18 +205,68 @@ impl<T: FormattableText + 'static> Label<T> {
         Label {
             core: Default::default(),
             label: Text::new_multi(label),
+            selectable: false,
+            selection: SelectionHelper::new(0, 0),
+            input_handler: Default::default(),
         }
     }
 
+    /// Set whether text is selectable (inline)
+    ///
+    /// If true, text may be selected by mouse or touch drag (a double-click
+    /// selects a word, a triple-click a line) and copied to the clipboard
+    /// via <kbd>Ctrl</kbd>+<kbd>C</kbd>. Default: false.
+    #[inline]
+    #[must_use]
+    pub fn with_selectable(mut self, selectable: bool) -> Self {
+        self.selectable = selectable;
+        self
+    }
+
+    /// Get whether text is selectable
+    #[inline]
+    pub fn is_selectable(&self) -> bool {
+        self.selectable
+    }
+
+    /// Draw text with selection highlighting, if anything is selected
+    ///
+    /// Returns false (without drawing) if nothing is selected.
+    fn draw_selection(&self, draw: &mut DrawCtx) -> bool {
+        if self.selection.is_empty() {
+            return false;
+        }
+        let range = self.selection.range();
+        draw.text_selected(self.core.rect.pos, &self.label, range, TextClass::Label);
+        true
+    }
+
     /// Set text in an existing `Label`
     ///
     /// Note: this must not be called before fonts have been initialised
     /// (usually done by the theme when the main loop starts).
+    ///
+    /// Any selection is cleared.
     pub fn set_text(&mut self, text: T) -> TkAction {
+        self.selection.clear();
         kas::text::util::set_text_and_prepare(&mut self.label, text, self.core.rect.size)
     }
 }
 
+/// Set text from a `String`, clearing `selection`
+///
+/// Selection positions are not valid for new text. This is shared by [`Label`]
+/// and [`ScrollLabel`](crate::ScrollLabel).
+pub(crate) fn set_string_and_clear<T: EditableText>(
+    text: &mut Text<T>,
+    selection: &mut SelectionHelper,
+    string: String,
+    avail: Size,
+) -> TkAction {
+    selection.clear();
+    kas::text::util::set_string_and_prepare(text, string, avail)
+}
+
 /// Label with `&'static str` as backing type
 ///
 /// Warning: this type does not support [`HasString`]. Assignment is possible
@@ -158,6 +298,11 @@ impl AccelLabel {
     pub fn keys(&self) -> &[event::VirtualKeyCode] {
         self.label.text().keys()
     }
+
+    /// Get the label's [`AccelString`]
+    pub fn accel_string(&self) -> &AccelString {
+        self.label.text()
+    }
 }
 
 impl SetAccel for AccelLabel {
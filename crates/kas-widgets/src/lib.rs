@@ -8,37 +8,54 @@
 //! ## Dialogs
 //!
 //! -   [`MessageBox`]: a simple window with a message and an "Ok" button
+//! -   [`Confirm`], [`Alert`] and [`TextPrompt`]: standard dialogs emitting
+//!     their result as a message
+//! -   [`DialogWindow`]: shows a dialog in its own window
+//! -   [`Settings`]: edits theme, event and shortcut configuration
 //!
 //! ## Container widgets
 //!
 //! -   [`Frame`]: a simple frame around a single child
+//! -   [`Inert`]: draws content without highlighting and blocks its input
 //! -   [`ScrollRegion`]: may be larger on the inside than the outside
+//! -   [`ZoomView`]: a zoomable and pannable view over scaled content
 //! -   [`Stack`]: a stack of widgets in the same rect (TODO: `TabbedStack`)
 //! -   [`List`]: a dynamic row / column of children
 //! -   [`Splitter`]: similar to [`List`] but with resizing handles
+//! -   [`Form`]: a column of labelled widgets
+//! -   [`Wizard`]: a sequence of pages with navigation buttons
 //! -   [`Window`] is usually the root widget and has special handling for
 //!     pop-ups and callbacks
 //!
 //! ## Menus
 //!
 //! -   [`ComboBox`]: a simple pop-up selector
-//! -   [`MenuBar`], [`SubMenu`]: menu parent widgets
+//! -   [`MenuBar`], [`SubMenu`], [`ContextMenu`]: menu parent widgets
 //! -   [`MenuEntry`], [`MenuToggle`], [`Separator`]: menu entries
+//! -   [`RecentFilesMenu`]: a sub-menu over a [`RecentFiles`] list
+//! -   [`ToolBar`], [`ToolItem`]: a bar of compact buttons
 //!
 //! ## Controls
 //!
 //! -   [`TextButton`]: a simple button
 //! -   [`CheckBox`]: a checkable box
 //! -   [`RadioBox`]: a checkable box bound to a group
+//! -   [`Switch`]: an on/off toggle switch
 //! -   [`EditBox`]: a text-editing box
+//! -   [`MaskedEdit`]: a text-editing box with an input mask
 //! -   [`ScrollBar`]: a scrollbar
 //! -   [`Slider`]: a slider
+//! -   [`KeyCapture`]: a key-binding input
+//! -   [`Paginator`]: page navigation buttons
 //!
 //! ## Static widgets
 //!
 //! -   [`Filler`]: an empty widget, sometimes used to fill space
 //! -   [`Separator`]: a visible bar to separate things
 //! -   [`Label`]: a simple text label
+//! -   [`LogView`]: a view over a log of text lines
+//! -   [`Image`], [`Icon`]: images
+//! -   `Gallery`: previews of each widget in each state (feature `demo`)
 //!
 //! ## Components
 //!
@@ -46,6 +63,8 @@
 //! -   [`CheckBoxBare`]: `CheckBox` without its label
 //! -   [`RadioBoxBare`]: `RadioBox` without its label
 //! -   [`DragHandle`]: a handle (e.g. for a slider, splitter or scrollbar)
+//! -   [`TitleBar`]: a window title bar, used for client-side decorations
+//! -   [`ResizeGrip`]: a grip for resizing the window by dragging
 
 // Use ``never_loop`` until: https://github.com/rust-lang/rust-clippy/issues/7397 is fixed
 #![allow(clippy::or_fun_call, clippy::never_loop, clippy::comparison_chain)]
@@ -60,50 +79,90 @@ mod dialog;
 mod drag;
 mod edit_field;
 mod filler;
+mod form;
 mod frame;
+#[cfg(feature = "demo")]
+mod gallery;
 mod grid;
+mod inert;
+mod key_capture;
 mod label;
 mod list;
+mod log_view;
 #[macro_use]
 mod macros;
+mod masked_edit;
 mod menu;
 mod nav_frame;
+mod paginator;
 mod progress;
 mod radiobox;
+mod recent;
+mod resize_grip;
 mod scroll;
 mod scroll_label;
 mod scrollbar;
 mod separator;
+mod settings;
 mod slider;
 mod splitter;
 mod sprite;
 mod stack;
+mod switch;
+mod title_bar;
+mod toolbar;
 mod window;
+mod wizard;
+mod zoom;
 
 pub mod adapter;
+#[cfg(feature = "declarative")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "declarative")))]
+pub mod declarative;
+pub mod spell;
 pub mod view;
 
 pub use button::{Button, TextButton};
 pub use checkbox::{CheckBox, CheckBoxBare};
 pub use combobox::ComboBox;
-pub use dialog::MessageBox;
-pub use drag::DragHandle;
-pub use edit_field::{EditBox, EditField, EditGuard};
+pub use dialog::{Alert, Confirm, ConfirmResult, DialogWindow, MessageBox, Severity, TextPrompt};
+pub use drag::{DragHandle, TrackClick};
+pub use edit_field::{CommitMode, Completions, EditBox, EditField, EditGuard};
 pub use filler::Filler;
+pub use form::{BoxForm, Form};
 pub use frame::Frame;
+#[cfg(feature = "demo")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "demo")))]
+pub use gallery::Gallery;
 pub use grid::{BoxGrid, Grid};
+pub use inert::Inert;
+pub use key_capture::KeyCapture;
 pub use label::{AccelLabel, Label, StrLabel, StringLabel};
 pub use list::*;
+pub use log_view::LogView;
+pub use masked_edit::{Mask, MaskClass, MaskGuard, MaskedEdit};
 pub use menu::*;
 pub use nav_frame::NavFrame;
+pub use paginator::Paginator;
 pub use progress::ProgressBar;
 pub use radiobox::{RadioBox, RadioBoxBare, RadioBoxGroup};
+pub use recent::{RecentFiles, RecentFilesMenu};
+pub use resize_grip::ResizeGrip;
 pub use scroll::ScrollRegion;
 pub use scroll_label::ScrollLabel;
-pub use scrollbar::{ScrollBar, ScrollBarRegion, ScrollBars, Scrollable};
+pub use scrollbar::{
+    ScrollBar, ScrollBarMode, ScrollBarMsg, ScrollBarRegion, ScrollBars, Scrollable,
+};
 pub use separator::Separator;
+pub use settings::Settings;
 pub use slider::{Slider, SliderType};
+pub use spell::SpellChecker;
 pub use splitter::*;
-pub use sprite::Image;
+pub use sprite::{Icon, Image};
 pub use stack::{BoxStack, RefStack, Stack};
+pub use switch::Switch;
+pub use title_bar::TitleBar;
+pub use toolbar::{ToolBar, ToolItem};
 pub use window::Window;
+pub use wizard::{Wizard, WizardMsg};
+pub use zoom::ZoomView;
@@ -7,7 +7,7 @@
 
 use kas::dir::{Down, Right};
 use kas::{event, layout, prelude::*};
-use std::ops::{Index, IndexMut};
+use std::ops::{Bound, Index, IndexMut, Range, RangeBounds};
 
 /// Support for optionally-indexed messages
 pub trait FromIndexed<T> {
@@ -347,13 +347,13 @@ widget! {
         ///
         /// Panics if `index` is out of bounds.
         ///
-        /// Triggers a [reconfigure action](EventState::send_action).
-        // TODO: in theory it is possible to avoid a reconfigure where both widgets
-        // have no children and have compatible size. Is this a good idea and can
-        // we somehow test "has compatible size"?
-        pub fn replace(&mut self, index: usize, mut widget: W) -> (W, TkAction) {
+        /// The new widget is configured immediately (via `mgr`); other widgets
+        /// keep their [`WidgetId`]. Triggers a resize action.
+        pub fn replace(&mut self, mgr: &mut SetRectMgr, index: usize, mut widget: W) -> W {
             std::mem::swap(&mut widget, &mut self.widgets[index]);
-            (widget, TkAction::RECONFIGURE)
+            mgr.configure(self.id().make_child(index), &mut self.widgets[index]);
+            *mgr |= TkAction::RESIZE;
+            widget
         }
 
         /// Append child widgets from an iterator
@@ -391,14 +391,77 @@ widget! {
         ///
         /// See documentation of [`Vec::retain`].
         ///
-        /// Triggers a [reconfigure action](EventState::send_action) if any widgets
-        /// are removed.
-        pub fn retain<F: FnMut(&W) -> bool>(&mut self, f: F) -> TkAction {
+        /// Only widgets whose index changes are reconfigured (via `mgr`); other
+        /// widgets keep their [`WidgetId`]. If any widget is removed, this
+        /// triggers a resize.
+        pub fn retain<F: FnMut(&W) -> bool>(&mut self, mgr: &mut SetRectMgr, mut f: F) {
+            let keep: Vec<bool> = self.widgets.iter().map(|w| f(w)).collect();
+            let first = match keep.iter().position(|k| !k) {
+                Some(index) => index,
+                None => return,
+            };
+            let mut iter = keep.into_iter();
+            self.widgets.retain(|_| iter.next().unwrap());
+            self.configure_range(mgr, first..self.widgets.len());
+            *mgr |= TkAction::RESIZE;
+        }
+
+        /// Replace the widgets in `range` with those from `replace_with`
+        ///
+        /// Removed widgets are returned. See documentation of [`Vec::splice`].
+        ///
+        /// Only new widgets and those whose index changes are configured (via
+        /// `mgr`); other widgets keep their [`WidgetId`]. Triggers a resize
+        /// unless no widgets are added or removed.
+        ///
+        /// Panics if the range is out of bounds.
+        pub fn splice<R, I>(&mut self, mgr: &mut SetRectMgr, range: R, replace_with: I) -> Vec<W>
+        where
+            R: RangeBounds<usize>,
+            I: IntoIterator<Item = W>,
+        {
+            let start = match range.start_bound() {
+                Bound::Included(index) => *index,
+                Bound::Excluded(index) => *index + 1,
+                Bound::Unbounded => 0,
+            };
+            let old_len = self.widgets.len();
+            let removed: Vec<W> = self.widgets.splice(range, replace_with).collect();
             let len = self.widgets.len();
-            self.widgets.retain(f);
-            match len == self.widgets.len() {
-                true => TkAction::empty(),
-                false => TkAction::RECONFIGURE,
+            if len == old_len && removed.is_empty() {
+                return removed;
+            }
+            // Widgets after the replaced range move unless the length is unchanged
+            let end = match len == old_len {
+                true => start + removed.len(),
+                false => len,
+            };
+            self.configure_range(mgr, start..end);
+            *mgr |= TkAction::RESIZE;
+            removed
+        }
+
+        /// Swap the widgets at indices `a` and `b`
+        ///
+        /// Only these two widgets are reconfigured (via `mgr`). Triggers a
+        /// resize.
+        ///
+        /// Panics if `a` or `b` are out of bounds.
+        pub fn swap(&mut self, mgr: &mut SetRectMgr, a: usize, b: usize) {
+            if a == b {
+                return;
+            }
+            self.widgets.swap(a, b);
+            self.configure_range(mgr, a..(a + 1));
+            self.configure_range(mgr, b..(b + 1));
+            *mgr |= TkAction::RESIZE;
+        }
+
+        /// Configure widgets in `range`, assigning identifiers by index
+        fn configure_range(&mut self, mgr: &mut SetRectMgr, range: Range<usize>) {
+            for index in range {
+                let id = self.id_ref().make_child(index);
+                mgr.configure(id, &mut self.widgets[index]);
             }
         }
 
@@ -0,0 +1,312 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Log view widget
+
+use super::Scrollable;
+use kas::draw::{color::Rgba, Draw};
+use kas::event::{self, components::ScrollComponent};
+use kas::prelude::*;
+use kas::text::Environment;
+use kas::theme::TextClass;
+use std::collections::VecDeque;
+
+#[derive(Clone, Debug)]
+struct LogLine {
+    text: Text<String>,
+    col: Option<Rgba>,
+    width: f32,
+}
+
+widget! {
+    /// A view over a log of text lines
+    ///
+    /// Lines are stored in a ring buffer: once `capacity` is reached, appending
+    /// a line drops the oldest. Each line is prepared independently, thus
+    /// appending is cheap and does not require a resize. Lines are not wrapped.
+    ///
+    /// In follow-tail mode (the default), the view scrolls to show new lines
+    /// unless the user has scrolled away from the end.
+    ///
+    /// Lines may optionally have a colour; otherwise the theme's text colour is
+    /// used. Scrollbars are not included; wrap with [`ScrollBars`] if required.
+    ///
+    /// [`ScrollBars`]: crate::ScrollBars
+    #[derive(Clone, Debug)]
+    pub struct LogView {
+        #[widget_core]
+        core: CoreData,
+        sample: Text<&'static str>,
+        env: Environment,
+        env_ready: bool,
+        line_height: i32,
+        width: f32,
+        lines: VecDeque<LogLine>,
+        capacity: usize,
+        follow_tail: bool,
+        scroll: ScrollComponent,
+    }
+
+    impl WidgetConfig for Self {
+        fn configure(&mut self, mgr: &mut SetRectMgr) {
+            mgr.register_nav_fallback(self.id());
+        }
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
+            // The sample is used to select font properties for all lines
+            let _ = size_mgr.text_bound(&mut self.sample, TextClass::LabelScroll, axis);
+            self.line_height = size_mgr.line_height(TextClass::LabelScroll);
+            self.scroll.set_scroll_rate(3.0 * f32::conv(self.line_height));
+            self.update_env();
+
+            let margins = size_mgr.text_margins().extract(axis);
+            let (min, ideal) = match axis.is_horizontal() {
+                true => (8 * self.line_height, 24 * self.line_height),
+                false => (3 * self.line_height, 12 * self.line_height),
+            };
+            SizeRules::new(min, ideal, margins, Stretch::Low)
+        }
+
+        fn set_rect(&mut self, _: &mut SetRectMgr, rect: Rect, _: AlignHints) {
+            self.core.rect = rect;
+            let at_end = self.is_at_end();
+            let _ = self.scroll.set_sizes(rect.size, self.content_size());
+            if self.follow_tail && at_end {
+                let _ = self.scroll.set_offset(self.scroll.max_offset());
+            }
+        }
+
+        #[inline]
+        fn translation(&self) -> Offset {
+            self.scroll_offset()
+        }
+
+        fn draw(&mut self, mut draw: DrawMgr) {
+            let mut draw = draw.with_core(self.core_data());
+            let class = TextClass::LabelScroll;
+            let rect = self.core.rect;
+            let offset = self.scroll_offset();
+            let line_height = self.line_height;
+            if line_height <= 0 {
+                return;
+            }
+            let first = usize::conv(offset.1 / line_height);
+            let last = usize::conv((offset.1 + rect.size.1) / line_height + 1);
+            draw.with_clip_region(rect, offset, |mut draw| {
+                let lines = self.lines.iter().enumerate();
+                for (i, line) in lines.take(last).skip(first) {
+                    let pos = rect.pos + Offset(0, line_height * i32::conv(i));
+                    match line.col {
+                        Some(col) => draw.draw_device().text(pos.into(), line.text.as_ref(), col),
+                        None => draw.text(pos, line.text.as_ref(), class),
+                    }
+                }
+            });
+        }
+    }
+
+    impl event::Handler for Self {
+        type Msg = VoidMsg;
+
+        fn handle(&mut self, mgr: &mut EventMgr, event: Event) -> Response<Self::Msg> {
+            let id = self.id();
+            let (action, response) =
+                self.scroll
+                    .scroll_by_event(mgr, event, self.id(), self.core.rect.size, |mgr, source, _, coord| {
+                        if source.is_primary() && mgr.config_enable_mouse_pan() {
+                            let icon = Some(event::CursorIcon::Grabbing);
+                            mgr.grab_press_unique(id, source, coord, icon);
+                        }
+                    });
+            if !action.is_empty() {
+                *mgr |= action;
+                Response::Scrolled
+            } else {
+                response
+            }
+        }
+    }
+
+    impl Scrollable for Self {
+        fn scroll_axes(&self, size: Size) -> (bool, bool) {
+            let content = self.content_size();
+            (content.0 > size.0, content.1 > size.1)
+        }
+
+        #[inline]
+        fn max_scroll_offset(&self) -> Offset {
+            self.scroll.max_offset()
+        }
+
+        #[inline]
+        fn scroll_offset(&self) -> Offset {
+            self.scroll.offset()
+        }
+
+        #[inline]
+        fn set_scroll_offset(&mut self, mgr: &mut EventMgr, offset: Offset) -> Offset {
+            *mgr |= self.scroll.set_offset(offset);
+            self.scroll.offset()
+        }
+    }
+}
+
+impl LogView {
+    /// Construct, holding at most `capacity` lines
+    ///
+    /// A `capacity` of zero is treated as one.
+    pub fn new(capacity: usize) -> Self {
+        LogView {
+            core: Default::default(),
+            sample: Text::new(Default::default(), ""),
+            env: Default::default(),
+            env_ready: false,
+            line_height: 0,
+            width: 0.0,
+            lines: VecDeque::new(),
+            capacity: capacity.max(1),
+            follow_tail: true,
+            scroll: Default::default(),
+        }
+    }
+
+    /// Set whether the view follows the tail of the log
+    ///
+    /// Default: true.
+    #[inline]
+    #[must_use]
+    pub fn with_follow_tail(mut self, follow_tail: bool) -> Self {
+        self.follow_tail = follow_tail;
+        self
+    }
+
+    /// Get whether the view follows the tail of the log
+    #[inline]
+    pub fn follow_tail(&self) -> bool {
+        self.follow_tail
+    }
+
+    /// Set whether the view follows the tail of the log
+    ///
+    /// If enabled, this scrolls to the end of the log.
+    pub fn set_follow_tail(&mut self, follow_tail: bool) -> TkAction {
+        self.follow_tail = follow_tail;
+        match follow_tail {
+            true => self.scroll.set_offset(self.scroll.max_offset()),
+            false => TkAction::empty(),
+        }
+    }
+
+    /// Get the maximum number of lines held
+    #[inline]
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Get the number of lines held
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.lines.len()
+    }
+
+    /// True if no lines are held
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.lines.is_empty()
+    }
+
+    /// Append a line using the theme's text colour
+    ///
+    /// If `line` contains line breaks, multiple lines are appended.
+    pub fn push_line<S: ToString>(&mut self, line: S) -> TkAction {
+        self.push(line.to_string(), None)
+    }
+
+    /// Append a line with the given colour
+    ///
+    /// If `line` contains line breaks, multiple lines are appended.
+    pub fn push_line_col<S: ToString>(&mut self, line: S, col: Rgba) -> TkAction {
+        self.push(line.to_string(), Some(col))
+    }
+
+    /// Remove all lines
+    pub fn clear(&mut self) -> TkAction {
+        self.lines.clear();
+        self.width = 0.0;
+        let _ = self
+            .scroll
+            .set_sizes(self.core.rect.size, self.content_size());
+        TkAction::REDRAW | self.scroll.set_offset(Offset::ZERO)
+    }
+
+    fn push(&mut self, string: String, col: Option<Rgba>) -> TkAction {
+        let at_end = self.is_at_end();
+        let mut removed = 0i32;
+        let mut removed_width = 0.0f32;
+        for s in string.split('\n') {
+            let mut text = Text::new(self.env.clone(), s.to_string());
+            // Lines pushed before size_rules are prepared later by update_env
+            let mut width = 0.0;
+            if self.env_ready {
+                width = text.prepare().map(|req| req.0).unwrap_or(0.0);
+            }
+            if self.lines.len() >= self.capacity {
+                if let Some(line) = self.lines.pop_front() {
+                    removed += 1;
+                    removed_width = removed_width.max(line.width);
+                }
+            }
+            self.width = self.width.max(width);
+            self.lines.push_back(LogLine { text, col, width });
+        }
+        if removed_width >= self.width {
+            self.width = self.lines.iter().map(|line| line.width).fold(0.0, f32::max);
+        }
+
+        let mut action = self
+            .scroll
+            .set_sizes(self.core.rect.size, self.content_size());
+        if self.follow_tail && at_end {
+            action |= self.scroll.set_offset(self.scroll.max_offset());
+        } else if removed > 0 {
+            // Keep the view on the same lines
+            let offset = self.scroll.offset() - Offset(0, removed * self.line_height);
+            action |= self.scroll.set_offset(offset);
+        }
+        action | TkAction::REDRAW
+    }
+
+    fn is_at_end(&self) -> bool {
+        self.scroll.offset().1 >= self.scroll.max_offset().1
+    }
+
+    fn content_size(&self) -> Size {
+        let rows = i32::conv(self.lines.len());
+        let content = Size(i32::conv_ceil(self.width), rows * self.line_height);
+        content.max(self.core.rect.size)
+    }
+
+    // Update the environment from the sample, re-preparing lines if required
+    fn update_env(&mut self) {
+        let mut env = self.sample.env().clone();
+        env.set_bounds(kas::text::Vec2::INFINITY);
+        env.set_wrap(false);
+        let key = |env: &Environment| (env.font_id, env.dpp, env.pt_size);
+        if self.env_ready && key(&env) == key(&self.env) {
+            return;
+        }
+
+        self.env = env;
+        self.env_ready = true;
+        self.width = 0.0;
+        for line in &mut self.lines {
+            let env = &self.env;
+            line.width = line.text.update_env(|e| *e = env.clone()).0;
+            self.width = self.width.max(line.width);
+        }
+    }
+}
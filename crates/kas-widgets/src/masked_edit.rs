@@ -0,0 +1,449 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Masked text input
+
+use crate::{EditBox, EditField, EditGuard};
+use kas::prelude::*;
+
+/// The class of characters accepted by a slot of a [`Mask`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum MaskClass {
+    /// A decimal digit (mask character `_`)
+    Digit,
+    /// A hexadecimal digit (mask character `H`)
+    HexDigit,
+    /// An alphabetic character (mask character `A`)
+    Letter,
+    /// An alphanumeric character (mask character `*`)
+    Alphanumeric,
+}
+
+impl MaskClass {
+    /// Get the class represented by mask character `c`, if any
+    pub fn from_mask_char(c: char) -> Option<Self> {
+        Some(match c {
+            '_' => MaskClass::Digit,
+            'H' => MaskClass::HexDigit,
+            'A' => MaskClass::Letter,
+            '*' => MaskClass::Alphanumeric,
+            _ => return None,
+        })
+    }
+
+    /// True if `c` is accepted by this class
+    pub fn matches(self, c: char) -> bool {
+        match self {
+            MaskClass::Digit => c.is_ascii_digit(),
+            MaskClass::HexDigit => c.is_ascii_hexdigit(),
+            MaskClass::Letter => c.is_alphabetic(),
+            MaskClass::Alphanumeric => c.is_alphanumeric(),
+        }
+    }
+}
+
+/// A run of input slots, preceded by literal text
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct Segment {
+    literal: String,
+    slots: Vec<MaskClass>,
+}
+
+/// An input mask
+///
+/// A mask is constructed from a pattern of slot characters (see
+/// [`MaskClass`]) and literal text. Consecutive slots form a *segment*;
+/// segments are separated by literals. For example, `___.___.___.___` has four
+/// segments of three digits while `+__ (___) ___-____` has four segments of
+/// two, three, three and four digits. A literal slot character may be
+/// escaped with `\`, e.g. `\A-___`.
+///
+/// Literals are inserted automatically when a segment is filled. Typing (the
+/// start of) a literal moves to the next segment if the current segment is
+/// complete (see [`Mask::with_partial_segments`]).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mask {
+    segments: Vec<Segment>,
+    trailing: String,
+    partial: bool,
+}
+
+impl Mask {
+    /// Construct from a `pattern`
+    pub fn new(pattern: &str) -> Self {
+        let mut segments = vec![];
+        let mut literal = String::new();
+        let mut slots = vec![];
+        let mut chars = pattern.chars();
+        while let Some(c) = chars.next() {
+            let class = match c {
+                '\\' => None,
+                c => MaskClass::from_mask_char(c),
+            };
+            if let Some(class) = class {
+                slots.push(class);
+                continue;
+            }
+            if !slots.is_empty() {
+                let literal = std::mem::take(&mut literal);
+                let slots = std::mem::take(&mut slots);
+                segments.push(Segment { literal, slots });
+            }
+            match c {
+                '\\' => literal.extend(chars.next()),
+                c => literal.push(c),
+            }
+        }
+        if !slots.is_empty() {
+            let literal = std::mem::take(&mut literal);
+            segments.push(Segment { literal, slots });
+        }
+        Mask {
+            segments,
+            trailing: literal,
+            partial: false,
+        }
+    }
+
+    /// Construct a mask for IPv4 addresses
+    ///
+    /// This is `___.___.___.___` with partial segments allowed.
+    pub fn ipv4() -> Self {
+        Mask::new("___.___.___.___").with_partial_segments(true)
+    }
+
+    /// Allow partially-filled segments (chain style)
+    ///
+    /// If true, a segment is complete when it is not empty (e.g. `1.2.3.4` is
+    /// a complete IPv4 address); otherwise segments are only complete when
+    /// full. Default: false.
+    #[must_use]
+    pub fn with_partial_segments(mut self, partial: bool) -> Self {
+        self.partial = partial;
+        self
+    }
+
+    /// Get the number of segments
+    pub fn num_segments(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Split `text` into segment values
+    ///
+    /// The result has one entry per segment; literals are excluded.
+    pub fn segments(&self, text: &str) -> Vec<String> {
+        self.parse(text, 0).0
+    }
+
+    /// True if `text` completes the mask
+    pub fn is_complete(&self, text: &str) -> bool {
+        let segments = self.segments(text);
+        (self.segments.iter().zip(segments.iter())).all(|(seg, value)| {
+            let len = value.chars().count();
+            len == seg.slots.len() || (self.partial && len > 0)
+        })
+    }
+
+    /// Format `text` according to the mask
+    ///
+    /// Returns the formatted text and the new position of the `cursor`
+    /// (both byte indices). Characters not accepted by the mask are removed.
+    /// If `inserted`, the cursor is advanced over a literal following a
+    /// segment filled by the last input.
+    pub fn apply(&self, text: &str, cursor: usize, inserted: bool) -> (String, usize) {
+        let n = self.segments.len();
+        if n == 0 {
+            return (self.trailing.clone(), self.trailing.len());
+        }
+
+        let (contents, mut reached, (mut cur_seg, mut cur_len)) = self.parse(text, cursor);
+        if !inserted {
+            // Drop empty trailing segments, thus a literal may be deleted
+            while reached > 1 && contents[reached - 1].is_empty() {
+                reached -= 1;
+            }
+            if cur_seg >= reached.max(1) {
+                cur_seg = reached.max(1) - 1;
+                cur_len = contents[cur_seg].chars().count();
+            }
+        }
+        let full = |i: usize| contents[i].chars().count() == self.segments[i].slots.len();
+        let advance = inserted && cur_len == self.segments[cur_seg].slots.len();
+        if advance && cur_seg + 1 < n {
+            reached = reached.max(cur_seg + 2);
+        }
+
+        let mut s = String::new();
+        let mut pos = 0;
+        for (i, seg) in self.segments.iter().enumerate().take(reached) {
+            s.push_str(&seg.literal);
+            if i == cur_seg {
+                pos = s.len();
+                pos += contents[i]
+                    .chars()
+                    .take(cur_len)
+                    .map(char::len_utf8)
+                    .sum::<usize>();
+            } else if advance && i == cur_seg + 1 {
+                pos = s.len();
+            }
+            s.push_str(&contents[i]);
+        }
+        if reached == n && full(n - 1) {
+            s.push_str(&self.trailing);
+            if advance && cur_seg + 1 == n {
+                pos = s.len();
+            }
+        }
+        (s, pos)
+    }
+
+    // Returns segment contents, the number of segments reached and the
+    // cursor's position as (segment, number of chars)
+    fn parse(&self, text: &str, cursor: usize) -> (Vec<String>, usize, (usize, usize)) {
+        let n = self.segments.len();
+        let mut contents = vec![String::new(); n];
+        if n == 0 {
+            return (contents, 0, (0, 0));
+        }
+
+        let mut k = 0;
+        let mut reached = 0;
+        let mut cur = None;
+        for (i, c) in text.char_indices() {
+            if i == cursor {
+                cur = Some((k, contents[k].chars().count()));
+            }
+            reached = reached.max(k + 1);
+
+            let seg = &self.segments[k];
+            let len = contents[k].chars().count();
+            if len < seg.slots.len() && seg.slots[len].matches(c) {
+                contents[k].push(c);
+            } else if len == 0 && seg.literal.contains(c) {
+                // Part of this segment's literal
+            } else if k + 1 < n && (len == seg.slots.len() || (self.partial && len > 0)) {
+                let next = &self.segments[k + 1];
+                if next.literal.contains(c) {
+                    k += 1;
+                } else if len == seg.slots.len() && next.slots[0].matches(c) {
+                    k += 1;
+                    contents[k].push(c);
+                }
+                reached = reached.max(k + 1);
+            }
+        }
+        let cur = cur.unwrap_or_else(|| (k, contents[k].chars().count()));
+        (contents, reached, cur)
+    }
+}
+
+/// An [`EditGuard`] applying a [`Mask`]
+///
+/// The message emitted (on activation or loss of focus) is the text, if it
+/// completes the mask. Otherwise, the field's error state is set.
+#[derive(Clone, Debug)]
+pub struct MaskGuard {
+    mask: Mask,
+    last_len: usize,
+}
+
+impl MaskGuard {
+    /// Construct
+    pub fn new(mask: Mask) -> Self {
+        MaskGuard { mask, last_len: 0 }
+    }
+
+    /// Access the mask
+    pub fn mask(&self) -> &Mask {
+        &self.mask
+    }
+
+    fn validate(edit: &mut EditField<Self>) -> Option<String> {
+        let complete = edit.guard.mask.is_complete(edit.get_str());
+        edit.set_error_state(!complete);
+        complete.then(|| edit.get_str().to_string())
+    }
+}
+
+impl EditGuard for MaskGuard {
+    type Msg = String;
+
+    fn activate(edit: &mut EditField<Self>, mgr: &mut EventMgr) -> Option<Self::Msg> {
+        mgr.redraw(edit.id());
+        Self::validate(edit)
+    }
+
+    fn focus_lost(edit: &mut EditField<Self>, mgr: &mut EventMgr) -> Option<Self::Msg> {
+        mgr.redraw(edit.id());
+        Self::validate(edit)
+    }
+
+    fn edit(edit: &mut EditField<Self>, mgr: &mut EventMgr) -> Option<Self::Msg> {
+        let text = edit.get_str();
+        let inserted = text.len() > edit.guard.last_len;
+        let (string, pos) = edit.guard.mask.apply(text, edit.edit_pos(), inserted);
+        *mgr |= edit.set_string(string);
+        *mgr |= edit.set_edit_pos(pos);
+        edit.guard.last_len = edit.get_str().len();
+        edit.set_error_state(false);
+        None
+    }
+
+    fn update(edit: &mut EditField<Self>) {
+        edit.guard.last_len = edit.get_str().len();
+    }
+}
+
+widget! {
+    /// A text-edit box with an input mask
+    ///
+    /// Input is formatted according to a [`Mask`] while typing: characters not
+    /// accepted by the current slot are ignored and literals are inserted
+    /// automatically, thus the cursor advances between segments.
+    ///
+    /// The composed value is emitted as a message on activation (e.g. the
+    /// Enter key) or loss of focus, when it completes the mask; otherwise the
+    /// box shows an error state.
+    #[autoimpl(Deref, DerefMut, HasStr on self.inner)]
+    #[derive(Clone, Debug)]
+    #[handler(msg = String)]
+    #[widget{
+        layout = single;
+    }]
+    pub struct MaskedEdit {
+        #[widget_core]
+        core: CoreData,
+        #[widget]
+        inner: EditBox<MaskGuard>,
+    }
+
+    impl Self {
+        /// Construct with the given `mask`
+        pub fn new(mask: Mask) -> Self {
+            MaskedEdit {
+                core: Default::default(),
+                inner: EditBox::new("").with_guard(MaskGuard::new(mask)),
+            }
+        }
+
+        /// Access the mask
+        pub fn mask(&self) -> &Mask {
+            self.inner.guard.mask()
+        }
+
+        /// Get the values of each segment
+        pub fn segments(&self) -> Vec<String> {
+            self.mask().segments(self.inner.get_str())
+        }
+
+        /// True if the input completes the mask
+        pub fn is_complete(&self) -> bool {
+            self.mask().is_complete(self.inner.get_str())
+        }
+
+        /// Set the value
+        ///
+        /// The `value` is formatted according to the mask.
+        pub fn set_value(&mut self, value: &str) -> TkAction {
+            let (string, pos) = self.mask().apply(value, value.len(), false);
+            self.inner.set_string(string) | self.inner.set_edit_pos(pos)
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn phone() -> Mask {
+        Mask::new("+__ (___) ___-____")
+    }
+
+    #[test]
+    fn parse_phone() {
+        let mask = phone();
+        assert_eq!(mask.num_segments(), 4);
+        let full = "+12 (345) 678-9012";
+        assert_eq!(mask.segments(full), ["12", "345", "678", "9012"]);
+        assert!(mask.is_complete(full));
+        // Literals are optional in input
+        assert_eq!(mask.segments("123456789012"), ["12", "345", "678", "9012"]);
+        assert_eq!(mask.segments("+12 (3"), ["12", "3", "", ""]);
+        assert!(!mask.is_complete("+12 (3"));
+    }
+
+    #[test]
+    fn parse_ipv4() {
+        let mask = Mask::ipv4();
+        assert_eq!(mask.segments("192.168.0.1"), ["192", "168", "0", "1"]);
+        assert!(mask.is_complete("1.2.3.4"));
+        assert!(!mask.is_complete("1.2.3"));
+        // Without partial segments, all must be full
+        let strict = Mask::new("___.___.___.___");
+        assert!(!strict.is_complete("1.2.3.4"));
+        assert!(strict.is_complete("001.002.003.004"));
+    }
+
+    #[test]
+    fn insert_phone() {
+        let mask = phone();
+        assert_eq!(mask.apply("1", 1, true), ("+1".to_string(), 2));
+        // Filling a segment inserts the following literal
+        assert_eq!(mask.apply("+12", 3, true), ("+12 (".to_string(), 5));
+        // Typing over a missing literal moves to the next segment
+        assert_eq!(mask.apply("+123", 4, true), ("+12 (3".to_string(), 6));
+        assert_eq!(mask.apply("+12 (3", 6, true), ("+12 (3".to_string(), 6));
+        // Characters not accepted are removed
+        assert_eq!(mask.apply("+12 (3x", 7, true), ("+12 (3".to_string(), 6));
+        let full = "+12 (345) 678-9012";
+        assert_eq!(mask.apply(full, 18, true), (full.to_string(), 18));
+    }
+
+    #[test]
+    fn delete_phone() {
+        let mask = phone();
+        // Deleting a literal deletes the whole literal
+        assert_eq!(mask.apply("+12 ", 4, false), ("+12".to_string(), 3));
+        // Trailing literals are dropped when not inserting
+        assert_eq!(mask.apply("+12 (", 5, false), ("+12".to_string(), 3));
+        assert_eq!(mask.apply("+12 (3", 6, false), ("+12 (3".to_string(), 6));
+        assert_eq!(mask.apply("+1", 2, false), ("+1".to_string(), 2));
+    }
+
+    #[test]
+    fn insert_ipv4() {
+        let mask = Mask::ipv4();
+        assert_eq!(mask.apply("192", 3, true), ("192.".to_string(), 4));
+        assert_eq!(mask.apply("1.2.3.4", 7, true), ("1.2.3.4".to_string(), 7));
+        // A literal completes a partial segment; a second is ignored
+        assert_eq!(mask.apply("1.", 2, true), ("1.".to_string(), 2));
+        assert_eq!(mask.apply("1..", 3, true), ("1.".to_string(), 2));
+        // Insertion within a segment keeps the cursor there
+        assert_eq!(mask.apply("12.3", 2, true), ("12.3".to_string(), 2));
+        // ... unless the segment is filled
+        assert_eq!(mask.apply("125.3", 3, true), ("125.3".to_string(), 4));
+    }
+
+    #[test]
+    fn set_value() {
+        let mut edit = MaskedEdit::new(phone());
+        let _ = edit.set_value("123456789012");
+        assert_eq!(edit.get_str(), "+12 (345) 678-9012");
+        assert_eq!(edit.edit_pos(), 18);
+        assert!(edit.is_complete());
+
+        // Invalid characters are dropped
+        let _ = edit.set_value("ab12x3");
+        assert_eq!(edit.get_str(), "+12 (3");
+        assert_eq!(edit.edit_pos(), 6);
+        assert_eq!(edit.segments(), ["12", "3", "", ""]);
+        assert!(!edit.is_complete());
+
+        let _ = edit.set_value("abc");
+        assert_eq!(edit.get_str(), "");
+        assert_eq!(edit.edit_pos(), 0);
+    }
+}
@@ -7,10 +7,12 @@
 
 use std::ops::{Deref, DerefMut};
 
+mod context_menu;
 mod menu_entry;
 mod menubar;
 mod submenu;
 
+pub use context_menu::ContextMenu;
 pub use menu_entry::{MenuEntry, MenuToggle};
 pub use menubar::MenuBar;
 pub use submenu::SubMenu;
@@ -102,9 +104,27 @@ impl<M: 'static> WidgetConfig for Box<dyn Menu<Msg = M>> {
     fn hover_highlight(&self) -> bool {
         self.as_ref().hover_highlight()
     }
+    fn hover_events(&self) -> bool {
+        self.as_ref().hover_events()
+    }
     fn cursor_icon(&self) -> event::CursorIcon {
         self.as_ref().cursor_icon()
     }
+    fn value_description(&self) -> Option<String> {
+        self.as_ref().value_description()
+    }
+}
+
+impl<M: 'static> SaveState for Box<dyn Menu<Msg = M>> {
+    fn save_state(&self) -> Option<StateValue> {
+        self.as_ref().save_state()
+    }
+    fn restore_state(&mut self, state: &StateValue) -> TkAction {
+        self.as_mut().restore_state(state)
+    }
+    fn saves_children(&self) -> bool {
+        self.as_ref().saves_children()
+    }
 }
 
 impl<M: 'static> Layout for Box<dyn Menu<Msg = M>> {
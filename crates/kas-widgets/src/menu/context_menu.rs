@@ -0,0 +1,155 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Context menu
+
+use super::Menu;
+use crate::Column;
+use kas::event::{self, MouseButton, PressSource};
+use kas::prelude::*;
+use kas::{layout, WindowId};
+
+fn is_secondary(source: PressSource) -> bool {
+    matches!(source, PressSource::Mouse(MouseButton::Right, _))
+}
+
+widget! {
+    /// A widget with a context menu
+    ///
+    /// The menu is opened by a secondary (right) mouse-button press or a touch
+    /// long press ([`Event::LongPress`]) over the `inner` widget, where this
+    /// is not used by `inner` itself. Messages from menu entries are passed
+    /// on like those from `inner`.
+    #[autoimpl(Deref, DerefMut on self.inner)]
+    #[derive(Clone, Debug)]
+    pub struct ContextMenu<W: Widget, M: Menu<Msg = <W as Handler>::Msg>> {
+        #[widget_core]
+        core: CoreData,
+        #[widget]
+        pub inner: W,
+        #[widget]
+        pub list: Column<M>,
+        popup_id: Option<WindowId>,
+    }
+
+    impl Self {
+        /// Construct
+        #[inline]
+        pub fn new(inner: W, list: Vec<M>) -> Self {
+            ContextMenu {
+                core: Default::default(),
+                inner,
+                list: Column::new(list),
+                popup_id: None,
+            }
+        }
+
+        /// True if the menu is open
+        #[inline]
+        pub fn menu_is_open(&self) -> bool {
+            self.popup_id.is_some()
+        }
+
+        fn open_menu(&mut self, mgr: &mut EventMgr) {
+            if self.popup_id.is_none() {
+                self.popup_id = mgr.add_popup(kas::Popup {
+                    id: self.list.id(),
+                    parent: self.id(),
+                    direction: Direction::Down,
+                });
+            }
+        }
+        fn close_menu(&mut self, mgr: &mut EventMgr) {
+            if let Some(id) = self.popup_id {
+                mgr.close_window(id, true);
+            }
+        }
+    }
+
+    impl Layout for Self {
+        fn layout(&mut self) -> layout::Layout<'_> {
+            layout::Layout::single(&mut self.inner)
+        }
+    }
+
+    impl event::Handler for Self {
+        type Msg = <W as Handler>::Msg;
+
+        fn handle(&mut self, mgr: &mut EventMgr, event: Event) -> Response<Self::Msg> {
+            match event {
+                Event::PressStart {
+                    source,
+                    start_id,
+                    coord,
+                } => match start_id {
+                    Some(id) if self.list.is_ancestor_of(&id) => {
+                        // Press within the open menu
+                        if source.is_primary() {
+                            mgr.grab_press_unique(self.id(), source, coord, None);
+                            mgr.set_grab_depress(source, Some(id));
+                        }
+                        Response::Used
+                    }
+                    Some(id) if is_secondary(source) && self.inner.is_ancestor_of(&id) => {
+                        self.open_menu(mgr);
+                        Response::Used
+                    }
+                    _ => Response::Unused,
+                },
+                Event::LongPress(_) => {
+                    self.open_menu(mgr);
+                    Response::Used
+                }
+                Event::PressMove { source, cur_id, .. } => {
+                    let cond = cur_id.filter(|id| self.list.is_strict_ancestor_of(id));
+                    mgr.set_grab_depress(source, cond);
+                    Response::Used
+                }
+                Event::PressEnd { end_id, success, .. } => {
+                    match end_id.filter(|id| self.list.is_strict_ancestor_of(id)) {
+                        Some(id) if success => self.send(mgr, id, Event::Activate),
+                        _ => Response::Used,
+                    }
+                }
+                Event::PopupRemoved(id) => {
+                    debug_assert_eq!(Some(id), self.popup_id);
+                    self.popup_id = None;
+                    Response::Used
+                }
+                _ => Response::Unused,
+            }
+        }
+    }
+
+    impl event::SendEvent for Self {
+        fn send(&mut self, mgr: &mut EventMgr, id: WidgetId, event: Event) -> Response<Self::Msg> {
+            if self.is_disabled() {
+                return Response::Unused;
+            }
+
+            if self.eq_id(&id) {
+                self.handle(mgr, event)
+            } else if self.list.is_ancestor_of(&id) {
+                match self.list.send(mgr, id, event) {
+                    r @ (Response::Update | Response::Msg(_)) => {
+                        self.close_menu(mgr);
+                        r
+                    }
+                    r => r,
+                }
+            } else {
+                let opens = match event {
+                    Event::LongPress(_) => true,
+                    Event::PressStart { source, .. } => is_secondary(source),
+                    _ => false,
+                };
+                match self.inner.send(mgr, id, event.clone()) {
+                    Response::Unused if opens => self.handle(mgr, event),
+                    r => r,
+                }
+            }
+        }
+    }
+}
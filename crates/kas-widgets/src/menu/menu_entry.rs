@@ -6,26 +6,31 @@
 //! Menu Entries
 
 use super::Menu;
-use crate::{AccelLabel, CheckBoxBare};
-use kas::theme::TextClass;
+use crate::{AccelLabel, CheckBoxBare, Icon};
+use kas::theme::{IconName, TextClass};
 use kas::{layout, prelude::*};
 use std::fmt::Debug;
 
 widget! {
     /// A standard menu entry
+    ///
+    /// Optionally, an [`Icon`] may be shown before the label.
     #[derive(Clone, Debug, Default)]
     pub struct MenuEntry<M: Clone + Debug + 'static> {
         #[widget_core]
         core: kas::CoreData,
+        #[widget]
+        icon: Icon,
         label: Text<AccelString>,
         layout_label: layout::TextStorage,
+        layout_list: layout::FixedRowStorage<2>,
         layout_frame: layout::FrameStorage,
         msg: M,
     }
 
     impl WidgetConfig for Self {
         fn configure(&mut self, mgr: &mut SetRectMgr) {
-            mgr.add_accel_keys(self.id_ref(), self.label.text().keys());
+            mgr.add_accel_string(self.id_ref(), self.label.text());
         }
 
         fn key_nav(&self) -> bool {
@@ -35,13 +40,22 @@ widget! {
 
     impl Layout for Self {
         fn layout(&mut self) -> layout::Layout<'_> {
-            let inner = layout::Layout::text(&mut self.layout_label, &mut self.label, TextClass::MenuLabel);
+            let text = layout::Layout::text(&mut self.layout_label, &mut self.label, TextClass::MenuLabel);
+            let inner = if self.icon.is_empty() {
+                text
+            } else {
+                let icon = layout::Layout::single(&mut self.icon);
+                layout::Layout::list([icon, text].into_iter(), Direction::Right, &mut self.layout_list)
+            };
             layout::Layout::frame(&mut self.layout_frame, inner)
         }
 
         fn draw(&mut self, mut draw: DrawMgr) {
             let mut draw = draw.with_core(self.core_data());
             draw.menu_entry(self.core.rect);
+            if !self.icon.is_empty() {
+                self.icon.draw(draw.re());
+            }
             draw.text_accel(
                 self.layout_label.pos,
                 &self.label,
@@ -60,13 +74,29 @@ widget! {
         pub fn new<S: Into<AccelString>>(label: S, msg: M) -> Self {
             MenuEntry {
                 core: Default::default(),
+                icon: Default::default(),
                 label: Text::new_single(label.into()),
                 layout_label: Default::default(),
+                layout_list: Default::default(),
                 layout_frame: Default::default(),
                 msg,
             }
         }
 
+        /// Set a named icon (chain style)
+        ///
+        /// See [`kas::theme::icons`].
+        #[must_use]
+        pub fn with_named_icon<N: Into<IconName>>(mut self, name: N) -> Self {
+            let _ = self.icon.set_name(Some(name.into()));
+            self
+        }
+
+        /// Set or clear the named icon
+        pub fn set_named_icon(&mut self, name: Option<IconName>) -> TkAction {
+            self.icon.set_name(name)
+        }
+
         /// Replace the message value
         pub fn set_msg(&mut self, msg: M) {
             self.msg = msg;
@@ -121,7 +151,7 @@ widget! {
 
     impl WidgetConfig for Self {
         fn configure(&mut self, mgr: &mut SetRectMgr) {
-            mgr.add_accel_keys(self.checkbox.id_ref(), self.label.keys());
+            mgr.add_accel_string(self.checkbox.id_ref(), self.label.accel_string());
         }
     }
 
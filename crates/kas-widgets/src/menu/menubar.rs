@@ -8,6 +8,7 @@
 use super::{Menu, SubMenu};
 use crate::IndexedList;
 use kas::event::{self, Command};
+use kas::layout::solve_size_rules;
 use kas::prelude::*;
 
 widget! {
@@ -15,15 +16,24 @@ widget! {
     ///
     /// This widget houses a sequence of menu buttons, allowing input actions across
     /// menus.
+    ///
+    /// When there is insufficient space to show all menus, trailing menus are
+    /// moved into an overflow ("more") menu, shown at the end of the bar. This
+    /// is re-evaluated whenever the bar is resized. Note that [`MenuBar::bar`]
+    /// contains only those menus currently shown on the bar.
     #[derive(Clone, Debug)]
-    #[widget{
-        layout = single;
-    }]
     pub struct MenuBar<W: Menu, D: Directional = kas::dir::Right> {
         #[widget_core]
         core: CoreData,
         #[widget]
         pub bar: IndexedList<D, SubMenu<D::Flipped, W>>,
+        #[widget]
+        more: SubMenu<D::Flipped, SubMenu<D::Flipped, W>>,
+        // Size along the main axis of each menu (on bar, then under more)
+        widths: Vec<i32>,
+        more_width: i32,
+        // Set when overflow should be re-evaluated once menus are closed
+        relayout: bool,
         // Open mode. Used to close with click on root only when previously open.
         opening: bool,
         delayed_open: Option<WidgetId>,
@@ -46,13 +56,168 @@ widget! {
             for menu in menus.iter_mut() {
                 menu.key_nav = false;
             }
+            let mut more = SubMenu::new_with_direction(direction.flipped(), "»", vec![]);
+            more.key_nav = false;
             MenuBar {
                 core: Default::default(),
                 bar: IndexedList::new_with_direction(direction, menus),
+                more,
+                widths: vec![],
+                more_width: 0,
+                relayout: false,
                 opening: false,
                 delayed_open: None,
             }
         }
+
+        /// True if some menus have been moved to the overflow menu
+        #[inline]
+        pub fn is_overflowing(&self) -> bool {
+            !self.more.list.is_empty()
+        }
+
+        // Number of menus shown on the bar, including the overflow menu
+        fn num_menus(&self) -> usize {
+            self.bar.len() + usize::from(self.is_overflowing())
+        }
+
+        fn menu_mut(&mut self, index: usize) -> &mut dyn Menu<Msg = W::Msg> {
+            if index < self.bar.len() {
+                &mut self.bar[index]
+            } else {
+                &mut self.more
+            }
+        }
+
+        fn any_menu_open(&mut self) -> bool {
+            (0..self.num_menus()).any(|i| self.menu_mut(i).menu_is_open())
+        }
+
+        // Move menus between the bar and the overflow menu such that the first
+        // `n` menus are on the bar, then reconfigure and re-solve sizes.
+        fn set_num_visible(&mut self, mgr: &mut SetRectMgr, n: usize) {
+            while self.bar.len() > n {
+                let mut menu = self.bar.pop().0.unwrap();
+                menu.key_nav = true;
+                menu.direction_override = Some(Direction::Right);
+                let _ = self.more.list.insert(0, menu);
+            }
+            while self.bar.len() < n {
+                let mut menu = self.more.list.remove(0).0;
+                menu.key_nav = false;
+                menu.direction_override = None;
+                let _ = self.bar.push(menu);
+            }
+
+            mgr.configure(self.bar.id(), &mut self.bar);
+            mgr.configure(self.more.id(), &mut self.more);
+            let size_mgr = mgr.size_mgr();
+            solve_size_rules(&mut self.bar, size_mgr.re(), None, None);
+            solve_size_rules(&mut self.more, size_mgr, None, None);
+        }
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
+            let bar_rules = self.bar.size_rules(size_mgr.re(), axis);
+            let more_rules = self.more.size_rules(size_mgr.re(), axis);
+            if axis.is_vertical() != self.bar.direction().is_vertical() {
+                return bar_rules.max(more_rules);
+            }
+
+            fn menu_width(rules: SizeRules) -> i32 {
+                let margins = rules.margins_i32();
+                rules.ideal_size() + margins.0.max(margins.1)
+            }
+
+            self.widths.clear();
+            for i in 0..self.bar.len() {
+                let rules = self.bar[i].size_rules(size_mgr.re(), axis);
+                self.widths.push(menu_width(rules));
+            }
+            for i in 0..self.more.list.len() {
+                let rules = self.more.list[i].size_rules(size_mgr.re(), axis);
+                self.widths.push(menu_width(rules));
+            }
+            self.more_width = menu_width(more_rules);
+
+            let ideal = self.widths.iter().sum::<i32>().max(bar_rules.ideal_size());
+            SizeRules::new(
+                more_rules.min_size(),
+                ideal,
+                bar_rules.margins(),
+                bar_rules.stretch(),
+            )
+        }
+
+        fn set_rect(&mut self, mgr: &mut SetRectMgr, rect: Rect, align: AlignHints) {
+            self.core.rect = rect;
+            let dir = self.bar.direction();
+            let avail = rect.size.extract(dir);
+
+            let mut n = self.widths.len();
+            if self.widths.iter().sum::<i32>() > avail {
+                let mut used = self.more_width;
+                n = self
+                    .widths
+                    .iter()
+                    .take_while(|w| {
+                        used += **w;
+                        used <= avail
+                    })
+                    .count();
+            }
+            if n != self.bar.len() {
+                if self.any_menu_open() {
+                    // Moving menus would invalidate identifiers of open menus
+                    self.relayout = true;
+                } else {
+                    self.set_num_visible(mgr, n);
+                }
+            }
+
+            if !self.is_overflowing() {
+                self.bar.set_rect(mgr, rect, align);
+                return;
+            }
+
+            let more_len = self.more_width.min(avail);
+            let offset = |len| match dir.is_vertical() {
+                false => Offset(len, 0),
+                true => Offset(0, len),
+            };
+            let mut bar_rect = rect;
+            bar_rect.size.set_component(dir, avail - more_len);
+            let mut more_rect = rect;
+            more_rect.size.set_component(dir, more_len);
+            if dir.is_reversed() {
+                bar_rect.pos += offset(more_len);
+            } else {
+                more_rect.pos += offset(avail - more_len);
+            }
+            self.bar.set_rect(mgr, bar_rect, align);
+            self.more.set_rect(mgr, more_rect, align);
+        }
+
+        fn find_id(&mut self, coord: Coord) -> Option<WidgetId> {
+            if !self.rect().contains(coord) {
+                return None;
+            }
+            let more = match self.is_overflowing() {
+                true => self.more.find_id(coord),
+                false => None,
+            };
+            more.or_else(|| self.bar.find_id(coord))
+                .or(Some(self.id()))
+        }
+
+        fn draw(&mut self, mut draw: DrawMgr) {
+            let mut draw = draw.with_core(self.core_data());
+            self.bar.draw(draw.re());
+            if self.is_overflowing() {
+                self.more.draw(draw.re());
+            }
+        }
     }
 
     impl<W: Menu<Msg = M>, D: Directional, M: 'static> event::Handler for MenuBar<W, D> {
@@ -79,11 +244,10 @@ widget! {
                             mgr.set_grab_depress(source, start_id.clone());
                             self.opening = false;
                             if self.rect().contains(coord) {
-                                if self
-                                    .bar
-                                    .iter()
-                                    .any(|w| w.eq_id(&start_id) && !w.menu_is_open())
-                                {
+                                if (0..self.num_menus()).any(|i| {
+                                    let w = self.menu_mut(i);
+                                    w.eq_id(&start_id) && !w.menu_is_open()
+                                }) {
                                     self.opening = true;
                                     self.set_menu_path(mgr, start_id.as_ref(), false);
                                 } else {
@@ -109,7 +273,7 @@ widget! {
                 } => {
                     mgr.set_grab_depress(source, cur_id.clone());
                     if let Some(id) = cur_id {
-                        if self.bar.is_strict_ancestor_of(&id) {
+                        if self.bar.is_strict_ancestor_of(&id) || self.more.is_ancestor_of(&id) {
                             // We instantly open a sub-menu on motion over the bar,
                             // but delay when over a sub-menu (most intuitive?)
                             if self.rect().contains(coord) && !self.bar.eq_id(&id) {
@@ -133,9 +297,10 @@ widget! {
                             // end coordinate is on the menubar
                             if !self.opening {
                                 self.delayed_open = None;
-                                for i in 0..self.bar.len() {
-                                    if self.bar[i].eq_id(&id) {
-                                        self.bar[i].set_menu_path(mgr, None, false);
+                                for i in 0..self.num_menus() {
+                                    let w = self.menu_mut(i);
+                                    if w.eq_id(&id) {
+                                        w.set_menu_path(mgr, None, false);
                                     }
                                 }
                             }
@@ -159,13 +324,14 @@ widget! {
                     let reverse = self.bar.direction().is_reversed() ^ matches!(cmd, Left | Up);
                     match cmd.as_direction().map(|d| d.is_vertical()) {
                         Some(v) if v == is_vert => {
-                            for i in 0..self.bar.len() {
-                                if self.bar[i].menu_is_open() {
+                            let len = self.num_menus();
+                            for i in 0..len {
+                                if self.menu_mut(i).menu_is_open() {
                                     let mut j = isize::conv(i);
                                     j = if reverse { j - 1 } else { j + 1 };
-                                    j = j.rem_euclid(self.bar.len().cast());
-                                    self.bar[i].set_menu_path(mgr, None, true);
-                                    let w = &mut self.bar[usize::conv(j)];
+                                    j = j.rem_euclid(len.cast());
+                                    self.menu_mut(i).set_menu_path(mgr, None, true);
+                                    let w = self.menu_mut(usize::conv(j));
                                     w.set_menu_path(mgr, Some(&w.id()), true);
                                     break;
                                 }
@@ -193,9 +359,15 @@ widget! {
             if self.eq_id(&id) {
                 self.handle(mgr, event)
             } else {
-                match self.bar.send(mgr, id.clone(), event.clone()) {
+                let r = if self.more.is_ancestor_of(&id) {
+                    self.more.send(mgr, id.clone(), event.clone())
+                } else {
+                    let r = self.bar.send(mgr, id.clone(), event.clone());
+                    r.try_into().unwrap_or_else(|(_, msg)| Response::Msg(msg))
+                };
+                match r {
                     Response::Unused => self.handle(mgr, event),
-                    r => r.try_into().unwrap_or_else(|(_, msg)| {
+                    r => r.try_into().unwrap_or_else(|msg| {
                         log::trace!(
                             "Received by {} from {}: {:?}",
                             self.id(),
@@ -216,6 +388,11 @@ widget! {
             for i in 0..self.bar.len() {
                 self.bar[i].set_menu_path(mgr, target, set_focus);
             }
+            self.more.set_menu_path(mgr, target, set_focus);
+            if target.is_none() && self.relayout {
+                self.relayout = false;
+                mgr.send_action(TkAction::RESIZE);
+            }
         }
     }
 }
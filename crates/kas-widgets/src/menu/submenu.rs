@@ -19,6 +19,8 @@ widget! {
         #[widget_core]
         core: CoreData,
         direction: D,
+        // Used by MenuBar when this menu is moved into the overflow menu
+        pub(crate) direction_override: Option<Direction>,
         pub(crate) key_nav: bool,
         label: Text<AccelString>,
         label_store: layout::TextStorage,
@@ -62,6 +64,7 @@ widget! {
             SubMenu {
                 core: Default::default(),
                 direction,
+                direction_override: None,
                 key_nav: true,
                 label: Text::new_single(label.into()),
                 label_store: Default::default(),
@@ -71,12 +74,16 @@ widget! {
             }
         }
 
+        fn menu_direction(&self) -> Direction {
+            self.direction_override.unwrap_or_else(|| self.direction.as_direction())
+        }
+
         fn open_menu(&mut self, mgr: &mut EventMgr, set_focus: bool) {
             if self.popup_id.is_none() {
                 self.popup_id = mgr.add_popup(kas::Popup {
                     id: self.list.id(),
                     parent: self.id(),
-                    direction: self.direction.as_direction(),
+                    direction: self.menu_direction(),
                 });
                 if set_focus {
                     mgr.next_nav_focus(self, false, true);
@@ -96,7 +103,7 @@ widget! {
                         let rev = dir.is_reversed() ^ self.list.direction().is_reversed();
                         mgr.next_nav_focus(self, rev, true);
                         Response::Used
-                    } else if dir == self.direction.as_direction().reversed() {
+                    } else if dir == self.menu_direction().reversed() {
                         self.close_menu(mgr, true);
                         Response::Used
                     } else {
@@ -110,7 +117,7 @@ widget! {
                 } else {
                     Response::Unused
                 }
-            } else if Some(self.direction.as_direction()) == cmd.as_direction() {
+            } else if Some(self.menu_direction()) == cmd.as_direction() {
                 self.open_menu(mgr, true);
                 Response::Used
             } else {
@@ -122,7 +129,7 @@ widget! {
     impl WidgetConfig for Self {
         fn pre_configure(&mut self, mgr: &mut SetRectMgr, id: WidgetId) {
             self.core_data_mut().id = id;
-            mgr.add_accel_keys(self.id_ref(), self.label.text().keys());
+            mgr.add_accel_string(self.id_ref(), self.label.text());
             mgr.new_accel_layer(self.id(), true);
         }
 
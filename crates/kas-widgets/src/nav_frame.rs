@@ -12,6 +12,10 @@ widget! {
     ///
     /// This widget is a wrapper that can be used to make a static widget such as a
     /// `Label` navigable with the keyboard.
+    ///
+    /// When focussed, a focus ring is drawn; its placement, thickness and
+    /// whether it is shown for mouse-initiated focus are configured by the
+    /// theme (e.g. `kas_theme::FocusRingConfig`).
     #[autoimpl(Deref, DerefMut on self.inner)]
     #[autoimpl(class_traits where W: trait on self.inner)]
     #[derive(Clone, Debug, Default)]
@@ -0,0 +1,175 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Page navigation control
+
+use crate::{EditBox, EditField, EditGuard, Label, Row, StrLabel, TextButton};
+use kas::prelude::*;
+
+#[derive(Clone, Copy, Debug)]
+enum PageNav {
+    Prev,
+    Next,
+    /// Page-number button, by index (not page)
+    Button(usize),
+}
+
+/// Guard for the jump-to-page box: emits the page number (from 1)
+#[derive(Clone, Debug, Default)]
+struct JumpGuard;
+
+impl EditGuard for JumpGuard {
+    type Msg = usize;
+
+    fn activate(edit: &mut EditField<Self>, mgr: &mut EventMgr) -> Option<Self::Msg> {
+        let number = edit.get_str().trim().parse::<usize>().ok();
+        let number = number.filter(|n| *n > 0);
+        edit.set_error_state(number.is_none());
+        mgr.redraw(edit.id());
+        number
+    }
+}
+
+widget! {
+    /// A page navigation control
+    ///
+    /// This shows "previous" and "next" buttons, buttons for a range of page
+    /// numbers around the current page and a box to jump to a page by number.
+    /// Pages are numbered from 1 for display, but indexed from 0 in this API.
+    ///
+    /// When the user selects a page, the paginator updates itself and emits the
+    /// page index as a message. For example, this may be passed to
+    /// [`crate::view::ListView::set_page`].
+    #[derive(Clone, Debug)]
+    #[handler(msg = usize)]
+    #[widget{
+        layout = row: [self.prev, self.buttons, self.next, self.label, self.jump];
+    }]
+    pub struct Paginator {
+        #[widget_core]
+        core: CoreData,
+        #[widget(flatmap_msg = navigate)]
+        prev: TextButton<PageNav>,
+        #[widget(flatmap_msg = navigate)]
+        buttons: Row<TextButton<PageNav>>,
+        #[widget(flatmap_msg = navigate)]
+        next: TextButton<PageNav>,
+        #[widget]
+        label: StrLabel,
+        #[widget(flatmap_msg = jump)]
+        jump: EditBox<JumpGuard>,
+        page: usize,
+        num_pages: usize,
+        max_buttons: usize,
+    }
+
+    impl Self {
+        /// Construct with the given number of pages
+        ///
+        /// The first page is selected initially.
+        pub fn new(num_pages: usize) -> Self {
+            let mut paginator = Paginator {
+                core: Default::default(),
+                prev: TextButton::new_msg("‹", PageNav::Prev),
+                buttons: Row::new(vec![]),
+                next: TextButton::new_msg("›", PageNav::Next),
+                label: Label::new("Go to:"),
+                jump: EditBox::new("").with_guard(JumpGuard),
+                page: 0,
+                num_pages,
+                max_buttons: 7,
+            };
+            let _ = paginator.update_buttons();
+            paginator
+        }
+
+        /// Set the maximum number of page-number buttons shown (inline)
+        ///
+        /// Default: 7.
+        #[must_use]
+        pub fn with_max_buttons(mut self, max_buttons: usize) -> Self {
+            self.max_buttons = max_buttons;
+            let _ = self.update_buttons();
+            self
+        }
+
+        /// Get the index of the current page
+        #[inline]
+        pub fn page(&self) -> usize {
+            self.page
+        }
+
+        /// Set the current page
+        ///
+        /// The `page` is clamped to the number of pages. No message is emitted.
+        pub fn set_page(&mut self, page: usize) -> TkAction {
+            self.page = page.min(self.num_pages.saturating_sub(1));
+            self.update_buttons()
+        }
+
+        /// Get the number of pages
+        #[inline]
+        pub fn num_pages(&self) -> usize {
+            self.num_pages
+        }
+
+        /// Set the number of pages
+        ///
+        /// The current page is clamped to the new number of pages.
+        pub fn set_num_pages(&mut self, num_pages: usize) -> TkAction {
+            self.num_pages = num_pages;
+            self.set_page(self.page)
+        }
+
+        /// Index of the page shown by the first page-number button
+        fn first_shown(&self) -> usize {
+            let count = self.max_buttons.min(self.num_pages);
+            let first = self.page.saturating_sub(count / 2);
+            first.min(self.num_pages - count)
+        }
+
+        fn update_buttons(&mut self) -> TkAction {
+            let count = self.max_buttons.min(self.num_pages);
+            let mut action = TkAction::empty();
+            if self.buttons.len() != count {
+                action |= self
+                    .buttons
+                    .resize_with(count, |i| TextButton::new_msg("", PageNav::Button(i)));
+            }
+            let first = self.first_shown();
+            for (i, button) in self.buttons.iter_mut().enumerate() {
+                let page = first + i;
+                action |= button.set_accel((page + 1).to_string());
+                action |= button.set_disabled(page == self.page);
+            }
+            action |= self.prev.set_disabled(self.page == 0);
+            action | self.next.set_disabled(self.page + 1 >= self.num_pages)
+        }
+
+        fn select(&mut self, mgr: &mut EventMgr, page: Option<usize>) -> Response<usize> {
+            match page {
+                Some(page) if page < self.num_pages && page != self.page => {
+                    *mgr |= self.set_page(page);
+                    Response::Msg(page)
+                }
+                _ => Response::Used,
+            }
+        }
+
+        fn navigate(&mut self, mgr: &mut EventMgr, nav: PageNav) -> Response<usize> {
+            let page = match nav {
+                PageNav::Prev => self.page.checked_sub(1),
+                PageNav::Next => Some(self.page + 1),
+                PageNav::Button(i) => Some(self.first_shown() + i),
+            };
+            self.select(mgr, page)
+        }
+
+        fn jump(&mut self, mgr: &mut EventMgr, number: usize) -> Response<usize> {
+            let page = (number - 1).min(self.num_pages.saturating_sub(1));
+            self.select(mgr, Some(page))
+        }
+    }
+}
@@ -7,12 +7,15 @@
 
 use std::fmt::Debug;
 
+use crate::slider::describe_fraction;
 use kas::prelude::*;
 
 widget! {
     /// A progress bar
     ///
-    /// The "progress" value may range from 0.0 to 1.0.
+    /// The "progress" value may range from 0.0 to 1.0. It is described as a
+    /// percentage (see [`WidgetConfig::value_description`] and
+    /// [`ProgressBar::with_value_label`]).
     #[derive(Clone, Debug, Default)]
     pub struct ProgressBar<D: Directional> {
         #[widget_core]
@@ -20,6 +23,7 @@ widget! {
         direction: D,
         width: i32,
         value: f32,
+        value_label: Option<String>,
     }
 
     impl Self where D: Default {
@@ -43,6 +47,7 @@ widget! {
                 direction,
                 width: 0,
                 value: 0.0,
+                value_label: None,
             }
         }
 
@@ -54,6 +59,16 @@ widget! {
             self
         }
 
+        /// Set a label for value descriptions (chain style)
+        ///
+        /// For example, with label "Download" the value is described as
+        /// "Download 75%".
+        #[must_use]
+        pub fn with_value_label<S: ToString>(mut self, label: S) -> Self {
+            self.value_label = Some(label.to_string());
+            self
+        }
+
         /// Get the current value
         #[inline]
         pub fn value(&self) -> f32 {
@@ -75,6 +90,13 @@ widget! {
         }
     }
 
+    impl WidgetConfig for Self {
+        fn value_description(&self) -> Option<String> {
+            let label = self.value_label.as_deref();
+            Some(describe_fraction(label, self.value.into()))
+        }
+    }
+
     impl Layout for Self {
         fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
             let mut size = size_mgr.progress_bar();
@@ -9,26 +9,111 @@ use super::AccelLabel;
 use kas::prelude::*;
 use kas::updatable::{SharedRc, SingleData, Updatable};
 use log::trace;
+use std::fmt::Debug;
 use std::rc::Rc;
 
-/// Type of radiobox group
-pub type RadioBoxGroup = SharedRc<WidgetId>;
+/// A group of radioboxes
+///
+/// Each [`RadioBox`] and [`RadioBoxBare`] constructed over a group is
+/// associated with a value of type `T`. The group holds the value of the
+/// selected radiobox, if any.
+///
+/// This type is a shared reference: clones refer to the same group.
+#[autoimpl(Default using self.0 = SharedRc::new(None))]
+#[derive(Clone, Debug)]
+pub struct RadioBoxGroup<T: Clone + Debug + PartialEq>(SharedRc<Option<T>>);
+
+impl<T: Clone + Debug + PartialEq> RadioBoxGroup<T> {
+    /// Construct a new group, with nothing selected
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the value of the selected radiobox, if any
+    #[inline]
+    pub fn value(&self) -> Option<T> {
+        self.0.get_cloned()
+    }
+
+    /// Set the selected value
+    ///
+    /// The radiobox associated with `value` (if any) is selected; all others
+    /// are deselected. To update widgets, the returned [`UpdateHandle`] should
+    /// be passed to [`EventMgr::trigger_update`].
+    #[inline]
+    pub fn set_value(&self, value: Option<T>) -> Option<UpdateHandle> {
+        self.0.update(value)
+    }
+}
+
+impl<T: Clone + Debug + PartialEq> Updatable for RadioBoxGroup<T> {
+    fn update_handle(&self) -> Option<UpdateHandle> {
+        self.0.update_handle()
+    }
+}
+
+impl<T: Clone + Debug + PartialEq> SingleData for RadioBoxGroup<T> {
+    type Item = Option<T>;
+
+    fn version(&self) -> u64 {
+        self.0.version()
+    }
+
+    fn get_cloned(&self) -> Self::Item {
+        self.0.get_cloned()
+    }
+
+    fn update(&self, value: Self::Item) -> Option<UpdateHandle> {
+        self.0.update(value)
+    }
+}
+
+// Type-erased binding of a radiobox to its group
+trait GroupEntry: Debug {
+    fn update_handle(&self) -> Option<UpdateHandle>;
+    fn is_selected(&self) -> bool;
+    fn select(&self) -> Option<UpdateHandle>;
+}
+
+#[derive(Debug)]
+struct Entry<T: Clone + Debug + PartialEq> {
+    group: RadioBoxGroup<T>,
+    value: T,
+}
+
+impl<T: Clone + Debug + PartialEq> GroupEntry for Entry<T> {
+    fn update_handle(&self) -> Option<UpdateHandle> {
+        self.group.update_handle()
+    }
+
+    fn is_selected(&self) -> bool {
+        self.group.0.get_cloned().as_ref() == Some(&self.value)
+    }
+
+    fn select(&self) -> Option<UpdateHandle> {
+        self.group.set_value(Some(self.value.clone()))
+    }
+}
 
 widget! {
     /// A bare radiobox (no label)
+    ///
+    /// The radiobox is associated with a value within a [`RadioBoxGroup`]; it is
+    /// selected when the group's value equals its own.
     #[autoimpl(Debug skip self.on_select)]
     #[derive(Clone)]
     pub struct RadioBoxBare<M: 'static> {
         #[widget_core]
         core: CoreData,
         state: bool,
-        group: SharedRc<WidgetId>,
+        entry: Rc<dyn GroupEntry>,
         on_select: Option<Rc<dyn Fn(&mut EventMgr) -> Option<M>>>,
     }
 
     impl WidgetConfig for Self {
         fn configure(&mut self, mgr: &mut SetRectMgr) {
-            if let Some(handle) = self.group.update_handle() {
+            if let Some(handle) = self.entry.update_handle() {
                 mgr.update_on_handle(handle, self.id());
             }
         }
@@ -56,7 +141,7 @@ widget! {
                         trace!("RadioBoxBare: set {}", self.id());
                         self.state = true;
                         mgr.redraw(self.id());
-                        if let Some(handle) = self.group.update(self.id()) {
+                        if let Some(handle) = self.entry.select() {
                             mgr.trigger_update(handle, 0);
                         }
                         Response::update_or_msg(self.on_select.as_ref().and_then(|f| f(mgr)))
@@ -65,9 +150,10 @@ widget! {
                     }
                 }
                 Event::HandleUpdate { .. } => {
-                    if self.state && !self.eq_id(self.group.get_cloned()) {
-                        trace!("RadioBoxBare: unset {}", self.id());
-                        self.state = false;
+                    let state = self.entry.is_selected();
+                    if state != self.state {
+                        trace!("RadioBoxBare: set {} to {}", self.id(), state);
+                        self.state = state;
                         mgr.redraw(self.id());
                         Response::Update
                     } else {
@@ -101,16 +187,21 @@ widget! {
     }
 
     impl RadioBoxBare<VoidMsg> {
-        /// Construct a radiobox
+        /// Construct a radiobox with given `group` and `value`
         ///
         /// All instances of [`RadioBoxBare`] and [`RadioBox`] constructed over the
-        /// same `group` will be considered part of a single group.
+        /// same `group` will be considered part of a single group. The radiobox
+        /// is initially selected if the group's value equals `value`.
         #[inline]
-        pub fn new(group: SharedRc<WidgetId>) -> Self {
+        pub fn new<T>(group: RadioBoxGroup<T>, value: T) -> Self
+        where
+            T: Clone + Debug + PartialEq + 'static,
+        {
+            let entry = Entry { group, value };
             RadioBoxBare {
                 core: Default::default(),
-                state: false,
-                group,
+                state: entry.is_selected(),
+                entry: Rc::new(entry),
                 on_select: None,
             }
         }
@@ -131,14 +222,14 @@ widget! {
             RadioBoxBare {
                 core: self.core,
                 state: self.state,
-                group: self.group,
+                entry: self.entry,
                 on_select: Some(Rc::new(f)),
             }
         }
     }
 
     impl Self {
-        /// Construct a radiobox with given `group` and event handler `f`
+        /// Construct a radiobox with given `group`, `value` and event handler `f`
         ///
         /// All instances of [`RadioBoxBare`] and [`RadioBox`] constructed over the
         /// same `group` will be considered part of a single group.
@@ -149,18 +240,25 @@ widget! {
         ///
         /// No handler is called on deselection, but [`Response::Update`] is returned.
         #[inline]
-        pub fn new_on<F>(group: SharedRc<WidgetId>, f: F) -> Self
+        pub fn new_on<T, F>(group: RadioBoxGroup<T>, value: T, f: F) -> Self
         where
+            T: Clone + Debug + PartialEq + 'static,
             F: Fn(&mut EventMgr) -> Option<M> + 'static,
         {
-            RadioBoxBare::new(group).on_select(f)
+            RadioBoxBare::new(group, value).on_select(f)
         }
 
         /// Set the initial state of the radiobox.
+        ///
+        /// If `state` is true, this also sets the value of the group. (Since
+        /// widgets are not yet configured, no update need be triggered.)
         #[inline]
         #[must_use]
         pub fn with_state(mut self, state: bool) -> Self {
             self.state = state;
+            if state {
+                let _ = self.entry.select();
+            }
             self
         }
     }
@@ -201,23 +299,27 @@ widget! {
 
     impl WidgetConfig for Self {
         fn configure(&mut self, mgr: &mut SetRectMgr) {
-            mgr.add_accel_keys(self.radiobox.id_ref(), self.label.keys());
+            mgr.add_accel_string(self.radiobox.id_ref(), self.label.accel_string());
         }
     }
 
     impl RadioBox<VoidMsg> {
-        /// Construct a radiobox with a given `label` and `group`
+        /// Construct a radiobox with a given `label`, `group` and `value`
         ///
         /// RadioBox labels are optional; if no label is desired, use an empty
         /// string.
         ///
         /// All instances of [`RadioBoxBare`] and [`RadioBox`] constructed over the
-        /// same `group` will be considered part of a single group.
+        /// same `group` will be considered part of a single group. The radiobox
+        /// is initially selected if the group's value equals `value`.
         #[inline]
-        pub fn new<T: Into<AccelString>>(label: T, group: SharedRc<WidgetId>) -> Self {
+        pub fn new<S: Into<AccelString>, T>(label: S, group: RadioBoxGroup<T>, value: T) -> Self
+        where
+            T: Clone + Debug + PartialEq + 'static,
+        {
             RadioBox {
                 core: Default::default(),
-                radiobox: RadioBoxBare::new(group),
+                radiobox: RadioBoxBare::new(group, value),
                 label: AccelLabel::new(label.into()),
             }
         }
@@ -244,7 +346,7 @@ widget! {
     }
 
     impl Self {
-        /// Construct a radiobox with given `label`, `group` and event handler `f`
+        /// Construct a radiobox with given `label`, `group`, `value` and event handler `f`
         ///
         /// RadioBox labels are optional; if no label is desired, use an empty
         /// string.
@@ -258,14 +360,20 @@ widget! {
         ///
         /// No handler is called on deselection, but [`Response::Update`] is returned.
         #[inline]
-        pub fn new_on<T: Into<AccelString>, F>(label: T, group: SharedRc<WidgetId>, f: F) -> Self
+        pub fn new_on<S: Into<AccelString>, T, F>(
+            label: S,
+            group: RadioBoxGroup<T>,
+            value: T,
+            f: F,
+        ) -> Self
         where
+            T: Clone + Debug + PartialEq + 'static,
             F: Fn(&mut EventMgr) -> Option<M> + 'static,
         {
-            RadioBox::new(label, group).on_select(f)
+            RadioBox::new(label, group, value).on_select(f)
         }
 
-        /// Construct a radiobox with given `label`, `group` and payload `msg`
+        /// Construct a radiobox with given `label`, `group` and `value`, emitting `value`
         ///
         /// RadioBox labels are optional; if no label is desired, use an empty
         /// string.
@@ -274,15 +382,16 @@ widget! {
         /// same `group` will be considered part of a single group.
         ///
         /// On selection (through user input events or [`Event::Activate`]) a clone
-        /// of `msg` is returned to the parent widget via [`Response::Msg`].
+        /// of `value` is returned to the parent widget via [`Response::Msg`].
         ///
         /// No handler is called on deselection, but [`Response::Update`] is returned.
         #[inline]
-        pub fn new_msg<S: Into<AccelString>>(label: S, group: SharedRc<WidgetId>, msg: M) -> Self
+        pub fn new_msg<S: Into<AccelString>>(label: S, group: RadioBoxGroup<M>, value: M) -> Self
         where
-            M: Clone,
+            M: Clone + Debug + PartialEq,
         {
-            Self::new_on(label, group, move |_| Some(msg.clone()))
+            let msg = value.clone();
+            Self::new_on(label, group, value, move |_| Some(msg.clone()))
         }
 
         /// Set the initial state of the radiobox.
@@ -0,0 +1,313 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Recently-used files
+
+use crate::menu::{Menu, MenuEntry, SubMenu};
+use kas::dir::Right;
+use kas::prelude::*;
+use kas::updatable::{ListData, Updatable, UpdatableHandler};
+use std::cell::RefCell;
+use std::fmt::Debug;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+#[derive(Debug)]
+struct Inner {
+    paths: Vec<PathBuf>,
+    limit: usize,
+    version: u64,
+    #[cfg(feature = "config")]
+    store: Option<PathBuf>,
+}
+
+impl Inner {
+    fn changed(&mut self) {
+        self.paths.truncate(self.limit);
+        self.version += 1;
+        self.save();
+    }
+
+    #[cfg(feature = "config")]
+    fn save(&self) {
+        if let Some(ref store) = self.store {
+            if let Err(e) = kas::config::Format::guess_and_write_path(store, &self.paths) {
+                log::warn!("RecentFiles: failed to write {}: {}", store.display(), e);
+            }
+        }
+    }
+
+    #[cfg(not(feature = "config"))]
+    fn save(&self) {}
+}
+
+/// A list of recently-used files
+///
+/// Paths are ordered most-recently-used first, and the list holds at most
+/// [`RecentFiles::limit`] entries. This is a shared data object: clones refer
+/// to the same list, and methods modifying the list return an
+/// [`UpdateHandle`] which should be passed to [`EventMgr::trigger_update`] to
+/// notify views.
+///
+/// The list may be persisted via the config system (see
+/// [`RecentFiles::with_store`]).
+///
+/// Views:
+///
+/// -   [`RecentFilesMenu`] is a sub-menu with an entry per file
+/// -   [`ListData`] is implemented (with key `usize` and item the displayed
+///     path), thus [`crate::view::ListView`] may be used; use
+///     [`RecentFiles::get`] to find the path of a selected entry
+#[derive(Clone, Debug)]
+pub struct RecentFiles(Rc<(UpdateHandle, RefCell<Inner>)>);
+
+impl RecentFiles {
+    /// Construct an empty list, holding at most `limit` paths
+    pub fn new(limit: usize) -> Self {
+        let inner = Inner {
+            paths: vec![],
+            limit,
+            version: 0,
+            #[cfg(feature = "config")]
+            store: None,
+        };
+        RecentFiles(Rc::new((UpdateHandle::new(), RefCell::new(inner))))
+    }
+
+    /// Persist the list to `path` (chain style)
+    ///
+    /// If `path` exists, the list is read from it immediately. Afterwards,
+    /// every change to the list is written to `path`. The format is guessed
+    /// from the path's extension (see [`kas::config::Format`]). Errors are
+    /// logged and otherwise ignored.
+    #[cfg(feature = "config")]
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "config")))]
+    #[must_use]
+    pub fn with_store<P: Into<PathBuf>>(self, path: P) -> Self {
+        let path = path.into();
+        {
+            let mut inner = (self.0).1.borrow_mut();
+            if path.is_file() {
+                match kas::config::Format::guess_and_read_path::<Vec<PathBuf>>(&path) {
+                    Ok(paths) => {
+                        inner.paths = paths;
+                        inner.paths.truncate(inner.limit);
+                        inner.version += 1;
+                    }
+                    Err(e) => log::warn!("RecentFiles: failed to read {}: {}", path.display(), e),
+                }
+            }
+            inner.store = Some(path);
+        }
+        self
+    }
+
+    /// Get the maximum number of paths
+    pub fn limit(&self) -> usize {
+        (self.0).1.borrow().limit
+    }
+
+    /// Set the maximum number of paths
+    ///
+    /// Excess paths (the least recently used) are removed.
+    pub fn set_limit(&self, limit: usize) -> UpdateHandle {
+        let mut inner = (self.0).1.borrow_mut();
+        inner.limit = limit;
+        inner.changed();
+        (self.0).0
+    }
+
+    /// True if the list is empty
+    pub fn is_empty(&self) -> bool {
+        (self.0).1.borrow().paths.is_empty()
+    }
+
+    /// Get a copy of all paths, most recent first
+    pub fn paths(&self) -> Vec<PathBuf> {
+        (self.0).1.borrow().paths.clone()
+    }
+
+    /// Get the path at `index` (where `0` is the most recent)
+    pub fn get(&self, index: usize) -> Option<PathBuf> {
+        (self.0).1.borrow().paths.get(index).cloned()
+    }
+
+    /// Add `path` as the most recently used
+    ///
+    /// If `path` is already present, it is moved to the front of the list.
+    pub fn add<P: Into<PathBuf>>(&self, path: P) -> UpdateHandle {
+        let path = path.into();
+        let mut inner = (self.0).1.borrow_mut();
+        inner.paths.retain(|p| *p != path);
+        inner.paths.insert(0, path);
+        inner.changed();
+        (self.0).0
+    }
+
+    /// Remove `path`, if present
+    ///
+    /// This may be used e.g. when opening a listed path fails.
+    pub fn remove(&self, path: &Path) -> Option<UpdateHandle> {
+        let mut inner = (self.0).1.borrow_mut();
+        let len = inner.paths.len();
+        inner.paths.retain(|p| p.as_path() != path);
+        if inner.paths.len() == len {
+            return None;
+        }
+        inner.changed();
+        Some((self.0).0)
+    }
+
+    /// Remove all paths
+    pub fn clear(&self) -> UpdateHandle {
+        let mut inner = (self.0).1.borrow_mut();
+        inner.paths.clear();
+        inner.changed();
+        (self.0).0
+    }
+}
+
+impl Updatable for RecentFiles {
+    fn update_handle(&self) -> Option<UpdateHandle> {
+        Some((self.0).0)
+    }
+}
+
+impl<K, M> UpdatableHandler<K, M> for RecentFiles {
+    fn handle(&self, _: &K, _: &M) -> Option<UpdateHandle> {
+        None
+    }
+}
+
+impl ListData for RecentFiles {
+    type Key = usize;
+    type Item = String;
+
+    fn version(&self) -> u64 {
+        (self.0).1.borrow().version
+    }
+
+    fn len(&self) -> usize {
+        (self.0).1.borrow().paths.len()
+    }
+
+    fn contains_key(&self, key: &Self::Key) -> bool {
+        *key < self.len()
+    }
+
+    fn get_cloned(&self, key: &Self::Key) -> Option<Self::Item> {
+        let inner = (self.0).1.borrow();
+        inner.paths.get(*key).map(|p| p.display().to_string())
+    }
+
+    fn update(&self, _: &Self::Key, _: Self::Item) -> Option<UpdateHandle> {
+        // Paths are not editable through views
+        None
+    }
+
+    fn iter_vec_from(&self, start: usize, limit: usize) -> Vec<(Self::Key, Self::Item)> {
+        let inner = (self.0).1.borrow();
+        (inner.paths.iter().enumerate())
+            .skip(start)
+            .take(limit)
+            .map(|(i, p)| (i, p.display().to_string()))
+            .collect()
+    }
+}
+
+widget! {
+    /// A sub-menu listing recently-used files
+    ///
+    /// Entries are rebuilt automatically when the [`RecentFiles`] list is
+    /// updated. Activating an entry emits the message constructed from its
+    /// path. The first nine entries are given accelerator keys `1`–`9`. The
+    /// menu is disabled while the list is empty.
+    #[autoimpl(Debug skip self.msg_fn)]
+    #[widget{
+        layout = single;
+    }]
+    pub struct RecentFilesMenu<M: Clone + Debug + 'static> {
+        #[widget_core]
+        core: CoreData,
+        #[widget]
+        menu: SubMenu<Right, MenuEntry<M>>,
+        files: RecentFiles,
+        msg_fn: Rc<dyn Fn(PathBuf) -> M>,
+    }
+
+    impl Self {
+        /// Construct, with a `label`, a list of `files` and a message
+        /// constructor `f`
+        ///
+        /// When an entry is activated, the message `f(path)` is emitted.
+        pub fn new<S, F>(label: S, files: RecentFiles, f: F) -> Self
+        where
+            S: Into<AccelString>,
+            F: Fn(PathBuf) -> M + 'static,
+        {
+            let mut menu = RecentFilesMenu {
+                core: Default::default(),
+                menu: SubMenu::right(label, vec![]),
+                files,
+                msg_fn: Rc::new(f),
+            };
+            let _ = menu.update_entries();
+            menu
+        }
+
+        /// Access the list of files
+        pub fn files(&self) -> &RecentFiles {
+            &self.files
+        }
+
+        fn update_entries(&mut self) -> TkAction {
+            let paths = self.files.paths();
+            let mut action = self.menu.set_disabled(paths.is_empty());
+            action |= self.menu.list.clear();
+            let entries = paths.into_iter().enumerate().map(|(i, path)| {
+                // Escape '&' which otherwise marks an accelerator key
+                let name = path.display().to_string().replace('&', "&&");
+                let label = match i {
+                    0..=8 => format!("&{} {}", i + 1, name),
+                    _ => name,
+                };
+                MenuEntry::new(label, (self.msg_fn)(path))
+            });
+            action | self.menu.list.extend(entries)
+        }
+    }
+
+    impl WidgetConfig for Self {
+        fn configure(&mut self, mgr: &mut SetRectMgr) {
+            if let Some(handle) = self.files.update_handle() {
+                mgr.update_on_handle(handle, self.id());
+            }
+        }
+    }
+
+    impl Handler for Self {
+        type Msg = M;
+
+        fn handle(&mut self, mgr: &mut EventMgr, event: Event) -> Response<M> {
+            match event {
+                Event::HandleUpdate { .. } => {
+                    *mgr |= self.update_entries();
+                    Response::Used
+                }
+                _ => Response::Unused,
+            }
+        }
+    }
+
+    impl Menu for Self {
+        fn menu_is_open(&self) -> bool {
+            self.menu.menu_is_open()
+        }
+
+        fn set_menu_path(&mut self, mgr: &mut EventMgr, target: Option<&WidgetId>, set_focus: bool) {
+            self.menu.set_menu_path(mgr, target, set_focus);
+        }
+    }
+}
@@ -0,0 +1,82 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Window resize grip
+
+use kas::event::{WindowEdge, WindowRegion};
+use kas::layout::Margins;
+use kas::prelude::*;
+
+widget! {
+    /// A window resize grip
+    ///
+    /// This is a small, square widget which should be placed in the
+    /// bottom-right corner of a window (e.g. at the end of a status bar).
+    /// Dragging the grip resizes the window (the grip acts as a
+    /// [`WindowRegion::Resize`] border) while double-clicking it resizes
+    /// the window to its content (see [`TkAction::SIZE_TO_CONTENT`]).
+    #[derive(Clone, Debug, Default)]
+    #[widget{
+        hover_highlight = true;
+    }]
+    pub struct ResizeGrip {
+        #[widget_core]
+        core: CoreData,
+    }
+
+    impl Self {
+        /// Construct
+        #[inline]
+        pub fn new() -> Self {
+            ResizeGrip {
+                core: Default::default(),
+            }
+        }
+    }
+
+    impl WidgetConfig for Self {
+        fn window_region(&self) -> WindowRegion {
+            WindowRegion::Resize(WindowEdge::SouthEast)
+        }
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
+            let side = size_mgr.scrollbar().0 .1;
+            SizeRules::extract_fixed(axis, Size::splat(side), Margins::ZERO)
+        }
+
+        fn draw(&mut self, mut draw: DrawMgr) {
+            let mut draw = draw.with_core(self.core_data());
+            // Six dots forming a triangle in the bottom-right corner
+            let rect = self.core.rect;
+            let dot = draw.size_mgr().separator().max(Size::splat(1));
+            let step = Size(rect.size.0 / 3, rect.size.1 / 3);
+            let corner = rect.pos + rect.size - step;
+            for (x, y) in [(0, 0), (1, 0), (2, 0), (0, 1), (1, 1), (0, 2)] {
+                let pos = corner - Offset(x * step.0, y * step.1);
+                let pos = pos + Offset((step.0 - dot.0) / 2, (step.1 - dot.1) / 2);
+                draw.separator(Rect::new(pos, dot));
+            }
+        }
+    }
+
+    impl Handler for Self {
+        type Msg = VoidMsg;
+
+        fn handle(&mut self, mgr: &mut EventMgr, event: Event) -> Response<Self::Msg> {
+            match event {
+                // Drags are handled by the shell (see window_region)
+                Event::PressStart { source, .. } if source.is_primary() => {
+                    if source.repetitions() == 2 {
+                        mgr.send_action(TkAction::SIZE_TO_CONTENT);
+                    }
+                    Response::Used
+                }
+                _ => Response::Unused,
+            }
+        }
+    }
+}
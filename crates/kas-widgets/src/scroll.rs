@@ -18,6 +18,11 @@ widget! {
     ///
     /// Scrollbars are not included; use [`ScrollBarRegion`] if you want those.
     ///
+    /// By default, the inner widget is stretched to fill the region. Content
+    /// alignment may be set with [`ScrollRegion::with_content_align`], in
+    /// which case content smaller than the region is sized to its ideal size
+    /// and aligned (e.g. to center small images).
+    ///
     /// [`ScrollBarRegion`]: crate::ScrollBarRegion
     #[autoimpl(Deref, DerefMut on self.inner)]
     #[autoimpl(class_traits where W: trait on self.inner)]
@@ -27,6 +32,8 @@ widget! {
         #[widget_core]
         core: CoreData,
         min_child_size: Size,
+        ideal_child_size: Size,
+        content_align: (Align, Align),
         offset: Offset,
         frame_size: Size,
         scroll: ScrollComponent,
@@ -41,6 +48,8 @@ widget! {
             ScrollRegion {
                 core: Default::default(),
                 min_child_size: Size::ZERO,
+                ideal_child_size: Size::ZERO,
+                content_align: (Align::Stretch, Align::Stretch),
                 offset: Default::default(),
                 frame_size: Default::default(),
                 scroll: Default::default(),
@@ -48,6 +57,20 @@ widget! {
             }
         }
 
+        /// Set content alignment (inline, builder style)
+        ///
+        /// On each axis, [`Align::Stretch`] (the default) stretches content to
+        /// fill the region while other values size content to its ideal size
+        /// (when smaller than the region) and align it. [`Align::Default`]
+        /// aligns to the top-left.
+        #[inline]
+        #[must_use]
+        pub fn with_content_align(mut self, align: (Align, Align)) -> Self {
+            self.content_align = align;
+            self.scroll.set_align(align);
+            self
+        }
+
         /// Access inner widget directly
         #[inline]
         pub fn inner(&self) -> &W {
@@ -92,10 +115,24 @@ widget! {
         }
     }
 
+    impl SaveState for Self {
+        fn save_state(&self) -> Option<StateValue> {
+            Some(self.scroll.offset().into())
+        }
+
+        fn restore_state(&mut self, state: &StateValue) -> TkAction {
+            match state.as_offset() {
+                Some(offset) => self.scroll.set_offset(offset),
+                None => TkAction::empty(),
+            }
+        }
+    }
+
     impl Layout for Self {
         fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
             let mut rules = self.inner.size_rules(size_mgr.re(), axis);
             self.min_child_size.set_component(axis, rules.min_size());
+            self.ideal_child_size.set_component(axis, rules.ideal_size());
             let line_height = size_mgr.line_height(TextClass::Label);
             self.scroll.set_scroll_rate(3.0 * f32::conv(line_height));
             rules.reduce_min_to(line_height);
@@ -110,7 +147,14 @@ widget! {
 
         fn set_rect(&mut self, mgr: &mut SetRectMgr, rect: Rect, align: AlignHints) {
             self.core.rect = rect;
-            let child_size = (rect.size - self.frame_size).max(self.min_child_size);
+            let mut child_size = (rect.size - self.frame_size).max(self.min_child_size);
+            let ideal = self.ideal_child_size.max(self.min_child_size);
+            if self.content_align.0 != Align::Stretch {
+                child_size.0 = child_size.0.min(ideal.0);
+            }
+            if self.content_align.1 != Align::Stretch {
+                child_size.1 = child_size.1.min(ideal.1);
+            }
             let child_rect = Rect::new(rect.pos + self.offset, child_size);
             self.inner.set_rect(mgr, child_rect, align);
             let _ = self
@@ -127,12 +171,12 @@ widget! {
 
         #[inline]
         fn translation(&self) -> Offset {
-            self.scroll_offset()
+            self.scroll.translation()
         }
 
         fn draw(&mut self, mut draw: DrawMgr) {
             let mut draw = draw.with_core(self.core_data());
-            draw.with_clip_region(self.core.rect, self.scroll_offset(), |mut draw| {
+            draw.with_clip_region(self.core.rect, self.scroll.translation(), |mut draw| {
                 self.inner.draw(draw.re())
             });
         }
@@ -5,9 +5,10 @@
 
 //! Scrollable and selectable label
 
+use super::label::set_string_and_clear;
 use super::Scrollable;
 use kas::event::components::{TextInput, TextInputAction};
-use kas::event::{self, Command, ScrollDelta};
+use kas::event::{self, Command};
 use kas::geom::Vec2;
 use kas::prelude::*;
 use kas::text::format::{EditableText, FormattableText};
@@ -16,7 +17,11 @@ use kas::theme::TextClass;
 
 widget! {
     /// A text label supporting scrolling and selection
-    #[derive(Clone, Default, Debug)]
+    ///
+    /// Text is line-wrapped by default. If wrapping is disabled (see
+    /// [`ScrollLabel::with_wrap`]), long lines may be scrolled horizontally
+    /// (e.g. using [`ScrollBars`](super::ScrollBars)).
+    #[derive(Clone, Debug)]
     #[widget{
         cursor_icon = event::CursorIcon::Text;
     }]
@@ -24,6 +29,7 @@ widget! {
         #[widget_core]
         core: CoreData,
         view_offset: Offset,
+        wrap: bool,
         text: Text<T>,
         required: Vec2,
         selection: SelectionHelper,
@@ -37,12 +43,15 @@ widget! {
 
         fn set_rect(&mut self, _: &mut SetRectMgr, rect: Rect, align: AlignHints) {
             self.core.rect = rect;
+            self.input_handler.set_rect(rect);
             let size = rect.size;
+            let wrap = self.wrap;
             self.required = self
                 .text
                 .update_env(|env| {
                     env.set_align(align.unwrap_or(Align::Default, Align::Default));
                     env.set_bounds(size.into());
+                    env.set_wrap(wrap);
                 })
                 .into();
             self.set_view_offset_from_edit_pos();
@@ -74,6 +83,15 @@ widget! {
         }
     }
 
+    impl Default for Self
+    where
+        T: Default,
+    {
+        fn default() -> Self {
+            Self::new(T::default())
+        }
+    }
+
     impl Self {
         /// Construct an `ScrollLabel` with the given inital `text`
         #[inline]
@@ -81,6 +99,7 @@ widget! {
             ScrollLabel {
                 core: Default::default(),
                 view_offset: Default::default(),
+                wrap: true,
                 text: Text::new_multi(text),
                 required: Vec2::ZERO,
                 selection: SelectionHelper::new(0, 0),
@@ -88,6 +107,17 @@ widget! {
             }
         }
 
+        /// Set whether lines are wrapped (default: true)
+        ///
+        /// When disabled, lines are not wrapped and may be scrolled
+        /// horizontally.
+        #[inline]
+        #[must_use]
+        pub fn with_wrap(mut self, wrap: bool) -> Self {
+            self.wrap = wrap;
+            self
+        }
+
         fn set_edit_pos_from_coord(&mut self, mgr: &mut EventMgr, coord: Coord) {
             let rel_pos = (coord - self.rect().pos + self.view_offset).into();
             self.selection
@@ -144,7 +174,7 @@ widget! {
     {
         fn set_string(&mut self, string: String) -> TkAction {
             let avail = self.core.rect.size;
-            kas::text::util::set_string_and_prepare(&mut self.text, string, avail)
+            set_string_and_clear(&mut self.text, &mut self.selection, string, avail)
         }
     }
 
@@ -179,15 +209,9 @@ widget! {
                     Response::Used
                 }
                 Event::Scroll(delta) => {
-                    let delta2 = match delta {
-                        ScrollDelta::LineDelta(x, y) => {
-                            // We arbitrarily scroll 3 lines:
-                            let dist = 3.0 * self.text.env().height(Default::default());
-                            Offset((x * dist).cast_nearest(), (y * dist).cast_nearest())
-                        }
-                        ScrollDelta::PixelDelta(coord) => coord,
-                    };
-                    self.pan_delta(mgr, delta2)
+                    // We arbitrarily scroll 3 lines:
+                    let dist = 3.0 * self.text.env().height(Default::default());
+                    self.pan_delta(mgr, delta.as_offset(dist))
                 }
                 event => match self.input_handler.handle(mgr, self.id(), event) {
                     TextInputAction::None | TextInputAction::Focus => Response::Used,
@@ -215,8 +239,8 @@ widget! {
 
     impl Scrollable for Self {
         fn scroll_axes(&self, size: Size) -> (bool, bool) {
-            let max = self.max_scroll_offset();
-            (max.0 > size.0, max.1 > size.1)
+            let size = Vec2::from(size);
+            (self.required.0 > size.0, self.required.1 > size.1)
         }
 
         fn max_scroll_offset(&self) -> Offset {
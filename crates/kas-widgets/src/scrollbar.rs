@@ -7,16 +7,72 @@
 
 use std::fmt::Debug;
 
-use super::{DragHandle, ScrollRegion};
+use super::{DragHandle, Filler, ScrollRegion, TrackClick};
+use crate::slider::describe_fraction;
+use kas::draw::{color::Rgba, Draw};
+use kas::event::ScrollDelta;
+use kas::geom::{Quad, Vec2};
 use kas::{event, prelude::*};
 
+/// Thickness of position marks along the track, in virtual pixels
+const MARK_THICKNESS: f32 = 2.0;
+
+/// Message emitted by a [`ScrollBar`] on change of value
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ScrollBarMsg {
+    /// The value was set by dragging the handle
+    ///
+    /// This includes a press on the track with [`TrackClick::Jump`]. While
+    /// the handle is dragged, values are intermediate
+    /// ([`ScrollBar::is_dragging`] returns true); on release the final value
+    /// is emitted again.
+    Drag(i32),
+    /// The value was moved by one page by a press on the track
+    ///
+    /// See [`TrackClick::Page`].
+    Page(i32),
+    /// The value was adjusted by the mouse wheel or touchpad
+    Scroll(i32),
+}
+
+impl ScrollBarMsg {
+    /// Get the new value
+    #[inline]
+    pub fn value(self) -> i32 {
+        match self {
+            ScrollBarMsg::Drag(value) => value,
+            ScrollBarMsg::Page(value) => value,
+            ScrollBarMsg::Scroll(value) => value,
+        }
+    }
+}
+
 widget! {
     /// A scroll bar
     ///
     /// Scroll bars allow user-input of a value between 0 and a defined maximum,
     /// and allow the size of the handle to be specified.
+    ///
+    /// The value is described as a percentage of the maximum (see
+    /// [`WidgetConfig::value_description`]); changes caused by user input are
+    /// announced via [`EventState::announce_value`].
+    ///
+    /// The value may be adjusted by dragging the handle (hold Shift for fine
+    /// adjustment), by clicking the track (see [`ScrollBar::with_track_click`])
+    /// or with the mouse wheel while hovered (one line is a tenth of a page).
+    ///
+    /// The handle is never shorter than a minimum length defined by the theme
+    /// (see [`SizeMgr::scrollbar`]), even when the page is a small fraction of
+    /// the total.
+    ///
+    /// The new value is emitted as a [`ScrollBarMsg`] on each change; the
+    /// variant distinguishes dragging of the handle from paging and wheel
+    /// input.
+    ///
+    /// Positions of interest (e.g. search results or errors) may be marked
+    /// along the track; see [`ScrollBar::set_marks`].
     #[derive(Clone, Debug, Default)]
-    #[handler(msg = i32)]
+    #[handler(msg = ScrollBarMsg)]
     pub struct ScrollBar<D: Directional> {
         #[widget_core]
         core: CoreData,
@@ -28,6 +84,10 @@ widget! {
         handle_value: i32, // contract: > 0
         max_value: i32,
         value: i32,
+        value_label: Option<String>,
+        track_click: TrackClick,
+        drag_start: Option<i32>,
+        marks: Vec<(i32, Rgba)>,
         #[widget]
         handle: DragHandle,
     }
@@ -56,6 +116,10 @@ widget! {
                 handle_value: 1,
                 max_value: 0,
                 value: 0,
+                value_label: None,
+                track_click: TrackClick::Jump,
+                drag_start: None,
+                marks: vec![],
                 handle: DragHandle::new(),
             }
         }
@@ -78,6 +142,55 @@ widget! {
             self
         }
 
+        /// Set a label for value descriptions (chain style)
+        ///
+        /// For example, with label "Position" the value is described as
+        /// "Position 75%".
+        #[must_use]
+        pub fn with_value_label<S: ToString>(mut self, label: S) -> Self {
+            self.value_label = Some(label.to_string());
+            self
+        }
+
+        /// Set the behaviour of clicks on the track (chain style)
+        ///
+        /// With [`TrackClick::Page`], a click moves the value by the handle
+        /// value (one page). Default: [`TrackClick::Jump`].
+        #[must_use]
+        pub fn with_track_click(mut self, track_click: TrackClick) -> Self {
+            self.track_click = track_click;
+            self
+        }
+
+        /// Set position marks (chain style)
+        ///
+        /// See [`ScrollBar::set_marks`].
+        #[must_use]
+        pub fn with_marks(mut self, marks: Vec<(i32, Rgba)>) -> Self {
+            self.marks = marks;
+            self
+        }
+
+        /// Set position marks
+        ///
+        /// Each mark is a `(position, colour)` pair, drawn as a line across the
+        /// track. Positions use the units of [`ScrollBar::set_limits`] and refer
+        /// to the whole content, thus range from `0` to `max_value + handle_value`.
+        /// For example, an editor may mark the lines of search results.
+        pub fn set_marks(&mut self, marks: Vec<(i32, Rgba)>) -> TkAction {
+            if marks == self.marks {
+                return TkAction::empty();
+            }
+            self.marks = marks;
+            TkAction::REDRAW
+        }
+
+        /// Get position marks
+        #[inline]
+        pub fn marks(&self) -> &[(i32, Rgba)] {
+            &self.marks
+        }
+
         /// Set the page limits
         ///
         /// The `max_value` parameter specifies the maximum possible value.
@@ -125,6 +238,14 @@ widget! {
             self.value
         }
 
+        /// True while the handle is being dragged
+        ///
+        /// Values emitted while dragging are intermediate.
+        #[inline]
+        pub fn is_dragging(&self) -> bool {
+            self.handle.is_dragging()
+        }
+
         /// Set the value
         pub fn set_value(&mut self, value: i32) -> TkAction {
             let value = value.clamp(0, self.max_value);
@@ -136,6 +257,43 @@ widget! {
             }
         }
 
+        // Value after a mouse wheel or touchpad scroll
+        fn value_scrolled(&self, delta: ScrollDelta) -> i32 {
+            // One line is a tenth of a page:
+            let line = (self.handle_value / 10).max(1);
+            let d = delta.as_offset(f32::conv(line));
+            let d = match self.direction.is_vertical() {
+                false if d.0 != 0 => d.0,
+                _ => d.1,
+            };
+            self.value - d
+        }
+
+        // Set the value from user input other than dragging
+        fn set_value_by_user(
+            &mut self,
+            mgr: &mut EventMgr,
+            value: i32,
+            msg: fn(i32) -> ScrollBarMsg,
+        ) -> Response<ScrollBarMsg> {
+            let action = self.set_value(value);
+            if action.is_empty() {
+                Response::Used
+            } else {
+                mgr.send_action(action);
+                mgr.announce_value(self.id_ref(), self.describe());
+                Response::Msg(msg(self.value))
+            }
+        }
+
+        fn describe(&self) -> String {
+            let frac = match self.max_value {
+                0 => 0.0,
+                max => f64::from(self.value) / f64::from(max),
+            };
+            describe_fraction(self.value_label.as_deref(), frac)
+        }
+
         #[inline]
         fn bar_len(&self) -> i32 {
             match self.direction.is_vertical() {
@@ -158,6 +316,26 @@ widget! {
             self.handle.set_size_and_offset(size, self.offset())
         }
 
+        // Quad of a mark at content position `value`, with the given thickness
+        fn mark_quad(&self, value: i32, thickness: f32) -> Option<Quad> {
+            let rect = self.core.rect;
+            let len = f32::conv(self.bar_len()) - thickness;
+            let total = f32::conv(self.max_value) + f32::conv(self.handle_value);
+            if total == 0.0 {
+                return None;
+            }
+            let frac = (f32::conv(value) / total).clamp(0.0, 1.0);
+            let mut pos = (frac * len).round();
+            if self.direction.is_reversed() {
+                pos = len - pos;
+            }
+            let (offset, size) = match self.direction.is_vertical() {
+                false => (Vec2(pos, 0.0), Vec2(thickness, f32::conv(rect.size.1))),
+                true => (Vec2(0.0, pos), Vec2(f32::conv(rect.size.0), thickness)),
+            };
+            Some(Quad::from_pos_and_size(Vec2::from(rect.pos) + offset, size))
+        }
+
         // translate value to offset in local coordinates
         fn offset(&self) -> Offset {
             let len = self.bar_len() - self.handle_len;
@@ -178,23 +356,21 @@ widget! {
         }
 
         // true if not equal to old value
-        fn set_offset(&mut self, offset: Offset) -> bool {
+        fn set_offset(&mut self, offset: Vec2) -> bool {
             let len = self.bar_len() - self.handle_len;
             let mut offset = match self.direction.is_vertical() {
-                false => offset.0,
-                true => offset.1,
+                false => f64::from(offset.0),
+                true => f64::from(offset.1),
             };
             if self.direction.is_reversed() {
-                offset = len - offset;
+                offset = f64::from(len) - offset;
             }
 
-            let lhs = i64::from(offset) * i64::from(self.max_value);
-            let rhs = i64::conv(len);
-            if rhs == 0 {
+            if len == 0 {
                 debug_assert_eq!(self.value, 0);
                 return false;
             }
-            let value = i32::conv((lhs + (rhs / 2)) / rhs);
+            let value: i32 = (offset * f64::from(self.max_value) / f64::from(len)).cast_nearest();
             let value = value.clamp(0, self.max_value);
             if value != self.value {
                 self.value = value;
@@ -204,6 +380,12 @@ widget! {
         }
     }
 
+    impl WidgetConfig for Self {
+        fn value_description(&self) -> Option<String> {
+            Some(self.describe())
+        }
+    }
+
     impl Layout for Self {
         fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
             let (size, min_len) = size_mgr.scrollbar();
@@ -237,6 +419,25 @@ widget! {
 
         fn draw(&mut self, mut draw: DrawMgr) {
             let mut draw = draw.with_core(self.handle.core_data());
+            // Marks are drawn first, thus beneath the handle
+            if !self.marks.is_empty() {
+                let thickness = draw.pixel_align().length_from_virtual(MARK_THICKNESS);
+                // Marks have equal size: draw each run of equal colour at once
+                let mut positions = Vec::with_capacity(self.marks.len());
+                let mut iter = self.marks.iter().peekable();
+                while let Some((value, col)) = iter.next() {
+                    let quad = match self.mark_quad(*value, thickness) {
+                        Some(quad) => quad,
+                        None => break,
+                    };
+                    positions.push(quad.a);
+                    if iter.peek().map(|(_, next)| next != col).unwrap_or(true) {
+                        let size = quad.size();
+                        draw.draw_device().rect_instances(size, &positions, *col);
+                        positions.clear();
+                    }
+                }
+            }
             let dir = self.direction.as_direction();
             draw.scrollbar(self.core.rect, self.handle.rect(), dir);
         }
@@ -248,24 +449,59 @@ widget! {
                 return Response::Unused;
             }
 
+            if let Event::Scroll(delta) = event {
+                let value = self.value_scrolled(delta);
+                return self.set_value_by_user(mgr, value, ScrollBarMsg::Scroll);
+            }
+
             let offset = if self.eq_id(&id) {
                 match event {
-                    Event::PressStart { source, coord, .. } => {
-                        self.handle.handle_press_on_track(mgr, source, coord)
-                    }
+                    Event::PressStart { source, coord, .. } => match self.track_click {
+                        TrackClick::Jump => {
+                            self.drag_start = Some(self.value);
+                            let offset = self.handle.handle_press_on_track(mgr, source, coord);
+                            Vec2::from(offset)
+                        }
+                        TrackClick::Page => {
+                            let handle = self.handle.rect();
+                            let before = match self.direction.is_vertical() {
+                                false => coord.0 < handle.pos.0,
+                                true => coord.1 < handle.pos.1,
+                            };
+                            let value = match before == self.direction.is_reversed() {
+                                false => self.value - self.handle_value,
+                                true => self.value + self.handle_value,
+                            };
+                            return self.set_value_by_user(mgr, value, ScrollBarMsg::Page);
+                        }
+                    },
                     _ => return Response::Unused,
                 }
             } else {
                 debug_assert!(self.handle.id().is_ancestor_of(&id));
+                let is_end = matches!(event, Event::PressEnd { .. });
+                if matches!(event, Event::PressStart { .. }) {
+                    self.drag_start = Some(self.value);
+                }
                 match self.handle.send(mgr, id, event).try_into() {
-                    Ok(res) => return res,
-                    Err(offset) => offset,
+                    Ok(res) => {
+                        // Emit the final value, if changed by dragging
+                        if let Some(start) = self.drag_start.filter(|_| is_end) {
+                            self.drag_start = None;
+                            if start != self.value {
+                                return Response::Msg(ScrollBarMsg::Drag(self.value));
+                            }
+                        }
+                        return res;
+                    }
+                    Err(_) => self.handle.drag_offset(),
                 }
             };
 
             if self.set_offset(offset) {
                 mgr.redraw(self.handle.id());
-                Response::Msg(self.value)
+                mgr.announce_value(self.id_ref(), self.describe());
+                Response::Msg(ScrollBarMsg::Drag(self.value))
             } else {
                 Response::Used
             }
@@ -321,10 +557,38 @@ pub trait Scrollable: Widget {
     }
 }
 
+/// Visibility mode of a scroll bar
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ScrollBarMode {
+    /// Show the bar when scrolling is required
+    ///
+    /// This reserves enough space for the bar even when not shown.
+    Auto,
+    /// Always show the bar
+    Always,
+    /// Never show the bar
+    Never,
+}
+
+impl Default for ScrollBarMode {
+    fn default() -> Self {
+        ScrollBarMode::Auto
+    }
+}
+
+impl ScrollBarMode {
+    fn from_bool(show: bool) -> Self {
+        match show {
+            false => ScrollBarMode::Never,
+            true => ScrollBarMode::Always,
+        }
+    }
+}
+
 widget! {
     /// A scrollable region with bars
     ///
-    /// This is essentially a `ScrollBars<ScrollRegion<W>>`:
+    /// This is essentially a `ScrollBars<ScrollRegion<W>, C>`:
     /// [`ScrollRegion`] handles the actual scrolling and wheel/touch events,
     /// while [`ScrollBars`] adds scrollbar controls.
     #[autoimpl(Deref, DerefMut on self.0)]
@@ -334,15 +598,11 @@ widget! {
         derive = self.0;
     }]
     #[handler(msg = <W as event::Handler>::Msg)]
-    pub struct ScrollBarRegion<W: Widget>(ScrollBars<ScrollRegion<W>>);
+    pub struct ScrollBarRegion<W: Widget, C: Widget<Msg = VoidMsg> = Filler>(
+        ScrollBars<ScrollRegion<W>, C>,
+    );
 
     impl Self {
-        /// Construct a `ScrollBarRegion<W>`
-        #[inline]
-        pub fn new(inner: W) -> Self {
-            ScrollBarRegion(ScrollBars::new(ScrollRegion::new(inner)))
-        }
-
         /// Auto-enable bars
         ///
         /// If enabled (default), this automatically enables/disables scroll bars
@@ -365,6 +625,22 @@ widget! {
             ScrollBarRegion(self.0.with_bars(horiz, vert))
         }
 
+        /// Set the visibility mode of each scroll bar (chain style)
+        #[inline]
+        #[must_use]
+        pub fn with_bar_modes(self, horiz: ScrollBarMode, vert: ScrollBarMode) -> Self {
+            ScrollBarRegion(self.0.with_bar_modes(horiz, vert))
+        }
+
+        /// Set the corner widget (chain style)
+        ///
+        /// See [`ScrollBars::with_corner`].
+        #[inline]
+        #[must_use]
+        pub fn with_corner<C2: Widget<Msg = VoidMsg>>(self, corner: C2) -> ScrollBarRegion<W, C2> {
+            ScrollBarRegion(self.0.with_corner(corner))
+        }
+
         /// Set which scroll bars are visible
         ///
         /// Calling this method also disables automatic scroll bars.
@@ -374,6 +650,12 @@ widget! {
             self.0.set_bars(horiz, vert)
         }
 
+        /// Set the visibility mode of each scroll bar
+        #[inline]
+        pub fn set_bar_modes(&mut self, horiz: ScrollBarMode, vert: ScrollBarMode) -> TkAction {
+            self.0.set_bar_modes(horiz, vert)
+        }
+
         /// Query which scroll bars are visible
         ///
         /// Returns `(horiz, vert)` tuple.
@@ -382,6 +664,14 @@ widget! {
             self.0.bars()
         }
 
+        /// Query the visibility mode of each scroll bar
+        ///
+        /// Returns `(horiz, vert)` tuple.
+        #[inline]
+        pub fn bar_modes(&self) -> (ScrollBarMode, ScrollBarMode) {
+            self.0.bar_modes()
+        }
+
         /// Access inner widget directly
         #[inline]
         pub fn inner(&self) -> &W {
@@ -415,6 +705,14 @@ widget! {
     }
 }
 
+impl<W: Widget> ScrollBarRegion<W> {
+    /// Construct a `ScrollBarRegion<W>`
+    #[inline]
+    pub fn new(inner: W) -> Self {
+        ScrollBarRegion(ScrollBars::new(ScrollRegion::new(inner)))
+    }
+}
+
 widget! {
     /// Scrollbar controls
     ///
@@ -425,14 +723,18 @@ widget! {
     /// the result looks poor when content is scrolled. Instead the content should
     /// force internal margins by wrapping contents with a (zero-sized) frame.
     /// [`ScrollRegion`] already does this.
+    ///
+    /// When both bars are shown, the square between their ends is occupied by
+    /// a corner widget `C` (by default an empty [`Filler`]); see
+    /// [`ScrollBars::with_corner`].
     #[autoimpl(Deref, DerefMut on self.inner)]
     #[autoimpl(class_traits where W: trait on self.inner)]
     #[derive(Clone, Debug, Default)]
     #[handler(msg = <W as event::Handler>::Msg)]
-    pub struct ScrollBars<W: Scrollable> {
+    pub struct ScrollBars<W: Scrollable, C: Widget<Msg = VoidMsg> = Filler> {
         #[widget_core]
         core: CoreData,
-        auto_bars: bool,
+        bar_modes: (ScrollBarMode, ScrollBarMode),
         show_bars: (bool, bool),
         #[widget]
         horiz_bar: ScrollBar<kas::dir::Right>,
@@ -440,25 +742,11 @@ widget! {
         vert_bar: ScrollBar<kas::dir::Down>,
         #[widget]
         inner: W,
+        #[widget]
+        corner: C,
     }
 
     impl Self {
-        /// Construct
-        ///
-        /// By default scrollbars are automatically enabled based on requirements.
-        /// See [`ScrollBars::with_auto_bars`] and [`ScrollBars::with_bars`].
-        #[inline]
-        pub fn new(inner: W) -> Self {
-            ScrollBars {
-                core: Default::default(),
-                auto_bars: true,
-                show_bars: (false, false),
-                horiz_bar: ScrollBar::new(),
-                vert_bar: ScrollBar::new(),
-                inner,
-            }
-        }
-
         /// Auto-enable bars
         ///
         /// If enabled (default), this automatically enables/disables scroll bars
@@ -469,7 +757,13 @@ widget! {
         #[inline]
         #[must_use]
         pub fn with_auto_bars(mut self, enable: bool) -> Self {
-            self.auto_bars = enable;
+            self.bar_modes = match enable {
+                true => (ScrollBarMode::Auto, ScrollBarMode::Auto),
+                false => (
+                    ScrollBarMode::from_bool(self.show_bars.0),
+                    ScrollBarMode::from_bool(self.show_bars.1),
+                ),
+            };
             self
         }
 
@@ -478,20 +772,60 @@ widget! {
         /// Calling this method also disables automatic scroll bars.
         #[inline]
         #[must_use]
-        pub fn with_bars(mut self, horiz: bool, vert: bool) -> Self {
-            self.auto_bars = false;
-            self.show_bars = (horiz, vert);
+        pub fn with_bars(self, horiz: bool, vert: bool) -> Self {
+            let (horiz, vert) = (ScrollBarMode::from_bool(horiz), ScrollBarMode::from_bool(vert));
+            self.with_bar_modes(horiz, vert)
+        }
+
+        /// Set the visibility mode of each scroll bar (chain style)
+        ///
+        /// This allows forcing one bar to show or hide while the other is
+        /// automatic.
+        #[inline]
+        #[must_use]
+        pub fn with_bar_modes(mut self, horiz: ScrollBarMode, vert: ScrollBarMode) -> Self {
+            let _ = self.set_bar_modes(horiz, vert);
             self
         }
 
+        /// Set the corner widget (chain style)
+        ///
+        /// This widget occupies the square between the ends of the two scroll
+        /// bars, and is only shown when both bars are visible. It may be used
+        /// for e.g. a resize grip.
+        #[must_use]
+        pub fn with_corner<C2: Widget<Msg = VoidMsg>>(self, corner: C2) -> ScrollBars<W, C2> {
+            ScrollBars {
+                core: self.core,
+                bar_modes: self.bar_modes,
+                show_bars: self.show_bars,
+                horiz_bar: self.horiz_bar,
+                vert_bar: self.vert_bar,
+                inner: self.inner,
+                corner,
+            }
+        }
+
         /// Set which scroll bars are visible
         ///
         /// Calling this method also disables automatic scroll bars.
         /// A resize is required to update the child and scrollbar widgets.
         #[inline]
         pub fn set_bars(&mut self, horiz: bool, vert: bool) -> TkAction {
-            self.auto_bars = false;
-            self.show_bars = (horiz, vert);
+            let (horiz, vert) = (ScrollBarMode::from_bool(horiz), ScrollBarMode::from_bool(vert));
+            self.set_bar_modes(horiz, vert)
+        }
+
+        /// Set the visibility mode of each scroll bar
+        ///
+        /// A resize is required to update the child and scrollbar widgets.
+        pub fn set_bar_modes(&mut self, horiz: ScrollBarMode, vert: ScrollBarMode) -> TkAction {
+            self.bar_modes = (horiz, vert);
+            let show = |mode, current| match mode {
+                ScrollBarMode::Auto => current,
+                mode => mode == ScrollBarMode::Always,
+            };
+            self.show_bars = (show(horiz, self.show_bars.0), show(vert, self.show_bars.1));
             TkAction::RESIZE
         }
 
@@ -503,6 +837,14 @@ widget! {
             self.show_bars
         }
 
+        /// Query the visibility mode of each scroll bar
+        ///
+        /// Returns `(horiz, vert)` tuple.
+        #[inline]
+        pub fn bar_modes(&self) -> (ScrollBarMode, ScrollBarMode) {
+            self.bar_modes
+        }
+
         /// Access inner widget directly
         #[inline]
         pub fn inner(&self) -> &W {
@@ -515,6 +857,18 @@ widget! {
             &mut self.inner
         }
 
+        /// Access the corner widget
+        #[inline]
+        pub fn corner(&self) -> &C {
+            &self.corner
+        }
+
+        /// Access the corner widget
+        #[inline]
+        pub fn corner_mut(&mut self) -> &mut C {
+            &mut self.corner
+        }
+
         fn draw_(&mut self, mut draw: DrawMgr) {
             let mut draw = draw.with_core(self.core_data());
             if self.show_bars.0 {
@@ -523,6 +877,9 @@ widget! {
             if self.show_bars.1 {
                 self.vert_bar.draw(draw.re());
             }
+            if self.show_bars.0 && self.show_bars.1 {
+                self.corner.draw(draw.re());
+            }
             self.inner.draw(draw.re());
         }
     }
@@ -556,9 +913,12 @@ widget! {
     impl Layout for Self {
         fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
             let mut rules = self.inner.size_rules(size_mgr.re(), axis);
-            if axis.is_horizontal() && (self.auto_bars || self.show_bars.1) {
+            // The corner is sized by the bars:
+            let _ = self.corner.size_rules(size_mgr.re(), axis);
+            let (horiz, vert) = self.bar_modes;
+            if axis.is_horizontal() && vert != ScrollBarMode::Never {
                 rules.append(self.vert_bar.size_rules(size_mgr.re(), axis));
-            } else if axis.is_vertical() && (self.auto_bars || self.show_bars.0) {
+            } else if axis.is_vertical() && horiz != ScrollBarMode::Never {
                 rules.append(self.horiz_bar.size_rules(size_mgr.re(), axis));
             }
             rules
@@ -570,9 +930,13 @@ widget! {
             let mut child_size = rect.size;
 
             let bar_width = (mgr.size_mgr().scrollbar().0).1;
-            if self.auto_bars {
-                self.show_bars = self.inner.scroll_axes(child_size);
-            }
+            let required = self.inner.scroll_axes(child_size);
+            let show = |mode, required| match mode {
+                ScrollBarMode::Auto => required,
+                mode => mode == ScrollBarMode::Always,
+            };
+            let (horiz, vert) = self.bar_modes;
+            self.show_bars = (show(horiz, required.0), show(vert, required.1));
             if self.show_bars.0 {
                 child_size.1 -= bar_width;
             }
@@ -583,6 +947,7 @@ widget! {
             let child_rect = Rect::new(pos, child_size);
             self.inner.set_rect(mgr, child_rect, align);
             let max_scroll_offset = self.inner.max_scroll_offset();
+            let offset = self.inner.scroll_offset();
 
             if self.show_bars.0 {
                 let pos = Coord(pos.0, rect.pos2().1 - bar_width);
@@ -590,13 +955,38 @@ widget! {
                 self.horiz_bar
                     .set_rect(mgr, Rect { pos, size }, AlignHints::NONE);
                 let _ = self.horiz_bar.set_limits(max_scroll_offset.0, rect.size.0);
+                let _ = self.horiz_bar.set_value(offset.0);
             }
             if self.show_bars.1 {
                 let pos = Coord(rect.pos2().0 - bar_width, pos.1);
-                let size = Size::new(bar_width, self.core.rect.size.1);
+                let size = Size::new(bar_width, child_size.1);
                 self.vert_bar
                     .set_rect(mgr, Rect { pos, size }, AlignHints::NONE);
                 let _ = self.vert_bar.set_limits(max_scroll_offset.1, rect.size.1);
+                let _ = self.vert_bar.set_value(offset.1);
+            }
+            if self.show_bars.0 && self.show_bars.1 {
+                let pos = rect.pos2() - Offset::splat(bar_width);
+                let size = Size::splat(bar_width);
+                self.corner.set_rect(mgr, Rect { pos, size }, AlignHints::NONE);
+            }
+        }
+
+        fn spatial_nav(
+            &mut self,
+            _: &mut SetRectMgr,
+            reverse: bool,
+            from: Option<usize>,
+        ) -> Option<usize> {
+            // Children: horiz_bar, vert_bar, inner, corner; hidden ones are skipped
+            let (horiz, vert) = self.show_bars;
+            let visible = [horiz, vert, true, horiz && vert];
+            let mut iter = (0..visible.len()).filter(|i| visible[*i]);
+            match (reverse, from) {
+                (false, None) => iter.next(),
+                (false, Some(from)) => iter.find(|i| *i > from),
+                (true, None) => iter.next_back(),
+                (true, Some(from)) => iter.rev().find(|i| *i < from),
             }
         }
 
@@ -604,9 +994,19 @@ widget! {
             if !self.rect().contains(coord) {
                 return None;
             }
-            self.vert_bar.find_id(coord)
-                .or_else(|| self.horiz_bar.find_id(coord))
-                .or_else(|| self.inner.find_id(coord))
+            // Hidden bars and corner may have stale rects: skip them
+            let (horiz, vert) = self.show_bars;
+            let mut id = None;
+            if horiz && vert {
+                id = self.corner.find_id(coord);
+            }
+            if id.is_none() && vert {
+                id = self.vert_bar.find_id(coord);
+            }
+            if id.is_none() && horiz {
+                id = self.horiz_bar.find_id(coord);
+            }
+            id.or_else(|| self.inner.find_id(coord))
                 .or(Some(self.id()))
         }
 
@@ -621,7 +1021,7 @@ widget! {
     }
 
     #[cfg(feature = "min_spec")]
-    impl<W: Widget> Layout for ScrollBars<ScrollRegion<W>> {
+    impl<W: Widget, C: Widget<Msg = VoidMsg>> Layout for ScrollBars<ScrollRegion<W>, C> {
         fn draw(&mut self, mut draw: DrawMgr) {
             let mut draw = draw.with_core(self.core_data());
             // Enlarge clip region to *our* rect:
@@ -636,6 +1036,9 @@ widget! {
                 if self.show_bars.1 {
                     self.vert_bar.draw(draw.re());
                 }
+                if self.show_bars.0 && self.show_bars.1 {
+                    self.corner.draw(draw.re());
+                }
             });
         }
     }
@@ -651,7 +1054,7 @@ widget! {
                     .send(mgr, id, event)
                     .try_into()
                     .unwrap_or_else(|msg| {
-                        let offset = Offset(msg, self.inner.scroll_offset().1);
+                        let offset = Offset(msg.value(), self.inner.scroll_offset().1);
                         self.inner.set_scroll_offset(mgr, offset);
                         Response::Used
                     }),
@@ -659,7 +1062,7 @@ widget! {
                     .send(mgr, id, event)
                     .try_into()
                     .unwrap_or_else(|msg| {
-                        let offset = Offset(self.inner.scroll_offset().0, msg);
+                        let offset = Offset(self.inner.scroll_offset().0, msg.value());
                         self.inner.set_scroll_offset(mgr, offset);
                         Response::Used
                     }),
@@ -672,6 +1075,7 @@ widget! {
                     }
                     r
                 }
+                Some(3) => self.corner.send(mgr, id, event).void_into(),
                 _ if self.eq_id(id) => self.handle(mgr, event),
                 _ => {
                     debug_assert!(false, "SendEvent::send: bad WidgetId");
@@ -681,3 +1085,22 @@ widget! {
         }
     }
 }
+
+impl<W: Scrollable> ScrollBars<W> {
+    /// Construct
+    ///
+    /// By default scrollbars are automatically enabled based on requirements.
+    /// See [`ScrollBars::with_auto_bars`] and [`ScrollBars::with_bar_modes`].
+    #[inline]
+    pub fn new(inner: W) -> Self {
+        ScrollBars {
+            core: Default::default(),
+            bar_modes: (ScrollBarMode::Auto, ScrollBarMode::Auto),
+            show_bars: (false, false),
+            horiz_bar: ScrollBar::new(),
+            vert_bar: ScrollBar::new(),
+            inner,
+            corner: Filler::new(),
+        }
+    }
+}
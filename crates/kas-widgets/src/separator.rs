@@ -5,21 +5,36 @@
 
 //! A separator
 
+use std::f32::consts::FRAC_PI_2;
 use std::fmt::Debug;
 use std::marker::PhantomData;
 
 use crate::Menu;
+use kas::theme::TextClass;
 use kas::{event::VoidMsg, prelude::*};
 
 widget! {
     /// A separator
     ///
     /// This widget draws a bar when in a list.
+    ///
+    /// Optionally, the separator may carry a text label (see
+    /// [`Separator::with_label`]), for example to title a group of menu entries
+    /// or a section of a form. The label is drawn leading or centered (see
+    /// [`Separator::with_label_align`]) with the bar filling the remaining
+    /// space. Since the bar's orientation cannot be inferred from an unsized
+    /// rect, a labelled separator is horizontal unless
+    /// [`Separator::with_vertical`] is used; vertical labels read bottom-to-top.
     #[derive(Clone, Debug, Default)]
     #[handler(msg=M)]
     pub struct Separator<M: Debug + 'static = VoidMsg> {
         #[widget_core]
         core: CoreData,
+        label: Option<Text<String>>,
+        label_align: Align,
+        vertical: bool,
+        // Size of label text, in the text's own (unrotated) frame
+        text_size: Size,
         _msg: PhantomData<M>,
     }
 
@@ -29,6 +44,10 @@ widget! {
         pub fn new() -> Self {
             Separator {
                 core: Default::default(),
+                label: None,
+                label_align: Align::Default,
+                vertical: false,
+                text_size: Size::ZERO,
                 _msg: Default::default(),
             }
         }
@@ -43,20 +62,152 @@ widget! {
         pub fn infer() -> Self {
             Separator {
                 core: Default::default(),
+                label: None,
+                label_align: Align::Default,
+                vertical: false,
+                text_size: Size::ZERO,
                 _msg: Default::default(),
             }
         }
+
+        /// Set a text label (inline, builder style)
+        #[inline]
+        #[must_use]
+        pub fn with_label<S: ToString>(mut self, label: S) -> Self {
+            self.label = Some(Text::new_single(label.to_string()));
+            self
+        }
+
+        /// Set label alignment (inline, builder style)
+        ///
+        /// Supported values are [`Align::Default`] (leading; the default) and
+        /// [`Align::Center`]. Other values are treated as [`Align::Center`].
+        #[inline]
+        #[must_use]
+        pub fn with_label_align(mut self, align: Align) -> Self {
+            self.label_align = align;
+            self
+        }
+
+        /// Set whether the bar is vertical (inline, builder style)
+        ///
+        /// This only affects labelled separators.
+        #[inline]
+        #[must_use]
+        pub fn with_vertical(mut self, vertical: bool) -> Self {
+            self.vertical = vertical;
+            self
+        }
+
+        /// Get the label, if any
+        pub fn label(&self) -> Option<&str> {
+            self.label.as_ref().map(|label| label.as_str())
+        }
+
+        /// Set or clear the label
+        pub fn set_label<S: ToString>(&mut self, label: Option<S>) -> TkAction {
+            self.label = label.map(|label| Text::new_single(label.to_string()));
+            TkAction::RESIZE
+        }
+
+        // Label position along the bar and length of the bar segments before
+        // and after it; `len` is the bar's length and `gap` the space on each
+        // side of the label.
+        fn label_span(&self, len: i32, gap: i32) -> (i32, i32, i32) {
+            let text = self.text_size.0;
+            let start = match self.label_align {
+                Align::Default => 0,
+                _ => (len - text) / 2,
+            };
+            let before = (start - gap).max(0);
+            let after = (len - start - text - gap).max(0);
+            (start, before, after)
+        }
     }
 
     impl Layout for Self {
         fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
             let margins = size_mgr.frame_margins();
-            SizeRules::extract_fixed(axis, size_mgr.separator(), margins)
+            let label = match self.label.as_mut() {
+                Some(label) => label,
+                None => return SizeRules::extract_fixed(axis, size_mgr.separator(), margins),
+            };
+
+            if axis.is_horizontal() {
+                // The horizontal axis is always sized first; measure the text
+                let width = size_mgr.text_bound(label, TextClass::Label, axis).min_size();
+                let height = size_mgr.line_height(TextClass::Label);
+                self.text_size = Size(width, height);
+            }
+
+            let sep = size_mgr.separator();
+            let margins = margins.extract(axis);
+            if axis.is_vertical() == self.vertical {
+                // Along the bar: text, gaps and bar segments of at least one
+                // line height each
+                let (text, line) = (self.text_size.0, self.text_size.1);
+                let segments = match self.label_align {
+                    Align::Default => 1,
+                    _ => 2,
+                };
+                let min = text + segments * (line / 2 + line);
+                SizeRules::new(min, min, margins, Stretch::Low)
+            } else {
+                let thickness = sep.extract(axis);
+                SizeRules::fixed(self.text_size.1.max(thickness), margins)
+            }
+        }
+
+        fn set_rect(&mut self, _: &mut SetRectMgr, rect: Rect, _: AlignHints) {
+            self.core.rect = rect;
+            if let Some(label) = self.label.as_mut() {
+                let size = self.text_size;
+                label.update_env(|env| {
+                    env.set_bounds(size.into());
+                    env.set_align((Align::Default, Align::Center));
+                });
+            }
         }
 
         fn draw(&mut self, mut draw: DrawMgr) {
             let mut draw = draw.with_core(self.core_data());
-            draw.separator(self.core.rect);
+            let rect = self.core.rect;
+            let label = match self.label.as_ref() {
+                Some(label) => label,
+                None => return draw.separator(rect),
+            };
+
+            let sep = draw.size_mgr().separator();
+            let (text_w, text_h) = (self.text_size.0, self.text_size.1);
+            let gap = text_h / 2;
+            if !self.vertical {
+                let (start, before, after) = self.label_span(rect.size.0, gap);
+                let t = sep.1.min(rect.size.1);
+                let y = rect.pos.1 + (rect.size.1 - t) / 2;
+                if before > 0 {
+                    draw.separator(Rect::new(Coord(rect.pos.0, y), Size(before, t)));
+                }
+                if after > 0 {
+                    let x = rect.pos.0 + rect.size.0 - after;
+                    draw.separator(Rect::new(Coord(x, y), Size(after, t)));
+                }
+                let pos = rect.pos + Offset(start, (rect.size.1 - text_h) / 2);
+                draw.text(pos, label.as_ref(), TextClass::Label);
+            } else {
+                let (start, before, after) = self.label_span(rect.size.1, gap);
+                let t = sep.0.min(rect.size.0);
+                let x = rect.pos.0 + (rect.size.0 - t) / 2;
+                if before > 0 {
+                    draw.separator(Rect::new(Coord(x, rect.pos.1), Size(t, before)));
+                }
+                if after > 0 {
+                    let y = rect.pos.1 + rect.size.1 - after;
+                    draw.separator(Rect::new(Coord(x, y), Size(t, after)));
+                }
+                // Rotated text occupies pos - (0, width) to pos + (height, 0)
+                let pos = rect.pos + Offset((rect.size.0 - text_h) / 2, start + text_w);
+                draw.text_rotated(pos, label.as_ref(), TextClass::Label, -FRAC_PI_2);
+            }
         }
     }
 
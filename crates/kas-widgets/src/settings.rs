@@ -0,0 +1,349 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Settings dialog
+
+use crate::{
+    CheckBox, ComboBox, Form, KeyCapture, Label, ScrollBarRegion, Slider, StrLabel, StringLabel,
+    TextButton,
+};
+use kas::dir::Right;
+use kas::event::config::{MousePan, Shortcuts};
+use kas::event::{Command, ModifiersState, VirtualKeyCode};
+use kas::prelude::*;
+
+const MOUSE_PAN: [MousePan; 4] = [
+    MousePan::Never,
+    MousePan::WithAlt,
+    MousePan::WithCtrl,
+    MousePan::Always,
+];
+const MOUSE_PAN_NAMES: [&str; 4] = ["Never", "With Alt", "With Ctrl", "Always"];
+
+fn mouse_pan_index(pan: MousePan) -> usize {
+    MOUSE_PAN.iter().position(|p| *p == pan).unwrap_or(0)
+}
+
+/// Get all bindings of `cmd`, ordered by name
+fn bindings_of(mgr: &EventState, cmd: Command) -> Vec<(ModifiersState, VirtualKeyCode)> {
+    let mut bindings: Vec<_> = mgr.config().shortcuts(|s| {
+        s.iter()
+            .filter(|(_, _, c)| *c == cmd)
+            .map(|(modifiers, vkey, _)| (modifiers, vkey))
+            .collect()
+    });
+    bindings.sort_by_key(|(modifiers, vkey)| Shortcuts::key_name(*modifiers, *vkey));
+    bindings
+}
+
+/// Describe bindings other than the first
+fn describe_others(bindings: &[(ModifiersState, VirtualKeyCode)]) -> String {
+    let names: Vec<_> = (bindings.iter().skip(1))
+        .map(|(modifiers, vkey)| Shortcuts::key_name(*modifiers, *vkey))
+        .collect();
+    match names.is_empty() {
+        true => String::new(),
+        false => format!("also {}", names.join(", ")),
+    }
+}
+
+widget! {
+    /// A row of the shortcuts table
+    ///
+    /// The [`KeyCapture`] control edits the first binding of the command (by
+    /// name); any further bindings are listed beside it. Clearing the first
+    /// binding thus makes the next binding editable.
+    #[derive(Clone, Debug)]
+    #[widget{
+        layout = row: [self.capture, self.others];
+    }]
+    struct ShortcutEntry {
+        #[widget_core]
+        core: CoreData,
+        cmd: Command,
+        #[widget(update = clear, flatmap_msg = rebind)]
+        capture: KeyCapture,
+        #[widget]
+        others: StringLabel,
+    }
+
+    impl Self {
+        fn new(cmd: Command) -> Self {
+            ShortcutEntry {
+                core: Default::default(),
+                cmd,
+                capture: KeyCapture::new(None),
+                others: Label::new(String::new()),
+            }
+        }
+
+        fn refresh(&mut self, mgr: &EventState) -> TkAction {
+            let bindings = bindings_of(mgr, self.cmd);
+            let mut action = TkAction::empty();
+            let binding = bindings.first().cloned();
+            if binding != self.capture.binding() {
+                action |= self.capture.set_binding(binding);
+            }
+            let others = describe_others(&bindings);
+            if others != self.others.get_str() {
+                action |= self.others.set_text(others);
+            }
+            action
+        }
+
+        fn clear(&mut self, mgr: &mut EventMgr) {
+            if let Some((modifiers, vkey)) = bindings_of(mgr, self.cmd).first().cloned() {
+                mgr.config().update_base(|config| {
+                    config.shortcuts.remove(modifiers, vkey);
+                });
+            }
+        }
+
+        fn rebind(
+            &mut self,
+            mgr: &mut EventMgr,
+            (modifiers, vkey): (ModifiersState, VirtualKeyCode),
+        ) -> Response<VoidMsg> {
+            let old = bindings_of(mgr, self.cmd).first().cloned();
+            let cmd = self.cmd;
+            mgr.config().update_base(|config| {
+                if let Some((modifiers, vkey)) = old {
+                    config.shortcuts.remove(modifiers, vkey);
+                }
+                config.shortcuts.insert(modifiers, vkey, cmd);
+            });
+            // The new binding may replace that of another command
+            Response::Update
+        }
+    }
+
+    impl WidgetConfig for Self {
+        fn configure(&mut self, mgr: &mut SetRectMgr) {
+            let action = self.refresh(mgr);
+            *mgr |= action;
+        }
+    }
+}
+
+widget! {
+    /// A settings dialog
+    ///
+    /// This widget presents controls for the user-facing parts of the
+    /// configuration: theme font size and colour scheme, event-handling
+    /// options and [`Shortcuts`] (via [`KeyCapture`] controls, thus
+    /// shortcuts may be rebound or cleared).
+    ///
+    /// Changes are applied immediately: theme settings via
+    /// [`EventMgr::adjust_theme`] and event configuration via
+    /// [`kas::event::config::WindowConfig::update_base`]. The "Save" button
+    /// writes the configuration to disk (see [`EventMgr::save_config`]), as
+    /// does the shell on exit.
+    ///
+    /// Available colour schemes must be supplied via
+    /// [`Settings::with_schemes`]; otherwise scheme selection is disabled.
+    #[derive(Clone, Debug)]
+    #[widget{
+        layout = column: [
+            grid: {
+                0, 0: self.l_font_size; 0, 1: self.font_size;
+                1, 0: self.l_scheme; 1, 1: self.scheme;
+                2, 0: self.l_menu_delay; 2, 1: self.menu_delay;
+                3, 0: self.l_double_click; 3, 1: self.double_click;
+                4, 0: self.l_mouse_pan; 4, 1: self.mouse_pan;
+                5, 0: self.l_mouse_text_pan; 5, 1: self.mouse_text_pan;
+                6, 0..2: self.mouse_nav_focus;
+                7, 0..2: self.touch_nav_focus;
+                8, 0..2: self.scroll_natural;
+            },
+            self.l_shortcuts,
+            self.shortcuts,
+            self.save,
+        ];
+    }]
+    pub struct Settings {
+        #[widget_core]
+        core: CoreData,
+        #[widget]
+        l_font_size: StrLabel,
+        #[widget(use_msg = set_font_size)]
+        font_size: Slider<f32, Right>,
+        #[widget]
+        l_scheme: StrLabel,
+        #[widget(use_msg = set_scheme)]
+        scheme: ComboBox<usize>,
+        #[widget]
+        l_menu_delay: StrLabel,
+        #[widget(use_msg = set_menu_delay)]
+        menu_delay: Slider<u32, Right>,
+        #[widget]
+        l_double_click: StrLabel,
+        #[widget(use_msg = set_double_click)]
+        double_click: Slider<u32, Right>,
+        #[widget]
+        l_mouse_pan: StrLabel,
+        #[widget(use_msg = set_mouse_pan)]
+        mouse_pan: ComboBox<usize>,
+        #[widget]
+        l_mouse_text_pan: StrLabel,
+        #[widget(use_msg = set_mouse_text_pan)]
+        mouse_text_pan: ComboBox<usize>,
+        #[widget(use_msg = set_mouse_nav_focus)]
+        mouse_nav_focus: CheckBox<bool>,
+        #[widget(use_msg = set_touch_nav_focus)]
+        touch_nav_focus: CheckBox<bool>,
+        #[widget(use_msg = set_scroll_natural)]
+        scroll_natural: CheckBox<bool>,
+        #[widget]
+        l_shortcuts: StrLabel,
+        #[widget(update = shortcuts_changed)]
+        shortcuts: ScrollBarRegion<Form<ShortcutEntry>>,
+        #[widget(use_msg = save)]
+        save: TextButton<()>,
+        schemes: Vec<String>,
+    }
+
+    impl Self {
+        /// Construct
+        pub fn new() -> Self {
+            let mut commands: Vec<Command> = Shortcuts::platform_defaults()
+                .iter()
+                .map(|(_, _, cmd)| cmd)
+                .collect();
+            commands.sort_by_cached_key(|cmd| format!("{:?}", cmd));
+            commands.dedup();
+            let mut form = Form::new();
+            for cmd in commands {
+                let _ = form.push(format!("{:?}", cmd), ShortcutEntry::new(cmd));
+            }
+
+            let mut scheme = ComboBox::new_vec(vec![], 0).on_select(|_, index| Some(index));
+            let _ = scheme.set_disabled(true);
+
+            Settings {
+                core: Default::default(),
+                l_font_size: Label::new("Font size"),
+                font_size: Slider::new(6.0, 24.0, 0.5),
+                l_scheme: Label::new("Colour scheme"),
+                scheme,
+                l_menu_delay: Label::new("Menu delay (ms)"),
+                menu_delay: Slider::new(0, 1000, 50),
+                l_double_click: Label::new("Double-click interval (ms)"),
+                double_click: Slider::new(100, 2000, 50),
+                l_mouse_pan: Label::new("Mouse panning"),
+                mouse_pan: ComboBox::new(MOUSE_PAN_NAMES, 0)
+                    .on_select(|_, index| Some(index)),
+                l_mouse_text_pan: Label::new("Mouse text panning"),
+                mouse_text_pan: ComboBox::new(MOUSE_PAN_NAMES, 0)
+                    .on_select(|_, index| Some(index)),
+                mouse_nav_focus: CheckBox::new("Mouse clicks set navigation focus")
+                    .on_toggle(|_, state| Some(state)),
+                touch_nav_focus: CheckBox::new("Touch events set navigation focus")
+                    .on_toggle(|_, state| Some(state)),
+                scroll_natural: CheckBox::new("Natural scrolling")
+                    .on_toggle(|_, state| Some(state)),
+                l_shortcuts: Label::new("Shortcuts"),
+                shortcuts: ScrollBarRegion::new(form),
+                save: TextButton::new_msg("&Save", ()),
+                schemes: vec![],
+            }
+        }
+
+        /// Set the list of colour schemes (chain style)
+        ///
+        /// The theme's schemes may be listed via
+        /// [`kas::theme::ThemeControl::list_schemes`]. The `active` scheme is
+        /// selected initially.
+        #[must_use]
+        pub fn with_schemes<I, S>(mut self, schemes: I, active: &str) -> Self
+        where
+            I: IntoIterator<Item = S>,
+            S: ToString,
+        {
+            self.schemes = schemes.into_iter().map(|s| s.to_string()).collect();
+            let index = self.schemes.iter().position(|s| s == active).unwrap_or(0);
+            let names = self.schemes.iter().map(|s| s.as_str());
+            self.scheme = ComboBox::new(names, index).on_select(|_, index| Some(index));
+            let _ = self.scheme.set_disabled(self.schemes.is_empty());
+            self
+        }
+
+        fn set_font_size(&mut self, mgr: &mut EventMgr, size: f32) {
+            mgr.adjust_theme(|theme| theme.set_font_size(size));
+        }
+
+        fn set_scheme(&mut self, mgr: &mut EventMgr, index: usize) {
+            if let Some(name) = self.schemes.get(index) {
+                mgr.adjust_theme(|theme| theme.set_scheme(name));
+            }
+        }
+
+        fn set_menu_delay(&mut self, mgr: &mut EventMgr, ms: u32) {
+            mgr.config().update_base(|config| config.menu_delay_ms = ms);
+        }
+
+        fn set_double_click(&mut self, mgr: &mut EventMgr, ms: u32) {
+            mgr.config().update_base(|config| config.double_click_ms = ms);
+        }
+
+        fn set_mouse_pan(&mut self, mgr: &mut EventMgr, index: usize) {
+            let pan = MOUSE_PAN[index];
+            mgr.config().update_base(|config| config.mouse_pan = pan);
+        }
+
+        fn set_mouse_text_pan(&mut self, mgr: &mut EventMgr, index: usize) {
+            let pan = MOUSE_PAN[index];
+            mgr.config().update_base(|config| config.mouse_text_pan = pan);
+        }
+
+        fn set_mouse_nav_focus(&mut self, mgr: &mut EventMgr, state: bool) {
+            mgr.config().update_base(|config| config.mouse_nav_focus = state);
+        }
+
+        fn set_touch_nav_focus(&mut self, mgr: &mut EventMgr, state: bool) {
+            mgr.config().update_base(|config| config.touch_nav_focus = state);
+        }
+
+        fn set_scroll_natural(&mut self, mgr: &mut EventMgr, state: bool) {
+            mgr.config().update_base(|config| config.scroll_natural = state);
+        }
+
+        fn shortcuts_changed(&mut self, mgr: &mut EventMgr) {
+            let mut action = TkAction::empty();
+            for entry in self.shortcuts.inner_mut().iter_mut() {
+                action |= entry.refresh(mgr);
+            }
+            *mgr |= action;
+        }
+
+        fn save(&mut self, mgr: &mut EventMgr, _: ()) {
+            mgr.save_config();
+        }
+    }
+
+    impl WidgetConfig for Self {
+        fn configure(&mut self, mgr: &mut SetRectMgr) {
+            let size_mgr = mgr.size_mgr();
+            let font_size = size_mgr.pixels_from_em(1.0) / size_mgr.pixels_from_points(1.0);
+            let mut action = self.font_size.set_value(font_size);
+
+            let config = mgr.config();
+            action |= self.menu_delay.set_value(config.menu_delay().as_millis().cast());
+            let double_click = config.double_click_timeout().as_millis().cast();
+            action |= self.double_click.set_value(double_click);
+            action |= self.mouse_pan.set_active(mouse_pan_index(config.mouse_pan()));
+            action |= self.mouse_text_pan.set_active(mouse_pan_index(config.mouse_text_pan()));
+            action |= self.mouse_nav_focus.set_bool(config.mouse_nav_focus());
+            action |= self.touch_nav_focus.set_bool(config.touch_nav_focus());
+            action |= self.scroll_natural.set_bool(config.scroll_natural());
+            *mgr |= action;
+        }
+    }
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self::new()
+    }
+}
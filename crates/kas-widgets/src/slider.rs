@@ -9,8 +9,9 @@ use std::fmt::Debug;
 use std::ops::{Add, Sub};
 use std::time::Duration;
 
-use super::DragHandle;
-use kas::event::{self, Command};
+use super::{DragHandle, TrackClick};
+use kas::event::{self, Command, ScrollDelta};
+use kas::geom::Vec2;
 use kas::prelude::*;
 
 /// Requirements on type used by [`Slider`]
@@ -82,16 +83,34 @@ impl SliderType for Duration {
     }
 }
 
+/// Describe a value as a percentage, optionally with a `label`
+pub(crate) fn describe_fraction(label: Option<&str>, frac: f64) -> String {
+    let percent = (frac * 100.0).round();
+    match label {
+        Some(label) => format!("{} {}%", label, percent),
+        None => format!("{}%", percent),
+    }
+}
+
 widget! {
     /// A slider
     ///
     /// Sliders allow user input of a value from a fixed range.
+    ///
+    /// The value is described as a percentage of the range (see
+    /// [`WidgetConfig::value_description`] and [`Slider::with_value_label`]);
+    /// changes caused by user input are announced via
+    /// [`EventState::announce_value`].
+    ///
+    /// The value may be adjusted by dragging the handle (hold Shift for fine
+    /// adjustment), by clicking the track (see [`Slider::with_track_click`]),
+    /// with the mouse wheel while hovered or via the keyboard.
+    ///
+    /// The new value is emitted as a message on each change. While the handle
+    /// is dragged, these values are intermediate ([`Slider::is_dragging`]
+    /// returns true); on release the final value is emitted again.
     #[derive(Clone, Debug, Default)]
     #[handler(msg = T)]
-    #[widget{
-        key_nav = true;
-        hover_highlight = true;
-    }]
     pub struct Slider<T: SliderType, D: Directional> {
         #[widget_core]
         core: CoreData,
@@ -100,6 +119,9 @@ widget! {
         range: (T, T),
         step: T,
         value: T,
+        value_label: Option<String>,
+        track_click: TrackClick,
+        drag_start: Option<T>,
         #[widget]
         handle: DragHandle,
     }
@@ -138,6 +160,9 @@ widget! {
                 range: (min, max),
                 step,
                 value,
+                value_label: None,
+                track_click: TrackClick::Jump,
+                drag_start: None,
                 handle: DragHandle::new(),
             }
         }
@@ -155,12 +180,40 @@ widget! {
             self
         }
 
+        /// Set a label for value descriptions (chain style)
+        ///
+        /// For example, with label "Volume" the value is described as
+        /// "Volume 75%".
+        #[must_use]
+        pub fn with_value_label<S: ToString>(mut self, label: S) -> Self {
+            self.value_label = Some(label.to_string());
+            self
+        }
+
+        /// Set the behaviour of clicks on the track (chain style)
+        ///
+        /// With [`TrackClick::Page`], a click moves the value by `step * 16`.
+        /// Default: [`TrackClick::Jump`].
+        #[must_use]
+        pub fn with_track_click(mut self, track_click: TrackClick) -> Self {
+            self.track_click = track_click;
+            self
+        }
+
         /// Get the current value
         #[inline]
         pub fn value(&self) -> T {
             self.value
         }
 
+        /// True while the handle is being dragged
+        ///
+        /// Values emitted while dragging are intermediate.
+        #[inline]
+        pub fn is_dragging(&self) -> bool {
+            self.handle.is_dragging()
+        }
+
         /// Set the value
         ///
         /// Returns [`TkAction::REDRAW`] if a redraw is required.
@@ -178,6 +231,71 @@ widget! {
             }
         }
 
+        // Page step: step * 16
+        fn page(&self) -> T {
+            // Generics makes this easier than constructing a literal and multiplying!
+            let mut x = self.step + self.step;
+            x = x + x;
+            x = x + x;
+            x + x
+        }
+
+        // Value plus x if increase, else minus x, clamped to the range
+        fn value_moved_by(&self, x: T, increase: bool) -> T {
+            if increase {
+                if x > self.range.1 - self.value {
+                    self.range.1
+                } else {
+                    self.value + x
+                }
+            } else if x > self.value - self.range.0 {
+                self.range.0
+            } else {
+                self.value - x
+            }
+        }
+
+        // Value after a mouse wheel or touchpad scroll: up or right increases
+        fn value_scrolled(&self, delta: ScrollDelta) -> T {
+            let vertical = self.direction.is_vertical();
+            match delta {
+                ScrollDelta::LineDelta(x, y) => {
+                    let lines = if y != 0.0 { y } else { -x };
+                    let x = self.step.mul_f64(f64::from(lines.abs()));
+                    self.value_moved_by(x, lines > 0.0)
+                }
+                ScrollDelta::PixelDelta(d) => {
+                    let (d, max_offset) = match vertical {
+                        false => (-d.0, self.handle.max_offset().0),
+                        true => (d.1, self.handle.max_offset().1),
+                    };
+                    let frac = (f64::from(d.abs()) / f64::from(max_offset.max(1))).min(1.0);
+                    let x = (self.range.1 - self.range.0).mul_f64(frac);
+                    self.value_moved_by(x, d > 0)
+                }
+            }
+        }
+
+        // Set the value from user input other than dragging
+        fn set_value_by_user(&mut self, mgr: &mut EventMgr, value: T) -> Response<T> {
+            let action = self.set_value(value);
+            if action.is_empty() {
+                Response::Used
+            } else {
+                mgr.send_action(action);
+                mgr.announce_value(self.id_ref(), self.describe());
+                Response::Msg(self.value)
+            }
+        }
+
+        fn describe(&self) -> String {
+            let a = self.value - self.range.0;
+            let b = self.range.1 - self.range.0;
+            // NOTE: f64::max maps NaN (from an empty range) to 0
+            let frac = a.div_as_f64(b).max(0.0);
+            describe_fraction(self.value_label.as_deref(), frac)
+        }
+
         // translate value to offset in local coordinates
         fn offset(&self) -> Offset {
             let a = self.value - self.range.0;
@@ -196,12 +314,12 @@ widget! {
 
         // true if not equal to old value
         #[allow(clippy::neg_cmp_op_on_partial_ord)]
-        fn set_offset(&mut self, offset: Offset) -> bool {
+        fn set_offset(&mut self, offset: Vec2) -> bool {
             let b = self.range.1 - self.range.0;
-            let max_offset = self.handle.max_offset();
+            let max_offset = Vec2::from(self.handle.max_offset());
             let mut a = match self.direction.is_vertical() {
-                false => b.mul_f64(offset.0 as f64 / max_offset.0 as f64),
-                true => b.mul_f64(offset.1 as f64 / max_offset.1 as f64),
+                false => b.mul_f64(f64::from(offset.0) / f64::from(max_offset.0)),
+                true => b.mul_f64(f64::from(offset.1) / f64::from(max_offset.1)),
             };
             if self.direction.is_reversed() {
                 a = b - a;
@@ -222,6 +340,18 @@ widget! {
         }
     }
 
+    impl WidgetConfig for Self {
+        fn key_nav(&self) -> bool {
+            true
+        }
+        fn hover_highlight(&self) -> bool {
+            true
+        }
+        fn value_description(&self) -> Option<String> {
+            Some(self.describe())
+        }
+    }
+
     impl Layout for Self {
         fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
             let (size, min_len) = size_mgr.slider();
@@ -269,16 +399,37 @@ widget! {
                 return Response::Unused;
             }
 
+            if let Event::Scroll(delta) = event {
+                let value = self.value_scrolled(delta);
+                return self.set_value_by_user(mgr, value);
+            }
+
             let offset = if self.handle.id().is_ancestor_of(&id) {
                 match event {
                     Event::NavFocus(key_focus) => {
                         mgr.set_nav_focus(self.id(), key_focus);
                         return Response::Used; // NavFocus event will be sent to self
                     }
-                    event => match self.handle.send(mgr, id, event).try_into() {
-                        Ok(res) => return res,
-                        Err(offset) => offset,
-                    },
+                    event => {
+                        let is_start = matches!(event, Event::PressStart { .. });
+                        let is_end = matches!(event, Event::PressEnd { .. });
+                        if is_start {
+                            self.drag_start = Some(self.value);
+                        }
+                        match self.handle.send(mgr, id, event).try_into() {
+                            Ok(res) => {
+                                // Emit the final value, if changed by dragging
+                                if let Some(start) = self.drag_start.filter(|_| is_end) {
+                                    self.drag_start = None;
+                                    if start != self.value {
+                                        return Response::Msg(self.value);
+                                    }
+                                }
+                                return res;
+                            }
+                            Err(_) => self.handle.drag_offset(),
+                        }
+                    }
                 }
             } else {
                 debug_assert_eq!(id, self.id());
@@ -292,45 +443,43 @@ widget! {
                     Event::Command(cmd, _) => {
                         let rev = self.direction.is_reversed();
                         let v = match cmd {
-                            Command::Left | Command::Up => match rev {
-                                false => self.value - self.step,
-                                true => self.value + self.step,
-                            },
-                            Command::Right | Command::Down => match rev {
-                                false => self.value + self.step,
-                                true => self.value - self.step,
-                            },
+                            Command::Left | Command::Up => self.value_moved_by(self.step, rev),
+                            Command::Right | Command::Down => {
+                                self.value_moved_by(self.step, !rev)
+                            }
                             Command::PageUp | Command::PageDown => {
-                                // Generics makes this easier than constructing a literal and multiplying!
-                                let mut x = self.step + self.step;
-                                x = x + x;
-                                x = x + x;
-                                x = x + x;
-                                match rev == (cmd == Command::PageDown) {
-                                    false => self.value + x,
-                                    true => self.value - x,
-                                }
+                                let increase = rev != (cmd == Command::PageDown);
+                                self.value_moved_by(self.page(), increase)
                             }
                             Command::Home => self.range.0,
                             Command::End => self.range.1,
                             _ => return Response::Unused,
                         };
-                        let action = self.set_value(v);
-                        return if action.is_empty() {
-                            Response::Used
-                        } else {
-                            mgr.send_action(action);
-                            Response::Msg(self.value)
-                        };
-                    }
-                    Event::PressStart { source, coord, .. } => {
-                        self.handle.handle_press_on_track(mgr, source, coord)
+                        return self.set_value_by_user(mgr, v);
                     }
+                    Event::PressStart { source, coord, .. } => match self.track_click {
+                        TrackClick::Jump => {
+                            self.drag_start = Some(self.value);
+                            let offset = self.handle.handle_press_on_track(mgr, source, coord);
+                            Vec2::from(offset)
+                        }
+                        TrackClick::Page => {
+                            let handle = self.handle.rect();
+                            let before = match self.direction.is_vertical() {
+                                false => coord.0 < handle.pos.0,
+                                true => coord.1 < handle.pos.1,
+                            };
+                            let increase = before == self.direction.is_reversed();
+                            let value = self.value_moved_by(self.page(), increase);
+                            return self.set_value_by_user(mgr, value);
+                        }
+                    },
                     _ => return Response::Unused,
                 }
             };
 
             let r = if self.set_offset(offset) {
+                mgr.announce_value(self.id_ref(), self.describe());
                 Response::Msg(self.value)
             } else {
                 Response::Used
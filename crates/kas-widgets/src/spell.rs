@@ -0,0 +1,205 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Spell-checking hooks
+//!
+//! Text widgets may be assigned a [`SpellChecker`]. With feature `spellcheck`,
+//! a simple dictionary-based implementation is available: `DictionaryChecker`.
+
+use std::fmt::Debug;
+use std::ops::Range;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A spell checker
+///
+/// A spell checker may be assigned to text widgets (see
+/// [`EditField::with_spell_checker`](crate::EditField::with_spell_checker)).
+/// The widget calls [`SpellChecker::check`] with the visible part of its text
+/// and marks misspelled words with a wavy underline.
+///
+/// Checkers are usually shared between widgets, hence methods take `&self`;
+/// use interior mutability where required.
+pub trait SpellChecker: Debug {
+    /// Check a `range` of `text`
+    ///
+    /// The `range` is aligned to line boundaries. Returns the byte ranges of
+    /// misspelled words within `text`; these must be sorted and may not
+    /// overlap. The [`words`] helper may be used to find words.
+    fn check(&self, text: &str, range: Range<usize>) -> Vec<Range<usize>>;
+
+    /// Get suggested replacements for a misspelled `word`
+    ///
+    /// Suggestions should be ordered from most to least likely. By default,
+    /// no suggestions are made.
+    fn suggest(&self, word: &str) -> Vec<String> {
+        let _ = word;
+        vec![]
+    }
+
+    /// Add a `word` to the user's dictionary
+    ///
+    /// By default, this does nothing.
+    fn add_word(&self, word: &str) {
+        let _ = word;
+    }
+}
+
+/// Iterate over words in a `range` of `text`
+///
+/// Yields the byte range (within `text`) and slice of each word. Word
+/// boundaries follow Unicode rules; segments containing no alphabetic
+/// characters (whitespace, punctuation, numbers) are skipped.
+pub fn words(text: &str, range: Range<usize>) -> impl Iterator<Item = (Range<usize>, &str)> {
+    let start = range.start;
+    text[range]
+        .split_word_bound_indices()
+        .filter(|(_, word)| word.chars().any(|c| c.is_alphabetic()))
+        .map(move |(i, word)| (start + i..start + i + word.len(), word))
+}
+
+#[cfg(feature = "spellcheck")]
+pub use dictionary::DictionaryChecker;
+
+#[cfg(feature = "spellcheck")]
+mod dictionary {
+    use super::{words, SpellChecker};
+    use std::cell::RefCell;
+    use std::collections::HashSet;
+    use std::ops::Range;
+    use std::path::Path;
+
+    /// Maximum number of suggestions returned
+    const MAX_SUGGESTIONS: usize = 8;
+
+    /// A dictionary-based spell checker
+    ///
+    /// Dictionaries may be loaded from Hunspell-format `.dic` files. Affix
+    /// rules are not supported: flags following a `/` are ignored, thus only
+    /// the listed stem forms are recognised.
+    ///
+    /// Words are accepted if listed exactly or if the lower-case form is
+    /// listed. Suggestions are listed words within one edit (insertion,
+    /// deletion, substitution or transposition) of the misspelled word.
+    #[cfg_attr(doc_cfg, doc(cfg(feature = "spellcheck")))]
+    #[derive(Clone, Debug, Default)]
+    pub struct DictionaryChecker {
+        words: RefCell<HashSet<String>>,
+    }
+
+    impl DictionaryChecker {
+        /// Construct with an empty dictionary
+        #[inline]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Construct from the contents of a Hunspell `.dic` file
+        ///
+        /// The first line (word count) is optional.
+        pub fn from_dic(contents: &str) -> Self {
+            let checker = Self::new();
+            let mut lines = contents.lines();
+            let first = lines.next().unwrap_or("");
+            if first.trim().parse::<usize>().is_err() {
+                checker.insert_dic_line(first);
+            }
+            for line in lines {
+                checker.insert_dic_line(line);
+            }
+            checker
+        }
+
+        /// Load a Hunspell `.dic` file
+        pub fn load_dic<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+            let contents = std::fs::read_to_string(path)?;
+            Ok(Self::from_dic(&contents))
+        }
+
+        /// Add a word
+        pub fn insert(&self, word: &str) {
+            self.words.borrow_mut().insert(word.to_string());
+        }
+
+        /// True if `word` is accepted
+        pub fn contains(&self, word: &str) -> bool {
+            let words = self.words.borrow();
+            words.contains(word) || words.contains(&word.to_lowercase())
+        }
+
+        /// Get the number of words in the dictionary
+        pub fn len(&self) -> usize {
+            self.words.borrow().len()
+        }
+
+        /// True if the dictionary is empty
+        pub fn is_empty(&self) -> bool {
+            self.words.borrow().is_empty()
+        }
+
+        fn insert_dic_line(&self, line: &str) {
+            // Comments start with '#' or whitespace (as used by some dictionaries)
+            if line.starts_with(|c: char| c == '#' || c.is_whitespace()) {
+                return;
+            }
+            let word = line.split('/').next().unwrap_or("").trim();
+            if !word.is_empty() {
+                self.insert(word);
+            }
+        }
+    }
+
+    impl SpellChecker for DictionaryChecker {
+        fn check(&self, text: &str, range: Range<usize>) -> Vec<Range<usize>> {
+            words(text, range)
+                .filter(|(_, word)| !self.contains(word))
+                .map(|(range, _)| range)
+                .collect()
+        }
+
+        fn suggest(&self, word: &str) -> Vec<String> {
+            let chars: Vec<char> = word.chars().collect();
+            let mut alphabet: Vec<char> = ('a'..='z').collect();
+            alphabet.extend(chars.iter().filter(|c| !c.is_ascii_lowercase()));
+
+            let mut candidates = vec![];
+            for i in 0..=chars.len() {
+                let (a, b) = chars.split_at(i);
+                if !b.is_empty() {
+                    // Deletion
+                    candidates.push(a.iter().chain(&b[1..]).collect::<String>());
+                }
+                if b.len() > 1 {
+                    // Transposition
+                    let swapped = [b[1], b[0]];
+                    candidates.push(a.iter().chain(&swapped).chain(&b[2..]).collect());
+                }
+                for c in &alphabet {
+                    if !b.is_empty() && b[0] != *c {
+                        // Substitution
+                        let s = std::iter::once(c);
+                        candidates.push(a.iter().chain(s).chain(&b[1..]).collect());
+                    }
+                    // Insertion
+                    candidates.push(a.iter().chain(std::iter::once(c)).chain(b).collect());
+                }
+            }
+
+            let mut suggestions: Vec<String> = vec![];
+            for candidate in candidates {
+                if self.contains(&candidate) && !suggestions.contains(&candidate) {
+                    suggestions.push(candidate);
+                    if suggestions.len() >= MAX_SUGGESTIONS {
+                        break;
+                    }
+                }
+            }
+            suggestions
+        }
+
+        fn add_word(&self, word: &str) {
+            self.insert(word);
+        }
+    }
+}
@@ -80,6 +80,7 @@ widget! {
         handles: Vec<DragHandle>,
         data: layout::DynRowStorage,
         direction: D,
+        restore: Vec<f32>,
     }
 
     impl WidgetChildren for Self {
@@ -170,6 +171,16 @@ widget! {
 
                 n += 1;
             }
+
+            let restore = std::mem::take(&mut self.restore);
+            if restore.len() == self.handles.len() {
+                for (n, frac) in restore.into_iter().enumerate() {
+                    let max = self.handles[n].max_offset().extract(self.direction);
+                    let offset = Offset::splat(i32::conv_nearest(frac * max as f32));
+                    let _ = self.handles[n].set_offset(offset);
+                    self.adjust_size(mgr, n);
+                }
+            }
         }
 
         fn find_id(&mut self, coord: Coord) -> Option<WidgetId> {
@@ -210,6 +221,37 @@ widget! {
         }
     }
 
+    impl SaveState for Self {
+        // Save handle positions as a fraction of the track length
+        fn save_state(&self) -> Option<StateValue> {
+            if self.handles.is_empty() {
+                return None;
+            }
+            let v = self.handles.iter().map(|h| {
+                let offset = h.offset().extract(self.direction);
+                let max = h.max_offset().extract(self.direction);
+                let frac = if max > 0 { offset as f64 / max as f64 } else { 0.0 };
+                StateValue::Float(frac)
+            });
+            Some(StateValue::List(v.collect()))
+        }
+
+        fn restore_state(&mut self, state: &StateValue) -> TkAction {
+            let list = state.as_list().unwrap_or(&[]);
+            let restore: Option<Vec<f32>> = list
+                .iter()
+                .map(|v| v.as_float().map(|x| x as f32))
+                .collect();
+            match restore {
+                Some(v) if v.len() == self.handles.len() && !v.is_empty() => {
+                    self.restore = v;
+                    TkAction::RESIZE
+                }
+                _ => TkAction::empty(),
+            }
+        }
+    }
+
     impl event::SendEvent for Self {
         fn send(&mut self, mgr: &mut EventMgr, id: WidgetId, event: Event) -> Response<Self::Msg> {
             if !self.is_disabled() && !self.widgets.is_empty() {
@@ -275,6 +317,7 @@ impl<D: Directional, W: Widget> Splitter<D, W> {
             handles,
             data: Default::default(),
             direction,
+            restore: vec![],
         }
     }
 
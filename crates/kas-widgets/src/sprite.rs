@@ -5,17 +5,25 @@
 
 //! 2D pixmap widget
 
-use kas::layout::SpriteDisplay;
+use kas::layout::{AspectScaling, SpriteDisplay};
 use kas::prelude::*;
+use kas::theme::icons::{self, IconData, IconSource};
+use kas::theme::{IconName, IconSize, TextClass};
+use std::borrow::Cow;
 use std::path::PathBuf;
 
 widget! {
     /// An image with margins
+    ///
+    /// The image is scaled to its allocation according to its
+    /// [fit mode](Image::with_aspect). With [`AspectScaling::Cover`], the
+    /// image is clipped to the allocated rect.
     #[derive(Clone, Debug, Default)]
     pub struct Image {
         #[widget_core]
         core: CoreData,
         sprite: SpriteDisplay,
+        image_rect: Rect,
         path: PathBuf,
         do_load: bool,
         id: Option<ImageId>,
@@ -46,23 +54,215 @@ widget! {
 
         fn set_rect(&mut self, _: &mut SetRectMgr, rect: Rect, align: AlignHints) {
             self.core_data_mut().rect = self.sprite.align_rect(rect, align);
+            self.image_rect = self.sprite.sprite_rect(rect, align);
         }
 
         fn draw(&mut self, mut draw: DrawMgr) {
             let mut draw = draw.with_core(self.core_data());
             if let Some(id) = self.id {
-                draw.image(id, self.rect());
+                let rect = self.image_rect;
+                if self.sprite.aspect == AspectScaling::Cover {
+                    draw.with_clip_region(self.core.rect, Offset::ZERO, |mut draw| {
+                        draw.image(id, rect);
+                    });
+                } else {
+                    draw.image(id, rect);
+                }
+            }
+        }
+    }
+}
+
+widget! {
+    /// An icon
+    ///
+    /// Displays either a pre-loaded image or a named icon (see
+    /// [`kas::theme::icons`]) as a square. For a pre-loaded image, the side is
+    /// derived from the line height of [`TextClass::Button`] text, multiplied
+    /// by a scale factor; for a named icon, the side is chosen by the theme
+    /// (see [`SizeMgr::icon`]). Margins match those of text, making this
+    /// suitable for use within buttons (see [`crate::Button::image`] and
+    /// [`crate::TextButton::with_icon`]).
+    ///
+    /// An icon without an image or name has zero size.
+    #[derive(Clone, Debug)]
+    pub struct Icon {
+        #[widget_core]
+        core: CoreData,
+        id: Option<ImageId>,
+        name: Option<IconName>,
+        size: IconSize,
+        scale: f32,
+        side: i32,
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
+            if self.name.is_some() {
+                self.side = size_mgr.icon(self.size);
+            } else if self.id.is_some() {
+                let height = f32::conv(size_mgr.line_height(TextClass::Button));
+                self.side = (self.scale * height).cast_nearest();
+            } else {
+                return SizeRules::EMPTY;
+            }
+            let margins = size_mgr.text_margins().extract(axis);
+            SizeRules::fixed(self.side, margins)
+        }
+
+        fn set_rect(&mut self, _: &mut SetRectMgr, rect: Rect, align: AlignHints) {
+            self.core.rect = align
+                .complete(Align::Center, Align::Center)
+                .aligned_rect(Size::splat(self.side), rect);
+        }
+
+        fn draw(&mut self, mut draw: DrawMgr) {
+            let mut draw = draw.with_core(self.core_data());
+            if let Some(id) = self.id {
+                draw.image(id, self.core.rect);
+            } else if let Some(name) = self.name.as_ref() {
+                draw.icon(name, self.core.rect);
             }
         }
     }
 }
 
+impl Default for Icon {
+    fn default() -> Self {
+        Icon {
+            core: Default::default(),
+            id: None,
+            name: None,
+            size: IconSize::Small,
+            scale: 1.0,
+            side: 0,
+        }
+    }
+}
+
+impl Icon {
+    /// Construct with an image
+    ///
+    /// The image must have been loaded already, e.g. via
+    /// [`DrawShared::image_from_path`].
+    #[inline]
+    pub fn new(id: ImageId) -> Self {
+        Icon {
+            id: Some(id),
+            ..Default::default()
+        }
+    }
+
+    /// Construct with a named icon
+    ///
+    /// The icon is resolved when drawn; see [`kas::theme::icons`].
+    #[inline]
+    pub fn named<N: Into<IconName>>(name: N) -> Self {
+        Icon {
+            name: Some(name.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Construct with SVG data
+    ///
+    /// The image is registered as an icon under a name derived from its
+    /// content (see [`icons::register_unique`]) and sized as a named icon.
+    /// Rendering requires feature `svg`.
+    pub fn svg<D: Into<Cow<'static, [u8]>>>(data: D) -> Self {
+        let data = IconData::new(IconSource::Svg(data.into()));
+        Icon::named(icons::register_unique(data))
+    }
+
+    /// Set the size of a named icon (chain style)
+    ///
+    /// By default this is [`IconSize::Small`].
+    #[inline]
+    #[must_use]
+    pub fn with_size(mut self, size: IconSize) -> Self {
+        self.size = size;
+        self
+    }
+
+    /// Set the size of a named icon
+    #[inline]
+    pub fn set_size(&mut self, size: IconSize) -> TkAction {
+        self.size = size;
+        TkAction::RESIZE
+    }
+
+    /// Set the scale factor relative to line height (chain style)
+    ///
+    /// By default this is `1.0`.
+    #[inline]
+    #[must_use]
+    pub fn with_scale(mut self, scale: f32) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Set the scale factor relative to line height
+    #[inline]
+    pub fn set_scale(&mut self, scale: f32) -> TkAction {
+        self.scale = scale;
+        TkAction::RESIZE
+    }
+
+    /// Get the image
+    #[inline]
+    pub fn image(&self) -> Option<ImageId> {
+        self.id
+    }
+
+    /// Set or clear the image
+    ///
+    /// This replaces any named icon.
+    /// Note: the image is not freed by this widget.
+    pub fn set_image(&mut self, id: Option<ImageId>) -> TkAction {
+        let action = match (self.id, id) {
+            (Some(_), Some(_)) => TkAction::REDRAW,
+            (None, None) if self.name.is_none() => TkAction::empty(),
+            _ => TkAction::RESIZE,
+        };
+        self.id = id;
+        self.name = None;
+        action
+    }
+
+    /// Get the icon name
+    #[inline]
+    pub fn name(&self) -> Option<&IconName> {
+        self.name.as_ref()
+    }
+
+    /// Set or clear the named icon
+    ///
+    /// This replaces any image.
+    pub fn set_name(&mut self, name: Option<IconName>) -> TkAction {
+        let action = match (self.name.is_some(), name.is_some()) {
+            (true, true) => TkAction::REDRAW,
+            (false, false) if self.id.is_none() => TkAction::empty(),
+            _ => TkAction::RESIZE,
+        };
+        self.name = name;
+        self.id = None;
+        action
+    }
+
+    /// True if neither an image nor a name is set
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.id.is_none() && self.name.is_none()
+    }
+}
+
 impl Image {
     /// Construct with a path
     pub fn new<P: Into<PathBuf>>(path: P) -> Self {
         Image {
             core: Default::default(),
             sprite: Default::default(),
+            image_rect: Rect::ZERO,
             path: path.into(),
             do_load: true,
             id: None,
@@ -81,7 +281,35 @@ impl Image {
     #[inline]
     pub fn set_scaling(&mut self, f: impl FnOnce(&mut SpriteDisplay)) -> TkAction {
         f(&mut self.sprite);
-        // NOTE: if only `aspect` is changed, REDRAW is enough
+        TkAction::RESIZE
+    }
+
+    /// Set the fit mode (chain style)
+    ///
+    /// This controls how the image is scaled within its allocation:
+    ///
+    /// -   [`AspectScaling::None`]: draw at native size (1:1)
+    /// -   [`AspectScaling::Fixed`]: scale to fit, keeping aspect ratio
+    ///     (letterboxing); this is the default
+    /// -   [`AspectScaling::Free`]: scale to fill, ignoring aspect ratio
+    /// -   [`AspectScaling::Cover`]: scale to cover, keeping aspect ratio
+    ///     (cropping)
+    ///
+    /// Note that scaling beyond the ideal size requires a stretch policy
+    /// (see [`Image::with_scaling`]).
+    #[inline]
+    #[must_use]
+    pub fn with_aspect(mut self, aspect: AspectScaling) -> Self {
+        self.sprite.aspect = aspect;
+        self
+    }
+
+    /// Set the fit mode
+    ///
+    /// See [`Image::with_aspect`].
+    #[inline]
+    pub fn set_aspect(&mut self, aspect: AspectScaling) -> TkAction {
+        self.sprite.aspect = aspect;
         TkAction::RESIZE
     }
 
@@ -86,6 +86,19 @@ widget! {
         }
     }
 
+    impl SaveState for Self {
+        fn save_state(&self) -> Option<StateValue> {
+            Some(StateValue::Int(self.active.cast()))
+        }
+
+        fn restore_state(&mut self, state: &StateValue) -> TkAction {
+            match state.as_int().and_then(|x| usize::try_from(x).ok()) {
+                Some(active) if active < self.widgets.len() => self.set_active(active),
+                _ => TkAction::empty(),
+            }
+        }
+    }
+
     impl event::SendEvent for Self {
         fn send(&mut self, mgr: &mut EventMgr, id: WidgetId, event: Event) -> Response<Self::Msg> {
             if !self.is_disabled() {
@@ -252,13 +265,13 @@ impl<W: Widget> Stack<W> {
     ///
     /// Panics if `index` is out of bounds.
     ///
-    /// Triggers a [reconfigure action](EventState::send_action).
-    // TODO: in theory it is possible to avoid a reconfigure where both widgets
-    // have no children and have compatible size. Is this a good idea and can
-    // we somehow test "has compatible size"?
-    pub fn replace(&mut self, index: usize, mut widget: W) -> (W, TkAction) {
+    /// The new widget is configured immediately (via `mgr`); other widgets
+    /// keep their [`WidgetId`]. Triggers a resize action.
+    pub fn replace(&mut self, mgr: &mut SetRectMgr, index: usize, mut widget: W) -> W {
         std::mem::swap(&mut widget, &mut self.widgets[index]);
-        (widget, TkAction::RECONFIGURE)
+        mgr.configure(self.id().make_child(index), &mut self.widgets[index]);
+        *mgr |= TkAction::RESIZE;
+        widget
     }
 
     /// Append child widgets from an iterator
@@ -0,0 +1,147 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Switch widget
+
+use kas::event::{self, Command};
+use kas::prelude::*;
+use std::rc::Rc;
+
+widget! {
+    /// An on/off switch
+    ///
+    /// This is a two-state toggle similar in function to a [`CheckBoxBare`],
+    /// but drawn as a track with a sliding thumb. Themes may animate the thumb
+    /// when the state changes.
+    ///
+    /// The switch may be toggled by clicking or [`Event::Activate`]; when it
+    /// has navigation focus, the left and right arrow keys switch it off and
+    /// on respectively.
+    ///
+    /// [`CheckBoxBare`]: crate::CheckBoxBare
+    #[autoimpl(Debug skip self.on_toggle)]
+    #[derive(Clone, Default)]
+    #[widget{
+        key_nav = true;
+        hover_highlight = true;
+    }]
+    pub struct Switch<M: 'static> {
+        #[widget_core]
+        core: CoreData,
+        state: bool,
+        on_toggle: Option<Rc<dyn Fn(&mut EventMgr, bool) -> Option<M>>>,
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
+            let size = size_mgr.switch();
+            self.core.rect.size = size;
+            let margins = size_mgr.outer_margins();
+            SizeRules::extract_fixed(axis, size, margins)
+        }
+
+        fn set_rect(&mut self, _: &mut SetRectMgr, rect: Rect, align: AlignHints) {
+            let rect = align
+                .complete(Align::Center, Align::Center)
+                .aligned_rect(self.rect().size, rect);
+            self.core.rect = rect;
+        }
+
+        fn draw(&mut self, mut draw: DrawMgr) {
+            draw.with_core(self.core_data()).switch(self.core.rect, self.state);
+        }
+    }
+
+    impl Switch<VoidMsg> {
+        /// Construct a switch
+        #[inline]
+        pub fn new() -> Self {
+            Switch {
+                core: Default::default(),
+                state: false,
+                on_toggle: None,
+            }
+        }
+
+        /// Set event handler `f`
+        ///
+        /// On toggle (through user input events or [`Event::Activate`]) the
+        /// closure `f` is called. The result of `f` is converted to
+        /// [`Response::Msg`] or [`Response::Update`] and returned to the parent.
+        #[inline]
+        #[must_use]
+        pub fn on_toggle<M, F>(self, f: F) -> Switch<M>
+        where
+            F: Fn(&mut EventMgr, bool) -> Option<M> + 'static,
+        {
+            Switch {
+                core: self.core,
+                state: self.state,
+                on_toggle: Some(Rc::new(f)),
+            }
+        }
+    }
+
+    impl Self {
+        /// Construct a switch with event handler `f`
+        ///
+        /// On toggle (through user input events or [`Event::Activate`]) the
+        /// closure `f` is called. The result of `f` is converted to
+        /// [`Response::Msg`] or [`Response::Update`] and returned to the parent.
+        #[inline]
+        pub fn new_on<F>(f: F) -> Self
+        where
+            F: Fn(&mut EventMgr, bool) -> Option<M> + 'static,
+        {
+            Switch::new().on_toggle(f)
+        }
+
+        /// Set the initial state of the switch.
+        #[inline]
+        #[must_use]
+        pub fn with_state(mut self, state: bool) -> Self {
+            self.state = state;
+            self
+        }
+
+        fn toggle_to(&mut self, mgr: &mut EventMgr, state: bool) -> Response<M> {
+            if state == self.state {
+                return Response::Used;
+            }
+            self.state = state;
+            mgr.redraw(self.id());
+            Response::update_or_msg(self.on_toggle.as_ref().and_then(|f| f(mgr, state)))
+        }
+    }
+
+    impl HasBool for Self {
+        fn get_bool(&self) -> bool {
+            self.state
+        }
+
+        fn set_bool(&mut self, state: bool) -> TkAction {
+            self.state = state;
+            TkAction::REDRAW
+        }
+    }
+
+    impl event::Handler for Self {
+        type Msg = M;
+
+        #[inline]
+        fn activation_via_press(&self) -> bool {
+            true
+        }
+
+        fn handle(&mut self, mgr: &mut EventMgr, event: Event) -> Response<M> {
+            match event {
+                Event::Activate => self.toggle_to(mgr, !self.state),
+                Event::Command(Command::Left, _) => self.toggle_to(mgr, false),
+                Event::Command(Command::Right, _) => self.toggle_to(mgr, true),
+                _ => Response::Unused,
+            }
+        }
+    }
+}
@@ -0,0 +1,129 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Title bar
+
+use crate::TextButton;
+use kas::event::WindowRegion;
+use kas::layout;
+use kas::prelude::*;
+use kas::theme::TextClass;
+
+#[derive(Clone, Copy, Debug)]
+enum TitleButton {
+    Minimize,
+    Maximize,
+    Close,
+}
+
+widget! {
+    /// A window title bar
+    ///
+    /// This shows the window title and buttons to minimize, maximize and close
+    /// the window. Dragging the title moves the window while double-clicking
+    /// toggles maximization.
+    ///
+    /// This is used by [`crate::Window`] to draw client-side decorations
+    /// (see [`kas::Decorations::Kas`]).
+    #[derive(Clone, Debug)]
+    pub struct TitleBar {
+        #[widget_core]
+        core: CoreData,
+        title: Text<String>,
+        layout_title: layout::TextStorage,
+        layout_list: layout::FixedRowStorage<4>,
+        #[widget(use_msg = handle_button)]
+        minimize: TextButton<TitleButton>,
+        #[widget(use_msg = handle_button)]
+        maximize: TextButton<TitleButton>,
+        #[widget(use_msg = handle_button)]
+        close: TextButton<TitleButton>,
+    }
+
+    impl Self {
+        /// Construct with the given `title`
+        pub fn new<T: ToString>(title: T) -> Self {
+            TitleBar {
+                core: Default::default(),
+                title: Text::new_single(title.to_string()),
+                layout_title: Default::default(),
+                layout_list: Default::default(),
+                minimize: TextButton::new_msg("−", TitleButton::Minimize),
+                maximize: TextButton::new_msg("□", TitleButton::Maximize),
+                close: TextButton::new_msg("×", TitleButton::Close),
+            }
+        }
+
+        /// Get the title
+        pub fn title(&self) -> &str {
+            self.title.as_str()
+        }
+
+        /// Set the title
+        pub fn set_title<T: ToString>(&mut self, title: T) -> TkAction {
+            let text = title.to_string();
+            kas::text::util::set_text_and_prepare(&mut self.title, text, self.core.rect.size)
+        }
+
+        fn handle_button(&mut self, mgr: &mut EventMgr, button: TitleButton) {
+            match button {
+                TitleButton::Minimize => mgr.set_minimized(true),
+                TitleButton::Maximize => {
+                    let maximized = mgr.is_maximized();
+                    mgr.set_maximized(!maximized);
+                }
+                TitleButton::Close => mgr.send_action(TkAction::CLOSE),
+            }
+        }
+    }
+
+    impl WidgetConfig for Self {
+        fn window_region(&self) -> WindowRegion {
+            WindowRegion::Drag
+        }
+    }
+
+    impl Layout for Self {
+        fn layout(&mut self) -> layout::Layout<'_> {
+            let title = layout::Layout::text(&mut self.layout_title, &mut self.title, TextClass::Label);
+            let arr = [
+                title,
+                layout::Layout::single(&mut self.minimize),
+                layout::Layout::single(&mut self.maximize),
+                layout::Layout::single(&mut self.close),
+            ];
+            layout::Layout::list(arr.into_iter(), Direction::Right, &mut self.layout_list)
+        }
+
+        fn find_id(&mut self, coord: Coord) -> Option<WidgetId> {
+            if !self.rect().contains(coord) {
+                return None;
+            }
+            self.minimize
+                .find_id(coord)
+                .or_else(|| self.maximize.find_id(coord))
+                .or_else(|| self.close.find_id(coord))
+                .or(Some(self.id()))
+        }
+    }
+
+    impl Handler for Self {
+        type Msg = VoidMsg;
+
+        fn handle(&mut self, mgr: &mut EventMgr, event: Event) -> Response<Self::Msg> {
+            match event {
+                // Single clicks are handled by the shell (see window_region)
+                Event::PressStart { source, .. } if source.is_primary() && !source.is_touch() => {
+                    if source.repetitions() == 2 {
+                        let maximized = mgr.is_maximized();
+                        mgr.set_maximized(!maximized);
+                    }
+                    Response::Used
+                }
+                _ => Response::Unused,
+            }
+        }
+    }
+}
@@ -0,0 +1,623 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Tool-bar
+
+use crate::{Icon, Menu, Row, SubMenu};
+use kas::event;
+use kas::layout::{self, solve_size_rules};
+use kas::prelude::*;
+use kas::theme::{IconName, TextClass};
+use std::fmt;
+use std::rc::Rc;
+
+enum ItemKind<M> {
+    Button(Rc<dyn Fn(&mut EventMgr) -> Option<M>>),
+    Toggle(bool, Rc<dyn Fn(&mut EventMgr, bool) -> Option<M>>),
+    Separator,
+}
+
+impl<M> Clone for ItemKind<M> {
+    fn clone(&self) -> Self {
+        match self {
+            ItemKind::Button(f) => ItemKind::Button(f.clone()),
+            ItemKind::Toggle(state, f) => ItemKind::Toggle(*state, f.clone()),
+            ItemKind::Separator => ItemKind::Separator,
+        }
+    }
+}
+
+impl<M> fmt::Debug for ItemKind<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ItemKind::Button(_) => f.write_str("Button"),
+            ItemKind::Toggle(state, _) => f.debug_tuple("Toggle").field(state).finish(),
+            ItemKind::Separator => f.write_str("Separator"),
+        }
+    }
+}
+
+widget! {
+    /// An item within a [`ToolBar`]
+    ///
+    /// This is either a push-button, a toggle-button or a separator. Buttons
+    /// use compact (menu-like) sizing and are only highlighted when hovered,
+    /// depressed or (for toggle-buttons) toggled on.
+    ///
+    /// Each item has a `key`, used to address the item via [`ToolBar`] methods.
+    ///
+    /// Buttons may show an [`Icon`] before the label (see
+    /// [`ToolItem::with_icon`] and [`ToolItem::with_named_icon`]); a button
+    /// with an icon and an empty label shows only the icon.
+    #[derive(Clone, Debug)]
+    pub struct ToolItem<M: 'static> {
+        #[widget_core]
+        core: CoreData,
+        key: String,
+        kind: ItemKind<M>,
+        #[widget]
+        icon: Icon,
+        label: Text<AccelString>,
+        layout_label: layout::TextStorage,
+        layout_list: layout::FixedRowStorage<2>,
+        layout_frame: layout::FrameStorage,
+    }
+
+    impl Self {
+        fn new_kind(key: &str, label: AccelString, kind: ItemKind<M>) -> Self {
+            ToolItem {
+                core: Default::default(),
+                key: key.to_string(),
+                kind,
+                icon: Default::default(),
+                label: Text::new_single(label),
+                layout_label: Default::default(),
+                layout_list: Default::default(),
+                layout_frame: Default::default(),
+            }
+        }
+
+        /// Construct a push-button with a given `key`, `label` and event handler `f`
+        ///
+        /// On activation (through user input events or [`Event::Activate`]) the
+        /// closure `f` is called. The result of `f` is converted to
+        /// [`Response::Msg`] or [`Response::Used`] and returned to the parent.
+        #[inline]
+        pub fn button_on<S: Into<AccelString>, F>(key: &str, label: S, f: F) -> Self
+        where
+            F: Fn(&mut EventMgr) -> Option<M> + 'static,
+        {
+            Self::new_kind(key, label.into(), ItemKind::Button(Rc::new(f)))
+        }
+
+        /// Construct a push-button with a given `key`, `label` and payload `msg`
+        ///
+        /// On activation a clone of `msg` is returned to the parent widget.
+        #[inline]
+        pub fn button<S: Into<AccelString>>(key: &str, label: S, msg: M) -> Self
+        where
+            M: Clone,
+        {
+            Self::button_on(key, label, move |_| Some(msg.clone()))
+        }
+
+        /// Construct a toggle-button with a given `key`, `label` and event handler `f`
+        ///
+        /// On toggle (through user input events or [`Event::Activate`]) the
+        /// closure `f` is called with the new state. The message generated by
+        /// `f`, if any, is returned for handling through the parent widget (or
+        /// other ancestor).
+        #[inline]
+        pub fn toggle<S: Into<AccelString>, F>(key: &str, label: S, f: F) -> Self
+        where
+            F: Fn(&mut EventMgr, bool) -> Option<M> + 'static,
+        {
+            Self::new_kind(key, label.into(), ItemKind::Toggle(false, Rc::new(f)))
+        }
+
+        /// Construct a separator
+        #[inline]
+        pub fn separator() -> Self {
+            Self::new_kind("", AccelString::default(), ItemKind::Separator)
+        }
+
+        /// Set the initial state of a toggle-button (chain style)
+        ///
+        /// This has no effect on other items.
+        #[inline]
+        #[must_use]
+        pub fn with_state(mut self, state: bool) -> Self {
+            let _ = self.set_bool(state);
+            self
+        }
+
+        /// Set an icon (chain style)
+        ///
+        /// The image must have been loaded already; see [`Icon`].
+        #[inline]
+        #[must_use]
+        pub fn with_icon(mut self, id: ImageId) -> Self {
+            let _ = self.icon.set_image(Some(id));
+            self
+        }
+
+        /// Set a named icon (chain style)
+        ///
+        /// See [`kas::theme::icons`].
+        #[inline]
+        #[must_use]
+        pub fn with_named_icon<N: Into<IconName>>(mut self, name: N) -> Self {
+            let _ = self.icon.set_name(Some(name.into()));
+            self
+        }
+
+        /// Set or clear the icon
+        pub fn set_icon(&mut self, id: Option<ImageId>) -> TkAction {
+            self.icon.set_image(id)
+        }
+
+        /// Set or clear the named icon
+        pub fn set_named_icon(&mut self, name: Option<IconName>) -> TkAction {
+            self.icon.set_name(name)
+        }
+
+        /// Get the item's key
+        #[inline]
+        pub fn key(&self) -> &str {
+            &self.key
+        }
+
+        /// True if this item is a separator
+        #[inline]
+        pub fn is_separator(&self) -> bool {
+            matches!(self.kind, ItemKind::Separator)
+        }
+
+        /// True if this item is a toggle-button
+        #[inline]
+        pub fn is_toggle(&self) -> bool {
+            matches!(self.kind, ItemKind::Toggle(..))
+        }
+    }
+
+    impl WidgetConfig for Self {
+        fn configure(&mut self, mgr: &mut SetRectMgr) {
+            mgr.add_accel_string(self.id_ref(), self.label.text());
+        }
+
+        fn key_nav(&self) -> bool {
+            !self.is_separator()
+        }
+        fn hover_highlight(&self) -> bool {
+            !self.is_separator()
+        }
+    }
+
+    impl Layout for Self {
+        fn layout(&mut self) -> layout::Layout<'_> {
+            let text = layout::Layout::text(&mut self.layout_label, &mut self.label, TextClass::MenuLabel);
+            let inner = if self.icon.is_empty() {
+                text
+            } else if self.label.as_str().is_empty() {
+                layout::Layout::single(&mut self.icon)
+            } else {
+                let icon = layout::Layout::single(&mut self.icon);
+                let list = [icon, text].into_iter();
+                layout::Layout::list(list, Direction::Right, &mut self.layout_list)
+            };
+            layout::Layout::frame(&mut self.layout_frame, inner)
+        }
+
+        fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
+            if self.is_separator() {
+                let margins = size_mgr.frame_margins();
+                SizeRules::extract_fixed(axis, size_mgr.separator(), margins)
+            } else {
+                self.layout().size_rules(size_mgr, axis)
+            }
+        }
+
+        fn find_id(&mut self, coord: Coord) -> Option<WidgetId> {
+            // Items steal clicks from their icon
+            self.rect().contains(coord).then(|| self.id())
+        }
+
+        fn draw(&mut self, mut draw: DrawMgr) {
+            let mut draw = draw.with_core(self.core_data());
+            if self.is_separator() {
+                draw.separator(self.core.rect);
+                return;
+            }
+            if self.get_bool() {
+                draw.state.insert(InputState::DEPRESS);
+            }
+            draw.menu_entry(self.core.rect);
+            if !self.icon.is_empty() {
+                self.icon.draw(draw.re());
+            }
+            draw.text_accel(
+                self.layout_label.pos,
+                &self.label,
+                draw.ev_state().show_accel_labels(),
+                TextClass::MenuLabel,
+            );
+        }
+    }
+
+    impl Handler for Self {
+        type Msg = M;
+
+        #[inline]
+        fn activation_via_press(&self) -> bool {
+            !self.is_separator()
+        }
+
+        fn handle(&mut self, mgr: &mut EventMgr, event: Event) -> Response<M> {
+            match event {
+                Event::Activate => match self.kind {
+                    ItemKind::Button(ref f) => Response::used_or_msg(f(mgr)),
+                    ItemKind::Toggle(ref mut state, ref f) => {
+                        *state = !*state;
+                        mgr.redraw(self.core.id.clone());
+                        Response::used_or_msg(f(mgr, *state))
+                    }
+                    ItemKind::Separator => Response::Unused,
+                },
+                _ => Response::Unused,
+            }
+        }
+    }
+
+    impl HasBool for Self {
+        /// Get the state of a toggle-button (false for other items)
+        fn get_bool(&self) -> bool {
+            matches!(self.kind, ItemKind::Toggle(true, _))
+        }
+
+        /// Set the state of a toggle-button (no effect on other items)
+        fn set_bool(&mut self, state: bool) -> TkAction {
+            match self.kind {
+                ItemKind::Toggle(ref mut s, _) if *s != state => {
+                    *s = state;
+                    TkAction::REDRAW
+                }
+                _ => TkAction::empty(),
+            }
+        }
+    }
+
+    impl HasStr for Self {
+        fn get_str(&self) -> &str {
+            self.label.as_str()
+        }
+    }
+
+    impl SetAccel for Self {
+        fn set_accel_string(&mut self, string: AccelString) -> TkAction {
+            let mut action = TkAction::empty();
+            if self.label.text().keys() != string.keys() {
+                action |= TkAction::RECONFIGURE;
+            }
+            let avail = self.core.rect.size.clamped_sub(self.layout_frame.size);
+            action | kas::text::util::set_text_and_prepare(&mut self.label, string, avail)
+        }
+    }
+
+    /// Tool items may be shown within the overflow menu
+    impl Menu for Self {}
+}
+
+widget! {
+    /// A tool-bar
+    ///
+    /// A horizontal bar of [`ToolItem`]s: push-buttons, toggle-buttons and
+    /// separators. This complements [`crate::MenuBar`] for typical application
+    /// chrome.
+    ///
+    /// When there is insufficient space to show all items, trailing items are
+    /// moved into an overflow ("more") menu, shown at the end of the bar. This
+    /// is re-evaluated whenever the bar is resized.
+    ///
+    /// Items may be addressed via their key (see [`ToolItem::key`]); where
+    /// multiple items share a key, only the first is affected.
+    #[derive(Clone, Debug)]
+    pub struct ToolBar<M: 'static> {
+        #[widget_core]
+        core: CoreData,
+        #[widget]
+        bar: Row<ToolItem<M>>,
+        #[widget]
+        more: SubMenu<kas::dir::Down, ToolItem<M>>,
+        // Width of each item (on bar, then under more)
+        widths: Vec<i32>,
+        more_width: i32,
+        // Set when overflow should be re-evaluated once the menu is closed
+        relayout: bool,
+        // Set on press of the overflow button if this should open its menu
+        opening: bool,
+    }
+
+    impl Self {
+        /// Construct a tool-bar
+        pub fn new(items: Vec<ToolItem<M>>) -> Self {
+            ToolBar {
+                core: Default::default(),
+                bar: Row::new(items),
+                more: SubMenu::down("»", vec![]),
+                widths: vec![],
+                more_width: 0,
+                relayout: false,
+                opening: false,
+            }
+        }
+
+        /// True if there are no items
+        #[inline]
+        pub fn is_empty(&self) -> bool {
+            self.bar.is_empty() && self.more.list.is_empty()
+        }
+
+        /// Get the number of items
+        #[inline]
+        pub fn len(&self) -> usize {
+            self.bar.len() + self.more.list.len()
+        }
+
+        /// True if some items have been moved to the overflow menu
+        #[inline]
+        pub fn is_overflowing(&self) -> bool {
+            !self.more.list.is_empty()
+        }
+
+        /// Append an item
+        ///
+        /// Triggers a [reconfigure action](EventState::send_action).
+        pub fn push(&mut self, item: ToolItem<M>) -> TkAction {
+            if self.is_overflowing() {
+                self.more.list.push(item)
+            } else {
+                self.bar.push(item)
+            }
+        }
+
+        /// Iterate over items
+        pub fn iter(&self) -> impl Iterator<Item = &ToolItem<M>> {
+            self.bar.iter().chain(self.more.list.iter())
+        }
+
+        /// Get the first item with the given `key`
+        pub fn item(&self, key: &str) -> Option<&ToolItem<M>> {
+            self.iter().find(|item| item.key == key)
+        }
+
+        /// Get the first item with the given `key`, mutably
+        pub fn item_mut(&mut self, key: &str) -> Option<&mut ToolItem<M>> {
+            self.bar
+                .iter_mut()
+                .chain(self.more.list.iter_mut())
+                .find(|item| item.key == key)
+        }
+
+        /// Enable or disable the item with the given `key`
+        pub fn set_enabled(&mut self, key: &str, enabled: bool) -> TkAction {
+            self.item_mut(key)
+                .map(|item| item.set_disabled(!enabled))
+                .unwrap_or_default()
+        }
+
+        /// True if the item with the given `key` is enabled
+        ///
+        /// Returns `None` if no item matches `key`.
+        pub fn is_enabled(&self, key: &str) -> Option<bool> {
+            self.item(key).map(|item| !item.is_disabled())
+        }
+
+        /// Set the state of the toggle-button with the given `key`
+        pub fn set_state(&mut self, key: &str, state: bool) -> TkAction {
+            self.item_mut(key)
+                .map(|item| item.set_bool(state))
+                .unwrap_or_default()
+        }
+
+        /// Get the state of the toggle-button with the given `key`
+        ///
+        /// Returns `None` if no toggle-button matches `key`.
+        pub fn get_state(&self, key: &str) -> Option<bool> {
+            self.item(key)
+                .filter(|item| item.is_toggle())
+                .map(|item| item.get_bool())
+        }
+
+        // Move items between the bar and the overflow menu such that the first
+        // `n` items are on the bar, then reconfigure and re-solve sizes.
+        fn set_num_visible(&mut self, mgr: &mut SetRectMgr, n: usize) {
+            while self.bar.len() > n {
+                let item = self.bar.pop().0.unwrap();
+                let _ = self.more.list.insert(0, item);
+            }
+            while self.bar.len() < n {
+                let item = self.more.list.remove(0).0;
+                let _ = self.bar.push(item);
+            }
+
+            mgr.configure(self.bar.id(), &mut self.bar);
+            mgr.configure(self.more.id(), &mut self.more);
+            let size_mgr = mgr.size_mgr();
+            solve_size_rules(&mut self.bar, size_mgr.re(), None, None);
+            solve_size_rules(&mut self.more, size_mgr, None, None);
+        }
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
+            let bar_rules = self.bar.size_rules(size_mgr.re(), axis);
+            let more_rules = self.more.size_rules(size_mgr.re(), axis);
+            if axis.is_vertical() {
+                return bar_rules.max(more_rules);
+            }
+
+            fn item_width(rules: SizeRules) -> i32 {
+                let margins = rules.margins_i32();
+                rules.ideal_size() + margins.0.max(margins.1)
+            }
+
+            self.widths.clear();
+            for i in 0..self.bar.len() {
+                let rules = self.bar[i].size_rules(size_mgr.re(), axis);
+                self.widths.push(item_width(rules));
+            }
+            for i in 0..self.more.list.len() {
+                let rules = self.more.list[i].size_rules(size_mgr.re(), axis);
+                self.widths.push(item_width(rules));
+            }
+            self.more_width = item_width(more_rules);
+
+            let ideal = self.widths.iter().sum::<i32>().max(bar_rules.ideal_size());
+            SizeRules::new(
+                more_rules.min_size(),
+                ideal,
+                bar_rules.margins(),
+                bar_rules.stretch(),
+            )
+        }
+
+        fn set_rect(&mut self, mgr: &mut SetRectMgr, rect: Rect, align: AlignHints) {
+            self.core.rect = rect;
+            let avail = rect.size.0;
+
+            let mut n = self.widths.len();
+            if self.widths.iter().sum::<i32>() > avail {
+                let mut used = self.more_width;
+                n = self
+                    .widths
+                    .iter()
+                    .take_while(|w| {
+                        used += **w;
+                        used <= avail
+                    })
+                    .count();
+            }
+            if n != self.bar.len() {
+                if self.more.menu_is_open() {
+                    // Moving items would invalidate identifiers within the menu
+                    self.relayout = true;
+                } else {
+                    self.set_num_visible(mgr, n);
+                }
+            }
+
+            if !self.is_overflowing() {
+                self.bar.set_rect(mgr, rect, align);
+                return;
+            }
+
+            let more_width = self.more_width.min(avail);
+            let mut bar_rect = rect;
+            bar_rect.size.0 = avail - more_width;
+            let mut more_rect = rect;
+            more_rect.pos.0 += avail - more_width;
+            more_rect.size.0 = more_width;
+            self.bar.set_rect(mgr, bar_rect, align);
+            self.more.set_rect(mgr, more_rect, align);
+        }
+
+        fn find_id(&mut self, coord: Coord) -> Option<WidgetId> {
+            if !self.rect().contains(coord) {
+                return None;
+            }
+            let more = match self.is_overflowing() {
+                true => self.more.find_id(coord),
+                false => None,
+            };
+            more.or_else(|| self.bar.find_id(coord))
+                .or(Some(self.id()))
+        }
+
+        fn draw(&mut self, mut draw: DrawMgr) {
+            let mut draw = draw.with_core(self.core_data());
+            self.bar.draw(draw.re());
+            if self.is_overflowing() {
+                self.more.draw(draw.re());
+            }
+        }
+    }
+
+    impl event::Handler for Self {
+        type Msg = M;
+
+        fn handle(&mut self, mgr: &mut EventMgr, event: Event) -> Response<M> {
+            match event {
+                Event::PressStart {
+                    source,
+                    start_id,
+                    coord,
+                } => match start_id {
+                    Some(id) if self.more.eq_id(&id) => {
+                        if source.is_primary() {
+                            mgr.grab_press_unique(self.id(), source, coord, None);
+                            mgr.set_grab_depress(source, Some(id));
+                            self.opening = !self.more.menu_is_open();
+                            if !self.opening {
+                                self.more.set_menu_path(mgr, None, false);
+                            }
+                        }
+                        Response::Used
+                    }
+                    Some(id) if self.more.is_ancestor_of(&id) => {
+                        // Press within the open overflow menu
+                        let event = Event::PressStart {
+                            source,
+                            start_id: Some(id.clone()),
+                            coord,
+                        };
+                        self.more.send(mgr, id, event)
+                    }
+                    _ => Response::Unused,
+                },
+                Event::PressMove { source, cur_id, .. } => {
+                    let cond = cur_id.filter(|id| self.more.eq_id(id));
+                    mgr.set_grab_depress(source, cond);
+                    Response::Used
+                }
+                Event::PressEnd { end_id, success, .. } => {
+                    let on_more = end_id.map(|id| self.more.eq_id(&id)).unwrap_or(false);
+                    if success && on_more && self.opening {
+                        let id = self.more.id();
+                        self.more.set_menu_path(mgr, Some(&id), false);
+                    }
+                    self.opening = false;
+                    Response::Used
+                }
+                _ => Response::Unused,
+            }
+        }
+    }
+
+    impl event::SendEvent for Self {
+        fn send(&mut self, mgr: &mut EventMgr, id: WidgetId, event: Event) -> Response<Self::Msg> {
+            if self.is_disabled() {
+                return Response::Unused;
+            }
+
+            let r = if self.eq_id(&id) {
+                self.handle(mgr, event)
+            } else if self.more.eq_id(&id) && matches!(event, Event::PressStart { .. }) {
+                // The overflow button is opened by click; additionally this
+                // receives presses while its menu is open.
+                self.handle(mgr, event)
+            } else if self.more.is_ancestor_of(&id) {
+                self.more.send(mgr, id, event)
+            } else {
+                self.bar.send(mgr, id, event)
+            };
+
+            if self.relayout && !self.more.menu_is_open() {
+                self.relayout = false;
+                mgr.send_action(TkAction::RESIZE);
+            }
+            r
+        }
+    }
+}
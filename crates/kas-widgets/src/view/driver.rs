@@ -13,8 +13,11 @@ use crate::{
     SliderType,
 };
 use kas::prelude::*;
+use kas::updatable::LoadState;
+use std::cell::Cell;
 use std::fmt::Debug;
 use std::marker::PhantomData;
+use std::rc::Rc;
 
 /// View widget driver/binder
 ///
@@ -57,13 +60,36 @@ pub trait Driver<T>: Debug + 'static {
     /// Often it will be sufficient to implement custom handling/update logic
     /// in only one of these places.
     fn get(&self, widget: &Self::Widget) -> Option<T>;
+
+    /// Set the load state of the viewed data
+    ///
+    /// Views call this after [`Driver::set`] with the data's load state (see
+    /// [`kas::updatable::ListData::load_state`]). Where the state is not
+    /// [`LoadState::Ready`], the data is a placeholder; a driver may use this
+    /// to indicate loading, e.g. by disabling the widget. The default
+    /// implementation does nothing.
+    fn set_load_state(&self, widget: &mut Self::Widget, state: LoadState) -> TkAction {
+        let _ = (widget, state);
+        TkAction::empty()
+    }
+
+    /// Get text representing `data` for the clipboard
+    ///
+    /// Views use this to copy selected items (see [`super::CopyFormat`]).
+    /// Items for which this returns `None` are copied as empty fields. The
+    /// default implementation returns `None`.
+    fn to_clipboard_text(&self, data: &T) -> Option<String> {
+        let _ = data;
+        None
+    }
 }
 
 /// Default view widget constructor
 ///
 /// This struct implements [`Driver`], using a default widget for the data type:
 ///
-/// -   [`crate::Label`] for `String`, `&str`, integer and float types
+/// -   [`crate::Label`] for `String`, `&str`, integer and float types and
+///     [`kas::updatable::Cell`]
 /// -   [`crate::CheckBoxBare`] (disabled) for the bool type
 #[derive(Clone, Debug, Default)]
 pub struct Default;
@@ -74,7 +100,7 @@ pub struct Default;
 /// which also supports keyboard navigation:
 ///
 /// -   [`crate::NavFrame`] around a [`crate::Label`] for `String`, `&str`,
-///     integer and float types
+///     integer and float types and [`kas::updatable::Cell`]
 /// -   [`crate::CheckBoxBare`] (disabled) for the bool type
 #[derive(Clone, Debug, Default)]
 pub struct DefaultNav;
@@ -91,6 +117,9 @@ macro_rules! impl_via_to_string {
                 widget.set_string(data.to_string())
             }
             fn get(&self, _: &Self::Widget) -> Option<$t> { None }
+            fn to_clipboard_text(&self, data: &$t) -> Option<String> {
+                Some(data.to_string())
+            }
         }
         impl Driver<$t> for DefaultNav {
             type Msg = VoidMsg;
@@ -102,6 +131,9 @@ macro_rules! impl_via_to_string {
                 widget.set_string(data.to_string())
             }
             fn get(&self, _: &Self::Widget) -> Option<$t> { None }
+            fn to_clipboard_text(&self, data: &$t) -> Option<String> {
+                Some(data.to_string())
+            }
         }
     };
     ($t:ty, $($tt:ty),+) => {
@@ -113,6 +145,7 @@ impl_via_to_string!(String, &'static str);
 impl_via_to_string!(i8, i16, i32, i64, i128, isize);
 impl_via_to_string!(u8, u16, u32, u64, u128, usize);
 impl_via_to_string!(f32, f64);
+impl_via_to_string!(kas::updatable::Cell);
 
 impl Driver<bool> for Default {
     type Msg = VoidMsg;
@@ -126,6 +159,9 @@ impl Driver<bool> for Default {
     fn get(&self, widget: &Self::Widget) -> Option<bool> {
         Some(widget.get_bool())
     }
+    fn to_clipboard_text(&self, data: &bool) -> Option<String> {
+        Some(data.to_string())
+    }
 }
 
 impl Driver<bool> for DefaultNav {
@@ -140,23 +176,20 @@ impl Driver<bool> for DefaultNav {
     fn get(&self, widget: &Self::Widget) -> Option<bool> {
         Some(widget.get_bool())
     }
+    fn to_clipboard_text(&self, data: &bool) -> Option<String> {
+        Some(data.to_string())
+    }
 }
 
 /// Custom view widget constructor
 ///
 /// This struct implements [`Driver`], using a the parametrised widget type.
 /// This struct is only usable where no extra data (such as a label) is required.
+#[autoimpl(Default)]
 #[derive(Clone, Debug)]
 pub struct Widget<W: kas::Widget> {
     _pd: PhantomData<W>,
 }
-impl<W: kas::Widget> std::default::Default for Widget<W> {
-    fn default() -> Self {
-        Widget {
-            _pd: PhantomData::default(),
-        }
-    }
-}
 
 impl<T> Driver<T> for Widget<<Default as Driver<T>>::Widget>
 where
@@ -173,6 +206,9 @@ where
     fn get(&self, widget: &Self::Widget) -> Option<T> {
         Default.get(widget)
     }
+    fn to_clipboard_text(&self, data: &T) -> Option<String> {
+        Default.to_clipboard_text(data)
+    }
 }
 
 impl<G: EditGuard + std::default::Default> Driver<String> for Widget<EditField<G>> {
@@ -188,6 +224,9 @@ impl<G: EditGuard + std::default::Default> Driver<String> for Widget<EditField<G
     fn get(&self, widget: &Self::Widget) -> Option<String> {
         Some(widget.get_string())
     }
+    fn to_clipboard_text(&self, data: &String) -> Option<String> {
+        Some(data.clone())
+    }
 }
 impl<G: EditGuard + std::default::Default> Driver<String> for Widget<EditBox<G>> {
     type Msg = G::Msg;
@@ -202,6 +241,9 @@ impl<G: EditGuard + std::default::Default> Driver<String> for Widget<EditBox<G>>
     fn get(&self, widget: &Self::Widget) -> Option<String> {
         Some(widget.get_string())
     }
+    fn to_clipboard_text(&self, data: &String) -> Option<String> {
+        Some(data.clone())
+    }
 }
 
 impl<D: Directional + std::default::Default> Driver<f32> for Widget<ProgressBar<D>> {
@@ -216,6 +258,69 @@ impl<D: Directional + std::default::Default> Driver<f32> for Widget<ProgressBar<
     fn get(&self, widget: &Self::Widget) -> Option<f32> {
         Some(widget.value())
     }
+    fn to_clipboard_text(&self, data: &f32) -> Option<String> {
+        Some(data.to_string())
+    }
+}
+
+/// Display-only view widget constructor
+///
+/// This struct implements [`Driver`] for any widget type `W`, given functions
+/// to construct the widget and to set its value. Data is never read back from
+/// the widget, thus this is suitable for display-only widgets such as
+/// [`crate::Label`] and [`crate::ProgressBar`].
+///
+/// Example: viewing a `SharedRc<f32>` with a progress bar (updated whenever
+/// the data is updated):
+/// ```
+/// use kas::updatable::SharedRc;
+/// use kas_widgets::view::{driver, SingleView};
+/// use kas_widgets::ProgressBar;
+///
+/// let driver = driver::Display::new(ProgressBar::<kas::dir::Right>::new, |bar, value| {
+///     bar.set_value(value)
+/// });
+/// let view = SingleView::new_with_driver(driver, SharedRc::new(0.5f32));
+/// ```
+///
+/// Items are copied to the clipboard (see [`Driver::to_clipboard_text`]) only
+/// if a text function is given via [`Display::with_text`].
+#[autoimpl(Clone)]
+#[autoimpl(Debug skip self.make, self.set, self.text)]
+pub struct Display<T, W: kas::Widget> {
+    make: fn() -> W,
+    set: fn(&mut W, T) -> TkAction,
+    text: Option<fn(&T) -> String>,
+}
+impl<T, W: kas::Widget> Display<T, W> {
+    /// Construct, with functions to `make` a widget and `set` its value
+    pub fn new(make: fn() -> W, set: fn(&mut W, T) -> TkAction) -> Self {
+        let text = None;
+        Display { make, set, text }
+    }
+
+    /// Set a function representing values as clipboard text (inline)
+    #[must_use]
+    pub fn with_text(mut self, text: fn(&T) -> String) -> Self {
+        self.text = Some(text);
+        self
+    }
+}
+impl<T: 'static, W: kas::Widget> Driver<T> for Display<T, W> {
+    type Msg = W::Msg;
+    type Widget = W;
+    fn make(&self) -> Self::Widget {
+        (self.make)()
+    }
+    fn set(&self, widget: &mut Self::Widget, data: T) -> TkAction {
+        (self.set)(widget, data)
+    }
+    fn get(&self, _: &Self::Widget) -> Option<T> {
+        None
+    }
+    fn to_clipboard_text(&self, data: &T) -> Option<String> {
+        self.text.map(|text| text(data))
+    }
 }
 
 /// [`crate::CheckBox`] view widget constructor
@@ -242,24 +347,32 @@ impl Driver<bool> for CheckBox {
     fn get(&self, widget: &Self::Widget) -> Option<bool> {
         Some(widget.get_bool())
     }
+    fn to_clipboard_text(&self, data: &bool) -> Option<String> {
+        Some(data.to_string())
+    }
 }
 
 /// [`crate::RadioBoxBare`] view widget constructor
+///
+/// Each constructed widget is assigned a unique value within `group`.
 #[derive(Clone, Debug, Default)]
 pub struct RadioBoxBare {
-    group: RadioBoxGroup,
+    group: RadioBoxGroup<usize>,
+    next: Rc<Cell<usize>>,
 }
 impl RadioBoxBare {
     /// Construct, with given `group`
-    pub fn make(group: RadioBoxGroup) -> Self {
-        RadioBoxBare { group }
+    pub fn make(group: RadioBoxGroup<usize>) -> Self {
+        let next = Default::default();
+        RadioBoxBare { group, next }
     }
 }
 impl Driver<bool> for RadioBoxBare {
     type Msg = bool;
     type Widget = crate::RadioBoxBare<bool>;
     fn make(&self) -> Self::Widget {
-        crate::RadioBoxBare::new(self.group.clone()).on_select(|_| Some(true))
+        let value = self.next.replace(self.next.get() + 1);
+        crate::RadioBoxBare::new(self.group.clone(), value).on_select(|_| Some(true))
     }
     fn set(&self, widget: &mut Self::Widget, data: bool) -> TkAction {
         widget.set_bool(data)
@@ -267,26 +380,35 @@ impl Driver<bool> for RadioBoxBare {
     fn get(&self, widget: &Self::Widget) -> Option<bool> {
         Some(widget.get_bool())
     }
+    fn to_clipboard_text(&self, data: &bool) -> Option<String> {
+        Some(data.to_string())
+    }
 }
 
 /// [`crate::RadioBox`] view widget constructor
+///
+/// Each constructed widget is assigned a unique value within `group`.
 #[derive(Clone, Debug, Default)]
 pub struct RadioBox {
     label: AccelString,
-    group: RadioBoxGroup,
+    group: RadioBoxGroup<usize>,
+    next: Rc<Cell<usize>>,
 }
 impl RadioBox {
     /// Construct, with given `label` and `group`
-    pub fn make<T: Into<AccelString>>(label: T, group: RadioBoxGroup) -> Self {
+    pub fn make<T: Into<AccelString>>(label: T, group: RadioBoxGroup<usize>) -> Self {
         let label = label.into();
-        RadioBox { label, group }
+        let next = Default::default();
+        RadioBox { label, group, next }
     }
 }
 impl Driver<bool> for RadioBox {
     type Msg = bool;
     type Widget = crate::RadioBox<bool>;
     fn make(&self) -> Self::Widget {
-        crate::RadioBox::new(self.label.clone(), self.group.clone()).on_select(|_| Some(true))
+        let value = self.next.replace(self.next.get() + 1);
+        crate::RadioBox::new(self.label.clone(), self.group.clone(), value)
+            .on_select(|_| Some(true))
     }
     fn set(&self, widget: &mut Self::Widget, data: bool) -> TkAction {
         widget.set_bool(data)
@@ -294,6 +416,9 @@ impl Driver<bool> for RadioBox {
     fn get(&self, widget: &Self::Widget) -> Option<bool> {
         Some(widget.get_bool())
     }
+    fn to_clipboard_text(&self, data: &bool) -> Option<String> {
+        Some(data.to_string())
+    }
 }
 
 /// [`crate::Slider`] view widget constructor
@@ -338,4 +463,7 @@ impl<T: SliderType, D: Directional> Driver<T> for Slider<T, D> {
     fn get(&self, widget: &Self::Widget) -> Option<T> {
         Some(widget.value())
     }
+    fn to_clipboard_text(&self, data: &T) -> Option<String> {
+        Some(format!("{:?}", data))
+    }
 }
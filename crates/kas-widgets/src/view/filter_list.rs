@@ -9,12 +9,28 @@ use super::{driver, Driver, ListView, SelectionError, SelectionMode};
 use crate::Scrollable;
 use kas::event::ChildMsg;
 use kas::prelude::*;
-use kas::updatable::filter::Filter;
-use kas::updatable::{ListData, Updatable, UpdatableHandler};
-use std::cell::RefCell;
+use kas::updatable::filter::{AsyncFilter, Filter};
+use kas::updatable::{ListData, LoadState, Updatable, UpdatableHandler};
+use std::cell::{Cell, RefCell};
 use std::fmt::Debug;
+use std::ops::Range;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::time::{Duration, Instant};
 use UpdatableHandler as UpdHandler;
 
+/// Timer payload: debounced filter update
+const TIMER_DEBOUNCE: u64 = 0;
+/// Timer payload: filter the next chunk
+const TIMER_CHUNK: u64 = 1;
+/// Timer payload: poll results of asynchronous filtering
+const TIMER_ASYNC: u64 = 2;
+
+/// Matching items as (index in unfiltered data, key)
+type Matches<K> = Vec<(usize, K)>;
+
+/// Start filtering on a worker thread, yielding matches in chunks
+type Spawn<T, F> = Box<dyn Fn(&FilteredList<T, F>) -> Receiver<Matches<<T as ListData>::Key>>>;
+
 /// Filter accessor over another accessor
 ///
 /// This is an abstraction over a [`ListData`], applying a filter to items when
@@ -41,7 +57,10 @@ struct FilteredList<T: ListData, F: Filter<T::Item>> {
     ///
     /// If adjusting this, one should call [`FilteredList::refresh`] after.
     filter: F,
-    view: RefCell<Vec<T::Key>>,
+    /// Matching items as (index in `data`, key)
+    view: RefCell<Matches<T::Key>>,
+    /// Index of the next data item to filter, if refreshing incrementally
+    progress: Cell<Option<usize>>,
 }
 
 impl<T: ListData, F: Filter<T::Item>> FilteredList<T, F> {
@@ -50,7 +69,13 @@ impl<T: ListData, F: Filter<T::Item>> FilteredList<T, F> {
     fn new(data: T, filter: F) -> Self {
         let len = data.len().cast();
         let view = RefCell::new(Vec::with_capacity(len));
-        let s = FilteredList { data, filter, view };
+        let progress = Cell::new(None);
+        let s = FilteredList {
+            data,
+            filter,
+            view,
+            progress,
+        };
         let _ = s.refresh();
         s
     }
@@ -62,14 +87,53 @@ impl<T: ListData, F: Filter<T::Item>> FilteredList<T, F> {
     ///
     /// An update should be triggered using the returned handle.
     fn refresh(&self) -> Option<UpdateHandle> {
+        self.refresh_begin();
+        self.refresh_step(usize::MAX);
+        self.filter.update_handle()
+    }
+
+    /// Begin an incremental refresh
+    ///
+    /// This clears the view; items are filtered by [`FilteredList::refresh_step`].
+    fn refresh_begin(&self) {
+        self.view.borrow_mut().clear();
+        self.progress.set(Some(0));
+    }
+
+    /// Filter up to `limit` further items
+    ///
+    /// Returns true if items remain to be filtered.
+    fn refresh_step(&self, limit: usize) -> bool {
+        let start = match self.progress.get() {
+            Some(start) => start,
+            None => return false,
+        };
+        let items = self.data.iter_vec_from(start, limit);
+        let end = start + items.len();
         let mut view = self.view.borrow_mut();
-        view.clear();
-        for (key, item) in self.data.iter_vec(usize::MAX) {
+        for (index, (key, item)) in (start..).zip(items) {
             if self.filter.matches(item) {
-                view.push(key);
+                view.push((index, key));
             }
         }
-        self.filter.update_handle()
+        let more = end < self.data.len() && end > start;
+        self.progress.set(more.then(|| end));
+        more
+    }
+
+    /// True while an incremental refresh is in progress
+    fn is_refreshing(&self) -> bool {
+        self.progress.get().is_some()
+    }
+
+    /// Append matches found elsewhere (asynchronously) to the view
+    fn extend_view(&self, matches: Matches<T::Key>) {
+        self.view.borrow_mut().extend(matches);
+    }
+
+    /// Mark an incremental refresh as complete
+    fn refresh_end(&self) {
+        self.progress.set(None);
     }
 }
 
@@ -127,7 +191,7 @@ impl<T: ListData + 'static, F: Filter<T::Item>> ListData for FilteredList<T, F>
         let result = self.data.update(key, value);
         if result.is_some() && !new_visible {
             // remove the updated item from our filtered list
-            self.view.borrow_mut().retain(|item| item != key);
+            self.view.borrow_mut().retain(|(_, k)| k != key);
         }
         result
     }
@@ -139,11 +203,35 @@ impl<T: ListData + 'static, F: Filter<T::Item>> ListData for FilteredList<T, F>
             return Vec::new();
         }
         let mut v = Vec::with_capacity(end - start);
-        for k in &view[start..end] {
+        for (_, k) in &view[start..end] {
             v.push((k.clone(), self.data.get_cloned(k).unwrap()));
         }
         v
     }
+
+    fn request_range(&self, range: Range<usize>) {
+        // Map to ranges of the unfiltered data, merging contiguous indices
+        let view = self.view.borrow();
+        let end = view.len().min(range.end);
+        let mut requested: Option<Range<usize>> = None;
+        for (index, _) in view.get(range.start..end).unwrap_or(&[]) {
+            requested = match requested {
+                Some(r) if r.end == *index => Some(r.start..*index + 1),
+                Some(r) => {
+                    self.data.request_range(r);
+                    Some(*index..*index + 1)
+                }
+                None => Some(*index..*index + 1),
+            };
+        }
+        if let Some(r) = requested {
+            self.data.request_range(r);
+        }
+    }
+
+    fn load_state(&self, key: &Self::Key) -> LoadState {
+        self.data.load_state(key)
+    }
 }
 
 widget! {
@@ -160,8 +248,17 @@ widget! {
     /// machinery for recursive-updates on data-structures and/or a mechanism to
     /// test whether the underlying list-data changed. Implementing as a widget
     /// avoids this.
+    ///
+    /// For large data sets, filtering may be configured to be responsive:
+    ///
+    /// -   [`FilterListView::with_debounce`] delays applying the filter until
+    ///     the filter has not changed for some time (e.g. while typing)
+    /// -   [`FilterListView::with_incremental`] filters in chunks, one chunk per
+    ///     frame, showing results as they are found
+    /// -   [`FilterListView::with_async`] filters on a worker thread (for
+    ///     expensive predicates), showing results as they are found
     // TODO: impl Clone
-    #[derive(Debug)]
+    #[autoimpl(Debug skip self.spawn)]
     #[widget{
         layout = single;
     }]
@@ -175,6 +272,11 @@ widget! {
         core: CoreData,
         #[widget]
         list: ListView<D, FilteredList<T, F>, V>,
+        debounce: Duration,
+        chunk_size: usize,
+        filter_changed: Option<Instant>,
+        spawn: Option<Spawn<T, F>>,
+        receiver: Option<Receiver<Matches<T::Key>>>,
     }
 
     impl Self where D: Default, V: Default {
@@ -217,9 +319,57 @@ widget! {
             FilterListView {
                 core: Default::default(),
                 list: ListView::new_with_dir_driver(direction, view, data),
+                debounce: Duration::ZERO,
+                chunk_size: 0,
+                filter_changed: None,
+                spawn: None,
+                receiver: None,
             }
         }
 
+        /// Set the debounce delay (inline)
+        ///
+        /// When the filter is updated, the filter is applied only after no
+        /// further updates occur for this `delay`. Updates to the data are
+        /// applied immediately. Default: zero (no delay).
+        #[must_use]
+        pub fn with_debounce(mut self, delay: Duration) -> Self {
+            self.debounce = delay;
+            self
+        }
+
+        /// Get the debounce delay
+        pub fn debounce(&self) -> Duration {
+            self.debounce
+        }
+
+        /// Set the debounce delay
+        pub fn set_debounce(&mut self, delay: Duration) {
+            self.debounce = delay;
+        }
+
+        /// Enable incremental filtering (inline)
+        ///
+        /// If `chunk_size > 0`, updates filter at most `chunk_size` items per
+        /// frame, updating the view after each chunk. This keeps the UI
+        /// responsive while filtering large data sets, though the view is
+        /// incomplete until filtering finishes (see
+        /// [`FilterListView::is_filtering`]). Filtering restarts if the filter
+        /// or data changes in the mean-time.
+        ///
+        /// If `chunk_size == 0` (the default), all items are filtered at once.
+        #[must_use]
+        pub fn with_incremental(mut self, chunk_size: usize) -> Self {
+            self.chunk_size = chunk_size;
+            self
+        }
+
+        /// True while (incremental, asynchronous or debounced) filtering is
+        /// pending
+        pub fn is_filtering(&self) -> bool {
+            self.filter_changed.is_some() || self.list.data().is_refreshing()
+        }
+
         /// Access the stored data (pre-filter)
         pub fn unfiltered_data(&self) -> &T {
             &self.list.data().data
@@ -333,11 +483,71 @@ widget! {
         }
 
         /// Manually trigger an update to handle changed data or filter
+        ///
+        /// This filters all items immediately, regardless of configured
+        /// debouncing and incremental or asynchronous filtering.
         pub fn update_view(&mut self, mgr: &mut EventMgr) {
+            self.filter_changed = None;
+            self.receiver = None;
             self.list.data().refresh();
             self.list.update_view(mgr)
         }
 
+        // Refresh, according to chunk_size and spawn
+        fn begin_refresh(&mut self, mgr: &mut EventMgr) {
+            self.filter_changed = None;
+            // Dropping any previous receiver stops its worker
+            self.receiver = None;
+            if let Some(spawn) = self.spawn.as_ref() {
+                self.list.data().refresh_begin();
+                self.receiver = Some(spawn(self.list.data()));
+                mgr.update_on_timer(Duration::from_nanos(1), self.id(), TIMER_ASYNC);
+                self.list.update_view(mgr);
+            } else if self.chunk_size == 0 {
+                self.update_view(mgr);
+            } else {
+                self.list.data().refresh_begin();
+                self.refresh_step(mgr);
+            }
+        }
+
+        fn refresh_step(&mut self, mgr: &mut EventMgr) {
+            if self.list.data().refresh_step(self.chunk_size.max(1)) {
+                mgr.update_on_timer(Duration::from_nanos(1), self.id(), TIMER_CHUNK);
+            }
+            self.list.update_view(mgr);
+        }
+
+        fn poll_async(&mut self, mgr: &mut EventMgr) {
+            let receiver = match self.receiver.take() {
+                Some(receiver) => receiver,
+                None => return,
+            };
+            let mut changed = false;
+            loop {
+                match receiver.try_recv() {
+                    Ok(found) => {
+                        changed |= !found.is_empty();
+                        self.list.data().extend_view(found);
+                    }
+                    Err(TryRecvError::Empty) => {
+                        mgr.update_on_timer(Duration::from_nanos(1), self.id(), TIMER_ASYNC);
+                        self.receiver = Some(receiver);
+                        break;
+                    }
+                    Err(TryRecvError::Disconnected) => {
+                        // The worker finished
+                        self.list.data().refresh_end();
+                        changed = true;
+                        break;
+                    }
+                }
+            }
+            if changed {
+                self.list.update_view(mgr);
+            }
+        }
+
         /// Get the direction of contents
         pub fn direction(&self) -> Direction {
             self.list.direction()
@@ -352,6 +562,59 @@ widget! {
             self.list = self.list.with_num_visible(number);
             self
         }
+
+        /// Enable wrap mode (inline)
+        ///
+        /// See [`ListView::with_wrap`].
+        #[must_use]
+        pub fn with_wrap(mut self, wrap: bool) -> Self {
+            self.list = self.list.with_wrap(wrap);
+            self
+        }
+    }
+
+    impl Self
+    where
+        F: AsyncFilter<T::Item>,
+        T::Key: Send + 'static,
+        T::Item: Send + 'static,
+    {
+        /// Enable asynchronous filtering (inline)
+        ///
+        /// If enabled, the filter is applied on a worker thread to a snapshot
+        /// of the data (see [`AsyncFilter::snapshot`]), delivering matches in
+        /// chunks of up to `chunk_size` items. Results are shown as they
+        /// arrive; the view is incomplete until filtering finishes (see
+        /// [`FilterListView::is_filtering`]). If the filter or data changes in
+        /// the mean-time, the worker is abandoned and filtering restarts.
+        ///
+        /// This takes precedence over [`FilterListView::with_incremental`].
+        #[must_use]
+        pub fn with_async(mut self, chunk_size: usize) -> Self {
+            let chunk_size = chunk_size.max(1);
+            self.spawn = Some(Box::new(move |list: &FilteredList<T, F>| {
+                let items = list.data.iter_vec_from(0, usize::MAX);
+                let matches = list.filter.snapshot();
+                let (sender, receiver) = mpsc::channel();
+                std::thread::spawn(move || {
+                    let mut start = 0;
+                    for chunk in items.chunks(chunk_size) {
+                        let found: Matches<_> = (start..)
+                            .zip(chunk.iter())
+                            .filter(|(_, (_, item))| matches(item.clone()))
+                            .map(|(index, (key, _))| (index, key.clone()))
+                            .collect();
+                        start += chunk.len();
+                        if sender.send(found).is_err() {
+                            // The receiver was dropped: results are not wanted
+                            return;
+                        }
+                    }
+                });
+                receiver
+            }));
+            self
+        }
     }
 
     // TODO: support derive(Scrollable)?
@@ -380,11 +643,11 @@ widget! {
     impl WidgetConfig for Self {
         fn configure(&mut self, mgr: &mut SetRectMgr) {
             // We must refresh the filtered list when the underlying list changes
-            if let Some(handle) = self.list.data().data.update_handle() {
+            for handle in self.list.data().data.update_handles() {
                 mgr.update_on_handle(handle, self.id());
             }
             // As well as when the filter changes
-            if let Some(handle) = self.list.data().update_handle() {
+            for handle in self.list.data().update_handles() {
                 mgr.update_on_handle(handle, self.id());
             }
         }
@@ -395,10 +658,40 @@ widget! {
 
         fn handle(&mut self, mgr: &mut EventMgr, event: Event) -> Response<Self::Msg> {
             match event {
-                Event::HandleUpdate { .. } => {
-                    self.update_view(mgr);
+                Event::HandleUpdate { handle, .. } => {
+                    let is_filter = self.list.data().update_handles().contains(&handle);
+                    if is_filter && self.debounce > Duration::ZERO {
+                        self.filter_changed = Some(Instant::now());
+                        mgr.update_on_timer(self.debounce, self.id(), TIMER_DEBOUNCE);
+                    } else {
+                        self.begin_refresh(mgr);
+                    }
                     Response::Update
                 }
+                Event::TimerUpdate(TIMER_DEBOUNCE) => {
+                    if let Some(time) = self.filter_changed {
+                        // Timer updates are merged using the earliest time,
+                        // thus we may need to wait longer
+                        let elapsed = time.elapsed();
+                        if elapsed < self.debounce {
+                            let delay = self.debounce - elapsed;
+                            mgr.update_on_timer(delay, self.id(), TIMER_DEBOUNCE);
+                        } else {
+                            self.begin_refresh(mgr);
+                        }
+                    }
+                    Response::Used
+                }
+                Event::TimerUpdate(TIMER_CHUNK) => {
+                    if self.list.data().is_refreshing() && self.receiver.is_none() {
+                        self.refresh_step(mgr);
+                    }
+                    Response::Used
+                }
+                Event::TimerUpdate(TIMER_ASYNC) => {
+                    self.poll_async(mgr);
+                    Response::Used
+                }
                 _ => Response::Unused,
             }
         }
@@ -5,7 +5,7 @@
 
 //! List view widget
 
-use super::{driver, Driver, PressPhase, SelectionError, SelectionMode};
+use super::{driver, CopyFormat, Driver, PressPhase, SelectionError, SelectionMode};
 #[allow(unused)] // doc links
 use crate::ScrollBars;
 use crate::Scrollable;
@@ -41,6 +41,15 @@ widget! {
     ///
     /// This widget is [`Scrollable`], supporting keyboard, wheel and drag
     /// scrolling. You may wish to wrap this widget with [`ScrollBars`].
+    ///
+    /// In paged mode (see [`ListView::with_page_size`]), only one page of
+    /// items is shown at a time; [`crate::Paginator`] may be used to navigate
+    /// pages.
+    ///
+    /// In wrap mode (see [`ListView::with_wrap`]), items flow in lines across
+    /// the direction of scrolling, wrapping to the next line (an "icon view").
+    /// For example, with direction `Down`, items fill rows left-to-right and
+    /// the view scrolls vertically.
     #[derive(Clone, Debug)]
     pub struct ListView<
         D: Directional,
@@ -63,13 +72,22 @@ widget! {
         child_size_min: i32,
         child_size_ideal: i32,
         child_inter_margin: i32,
+        child_cross_ideal: i32,
+        child_cross_margin: i32,
         child_size: Size,
+        wrap: bool,
+        /// Items per line (1 unless wrapping)
+        per_line: usize,
         scroll: ScrollComponent,
         sel_mode: SelectionMode,
         // TODO(opt): replace selection list with RangeOrSet type?
         selection: LinearSet<T::Key>,
+        copy_format: CopyFormat,
         press_phase: PressPhase,
         press_target: Option<T::Key>,
+        /// Items per page, or zero if not paged
+        page_size: usize,
+        page: usize,
     }
 
     impl Self where D: Default, V: Default {
@@ -118,12 +136,19 @@ widget! {
                 child_size_min: 0,
                 child_size_ideal: 0,
                 child_inter_margin: 0,
+                child_cross_ideal: 0,
+                child_cross_margin: 0,
                 child_size: Size::ZERO,
+                wrap: false,
+                per_line: 1,
                 scroll: Default::default(),
                 sel_mode: SelectionMode::None,
                 selection: Default::default(),
+                copy_format: CopyFormat::default(),
                 press_phase: PressPhase::None,
                 press_target: None,
+                page_size: 0,
+                page: 0,
             }
         }
 
@@ -254,6 +279,51 @@ widget! {
             }
         }
 
+        /// Set the format used to copy selected items (inline)
+        ///
+        /// Default: [`CopyFormat::Tsv`].
+        #[must_use]
+        pub fn with_copy_format(mut self, format: CopyFormat) -> Self {
+            self.copy_format = format;
+            self
+        }
+
+        /// Get the format used to copy selected items
+        pub fn copy_format(&self) -> CopyFormat {
+            self.copy_format
+        }
+
+        /// Set the format used to copy selected items
+        pub fn set_copy_format(&mut self, format: CopyFormat) {
+            self.copy_format = format;
+        }
+
+        /// Get the selection as text, formatted for the clipboard
+        ///
+        /// Selected items are copied in data order, one per line.
+        /// Item text is provided by [`Driver::to_clipboard_text`]. Returns `None`
+        /// if nothing is selected or no selected item has text.
+        ///
+        /// This is used to handle [`Command::Copy`].
+        pub fn copy_selection(&self) -> Option<String> {
+            if self.selection.is_empty() {
+                return None;
+            }
+            let mut any = false;
+            let rows: Vec<_> = self
+                .data
+                .iter_vec(usize::MAX)
+                .into_iter()
+                .filter(|(key, _)| self.selection.contains(key))
+                .map(|(_, item)| {
+                    let text = self.view.to_clipboard_text(&item);
+                    any |= text.is_some();
+                    vec![text.unwrap_or_default()]
+                })
+                .collect();
+            any.then(|| self.copy_format.format(&rows))
+        }
+
         /// Manually trigger an update to handle changed data
         pub fn update_view(&mut self, mgr: &mut EventMgr) {
             let data = &self.data;
@@ -267,6 +337,20 @@ widget! {
             *mgr |= TkAction::SET_SIZE;
         }
 
+        /// Mark view widgets as out-of-date, without requiring an [`EventMgr`]
+        ///
+        /// This is an alternative to [`ListView::update_view`] for use where
+        /// no [`EventMgr`] is available: view widgets are updated from the data
+        /// on the next resize, which the returned action triggers.
+        pub fn invalidate_view(&mut self) -> TkAction {
+            let data = &self.data;
+            self.selection.retain(|key| data.contains_key(key));
+            for w in &mut self.widgets {
+                w.key = None;
+            }
+            TkAction::SET_SIZE
+        }
+
         /// Get the direction of contents
         pub fn direction(&self) -> Direction {
             self.direction.as_direction()
@@ -282,42 +366,191 @@ widget! {
             self
         }
 
+        /// Set the preferred number of items visible
+        ///
+        /// This affects the (ideal) size request and whether children are sized
+        /// according to their ideal or minimum size but not the minimum size.
+        pub fn set_num_visible(&mut self, number: i32) -> TkAction {
+            if self.ideal_visible == number {
+                return TkAction::empty();
+            }
+            self.ideal_visible = number;
+            TkAction::RESIZE
+        }
+
+        /// Enable wrap mode (inline)
+        ///
+        /// In wrap mode, items are placed side-by-side across the direction of
+        /// scrolling (at their ideal size), wrapping to the next line as
+        /// required. Only visible lines are instantiated.
+        ///
+        /// With wrapping, [`ListView::with_num_visible`] affects the ideal
+        /// number of both lines and items per line.
+        #[must_use]
+        pub fn with_wrap(mut self, wrap: bool) -> Self {
+            self.wrap = wrap;
+            self
+        }
+
+        /// Set wrap mode
+        ///
+        /// See [`ListView::with_wrap`].
+        pub fn set_wrap(&mut self, wrap: bool) -> TkAction {
+            if self.wrap == wrap {
+                return TkAction::empty();
+            }
+            self.wrap = wrap;
+            TkAction::RESIZE
+        }
+
+        /// Get whether wrap mode is enabled
+        pub fn wrap(&self) -> bool {
+            self.wrap
+        }
+
+        /// Enable paged mode (inline)
+        ///
+        /// If `page_size > 0`, the view shows only a page of (up to)
+        /// `page_size` items at a time instead of scrolling over all items.
+        /// (The page may still scroll if it does not fit the view.)
+        #[must_use]
+        pub fn with_page_size(mut self, page_size: usize) -> Self {
+            self.page_size = page_size;
+            self
+        }
+
+        /// Set the page size
+        ///
+        /// See [`ListView::with_page_size`]. Zero disables paged mode.
+        pub fn set_page_size(&mut self, page_size: usize) -> TkAction {
+            if self.page_size == page_size {
+                return TkAction::empty();
+            }
+            self.page_size = page_size;
+            self.page = 0;
+            self.invalidate_view()
+        }
+
+        /// Get the page size (zero if not paged)
+        pub fn page_size(&self) -> usize {
+            self.page_size
+        }
+
+        /// Get the number of pages
+        ///
+        /// This is at least 1 (including when not paged).
+        pub fn num_pages(&self) -> usize {
+            match self.page_size {
+                0 => 1,
+                size => ((self.data.len() + size - 1) / size).max(1),
+            }
+        }
+
+        /// Get the index of the current page
+        pub fn page(&self) -> usize {
+            self.page.min(self.num_pages() - 1)
+        }
+
+        /// Show page `page`
+        ///
+        /// The `page` is clamped to the number of pages. The view is scrolled
+        /// to the start of the page. Does nothing if not paged.
+        pub fn set_page(&mut self, mgr: &mut EventMgr, page: usize) {
+            if self.page_size == 0 {
+                return;
+            }
+            self.page = page.min(self.num_pages() - 1);
+            *mgr |= self.scroll.set_offset(Offset::ZERO);
+            self.update_view(mgr);
+        }
+
+        /// Range of data items shown: (start, len)
+        fn data_range(&self) -> (usize, usize) {
+            let data_len = self.data.len();
+            match self.page_size {
+                0 => (0, data_len),
+                size => {
+                    let start = self.page() * size;
+                    (start, size.min(data_len - start))
+                }
+            }
+        }
+
+        /// Scroll to and set navigation focus on the item at data `index`
+        ///
+        /// The `index` is a position within the data (as used by
+        /// [`ListData::iter_vec_from`]), not a key. Returns the rect of the
+        /// focussed item, or `None` if `index` is out of range or view widgets
+        /// have not yet been allocated (i.e. before the first `set_rect`).
+        ///
+        /// In paged mode, this changes page if required.
+        pub fn focus_item(&mut self, mgr: &mut SetRectMgr, index: usize, key_focus: bool) -> Option<Rect> {
+            if index >= self.data.len() || self.widgets.is_empty() {
+                return None;
+            }
+            if self.page_size > 0 && index / self.page_size != self.page() {
+                self.page = index / self.page_size;
+                *mgr |= self.scroll.set_offset(Offset::ZERO);
+                *mgr |= self.invalidate_view();
+                self.update_widgets(mgr);
+            }
+
+            let solver = self.position_solver(mgr);
+            let (rect, action) = self.scroll.focus_rect(solver.rect(index), self.core.rect);
+            if !action.is_empty() {
+                *mgr |= action;
+                self.update_widgets(mgr);
+            }
+            let len = usize::conv(self.cur_len);
+            mgr.set_nav_focus(self.widgets[index % len].widget.id(), key_focus);
+            Some(rect)
+        }
+
         /// Construct a position solver. Note: this does more work and updates to
         /// self than is necessary in several cases where it is used.
         fn position_solver(&mut self, mgr: &mut SetRectMgr) -> PositionSolver {
-            let data_len = self.data.len();
-            let data_len32 = i32::conv(data_len);
+            let (data_start, data_len) = self.data_range();
+            let per_line = self.per_line;
+            let num_lines = (data_len + per_line - 1) / per_line;
+            let num_lines32 = i32::conv(num_lines);
             let view_size = self.rect().size;
             let mut content_size = view_size;
-            let mut skip;
+            let (skip, cross_skip);
             if self.direction.is_horizontal() {
                 skip = Offset(self.child_size.0 + self.child_inter_margin, 0);
-                content_size.0 = (skip.0 * data_len32 - self.child_inter_margin).max(0);
+                cross_skip = Offset(0, self.child_size.1 + self.child_cross_margin);
+                content_size.0 = (skip.0 * num_lines32 - self.child_inter_margin).max(0);
             } else {
                 skip = Offset(0, self.child_size.1 + self.child_inter_margin);
-                content_size.1 = (skip.1 * data_len32 - self.child_inter_margin).max(0);
+                cross_skip = Offset(self.child_size.0 + self.child_cross_margin, 0);
+                content_size.1 = (skip.1 * num_lines32 - self.child_inter_margin).max(0);
             }
             *mgr |= self.scroll.set_sizes(view_size, content_size);
 
             let offset = u64::conv(self.scroll_offset().extract(self.direction));
-            // first visible data item, in downward direction:
-            let mut first_data = usize::conv(offset / u64::conv(skip.extract(self.direction)));
+            // first visible line, in downward direction:
+            let mut first_line = usize::conv(offset / u64::conv(skip.extract(self.direction)));
 
             // set_rect allocates enough widgets to view a page; we update widget-data allocations
-            let cur_len = self.widgets.len().min(data_len - first_data);
-            self.cur_len = cur_len.cast();
-
-            let mut pos_start = self.core.rect.pos + self.frame_offset;
-            if self.direction.is_reversed() {
-                first_data = (data_len - first_data).saturating_sub(cur_len);
-                pos_start += skip * i32::conv(data_len - 1);
-                skip = skip * -1;
+            let cur_lines = (self.widgets.len() / per_line).min(num_lines.saturating_sub(first_line));
+            let reversed = self.direction.is_reversed();
+            if reversed {
+                first_line = (num_lines - first_line).saturating_sub(cur_lines);
             }
+            // Adjust for paged mode: positions are relative to the page start
+            let first_data = data_start + first_line * per_line;
+            let cur_len = (cur_lines * per_line).min(data_start + data_len - first_data);
+            self.cur_len = cur_len.cast();
 
             PositionSolver {
-                pos_start,
+                pos_start: self.core.rect.pos + self.frame_offset,
                 skip,
+                cross_skip,
                 size: self.child_size,
+                data_start,
+                per_line,
+                num_lines,
+                reversed,
                 first_data,
                 cur_len,
             }
@@ -328,6 +561,8 @@ widget! {
             let solver = self.position_solver(mgr);
 
             let mut action = TkAction::empty();
+            let end = solver.first_data + solver.cur_len;
+            self.data.request_range(solver.first_data..end);
             for (i, item) in self
                 .data
                 .iter_vec_from(solver.first_data, solver.cur_len)
@@ -340,6 +575,8 @@ widget! {
                 if key != w.key {
                     w.key = key;
                     action |= self.view.set(&mut w.widget, item.1);
+                    let state = self.data.load_state(&item.0);
+                    action |= self.view.set_load_state(&mut w.widget, state);
                 }
                 let rect = solver.rect(i);
                 if w.widget.rect() != rect {
@@ -357,7 +594,7 @@ widget! {
             // TODO: maybe we should support a scrollbar on the other axis?
             // We would need to report a fake min-child-size to enable scrolling.
             let item_min = self.child_size_min + self.child_inter_margin;
-            let num = i32::conv(self.data.len());
+            let num = i32::conv((self.data_range().1 + self.per_line - 1) / self.per_line);
             let min_size = (item_min * num - self.child_inter_margin).max(0);
             (
                 self.direction.is_horizontal() && min_size > size.0,
@@ -402,13 +639,60 @@ widget! {
 
     impl WidgetConfig for Self {
         fn configure(&mut self, mgr: &mut SetRectMgr) {
-            if let Some(handle) = self.data.update_handle() {
+            for handle in self.data.update_handles() {
                 mgr.update_on_handle(handle, self.id());
             }
             mgr.register_nav_fallback(self.id());
         }
     }
 
+    impl SaveState for Self {
+        /// Saves the scroll offset and selection
+        ///
+        /// The selection is saved as a list of data indices (since keys may
+        /// not be representable as a [`StateValue`]).
+        fn save_state(&self) -> Option<StateValue> {
+            let mut selection = vec![];
+            if !self.selection.is_empty() {
+                for (i, (key, _)) in self.data.iter_vec(usize::MAX).into_iter().enumerate() {
+                    if self.selection.contains(&key) {
+                        selection.push(StateValue::Int(i.cast()));
+                    }
+                }
+            }
+            let offset = self.scroll.offset().into();
+            Some(StateValue::List(vec![offset, StateValue::List(selection)]))
+        }
+
+        fn restore_state(&mut self, state: &StateValue) -> TkAction {
+            let (offset, selection) = match state.as_list() {
+                Some([offset, selection]) => (offset, selection),
+                _ => return TkAction::empty(),
+            };
+            let mut action = match offset.as_offset() {
+                Some(offset) => self.scroll.set_offset(offset) | TkAction::SET_SIZE,
+                None => TkAction::empty(),
+            };
+            if let Some(selection) = selection.as_list() {
+                action |= self.clear_selected();
+                for index in selection.iter().filter_map(|v| v.as_int()) {
+                    let index = match usize::try_from(index) {
+                        Ok(index) => index,
+                        Err(_) => continue,
+                    };
+                    if let Some((key, _)) = self.data.iter_vec_from(index, 1).into_iter().next() {
+                        action |= self.select(key).unwrap_or_default();
+                    }
+                }
+            }
+            action
+        }
+
+        fn saves_children(&self) -> bool {
+            false
+        }
+    }
+
     impl Layout for Self {
         fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
             // We use an invisible frame for highlighting selections, drawing into the margin
@@ -424,6 +708,12 @@ widget! {
                 self.child_inter_margin = m.0.max(m.1).max(inner_margin);
                 rules.multiply_with_margin(2, self.ideal_visible);
                 rules.set_stretch(rules.stretch().max(Stretch::High));
+            } else if self.wrap {
+                self.child_cross_ideal = rules.ideal_size();
+                let m = rules.margins_i32();
+                self.child_cross_margin = m.0.max(m.1).max(inner_margin);
+                rules.multiply_with_margin(1, self.ideal_visible);
+                rules.set_stretch(rules.stretch().max(Stretch::High));
             }
             let (rules, offset, size) = frame.surround_with_margin(rules);
             self.frame_offset.set_component(axis, offset);
@@ -434,32 +724,47 @@ widget! {
         fn set_rect(&mut self, mgr: &mut SetRectMgr, rect: Rect, mut align: AlignHints) {
             self.core.rect = rect;
 
-            let mut child_size = rect.size - self.frame_size;
-            let num = if self.direction.is_horizontal() {
-                if child_size.0 >= self.ideal_visible * self.child_size_ideal {
-                    child_size.0 = self.child_size_ideal;
-                } else {
-                    child_size.0 = self.child_size_min;
-                }
-                let skip = child_size.0 + self.child_inter_margin;
+            let avail = rect.size - self.frame_size;
+            let is_horiz = self.direction.is_horizontal();
+            let (main_avail, cross_avail) = if is_horiz {
+                (avail.0, avail.1)
+            } else {
+                (avail.1, avail.0)
+            };
+            let main = if main_avail >= self.ideal_visible * self.child_size_ideal {
+                self.child_size_ideal
+            } else {
+                self.child_size_min
+            };
+            let skip = main + self.child_inter_margin;
+            let lines = (rect.size.extract(self.direction) + skip - 1) / skip + 1;
+
+            let mut cross = cross_avail;
+            self.per_line = 1;
+            if self.wrap {
+                cross = self.child_cross_ideal.min(cross_avail);
+                let cross_skip = (cross + self.child_cross_margin).max(1);
+                let n = (cross_avail + self.child_cross_margin) / cross_skip;
+                self.per_line = usize::conv(n.max(1));
+            }
+
+            let child_size;
+            if is_horiz {
+                child_size = Size(main, cross);
                 align.horiz = None;
-                (rect.size.0 + skip - 1) / skip + 1
             } else {
-                if child_size.1 >= self.ideal_visible * self.child_size_ideal {
-                    child_size.1 = self.child_size_ideal;
-                } else {
-                    child_size.1 = self.child_size_min;
-                }
-                let skip = child_size.1 + self.child_inter_margin;
+                child_size = Size(cross, main);
                 align.vert = None;
-                (rect.size.1 + skip - 1) / skip + 1
-            };
+            }
+            if self.wrap {
+                align = AlignHints::NONE;
+            }
+            let num = usize::conv(lines) * self.per_line;
 
             self.child_size = child_size;
             self.align_hints = align;
 
             let old_num = self.widgets.len();
-            let num = usize::conv(num);
             if old_num < num {
                 debug!("allocating widgets (old len = {}, new = {})", old_num, num);
                 self.widgets.reserve(num - old_num);
@@ -493,18 +798,19 @@ widget! {
             }
 
             let solver = self.position_solver(mgr);
-            let last_data = self.data.len() - 1;
+            let (first_data, data_len) = self.data_range();
+            let last_data = first_data + data_len - 1;
             let data = if let Some(index) = from {
                 let data = solver.child_to_data(index);
                 if !reverse && data < last_data {
                     data + 1
-                } else if reverse && data > 0 {
+                } else if reverse && data > first_data {
                     data - 1
                 } else {
                     return None;
                 }
             } else if !reverse {
-                0
+                first_data
             } else {
                 last_data
             };
@@ -617,41 +923,59 @@ widget! {
                         return Response::Used;
                     }
                 }
+                Event::Command(Command::Copy, _) => {
+                    return match self.copy_selection() {
+                        Some(text) => {
+                            mgr.set_clipboard(text);
+                            Response::Used
+                        }
+                        None => Response::Unused,
+                    };
+                }
                 Event::Command(cmd, _) => {
                     let solver = mgr.set_rect_mgr(|mgr| self.position_solver(mgr));
                     let cur = mgr
                         .nav_focus()
                         .and_then(|id| self.find_child_index(id))
                         .map(|index| solver.child_to_data(index));
-                    let last = self.data.len().wrapping_sub(1);
-                    let is_vert = self.direction.is_vertical();
+                    let (first, data_len) = self.data_range();
+                    let last = (first + data_len).wrapping_sub(1);
                     let len = solver.cur_len;
+                    let step = solver.per_line;
+
+                    // Commands moving to the previous/next line and item (in data order)
+                    let (mut line_prev, mut line_next) = (Command::Up, Command::Down);
+                    let (mut item_prev, mut item_next) = (Command::Left, Command::Right);
+                    if self.direction.is_horizontal() {
+                        std::mem::swap(&mut line_prev, &mut item_prev);
+                        std::mem::swap(&mut line_next, &mut item_next);
+                    }
+                    if self.direction.is_reversed() {
+                        std::mem::swap(&mut line_prev, &mut line_next);
+                    }
+                    let wrap = self.wrap;
+                    let page = (len / 2 / step).max(1) * step;
 
                     let data = match (cmd, cur) {
-                        _ if last == usize::MAX => None,
+                        _ if data_len == 0 => None,
                         _ if !self.widgets[0].widget.key_nav() => None,
-                        (Command::Home, _) => Some(0),
+                        (Command::Home, _) => Some(first),
                         (Command::End, _) => Some(last),
-                        (Command::Left, Some(cur)) if !is_vert && cur > 0 => Some(cur - 1),
-                        (Command::Up, Some(cur)) if is_vert && cur > 0 => Some(cur - 1),
-                        (Command::Right, Some(cur)) if !is_vert && cur < last => Some(cur + 1),
-                        (Command::Down, Some(cur)) if is_vert && cur < last => Some(cur + 1),
-                        (Command::PageUp, Some(cur)) if cur > 0 => Some(cur.saturating_sub(len / 2)),
-                        (Command::PageDown, Some(cur)) if cur < last => Some((cur + len / 2).min(last)),
+                        (c, Some(cur)) if c == line_prev && cur >= first + step => Some(cur - step),
+                        (c, Some(cur)) if c == line_next && (cur - first) / step < (last - first) / step => {
+                            Some((cur + step).min(last))
+                        }
+                        (c, Some(cur)) if wrap && c == item_prev && cur > first => Some(cur - 1),
+                        (c, Some(cur)) if wrap && c == item_next && cur < last => Some(cur + 1),
+                        (Command::PageUp, Some(cur)) if cur > first => Some(cur.saturating_sub(page).max(first)),
+                        (Command::PageDown, Some(cur)) if cur < last => Some((cur + page).min(last)),
                         _ => None,
                     };
-                    return if let Some(index) = data {
-                        // Set nav focus to index and update scroll position
-                        let (rect, action) = self.scroll.focus_rect(solver.rect(index), self.core.rect);
-                        if !action.is_empty() {
-                            *mgr |= action;
-                            mgr.set_rect_mgr(|mgr| self.update_widgets(mgr));
-                        }
-                        let len = usize::conv(self.cur_len);
-                        mgr.set_nav_focus(self.widgets[index % len].widget.id(), true);
-                        Response::Focus(rect)
-                    } else {
-                        Response::Used
+                    // Set nav focus to index and update scroll position
+                    let rect = data.and_then(|index| mgr.set_rect_mgr(|mgr| self.focus_item(mgr, index, true)));
+                    return match rect {
+                        Some(rect) => Response::Focus(rect),
+                        None => Response::Used,
                     };
                 }
                 _ => (), // fall through to scroll handler
@@ -779,9 +1103,17 @@ widget! {
 }
 
 struct PositionSolver {
+    /// Position of the content origin
     pos_start: Coord,
+    /// Offset between lines
     skip: Offset,
+    /// Offset between items within a line
+    cross_skip: Offset,
     size: Size,
+    data_start: usize,
+    per_line: usize,
+    num_lines: usize,
+    reversed: bool,
     first_data: usize,
     cur_len: usize,
 }
@@ -798,7 +1130,14 @@ impl PositionSolver {
 
     /// Rect of data item i
     fn rect(&self, i: usize) -> Rect {
-        let pos = self.pos_start + self.skip * i32::conv(i);
+        let index = i - self.data_start;
+        let mut line = index / self.per_line;
+        if self.reversed {
+            line = self.num_lines - 1 - line;
+        }
+        let pos = self.pos_start
+            + self.skip * i32::conv(line)
+            + self.cross_skip * i32::conv(index % self.per_line);
         Rect::new(pos, self.size)
     }
 }
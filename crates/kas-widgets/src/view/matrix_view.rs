@@ -5,7 +5,7 @@
 
 //! List view widget
 
-use super::{driver, Driver, PressPhase, SelectionError, SelectionMode};
+use super::{driver, CopyFormat, Driver, PressPhase, SelectionError, SelectionMode};
 #[allow(unused)] // doc links
 use crate::ScrollBars;
 use crate::Scrollable;
@@ -71,6 +71,7 @@ widget! {
         sel_mode: SelectionMode,
         // TODO(opt): replace selection list with RangeOrSet type?
         selection: LinearSet<T::Key>,
+        copy_format: CopyFormat,
         press_phase: PressPhase,
         press_target: Option<T::Key>,
     }
@@ -102,6 +103,7 @@ widget! {
                 scroll: Default::default(),
                 sel_mode: SelectionMode::None,
                 selection: Default::default(),
+                copy_format: CopyFormat::default(),
                 press_phase: PressPhase::None,
                 press_target: None,
             }
@@ -234,6 +236,75 @@ widget! {
             }
         }
 
+        /// Set the format used to copy selected items (inline)
+        ///
+        /// Default: [`CopyFormat::Tsv`].
+        #[must_use]
+        pub fn with_copy_format(mut self, format: CopyFormat) -> Self {
+            self.copy_format = format;
+            self
+        }
+
+        /// Get the format used to copy selected items
+        pub fn copy_format(&self) -> CopyFormat {
+            self.copy_format
+        }
+
+        /// Set the format used to copy selected items
+        pub fn set_copy_format(&mut self, format: CopyFormat) {
+            self.copy_format = format;
+        }
+
+        /// Get the selection as text, formatted for the clipboard
+        ///
+        /// Selected cells are copied in data order as a rectangle covering all
+        /// rows and columns with a selected cell; unselected cells within this
+        /// rectangle are copied as empty fields.
+        /// Item text is provided by [`Driver::to_clipboard_text`]. Returns `None`
+        /// if nothing is selected or no selected item has text.
+        ///
+        /// This is used to handle [`Command::Copy`].
+        pub fn copy_selection(&self) -> Option<String> {
+            if self.selection.is_empty() {
+                return None;
+            }
+            let cols = self.data.col_iter_vec(usize::MAX);
+            let mut used_cols = vec![false; cols.len()];
+            let mut lines = vec![];
+            for row in self.data.row_iter_vec(usize::MAX) {
+                let line: Vec<_> = cols
+                    .iter()
+                    .map(|col| {
+                        let key = T::make_key(&row, col);
+                        self.selection.contains(&key).then(|| key)
+                    })
+                    .collect();
+                if line.iter().any(|key| key.is_some()) {
+                    for (used, key) in used_cols.iter_mut().zip(line.iter()) {
+                        *used |= key.is_some();
+                    }
+                    lines.push(line);
+                }
+            }
+
+            let mut any = false;
+            let rows: Vec<Vec<String>> = lines
+                .into_iter()
+                .map(|line| {
+                    let iter = line.into_iter().zip(used_cols.iter());
+                    iter.filter(|(_, used)| **used)
+                        .map(|(key, _)| {
+                            let item = key.and_then(|key| self.data.get_cloned(&key));
+                            let text = item.and_then(|item| self.view.to_clipboard_text(&item));
+                            any |= text.is_some();
+                            text.unwrap_or_default()
+                        })
+                        .collect()
+                })
+                .collect();
+            any.then(|| self.copy_format.format(&rows))
+        }
+
         /// Manually trigger an update to handle changed data
         pub fn update_view(&mut self, mgr: &mut EventMgr) {
             let data = &self.data;
@@ -269,6 +340,9 @@ widget! {
             let offset = self.scroll_offset();
             let first_col = usize::conv(u64::conv(offset.0) / u64::conv(skip.0));
             let first_row = usize::conv(u64::conv(offset.1) / u64::conv(skip.1));
+            let col_end = first_col + usize::conv(self.alloc_len.cols);
+            let row_end = first_row + usize::conv(self.alloc_len.rows);
+            self.data.request_range(first_col..col_end, first_row..row_end);
             let cols = self
                 .data
                 .col_iter_vec_from(first_col, self.alloc_len.cols.cast());
@@ -295,6 +369,8 @@ widget! {
                         if let Some(item) = self.data.get_cloned(&key) {
                             w.key = Some(key.clone());
                             action |= self.view.set(&mut w.widget, item);
+                            let state = self.data.load_state(&key);
+                            action |= self.view.set_load_state(&mut w.widget, state);
                         } else {
                             w.key = None; // disables drawing and clicking
                         }
@@ -356,13 +432,72 @@ widget! {
 
     impl WidgetConfig for Self {
         fn configure(&mut self, mgr: &mut SetRectMgr) {
-            if let Some(handle) = self.data.update_handle() {
+            for handle in self.data.update_handles() {
                 mgr.update_on_handle(handle, self.id());
             }
             mgr.register_nav_fallback(self.id());
         }
     }
 
+    impl SaveState for Self {
+        /// Saves the scroll offset and selection
+        ///
+        /// The selection is saved as a list of `[column, row]` data indices
+        /// (since keys may not be representable as a [`StateValue`]).
+        fn save_state(&self) -> Option<StateValue> {
+            let mut selection = vec![];
+            if !self.selection.is_empty() {
+                let cols = self.data.col_iter_vec(usize::MAX);
+                for (r, row) in self.data.row_iter_vec(usize::MAX).iter().enumerate() {
+                    for (c, col) in cols.iter().enumerate() {
+                        if self.selection.contains(&T::make_key(row, col)) {
+                            let cell = vec![StateValue::Int(c.cast()), StateValue::Int(r.cast())];
+                            selection.push(StateValue::List(cell));
+                        }
+                    }
+                }
+            }
+            let offset = self.scroll.offset().into();
+            Some(StateValue::List(vec![offset, StateValue::List(selection)]))
+        }
+
+        fn restore_state(&mut self, state: &StateValue) -> TkAction {
+            let (offset, selection) = match state.as_list() {
+                Some([offset, selection]) => (offset, selection),
+                _ => return TkAction::empty(),
+            };
+            let mut action = match offset.as_offset() {
+                Some(offset) => self.scroll.set_offset(offset) | TkAction::SET_SIZE,
+                None => TkAction::empty(),
+            };
+            if let Some(selection) = selection.as_list() {
+                action |= self.clear_selected();
+                for cell in selection {
+                    let (c, r) = match cell.as_list() {
+                        Some([c, r]) => (c.as_int(), r.as_int()),
+                        _ => continue,
+                    };
+                    let index = |i: Option<i64>| i.and_then(|i| usize::try_from(i).ok());
+                    let (c, r) = match (index(c), index(r)) {
+                        (Some(c), Some(r)) => (c, r),
+                        _ => continue,
+                    };
+                    let col = self.data.col_iter_vec_from(c, 1).into_iter().next();
+                    let row = self.data.row_iter_vec_from(r, 1).into_iter().next();
+                    if let (Some(col), Some(row)) = (col, row) {
+                        let key = T::make_key(&row, &col);
+                        action |= self.select(key).unwrap_or_default();
+                    }
+                }
+            }
+            action
+        }
+
+        fn saves_children(&self) -> bool {
+            false
+        }
+    }
+
     impl Layout for Self {
         fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
             // We use an invisible frame for highlighting selections, drawing into the margin
@@ -587,6 +722,15 @@ widget! {
                         return Response::Used;
                     }
                 }
+                Event::Command(Command::Copy, _) => {
+                    return match self.copy_selection() {
+                        Some(text) => {
+                            mgr.set_clipboard(text);
+                            Response::Used
+                        }
+                        None => Response::Unused,
+                    };
+                }
                 Event::Command(cmd, _) => {
                     // Simplified version of logic in update_widgets
                     let (cols, rows): (usize, usize) = (self.cur_len.cols.cast(), self.cur_len.rows.cast());
@@ -35,6 +35,8 @@
 //!     keyboard navigation (e.g. useful to allow selection of static items)
 //! -   [`driver::CheckBox`] and [`driver::RadioBox`] support the `bool` type
 //! -   [`driver::Slider`] constructs a slider with a fixed range
+//! -   [`driver::Display`] supports display-only widgets (e.g. a progress bar)
+//!     via user-supplied functions
 //!
 //! In MVC terminology, the driver is perhaps most similar to the controller,
 //! while the widgets constructed by the driver are the view, but this analogy
@@ -57,6 +59,7 @@
 //!     on the performance of the model)
 //! -   supporting scrolling (see [`super::Scrollable`])
 //! -   supporting item selection
+//! -   copying selected items to the clipboard (see [`CopyFormat`])
 //! -   controlling scrolling and selection via otherwise unhandled events
 //!
 //! The following views are provided:
@@ -105,6 +108,60 @@ impl Default for SelectionMode {
     }
 }
 
+/// Clipboard format used to copy selections from [`ListView`] and [`MatrixView`]
+///
+/// Views copy selected items on [`kas::event::Command::Copy`] (usually
+/// <kbd>Ctrl+C</kbd>), with one line per row and one field per column. Item
+/// text is provided by [`Driver::to_clipboard_text`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CopyFormat {
+    /// Tab-separated values (fields may not contain tabs or line breaks;
+    /// these are replaced with spaces)
+    Tsv,
+    /// Comma-separated values (fields are quoted where required)
+    Csv,
+}
+impl Default for CopyFormat {
+    fn default() -> Self {
+        CopyFormat::Tsv
+    }
+}
+impl CopyFormat {
+    /// Format `rows` of fields as text
+    pub fn format(self, rows: &[Vec<String>]) -> String {
+        let mut text = String::new();
+        for (i, row) in rows.iter().enumerate() {
+            if i > 0 {
+                text.push('\n');
+            }
+            for (j, field) in row.iter().enumerate() {
+                match self {
+                    CopyFormat::Tsv => {
+                        if j > 0 {
+                            text.push('\t');
+                        }
+                        let escape = |c: char| matches!(c, '\t' | '\n' | '\r');
+                        text.push_str(&field.replace(escape, " "));
+                    }
+                    CopyFormat::Csv => {
+                        if j > 0 {
+                            text.push(',');
+                        }
+                        if field.contains(|c: char| matches!(c, ',' | '"' | '\n' | '\r')) {
+                            text.push('"');
+                            text.push_str(&field.replace('"', "\"\""));
+                            text.push('"');
+                        } else {
+                            text.push_str(field);
+                        }
+                    }
+                }
+            }
+        }
+        text
+    }
+}
+
 /// Selection errors
 #[derive(Error, Debug)]
 pub enum SelectionError {
@@ -113,3 +170,97 @@ pub enum SelectionError {
     #[error("invalid key or index")]
     Key,
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn rows(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        let row = |row: &&[&str]| row.iter().map(|s| s.to_string()).collect();
+        rows.iter().map(row).collect()
+    }
+
+    #[test]
+    fn copy_tsv() {
+        let format = CopyFormat::Tsv;
+        assert_eq!(format.format(&[]), "");
+        assert_eq!(format.format(&rows(&[&["a"]])), "a");
+        let text = format.format(&rows(&[&["a", "b"], &["", "c"]]));
+        assert_eq!(text, "a\tb\n\tc");
+        let text = format.format(&rows(&[&["a\tb", "c\nd\r"], &["e,\"f\""]]));
+        assert_eq!(text, "a b\tc d \ne,\"f\"");
+    }
+
+    #[test]
+    fn copy_csv() {
+        let format = CopyFormat::Csv;
+        assert_eq!(format.format(&[]), "");
+        let text = format.format(&rows(&[&["a", "b"], &["", "c"]]));
+        assert_eq!(text, "a,b\n,c");
+        let text = format.format(&rows(&[&["a,b", "say \"hi\""], &["c\nd", "e\tf"]]));
+        assert_eq!(text, "\"a,b\",\"say \"\"hi\"\"\"\n\"c\nd\",e\tf");
+    }
+
+    #[test]
+    fn driver_text() {
+        let text = |s: &str| Some(s.to_string());
+        assert_eq!(driver::Default.to_clipboard_text(&"abc"), text("abc"));
+        assert_eq!(driver::DefaultNav.to_clipboard_text(&-3i32), text("-3"));
+        assert_eq!(driver::Default.to_clipboard_text(&true), text("true"));
+        let slider = driver::Slider::<i32, kas::dir::Right>::make(0, 10, 1);
+        assert_eq!(slider.to_clipboard_text(&7), text("7"));
+
+        let display = driver::Display::new(crate::ProgressBar::<kas::dir::Right>::new, |bar, v| {
+            bar.set_value(v)
+        });
+        assert_eq!(display.to_clipboard_text(&0.5), None);
+        let display = display.with_text(|v| format!("{}%", v * 100.0));
+        assert_eq!(display.to_clipboard_text(&0.5), text("50%"));
+    }
+
+    #[test]
+    fn list_state() {
+        use kas::{SaveState, StateValue};
+        type List = ListView<kas::dir::Down, Vec<i32>>;
+        let data = vec![10, 20, 30, 40];
+        let mut list = List::new(data.clone()).with_selection_mode(SelectionMode::Multiple);
+        let _ = list.select(3);
+        let _ = list.select(1);
+        let state = list.save_state().unwrap();
+
+        let mut list = List::new(data).with_selection_mode(SelectionMode::Multiple);
+        let _ = list.select(0);
+        let _ = list.restore_state(&state);
+        let mut selected: Vec<usize> = list.selected_iter().cloned().collect();
+        selected.sort_unstable();
+        assert_eq!(selected, vec![1, 3]);
+        assert_eq!(list.save_state(), Some(state));
+
+        // Invalid values are ignored
+        let _ = list.restore_state(&StateValue::Bool(true));
+        assert_eq!(list.selected_iter().count(), 2);
+    }
+
+    #[test]
+    fn matrix_state() {
+        use kas::updatable::Table;
+        use kas::SaveState;
+        type Matrix = MatrixView<Table>;
+        let data = Table::from_csv("a,b\n1,2\n3,4\n").unwrap();
+        let mut matrix = Matrix::new(data.clone()).with_selection_mode(SelectionMode::Multiple);
+        let _ = matrix.select((1, 0));
+        let _ = matrix.select((0, 1));
+        let state = matrix.save_state().unwrap();
+
+        let mut matrix = Matrix::new(data).with_selection_mode(SelectionMode::Single);
+        let _ = matrix.restore_state(&state);
+        // Single selection mode retains only one entry
+        assert_eq!(matrix.selected_iter().count(), 1);
+
+        let _ = matrix.set_selection_mode(SelectionMode::Multiple);
+        let _ = matrix.restore_state(&state);
+        assert!(matrix.is_selected(&(1, 0)));
+        assert!(matrix.is_selected(&(0, 1)));
+        assert_eq!(matrix.save_state(), Some(state));
+    }
+}
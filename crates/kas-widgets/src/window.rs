@@ -5,43 +5,125 @@
 
 //! Window widgets
 
+use crate::TitleBar;
+use kas::cast::ConvFloat;
+use kas::event::{Command, CursorIcon, WindowKeyOp};
 use kas::layout;
 use kas::prelude::*;
-use kas::Icon;
-use kas::{Future, WindowId};
+use kas::{Decorations, Icon, WindowFlags};
+use kas::{Future, WidgetState, WindowId};
 use smallvec::SmallVec;
 use std::error::Error;
 use std::path::Path;
 
+/// Width of the edge used to resize windows with [`Decorations::Kas`] (virtual pixels)
+const RESIZE_BORDER: f32 = 4.0;
+
 widget! {
     /// The main instantiation of the [`Window`] trait.
+    ///
+    /// The window may be moved and resized via the keyboard using
+    /// [`Command::WindowMove`] and [`Command::WindowResize`] (by default
+    /// bound to <kbd>Alt</kbd>+<kbd>F7</kbd> and <kbd>Alt</kbd>+<kbd>F8</kbd>);
+    /// see [`EventMgr::start_window_key_op`].
     #[autoimpl(Clone where W: Clone skip self.popups, self.drop)]
     #[autoimpl(Debug skip self.drop, self.icon)]
     pub struct Window<W: Widget + 'static> {
         #[widget_core]
         core: CoreData,
         restrict_dimensions: (bool, bool),
+        zoom: bool,
         title: String,
+        decorations: Decorations,
+        flags: WindowFlags,
+        monitor: Option<usize>,
         #[widget]
         w: W,
-        popups: SmallVec<[(WindowId, kas::Popup); 16]>,
+        // Title bar; present only with client-side decorations
+        bar: Option<TitleBar>,
+        layout_list: layout::FixedRowStorage<2>,
+        resize_border: i32,
+        // Resize in progress: edge directions, press coord and target size
+        resize: Option<((i32, i32), Coord, Size)>,
+        // Pop-ups, with rect when placed in a native window
+        popups: SmallVec<[(WindowId, kas::Popup, Option<Rect>); 16]>,
         drop: Option<(Box<dyn FnMut(&mut W)>, UpdateHandle)>,
         icon: Option<Icon>,
+        restore: Option<WidgetState>,
+    }
+
+    impl WidgetChildren for Self {
+        #[inline]
+        fn num_children(&self) -> usize {
+            1 + usize::from(self.bar.is_some())
+        }
+        #[inline]
+        fn get_child(&self, index: usize) -> Option<&dyn WidgetConfig> {
+            match index {
+                0 => Some(self.w.as_widget()),
+                1 => self.bar.as_ref().map(|bar| bar.as_widget()),
+                _ => None,
+            }
+        }
+        #[inline]
+        fn get_child_mut(&mut self, index: usize) -> Option<&mut dyn WidgetConfig> {
+            match index {
+                0 => Some(self.w.as_widget_mut()),
+                1 => self.bar.as_mut().map(|bar| bar.as_widget_mut()),
+                _ => None,
+            }
+        }
+    }
+
+    impl WidgetConfig for Self {
+        fn configure(&mut self, mgr: &mut SetRectMgr) {
+            mgr.enable_zoom(self.zoom);
+        }
     }
 
     impl Layout for Self {
         #[inline]
         fn layout(&mut self) -> layout::Layout<'_> {
-            layout::Layout::single(&mut self.w)
+            if let Some(bar) = self.bar.as_mut() {
+                let arr = [
+                    layout::Layout::single(bar),
+                    layout::Layout::single(&mut self.w),
+                ];
+                layout::Layout::list(arr.into_iter(), Direction::Down, &mut self.layout_list)
+            } else {
+                layout::Layout::single(&mut self.w)
+            }
+        }
+
+        fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
+            self.resize_border = i32::conv_nearest(size_mgr.pixels_from_virtual(RESIZE_BORDER));
+            self.layout().size_rules(size_mgr, axis)
+        }
+
+        fn set_rect(&mut self, mgr: &mut SetRectMgr, rect: Rect, align: AlignHints) {
+            self.core.rect = rect;
+            self.layout().set_rect(mgr, rect, align);
+            if let Some(state) = self.restore.take() {
+                *mgr |= state.restore(self);
+            }
         }
 
         #[inline]
         fn find_id(&mut self, coord: Coord) -> Option<WidgetId> {
+            // Native pop-ups may lie outside of our rect
+            for popup in self.popups.iter_mut().rev() {
+                if let Some(id) = self.w.find_widget_mut(&popup.1.id).and_then(|w| w.find_id(coord)) {
+                    return Some(id);
+                }
+            }
             if !self.rect().contains(coord) {
                 return None;
             }
-            for popup in self.popups.iter_mut().rev() {
-                if let Some(id) = self.w.find_widget_mut(&popup.1.id).and_then(|w| w.find_id(coord)) {
+            if self.decorations == Decorations::Kas {
+                if self.resize_edges(coord) != (0, 0) {
+                    return Some(self.id());
+                }
+                if let Some(id) = self.bar.as_mut().and_then(|bar| bar.find_id(coord)) {
                     return Some(id);
                 }
             }
@@ -51,8 +133,11 @@ widget! {
         #[inline]
         fn draw(&mut self, mut draw: DrawMgr) {
             let mut draw = draw.with_core(self.core_data());
+            if let Some(bar) = self.bar.as_mut() {
+                bar.draw(draw.re());
+            }
             self.w.draw(draw.re());
-            for (_, popup) in &self.popups {
+            for (_, popup, _) in self.popups.iter().filter(|p| p.2.is_none()) {
                 if let Some(widget) = self.w.find_widget_mut(&popup.id) {
                     draw.with_overlay(widget.rect(), |mut draw| {
                         widget.draw(draw.re());
@@ -62,12 +147,88 @@ widget! {
         }
     }
 
+    impl Handler for Self {
+        type Msg = VoidMsg;
+
+        fn handle(&mut self, mgr: &mut EventMgr, event: Event) -> Response<Self::Msg> {
+            if let Event::Command(cmd, _) = event {
+                let op = match cmd {
+                    Command::WindowMove => WindowKeyOp::Move,
+                    Command::WindowResize => WindowKeyOp::Resize,
+                    _ => return Response::Unused,
+                };
+                mgr.start_window_key_op(op, self.core.rect.size);
+                return Response::Used;
+            }
+            if self.decorations != Decorations::Kas {
+                return Response::Unused;
+            }
+            match event {
+                Event::PressStart { source, coord, .. } if source.is_primary() => {
+                    let edges = self.resize_edges(coord);
+                    if edges == (0, 0) {
+                        return Response::Unused;
+                    }
+                    let icon = resize_cursor(edges);
+                    mgr.grab_press_unique(self.id(), source, coord, Some(icon));
+                    self.resize = Some((edges, coord, self.core.rect.size));
+                    Response::Used
+                }
+                Event::PressMove { coord, .. } => {
+                    if let Some((edges, ref mut press, ref mut size)) = self.resize {
+                        let delta = coord - *press;
+                        let mut offset = Offset::ZERO;
+                        // The window origin moves with left/top edges, thus
+                        // the press coordinate remains fixed relative to the
+                        // window; for right/bottom edges it must be updated.
+                        match edges.0 {
+                            -1 => {
+                                offset.0 = delta.0;
+                                size.0 -= delta.0;
+                            }
+                            1 => {
+                                size.0 += delta.0;
+                                press.0 = coord.0;
+                            }
+                            _ => (),
+                        }
+                        match edges.1 {
+                            -1 => {
+                                offset.1 = delta.1;
+                                size.1 -= delta.1;
+                            }
+                            1 => {
+                                size.1 += delta.1;
+                                press.1 = coord.1;
+                            }
+                            _ => (),
+                        }
+                        let size = *size;
+                        mgr.resize_window(offset, size);
+                    }
+                    Response::Used
+                }
+                Event::PressEnd { .. } => {
+                    self.resize = None;
+                    Response::Used
+                }
+                _ => Response::Unused,
+            }
+        }
+    }
+
     impl SendEvent for Self where W::Msg: Into<VoidMsg> {
         fn send(&mut self, mgr: &mut EventMgr, id: WidgetId, event: Event) -> Response<Self::Msg> {
-            if self.is_disabled() || self.eq_id(&id) {
+            if self.is_disabled() {
                 Response::Unused
-            } else {
+            } else if self.eq_id(&id) {
+                EventMgr::handle_generic(self, mgr, event)
+            } else if self.w.id().is_ancestor_of(&id) {
                 self.w.send(mgr, id, event).into()
+            } else if let Some(bar) = self.bar.as_mut() {
+                bar.send(mgr, id, event).into()
+            } else {
+                Response::Unused
             }
         }
     }
@@ -85,9 +246,21 @@ widget! {
             self.restrict_dimensions
         }
 
+        fn decorations(&self) -> Decorations {
+            self.decorations
+        }
+
+        fn flags(&self) -> WindowFlags {
+            self.flags
+        }
+
+        fn monitor(&self) -> Option<usize> {
+            self.monitor
+        }
+
         fn add_popup(&mut self, mgr: &mut EventMgr, id: WindowId, popup: kas::Popup) {
             let index = self.popups.len();
-            self.popups.push((id, popup));
+            self.popups.push((id, popup, None));
             mgr.set_rect_mgr(|mgr| self.resize_popup(mgr, index));
             mgr.send_action(TkAction::REDRAW);
         }
@@ -102,6 +275,37 @@ widget! {
             }
         }
 
+        fn set_popup_bounds(
+            &mut self,
+            mgr: &mut SetRectMgr,
+            id: WindowId,
+            bounds: Option<Rect>,
+        ) -> Option<Rect> {
+            let index = self.popups.iter().position(|p| p.0 == id)?;
+            self.popups[index].2 = None;
+            if let Some(bounds) = bounds {
+                let (_, fits) = self.place_popup(mgr, index, self.core.rect);
+                if !fits {
+                    let (rect, _) = self.place_popup(mgr, index, bounds);
+                    self.popups[index].2 = Some(rect);
+                }
+            }
+            self.resize_popup(mgr, index);
+            self.popups[index].2
+        }
+
+        fn draw_popup(&mut self, mut draw: DrawMgr, id: WindowId) {
+            let mut draw = draw.with_core(self.core_data());
+            if let Some((_, popup, Some(rect))) = self.popups.iter().find(|p| p.0 == id) {
+                if let Some(widget) = self.w.find_widget_mut(&popup.id) {
+                    let clip = Rect::new(Coord::ZERO, rect.size);
+                    draw.with_clip_region(clip, rect.pos - Coord::ZERO, |mut draw| {
+                        widget.draw(draw.re());
+                    });
+                }
+            }
+        }
+
         fn resize_popups(&mut self, mgr: &mut SetRectMgr) {
             for i in 0..self.popups.len() {
                 self.resize_popup(mgr, i);
@@ -123,11 +327,20 @@ impl<W: Widget> Window<W> {
         Window {
             core: Default::default(),
             restrict_dimensions: (true, false),
+            zoom: false,
             title: title.to_string(),
+            decorations: Decorations::Server,
+            flags: WindowFlags::empty(),
+            monitor: None,
             w,
+            bar: None,
+            layout_list: Default::default(),
+            resize_border: 0,
+            resize: None,
             popups: Default::default(),
             drop: None,
             icon: None,
+            restore: None,
         }
     }
 
@@ -138,6 +351,80 @@ impl<W: Widget> Window<W> {
         self.restrict_dimensions = (min, max);
     }
 
+    /// Set the window decorations (chain style)
+    ///
+    /// With [`Decorations::Kas`], the window draws its own [`TitleBar`] and
+    /// may be resized by dragging its edges, allowing the theme to control
+    /// the entire window appearance.
+    ///
+    /// By default, [`Decorations::Server`] is used. This must be set before
+    /// the window is added to the toolkit.
+    pub fn with_decorations(mut self, decorations: Decorations) -> Self {
+        self.decorations = decorations;
+        self.bar = match decorations {
+            Decorations::Kas => Some(TitleBar::new(self.title.clone())),
+            _ => None,
+        };
+        self
+    }
+
+    /// Set window level and behaviour flags (chain style)
+    ///
+    /// For example, use [`WindowFlags::ALWAYS_ON_TOP`] for a tool palette or
+    /// `ALWAYS_ON_TOP | SKIP_TASKBAR | NO_FOCUS` for a notification pop-up.
+    /// This must be set before the window is added to the toolkit; to change
+    /// flags later use [`EventMgr::set_window_flags`].
+    ///
+    /// By default, no flags are set.
+    pub fn with_flags(mut self, flags: WindowFlags) -> Self {
+        self.flags = flags;
+        self
+    }
+
+    /// Centre the window on a monitor (chain style)
+    ///
+    /// The window is centred within the work area of monitor `index` (see
+    /// [`kas::shell::Displays`]; available monitors may be enumerated via
+    /// [`EventMgr::displays`]) when created, where supported by the platform.
+    /// Where the work area is not reported (see
+    /// [`kas::shell::Monitor::work_area`]), the window is centred on the whole
+    /// monitor.
+    /// This must be set before the window is added to the toolkit.
+    ///
+    /// By default, placement is left to the platform.
+    #[must_use]
+    pub fn centre_on_monitor(mut self, index: usize) -> Self {
+        self.monitor = Some(index);
+        self
+    }
+
+    /// Enable text zoom commands
+    ///
+    /// When enabled, [`Command::ZoomIn`], [`Command::ZoomOut`] and
+    /// [`Command::ZoomReset`] (by default bound to <kbd>Ctrl</kbd>+<kbd>=</kbd>,
+    /// <kbd>Ctrl</kbd>+<kbd>-</kbd> and <kbd>Ctrl</kbd>+<kbd>0</kbd>) adjust
+    /// the theme's font size when not used by another widget.
+    /// See [`EventState::enable_zoom`].
+    ///
+    /// Zoom applies to this window only.
+    ///
+    /// By default, zoom is disabled.
+    #[must_use]
+    pub fn with_zoom(mut self, zoom: bool) -> Self {
+        self.zoom = zoom;
+        self
+    }
+
+    /// Restore widget state once sized (chain style)
+    ///
+    /// The `state` (usually from [`kas::Window::capture_widget_state`] in a
+    /// previous session) is applied after the window is first sized.
+    #[must_use]
+    pub fn with_restored_state(mut self, state: WidgetState) -> Self {
+        self.restore = Some(state);
+        self
+    }
+
     /// Set a closure to be called on destruction, and return a future
     ///
     /// This is a convenience wrapper around [`Window::on_drop_boxed`].
@@ -198,6 +485,40 @@ impl<W: Widget> Window<W> {
     }
 }
 
+impl<W: Widget> Window<W> {
+    // Get resize directions for an edge at coord (or (0, 0) if not on an edge)
+    fn resize_edges(&self, coord: Coord) -> (i32, i32) {
+        let r = self.core.rect;
+        let b = self.resize_border;
+        let dir = |p: i32, pos: i32, size: i32| {
+            if p < pos + b {
+                -1
+            } else if p >= pos + size - b {
+                1
+            } else {
+                0
+            }
+        };
+        (
+            dir(coord.0, r.pos.0, r.size.0),
+            dir(coord.1, r.pos.1, r.size.1),
+        )
+    }
+}
+
+fn resize_cursor(edges: (i32, i32)) -> CursorIcon {
+    match edges {
+        (-1, -1) => CursorIcon::NwResize,
+        (1, -1) => CursorIcon::NeResize,
+        (-1, 1) => CursorIcon::SwResize,
+        (1, 1) => CursorIcon::SeResize,
+        (-1, 0) => CursorIcon::WResize,
+        (1, 0) => CursorIcon::EResize,
+        (0, -1) => CursorIcon::NResize,
+        _ => CursorIcon::SResize,
+    }
+}
+
 // This is like WidgetChildren::find, but returns a translated Rect.
 fn find_rect(widget: &dyn WidgetConfig, id: WidgetId) -> Option<Rect> {
     let wid = widget.id();
@@ -216,14 +537,26 @@ fn find_rect(widget: &dyn WidgetConfig, id: WidgetId) -> Option<Rect> {
 
 impl<W: Widget> Window<W> {
     fn resize_popup(&mut self, mgr: &mut SetRectMgr, index: usize) {
+        let rect = match self.popups[index].2 {
+            Some(rect) => rect,
+            None => self.place_popup(mgr, index, self.core.rect).0,
+        };
+
+        let widget = self.w.find_widget_mut(&self.popups[index].1.id).unwrap();
+        let mut cache = layout::SolveCache::find_constraints(widget, mgr.size_mgr());
+        cache.apply_rect(widget, mgr, rect, false);
+    }
+
+    // Find a rect for pop-up `index` within bounds `r`, and whether this
+    // achieves the pop-up's ideal size
+    fn place_popup(&mut self, mgr: &mut SetRectMgr, index: usize, r: Rect) -> (Rect, bool) {
         // Notation: p=point/coord, s=size, m=margin
-        // r=window/root rect, c=anchor rect
-        let r = self.core.rect;
-        let popup = &mut self.popups[index].1;
+        // r=bounding rect, c=anchor rect
+        let popup = &self.popups[index].1;
 
         let c = find_rect(self.w.as_widget(), popup.parent.clone()).unwrap();
         let widget = self.w.find_widget_mut(&popup.id).unwrap();
-        let mut cache = layout::SolveCache::find_constraints(widget, mgr.size_mgr());
+        let cache = layout::SolveCache::find_constraints(widget, mgr.size_mgr());
         let ideal = cache.ideal(false);
         let m = cache.margins();
 
@@ -262,6 +595,7 @@ impl<W: Widget> Window<W> {
             Rect::new(Coord(x, y), Size::new(w, h))
         };
 
-        cache.apply_rect(widget, mgr, rect, false);
+        let fits = rect.size.0 >= ideal.0 && rect.size.1 >= ideal.1;
+        (rect, fits)
     }
 }
@@ -0,0 +1,203 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! A multi-step dialog
+
+use crate::{Label, ProgressBar, Stack, StringLabel, TextButton};
+use kas::dir::Right;
+use kas::event::VirtualKeyCode;
+use kas::prelude::*;
+use std::rc::Rc;
+
+/// Message type of [`Wizard`]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum WizardMsg<M> {
+    /// A message from a page
+    Page(M),
+    /// The last page was completed via "Finish"
+    Finish,
+    /// The wizard was cancelled
+    Cancel,
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Nav {
+    Back,
+    Next,
+    Cancel,
+}
+
+type Validator<W> = Rc<dyn Fn(&mut EventMgr, &mut W) -> bool>;
+
+widget! {
+    /// A multi-step dialog
+    ///
+    /// A wizard shows a sequence of pages (using a [`Stack`]) one at a time,
+    /// together with a progress indicator and "Back", "Next" (or "Finish" on
+    /// the last page) and "Cancel" buttons.
+    ///
+    /// Advancing from a page may be blocked by a validation hook (see
+    /// [`Wizard::with_validator`]). Finishing and cancelling are reported via
+    /// [`WizardMsg`]; messages from pages are forwarded as [`WizardMsg::Page`].
+    #[autoimpl(Clone where W: Clone)]
+    #[autoimpl(Debug skip self.validators)]
+    #[handler(msg = WizardMsg<<W as Handler>::Msg>)]
+    #[widget{
+        layout = column: [
+            row: [self.step, self.progress],
+            self.stack,
+            row: [self.cancel, self.back, self.next],
+        ];
+    }]
+    pub struct Wizard<W: Widget> {
+        #[widget_core]
+        core: CoreData,
+        #[widget]
+        step: StringLabel,
+        #[widget]
+        progress: ProgressBar<Right>,
+        #[widget(map_msg = page_msg)]
+        stack: Stack<W>,
+        #[widget(flatmap_msg = navigate)]
+        cancel: TextButton<Nav>,
+        #[widget(flatmap_msg = navigate)]
+        back: TextButton<Nav>,
+        #[widget(flatmap_msg = navigate)]
+        next: TextButton<Nav>,
+        validators: Vec<Option<Validator<W>>>,
+    }
+
+    impl Self {
+        /// Construct with the given `pages`
+        ///
+        /// The first page is shown initially.
+        pub fn new(pages: Vec<W>) -> Self {
+            let mut wizard = Wizard {
+                core: Default::default(),
+                step: Label::new(String::new()),
+                progress: ProgressBar::new(),
+                stack: Stack::new(pages, 0),
+                cancel: TextButton::new_msg("&Cancel", Nav::Cancel)
+                    .with_keys(&[VirtualKeyCode::Escape]),
+                back: TextButton::new_msg("&Back", Nav::Back),
+                next: TextButton::new_msg("&Next", Nav::Next),
+                validators: vec![],
+            };
+            let _ = wizard.update_nav();
+            wizard
+        }
+
+        /// Set a validation hook for page `index` (chain style)
+        ///
+        /// When "Next" or "Finish" is pressed on this page, `f` is called with
+        /// the page; if it returns `false`, the wizard does not advance. The
+        /// hook may update the page, e.g. to indicate an error.
+        #[must_use]
+        pub fn with_validator<F>(mut self, index: usize, f: F) -> Self
+        where
+            F: Fn(&mut EventMgr, &mut W) -> bool + 'static,
+        {
+            if self.validators.len() <= index {
+                self.validators.resize(index + 1, None);
+            }
+            self.validators[index] = Some(Rc::new(f));
+            self
+        }
+
+        /// Get the index of the current page
+        #[inline]
+        pub fn page_index(&self) -> usize {
+            self.stack.active_index()
+        }
+
+        /// Returns the number of pages
+        #[inline]
+        pub fn len(&self) -> usize {
+            self.stack.len()
+        }
+
+        /// True if there are no pages
+        #[inline]
+        pub fn is_empty(&self) -> bool {
+            self.stack.is_empty()
+        }
+
+        /// Access a page
+        ///
+        /// Panics if `index` is out of bounds.
+        #[inline]
+        pub fn page(&self, index: usize) -> &W {
+            &self.stack[index]
+        }
+
+        /// Access a page mutably
+        ///
+        /// Panics if `index` is out of bounds.
+        #[inline]
+        pub fn page_mut(&mut self, index: usize) -> &mut W {
+            &mut self.stack[index]
+        }
+
+        /// Show page `index`
+        ///
+        /// Validation hooks are not called. It is not required that
+        /// `index < self.len()`; if not, no page is shown.
+        pub fn set_page_index(&mut self, index: usize) -> TkAction {
+            self.stack.set_active(index) | self.update_nav()
+        }
+
+        fn update_nav(&mut self) -> TkAction {
+            let (index, len) = (self.stack.active_index(), self.stack.len());
+            let last = index + 1 >= len;
+            let mut action = self.back.set_disabled(index == 0);
+            action |= self.next.set_accel(if last { "&Finish" } else { "&Next" });
+            let step = if len > 0 { index.min(len - 1) + 1 } else { 0 };
+            action |= self.progress.set_value(f32::conv(step) / f32::conv(len.max(1)));
+            action | self.step.set_string(format!("Step {} of {}", step, len))
+        }
+
+        fn validate(&mut self, mgr: &mut EventMgr, index: usize) -> bool {
+            match self.validators.get(index).cloned().flatten() {
+                Some(f) if index < self.stack.len() => f(mgr, &mut self.stack[index]),
+                _ => true,
+            }
+        }
+
+        fn page_msg(
+            &mut self,
+            _: &mut EventMgr,
+            msg: <W as Handler>::Msg,
+        ) -> WizardMsg<<W as Handler>::Msg> {
+            WizardMsg::Page(msg)
+        }
+
+        fn navigate(
+            &mut self,
+            mgr: &mut EventMgr,
+            nav: Nav,
+        ) -> Response<WizardMsg<<W as Handler>::Msg>> {
+            let index = self.stack.active_index();
+            match nav {
+                Nav::Back => {
+                    if index > 0 {
+                        *mgr |= self.set_page_index(index - 1);
+                    }
+                    Response::Used
+                }
+                Nav::Next => {
+                    if !self.validate(mgr, index) {
+                        Response::Used
+                    } else if index + 1 < self.stack.len() {
+                        *mgr |= self.set_page_index(index + 1);
+                        Response::Used
+                    } else {
+                        Response::Msg(WizardMsg::Finish)
+                    }
+                }
+                Nav::Cancel => Response::Msg(WizardMsg::Cancel),
+            }
+        }
+    }
+}
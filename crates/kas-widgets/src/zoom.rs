@@ -0,0 +1,328 @@
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License in the LICENSE-APACHE file or at:
+//     https://www.apache.org/licenses/LICENSE-2.0
+
+//! Zoomable view
+
+use kas::draw::Affine;
+use kas::event::{self, GrabMode, ScrollDelta};
+use kas::geom::Vec2;
+use kas::prelude::*;
+use kas::theme::TextClass;
+use std::fmt::Debug;
+
+widget! {
+    /// A zoomable and pannable view
+    ///
+    /// Content is drawn scaled (via a transformed draw pass) and offset. The
+    /// view is zoomed by <kbd>Ctrl</kbd>+mouse wheel (about the mouse cursor)
+    /// or by a pinch gesture; it is panned by the mouse wheel or by dragging.
+    /// The zoom direction is unaffected by "natural" scrolling (see
+    /// [`kas::event::config::WindowConfig::scroll_natural`]).
+    /// Events are sent to the inner widget with coordinates mapped to its own
+    /// (unscaled) coordinate space.
+    ///
+    /// The inner widget is sized to at least the size of the view. It is
+    /// usually a custom widget drawing diagram or map content.
+    ///
+    /// Limitation: pop-ups opened by the inner widget are positioned as if
+    /// the content were not zoomed.
+    #[autoimpl(Deref, DerefMut on self.inner)]
+    #[autoimpl(class_traits where W: trait on self.inner)]
+    #[derive(Clone, Debug)]
+    #[handler(msg = <W as event::Handler>::Msg)]
+    pub struct ZoomView<W: Widget> {
+        #[widget_core]
+        core: CoreData,
+        min_child_size: Size,
+        scroll_rate: f32,
+        zoom: f32,
+        limits: (f32, f32),
+        /// Position of content relative to `core.rect.pos`, in scaled pixels
+        offset: Vec2,
+        #[widget]
+        inner: W,
+    }
+
+    impl Self {
+        /// Construct a new zoomable view around an inner widget
+        ///
+        /// Zoom is initially 1 and is limited to the range `0.125..=8.0`.
+        #[inline]
+        pub fn new(inner: W) -> Self {
+            ZoomView {
+                core: Default::default(),
+                min_child_size: Size::ZERO,
+                scroll_rate: 30.0,
+                zoom: 1.0,
+                limits: (0.125, 8.0),
+                offset: Vec2::ZERO,
+                inner,
+            }
+        }
+
+        /// Set the zoom limits (chain style)
+        ///
+        /// The initial zoom is clamped to these limits.
+        #[inline]
+        #[must_use]
+        pub fn with_limits(mut self, min: f32, max: f32) -> Self {
+            assert!(0.0 < min && min <= max);
+            self.limits = (min, max);
+            self.zoom = self.zoom.clamp(min, max);
+            self
+        }
+
+        /// Access inner widget directly
+        #[inline]
+        pub fn inner(&self) -> &W {
+            &self.inner
+        }
+
+        /// Access inner widget directly
+        #[inline]
+        pub fn inner_mut(&mut self) -> &mut W {
+            &mut self.inner
+        }
+
+        /// Get the current zoom factor
+        #[inline]
+        pub fn zoom(&self) -> f32 {
+            self.zoom
+        }
+
+        /// Set the zoom factor
+        ///
+        /// The view is zoomed about its centre. The factor is clamped to the
+        /// zoom limits.
+        pub fn set_zoom(&mut self, zoom: f32) -> TkAction {
+            let center = Vec2::from(self.core.rect.size) * 0.5;
+            self.zoom_about(zoom / self.zoom, center)
+        }
+
+        /// Get the transform from content coordinates to view coordinates
+        pub fn transform(&self) -> Affine {
+            let pos = Vec2::from(self.core.rect.pos);
+            Affine {
+                a: Vec2(self.zoom, 0.0),
+                b: Vec2(0.0, self.zoom),
+                t: pos * (1.0 - self.zoom) + self.offset,
+            }
+        }
+
+        /// Map a view coordinate to content coordinates
+        fn to_content(&self, coord: Coord) -> Coord {
+            let pos = Vec2::from(self.core.rect.pos);
+            let p = (Vec2::from(coord) - pos - self.offset) / self.zoom;
+            (pos + p).into()
+        }
+
+        /// Zoom by `factor` about `center` (relative to `core.rect.pos`)
+        fn zoom_about(&mut self, factor: f32, center: Vec2) -> TkAction {
+            let zoom = (self.zoom * factor).clamp(self.limits.0, self.limits.1);
+            let factor = zoom / self.zoom;
+            self.zoom = zoom;
+            let old_offset = self.offset;
+            self.offset = center + (self.offset - center) * factor;
+            self.clamp_offset();
+            if factor != 1.0 || self.offset != old_offset {
+                TkAction::REDRAW
+            } else {
+                TkAction::empty()
+            }
+        }
+
+        /// Move content by `delta`, returning the unused part
+        fn pan_by(&mut self, mgr: &mut EventMgr, delta: Vec2) -> Vec2 {
+            let old_offset = self.offset;
+            self.offset += delta;
+            self.clamp_offset();
+            if self.offset != old_offset {
+                mgr.redraw(self.id());
+            }
+            delta - (self.offset - old_offset)
+        }
+
+        /// Keep content within the view (or the view within content)
+        fn clamp_offset(&mut self) {
+            let view = Vec2::from(self.core.rect.size);
+            let content = Vec2::from(self.inner.rect().size) * self.zoom;
+            let d = view - content;
+            self.offset = self
+                .offset
+                .max(d.min(Vec2::ZERO))
+                .min(d.max(Vec2::ZERO));
+        }
+
+        /// Map coordinates of an event sent to the inner widget
+        fn content_event(&self, mut event: Event) -> Event {
+            match &mut event {
+                Event::PressStart { coord, .. } => {
+                    *coord = self.to_content(*coord);
+                }
+                Event::PressMove { coord, delta, .. } => {
+                    *coord = self.to_content(*coord);
+                    *delta = (Vec2::from(*delta) / self.zoom).into();
+                }
+                Event::PressEnd { coord, .. } => {
+                    *coord = self.to_content(*coord);
+                }
+                _ => {}
+            };
+            event
+        }
+    }
+
+    impl WidgetConfig for Self {
+        fn configure(&mut self, mgr: &mut SetRectMgr) {
+            mgr.register_nav_fallback(self.id());
+        }
+    }
+
+    impl SaveState for Self {
+        fn save_state(&self) -> Option<StateValue> {
+            let values = [self.zoom, self.offset.0, self.offset.1];
+            let list = values.iter().map(|x| StateValue::Float((*x).into()));
+            Some(StateValue::List(list.collect()))
+        }
+
+        fn restore_state(&mut self, state: &StateValue) -> TkAction {
+            let list = state.as_list().unwrap_or(&[]);
+            let values: Vec<f32> = list
+                .iter()
+                .filter_map(|v| v.as_float().map(|x| x as f32))
+                .collect();
+            if let [zoom, x, y] = values[..] {
+                self.zoom = zoom.clamp(self.limits.0, self.limits.1);
+                self.offset = Vec2(x, y);
+                self.clamp_offset();
+                TkAction::REDRAW
+            } else {
+                TkAction::empty()
+            }
+        }
+    }
+
+    impl Layout for Self {
+        fn size_rules(&mut self, size_mgr: SizeMgr, axis: AxisInfo) -> SizeRules {
+            let mut rules = self.inner.size_rules(size_mgr.re(), axis);
+            self.min_child_size.set_component(axis, rules.min_size());
+            let line_height = size_mgr.line_height(TextClass::Label);
+            self.scroll_rate = 3.0 * f32::conv(line_height);
+            rules.reduce_min_to(line_height);
+            rules
+        }
+
+        fn set_rect(&mut self, mgr: &mut SetRectMgr, rect: Rect, align: AlignHints) {
+            self.core.rect = rect;
+            let child_size = rect.size.max(self.min_child_size);
+            let child_rect = Rect::new(rect.pos, child_size);
+            self.inner.set_rect(mgr, child_rect, align);
+            self.clamp_offset();
+        }
+
+        fn find_id(&mut self, coord: Coord) -> Option<WidgetId> {
+            if !self.rect().contains(coord) {
+                return None;
+            }
+            let coord = self.to_content(coord);
+            self.inner.find_id(coord).or(Some(self.id()))
+        }
+
+        fn draw(&mut self, mut draw: DrawMgr) {
+            let mut draw = draw.with_core(self.core_data());
+            let transform = self.transform();
+            draw.with_transform(self.core.rect, Offset::ZERO, transform, |mut draw| {
+                self.inner.draw(draw.re())
+            });
+        }
+    }
+
+    impl event::SendEvent for Self {
+        fn send(&mut self, mgr: &mut EventMgr, id: WidgetId, event: Event) -> Response<Self::Msg> {
+            if self.is_disabled() {
+                return Response::Unused;
+            }
+
+            if self.inner.id().is_ancestor_of(&id) {
+                let child_event = self.content_event(event.clone());
+                match self.inner.send(mgr, id, child_event) {
+                    Response::Unused => (),
+                    Response::Pan(delta) => {
+                        return match self.pan_by(mgr, delta.into()) {
+                            delta if delta == Vec2::ZERO => Response::Scrolled,
+                            delta => Response::Pan(delta.into()),
+                        };
+                    }
+                    Response::Focus(rect) => {
+                        // Map to view coordinates, then ensure rect is visible
+                        let quad = self.transform().transform_bounds(rect.into());
+                        let rect = Rect::new(quad.a.into(), quad.size().into());
+                        let view = self.core.rect;
+                        let a = view.pos - rect.pos;
+                        let b = (view.pos + view.size) - (rect.pos + rect.size);
+                        let d = a.max(b.min(Offset::ZERO));
+                        let old_offset = self.offset;
+                        self.pan_by(mgr, d.into());
+                        let moved = Offset::from(self.offset - old_offset);
+                        return Response::Focus(rect + moved);
+                    }
+                    r => return r,
+                }
+            } else {
+                debug_assert!(self.eq_id(id), "SendEvent::send: bad WidgetId");
+            };
+
+            match event {
+                Event::Scroll(delta) if mgr.modifiers().ctrl() => {
+                    // Zoom direction follows the physical wheel direction:
+                    // undo the inversion applied by "natural" scrolling
+                    let sign = match mgr.config().scroll_natural() {
+                        true => -1.0,
+                        false => 1.0,
+                    };
+                    let factor = match delta {
+                        ScrollDelta::LineDelta(_, y) => 2f32.powf(sign * 0.5 * y),
+                        ScrollDelta::PixelDelta(d) => 2f32.powf(sign * 0.01 * f32::conv(d.1)),
+                    };
+                    let rect = self.core.rect;
+                    let coord = mgr.mouse_coord();
+                    let center = if rect.contains(coord) {
+                        Vec2::from(coord - rect.pos)
+                    } else {
+                        Vec2::from(rect.size) * 0.5
+                    };
+                    *mgr |= self.zoom_about(factor, center);
+                    Response::Scrolled
+                }
+                Event::Scroll(delta) => {
+                    let delta = Vec2::from(delta.as_offset(self.scroll_rate));
+                    match self.pan_by(mgr, delta) {
+                        delta if delta == Vec2::ZERO => Response::Scrolled,
+                        delta => Response::Pan(delta.into()),
+                    }
+                }
+                Event::PressStart { source, coord, .. } => {
+                    let icon = Some(event::CursorIcon::Grabbing);
+                    mgr.grab_press(self.id(), source, coord, GrabMode::PanScale, icon);
+                    Response::Used
+                }
+                Event::Pan { alpha, delta } => {
+                    // View point p moves to alpha * p + delta (without rotation)
+                    let pos = Vec2::from(self.core.rect.pos);
+                    let zoom = self.zoom * alpha.0 as f32;
+                    let zoom = zoom.clamp(self.limits.0, self.limits.1);
+                    let factor = zoom / self.zoom;
+                    self.zoom = zoom;
+                    let delta = Vec2::from(delta) + pos * (factor - 1.0);
+                    self.offset = self.offset * factor + delta;
+                    self.clamp_offset();
+                    mgr.redraw(self.id());
+                    Response::Used
+                }
+                _ => Response::Unused,
+            }
+        }
+    }
+}
@@ -16,7 +16,7 @@ use log::info;
 use std::f32::consts::PI;
 use std::time::Duration;
 
-use kas::draw::{color, Draw, DrawIface, DrawRounded, PassType};
+use kas::draw::{color, Affine, Draw, DrawIface, DrawRounded, PassType};
 use kas::geom::{Offset, Quad, Vec2};
 use kas::shell::draw::DrawPipe;
 use kas::text::util::set_text_and_prepare;
@@ -101,7 +101,7 @@ widget! {
             draw.text(self.time_pos.into(), self.time.as_ref(), col_time);
 
             // We use a new pass to control the draw order (force in front).
-            let mut draw = draw.new_pass(rect, Offset::ZERO, PassType::Clip);
+            let mut draw = draw.new_pass(rect, Offset::ZERO, Affine::IDENTITY, PassType::Clip);
             let mut line_seg = |t: f32, r1: f32, r2: f32, w, col| {
                 let v = Vec2(t.sin(), -t.cos());
                 draw.rounded_line(centre + v * r1, centre + v * r2, w, col);
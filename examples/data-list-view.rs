@@ -15,7 +15,7 @@ use kas::prelude::*;
 use kas::updatable::*;
 use kas::widgets::view::{Driver, ListView};
 use kas::widgets::*;
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::collections::HashMap;
 
 #[derive(Clone, Debug, VoidMsg)]
@@ -174,7 +174,9 @@ widget! {
 
 #[derive(Debug)]
 struct MyDriver {
-    radio_group: RadioBoxGroup,
+    radio_group: RadioBoxGroup<usize>,
+    // Each widget uses a unique value within radio_group
+    next: Cell<usize>,
 }
 impl Driver<(usize, bool, String)> for MyDriver {
     type Msg = EntryMsg;
@@ -185,8 +187,12 @@ impl Driver<(usize, bool, String)> for MyDriver {
         ListEntry {
             core: Default::default(),
             label: Label::new(String::default()),
-            radio: RadioBox::new("display this entry", self.radio_group.clone())
-                .on_select(move |_| Some(EntryMsg::Select)),
+            radio: RadioBox::new(
+                "display this entry",
+                self.radio_group.clone(),
+                self.next.replace(self.next.get() + 1),
+            )
+            .on_select(move |_| Some(EntryMsg::Select)),
             entry: EditBox::new(String::default()).with_guard(ListEntryGuard),
         }
     }
@@ -199,6 +205,9 @@ impl Driver<(usize, bool, String)> for MyDriver {
     fn get(&self, _widget: &Self::Widget) -> Option<(usize, bool, String)> {
         None // unused
     }
+    fn to_clipboard_text(&self, data: &(usize, bool, String)) -> Option<String> {
+        Some(data.2.clone())
+    }
 }
 
 fn main() -> kas::shell::Result<()> {
@@ -239,6 +248,7 @@ fn main() -> kas::shell::Result<()> {
 
     let driver = MyDriver {
         radio_group: Default::default(),
+        next: Default::default(),
     };
     let data = MyData::new(3);
     type MyList = ListView<Direction, MyData, MyDriver>;
@@ -11,6 +11,7 @@ use kas::prelude::*;
 use kas::updatable::filter::ContainsCaseInsensitive;
 use kas::widgets::view::{driver, FilterListView, SelectionMode, SingleView};
 use kas::widgets::{EditBox, Label, RadioBox, RadioBoxGroup, ScrollBars, Window};
+use std::time::Duration;
 
 const MONTHS: &[&str] = &[
     "January",
@@ -66,7 +67,9 @@ fn main() -> kas::shell::Result<()> {
                         ContainsCaseInsensitive,
                         driver::DefaultNav,
                     >> =
-                    ScrollBars::new(FilterListView::new(data, filter)),
+                    ScrollBars::new(FilterListView::new(data, filter)
+                        .with_debounce(Duration::from_millis(150))
+                        .with_incremental(10_000)),
             }
             impl Self {
                 fn set_selection_mode(&mut self, mgr: &mut EventMgr, mode: SelectionMode) {
@@ -22,6 +22,7 @@ enum Item {
     LightTheme,
     DarkTheme,
     Check(bool),
+    Switch(bool),
     Combo(i32),
     Radio(u32),
     Edit(String),
@@ -226,14 +227,15 @@ fn main() -> kas::shell::Result<()> {
                 2, 0: self.tbl; 2, 1: self.tb;
                 3, 0: self.bil; 3, 1: self.bi;
                 4, 0: self.cbl; 4, 1: self.cb;
-                5, 0: self.rbl; 5, 1: self.rb;
-                6, 0: self.rb2l; 6, 1: self.rb2;
-                7, 0: self.cbbl; 7, 1: self.cbb;
-                8, 0: self.sdl; 8, 1: self.sd;
-                9, 0: self.scl; 9, 1: self.sc;
-                10, 0: self.pgl; 10, 1: self.pg;
-                11, 0: self.svl; 11, 1: align(center): self.sv;
-                12, 0: self.pul; 12, 1: self.pu;
+                5, 0: self.swl; 5, 1: self.sw;
+                6, 0: self.rbl; 6, 1: self.rb;
+                7, 0: self.rb2l; 7, 1: self.rb2;
+                8, 0: self.cbbl; 8, 1: self.cbb;
+                9, 0: self.sdl; 9, 1: self.sd;
+                10, 0: self.scl; 10, 1: self.sc;
+                11, 0: self.pgl; 11, 1: self.pg;
+                12, 0: self.svl; 12, 1: align(center): self.sv;
+                13, 0: self.pul; 13, 1: self.pu;
             };
         }]
         #[handler(msg = Item)]
@@ -257,11 +259,13 @@ fn main() -> kas::shell::Result<()> {
             #[widget] cb = CheckBox::new("&Check me")
                 .with_state(true)
                 .on_toggle(|_, check| Some(Item::Check(check))),
+            #[widget] swl = Label::new("Switch"),
+            #[widget] sw = Switch::new_on(|_, on| Some(Item::Switch(on))),
             #[widget] rbl = Label::new("RadioBox"),
-            #[widget] rb = RadioBox::new("radio box &1", radio.clone())
+            #[widget] rb = RadioBox::new("radio box &1", radio.clone(), 1)
                 .on_select(|_| Some(Item::Radio(1))),
             #[widget] rb2l = Label::new("RadioBox"),
-            #[widget] rb2 = RadioBox::new("radio box &2", radio)
+            #[widget] rb2 = RadioBox::new("radio box &2", radio, 2)
                 .with_state(true)
                 .on_select(|_| Some(Item::Radio(2))),
             #[widget] cbbl = Label::new("ComboBox"),
@@ -284,10 +288,10 @@ fn main() -> kas::shell::Result<()> {
             fn handle_slider(&mut self, _: &mut EventMgr, msg: i32) -> Item {
                 Item::Slider(msg)
             }
-            fn handle_scroll(&mut self, mgr: &mut EventMgr, msg: i32) -> Item {
-                let ratio = msg as f32 / self.sc.max_value() as f32;
+            fn handle_scroll(&mut self, mgr: &mut EventMgr, msg: ScrollBarMsg) -> Item {
+                let ratio = msg.value() as f32 / self.sc.max_value() as f32;
                 *mgr |= self.pg.set_value(ratio);
-                Item::Scroll(msg)
+                Item::Scroll(msg.value())
             }
         }
     };
@@ -349,6 +353,7 @@ fn main() -> kas::shell::Result<()> {
                         Item::LightTheme => mgr.adjust_theme(|theme| theme.set_scheme("light")),
                         Item::DarkTheme => mgr.adjust_theme(|theme| theme.set_scheme("dark")),
                         Item::Check(b) => println!("CheckBox: {}", b),
+                        Item::Switch(b) => println!("Switch: {}", b),
                         Item::Combo(c) => println!("ComboBox: {}", c),
                         Item::Radio(id) => println!("RadioBox: {}", id),
                         Item::Edit(s) => println!("Edited: {}", s),
@@ -358,7 +363,8 @@ fn main() -> kas::shell::Result<()> {
                 }
             }
         },
-    );
+    )
+    .with_zoom(true);
     if let Err(err) = window.load_icon_from_path("res/gallery.png") {
         println!("Failed to load window icon: {}", err);
     }
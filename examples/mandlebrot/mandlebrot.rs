@@ -17,7 +17,8 @@ use kas::geom::{DVec2, Vec2, Vec3};
 use kas::prelude::*;
 use kas::shell::draw::{CustomPipe, CustomPipeBuilder, CustomWindow, DrawCustom, DrawPipe};
 use kas::shell::Options;
-use kas::widgets::adapter::ReserveP;
+use kas::theme::TextClass;
+use kas::widgets::adapter::{Reserve, ReserveB};
 use kas::widgets::{Label, Slider, Window};
 
 #[cfg(not(feature = "shader64"))]
@@ -430,7 +431,7 @@ widget! {
     struct MandlebrotWindow {
         #[widget_core] core: CoreData,
         #[widget] label: Label<String>,
-        #[widget] iters: ReserveP<Label<String>>,
+        #[widget] iters: ReserveB<Label<String>>,
         #[widget(use_msg = iter)] slider: Slider<i32, kas::dir::Up>,
         // extra col span allows use of Label's margin
         #[widget(use_msg = mbrot)] mbrot: Mandlebrot,
@@ -443,9 +444,7 @@ widget! {
             let w = MandlebrotWindow {
                 core: Default::default(),
                 label: Label::new(mbrot.loc()),
-                iters: ReserveP::new(Label::from("64"), |size_mgr, axis| {
-                    Label::new("000").size_rules(size_mgr, axis)
-                }),
+                iters: Reserve::new_str(Label::from("64"), "000", TextClass::Label),
                 slider,
                 mbrot,
             };
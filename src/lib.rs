@@ -11,7 +11,11 @@
 //! -   high-level themable and mid-level [`draw`] APIs
 //! -   [`event`] handling code
 //! -   [`geom`]-etry types and widget [`layout`] solvers
-//! -   the standard [`widgets`] library
+//! -   the standard [`widgets`] library (feature `widgets`, enabled by default)
+//!
+//! Widget libraries may prefer to depend on `kas` without the `widgets`
+//! feature (or on `kas-core` directly); the [`prelude`] then includes only
+//! `kas-core` items.
 //!
 //! See also these external crates:
 //!
@@ -45,6 +49,8 @@ pub use kas_core::{Boxed, Layout, Window};
 pub use kas_core::{CoreData, Future, Popup, TkAction, WidgetId, WindowId};
 pub use kas_core::{Widget, WidgetChildren, WidgetConfig, WidgetCore};
 
+#[cfg(feature = "widgets")]
+#[cfg_attr(doc_cfg, doc(cfg(feature = "widgets")))]
 pub use kas_widgets as widgets;
 
 #[cfg(any(feature = "canvas", feature = "svg"))]
@@ -60,8 +66,15 @@ pub mod theme {
     pub use kas_theme::*;
 }
 
-#[cfg(feature = "wgpu")]
-pub use kas_wgpu as shell;
+/// Shell
+///
+/// This module merges [`kas_core::shell`] and (with the `wgpu` feature) [`kas_wgpu`].
+pub mod shell {
+    pub use kas_core::shell::*;
+
+    #[cfg(feature = "wgpu")]
+    pub use kas_wgpu::*;
+}
 
 #[cfg(feature = "dynamic")]
 #[allow(unused_imports)]
@@ -176,9 +176,64 @@
 //!     keyboard navigation (see [`WidgetConfig::key_nav`])
 //!  -  `hover_highlight = false`: a boolean, describing whether to request a
 //!     redraw when mouse-hover status changes (see [`WidgetConfig::hover_highlight`])
+//!  -  `hover_events = false`: a boolean, describing whether to send
+//!     [`Event::MouseEnter`] and [`Event::MouseLeave`] (see [`WidgetConfig::hover_events`])
 //!  -   `cursor_icon = kas::event::CursorIcon::Default`: the cursor icon to use
 //!     when the mouse hovers over this widget (see [`WidgetConfig::cursor_icon`])
 //!
+//! ### SaveState
+//!
+//! The [`SaveState`] trait is derived as a no-op (or forwarding to the inner
+//! widget when using `derive = self.inner;`) unless implemented manually by
+//! the widget (`impl SaveState for Self { ... }`).
+//!
+//! ### Builder
+//!
+//! With the `builder = true;` property (e.g. `#[widget{ builder = true; }]`),
+//! the macro additionally generates:
+//!
+//! -   an implementation of `Default`, where each field is initialised from its
+//!     `#[widget(default = EXPR)]` attribute if any, otherwise via
+//!     `Default::default()`
+//! -   a method `with_FIELD(self, value) -> Self` for each `pub` field which is
+//!     neither the `#[widget_core]` field nor a child widget
+//!
+//! The attribute `#[widget(default = EXPR)]` alone does not make a field a
+//! child widget; `default` may be combined with other arguments on children
+//! (e.g. `#[widget(default = Label::new("x"), use_msg = f)]`). Without
+//! `builder = true;`, use of `default` is an error.
+//!
+//! This allows construction like `MyWidget::default().with_size(10)`.
+//! Note that the struct should not also derive `Default`.
+//!
+//! ```
+//! use kas::prelude::*;
+//! use kas::widgets::Label;
+//!
+//! widget! {
+//!     #[derive(Clone, Debug)]
+//!     #[widget{
+//!         layout = single;
+//!         builder = true;
+//!     }]
+//!     pub struct Note {
+//!         #[widget_core]
+//!         core: CoreData,
+//!         #[widget]
+//!         label: Label<String>,
+//!         #[widget(default = 3)]
+//!         pub priority: u32,
+//!         pub author: String,
+//!     }
+//! }
+//!
+//! let note = Note::default().with_author("me".to_string());
+//! assert_eq!(note.priority, 3);
+//! assert_eq!(note.author, "me");
+//! let note = note.with_priority(1);
+//! assert_eq!(note.priority, 1);
+//! ```
+//!
 //! ### Handler and SendEvent
 //!
 //! The [`Handler`] and [`SendEvent`] traits are derived, unless opted out.
@@ -269,6 +324,58 @@
 //!
 //! -   `#[widget(update = f)]` where `f` has signature `fn f(&mut self, mgr: &mut EventMgr)`
 //!
+//! **Handling type-erased messages**
+//!
+//! Messages may also be pushed onto a stack with
+//! [`EventMgr::push_msg`](crate::event::EventMgr::push_msg), avoiding the
+//! need to thread a message type through each intermediate widget. Such
+//! messages may be handled by any ancestor:
+//!
+//! -   `#[widget(any_msg = f)]` where `f` has signature `fn f(&mut self, mgr: &mut EventMgr)`
+//!     is called after the child handles an event if the stack is not empty;
+//!     `f` should use [`EventMgr::try_pop_msg`](crate::event::EventMgr::try_pop_msg)
+//!     to take messages of the expected type(s), leaving others for further
+//!     ancestors.
+//!
+//! For example:
+//! ```
+//! # use kas::prelude::*;
+//! # use kas::widgets::TextButton;
+//! #[derive(Clone, Debug)]
+//! struct Increment;
+//!
+//! let counter = make_widget! {
+//!     #[widget{
+//!         layout = column: *;
+//!     }]
+//!     #[handler(msg = VoidMsg)]
+//!     struct {
+//!         #[widget(any_msg = handle_msgs)] _ = make_widget! {
+//!             #[widget{
+//!                 layout = row: *;
+//!             }]
+//!             #[handler(msg = VoidMsg)]
+//!             struct {
+//!                 #[widget(use_msg = push)] _ = TextButton::new_msg("+", ()),
+//!             }
+//!             impl Self {
+//!                 fn push(&mut self, mgr: &mut EventMgr, _: ()) {
+//!                     mgr.push_msg(Increment);
+//!                 }
+//!             }
+//!         },
+//!         count: u32 = 0,
+//!     }
+//!     impl Self {
+//!         fn handle_msgs(&mut self, mgr: &mut EventMgr) {
+//!             if let Some(Increment) = mgr.try_pop_msg() {
+//!                 self.count += 1;
+//!             }
+//!         }
+//!     }
+//! };
+//! ```
+//!
 //! ### Deriving `Widget` from a field
 //!
 //! In some cases it is desirable to create a "thin wrapper" around a widget
@@ -457,9 +564,9 @@
 // Imported for doc-links
 #[allow(unused)]
 use crate::{
-    event::{Handler, Response, SendEvent},
+    event::{Event, Handler, Response, SendEvent},
     layout::AlignHints,
-    CoreData, Layout, Widget, WidgetChildren, WidgetConfig, WidgetCore, WidgetId,
+    CoreData, Layout, SaveState, Widget, WidgetChildren, WidgetConfig, WidgetCore, WidgetId,
 };
 
 pub use kas_core::macros::*;
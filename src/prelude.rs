@@ -11,9 +11,11 @@
 //! ```
 //!
 //! This prelude may be more useful when implementing widgets than when simply
-//! using widgets in a GUI.
+//! using widgets in a GUI. It re-exports `kas_core::prelude`, plus items from
+//! the standard widget library when the `widgets` feature is enabled.
 
 #[doc(no_inline)]
 pub use kas_core::prelude::*;
+#[cfg(feature = "widgets")]
 #[doc(no_inline)]
 pub use kas_widgets::adapter::WidgetExt;